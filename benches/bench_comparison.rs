@@ -0,0 +1,56 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::Rng;
+use stats_ci::*;
+
+fn bench_comparison_parallel(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let size = 10_000_000;
+    let data_a = (0..size).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+    let data_b = (0..size).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+    let pairs = data_a
+        .iter()
+        .copied()
+        .zip(data_b.iter().copied())
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("comparison (10M pairs): sequential vs. parallel");
+    group.sample_size(10);
+
+    group.bench_function("Paired::from_pairs (sequential)", |b| {
+        b.iter(|| comparison::Paired::from_pairs(pairs.iter().copied()))
+    });
+    group.bench_function("Unpaired::from_iter (sequential)", |b| {
+        b.iter(|| comparison::Unpaired::from_iter(&data_a, &data_b))
+    });
+
+    // scaling with the number of threads
+    let max_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let threads = [1, 2, 4, 8, 16]
+        .into_iter()
+        .filter(|&n| n <= max_threads)
+        .collect::<Vec<_>>();
+    for &num_threads in &threads {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("Paired::from_pairs_parallel (threads)", num_threads),
+            &pairs,
+            |b, pairs| b.iter(|| pool.install(|| comparison::Paired::from_pairs_parallel(pairs))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Unpaired::from_iters_parallel (threads)", num_threads),
+            &(&data_a, &data_b),
+            |b, (data_a, data_b)| {
+                b.iter(|| {
+                    pool.install(|| comparison::Unpaired::from_iters_parallel(data_a, data_b))
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_comparison_parallel);
+criterion_main!(benches);
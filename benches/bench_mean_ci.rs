@@ -1,4 +1,7 @@
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+// sizes are kept as lists so that more can be added when needed
+#![allow(clippy::single_element_loop)]
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use rand::Rng;
 use stats_ci::*;
 
@@ -49,6 +52,18 @@ fn bench_mean_category(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::new("Geometric", size), &data, |b, data| {
             b.iter(|| mean::Geometric::ci(confidence, data))
         });
+
+        group.bench_with_input(
+            BenchmarkId::new("Harmonic (from_slice)", size),
+            &data,
+            |b, data| b.iter(|| mean::Harmonic::from_slice(data)?.ci_mean(confidence)),
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("Geometric (from_slice)", size),
+            &data,
+            |b, data| b.iter(|| mean::Geometric::from_slice(data)?.ci_mean(confidence)),
+        );
     }
     group.finish();
 }
@@ -83,7 +98,7 @@ fn bench_mean_rayon(c: &mut Criterion) {
                             }
                             stats
                         })
-                        .reduce(|| mean::Arithmetic::new(), |s1, s2| s1 + s2);
+                        .reduce(mean::Arithmetic::new, |s1, s2| s1 + s2);
 
                     stats.ci_mean(confidence)
                 })
@@ -99,7 +114,7 @@ fn bench_mean_rayon(c: &mut Criterion) {
                         .clone()
                         .par_iter()
                         .map(|&x| mean::Arithmetic::from_iter(&[x]).unwrap())
-                        .reduce(|| mean::Arithmetic::new(), |s1, s2| s1 + s2);
+                        .reduce(mean::Arithmetic::new, |s1, s2| s1 + s2);
 
                     stats.ci_mean(confidence)
                 })
@@ -109,10 +124,84 @@ fn bench_mean_rayon(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_mean_critical(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let confidence = Confidence::new_two_sided(0.95);
+
+    let mut group = c.benchmark_group("mean::Arithmetic (ci_mean vs. ci_mean_with_critical)");
+    group.sample_size(10);
+
+    for size in [10] {
+        // 1'000 samples of the given size, each used for 1'000 intervals
+        let samples = (0..1_000)
+            .map(|_| {
+                let data = (0..size).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+                mean::Arithmetic::from_iter(&data).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        group.bench_with_input(BenchmarkId::new("ci_mean", size), &samples, |b, samples| {
+            b.iter(|| {
+                for _ in 0..1_000 {
+                    for stats in samples {
+                        black_box(stats.ci_mean(confidence)).ok();
+                    }
+                }
+            })
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("ci_mean_with_critical", size),
+            &samples,
+            |b, samples| {
+                b.iter(|| {
+                    let (critical, _) = stats::critical_value(confidence, (size - 1) as f64)?;
+                    for _ in 0..1_000 {
+                        for stats in samples {
+                            black_box(stats.ci_mean_with_critical(confidence, critical)).ok();
+                        }
+                    }
+                    Ok::<(), error::CIError>(())
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_mean_chunked(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+
+    let mut group = c.benchmark_group("mean::Arithmetic (extend vs. append_chunk)");
+    group.sample_size(10);
+
+    for size in [100_000_000] {
+        let data = (0..size).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+        group.bench_with_input(BenchmarkId::new("extend", size), &data, |b, data| {
+            b.iter(|| {
+                let mut stats = mean::Arithmetic::new();
+                stats.extend(data)?;
+                Ok::<_, error::CIError>(stats)
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("append_chunk", size), &data, |b, data| {
+            b.iter(|| {
+                let mut chunks = data.chunks(1 << 16);
+                mean::Arithmetic::from_chunked_source(|| chunks.next())
+            })
+        });
+    }
+    group.finish();
+}
+
 fn bench_mean(c: &mut Criterion) {
     bench_mean_arithmetic(c);
     bench_mean_category(c);
+    bench_mean_critical(c);
     bench_mean_rayon(c);
+    bench_mean_chunked(c);
 }
 
 criterion_group!(benches, bench_mean);
@@ -0,0 +1,41 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::Rng;
+use stats_ci::*;
+
+fn bench_proportion_bitmap(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let size = 10_000_000;
+    let words = (0..size / 64).map(|_| rng.gen::<u64>()).collect::<Vec<_>>();
+    let bools = (0..size)
+        .map(|i| words[i / 64] >> (i % 64) & 1 == 1)
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("proportion (10M samples): bools vs. bitmap");
+    group.sample_size(10);
+
+    group.bench_function("Stats::extend (bool slice)", |b| {
+        b.iter(|| {
+            let mut stats = proportion::Stats::default();
+            stats.extend(&bools);
+            stats
+        })
+    });
+    group.bench_function("Stats::extend_iter (bits decoded from the bitmap)", |b| {
+        b.iter(|| {
+            let mut stats = proportion::Stats::default();
+            stats.extend_iter((0..size).map(|i| words[i / 64] >> (i % 64) & 1 == 1));
+            stats
+        })
+    });
+    group.bench_function("Stats::extend_from_bitmap_u64", |b| {
+        b.iter(|| {
+            let mut stats = proportion::Stats::default();
+            stats.extend_from_bitmap_u64(&words, size);
+            stats
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_proportion_bitmap);
+criterion_main!(benches);
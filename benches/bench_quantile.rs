@@ -0,0 +1,30 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::Rng;
+use stats_ci::*;
+
+fn bench_quantile_many(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let size = 1_000_000;
+    let data = (0..size).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+    let confidence = Confidence::new_two_sided(0.95);
+    let quantiles = [0.5, 0.9, 0.99];
+
+    let mut group = c.benchmark_group("quantile (1M): p50, p90, p99");
+    group.sample_size(10);
+
+    group.bench_function("quantile::ci (one call per quantile)", |b| {
+        b.iter(|| {
+            quantiles
+                .iter()
+                .map(|&quantile| quantile::ci(confidence, &data, quantile))
+                .collect::<CIResult<Vec<_>>>()
+        })
+    });
+    group.bench_function("quantile::ci_many", |b| {
+        b.iter(|| quantile::ci_many(confidence, &data, &quantiles))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_quantile_many);
+criterion_main!(benches);
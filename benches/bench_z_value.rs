@@ -0,0 +1,24 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use stats_ci::{proportion, stats, Confidence};
+
+fn bench_z_value(c: &mut Criterion) {
+    // 95% two-sided is read from the precomputed table, 96% goes through the quantile function
+    let tabulated = Confidence::new_two_sided(0.95);
+    let computed = Confidence::new_two_sided(0.96);
+
+    c.bench_function("stats::z_value (tabulated)", |b| {
+        b.iter(|| stats::z_value(black_box(tabulated)))
+    });
+    c.bench_function("stats::z_value (computed)", |b| {
+        b.iter(|| stats::z_value(black_box(computed)))
+    });
+    c.bench_function("proportion::ci (tabulated)", |b| {
+        b.iter(|| proportion::ci(black_box(tabulated), black_box(1_000), black_box(123)))
+    });
+    c.bench_function("proportion::ci (computed)", |b| {
+        b.iter(|| proportion::ci(black_box(computed), black_box(1_000), black_box(123)))
+    });
+}
+
+criterion_group!(benches, bench_z_value);
+criterion_main!(benches);
@@ -96,13 +96,13 @@ where
     println!("A   :   {} µs", a_ci);
     println!("B   :   {} µs", b_ci);
     println!("diff:   {} µs", paired);
-    let relative_ci = b_ci.relative_to(&a_ci) * 100.;
-    println!("relative: {} %", relative_ci);
-    println!(
-        "relative to A: {:.1} % ~ {:.1} %",
-        relative_ci.low_f(),
-        relative_ci.high_f()
-    );
+    let relative_ci = b_ci.try_relative_to(&a_ci).unwrap();
+    println!("relative to A: {}", relative_ci);
+    match relative_ci.is_improvement(true) {
+        Some(true) => println!("B is significantly faster than A"),
+        Some(false) => println!("B is significantly slower than A"),
+        None => println!("no significant difference between A and B"),
+    }
     println!();
 }
 
@@ -0,0 +1,36 @@
+///
+/// This example illustrates how to export confidence intervals computed over a parameter sweep
+/// as a tidy CSV table, ready to be plotted (e.g., with gnuplot, matplotlib, or pandas).
+///
+/// Usage: `cargo run --example export_sweep --features export > sweep.csv`
+///
+/// With gnuplot, for instance:
+/// ```text
+/// set datafile separator ","
+/// plot "sweep.csv" using 7:2:3:4 skip 1 with yerrorbars title "mean"
+/// ```
+///
+use rand::distributions::Distribution;
+use rand::SeedableRng;
+use stats_ci::*;
+
+fn main() -> CIResult<()> {
+    // seeded for reproducible output
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(42);
+    let distrib = statrs::distribution::Exp::new(0.1).unwrap();
+    let confidence = Confidence::new_two_sided(0.95);
+
+    let mut records = Vec::new();
+    for sample_size in [5, 10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000] {
+        let data: Vec<f64> = (0..sample_size).map(|_| distrib.sample(&mut rng)).collect();
+        let stats = mean::Arithmetic::from_iter(&data)?;
+        records.push(report::Record::from_stats(
+            format!("exp(0.1), n={}", sample_size),
+            &stats,
+            confidence,
+        )?);
+    }
+
+    print!("{}", report::to_csv(&records));
+    Ok(())
+}
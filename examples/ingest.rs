@@ -0,0 +1,55 @@
+///
+/// This example illustrates how to compute the confidence interval for the mean of a column of
+/// CSV or ndjson data, skipping missing values and reporting malformed rows.
+///
+/// Usage: `cargo run --example ingest --features io -- <file.csv|file.ndjson> <column>`
+///
+/// Without arguments, the example runs on a small embedded data set.
+///
+use stats_ci::*;
+
+const SAMPLE: &str = "\
+timestamp,latency_ms
+2023-01-01T00:00:00,12.4
+2023-01-01T00:00:01,11.9
+2023-01-01T00:00:02,
+2023-01-01T00:00:03,13.2
+2023-01-01T00:00:04,timeout
+2023-01-01T00:00:05,12.7
+2023-01-01T00:00:06,12.1
+";
+
+fn main() -> CIResult<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (stats, report) = match args.as_slice() {
+        [path, column] => {
+            let file =
+                std::fs::File::open(path).map_err(|e| error::CIError::Error(e.to_string()))?;
+            if path.ends_with(".ndjson") || path.ends_with(".jsonl") {
+                io::arithmetic_from_ndjson(file, column)?
+            } else {
+                io::arithmetic_from_csv(file, column)?
+            }
+        }
+        [] => io::arithmetic_from_csv(SAMPLE.as_bytes(), "latency_ms")?,
+        _ => {
+            eprintln!("usage: ingest <file.csv|file.ndjson> <column>");
+            std::process::exit(2);
+        }
+    };
+
+    println!("Rows: {}", report);
+    for (row, message) in &report.errors {
+        println!("  row {}: {}", row, message);
+    }
+    println!();
+
+    let confidence = Confidence::new_two_sided(0.95);
+    println!("Sample mean: {}", stats.sample_mean());
+    println!(
+        "{}% CI for the mean: {}",
+        confidence.percent(),
+        stats.ci_mean(confidence)?
+    );
+    Ok(())
+}
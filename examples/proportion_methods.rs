@@ -0,0 +1,44 @@
+/// This example compares the confidence intervals on a proportion computed with different methods,
+/// for a grid of sample sizes and numbers of successes.
+///
+use stats_ci::*;
+
+type Method = fn(Confidence, usize, usize) -> error::CIResult<Interval<f64>>;
+
+fn main() {
+    let confidence = Confidence::new_two_sided(0.95);
+    let methods: [(&str, Method); 3] = [
+        ("Wilson", proportion::ci_wilson),
+        ("Wald", proportion::ci_z_normal),
+        ("Agresti-Coull", proportion::ci_agresti_coull),
+    ];
+    let grid = [
+        (20, 0),
+        (20, 1),
+        (20, 10),
+        (29, 10),
+        (50, 2),
+        (100, 15),
+        (100, 50),
+        (1000, 3),
+        (1000, 990),
+    ];
+
+    println!("{} confidence intervals on a proportion", confidence);
+    println!();
+    print!("{:>12}", "successes/n");
+    for (name, _) in &methods {
+        print!(" {:>18}", name);
+    }
+    println!();
+    for (population, successes) in grid {
+        print!("{:>12}", format!("{}/{}", successes, population));
+        for (_, method) in &methods {
+            match method(confidence, population, successes) {
+                Ok(ci) => print!(" [{:>6.4}, {:>6.4}]", ci.low_f(), ci.high_f()),
+                Err(_) => print!(" {:>18}", "n/a"),
+            }
+        }
+        println!();
+    }
+}
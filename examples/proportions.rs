@@ -51,22 +51,22 @@ fn main() {
         );
     }
 
-    let count_one = sample.iter().filter(|&&x| x == 1).count();
+    // show the sample proportion and its ci (95%), counted in the same pass
+    let confidence = Confidence::new_two_sided(0.95);
+    let (ci, stats) = proportion::ci_if_counted(confidence, &sample, |&x| x == 1).unwrap();
     println!(
         "Sample proportion ({}/{}): {}",
-        count_one,
-        sample.len(),
-        count_one as f64 / sample.len() as f64
+        stats.successes(),
+        stats.population(),
+        stats.success_rate()
     );
-
-    // show the sample ci (95%)
-    let confidence = Confidence::new_two_sided(0.95);
-    let ci = proportion::ci_if(confidence, &sample, |&x| x == 1).unwrap();
     println!(
-        "Sample proportion ci ({} {}%): {}",
+        "Sample proportion ci ({} {}%): {}/{} → {}",
         confidence.kind(),
         confidence.percent(),
-        ci
+        stats.successes(),
+        stats.population(),
+        ci.display_compact(3)
     );
 
     // show the sample ci (99%)
@@ -1,9 +1,9 @@
-///
-/// This is the code used as examples in earlier versions of the
-/// README.md file.
-/// It is used to ensure that the code snippets compile, are
-/// up-to-date, and work as intended.
-///
+//!
+//! This is the code used as examples in earlier versions of the
+//! README.md file.
+//! It is used to ensure that the code snippets compile, are
+//! up-to-date, and work as intended.
+//!
 
 fn block_1() -> stats_ci::CIResult<()> {
     use stats_ci::*;
@@ -96,7 +96,7 @@ fn block_1() -> stats_ci::CIResult<()> {
         .clone()
         .par_iter()
         .map(|&x| mean::Arithmetic::from_iter(&[x]).unwrap())
-        .reduce(|| mean::Arithmetic::new(), |s1, s2| s1 + s2);
+        .reduce(mean::Arithmetic::new, |s1, s2| s1 + s2);
     println!("parallel ci: {}", stats.ci_mean(confidence)?);
     //     parallel ci: [48.09482399055084, 59.24517600944916]
 
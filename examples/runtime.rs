@@ -33,7 +33,7 @@ fn main() {
     let ci = population
         .par_iter()
         .map(|&x| mean::Arithmetic::from_iter(&[x]).unwrap())
-        .reduce(|| mean::Arithmetic::new(), |s1, s2| s1 + s2)
+        .reduce(mean::Arithmetic::new, |s1, s2| s1 + s2)
         .ci_mean(Confidence::new_two_sided(0.95))
         .unwrap();
     let elapsed = start.elapsed();
@@ -52,7 +52,7 @@ fn main() {
             }
             stats
         })
-        .reduce(|| mean::Arithmetic::new(), |s1, s2| s1 + s2)
+        .reduce(mean::Arithmetic::new, |s1, s2| s1 + s2)
         .ci_mean(Confidence::new_two_sided(0.95))
         .unwrap();
     let elapsed = start.elapsed();
@@ -0,0 +1,30 @@
+/// This example illustrates how to run an experiment until the confidence interval on the mean
+/// is precise enough, here until the half-width of the 95% interval is at most 0.5% of the mean.
+///
+use rand::{self, distributions::Distribution};
+use stats_ci::*;
+
+fn main() -> Result<(), error::CIError> {
+    // noisy measurements, e.g., of the running time of a benchmark
+    let mut rng = rand::thread_rng();
+    let distrib = statrs::distribution::LogNormal::new(4.6, 0.1).unwrap();
+
+    let confidence = Confidence::new_two_sided(0.95);
+    let rule = mean::StoppingRule::relative_half_width(0.005)?;
+    let mut stats = mean::Arithmetic::new();
+
+    while !rule.should_stop(&stats, confidence)? {
+        stats.append(distrib.sample(&mut rng))?;
+    }
+
+    let ci = stats.ci_mean(confidence)?;
+    println!("Stopped after {} samples", stats.sample_count());
+    println!("Evaluations of the interval: {}", rule.evaluations());
+    println!("Sample mean: {:.3}", stats.sample_mean());
+    println!("{} CI: {}", confidence, ci);
+    println!(
+        "Relative half-width: {:.3}%",
+        ci.width().unwrap() / 2. / stats.sample_mean() * 100.
+    );
+    Ok(())
+}
@@ -0,0 +1,744 @@
+//!
+//! Several confidence intervals computed on the same sample in a single pass, with optional
+//! simultaneous (joint) coverage.
+//!
+//! A [`Plan`] lists the intervals to compute (mean, proportion of the samples above a threshold,
+//! quantiles), each at its own confidence level. Running the plan on some data goes through the
+//! data once and returns the intervals in [`Results`].
+//!
+//! When several intervals are computed on the same sample, the probability that they all contain
+//! their respective true values is lower than their individual confidence levels. With
+//! [`Plan::simultaneous`], the levels are adjusted with the Bonferroni correction so that the
+//! intervals hold jointly: the risk \\( \alpha_i = 1 - c_i \\) of each of the \\( k \\) intervals
+//! is divided by \\( k \\). The joint confidence level is then at least
+//! \\( 1 - \frac{1}{k} \sum_i \alpha_i \\), i.e., the requested level when all intervals are
+//! requested at the same level.
+//!
+//! The module also packages two-level analyses, such as the comparison of a quantile between two
+//! sets of repeated runs ([`per_run_quantile_comparison`]).
+//!
+//! This module requires the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! let data = [
+//!     10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4, 10.7,
+//!     0.4, 10.1, 4.5, 7.1, 4.3, 37.4, 0.9, 10.1, 12.6, 21.7, 21.9, 2.0, 8.4, 9.3,
+//! ];
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let results = analysis::Plan::new()
+//!     .mean(confidence)
+//!     .proportion_above(20., confidence)
+//!     .quantile(0.5, confidence)
+//!     .simultaneous(true)
+//!     .run(&data)?;
+//!
+//! let median = results.get(analysis::Target::Quantile(0.5)).unwrap();
+//! println!("{}: {}", median.target, median.interval);
+//! // each interval is computed at level 1 - 0.05/3
+//! assert!((median.confidence.level() - (1. - 0.05 / 3.)).abs() < 1e-12);
+//! assert!(results.joint_level() >= 0.95 - 1e-12);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+use super::*;
+use error::*;
+
+///
+/// The quantity estimated by an interval of a [`Plan`].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Target {
+    /// The arithmetic mean (see [`mean::Arithmetic::ci_mean`]).
+    Mean,
+
+    /// The proportion of the samples strictly greater than the given threshold
+    /// (see [`proportion::Stats::ci`]).
+    ProportionAbove(f64),
+
+    /// The given quantile, in \\( [0, 1] \\) (see [`quantile::ci`]).
+    Quantile(f64),
+}
+
+impl core::fmt::Display for Target {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Target::Mean => write!(f, "mean"),
+            Target::ProportionAbove(threshold) => write!(f, "proportion above {}", threshold),
+            Target::Quantile(quantile) => write!(f, "quantile {}", quantile),
+        }
+    }
+}
+
+///
+/// The list of intervals to compute on a sample, built by chaining calls, then executed with
+/// [`Plan::run`].
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// let data = [3., 1., 4., 1., 5., 9., 2., 6., 5., 3., 5., 8., 9., 7., 9., 3., 2., 3., 8., 4.];
+/// let confidence = Confidence::new_two_sided(0.9);
+/// let results = analysis::Plan::new()
+///     .mean(confidence)
+///     .proportion_above(4., confidence)
+///     .run(&data)?;
+/// assert_eq!(results.len(), 2);
+/// assert_eq!(
+///     results.get(analysis::Target::Mean).unwrap().interval,
+///     mean::Arithmetic::ci(confidence, &data)?
+/// );
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Plan {
+    requests: Vec<(Target, Confidence)>,
+    simultaneous: bool,
+}
+
+impl Plan {
+    ///
+    /// Create an empty plan, without simultaneous coverage.
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Request an interval on the arithmetic mean.
+    ///
+    pub fn mean(self, confidence: Confidence) -> Self {
+        self.with(Target::Mean, confidence)
+    }
+
+    ///
+    /// Request an interval on the proportion of the samples strictly greater than `threshold`.
+    ///
+    pub fn proportion_above(self, threshold: f64, confidence: Confidence) -> Self {
+        self.with(Target::ProportionAbove(threshold), confidence)
+    }
+
+    ///
+    /// Request an interval on the given quantile, in \\( [0, 1] \\).
+    ///
+    pub fn quantile(self, quantile: f64, confidence: Confidence) -> Self {
+        self.with(Target::Quantile(quantile), confidence)
+    }
+
+    ///
+    /// Set whether the intervals must hold simultaneously, in which case their confidence levels
+    /// are adjusted with the Bonferroni correction (see the [module documentation](self)).
+    ///
+    pub fn simultaneous(mut self, simultaneous: bool) -> Self {
+        self.simultaneous = simultaneous;
+        self
+    }
+
+    fn with(mut self, target: Target, confidence: Confidence) -> Self {
+        self.requests.push((target, confidence));
+        self
+    }
+
+    ///
+    /// Confidence at which each requested interval is computed, in the order of the requests.
+    ///
+    /// Without simultaneous coverage, these are the requested confidences. Otherwise, the risk
+    /// of each is divided by the number of intervals; the kind of each interval is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stats_ci::*;
+    /// let plan = analysis::Plan::new()
+    ///     .mean(Confidence::new_two_sided(0.75))
+    ///     .quantile(0.9, Confidence::new_upper(0.5))
+    ///     .simultaneous(true);
+    /// assert_eq!(
+    ///     plan.adjusted_confidences(),
+    ///     vec![Confidence::new_two_sided(0.875), Confidence::new_upper(0.75)]
+    /// );
+    /// ```
+    ///
+    pub fn adjusted_confidences(&self) -> Vec<Confidence> {
+        let count = self.requests.len() as f64;
+        self.requests
+            .iter()
+            .map(|&(_, confidence)| {
+                if self.simultaneous {
+                    confidence.with_level(1. - (1. - confidence.level()) / count)
+                } else {
+                    confidence
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Compute the requested intervals on `data`, going through the data only once.
+    ///
+    /// The data is buffered only if a quantile is requested.
+    /// Each interval is the same as that computed by the corresponding function at the adjusted
+    /// confidence (see [`Plan::adjusted_confidences`]): [`mean::Arithmetic::ci`],
+    /// [`proportion::ci_if`], and [`quantile::ci`].
+    ///
+    /// Complexity: \\( O(n) \\), or \\( O(n \log n) \\) if a quantile is requested,
+    /// where \\( n \\) is the number of samples
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if `data` contains NaN
+    /// * any error of the functions computing the individual intervals (e.g., [`CIError::TooFewSamples`])
+    ///
+    pub fn run(&self, data: &[f64]) -> CIResult<Results> {
+        let needs_mean = self.requests.iter().any(|(t, _)| *t == Target::Mean);
+        let needs_buffer = self
+            .requests
+            .iter()
+            .any(|(t, _)| matches!(t, Target::Quantile(_)));
+        let thresholds: Vec<f64> = self
+            .requests
+            .iter()
+            .filter_map(|(t, _)| match t {
+                Target::ProportionAbove(threshold) => Some(*threshold),
+                _ => None,
+            })
+            .collect();
+
+        let mut mean_stats = mean::Arithmetic::new();
+        let mut proportion_stats = vec![proportion::Stats::default(); thresholds.len()];
+        let mut sorted = Vec::with_capacity(if needs_buffer { data.len() } else { 0 });
+        for &x in data {
+            if x.is_nan() {
+                return Err(CIError::InvalidInputData);
+            }
+            if needs_mean {
+                mean_stats.append(x)?;
+            }
+            for (stats, &threshold) in proportion_stats.iter_mut().zip(&thresholds) {
+                if x > threshold {
+                    stats.add_success();
+                } else {
+                    stats.add_failure();
+                }
+            }
+            if needs_buffer {
+                sorted.push(x);
+            }
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut proportion_stats = proportion_stats.into_iter();
+        let estimates = self
+            .requests
+            .iter()
+            .zip(self.adjusted_confidences())
+            .map(|(&(target, requested), confidence)| {
+                let interval = match target {
+                    Target::Mean => mean_stats.ci_mean(confidence)?,
+                    Target::ProportionAbove(_) => proportion_stats
+                        .next()
+                        .expect("one state per proportion")
+                        .ci(confidence)?,
+                    Target::Quantile(quantile) => {
+                        quantile::ci_sorted_unchecked(confidence, &sorted, quantile)?
+                    }
+                };
+                Ok(Estimate {
+                    target,
+                    requested,
+                    confidence,
+                    interval,
+                })
+            })
+            .collect::<CIResult<Vec<_>>>()?;
+        Ok(Results { estimates })
+    }
+}
+
+///
+/// An interval computed by a [`Plan`].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Estimate {
+    /// The estimated quantity.
+    pub target: Target,
+
+    /// The confidence requested in the plan.
+    pub requested: Confidence,
+
+    /// The confidence at which the interval was computed (adjusted for simultaneous coverage).
+    pub confidence: Confidence,
+
+    /// The confidence interval.
+    pub interval: Interval<f64>,
+}
+
+///
+/// The intervals computed by [`Plan::run`], in the order of the requests.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Results {
+    estimates: Vec<Estimate>,
+}
+
+impl Results {
+    ///
+    /// The first estimate of the given target, if it was requested.
+    ///
+    pub fn get(&self, target: Target) -> Option<&Estimate> {
+        self.estimates.iter().find(|e| e.target == target)
+    }
+
+    ///
+    /// The estimates, in the order of the requests.
+    ///
+    pub fn as_slice(&self) -> &[Estimate] {
+        &self.estimates
+    }
+
+    ///
+    /// Iterate over the estimates, in the order of the requests.
+    ///
+    pub fn iter(&self) -> core::slice::Iter<'_, Estimate> {
+        self.estimates.iter()
+    }
+
+    ///
+    /// Number of estimates.
+    ///
+    pub fn len(&self) -> usize {
+        self.estimates.len()
+    }
+
+    ///
+    /// Test whether there are no estimates.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.estimates.is_empty()
+    }
+
+    ///
+    /// Lower bound on the probability that all the intervals contain their true values, from the
+    /// Bonferroni inequality: \\( 1 - \sum_i \alpha_i \\), where \\( \alpha_i \\) is one minus the
+    /// level at which interval \\( i \\) was computed (or zero if the sum exceeds one).
+    ///
+    /// It is one if there are no estimates.
+    ///
+    pub fn joint_level(&self) -> f64 {
+        let risk: f64 = self.iter().map(|e| 1. - e.confidence.level()).sum();
+        (1. - risk).max(0.)
+    }
+}
+
+impl<'a> IntoIterator for &'a Results {
+    type Item = &'a Estimate;
+    type IntoIter = core::slice::Iter<'a, Estimate>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+///
+/// How the per-run quantiles of each set of runs are summarized and compared by
+/// [`per_run_quantile_comparison`].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SummaryKind {
+    /// The difference between the means of the per-run quantiles (see [`comparison::Unpaired::ci_mean`]).
+    MeanOfQuantiles,
+
+    /// The difference between the medians of the per-run quantiles (see [`comparison::quantile_diff_ci`]).
+    MedianOfQuantiles,
+}
+
+///
+/// Compute the confidence interval on the difference of a quantile between two sets of repeated
+/// runs (e.g., the 99th percentile of the latencies of a benchmark, run several times per build),
+/// with an uncertainty derived from the run-to-run variability of that quantile.
+///
+/// The quantile is first computed within each run, as with [`quantile::Stats::index`]. The
+/// per-run quantiles of the two sets are then compared as two independent samples: as the
+/// difference of their means, or of their medians (see [`SummaryKind`]). The difference is
+/// computed as the summary of `runs_a` minus that of `runs_b`.
+///
+/// Unlike [`comparison::quantile_diff_ci`] on the pooled observations of all runs, the interval
+/// accounts for the variations between runs (e.g., due to the state of the machine), which the
+/// sampling error within runs does not capture.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (two-sided or one-sided)
+/// * `runs_a` - the observations of each run of the first set
+/// * `runs_b` - the observations of each run of the second set
+/// * `quantile` - the quantile computed within each run (must be in \\( [0, 1] \\))
+/// * `summary` - how the per-run quantiles are summarized
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if a set has fewer than two runs, if a run is empty, or if
+///   there are too few runs for the median (see [`comparison::quantile_diff_ci`]; at least 8 runs
+///   per set are required with two-sided 95% confidence)
+/// * [`CIError::InvalidQuantile`] - if the quantile is not in \\( [0, 1] \\)
+/// * [`CIError::InvalidInputData`] - if a run contains NaN values
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// use analysis::SummaryKind;
+/// // 8 runs per build, each of 100 latencies; the second build is slower by 5 units
+/// let runs = |build: f64| {
+///     (0..8)
+///         .map(|run| (1..=100).map(|x| x as f64 + run as f64 + 5. * build).collect::<Vec<_>>())
+///         .collect::<Vec<_>>()
+/// };
+/// let (previous, current) = (runs(0.), runs(1.));
+/// let confidence = Confidence::new_two_sided(0.95);
+/// for summary in [SummaryKind::MeanOfQuantiles, SummaryKind::MedianOfQuantiles] {
+///     let ci = analysis::per_run_quantile_comparison(confidence, &current, &previous, 0.99, summary)?;
+///     assert!(ci.contains(&5.));
+///     assert!(ci.low_f() > 0.);
+/// }
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn per_run_quantile_comparison(
+    confidence: Confidence,
+    runs_a: &[Vec<f64>],
+    runs_b: &[Vec<f64>],
+    quantile: f64,
+    summary: SummaryKind,
+) -> CIResult<Interval<f64>> {
+    let quantiles_a = per_run_quantiles(runs_a, quantile)?;
+    let quantiles_b = per_run_quantiles(runs_b, quantile)?;
+    match summary {
+        SummaryKind::MeanOfQuantiles => {
+            comparison::Unpaired::from_iter(&quantiles_a, &quantiles_b)?.ci_mean(confidence)
+        }
+        SummaryKind::MedianOfQuantiles => {
+            comparison::quantile_diff_ci(confidence, &quantiles_a, &quantiles_b, 0.5)
+        }
+    }
+}
+
+///
+/// The given quantile of each run, checking that there are at least two runs.
+///
+fn per_run_quantiles(runs: &[Vec<f64>], quantile: f64) -> CIResult<Vec<f64>> {
+    if runs.len() < 2 {
+        return Err(CIError::TooFewSamples(runs.len()));
+    }
+    runs.iter()
+        .map(|run| {
+            let index = quantile::Stats::new(run.len()).index(quantile)?;
+            if run.iter().any(|x| x.is_nan()) {
+                return Err(CIError::InvalidInputData);
+            }
+            let mut values = run.clone();
+            let (_, &mut value, _) =
+                values.select_nth_unstable_by(index, |a, b| a.partial_cmp(b).unwrap());
+            Ok(value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    const DATA: [f64; 30] = [
+        10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4, 10.7, 0.4,
+        10.1, 4.5, 7.1, 4.3, 37.4, 0.9, 10.1, 12.6, 21.7, 21.9, 2.0, 8.4, 9.3,
+    ];
+
+    #[test]
+    fn test_plan_matches_individual_apis() -> CIResult<()> {
+        let two_sided = Confidence::new_two_sided(0.95);
+        let upper = Confidence::new_upper(0.9);
+        let lower = Confidence::new_lower(0.99);
+        let results = Plan::new()
+            .mean(two_sided)
+            .proportion_above(5., upper)
+            .quantile(0.5, two_sided)
+            .proportion_above(20., lower)
+            .quantile(0.9, upper)
+            .run(&DATA)?;
+
+        let expected = [
+            (
+                Target::Mean,
+                two_sided,
+                mean::Arithmetic::ci(two_sided, &DATA)?,
+            ),
+            (
+                Target::ProportionAbove(5.),
+                upper,
+                proportion::ci_if(upper, &DATA, |&x| x > 5.)?,
+            ),
+            (
+                Target::Quantile(0.5),
+                two_sided,
+                quantile::ci(two_sided, &DATA, 0.5)?,
+            ),
+            (
+                Target::ProportionAbove(20.),
+                lower,
+                proportion::ci_if(lower, &DATA, |&x| x > 20.)?,
+            ),
+            (
+                Target::Quantile(0.9),
+                upper,
+                quantile::ci(upper, &DATA, 0.9)?,
+            ),
+        ];
+        assert_eq!(results.len(), expected.len());
+        for (estimate, (target, confidence, interval)) in results.iter().zip(expected) {
+            assert_eq!(estimate.target, target);
+            assert_eq!(estimate.requested, confidence);
+            assert_eq!(estimate.confidence, confidence);
+            assert_eq!(estimate.interval, interval);
+        }
+        assert_eq!(
+            results.get(Target::Quantile(0.9)).map(|e| e.interval),
+            Some(quantile::ci(upper, &DATA, 0.9)?)
+        );
+        assert_eq!(results.get(Target::Quantile(0.1)), None);
+        assert_abs_diff_eq!(results.joint_level(), 1. - 0.05 - 0.1 - 0.05 - 0.01 - 0.1);
+
+        // without quantiles or proportions
+        let results = Plan::new().mean(two_sided).run(&DATA)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results.get(Target::Mean).unwrap().interval,
+            mean::Arithmetic::ci(two_sided, &DATA)?
+        );
+        assert!(Plan::new().run(&DATA)?.is_empty());
+        assert_eq!(Plan::new().run(&DATA)?.joint_level(), 1.);
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_simultaneous() -> CIResult<()> {
+        let plan = Plan::new()
+            .mean(Confidence::new_two_sided(0.95))
+            .proportion_above(5., Confidence::new_lower(0.95))
+            .quantile(0.5, Confidence::new_upper(0.95))
+            .quantile(0.9, Confidence::new_two_sided(0.8))
+            .simultaneous(true);
+        let adjusted = plan.adjusted_confidences();
+        let expected = [
+            Confidence::new_two_sided(1. - 0.05 / 4.),
+            Confidence::new_lower(1. - 0.05 / 4.),
+            Confidence::new_upper(1. - 0.05 / 4.),
+            Confidence::new_two_sided(1. - 0.2 / 4.),
+        ];
+        assert_eq!(adjusted.len(), expected.len());
+        for (adjusted, expected) in adjusted.iter().zip(expected) {
+            assert_eq!(adjusted.kind(), expected.kind());
+            assert_abs_diff_eq!(adjusted.level(), expected.level(), epsilon = 1e-12);
+        }
+
+        let results = plan.run(&DATA)?;
+        for (estimate, confidence) in results.iter().zip(&adjusted) {
+            assert_eq!(estimate.confidence, *confidence);
+            let interval = match estimate.target {
+                Target::Mean => mean::Arithmetic::ci(*confidence, &DATA)?,
+                Target::ProportionAbove(t) => proportion::ci_if(*confidence, &DATA, |&x| x > t)?,
+                Target::Quantile(q) => quantile::ci(*confidence, &DATA, q)?,
+            };
+            assert_eq!(estimate.interval, interval);
+        }
+        assert_eq!(
+            results.as_slice()[3].requested,
+            Confidence::new_two_sided(0.8)
+        );
+        // the adjusted intervals are wider than the unadjusted ones
+        let unadjusted = plan.clone().simultaneous(false).run(&DATA)?;
+        for (adjusted, unadjusted) in results.iter().zip(&unadjusted) {
+            assert!(adjusted.interval.includes(&unadjusted.interval));
+        }
+        assert_abs_diff_eq!(
+            results.joint_level(),
+            1. - (0.05 * 3. + 0.2) / 4.,
+            epsilon = 1e-12
+        );
+
+        // a single interval is not adjusted
+        let single = Plan::new()
+            .mean(Confidence::new_two_sided(0.95))
+            .simultaneous(true);
+        assert_eq!(
+            single.adjusted_confidences(),
+            vec![Confidence::new_two_sided(0.95)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let confidence = Confidence::new_two_sided(0.95);
+        let plan = Plan::new().mean(confidence).quantile(0.5, confidence);
+        assert_eq!(
+            plan.run(&[1., f64::NAN, 2.]),
+            Err(CIError::InvalidInputData)
+        );
+        assert_eq!(plan.run(&[]), Err(CIError::TooFewSamples(0)));
+    }
+
+    #[test]
+    fn test_per_run_quantile_comparison() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1676);
+
+        // each run is a shuffled grid 1..=200 shifted by a random offset of the run, so that the
+        // quantile of a run is that of the grid plus the offset: the offsets are uniform in
+        // [0, 4] for the first set and in [1, 5] for the second one, so that the true difference
+        // of the per-run quantiles is -1 (for both their means and their medians)
+        let mut make_runs = |count: usize, base: f64| {
+            (0..count)
+                .map(|_| {
+                    let offset = base + 4. * rng.gen::<f64>();
+                    let mut run = (1..=200).map(|x| x as f64 + offset).collect::<Vec<_>>();
+                    for i in (1..run.len()).rev() {
+                        run.swap(i, rng.gen_range(0..=i));
+                    }
+                    run
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let confidence = Confidence::new_two_sided(0.9);
+        let repetitions = 200;
+        for summary in [SummaryKind::MeanOfQuantiles, SummaryKind::MedianOfQuantiles] {
+            let mut hits = 0;
+            for _ in 0..repetitions {
+                let runs_a = make_runs(20, 0.);
+                let runs_b = make_runs(25, 1.);
+                let ci = per_run_quantile_comparison(confidence, &runs_a, &runs_b, 0.99, summary)?;
+                if ci.contains(&-1.) {
+                    hits += 1;
+                }
+            }
+            let coverage = hits as f64 / repetitions as f64;
+            assert!(
+                (0.84..=0.97).contains(&coverage),
+                "{summary:?}: coverage {coverage}"
+            );
+        }
+
+        // same as the two-sample comparison of the per-run quantiles
+        let runs_a = make_runs(10, 0.);
+        let runs_b = make_runs(12, 1.);
+        let quantiles = |runs: &[Vec<f64>]| {
+            runs.iter()
+                .map(|run| {
+                    let mut run = run.clone();
+                    run.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                    run[quantile::Stats::new(run.len()).index(0.9).unwrap()]
+                })
+                .collect::<Vec<_>>()
+        };
+        let (quantiles_a, quantiles_b) = (quantiles(&runs_a), quantiles(&runs_b));
+        assert_eq!(
+            per_run_quantile_comparison(
+                confidence,
+                &runs_a,
+                &runs_b,
+                0.9,
+                SummaryKind::MeanOfQuantiles
+            )?,
+            comparison::Unpaired::ci(confidence, &quantiles_a, &quantiles_b)?
+        );
+        assert_eq!(
+            per_run_quantile_comparison(
+                confidence,
+                &runs_a,
+                &runs_b,
+                0.9,
+                SummaryKind::MedianOfQuantiles
+            )?,
+            comparison::quantile_diff_ci(confidence, &quantiles_a, &quantiles_b, 0.5)?
+        );
+
+        // errors
+        let summary = SummaryKind::MeanOfQuantiles;
+        assert_eq!(
+            per_run_quantile_comparison(confidence, &runs_a[..1], &runs_b, 0.9, summary),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert_eq!(
+            per_run_quantile_comparison(confidence, &runs_a, &[], 0.9, summary),
+            Err(CIError::TooFewSamples(0))
+        );
+        assert_eq!(
+            per_run_quantile_comparison(confidence, &runs_a, &[vec![1.], vec![]], 0.9, summary),
+            Err(CIError::TooFewSamples(0))
+        );
+        assert_eq!(
+            per_run_quantile_comparison(confidence, &runs_a, &runs_b, 1.5, summary),
+            Err(CIError::InvalidQuantile(1.5))
+        );
+        assert_eq!(
+            per_run_quantile_comparison(
+                confidence,
+                &runs_a,
+                &[vec![1.], vec![f64::NAN]],
+                0.9,
+                summary
+            ),
+            Err(CIError::InvalidInputData)
+        );
+        // the median requires more runs than the mean
+        assert!(per_run_quantile_comparison(
+            confidence,
+            &runs_a[..3],
+            &runs_b[..3],
+            0.9,
+            SummaryKind::MeanOfQuantiles
+        )
+        .is_ok());
+        assert_eq!(
+            per_run_quantile_comparison(
+                confidence,
+                &runs_a[..3],
+                &runs_b[..3],
+                0.9,
+                SummaryKind::MedianOfQuantiles
+            ),
+            Err(CIError::TooFewSamples(3))
+        );
+        // at two-sided 95%, the median requires 8 runs per set
+        let confidence = Confidence::new_two_sided(0.95);
+        for count in 2..=7 {
+            assert_eq!(
+                per_run_quantile_comparison(
+                    confidence,
+                    &runs_a[..count],
+                    &runs_b[..count],
+                    0.9,
+                    SummaryKind::MedianOfQuantiles
+                ),
+                Err(CIError::TooFewSamples(count))
+            );
+        }
+        assert!(per_run_quantile_comparison(
+            confidence,
+            &runs_a[..8],
+            &runs_b[..8],
+            0.9,
+            SummaryKind::MedianOfQuantiles
+        )
+        .is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+//!
+//! Implementations of [`proptest::arbitrary::Arbitrary`] for the main types of the crate.
+//!
+//! This module is only available with the `proptest` feature enabled.
+//! The strategies always produce values that satisfy the invariants of their type:
+//!
+//! * [`Interval`]: the lower bound is never greater than the upper bound, and all three variants are generated;
+//! * [`Confidence`]: the level is always in the range (0, 1), and all three variants are generated;
+//! * [`proportion::Stats`]: the number of successes never exceeds the population;
+//! * [`mean::Arithmetic`]: generated from a random triple of sample count, mean, and variance.
+//!
+//! # Example
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use stats_ci::*;
+//!
+//! proptest! {
+//!     // in a test suite, annotate the function with `#[test]` instead of calling it
+//!     fn interval_contains_its_bounds(interval in any::<Interval<f64>>()) {
+//!         if let Some(low) = interval.low() {
+//!             prop_assert!(interval.contains(&low));
+//!         }
+//!     }
+//! }
+//! interval_contains_its_bounds();
+//! ```
+//!
+use crate::*;
+use proptest::prelude::*;
+
+/// Range of the finite bounds generated for floating point intervals.
+const FLOAT_RANGE: core::ops::Range<f64> = -1e9..1e9;
+
+/// Range of the bounds generated for integer intervals.
+/// Kept within 32 bits so that arithmetic on generated intervals cannot overflow.
+const INT_RANGE: core::ops::RangeInclusive<i64> = (i32::MIN as i64)..=(i32::MAX as i64);
+
+/// Range of confidence levels generated for [`Confidence`].
+const LEVEL_RANGE: core::ops::Range<f64> = 0.001..0.999;
+
+/// Maximum population generated for [`proportion::Stats`].
+const MAX_POPULATION: usize = 1_000_000;
+
+/// Maximum sample count generated for [`mean::Arithmetic`].
+const MAX_SAMPLE_COUNT: usize = 100_000;
+
+fn interval_strategy<T>(bound: BoxedStrategy<T>) -> BoxedStrategy<Interval<T>>
+where
+    T: PartialOrd + Clone + core::fmt::Debug + 'static,
+{
+    prop_oneof![
+        (bound.clone(), bound.clone()).prop_map(|(a, b)| {
+            if a <= b {
+                Interval::TwoSided(a, b)
+            } else {
+                Interval::TwoSided(b, a)
+            }
+        }),
+        bound.clone().prop_map(Interval::UpperOneSided),
+        bound.prop_map(Interval::LowerOneSided),
+    ]
+    .boxed()
+}
+
+impl Arbitrary for Interval<f64> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        interval_strategy(FLOAT_RANGE.boxed())
+    }
+}
+
+impl Arbitrary for Interval<i64> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        interval_strategy(INT_RANGE.boxed())
+    }
+}
+
+impl Arbitrary for Confidence {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            LEVEL_RANGE.prop_map(Confidence::new_two_sided),
+            LEVEL_RANGE.prop_map(Confidence::new_upper),
+            LEVEL_RANGE.prop_map(Confidence::new_lower),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for proportion::Stats {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (0..=MAX_POPULATION)
+            .prop_flat_map(|population| (Just(population), 0..=population))
+            .prop_map(|(population, successes)| proportion::Stats::new(population, successes))
+            .boxed()
+    }
+}
+
+impl Arbitrary for mean::Arithmetic<f64> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        (2..=MAX_SAMPLE_COUNT, -1e6..1e6_f64, 0_f64..1e6)
+            .prop_map(|(count, mean, variance)| {
+                let n = count as f64;
+                let sum = mean * n;
+                let sum_sq = variance * (n - 1.) + mean * sum;
+                mean::Arithmetic::from_raw(count, sum, sum_sq)
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_interval_f64_is_valid(interval in any::<Interval<f64>>()) {
+            if let Interval::TwoSided(low, high) = interval {
+                prop_assert!(low <= high);
+            }
+        }
+
+        #[test]
+        fn test_interval_i64_is_valid(interval in any::<Interval<i64>>()) {
+            if let Interval::TwoSided(low, high) = interval {
+                prop_assert!(low <= high);
+            }
+        }
+
+        #[test]
+        fn test_confidence_is_valid(confidence in any::<Confidence>()) {
+            prop_assert!(confidence.level() > 0. && confidence.level() < 1.);
+        }
+
+        #[test]
+        fn test_proportion_stats_is_valid(stats in any::<proportion::Stats>()) {
+            prop_assert!(stats.successes() <= stats.population());
+        }
+
+        #[test]
+        fn test_arithmetic_is_valid(stats in any::<mean::Arithmetic<f64>>()) {
+            prop_assert!(stats.sample_count() >= 2);
+            prop_assert!(stats.sample_mean().is_finite());
+            prop_assert!(stats.sample_variance().is_finite());
+        }
+    }
+}
@@ -11,6 +11,14 @@
 //!
 //! The structure [`Paired`] deals with paired observations and can be used in simple form through the function [`Paired::ci`] or incrementally
 //! with the function [`Paired::ci_mean`].
+//! The function [`Paired::test_margin`] answers whether one sample is smaller (or larger) than the other by at least a margin,
+//! e.g., for a regression gate on benchmarks.
+//! The structure [`PairedRatio`] compares paired observations multiplicatively, through the geometric mean of
+//! the ratios of the pairs (e.g., the speedup of a benchmark), with the functions [`PairedRatio::ci_ratio`]
+//! and [`PairedRatio::ci_percent_change`].
+//! The structure [`PairedRobust`] buffers the differences of the pairs to flag outlier pairs with
+//! [`PairedRobust::outlier_pairs`] and exclude them from the interval with
+//! [`PairedRobust::ci_mean_excluding_outliers`].
 //!
 //! # Unpaired observations
 //!
@@ -20,6 +28,13 @@
 //! The structure [`Unpaired`] deals with the case of unpaired observations and can be used in simple form through the function [`Unpaired::ci`]
 //! or incrementally with the function [`Unpaired::ci_mean`].
 //!
+//! # Quantiles
+//!
+//! The function [`quantile_diff_ci`] compares a given quantile (e.g., the median or the 99th percentile)
+//! of two independent samples rather than their means.
+//! When the two distributions differ mainly by a constant shift (e.g., latencies with an added delay),
+//! the function [`shift_ci`] estimates that shift from the differences of their deciles.
+//!
 //! # Examples
 //!
 //! ## Paired observations
@@ -125,11 +140,18 @@
 //! * [Wikipedia article on paired difference test](https://en.wikipedia.org/wiki/Paired_difference_test)
 //! * PennState. Stat 500. Lesson 7: Comparing Two Population Parameters. [Online](https://online.stat.psu.edu/stat500/lesson/7)
 //!
+use crate::stats::z_value;
 use crate::*;
 use error::*;
 use mean::StatisticsOps;
 use num_traits::Float;
 
+///
+/// Number of observations (or pairs) accumulated per chunk by the parallel constructors.
+///
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 4_096;
+
 ///
 /// Structure to collect statistics on two paired samples.
 ///
@@ -178,6 +200,39 @@ pub struct Paired<T: Float> {
 }
 
 impl<T: Float> Paired<T> {
+    ///
+    /// Create a new instance of `Paired` from summary statistics of the pairwise differences,
+    /// when the raw observations are not available.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - the number of pairs
+    /// * `mean_diff` - the mean of the differences \\( a_i - b_i \\)
+    /// * `std_dev_diff` - the sample standard deviation of the differences
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if `count` is less than 2
+    /// * [`CIError::InvalidInputData`] - if `mean_diff` is not finite, or if `std_dev_diff` is negative or not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// // zinc concentration example (see above): 10 pairs, mean difference 0.0804, std. dev. 0.0523
+    /// let stats = comparison::Paired::from_summary(10, 0.0804, 0.0523)?;
+    /// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+    /// # use approx::*;
+    /// # assert_abs_diff_eq!(ci, Interval::new(0.04299, 0.11781)?, epsilon = 1e-5);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn from_summary(count: usize, mean_diff: T, std_dev_diff: T) -> CIResult<Self> {
+        Ok(Self {
+            stats: mean::Arithmetic::from_summary(count, mean_diff, std_dev_diff)?,
+        })
+    }
+
     ///
     /// Add a pair of observations to the two samples.
     ///
@@ -200,6 +255,7 @@ impl<T: Float> Paired<T> {
     /// # assert_eq!(stats.sample_mean(), -1.);
     /// # Ok::<(),error::CIError>(())
     /// ```
+    #[must_use = "the sample is not recorded if an error is returned"]
     pub fn append_pair(&mut self, data_a: T, data_b: T) -> CIResult<()> {
         self.stats.append(data_a - data_b)
     }
@@ -225,7 +281,8 @@ impl<T: Float> Paired<T> {
     /// # assert_eq!(stats.sample_mean(), -1.);
     /// # Ok::<(),error::CIError>(())
     /// ```
-    pub fn extend_tuple<I>(&mut self, iter: &I) -> CIResult<()>
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_tuple<I: ?Sized>(&mut self, iter: &I) -> CIResult<()>
     where
         for<'a> &'a I: IntoIterator<Item = &'a (T, T)>,
     {
@@ -235,6 +292,114 @@ impl<T: Float> Paired<T> {
         Ok(())
     }
 
+    ///
+    /// Append pairs of observations consumed from an iterator, such as an iterator adapter.
+    ///
+    /// Unlike [`Paired::extend_tuple`], the pairs are taken by value, which avoids collecting
+    /// them into a temporary collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - the pairs of observations to add to the data
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data_a = [1., 3., 5.];
+    /// let data_b = [2., 4., 6.];
+    /// let mut stats = comparison::Paired::default();
+    /// stats.extend_pairs(data_a.iter().zip(data_b.iter()).map(|(a, b)| (a * 2., b * 2.)))?;
+    /// assert_eq!(stats.sample_count(), 3);
+    /// assert_eq!(stats.sample_mean(), -2.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_pairs<I>(&mut self, iter: I) -> CIResult<()>
+    where
+        I: IntoIterator<Item = (T, T)>,
+    {
+        for (x, y) in iter {
+            self.stats.append(x - y)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Create a new state from pairs of observations consumed from an iterator.
+    ///
+    /// This is a shortcut for [`Default::default`] and [`Paired::extend_pairs`].
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - the pairs of observations
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = comparison::Paired::from_pairs((1..=10).map(|i| (i as f64 + 0.5, i as f64)))?;
+    /// assert_eq!(stats.sample_count(), 10);
+    /// assert_eq!(stats.sample_mean(), 0.5);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn from_pairs<I>(iter: I) -> CIResult<Self>
+    where
+        I: IntoIterator<Item = (T, T)>,
+    {
+        let mut stats = Self::default();
+        stats.extend_pairs(iter)?;
+        Ok(stats)
+    }
+
+    ///
+    /// Create a new instance of `Paired` from a slice of pairs of observations, accumulating
+    /// fixed-size chunks of pairs in parallel and merging the chunk states with `+`
+    /// (see [`mean::Arithmetic::from_chunks_deterministic`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `pairs` - the pairs of observations
+    ///
+    /// # Notes
+    ///
+    /// The result does not depend on the number of threads. It may differ from that of
+    /// [`Paired::from_pairs`] at the level of rounding errors only: with IEEE doubles, the
+    /// mean and standard deviation typically agree to a relative error of \\( 10^{-12} \\) or better.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let pairs = (1..=100_000)
+    ///     .map(|i| (i as f64 + 0.5, i as f64))
+    ///     .collect::<Vec<_>>();
+    /// let stats = comparison::Paired::from_pairs_parallel(&pairs);
+    /// assert_eq!(stats.sample_count(), 100_000);
+    /// assert_abs_diff_eq!(stats.sample_mean(), 0.5);
+    /// ```
+    ///
+    #[cfg(feature = "rayon")]
+    pub fn from_pairs_parallel(pairs: &[(T, T)]) -> Self
+    where
+        T: Send + Sync,
+    {
+        Self {
+            stats: mean::Arithmetic::from_chunks_mapped(pairs, PARALLEL_CHUNK_SIZE, |&(a, b)| {
+                a - b
+            }),
+        }
+    }
+
     ///
     /// Append multiple observations to the two samples.
     ///
@@ -258,10 +423,47 @@ impl<T: Float> Paired<T> {
     /// # assert_eq!(stats.sample_mean(), -1.);
     /// # Ok::<(),error::CIError>(())
     /// ```
-    pub fn extend<I1, I2>(&mut self, data_a: &I1, data_b: &I2) -> CIResult<()>
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend<I1: ?Sized, I2: ?Sized>(&mut self, data_a: &I1, data_b: &I2) -> CIResult<()>
     where
         for<'a> &'a I1: IntoIterator<Item = &'a T>,
         for<'b> &'b I2: IntoIterator<Item = &'b T>,
+    {
+        self.extend_iter(data_a.into_iter().copied(), data_b.into_iter().copied())
+    }
+
+    ///
+    /// Append observations consumed from two iterators, such as iterator adapters, to the two samples.
+    ///
+    /// Unlike [`Paired::extend`], the observations are taken by value, which avoids collecting
+    /// them into temporary collections.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the observations for the first sample
+    /// * `data_b` - the observations for the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentSampleSizes`] - if the two iterators have different lengths
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let runs = 1..=5;
+    /// let mut stats = comparison::Paired::default();
+    /// stats.extend_iter(runs.clone().map(|i| i as f64 * 1.1), runs.map(|i| i as f64))?;
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.sample_mean(), 0.3, epsilon = 1e-12);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_iter<Ia, Ib>(&mut self, data_a: Ia, data_b: Ib) -> CIResult<()>
+    where
+        Ia: IntoIterator<Item = T>,
+        Ib: IntoIterator<Item = T>,
     {
         let mut data_a = data_a.into_iter();
         let mut data_b = data_b.into_iter();
@@ -270,21 +472,21 @@ impl<T: Float> Paired<T> {
             match (data_a.next(), data_b.next()) {
                 (Some(x), Some(y)) => {
                     count += 1;
-                    self.stats.append(*x - *y)?
+                    self.stats.append(x - y)?
                 }
                 (None, None) => return Ok(()),
                 // returns error if iterables have different lengths
                 (None, _) => {
-                    return Err(CIError::DifferentSampleSizes(
-                        count,
-                        count + 1 + data_b.count(),
-                    ))
+                    return Err(CIError::DifferentSampleSizes {
+                        size_a: count,
+                        size_b: count + 1 + data_b.count(),
+                    })
                 }
                 (_, None) => {
-                    return Err(CIError::DifferentSampleSizes(
-                        count + 1 + data_a.count(),
-                        count,
-                    ))
+                    return Err(CIError::DifferentSampleSizes {
+                        size_a: count + 1 + data_a.count(),
+                        size_b: count,
+                    })
                 }
             }
         }
@@ -348,6 +550,26 @@ impl<T: Float> Paired<T> {
         self.stats.sample_count()
     }
 
+    ///
+    /// Degrees of freedom of the interval on the mean difference, i.e., the number of pairs
+    /// minus one (or zero if there are no pairs).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn degrees_of_freedom(&self) -> f64 {
+        self.stats.degrees_of_freedom()
+    }
+
+    ///
+    /// Whether the interval on the mean difference is computed with the normal distribution
+    /// rather than Student's t distribution (see [`stats::uses_normal_approximation`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn uses_normal_approximation(&self) -> bool {
+        self.stats.uses_normal_approximation()
+    }
+
     ///
     /// Return the confidence interval of the difference between the means of the two samples.
     ///
@@ -382,6 +604,270 @@ impl<T: Float> Paired<T> {
         self.stats.ci_mean(confidence)
     }
 
+    ///
+    /// Return the confidence interval of the difference between the means of the two samples,
+    /// together with the quantities used to compute it.
+    ///
+    /// See [`mean::CiDetail`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data_a = [1., 2., 3., 5.];
+    /// let data_b = [4., 5., 6., 7.];
+    /// let mut stats = comparison::Paired::default();
+    /// stats.extend(&data_a, &data_b)?;
+    /// let detail = stats.ci_mean_detailed(Confidence::new_two_sided(0.95))?;
+    /// assert_eq!(detail.estimate, stats.sample_mean());
+    /// assert_eq!(detail.degrees_of_freedom, 3.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn ci_mean_detailed(&self, confidence: Confidence) -> CIResult<mean::CiDetail<T>> {
+        self.stats.ci_mean_detailed(confidence)
+    }
+
+    ///
+    /// Two-sided p-value of the paired t-test of the hypothesis that the means of the two samples
+    /// are equal, i.e., that the mean of the differences is zero (see [`mean::Arithmetic::p_value_vs`]).
+    ///
+    /// For any \( \alpha \), \( p < \alpha \) if and only if the two-sided interval computed
+    /// by [`Self::ci_mean`] with confidence \( 1 - \alpha \) excludes zero.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data_a = [1., 2., 3., 5.];
+    /// let data_b = [4., 5., 6., 7.];
+    /// let mut stats = comparison::Paired::default();
+    /// stats.extend(&data_a, &data_b)?;
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.p_value()?, 0.001609, epsilon = 1e-6);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn p_value(&self) -> CIResult<f64> {
+        self.stats.p_value_vs(T::zero())
+    }
+
+    ///
+    /// One-sided p-value of the paired t-test of the hypothesis that the means of the two samples
+    /// are equal, against the alternative that the difference is in the given direction
+    /// (see [`mean::Arithmetic::p_value_one_sided_vs`]).
+    ///
+    /// For any \( \alpha \), \( p < \alpha \) if and only if the one-sided interval computed
+    /// by [`Self::ci_mean`] with confidence \( 1 - \alpha \) excludes zero, where the interval is
+    /// lower one-sided for [`Direction::FirstSmaller`] and upper one-sided for [`Direction::FirstLarger`].
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - the direction of the difference under the alternative hypothesis
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// use comparison::Direction;
+    /// let data_a = [1., 2., 3., 5.];
+    /// let data_b = [4., 5., 6., 7.];
+    /// let mut stats = comparison::Paired::default();
+    /// stats.extend(&data_a, &data_b)?;
+    /// assert!(stats.p_value_one_sided(Direction::FirstSmaller)? < 0.001);
+    /// assert!(stats.p_value_one_sided(Direction::FirstLarger)? > 0.999);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn p_value_one_sided(&self, direction: Direction) -> CIResult<f64> {
+        self.stats
+            .p_value_one_sided_vs(T::zero(), direction.alternative())
+    }
+
+    ///
+    /// Test whether the difference between the means of the two samples is significant,
+    /// based on the confidence interval computed by [`Self::ci_mean`].
+    ///
+    /// See [`Interval::significance`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data_a = [1., 2., 3., 5.];
+    /// let data_b = [4., 5., 6., 7.];
+    /// let mut stats = comparison::Paired::default();
+    /// stats.extend(&data_a, &data_b)?;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// assert_eq!(stats.significance(confidence)?, Significance::NegativelySignificant);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn significance(&self, confidence: Confidence) -> CIResult<Significance> {
+        Ok(self.ci_mean(confidence)?.significance())
+    }
+
+    ///
+    /// Summarize the comparison in a single report: the number of pairs, the mean difference
+    /// and its standard error, and the confidence interval of the difference with its significance.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::ci_mean`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data_a = [1., 2., 3., 5.];
+    /// let data_b = [4., 5., 6., 7.];
+    /// let stats = comparison::Paired::from_pairs(data_a.into_iter().zip(data_b))?;
+    /// let report = stats.report(Confidence::new_two_sided(0.95))?;
+    /// assert_eq!(report.count, 4);
+    /// assert_eq!(report.mean_difference, -2.75);
+    /// assert!(report.significant);
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(
+    ///     report.difference,
+    ///     Interval::new(-3.5456115763209, -1.9543884236791)?,
+    ///     epsilon = 1e-10
+    /// );
+    /// let text = report.to_string();
+    /// assert!(text.starts_with(
+    ///     "pairs: n = 4, mean difference = -2.75, std. err. = 0.25\n\
+    ///      difference (A - B): [-3.5456"
+    /// ));
+    /// assert!(text.ends_with("(two-sided 95%), significant"));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn report(&self, confidence: Confidence) -> CIResult<PairedReport<T>> {
+        let difference = self.ci_mean(confidence)?;
+        Ok(PairedReport {
+            count: self.sample_count(),
+            mean_difference: self.sample_mean(),
+            sem: self.sample_sem(),
+            difference,
+            confidence,
+            significant: difference.significance().is_significant(),
+        })
+    }
+
+    ///
+    /// Test whether the mean of the first sample is smaller (resp. larger) than the mean of the
+    /// second sample by more than a given margin, e.g., whether a candidate is faster than a
+    /// baseline by at least some amount.
+    ///
+    /// The test is based on the one-sided confidence interval of the difference (first minus second)
+    /// in the direction of the test: the lower one-sided interval \( (-\infty, high] \) for
+    /// [`Direction::FirstSmaller`] and the upper one-sided interval \( [low, +\infty) \) for
+    /// [`Direction::FirstLarger`]. The test passes if the bound of the interval clears the margin strictly.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level; only the level is used and the kind (two-sided or one-sided)
+    ///   is ignored, since the one-sided interval is determined by `direction`
+    /// * `margin` - the margin, in the unit of the data; a negative margin tests for non-inferiority
+    ///   (e.g., "no slower than by some amount")
+    /// * `direction` - the direction of the difference being tested
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidMargin`] - if the margin is not finite
+    /// * [`CIError::TooFewSamples`] - if there are no pairs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// use comparison::Direction;
+    /// // running times (ms) of a candidate and a baseline on the same inputs
+    /// let candidate = [96.1, 97.4, 95.2, 98.0, 96.6, 95.9, 97.1, 96.3];
+    /// let baseline = [99.0, 100.2, 98.9, 100.5, 99.7, 99.1, 100.4, 99.6];
+    /// let stats = comparison::Paired::from_pairs(candidate.into_iter().zip(baseline))?;
+    ///
+    /// // is the candidate at least 2% faster than the baseline at 95% confidence?
+    /// let margin = 0.02 * mean::Arithmetic::from_iter(&baseline)?.sample_mean();
+    /// let result = stats.test_margin(Confidence::new(0.95), margin, Direction::FirstSmaller)?;
+    /// assert!(result.passed);
+    /// assert_eq!(result.confidence, Confidence::new_lower(0.95));
+    /// assert_eq!(result.headroom, -result.bound - margin);
+    ///
+    /// // ... but not 4% faster
+    /// let margin = 0.04 * mean::Arithmetic::from_iter(&baseline)?.sample_mean();
+    /// let result = stats.test_margin(Confidence::new(0.95), margin, Direction::FirstSmaller)?;
+    /// assert!(!result.passed);
+    /// assert!(result.headroom < 0.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// use comparison::Direction;
+    /// // throughput (requests/s) of a candidate and a baseline on the same machines
+    /// let candidate = [1220., 1185., 1240., 1210., 1198., 1232.];
+    /// let baseline = [1100., 1090., 1125., 1102., 1087., 1119.];
+    /// let stats = comparison::Paired::from_pairs(candidate.into_iter().zip(baseline))?;
+    ///
+    /// // is the candidate larger by at least 50 requests/s?
+    /// let result = stats.test_margin(Confidence::new(0.99), 50., Direction::FirstLarger)?;
+    /// assert!(result.passed);
+    /// assert_eq!(result.bound, stats.ci_mean(Confidence::new_upper(0.99))?.low_f());
+    /// assert_eq!(result.headroom, result.bound - 50.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn test_margin(
+        &self,
+        confidence: Confidence,
+        margin: T,
+        direction: Direction,
+    ) -> CIResult<MarginTestResult<T>> {
+        let margin_f64 = margin.try_f64("margin")?;
+        if !margin_f64.is_finite() {
+            return Err(CIError::InvalidMargin(margin_f64));
+        }
+        let (confidence, bound, lead) = match direction {
+            Direction::FirstSmaller => {
+                let confidence = Confidence::new_lower(confidence.level());
+                let bound = self.ci_mean(confidence)?.high_f();
+                (confidence, bound, -bound)
+            }
+            Direction::FirstLarger => {
+                let confidence = Confidence::new_upper(confidence.level());
+                let bound = self.ci_mean(confidence)?.low_f();
+                (confidence, bound, bound)
+            }
+        };
+        let headroom = lead - margin;
+        Ok(MarginTestResult {
+            confidence,
+            bound,
+            margin,
+            headroom,
+            passed: headroom > T::zero(),
+        })
+    }
+
     ///
     /// Compute the confidence interval of the difference between the means of the two samples.
     ///
@@ -446,6 +932,26 @@ impl<T: Float> Default for Paired<T> {
     }
 }
 
+///
+/// Merge the statistics of two disjoint sets of pairs, as if all pairs had been appended to a
+/// single instance.
+///
+/// This is the building block for accumulating pairs concurrently: each thread (or chunk)
+/// builds its own `Paired` and the partial states are then combined with `+` or `+=`
+/// (see [`Paired::from_pairs_parallel`]).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let stats_1 = comparison::Paired::from_pairs([(2., 1.), (4., 2.)])?;
+/// let stats_2 = comparison::Paired::from_pairs([(6., 3.)])?;
+/// let stats = stats_1 + stats_2;
+/// assert_eq!(stats.sample_count(), 3);
+/// assert_eq!(stats.sample_mean(), 2.);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
 impl<F: Float> core::ops::Add for Paired<F> {
     type Output = Self;
 
@@ -457,423 +963,600 @@ impl<F: Float> core::ops::Add for Paired<F> {
     }
 }
 
-impl<F: Float> core::ops::AddAssign for Paired<F> {
-    #[inline]
-    fn add_assign(&mut self, rhs: Self) {
-        self.stats += rhs.stats;
-    }
-}
-
 ///
-/// Structure to collect statistics on two unpaired samples.
+/// Direction of the difference tested by [`Paired::test_margin`], or of the alternative hypothesis
+/// of a one-sided p-value (see [`Paired::p_value_one_sided`] and [`Unpaired::p_value_one_sided`]).
 ///
-/// Given two independent samples, the goal is to compute the confidence interval
-/// of the difference between their means.
-/// Unlike with paired observations ([`Paired`]), the two samples do not have to
-/// have the same length.
-/// However, comparing with unpaired observations typically requires considerably
-/// more observations to reach the same degree of statistical accuracy. This is
-/// why paired observations are preferred when the circumstances allow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    ///
+    /// The mean of the first sample is smaller than that of the second sample (e.g., a faster candidate).
+    ///
+    FirstSmaller,
+
+    ///
+    /// The mean of the first sample is larger than that of the second sample (e.g., a higher throughput).
+    ///
+    FirstLarger,
+}
+
+impl Direction {
+    ///
+    /// Alternative hypothesis on the difference between the means (first minus second).
+    ///
+    fn alternative(self) -> stats::Alternative {
+        match self {
+            Direction::FirstSmaller => stats::Alternative::Less,
+            Direction::FirstLarger => stats::Alternative::Greater,
+        }
+    }
+}
+
 ///
-/// # Examples
+/// Outcome of a test of the difference between two means against a margin, as returned by
+/// [`Paired::test_margin`].
 ///
-/// ```
-/// # use stats_ci::*;
-/// // Gain in weight of 19 female rats between 28 and 84 days after birth.
-/// // 12 were fed on a high protein diet and 7 on a low protein diet.
-/// let data_high_protein = [
-///     134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
-/// ];
-/// let data_low_protein = [70., 118., 101., 85., 107., 132., 94.];
-/// let mut stats = comparison::Unpaired::default();
-/// stats.extend(&data_high_protein, &data_low_protein)?;
-/// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
-/// # Ok::<(),error::CIError>(())
-/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarginTestResult<T: Float> {
+    /// The one-sided confidence used for the test.
+    pub confidence: Confidence,
+
+    /// The bound of the one-sided interval of the difference (first minus second): the upper bound
+    /// for [`Direction::FirstSmaller`] and the lower bound for [`Direction::FirstLarger`].
+    pub bound: T,
+
+    /// The margin that the difference is tested against.
+    pub margin: T,
+
+    /// The amount by which the bound clears the margin in the direction of the test
+    /// (\( -bound - margin \) for [`Direction::FirstSmaller`] and \( bound - margin \) for
+    /// [`Direction::FirstLarger`]); positive when the test passes.
+    pub headroom: T,
+
+    /// Whether the difference exceeds the margin in the direction of the test.
+    pub passed: bool,
+}
+
 ///
-/// # References
+/// Summary of a comparison of paired samples, as returned by [`Paired::report`].
 ///
-/// * R. Jain, The Art of Computer Systems Performance Analysis, Wiley, 1991.
-/// * [Wikipedia article on Student's t-test](https://en.wikipedia.org/wiki/Student%27s_t-test#Independent_two-sample_t-test)
-/// * PennState. Stat 500. Lesson 7: Comparing Two Population Parameters. [Online](https://online.stat.psu.edu/stat500/lesson/7)
+/// The [`Display`](core::fmt::Display) implementation renders the report over two lines.
 ///
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Unpaired<T: Float> {
-    stats_a: mean::Arithmetic<T>,
-    stats_b: mean::Arithmetic<T>,
+pub struct PairedReport<T: Float> {
+    /// The number of pairs.
+    pub count: usize,
+
+    /// The mean of the differences (first minus second).
+    pub mean_difference: T,
+
+    /// The standard error of the mean difference.
+    pub sem: T,
+
+    /// The confidence interval of the mean difference.
+    pub difference: Interval<T>,
+
+    /// The confidence level used for the interval.
+    pub confidence: Confidence,
+
+    /// Whether the interval excludes zero.
+    pub significant: bool,
 }
 
-impl<T: Float> Default for Unpaired<T> {
-    fn default() -> Self {
-        Self {
-            stats_a: mean::Arithmetic::default(),
-            stats_b: mean::Arithmetic::default(),
-        }
+impl<T: Float + core::fmt::Display> core::fmt::Display for PairedReport<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(
+            f,
+            "pairs: n = {}, mean difference = {}, std. err. = {}",
+            self.count, self.mean_difference, self.sem
+        )?;
+        write_difference(f, &self.difference, self.confidence, self.significant)
     }
 }
 
-impl<T: Float> Unpaired<T> {
+impl<F: Float> core::ops::AddAssign for Paired<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.stats += rhs.stats;
+    }
+}
+
+impl<F: Float> utils::Accumulator for Paired<F> {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+///
+/// Pairs of durations, e.g., measured with [`std::time::Instant::elapsed`], collected as seconds.
+///
+#[cfg(feature = "std")]
+impl Paired<f64> {
     ///
-    /// Create a new instance of `Unpaired` from two statistics.
+    /// Add a pair of durations to the two samples, as numbers of seconds
+    /// (see [`std::time::Duration::as_secs_f64`]).
+    ///
+    /// The interval on the difference computed by [`Paired::ci_mean`] is then in signed seconds,
+    /// with negative values when the durations of the first sample are shorter.
     ///
     /// # Arguments
     ///
-    /// * `stats_a` - the statistics of the first sample
-    /// * `stats_b` - the statistics of the second sample
+    /// * `data_a` - the duration for the first sample
+    /// * `data_b` - the duration for the second sample
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Paired::append_pair`].
     ///
     /// # Examples
     ///
     /// ```
     /// # use stats_ci::*;
-    /// let stats_a = mean::Arithmetic::from_iter(&[1., 2., 3.])?;
-    /// let stats_b = mean::Arithmetic::from_iter(&[4., 5., 6.])?;
-    /// let stats = comparison::Unpaired::new(stats_a, stats_b);
+    /// use std::time::Duration;
+    /// let mut stats = comparison::Paired::default();
+    /// for (a, b) in [(10, 12), (11, 14), (9, 12), (12, 14)] {
+    ///     stats.append_pair_durations(Duration::from_millis(a), Duration::from_millis(b))?;
+    /// }
+    /// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+    /// // the first sample is about 1.6 to 3.4 milliseconds faster
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(ci, Interval::new(-0.003419, -0.001581)?, epsilon = 1e-6);
     /// # Ok::<(),error::CIError>(())
     /// ```
-    pub fn new(stats_a: mean::Arithmetic<T>, stats_b: mean::Arithmetic<T>) -> Self {
-        Self { stats_a, stats_b }
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_pair_durations(
+        &mut self,
+        data_a: std::time::Duration,
+        data_b: std::time::Duration,
+    ) -> CIResult<()> {
+        self.append_pair(data_a.as_secs_f64(), data_b.as_secs_f64())
     }
+}
 
+#[cfg(feature = "persist")]
+impl<F: Float> Paired<F> {
     ///
-    /// Create a new instance of `Unpaired` from two samples.
-    ///
-    /// # Arguments
-    ///
-    /// * `data_a` - the first sample
-    /// * `data_b` - the second sample
-    ///
-    /// # Errors
-    ///
-    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    /// Encode the state with the stable binary format of [`persist`] (see the module for the layout).
     ///
-    /// # Examples
+    /// Complexity: \\( O(1) \\)
     ///
+    /// # Example
     /// ```
-    /// # use stats_ci::*;
-    /// let stats = comparison::Unpaired::from_iter(&[1., 2., 3.], &[4., 5., 6.])?;
+    /// use stats_ci::*;
+    /// let stats = comparison::Paired::from_pairs([(1., 2.), (3., 5.), (4., 4.)])?;
+    /// assert_eq!(comparison::Paired::from_bytes(&stats.to_bytes())?, stats);
     /// # Ok::<(),error::CIError>(())
     /// ```
     ///
-    pub fn from_iter<Ia, Ib>(data_a: &Ia, data_b: &Ib) -> CIResult<Self>
-    where
-        for<'a> &'a Ia: IntoIterator<Item = &'a T>,
-        for<'b> &'b Ib: IntoIterator<Item = &'b T>,
-    {
-        let mut stats = Self::default();
-        stats.extend_a(data_a)?;
-        stats.extend_b(data_b)?;
-        Ok(stats)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = persist::Writer::new(persist::Kind::Paired);
+        self.stats.write_payload(&mut writer);
+        writer.finish()
     }
 
     ///
-    /// Return a reference to the statistics of the first sample.
+    /// Decode a state encoded with [`Paired::to_bytes`].
     ///
-    pub fn stats_a(&self) -> &mean::Arithmetic<T> {
-        &self.stats_a
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// Same as [`mean::Arithmetic::from_bytes`].
+    ///
+    pub fn from_bytes(bytes: &[u8]) -> CIResult<Self> {
+        let mut reader = persist::Reader::new(bytes, persist::Kind::Paired)?;
+        let stats = mean::Arithmetic::read_payload(&mut reader)?;
+        reader.finish()?;
+        Ok(Self { stats })
     }
 
     ///
-    /// Return a mutable reference to the statistics of the first sample.
+    /// Decode a state encoded with [`Paired::to_bytes`] and combine it with this one.
+    /// The state is left unchanged if decoding or combining fails.
     ///
-    /// # Examples
+    /// Complexity: \\( O(1) \\)
     ///
-    /// ```
-    /// # use stats_ci::*;
-    /// let mut stats = comparison::Unpaired::default();
-    /// stats.stats_a_mut().append(1.)?;
-    /// # Ok::<(),error::CIError>(())
-    /// ```
+    /// # Errors
     ///
-    pub fn stats_a_mut(&mut self) -> &mut mean::Arithmetic<T> {
-        &mut self.stats_a
+    /// Same as [`mean::Arithmetic::merge_from_bytes`].
+    ///
+    pub fn merge_from_bytes(&mut self, bytes: &[u8]) -> CIResult<()> {
+        self.stats = self.stats.try_add(Self::from_bytes(bytes)?.stats)?;
+        Ok(())
     }
+}
 
+///
+/// Structure to collect statistics on the ratios of two paired samples.
+///
+/// For paired observations \\( (a_i, b_i) \\) of strictly positive quantities (e.g., the running
+/// times of a benchmark after and before a change), the relevant comparison is often the ratio
+/// \\( a_i / b_i \\) rather than the difference. The ratios are summarized multiplicatively, that is,
+/// by their geometric mean, and the confidence interval is computed on the mean of the
+/// log-ratios \\( \ln a_i - \ln b_i \\) and then exponentiated back.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // running times (ms) of a benchmark before and after an optimization
+/// let before = [120., 95., 143., 88., 101., 132., 77., 110.];
+/// let after = [100.8, 81.7, 121.55, 73.04, 87.87, 112.2, 64.68, 94.6];
+///
+/// let mut stats = comparison::PairedRatio::default();
+/// stats.extend(&after, &before)?;
+/// let change = stats.ci_percent_change(Confidence::new_two_sided(0.95))?;
+/// println!(
+///     "after is {:.0}%-{:.0}% faster",
+///     -change.high_f(),
+///     -change.low_f()
+/// );
+/// // > after is 14%-16% faster
+/// # use approx::*;
+/// # assert_abs_diff_eq!(change, Interval::new(-16.0965, -13.9071)?, epsilon = 1e-4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * R. Jain, The Art of Computer Systems Performance Analysis, Wiley, 1991.
+/// * [Wikipedia article on geometric mean](https://en.wikipedia.org/wiki/Geometric_mean)
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairedRatio<T: Float> {
+    log_stats: mean::Arithmetic<T>,
+}
+
+impl<T: Float> PairedRatio<T> {
     ///
-    /// Return a reference to the statistics of the second sample.
+    /// Add a pair of observations to the two samples.
     ///
-    /// # Examples
+    /// # Arguments
     ///
-    /// ```
-    /// # use stats_ci::*;
-    /// # let mut stats = comparison::Unpaired::from_iter(&[1., 2. ,3.], &[4., 5., 6.])?;
-    /// let mean_b = stats.stats_b().sample_mean();
-    /// # Ok::<(),error::CIError>(())
-    /// ```
+    /// * `data_a` - the observation for the first sample (numerator of the ratio)
+    /// * `data_b` - the observation for the second sample (denominator of the ratio)
     ///
-    pub fn stats_b(&self) -> &mean::Arithmetic<T> {
-        &self.stats_b
-    }
-
+    /// # Errors
     ///
-    /// Return a mutable reference to the statistics of the second sample.
+    /// * [`CIError::NonPositiveValueAt`] - if either observation is not strictly positive,
+    ///   with the index of the pair (the number of pairs appended before it)
     ///
     /// # Examples
     ///
     /// ```
     /// # use stats_ci::*;
-    /// let mut stats = comparison::Unpaired::default();
-    /// stats.stats_b_mut().append(1.)?;
+    /// let mut stats = comparison::PairedRatio::default();
+    /// stats.append_pair(3., 2.)?;
+    /// assert_eq!(stats.sample_count(), 1);
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.sample_ratio(), 1.5, epsilon = 1e-12);
+    /// assert!(stats.append_pair(0., 2.).is_err());
     /// # Ok::<(),error::CIError>(())
     /// ```
-    ///
-    pub fn stats_b_mut(&mut self) -> &mut mean::Arithmetic<T> {
-        &mut self.stats_b
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_pair(&mut self, data_a: T, data_b: T) -> CIResult<()> {
+        for x in [data_a, data_b] {
+            if x <= T::zero() || x.is_nan() {
+                return Err(CIError::NonPositiveValueAt {
+                    index: self.sample_count(),
+                    value: x.to_f64().unwrap_or(f64::NAN),
+                });
+            }
+        }
+        self.log_stats.append(data_a.ln() - data_b.ln())
     }
 
     ///
-    /// Append a pair of observations to the two samples.
+    /// Append multiple pairs of observations to the two samples.
     ///
     /// # Arguments
     ///
-    /// * `data_a` - the new data for the first sample
-    /// * `data_b` - the new data for the second sample
+    /// * `iter` - an iterable collection of tuples to add to the data
     ///
     /// # Errors
     ///
-    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    /// * [`CIError::NonPositiveValueAt`] - if some observation is not strictly positive;
+    ///   the pairs before it are kept
     ///
-    pub fn append_pair(&mut self, data_a: T, data_b: T) -> CIResult<()> {
-        self.append_a(data_a)?;
-        self.append_b(data_b)?;
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::PairedRatio::default();
+    /// stats.extend_tuple(&[(2., 1.), (8., 2.)])?;
+    /// # assert_eq!(stats.sample_count(), 2);
+    /// # use approx::*;
+    /// # assert_abs_diff_eq!(stats.sample_ratio(), 8f64.sqrt(), epsilon = 1e-12);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_tuple<I: ?Sized>(&mut self, iter: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (T, T)>,
+    {
+        for &(x, y) in iter.into_iter() {
+            self.append_pair(x, y)?;
+        }
         Ok(())
     }
 
     ///
-    /// Append a single observation to the first sample.
+    /// Append pairs of observations consumed from an iterator, such as an iterator adapter.
     ///
     /// # Arguments
     ///
-    /// * `data_a` - the new data for the first sample
-    ///
-    pub fn append_a(&mut self, data_a: T) -> CIResult<()> {
-        self.stats_a.append(data_a)
-    }
-
-    ///
-    /// Append a single observation to the second sample.
+    /// * `iter` - the pairs of observations to add to the data
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `data_b` - the new data for the second sample
+    /// * [`CIError::NonPositiveValueAt`] - if some observation is not strictly positive;
+    ///   the pairs before it are kept
     ///
-    pub fn append_b(&mut self, data_b: T) -> CIResult<()> {
-        self.stats_b.append(data_b)
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_pairs<I>(&mut self, iter: I) -> CIResult<()>
+    where
+        I: IntoIterator<Item = (T, T)>,
+    {
+        for (x, y) in iter {
+            self.append_pair(x, y)?;
+        }
+        Ok(())
     }
 
     ///
-    /// Append observations to the first sample.
-    ///
-    /// # Arguments
+    /// Create a new state from pairs of observations consumed from an iterator.
     ///
-    /// * `data_a` - the new data for the first sample
+    /// This is a shortcut for [`Default::default`] and [`PairedRatio::extend_pairs`].
     ///
     /// # Errors
     ///
-    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    /// * [`CIError::NonPositiveValueAt`] - if some observation is not strictly positive
     ///
     /// # Examples
     ///
     /// ```
     /// # use stats_ci::*;
-    /// let mut stats = comparison::Unpaired::default();
-    /// stats.extend_a(&[1., 2., 3.])?;
-    /// # assert_eq!(stats.stats_a().sample_count(), 3);
-    /// # assert_eq!(stats.stats_a().sample_mean(), 2.);
+    /// let stats = comparison::PairedRatio::from_pairs((1..=10).map(|i| (i as f64 * 2., i as f64)))?;
+    /// assert_eq!(stats.sample_count(), 10);
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.sample_ratio(), 2., epsilon = 1e-12);
     /// # Ok::<(),error::CIError>(())
     /// ```
-    pub fn extend_a<I>(&mut self, data_a: &I) -> CIResult<()>
+    pub fn from_pairs<I>(iter: I) -> CIResult<Self>
     where
-        for<'a> &'a I: IntoIterator<Item = &'a T>,
+        I: IntoIterator<Item = (T, T)>,
     {
-        self.stats_a.extend(data_a)
+        let mut stats = Self::default();
+        stats.extend_pairs(iter)?;
+        Ok(stats)
     }
 
     ///
-    /// Append observations to the second sample.
+    /// Append multiple observations to the two samples.
     ///
     /// # Arguments
     ///
-    /// * `data_b` - the new data for the second sample
+    /// * `data_a` - an iterable collection of observations for the first sample (numerators)
+    /// * `data_b` - an iterable collection of observations for the second sample (denominators)
     ///
     /// # Errors
     ///
-    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    /// * [`CIError::DifferentSampleSizes`] - if the two iterables have different lengths
+    /// * [`CIError::NonPositiveValueAt`] - if some observation is not strictly positive
     ///
     /// # Examples
     ///
     /// ```
     /// # use stats_ci::*;
-    /// let mut stats = comparison::Unpaired::default();
-    /// stats.extend_b(&[1., 2., 3.])?;
-    /// # assert_eq!(stats.stats_b().sample_count(), 3);
-    /// # assert_eq!(stats.stats_b().sample_mean(), 2.);
+    /// let mut stats = comparison::PairedRatio::default();
+    /// stats.extend(&[2., 8.], &[1., 4.])?;
+    /// # assert_eq!(stats.sample_count(), 2);
+    /// # use approx::*;
+    /// # assert_abs_diff_eq!(stats.sample_ratio(), 2., epsilon = 1e-12);
     /// # Ok::<(),error::CIError>(())
     /// ```
-    pub fn extend_b<I>(&mut self, data_b: &I) -> CIResult<()>
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend<I1: ?Sized, I2: ?Sized>(&mut self, data_a: &I1, data_b: &I2) -> CIResult<()>
     where
-        for<'a> &'a I: IntoIterator<Item = &'a T>,
+        for<'a> &'a I1: IntoIterator<Item = &'a T>,
+        for<'b> &'b I2: IntoIterator<Item = &'b T>,
     {
-        self.stats_b.extend(data_b)
+        self.extend_iter(data_a.into_iter().copied(), data_b.into_iter().copied())
     }
 
     ///
-    /// Extend the two samples with new data.
+    /// Append observations consumed from two iterators, such as iterator adapters, to the two samples.
+    ///
+    /// Unlike [`PairedRatio::extend`], the observations are taken by value, which avoids collecting
+    /// them into temporary collections.
     ///
     /// # Arguments
     ///
-    /// * `data_a` - the new data for the first sample
-    /// * `data_b` - the new data for the second sample
+    /// * `data_a` - the observations for the first sample
+    /// * `data_b` - the observations for the second sample
     ///
     /// # Errors
     ///
-    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    /// * [`CIError::DifferentSampleSizes`] - if the two iterators have different lengths
+    /// * [`CIError::NonPositiveValueAt`] - if some observation is not strictly positive
     ///
     /// # Examples
     ///
     /// ```
     /// # use stats_ci::*;
-    /// let mut stats = comparison::Unpaired::default();
-    /// stats.extend(&[1., 2., 3.], &[4., 5., 6.])?;
-    /// # assert_eq!(stats.stats_a().sample_count(), 3);
-    /// # assert_eq!(stats.stats_a().sample_mean(), 2.);
-    /// # assert_eq!(stats.stats_b().sample_count(), 3);
-    /// # assert_eq!(stats.stats_b().sample_mean(), 5.);
+    /// let runs = 1..=5;
+    /// let mut stats = comparison::PairedRatio::default();
+    /// stats.extend_iter(runs.clone().map(|i| i as f64 * 1.1), runs.map(|i| i as f64))?;
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.sample_ratio(), 1.1, epsilon = 1e-12);
     /// # Ok::<(),error::CIError>(())
     /// ```
-    pub fn extend<Ia, Ib>(&mut self, data_a: &Ia, data_b: &Ib) -> CIResult<()>
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_iter<Ia, Ib>(&mut self, data_a: Ia, data_b: Ib) -> CIResult<()>
     where
-        for<'a> &'a Ia: IntoIterator<Item = &'a T>,
-        for<'b> &'b Ib: IntoIterator<Item = &'b T>,
+        Ia: IntoIterator<Item = T>,
+        Ib: IntoIterator<Item = T>,
     {
-        self.stats_a.extend(data_a)?;
-        self.stats_b.extend(data_b)?;
-        Ok(())
-    }
-
-    ///
-    /// Compute the confidence interval of the difference between the means of the two samples.
+        let mut data_a = data_a.into_iter();
+        let mut data_b = data_b.into_iter();
+        let mut count = 0;
+        loop {
+            match (data_a.next(), data_b.next()) {
+                (Some(x), Some(y)) => {
+                    count += 1;
+                    self.append_pair(x, y)?
+                }
+                (None, None) => return Ok(()),
+                // returns error if iterables have different lengths
+                (None, _) => {
+                    return Err(CIError::DifferentSampleSizes {
+                        size_a: count,
+                        size_b: count + 1 + data_b.count(),
+                    })
+                }
+                (_, None) => {
+                    return Err(CIError::DifferentSampleSizes {
+                        size_a: count + 1 + data_a.count(),
+                        size_b: count,
+                    })
+                }
+            }
+        }
+    }
+
     ///
-    /// # Arguments
+    /// Return the geometric mean of the ratios \\( a_i / b_i \\).
     ///
-    /// * `confidence` - the confidence level
+    /// # Examples
     ///
-    /// # Returns
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::PairedRatio::default();
+    /// stats.extend(&[1., 8.], &[2., 4.])?;
+    /// assert_eq!(stats.sample_ratio(), 1.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn sample_ratio(&self) -> T {
+        self.log_stats.sample_mean().exp()
+    }
+
     ///
-    /// The confidence interval of the difference as a result.
+    /// Return the number of sample pairs.
     ///
-    /// # Errors
+    pub fn sample_count(&self) -> usize {
+        self.log_stats.sample_count()
+    }
+
     ///
-    /// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+    /// Return the statistics of the log-ratios \\( \ln a_i - \ln b_i \\).
+    ///
+    pub fn log_stats(&self) -> &mean::Arithmetic<T> {
+        &self.log_stats
+    }
+
+    ///
+    /// Return the confidence interval of the geometric mean of the ratios.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Notes
+    ///
+    /// If the interval includes one, the ratio is not significant.
+    /// If the interval is strictly above (resp. below) one, the first sample is significantly
+    /// greater (resp. smaller) than the second sample, multiplicatively.
     ///
     /// # Examples
     ///
     /// ```
     /// # use stats_ci::*;
-    /// let confidence = Confidence::new_two_sided(0.95);
-    /// let mut stats = comparison::Unpaired::default();
-    /// stats.extend(&[1., 2., 3.], &[4., 5., 6.])?;
-    /// let ci = stats.ci_mean(confidence)?;
+    /// let mut stats = comparison::PairedRatio::default();
+    /// stats.extend(&[2., 4., 6.], &[1., 2., 3.])?;
+    /// let ci = stats.ci_ratio(Confidence::new_two_sided(0.95))?;
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(ci, Interval::new(2., 2.)?, epsilon = 1e-12);
     /// # Ok::<(),error::CIError>(())
     /// ```
+    pub fn ci_ratio(&self, confidence: Confidence) -> CIResult<Interval<T>> {
+        let log_ci = self.log_stats.ci_mean(confidence)?;
+        let (lo, hi) = (log_ci.low_f().exp(), log_ci.high_f().exp());
+        Interval::from_finite_bounds_for(confidence, lo, hi)
+    }
+
     ///
-    /// # Notes
+    /// Return the confidence interval of the percent change of the first sample relative to the
+    /// second sample, that is \\( (r - 1) \cdot 100 \\) where \\( r \\) is the geometric mean
+    /// of the ratios (see [`Self::ci_ratio`]).
     ///
-    /// If the interval includes zero, the difference is not significant.
-    /// If the interval is strictly positive (resp. negative), the mean of the first sample is significantly
-    /// greater (resp. smaller) than the mean of the second sample.
+    /// # Arguments
     ///
-    /// # References
+    /// * `confidence` - the confidence level
     ///
-    /// * R. Jain, The Art of Computer Systems Performance Analysis, Wiley, 1991.
-    /// * [Wikipedia article on Student's t-test](https://en.wikipedia.org/wiki/Student%27s_t-test#Independent_two-sample_t-test)
-    /// * PennState. Stat 500. Lesson 7: Comparing Two Population Parameters. [Online](https://online.stat.psu.edu/stat500/lesson/7)
+    /// # Examples
     ///
-    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<T>> {
-        let stats_a = self.stats_a;
-        let stats_b = self.stats_b;
-
-        let n_a = T::from(stats_a.sample_count()).convert("stats_a.sample_count")?;
-        let n_b = T::from(stats_b.sample_count()).convert("stats_b.sample_count")?;
-        let mean_a = stats_a.sample_mean();
-        let mean_b = stats_b.sample_mean();
-        let std_dev_a = stats_a.sample_std_dev();
-        let std_dev_b = stats_b.sample_std_dev();
-
-        let mean_difference = mean_a - mean_b;
-        let sa2_na = // $s_a^2 / n_a$
-            std_dev_a * std_dev_a / n_a;
-        let sb2_nb = // $s_b^2 / n_b$
-            std_dev_b * std_dev_b / n_b;
-        let sum_s2_n = // $s_a^2 / n_a + s_b^2 / n_b$
-            sa2_na + sb2_nb;
-        let std_err_mean = // $\sqrt{s_a^2 / n_a + s_b^2 / n_b}$
-            sum_s2_n.sqrt();
-        let effective_dof = // $ \frac{ (s_a^a / n_a + s_b^2 / n_b)^2 }{ \frac{1}{n_a+1} \left(\frac{s_a^2}{n_a}\right)^2 + \frac{1}{n_b+1} \left(\frac{s_b^2}{n_b}\right)^2 } - 2$
-            sum_s2_n * sum_s2_n
-                / (sa2_na * sa2_na / (n_a + T::one())
-                    + sb2_nb * sb2_nb / (n_b + T::one())) - T::one() - T::one();
-
-        let (lo, hi) = stats::interval_bounds(
-            confidence,
-            mean_difference.try_f64("mean_difference")?,
-            std_err_mean.try_f64("std_err_mean")?,
-            effective_dof.try_f64("effective_dof")?,
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let mut stats = comparison::PairedRatio::default();
+    /// stats.extend(&[1.1, 2.2, 3.3], &[1., 2., 3.])?;
+    /// let ci = stats.ci_percent_change(Confidence::new_two_sided(0.95))?;
+    /// assert_abs_diff_eq!(ci, Interval::new(10., 10.)?, epsilon = 1e-10);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn ci_percent_change(&self, confidence: Confidence) -> CIResult<Interval<T>> {
+        let hundred = T::from(100).convert("100")?;
+        let ratio_ci = self.ci_ratio(confidence)?;
+        let (lo, hi) = (
+            (ratio_ci.low_f() - T::one()) * hundred,
+            (ratio_ci.high_f() - T::one()) * hundred,
         );
-        let lo = T::from(lo).convert("lo")?;
-        let hi = T::from(hi).convert("hi")?;
-        match confidence {
-            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
-            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
-            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
-        }
+        Interval::from_finite_bounds_for(confidence, lo, hi)
     }
 
     ///
-    /// Compute the confidence interval of the difference between the means of the two samples.
+    /// Test whether the ratio is significantly different from one, based on the confidence
+    /// interval computed by [`Self::ci_ratio`].
     ///
-    /// # Arguments
+    /// See [`Interval::significance`] for details (the interval on the log-ratios is compared to zero).
     ///
-    /// * `confidence` - the confidence level
-    /// * `data_a` - the first sample
-    /// * `data_b` - the second sample
+    /// # Examples
     ///
-    /// # Returns
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::PairedRatio::default();
+    /// stats.extend(&[1.9, 2.1, 2.0, 1.8], &[1., 1.1, 0.9, 1.])?;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// assert_eq!(stats.significance(confidence)?, Significance::PositivelySignificant);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn significance(&self, confidence: Confidence) -> CIResult<Significance> {
+        Ok(self.log_stats.ci_mean(confidence)?.significance())
+    }
+
     ///
-    /// The confidence interval of the difference as a result.
+    /// Compute the confidence interval of the geometric mean of the ratios of two paired samples.
     ///
     /// # Errors
     ///
-    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
-    /// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
-    ///
-    /// # Notes
-    ///
-    /// If the interval includes zero, the difference is not significant.
-    /// If the interval is strictly positive (resp. negative), the mean of the first sample is significantly
-    /// greater (resp. smaller) than the mean of the second sample.
-    ///
-    /// This function provides a simple interface to obtain the confidence interval with a single call, when
-    /// the samples are known a priori and there is no need to include additional observations,
-    /// obtain the confidence intervals for other levels or access the sample statistics. For more refined
-    /// use cases, it is recommended to use [`Unpaired::ci_mean`] instead.
+    /// * [`CIError::DifferentSampleSizes`] - if the two samples do not have the same length
+    /// * [`CIError::NonPositiveValueAt`] - if some observation is not strictly positive
     ///
     /// # Examples
     ///
     /// ```
     /// # use stats_ci::*;
-    /// let data_a = [1., 2., 3.];
-    /// let data_b = [4., 5., 6.];
-    /// let ci = comparison::Unpaired::ci(Confidence::new_two_sided(0.95), &data_a, &data_b)?;
+    /// let after = [9.5, 10.1, 8.7, 9.9];
+    /// let before = [10.3, 11., 9.4, 10.5];
+    /// let ci = comparison::PairedRatio::ci(Confidence::new_two_sided(0.95), &after, &before)?;
+    /// assert!(ci.high_f() < 1.);
     /// # Ok::<(),error::CIError>(())
     /// ```
     ///
-    /// # References
-    ///
-    /// * R. Jain, The Art of Computer Systems Performance Analysis, Wiley, 1991.
-    /// * [Wikipedia article on Student's t-test](https://en.wikipedia.org/wiki/Student%27s_t-test#Independent_two-sample_t-test)
-    /// * PennState. Stat 500. Lesson 7: Comparing Two Population Parameters. [Online](https://online.stat.psu.edu/stat500/lesson/7)
-    ///
     pub fn ci<Ia, Ib>(confidence: Confidence, data_a: &Ia, data_b: &Ib) -> CIResult<Interval<T>>
     where
         for<'a> &'a Ia: IntoIterator<Item = &'a T>,
@@ -881,100 +1564,2638 @@ impl<T: Float> Unpaired<T> {
     {
         let mut stats = Self::default();
         stats.extend(data_a, data_b)?;
-        stats.ci_mean(confidence)
+        stats.ci_ratio(confidence)
     }
 }
 
-impl<F: Float> core::ops::Add for Unpaired<F> {
+impl<T: Float> Default for PairedRatio<T> {
+    fn default() -> Self {
+        Self {
+            log_stats: mean::Arithmetic::default(),
+        }
+    }
+}
+
+///
+/// Merge the statistics of two disjoint sets of pairs, as if all pairs had been appended to a
+/// single instance.
+///
+impl<F: Float> core::ops::Add for PairedRatio<F> {
     type Output = Self;
 
     #[inline]
     fn add(self, rhs: Self) -> Self::Output {
         Self {
-            stats_a: self.stats_a + rhs.stats_a,
-            stats_b: self.stats_b + rhs.stats_b,
+            log_stats: self.log_stats + rhs.log_stats,
         }
     }
 }
 
-impl<F: Float> core::ops::AddAssign for Unpaired<F> {
+impl<F: Float> core::ops::AddAssign for PairedRatio<F> {
     #[inline]
     fn add_assign(&mut self, rhs: Self) {
-        self.stats_a += rhs.stats_a;
-        self.stats_b += rhs.stats_b;
+        self.log_stats += rhs.log_stats;
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use approx::*;
-
-    #[test]
-    fn test_paired() {
-        {
-            // Case 1
-            // based on example from https://online.stat.psu.edu/stat500/lesson/7/7.3/7.3.2
-
-            // Zinc concentration in water samples from a river
-            let data_bottom_water = [
-                0.430, 0.266, 0.567, 0.531, 0.707, 0.716, 0.651, 0.589, 0.469, 0.723,
-            ];
-            let data_surface_water = [
-                0.415, 0.238, 0.390, 0.410, 0.605, 0.609, 0.632, 0.523, 0.411, 0.612,
-            ];
+impl<F: Float> utils::Accumulator for PairedRatio<F> {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
 
-            let ci = Paired::ci(
-                Confidence::new_two_sided(0.95),
-                &data_bottom_water,
-                &data_surface_water,
-            )
-            .unwrap();
+///
+/// Structure to collect paired observations and flag the pairs whose difference is an outlier.
+///
+/// Unlike [`Paired`], which only keeps summary statistics, the differences \\( a_i - b_i \\) are
+/// buffered in the order in which the pairs are appended, so that a single wild pair (e.g., caused
+/// by a garbage collection pause or thermal throttling) can be identified and optionally excluded
+/// from the interval on the mean difference.
+///
+/// A difference is an outlier when it lies more than \\( k \cdot IQR \\) below the first quartile
+/// or above the third quartile of the differences (Tukey's fences), where \\( IQR \\) is the
+/// interquartile range and \\( k \\) is typically 1.5 (outliers) or 3 (far outliers).
+/// The exclusion is never silent: [`PairedRobust::ci_mean_excluding_outliers`] reports the number
+/// of pairs that were dropped together with the interval.
+///
+/// Complexity: \\( O(n) \\) memory for \\( n \\) pairs.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data_a = [11.1, 12.9, 12.0, 10.7, 11.3, 12.55, 10.95, 21.0, 10.1, 11.9];
+/// let data_b = [10.0, 12.0, 11.0, 9.5, 10.5, 11.5, 10.0, 12.0, 9.0, 11.0];
+///
+/// let mut stats = comparison::PairedRobust::default();
+/// stats.extend(&data_a, &data_b)?;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let outliers = stats.outlier_pairs(1.5)?;
+/// assert_eq!(outliers.len(), 1);
+/// assert_eq!(outliers[0].0, 7);
+/// let (ci, excluded) = stats.ci_mean_excluding_outliers(confidence, 1.5)?;
+/// assert_eq!(excluded, 1);
+/// assert!(ci.width() < stats.ci_mean(confidence)?.width());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * J. W. Tukey, Exploratory Data Analysis, Addison-Wesley, 1977.
+/// * [Wikipedia article on outliers (Tukey's fences)](https://en.wikipedia.org/wiki/Outlier#Tukey's_fences)
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairedRobust<T: Float> {
+    differences: Vec<T>,
+}
 
-            println!("ci = {} (ref: )", ci);
-            println!("reference: (0.04299, 0.11781)");
-            assert_abs_diff_eq!(ci, Interval::new(0.04299, 0.11781).unwrap(), epsilon = 1e-4);
+impl<T: Float> Default for PairedRobust<T> {
+    fn default() -> Self {
+        Self {
+            differences: Vec::new(),
         }
-        {
-            // Case 2
-            // based on example from https://www.khanacademy.org/math/ap-statistics/xfb5d8e68:inference-quantitative-means/one-sample-t-interval-mean/a/one-sample-t-interval-paired-data
-
-            let data_watch_a = [9.8, 9.8, 10.1, 10.1, 10.2];
-            let data_watch_b = [10.1, 10., 10.2, 9.9, 10.1];
-            let ci = Paired::ci(
-                Confidence::new_two_sided(0.95),
-                &data_watch_b,
-                &data_watch_a,
-            )
-            .unwrap();
-
-            println!("ci = {}", ci);
-            println!("reference: (-0.20, 0.32)");
-            assert_abs_diff_eq!(ci, Interval::new(-0.20, 0.32).unwrap(), epsilon = 1e-2);
+    }
+}
 
-            let data_pre = [140., 152., 153., 159., 150., 146.];
-            let data_post = [150., 159., 170., 164., 148., 166.];
-            let ci = Paired::ci(Confidence::new_two_sided(0.95), &data_post, &data_pre).unwrap();
+impl<T: Float> utils::Accumulator for PairedRobust<T> {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
 
-            println!("ci = {}", ci);
-            println!("reference: (1.03,17.97)");
-            assert_abs_diff_eq!(ci, Interval::new(1.03, 17.97).unwrap(), epsilon = 1e-2);
+impl<T: Float> PairedRobust<T> {
+    ///
+    /// Add a pair of observations to the two samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the observation for the first sample
+    /// * `data_b` - the observation for the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if the difference between the observations is NaN
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::PairedRobust::default();
+    /// stats.append_pair(3., 2.)?;
+    /// assert_eq!(stats.differences(), &[1.]);
+    /// assert!(stats.append_pair(f64::NAN, 2.).is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_pair(&mut self, data_a: T, data_b: T) -> CIResult<()> {
+        let difference = data_a - data_b;
+        if difference.is_nan() {
+            return Err(CIError::InvalidInputData);
         }
+        self.differences.push(difference);
+        Ok(())
     }
 
-    #[test]
-    fn test_unpaired() {
-        // based on example from https://www.statsdirect.co.uk/help/parametric_methods/utt.htm
-        // itself based on Armitage P, Berry G. Statistical Methods in Medical Research (3rd edition). Blackwell 1994.
-        // Consider the gain in weight of 19 female rats between 28 and 84 days after birth. 12 were fed on a high protein diet and 7 on a low protein diet.
-        let data_high_protein = [
-            134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
-        ];
-        let data_low_protein = [70., 118., 101., 85., 107., 132., 94.];
-        let ci = Unpaired::ci(
-            Confidence::new_two_sided(0.95),
-            &data_high_protein,
-            &data_low_protein,
+    ///
+    /// Create a new state from pairs of observations consumed from an iterator.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if the difference of some pair is NaN
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = comparison::PairedRobust::from_pairs((1..=10).map(|i| (i as f64 + 1., i as f64)))?;
+    /// assert_eq!(stats.sample_count(), 10);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn from_pairs<I>(iter: I) -> CIResult<Self>
+    where
+        I: IntoIterator<Item = (T, T)>,
+    {
+        let mut stats = Self::default();
+        for (x, y) in iter {
+            stats.append_pair(x, y)?;
+        }
+        Ok(stats)
+    }
+
+    ///
+    /// Append multiple observations to the two samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - an iterable collection of observations for the first sample
+    /// * `data_b` - an iterable collection of observations for the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentSampleSizes`] - if the two iterables have different lengths
+    /// * [`CIError::InvalidInputData`] - if the difference of some pair is NaN
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend<I1: ?Sized, I2: ?Sized>(&mut self, data_a: &I1, data_b: &I2) -> CIResult<()>
+    where
+        for<'a> &'a I1: IntoIterator<Item = &'a T>,
+        for<'b> &'b I2: IntoIterator<Item = &'b T>,
+    {
+        self.extend_iter(data_a.into_iter().copied(), data_b.into_iter().copied())
+    }
+
+    ///
+    /// Append observations consumed from two iterators, such as iterator adapters, to the two samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the observations for the first sample
+    /// * `data_b` - the observations for the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentSampleSizes`] - if the two iterators have different lengths
+    /// * [`CIError::InvalidInputData`] - if the difference of some pair is NaN
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_iter<Ia, Ib>(&mut self, data_a: Ia, data_b: Ib) -> CIResult<()>
+    where
+        Ia: IntoIterator<Item = T>,
+        Ib: IntoIterator<Item = T>,
+    {
+        let mut data_a = data_a.into_iter();
+        let mut data_b = data_b.into_iter();
+        let mut count = 0;
+        loop {
+            match (data_a.next(), data_b.next()) {
+                (Some(x), Some(y)) => {
+                    count += 1;
+                    self.append_pair(x, y)?
+                }
+                (None, None) => return Ok(()),
+                // returns error if iterables have different lengths
+                (None, _) => {
+                    return Err(CIError::DifferentSampleSizes {
+                        size_a: count,
+                        size_b: count + 1 + data_b.count(),
+                    })
+                }
+                (_, None) => {
+                    return Err(CIError::DifferentSampleSizes {
+                        size_a: count + 1 + data_a.count(),
+                        size_b: count,
+                    })
+                }
+            }
+        }
+    }
+
+    ///
+    /// Return the number of sample pairs.
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.differences.len()
+    }
+
+    ///
+    /// Return the differences \\( a_i - b_i \\) of the pairs, in the order in which they were appended.
+    ///
+    pub fn differences(&self) -> &[T] {
+        &self.differences
+    }
+
+    ///
+    /// Return the summary statistics of all pairs, as collected by [`Paired`].
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    pub fn paired(&self) -> CIResult<Paired<T>> {
+        Ok(Paired {
+            stats: mean::Arithmetic::from_iter(&self.differences)?,
+        })
+    }
+
+    ///
+    /// Return the confidence interval of the mean difference over all pairs, including outliers
+    /// (see [`Paired::ci_mean`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no pairs
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<T>> {
+        self.paired()?.ci_mean(confidence)
+    }
+
+    ///
+    /// List the pairs whose difference is an outlier, i.e., lies more than `k_iqr` times the
+    /// interquartile range below the first quartile or above the third quartile of the differences.
+    ///
+    /// The quartiles are the sample quantiles of the differences as indexed by [`quantile::Stats::index`].
+    ///
+    /// Complexity: \\( O(n \log n) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `k_iqr` - the multiple of the interquartile range beyond which a difference is an outlier
+    ///   (must be positive and finite), typically 1.5
+    ///
+    /// # Output
+    ///
+    /// * The index of each outlier pair (in the order in which the pairs were appended) and its difference
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidOutlierFactor`] - if `k_iqr` is not positive and finite
+    /// * [`CIError::TooFewSamples`] - if there are no pairs
+    /// * [`CIError::FloatConversionError`] - if `k_iqr` cannot be converted to `T`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::PairedRobust::default();
+    /// stats.extend(&[2., 3., 4., 25., 6., 7.], &[1., 2., 3., 4., 5., 6.])?;
+    /// assert_eq!(stats.outlier_pairs(1.5)?, vec![(3, 21.)]);
+    /// assert!(stats.outlier_pairs(0.).is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn outlier_pairs(&self, k_iqr: f64) -> CIResult<Vec<(usize, T)>> {
+        let (low, high) = self.fences(k_iqr)?;
+        Ok(self
+            .differences
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(_, x)| x < low || high < x)
+            .collect())
+    }
+
+    ///
+    /// Return the confidence interval of the mean difference over the pairs that are not
+    /// outliers (see [`PairedRobust::outlier_pairs`]), together with the number of pairs excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `k_iqr` - the multiple of the interquartile range beyond which a difference is an outlier
+    ///   (must be positive and finite), typically 1.5
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidOutlierFactor`] - if `k_iqr` is not positive and finite
+    /// * [`CIError::TooFewSamples`] - if there are no pairs
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::PairedRobust::default();
+    /// stats.extend(&[2., 3., 4., 25., 6., 7.], &[1., 2., 3., 4., 5., 6.])?;
+    /// let (ci, excluded) = stats.ci_mean_excluding_outliers(Confidence::new_two_sided(0.95), 1.5)?;
+    /// assert_eq!(excluded, 1);
+    /// assert_eq!(ci, Interval::new(1., 1.)?);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn ci_mean_excluding_outliers(
+        &self,
+        confidence: Confidence,
+        k_iqr: f64,
+    ) -> CIResult<(Interval<T>, usize)> {
+        let (low, high) = self.fences(k_iqr)?;
+        let mut stats = mean::Arithmetic::new();
+        stats.extend_iter(
+            self.differences
+                .iter()
+                .copied()
+                .filter(|&x| low <= x && x <= high),
+        )?;
+        let excluded = self.sample_count() - stats.sample_count();
+        Ok((stats.ci_mean(confidence)?, excluded))
+    }
+
+    ///
+    /// Tukey's fences \\( [Q_1 - k \cdot IQR, Q_3 + k \cdot IQR] \\) of the differences.
+    ///
+    fn fences(&self, k_iqr: f64) -> CIResult<(T, T)> {
+        if !k_iqr.is_finite() || k_iqr <= 0. {
+            return Err(CIError::InvalidOutlierFactor(k_iqr));
+        }
+        let k_iqr = T::from(k_iqr).convert("k_iqr")?;
+        let mut sorted = self.differences.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let index = quantile::Stats::new(sorted.len());
+        let first_quartile = sorted[index.index(0.25)?];
+        let third_quartile = sorted[index.index(0.75)?];
+        let span = k_iqr * (third_quartile - first_quartile);
+        Ok((first_quartile - span, third_quartile + span))
+    }
+}
+
+///
+/// Structure to collect statistics on two unpaired samples.
+///
+/// Given two independent samples, the goal is to compute the confidence interval
+/// of the difference between their means.
+/// Unlike with paired observations ([`Paired`]), the two samples do not have to
+/// have the same length.
+/// However, comparing with unpaired observations typically requires considerably
+/// more observations to reach the same degree of statistical accuracy. This is
+/// why paired observations are preferred when the circumstances allow.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // Gain in weight of 19 female rats between 28 and 84 days after birth.
+/// // 12 were fed on a high protein diet and 7 on a low protein diet.
+/// let data_high_protein = [
+///     134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
+/// ];
+/// let data_low_protein = [70., 118., 101., 85., 107., 132., 94.];
+/// let mut stats = comparison::Unpaired::default();
+/// stats.extend(&data_high_protein, &data_low_protein)?;
+/// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * R. Jain, The Art of Computer Systems Performance Analysis, Wiley, 1991.
+/// * [Wikipedia article on Student's t-test](https://en.wikipedia.org/wiki/Student%27s_t-test#Independent_two-sample_t-test)
+/// * PennState. Stat 500. Lesson 7: Comparing Two Population Parameters. [Online](https://online.stat.psu.edu/stat500/lesson/7)
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Unpaired<T: Float> {
+    stats_a: mean::Arithmetic<T>,
+    stats_b: mean::Arithmetic<T>,
+}
+
+impl<T: Float> Default for Unpaired<T> {
+    fn default() -> Self {
+        Self {
+            stats_a: mean::Arithmetic::default(),
+            stats_b: mean::Arithmetic::default(),
+        }
+    }
+}
+
+impl<T: Float> Unpaired<T> {
+    ///
+    /// Create a new instance of `Unpaired` from two statistics.
+    ///
+    /// # Arguments
+    ///
+    /// * `stats_a` - the statistics of the first sample
+    /// * `stats_b` - the statistics of the second sample
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats_a = mean::Arithmetic::from_iter(&[1., 2., 3.])?;
+    /// let stats_b = mean::Arithmetic::from_iter(&[4., 5., 6.])?;
+    /// let stats = comparison::Unpaired::new(stats_a, stats_b);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn new(stats_a: mean::Arithmetic<T>, stats_b: mean::Arithmetic<T>) -> Self {
+        Self { stats_a, stats_b }
+    }
+
+    ///
+    /// Create a new instance of `Unpaired` from two samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the first sample
+    /// * `data_b` - the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = comparison::Unpaired::from_iter(&[1., 2., 3.], &[4., 5., 6.])?;
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn from_iter<Ia: ?Sized, Ib: ?Sized>(data_a: &Ia, data_b: &Ib) -> CIResult<Self>
+    where
+        for<'a> &'a Ia: IntoIterator<Item = &'a T>,
+        for<'b> &'b Ib: IntoIterator<Item = &'b T>,
+    {
+        let mut stats = Self::default();
+        stats.extend_a(data_a)?;
+        stats.extend_b(data_b)?;
+        Ok(stats)
+    }
+
+    ///
+    /// Create a new instance of `Unpaired` from two samples, accumulating each sample in parallel
+    /// by fixed-size chunks merged with `+` (see [`mean::Arithmetic::from_chunks_deterministic`]).
+    /// The two samples are also processed concurrently.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the first sample
+    /// * `data_b` - the second sample
+    ///
+    /// # Notes
+    ///
+    /// The result does not depend on the number of threads. It may differ from that of
+    /// [`Unpaired::from_iter`] at the level of rounding errors only: with IEEE doubles, the
+    /// means and standard deviations typically agree to a relative error of \\( 10^{-12} \\) or better.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data_a = (0..100_000).map(|i| (i % 100) as f64).collect::<Vec<_>>();
+    /// let data_b = (0..50_000).map(|i| (i % 10) as f64).collect::<Vec<_>>();
+    /// let stats = comparison::Unpaired::from_iters_parallel(&data_a, &data_b);
+    /// assert_eq!(stats.stats_a().sample_count(), 100_000);
+    /// assert_eq!(stats.stats_b().sample_count(), 50_000);
+    /// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+    /// assert!(ci.contains(&45.));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    #[cfg(feature = "rayon")]
+    pub fn from_iters_parallel(data_a: &[T], data_b: &[T]) -> Self
+    where
+        T: Send + Sync,
+    {
+        let (stats_a, stats_b) = rayon::join(
+            || mean::Arithmetic::from_chunks_deterministic(data_a, PARALLEL_CHUNK_SIZE),
+            || mean::Arithmetic::from_chunks_deterministic(data_b, PARALLEL_CHUNK_SIZE),
+        );
+        Self { stats_a, stats_b }
+    }
+
+    ///
+    /// Create a new instance of `Unpaired` from summary statistics of the two samples,
+    /// when the raw observations are not available.
+    ///
+    /// # Arguments
+    ///
+    /// * `count_a` - the size of the first sample
+    /// * `mean_a` - the mean of the first sample
+    /// * `std_dev_a` - the sample standard deviation of the first sample
+    /// * `count_b` - the size of the second sample
+    /// * `mean_b` - the mean of the second sample
+    /// * `std_dev_b` - the sample standard deviation of the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if either sample has fewer than 2 observations
+    /// * [`CIError::InvalidInputData`] - if a mean is not finite, or if a standard deviation is negative or not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = comparison::Unpaired::from_summaries(12, 120., 21.39, 7, 101., 20.62)?;
+    /// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+    /// # assert!(ci.contains(&19.));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn from_summaries(
+        count_a: usize,
+        mean_a: T,
+        std_dev_a: T,
+        count_b: usize,
+        mean_b: T,
+        std_dev_b: T,
+    ) -> CIResult<Self> {
+        Ok(Self::new(
+            mean::Arithmetic::from_summary(count_a, mean_a, std_dev_a)?,
+            mean::Arithmetic::from_summary(count_b, mean_b, std_dev_b)?,
+        ))
+    }
+
+    ///
+    /// Return a reference to the statistics of the first sample.
+    ///
+    pub fn stats_a(&self) -> &mean::Arithmetic<T> {
+        &self.stats_a
+    }
+
+    ///
+    /// Return a mutable reference to the statistics of the first sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.stats_a_mut().append(1.)?;
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn stats_a_mut(&mut self) -> &mut mean::Arithmetic<T> {
+        &mut self.stats_a
+    }
+
+    ///
+    /// Return a reference to the statistics of the second sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # let mut stats = comparison::Unpaired::from_iter(&[1., 2. ,3.], &[4., 5., 6.])?;
+    /// let mean_b = stats.stats_b().sample_mean();
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn stats_b(&self) -> &mean::Arithmetic<T> {
+        &self.stats_b
+    }
+
+    ///
+    /// Return a mutable reference to the statistics of the second sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.stats_b_mut().append(1.)?;
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn stats_b_mut(&mut self) -> &mut mean::Arithmetic<T> {
+        &mut self.stats_b
+    }
+
+    ///
+    /// Append a pair of observations to the two samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the new data for the first sample
+    /// * `data_b` - the new data for the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_pair(&mut self, data_a: T, data_b: T) -> CIResult<()> {
+        self.append_a(data_a)?;
+        self.append_b(data_b)?;
+        Ok(())
+    }
+
+    ///
+    /// Append a single observation to the first sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the new data for the first sample
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_a(&mut self, data_a: T) -> CIResult<()> {
+        self.stats_a.append(data_a)
+    }
+
+    ///
+    /// Append a single observation to the second sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_b` - the new data for the second sample
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_b(&mut self, data_b: T) -> CIResult<()> {
+        self.stats_b.append(data_b)
+    }
+
+    ///
+    /// Append observations to the first sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the new data for the first sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.extend_a(&[1., 2., 3.])?;
+    /// # assert_eq!(stats.stats_a().sample_count(), 3);
+    /// # assert_eq!(stats.stats_a().sample_mean(), 2.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_a<I: ?Sized>(&mut self, data_a: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a T>,
+    {
+        self.stats_a.extend(data_a)
+    }
+
+    ///
+    /// Append observations to the second sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_b` - the new data for the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.extend_b(&[1., 2., 3.])?;
+    /// # assert_eq!(stats.stats_b().sample_count(), 3);
+    /// # assert_eq!(stats.stats_b().sample_mean(), 2.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_b<I: ?Sized>(&mut self, data_b: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a T>,
+    {
+        self.stats_b.extend(data_b)
+    }
+
+    ///
+    /// Append observations consumed from an iterator to the first sample.
+    ///
+    /// Unlike [`Unpaired::extend_a`], the observations are taken by value, which avoids collecting
+    /// them into a temporary collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the new data for the first sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.extend_a_iter((1..=3).map(|i| i as f64))?;
+    /// # assert_eq!(stats.stats_a().sample_count(), 3);
+    /// # assert_eq!(stats.stats_a().sample_mean(), 2.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_a_iter<I>(&mut self, data_a: I) -> CIResult<()>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.stats_a.extend_iter(data_a)
+    }
+
+    ///
+    /// Append observations consumed from an iterator to the second sample.
+    ///
+    /// Unlike [`Unpaired::extend_b`], the observations are taken by value, which avoids collecting
+    /// them into a temporary collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_b` - the new data for the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.extend_b_iter((1..=3).map(|i| i as f64))?;
+    /// # assert_eq!(stats.stats_b().sample_count(), 3);
+    /// # assert_eq!(stats.stats_b().sample_mean(), 2.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_b_iter<I>(&mut self, data_b: I) -> CIResult<()>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.stats_b.extend_iter(data_b)
+    }
+
+    ///
+    /// Extend the two samples with new data.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the new data for the first sample
+    /// * `data_b` - the new data for the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.extend(&[1., 2., 3.], &[4., 5., 6.])?;
+    /// # assert_eq!(stats.stats_a().sample_count(), 3);
+    /// # assert_eq!(stats.stats_a().sample_mean(), 2.);
+    /// # assert_eq!(stats.stats_b().sample_count(), 3);
+    /// # assert_eq!(stats.stats_b().sample_mean(), 5.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend<Ia: ?Sized, Ib: ?Sized>(&mut self, data_a: &Ia, data_b: &Ib) -> CIResult<()>
+    where
+        for<'a> &'a Ia: IntoIterator<Item = &'a T>,
+        for<'b> &'b Ib: IntoIterator<Item = &'b T>,
+    {
+        self.stats_a.extend(data_a)?;
+        self.stats_b.extend(data_b)?;
+        Ok(())
+    }
+
+    ///
+    /// Extend the two samples with new data consumed from iterators, such as iterator adapters.
+    ///
+    /// This is a shortcut for [`Unpaired::extend_a_iter`] and [`Unpaired::extend_b_iter`].
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the new data for the first sample
+    /// * `data_b` - the new data for the second sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.extend_iter((1..=3).map(f64::from), vec![4., 5., 6., 7.])?;
+    /// # assert_eq!(stats.stats_a().sample_count(), 3);
+    /// # assert_eq!(stats.stats_b().sample_count(), 4);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_iter<Ia, Ib>(&mut self, data_a: Ia, data_b: Ib) -> CIResult<()>
+    where
+        Ia: IntoIterator<Item = T>,
+        Ib: IntoIterator<Item = T>,
+    {
+        self.extend_a_iter(data_a)?;
+        self.extend_b_iter(data_b)?;
+        Ok(())
+    }
+
+    ///
+    /// Compute the confidence interval of the difference between the means of the two samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Returns
+    ///
+    /// The confidence interval of the difference as a result.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.extend(&[1., 2., 3.], &[4., 5., 6.])?;
+    /// let ci = stats.ci_mean(confidence)?;
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// If the interval includes zero, the difference is not significant.
+    /// If the interval is strictly positive (resp. negative), the mean of the first sample is significantly
+    /// greater (resp. smaller) than the mean of the second sample.
+    ///
+    /// If both samples have zero variance (each consists of identical values), the interval is
+    /// degenerate at the difference of the means.
+    ///
+    /// # References
+    ///
+    /// * R. Jain, The Art of Computer Systems Performance Analysis, Wiley, 1991.
+    /// * [Wikipedia article on Student's t-test](https://en.wikipedia.org/wiki/Student%27s_t-test#Independent_two-sample_t-test)
+    /// * PennState. Stat 500. Lesson 7: Comparing Two Population Parameters. [Online](https://online.stat.psu.edu/stat500/lesson/7)
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<T>> {
+        self.ci_mean_detailed(confidence)
+            .map(|detail| detail.interval)
+    }
+
+    ///
+    /// Test whether the difference between the means of the two samples is significant,
+    /// based on the confidence interval computed by [`Self::ci_mean`].
+    ///
+    /// See [`Interval::significance`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = comparison::Unpaired::from_iter(&[1., 2., 3.], &[1.5, 2.5, 3.5])?;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// assert_eq!(stats.significance(confidence)?, Significance::NotSignificant);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn significance(&self, confidence: Confidence) -> CIResult<Significance> {
+        Ok(self.ci_mean(confidence)?.significance())
+    }
+
+    ///
+    /// Summarize the comparison in a single report: the count, mean, standard deviation, and
+    /// standard error of each sample, and the confidence interval of the difference between the
+    /// means with its significance.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::ci_mean`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = comparison::Unpaired::from_iter(&[1., 2., 3.], &[4., 5., 6., 8.])?;
+    /// let report = stats.report(Confidence::new_two_sided(0.95))?;
+    /// assert_eq!(report.group_a.count, 3);
+    /// assert_eq!(report.group_b.mean, 5.75);
+    /// assert!(report.significant);
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(
+    ///     report.difference,
+    ///     Interval::new(-6.2329749763528, -1.2670250236472)?,
+    ///     epsilon = 1e-10
+    /// );
+    /// let text = report.to_string();
+    /// assert!(text.starts_with(
+    ///     "group A: n = 3, mean = 2, std. dev. = 1, std. err. = 0.5773502691896258\n\
+    ///      group B: n = 4, mean = 5.75, std. dev. = 1.707825127659933, std. err. = 0.8539125638299665\n\
+    ///      difference (A - B): [-6.2329"
+    /// ));
+    /// assert!(text.ends_with("(two-sided 95%), significant"));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn report(&self, confidence: Confidence) -> CIResult<UnpairedReport<T>> {
+        let difference = self.ci_mean(confidence)?;
+        Ok(UnpairedReport {
+            group_a: GroupSummary::of(&self.stats_a),
+            group_b: GroupSummary::of(&self.stats_b),
+            difference,
+            confidence,
+            significant: difference.significance().is_significant(),
+        })
+    }
+
+    ///
+    /// Compute the confidence interval of the difference between the means of the two samples,
+    /// together with the quantities used to compute it.
+    ///
+    /// The degrees of freedom reported are the effective degrees of freedom (Welch-Satterthwaite).
+    /// See [`mean::CiDetail`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.extend(&[1., 2., 3.], &[4., 5., 6., 8.])?;
+    /// let detail = stats.ci_mean_detailed(Confidence::new_two_sided(0.95))?;
+    /// assert_eq!(detail.estimate, -3.75);
+    /// assert_eq!(detail.interval, stats.ci_mean(Confidence::new_two_sided(0.95))?);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_mean_detailed(&self, confidence: Confidence) -> CIResult<mean::CiDetail<T>> {
+        let stats_a = self.stats_a;
+        let stats_b = self.stats_b;
+
+        for count in [stats_a.sample_count(), stats_b.sample_count()] {
+            if count < 2 {
+                return Err(CIError::TooFewSamples(count));
+            }
+        }
+
+        let mean_difference = stats_a.sample_mean() - stats_b.sample_mean();
+        let Some((std_err_mean, effective_dof)) = self.welch()? else {
+            // both samples are constant: the difference is known exactly
+            return mean::CiDetail::degenerate(
+                confidence,
+                mean_difference.try_f64("mean_difference")?,
+                (stats_a.sample_count() + stats_b.sample_count() - 2) as f64,
+            );
+        };
+
+        mean::CiDetail::new(
+            confidence,
+            mean_difference.try_f64("mean_difference")?,
+            std_err_mean.try_f64("std_err_mean")?,
+            effective_dof.try_f64("effective_dof")?,
+        )
+    }
+
+    ///
+    /// Two-sided p-value of Welch's t-test of the hypothesis that the means of the two populations
+    /// are equal, i.e., \( 2 P(T > |t|) \) where \( t \) is the difference between the sample means
+    /// divided by its standard error and \( T \) follows Student's t distribution with the effective
+    /// degrees of freedom (Welch-Satterthwaite) reported by [`Self::ci_mean_detailed`].
+    ///
+    /// For any \( \alpha \), \( p < \alpha \) if and only if the two-sided interval computed
+    /// by [`Self::ci_mean`] with confidence \( 1 - \alpha \) excludes zero.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if one of the samples has fewer than two observations
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.extend(&[1., 2., 3.], &[4., 5., 6., 8.])?;
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.p_value()?, 0.009659, epsilon = 1e-6);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn p_value(&self) -> CIResult<f64> {
+        self.ci_mean_detailed(Confidence::default())?
+            .p_value(0., None)
+    }
+
+    ///
+    /// One-sided p-value of Welch's t-test of the hypothesis that the means of the two populations
+    /// are equal, against the alternative that the difference is in the given direction
+    /// (see [`Self::p_value`]).
+    ///
+    /// For any \( \alpha \), \( p < \alpha \) if and only if the one-sided interval computed
+    /// by [`Self::ci_mean`] with confidence \( 1 - \alpha \) excludes zero, where the interval is
+    /// lower one-sided for [`Direction::FirstSmaller`] and upper one-sided for [`Direction::FirstLarger`].
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - the direction of the difference under the alternative hypothesis
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if one of the samples has fewer than two observations
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// use comparison::Direction;
+    /// let mut stats = comparison::Unpaired::default();
+    /// stats.extend(&[1., 2., 3.], &[4., 5., 6., 8.])?;
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.p_value_one_sided(Direction::FirstSmaller)?, 0.004830, epsilon = 1e-6);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn p_value_one_sided(&self, direction: Direction) -> CIResult<f64> {
+        self.ci_mean_detailed(Confidence::default())?
+            .p_value(0., Some(direction.alternative()))
+    }
+
+    ///
+    /// Standard error of the difference between the means and its effective degrees of freedom
+    /// (Welch-Satterthwaite), for samples of at least two observations each, or `None` if both
+    /// samples are constant.
+    ///
+    fn welch(&self) -> CIResult<Option<(T, T)>> {
+        let (stats_a, stats_b) = (&self.stats_a, &self.stats_b);
+        let n_a = T::from(stats_a.sample_count()).convert("stats_a.sample_count")?;
+        let n_b = T::from(stats_b.sample_count()).convert("stats_b.sample_count")?;
+        let std_dev_a = stats_a.sample_std_dev();
+        let std_dev_b = stats_b.sample_std_dev();
+        if std_dev_a == T::zero() && std_dev_b == T::zero() {
+            return Ok(None);
+        }
+        let sa2_na = // $s_a^2 / n_a$
+            std_dev_a * std_dev_a / n_a;
+        let sb2_nb = // $s_b^2 / n_b$
+            std_dev_b * std_dev_b / n_b;
+        let sum_s2_n = // $s_a^2 / n_a + s_b^2 / n_b$
+            sa2_na + sb2_nb;
+        let std_err_mean = // $\sqrt{s_a^2 / n_a + s_b^2 / n_b}$
+            sum_s2_n.sqrt();
+        let effective_dof = // $ \frac{ (s_a^a / n_a + s_b^2 / n_b)^2 }{ \frac{1}{n_a+1} \left(\frac{s_a^2}{n_a}\right)^2 + \frac{1}{n_b+1} \left(\frac{s_b^2}{n_b}\right)^2 } - 2$
+            sum_s2_n * sum_s2_n
+                / (sa2_na * sa2_na / (n_a + T::one())
+                    + sb2_nb * sb2_nb / (n_b + T::one())) - T::one() - T::one();
+        Ok(Some((std_err_mean, effective_dof)))
+    }
+
+    ///
+    /// Degrees of freedom of the interval on the difference between the means, i.e., the
+    /// effective degrees of freedom (Welch-Satterthwaite), as reported by [`Self::ci_mean_detailed`].
+    ///
+    /// When both samples are constant, these are \\( n_a + n_b - 2 \\). When one of the samples
+    /// has fewer than two observations, no interval can be computed and the result is zero.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = comparison::Unpaired::from_iter(&[1., 2., 3.], &[4., 5., 6., 8.])?;
+    /// let detail = stats.ci_mean_detailed(Confidence::new_two_sided(0.95))?;
+    /// assert_eq!(stats.degrees_of_freedom(), detail.degrees_of_freedom);
+    /// assert!(!stats.uses_normal_approximation());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn degrees_of_freedom(&self) -> f64 {
+        let (count_a, count_b) = (self.stats_a.sample_count(), self.stats_b.sample_count());
+        if count_a < 2 || count_b < 2 {
+            return 0.;
+        }
+        match self.welch() {
+            Ok(Some((_, dof))) => dof.to_f64().unwrap_or(f64::NAN),
+            Ok(None) => (count_a + count_b - 2) as f64,
+            Err(_) => f64::NAN,
+        }
+    }
+
+    ///
+    /// Whether the interval on the difference between the means is computed with the normal
+    /// distribution rather than Student's t distribution, based on [`Self::degrees_of_freedom`]
+    /// (see [`stats::uses_normal_approximation`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn uses_normal_approximation(&self) -> bool {
+        stats::uses_normal_approximation(self.degrees_of_freedom())
+    }
+
+    ///
+    /// Effective degrees of freedom (Welch-Satterthwaite) of the interval on the difference between
+    /// the means, i.e., exactly the value used by [`Self::ci_mean`] and reported by
+    /// [`Self::ci_mean_detailed`].
+    ///
+    /// Unlike [`Self::degrees_of_freedom`], the result is expressed in the float type of the samples,
+    /// and the samples being too small is reported as an error.
+    /// When both samples are constant, these are \\( n_a + n_b - 2 \\).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if one of the samples has fewer than two observations
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = comparison::Unpaired::from_iter(&[1., 2., 3.], &[4., 5., 6., 8.])?;
+    /// let detail = stats.ci_mean_detailed(Confidence::new_two_sided(0.95))?;
+    /// assert_eq!(stats.effective_dof()?, detail.degrees_of_freedom);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn effective_dof(&self) -> CIResult<T> {
+        let (count_a, count_b) = (self.stats_a.sample_count(), self.stats_b.sample_count());
+        for count in [count_a, count_b] {
+            if count < 2 {
+                return Err(CIError::TooFewSamples(count));
+            }
+        }
+        match self.welch()? {
+            Some((_, effective_dof)) => Ok(effective_dof),
+            None => T::from(count_a + count_b - 2).convert("degrees_of_freedom"),
+        }
+    }
+
+    ///
+    /// Confidence interval of the ratio of the variances of the two populations
+    /// \\( \sigma_a^2 / \sigma_b^2 \\), assuming that both populations are normally distributed.
+    ///
+    /// The interval is obtained from the F distribution with \\( n_a - 1 \\) and \\( n_b - 1 \\)
+    /// degrees of freedom:
+    /// \\( \left[ \frac{s_a^2 / s_b^2}{F_{n_a-1,n_b-1}(q)}, \frac{s_a^2 / s_b^2}{F_{n_a-1,n_b-1}(1-q)} \right] \\)
+    /// where \\( s_a \\) and \\( s_b \\) are the sample standard deviations, as computed by R's `var.test`.
+    /// An interval that excludes one suggests that the variances differ, in which case the pooled-variance
+    /// (Student's) interval on the difference between the means is not appropriate.
+    ///
+    /// As for [`mean::Arithmetic::ci_std_dev`], this interval is sensitive to departures from normality.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if one of the samples has fewer than two observations
+    /// * [`CIError::ZeroVariance`] - if the second sample is constant
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data_a = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+    /// let data_b = [3., 6., 9., 12., 15., 18., 21., 24., 27., 30.];
+    /// let stats = comparison::Unpaired::from_iter(&data_a, &data_b)?;
+    /// let ci = stats.ci_variance_ratio(Confidence::new_two_sided(0.95))?;
+    /// assert!(ci.contains(&(1. / 9.)));
+    /// assert!(!ci.contains(&1.)); // the variances differ
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_variance_ratio(&self, confidence: Confidence) -> CIResult<Interval<T>> {
+        let (count_a, count_b) = (self.stats_a.sample_count(), self.stats_b.sample_count());
+        for count in [count_a, count_b] {
+            if count < 2 {
+                return Err(CIError::TooFewSamples(count));
+            }
+        }
+        let std_dev_a = self.stats_a.sample_std_dev().try_f64("stats_a.std_dev")?;
+        let std_dev_b = self.stats_b.sample_std_dev().try_f64("stats_b.std_dev")?;
+        if std_dev_b == 0. {
+            return Err(CIError::ZeroVariance("stats_b".to_string()));
+        }
+        // the ratio of the standard deviations is squared to avoid overflowing the variances
+        let ratio = (std_dev_a / std_dev_b).powi(2);
+        let (d1, d2) = ((count_a - 1) as f64, (count_b - 1) as f64);
+        let q = confidence.quantile();
+        let low = ratio / stats::fisher_snedecor_value(q, d1, d2)?;
+        let high = ratio / stats::fisher_snedecor_value(1. - q, d1, d2)?;
+        Interval::from_finite_bounds_for(
+            confidence,
+            low.try_narrow("low")?,
+            high.try_narrow("high")?,
+        )
+    }
+
+    ///
+    /// Compute the confidence interval of the difference between the means of the two samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `data_a` - the first sample
+    /// * `data_b` - the second sample
+    ///
+    /// # Returns
+    ///
+    /// The confidence interval of the difference as a result.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if the conversion to `T` fails
+    /// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+    ///
+    /// # Notes
+    ///
+    /// If the interval includes zero, the difference is not significant.
+    /// If the interval is strictly positive (resp. negative), the mean of the first sample is significantly
+    /// greater (resp. smaller) than the mean of the second sample.
+    ///
+    /// This function provides a simple interface to obtain the confidence interval with a single call, when
+    /// the samples are known a priori and there is no need to include additional observations,
+    /// obtain the confidence intervals for other levels or access the sample statistics. For more refined
+    /// use cases, it is recommended to use [`Unpaired::ci_mean`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data_a = [1., 2., 3.];
+    /// let data_b = [4., 5., 6.];
+    /// let ci = comparison::Unpaired::ci(Confidence::new_two_sided(0.95), &data_a, &data_b)?;
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # References
+    ///
+    /// * R. Jain, The Art of Computer Systems Performance Analysis, Wiley, 1991.
+    /// * [Wikipedia article on Student's t-test](https://en.wikipedia.org/wiki/Student%27s_t-test#Independent_two-sample_t-test)
+    /// * PennState. Stat 500. Lesson 7: Comparing Two Population Parameters. [Online](https://online.stat.psu.edu/stat500/lesson/7)
+    ///
+    pub fn ci<Ia, Ib>(confidence: Confidence, data_a: &Ia, data_b: &Ib) -> CIResult<Interval<T>>
+    where
+        for<'a> &'a Ia: IntoIterator<Item = &'a T>,
+        for<'a> &'a Ib: IntoIterator<Item = &'a T>,
+    {
+        let mut stats = Self::default();
+        stats.extend(data_a, data_b)?;
+        stats.ci_mean(confidence)
+    }
+}
+
+///
+/// Merge the statistics of two disjoint pairs of samples, sample by sample, as if all
+/// observations had been appended to a single instance.
+///
+/// This is the building block for accumulating observations concurrently: each thread (or
+/// chunk) builds its own `Unpaired` and the partial states are then combined with `+` or `+=`
+/// (see [`Unpaired::from_iters_parallel`]).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let mut stats = comparison::Unpaired::from_iter(&[1., 2.], &[4.])?;
+/// stats += comparison::Unpaired::from_iter(&[3.], &[5., 6.])?;
+/// assert_eq!(stats.stats_a().sample_mean(), 2.);
+/// assert_eq!(stats.stats_b().sample_mean(), 5.);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+impl<F: Float> core::ops::Add for Unpaired<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            stats_a: self.stats_a + rhs.stats_a,
+            stats_b: self.stats_b + rhs.stats_b,
+        }
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for Unpaired<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.stats_a += rhs.stats_a;
+        self.stats_b += rhs.stats_b;
+    }
+}
+
+impl<F: Float> utils::Accumulator for Unpaired<F> {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+///
+/// Durations, e.g., measured with [`std::time::Instant::elapsed`], collected as seconds.
+///
+#[cfg(feature = "std")]
+impl Unpaired<f64> {
+    ///
+    /// Add a duration to each of the two samples, as numbers of seconds
+    /// (see [`std::time::Duration::as_secs_f64`]).
+    ///
+    /// The interval on the difference computed by [`Unpaired::ci_mean`] is then in signed seconds,
+    /// with negative values when the durations of the first sample are shorter.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_a` - the duration for the first sample
+    /// * `data_b` - the duration for the second sample
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Unpaired::append_pair`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// use std::time::Duration;
+    /// let mut stats = comparison::Unpaired::default();
+    /// for (a, b) in [(10, 12), (11, 14), (9, 12), (12, 14)] {
+    ///     stats.append_pair_durations(Duration::from_millis(a), Duration::from_millis(b))?;
+    /// }
+    /// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+    /// assert!(ci.high_f() < 0.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_pair_durations(
+        &mut self,
+        data_a: std::time::Duration,
+        data_b: std::time::Duration,
+    ) -> CIResult<()> {
+        self.append_pair(data_a.as_secs_f64(), data_b.as_secs_f64())
+    }
+}
+
+///
+/// Summary statistics of one of the samples of a comparison, as found in [`UnpairedReport`].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupSummary<T: Float> {
+    /// The number of observations.
+    pub count: usize,
+
+    /// The sample mean.
+    pub mean: T,
+
+    /// The sample standard deviation.
+    pub std_dev: T,
+
+    /// The standard error of the mean.
+    pub sem: T,
+}
+
+impl<T: Float> GroupSummary<T> {
+    fn of(stats: &mean::Arithmetic<T>) -> Self {
+        Self {
+            count: stats.sample_count(),
+            mean: stats.sample_mean(),
+            std_dev: stats.sample_std_dev(),
+            sem: stats.sample_sem(),
+        }
+    }
+}
+
+impl<T: Float + core::fmt::Display> core::fmt::Display for GroupSummary<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "n = {}, mean = {}, std. dev. = {}, std. err. = {}",
+            self.count, self.mean, self.std_dev, self.sem
+        )
+    }
+}
+
+///
+/// Summary of a comparison of unpaired samples, as returned by [`Unpaired::report`].
+///
+/// The [`Display`](core::fmt::Display) implementation renders the report over three lines:
+/// one per sample and one for the difference.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnpairedReport<T: Float> {
+    /// The summary of the first sample.
+    pub group_a: GroupSummary<T>,
+
+    /// The summary of the second sample.
+    pub group_b: GroupSummary<T>,
+
+    /// The confidence interval of the difference between the means (first minus second).
+    pub difference: Interval<T>,
+
+    /// The confidence level used for the interval.
+    pub confidence: Confidence,
+
+    /// Whether the interval excludes zero.
+    pub significant: bool,
+}
+
+impl<T: Float + core::fmt::Display> core::fmt::Display for UnpairedReport<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "group A: {}", self.group_a)?;
+        writeln!(f, "group B: {}", self.group_b)?;
+        write_difference(f, &self.difference, self.confidence, self.significant)
+    }
+}
+
+///
+/// Last line of the reports: the interval of the difference, its confidence, and its significance.
+///
+fn write_difference<T: Float + core::fmt::Display>(
+    f: &mut core::fmt::Formatter<'_>,
+    difference: &Interval<T>,
+    confidence: Confidence,
+    significant: bool,
+) -> core::fmt::Result {
+    write!(
+        f,
+        "difference (A - B): {} ({}), {}",
+        difference,
+        confidence,
+        if significant {
+            "significant"
+        } else {
+            "not significant"
+        }
+    )
+}
+
+#[cfg(feature = "persist")]
+impl<F: Float> Unpaired<F> {
+    ///
+    /// Encode the state with the stable binary format of [`persist`] (see the module for the layout).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let stats = comparison::Unpaired::from_iter(&[1., 2., 3.], &[4., 6.])?;
+    /// assert_eq!(comparison::Unpaired::from_bytes(&stats.to_bytes())?, stats);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = persist::Writer::new(persist::Kind::Unpaired);
+        self.stats_a.write_payload(&mut writer);
+        self.stats_b.write_payload(&mut writer);
+        writer.finish()
+    }
+
+    ///
+    /// Decode a state encoded with [`Unpaired::to_bytes`].
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// Same as [`mean::Arithmetic::from_bytes`].
+    ///
+    pub fn from_bytes(bytes: &[u8]) -> CIResult<Self> {
+        let mut reader = persist::Reader::new(bytes, persist::Kind::Unpaired)?;
+        let stats_a = mean::Arithmetic::read_payload(&mut reader)?;
+        let stats_b = mean::Arithmetic::read_payload(&mut reader)?;
+        reader.finish()?;
+        Ok(Self { stats_a, stats_b })
+    }
+
+    ///
+    /// Decode a state encoded with [`Unpaired::to_bytes`] and combine it with this one.
+    /// The state is left unchanged if decoding or combining fails.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// Same as [`mean::Arithmetic::merge_from_bytes`].
+    ///
+    pub fn merge_from_bytes(&mut self, bytes: &[u8]) -> CIResult<()> {
+        let other = Self::from_bytes(bytes)?;
+        let stats_a = self.stats_a.try_add(other.stats_a)?;
+        let stats_b = self.stats_b.try_add(other.stats_b)?;
+        self.stats_a = stats_a;
+        self.stats_b = stats_b;
+        Ok(())
+    }
+}
+
+///
+/// Variant of a two-sample comparison to which an observation belongs (see [`StreamingUnpaired`]).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Variant {
+    ///
+    /// The first sample (e.g., the candidate of an A/B test).
+    ///
+    A,
+
+    ///
+    /// The second sample (e.g., the baseline of an A/B test).
+    ///
+    B,
+}
+
+///
+/// Streaming comparison of two unpaired samples whose observations are grouped in buckets
+/// (e.g., periods of time), to guard against drift.
+///
+/// When the observations of the two variants arrive at different rates, the plain [`Unpaired`]
+/// comparison silently mixes the difference between the variants with the differences between
+/// periods: if most observations of one variant are made at peak hours, the comparison may
+/// reflect the peak rather than the variant (Simpson's paradox). Instead, this structure keeps
+/// one [`Unpaired`] state per bucket, compares the variants within each bucket, and combines the
+/// per-bucket differences (stratified estimate, see [`Self::ci_mean_overall`]).
+///
+/// Buckets where either variant has fewer than a minimum number of observations cannot be compared
+/// and are excluded; [`Self::imbalance_report`] tells which ones, and how much data they hold.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use comparison::Variant;
+/// let mut stats = comparison::StreamingUnpaired::new(2);
+/// // off-peak (bucket 0): mostly B; peak (bucket 1): mostly A, and slower for both
+/// for i in 0..8 {
+///     stats.append(Variant::A, 20. + (i % 3) as f64, 1)?;
+///     stats.append(Variant::B, 11. + (i % 3) as f64, 0)?;
+/// }
+/// for i in 0..2 {
+///     stats.append(Variant::A, 9. + i as f64, 0)?;
+///     stats.append(Variant::B, 22. + i as f64, 1)?;
+/// }
+/// stats.append(Variant::A, 15., 2)?;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // A looks slower overall, only because it is mostly observed at peak hours
+/// assert!(stats.pooled().ci_mean(confidence)?.low_f() > 0.);
+/// // within buckets, A is faster
+/// assert!(stats.ci_mean_overall(confidence)?.high_f() < 0.);
+/// let report = stats.imbalance_report();
+/// assert_eq!(report.excluded_buckets, vec![2]);
+/// assert_eq!(report.excluded_observations, 1);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamingUnpaired<T: Float> {
+    buckets: std::collections::BTreeMap<u32, Unpaired<T>>,
+    min_per_variant: usize,
+}
+
+impl<T: Float> Default for StreamingUnpaired<T> {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl<T: Float> StreamingUnpaired<T> {
+    ///
+    /// Create a new empty state.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_per_variant` - the minimum number of observations of each variant for a bucket to be
+    ///   included in the comparison; values smaller than 2 are raised to 2, as the variance of each
+    ///   variant must be estimated within the bucket
+    ///
+    pub fn new(min_per_variant: usize) -> Self {
+        Self {
+            buckets: std::collections::BTreeMap::new(),
+            min_per_variant: min_per_variant.max(2),
+        }
+    }
+
+    ///
+    /// Return the minimum number of observations of each variant for a bucket to be included.
+    ///
+    pub fn min_per_variant(&self) -> usize {
+        self.min_per_variant
+    }
+
+    ///
+    /// Append an observation of a variant to a bucket.
+    /// On error, the state is left unchanged (in particular, no empty bucket is created).
+    ///
+    /// Complexity: \\( O(\log b) \\), where \\( b \\) is the number of buckets
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - the variant of the observation
+    /// * `value` - the observed value
+    /// * `bucket` - the identifier of the bucket (e.g., the hour of the observation)
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Unpaired::append_a`].
+    ///
+    pub fn append(&mut self, variant: Variant, value: T, bucket: u32) -> CIResult<()> {
+        let append = |stats: &mut Unpaired<T>| match variant {
+            Variant::A => stats.append_a(value),
+            Variant::B => stats.append_b(value),
+        };
+        if let Some(stats) = self.buckets.get_mut(&bucket) {
+            return append(stats);
+        }
+        // only create the bucket once the observation is accepted
+        let mut stats = Unpaired::default();
+        append(&mut stats)?;
+        self.buckets.insert(bucket, stats);
+        Ok(())
+    }
+
+    ///
+    /// Return the state of a bucket, if it has any observation.
+    ///
+    pub fn bucket(&self, bucket: u32) -> Option<&Unpaired<T>> {
+        self.buckets.get(&bucket)
+    }
+
+    ///
+    /// Iterate over the buckets and their states, in increasing order of identifiers.
+    ///
+    pub fn buckets(&self) -> impl Iterator<Item = (u32, &Unpaired<T>)> {
+        self.buckets.iter().map(|(&bucket, stats)| (bucket, stats))
+    }
+
+    ///
+    /// Return the plain comparison of all the observations, regardless of their buckets.
+    ///
+    /// This is the comparison that ignores drift, e.g., to compare it with [`Self::ci_mean_overall`].
+    ///
+    /// Complexity: \\( O(b) \\), where \\( b \\) is the number of buckets
+    ///
+    pub fn pooled(&self) -> Unpaired<T> {
+        self.buckets
+            .values()
+            .fold(Unpaired::default(), |acc, stats| acc + stats.clone())
+    }
+
+    fn is_included(&self, stats: &Unpaired<T>) -> bool {
+        stats.stats_a().sample_count() >= self.min_per_variant
+            && stats.stats_b().sample_count() >= self.min_per_variant
+    }
+
+    ///
+    /// Compute the confidence interval of the difference between the means of the two variants
+    /// (A minus B), combining the differences within the buckets where both variants have at
+    /// least [`Self::min_per_variant`] observations.
+    ///
+    /// The difference is the stratified estimate
+    /// \\[
+    /// \hat{d} = \sum_k w_k (\bar{a}_k - \bar{b}_k), \quad
+    /// SE^2 = \sum_k w_k^2 \left( \frac{s_{a,k}^2}{n_{a,k}} + \frac{s_{b,k}^2}{n_{b,k}} \right)
+    /// \\]
+    /// where \\( w_k \\) is the fraction of the included observations that belong to bucket \\( k \\).
+    /// The effective degrees of freedom are approximated over all the terms of \\( SE^2 \\) as in
+    /// [`Unpaired::ci_mean_detailed`], so that with a single bucket, the interval is that of
+    /// [`Unpaired::ci_mean`].
+    ///
+    /// Complexity: \\( O(b) \\), where \\( b \\) is the number of buckets
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if no bucket has enough observations of both variants
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    pub fn ci_mean_overall(&self, confidence: Confidence) -> CIResult<Interval<T>> {
+        let included = self
+            .buckets
+            .values()
+            .filter(|stats| self.is_included(stats))
+            .collect::<Vec<_>>();
+        if included.is_empty() {
+            return Err(CIError::TooFewSamples(0));
+        }
+        let total = included
+            .iter()
+            .map(|stats| stats.stats_a().sample_count() + stats.stats_b().sample_count())
+            .sum::<usize>() as f64;
+
+        let mut difference = 0.;
+        let mut variance = 0.;
+        let mut dof_denominator = 0.;
+        let mut constant_dof = 0.;
+        for stats in included {
+            let (n_a, n_b) = (
+                stats.stats_a().sample_count(),
+                stats.stats_b().sample_count(),
+            );
+            let weight = (n_a + n_b) as f64 / total;
+            let mean_a = stats.stats_a().sample_mean().try_f64("mean_a")?;
+            let mean_b = stats.stats_b().sample_mean().try_f64("mean_b")?;
+            difference += weight * (mean_a - mean_b);
+            for (stats, n) in [(stats.stats_a(), n_a), (stats.stats_b(), n_b)] {
+                let std_dev = stats.sample_std_dev().try_f64("std_dev")?;
+                // $w_k^2 s^2 / n$
+                let term = weight * weight * std_dev * std_dev / n as f64;
+                variance += term;
+                dof_denominator += term * term / (n + 1) as f64;
+                constant_dof += (n - 1) as f64;
+            }
+        }
+
+        let detail: mean::CiDetail<T> = if variance == 0. {
+            // all the buckets are constant: the difference is known exactly
+            mean::CiDetail::degenerate(confidence, difference, constant_dof)?
+        } else {
+            mean::CiDetail::new(
+                confidence,
+                difference,
+                variance.sqrt(),
+                variance * variance / dof_denominator - 2.,
+            )?
+        };
+        Ok(detail.interval)
+    }
+
+    ///
+    /// Report the buckets excluded from [`Self::ci_mean_overall`] because either variant has too
+    /// few observations, and the amount of data dropped with them.
+    ///
+    /// Complexity: \\( O(b) \\), where \\( b \\) is the number of buckets
+    ///
+    pub fn imbalance_report(&self) -> ImbalanceReport {
+        let mut report = ImbalanceReport::default();
+        for (&bucket, stats) in &self.buckets {
+            let count = stats.stats_a().sample_count() + stats.stats_b().sample_count();
+            if self.is_included(stats) {
+                report.included_buckets.push(bucket);
+                report.included_observations += count;
+            } else {
+                report.excluded_buckets.push(bucket);
+                report.excluded_observations += count;
+            }
+        }
+        report
+    }
+
+    ///
+    /// Merge two states, aligning their buckets by identifier, as if all the observations had
+    /// been appended to the first state.
+    ///
+    /// Complexity: \\( O(b \log b) \\), where \\( b \\) is the number of buckets
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentMinPerVariant`] - if the states have different minimum numbers of
+    ///   observations per variant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// use comparison::{StreamingUnpaired, Variant};
+    /// let mut stats = StreamingUnpaired::new(2);
+    /// stats.append(Variant::A, 1., 0)?;
+    /// let mut other = StreamingUnpaired::new(2);
+    /// other.append(Variant::A, 2., 0)?;
+    /// let merged = stats.clone().try_add(other)?;
+    /// assert_eq!(merged.bucket(0).unwrap().stats_a().sample_count(), 2);
+    ///
+    /// assert_eq!(
+    ///     stats.try_add(StreamingUnpaired::new(5)),
+    ///     Err(error::CIError::DifferentMinPerVariant { min_a: 2, min_b: 5 })
+    /// );
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn try_add(mut self, rhs: Self) -> CIResult<Self> {
+        if self.min_per_variant != rhs.min_per_variant {
+            return Err(CIError::DifferentMinPerVariant {
+                min_a: self.min_per_variant,
+                min_b: rhs.min_per_variant,
+            });
+        }
+        self += rhs;
+        Ok(self)
+    }
+}
+
+///
+/// Merge of two states, aligning their buckets by identifier, as if all the observations had been
+/// appended to the first state.
+///
+/// # Panics
+///
+/// Panics if the two states have different minimum numbers of observations per variant, which
+/// would silently change which buckets are compared; see [`StreamingUnpaired::try_add`] for a
+/// fallible merge.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use comparison::Variant;
+/// let mut stats = comparison::StreamingUnpaired::new(2);
+/// stats.append(Variant::A, 1., 0)?;
+/// let mut other = comparison::StreamingUnpaired::new(2);
+/// other.append(Variant::A, 2., 0)?;
+/// other.append(Variant::B, 3., 1)?;
+/// stats += other;
+/// assert_eq!(stats.bucket(0).unwrap().stats_a().sample_count(), 2);
+/// assert_eq!(stats.buckets().count(), 2);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+impl<F: Float> core::ops::Add for StreamingUnpaired<F> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for StreamingUnpaired<F> {
+    fn add_assign(&mut self, rhs: Self) {
+        assert_eq!(
+            self.min_per_variant, rhs.min_per_variant,
+            "Cannot combine states with different minimum numbers of observations per variant"
+        );
+        for (bucket, stats) in rhs.buckets {
+            *self.buckets.entry(bucket).or_default() += stats;
+        }
+    }
+}
+
+impl<F: Float> utils::Accumulator for StreamingUnpaired<F> {
+    fn reset(&mut self) -> Self {
+        core::mem::replace(self, Self::new(self.min_per_variant))
+    }
+}
+
+///
+/// Buckets included in and excluded from the comparison of a [`StreamingUnpaired`] state,
+/// as returned by [`StreamingUnpaired::imbalance_report`].
+///
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImbalanceReport {
+    /// The identifiers of the buckets included in the comparison, in increasing order.
+    pub included_buckets: Vec<u32>,
+
+    /// The identifiers of the buckets excluded from the comparison, in increasing order.
+    pub excluded_buckets: Vec<u32>,
+
+    /// The number of observations (of both variants) in the included buckets.
+    pub included_observations: usize,
+
+    /// The number of observations (of both variants) in the excluded buckets.
+    pub excluded_observations: usize,
+}
+
+impl ImbalanceReport {
+    ///
+    /// Fraction of the observations dropped with the excluded buckets, in \\( [0, 1] \\)
+    /// (zero if there are no observations).
+    ///
+    pub fn fraction_dropped(&self) -> f64 {
+        let total = self.included_observations + self.excluded_observations;
+        if total == 0 {
+            0.
+        } else {
+            self.excluded_observations as f64 / total as f64
+        }
+    }
+}
+
+impl core::fmt::Display for ImbalanceReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} of {} buckets excluded, {:.1}% of the observations dropped",
+            self.excluded_buckets.len(),
+            self.excluded_buckets.len() + self.included_buckets.len(),
+            100. * self.fraction_dropped()
+        )
+    }
+}
+
+///
+/// Compute the confidence interval on the difference between the same quantile of two
+/// independent samples (e.g., the difference between their medians or their 99th percentiles).
+///
+/// The difference is computed as \\( \hat{x}_{q,a} - \hat{x}_{q,b} \\), hence a strictly negative
+/// interval means that the quantile of the first sample is significantly smaller than that of the
+/// second sample.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (two-sided or one-sided)
+/// * `data_a` - the first sample
+/// * `data_b` - the second sample
+/// * `quantile` - the quantile to compare (must be in (0, 1))
+///
+/// # Errors
+///
+/// * [`CIError::InvalidQuantile`] - if the quantile is not in (0, 1)
+/// * [`CIError::TooFewSamples`] - if one of the samples is too small to estimate the standard error of its quantile
+/// * [`CIError::InvalidInputData`] - if the data contains NaN values
+/// * [`CIError::FloatConversionError`] - if some data cannot be converted to a float
+///
+/// # Notes
+///
+/// The interval relies on the normal approximation of the distribution of sample quantiles.
+/// The standard error of each sample quantile is estimated from the width of the distribution-free
+/// (binomial-based) 95% confidence interval of that quantile, i.e.
+/// \\( SE \approx \frac{x_{(u)} - x_{(l)}}{2 z_{0.975}} \\)
+/// where \\( l, u = nq \mp z_{0.975} \sqrt{nq(1-q)} \\), which avoids estimating the density of the
+/// data explicitly. The standard errors of both samples are then combined as
+/// \\( \sqrt{SE_a^2 + SE_b^2} \\).
+///
+/// Extreme quantiles require correspondingly larger samples; for instance, the 99th percentile
+/// requires at least a few hundred observations per sample.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let baseline = (1..=200).map(|x| x as f64).collect::<Vec<_>>();
+/// let candidate = (1..=200).map(|x| x as f64 * 0.5).collect::<Vec<_>>();
+/// // is the candidate's 90th percentile lower than the baseline's?
+/// let ci = comparison::quantile_diff_ci(Confidence::new_lower(0.95), &candidate, &baseline, 0.9)?;
+/// assert!(ci.is_lower());
+/// assert!(ci.high_f() < 0.);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * R. M. Price, D. G. Bonett. Estimating the variance of the sample median. Journal of Statistical Computation and Simulation, 68(3):295–305, 2001.
+/// * [Wikipedia article on quantiles (estimating quantiles from a sample)](https://en.wikipedia.org/wiki/Quantile#Estimating_quantiles_from_a_sample)
+///
+pub fn quantile_diff_ci<T, Ia, Ib>(
+    confidence: Confidence,
+    data_a: &Ia,
+    data_b: &Ib,
+    quantile: f64,
+) -> CIResult<Interval<f64>>
+where
+    T: Float,
+    for<'a> &'a Ia: IntoIterator<Item = &'a T>,
+    for<'a> &'a Ib: IntoIterator<Item = &'a T>,
+{
+    if quantile <= 0. || 1. <= quantile {
+        return Err(CIError::InvalidQuantile(quantile));
+    }
+
+    let (quantile_a, std_err_a) = quantile_with_std_err(&sorted_f64(data_a)?, quantile)?;
+    let (quantile_b, std_err_b) = quantile_with_std_err(&sorted_f64(data_b)?, quantile)?;
+
+    let difference = quantile_a - quantile_b;
+    let std_err = (std_err_a * std_err_a + std_err_b * std_err_b).sqrt();
+    let span = z_value(confidence) * std_err;
+
+    Interval::from_finite_bounds_for(confidence, difference - span, difference + span)
+}
+
+///
+/// Quantiles at which [`shift_ci`] matches the two samples: the deciles.
+///
+const DECILES: [f64; 9] = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9];
+
+///
+/// Compute the confidence interval on the shift between two independent samples, __assuming a
+/// location-shift model__, i.e., that the distribution of the first sample is that of the second
+/// one shifted by a constant \\( \delta \\), such that \\( x_{q,a} - x_{q,b} = \delta \\) for every quantile \\( q \\).
+///
+/// The shift is estimated as the mean of the differences between the matched deciles of both
+/// samples (see [`shift_ci_with_grid`] for other quantiles). As with [`quantile_diff_ci`], a strictly
+/// negative interval means that the first sample is significantly smaller than the second one.
+///
+/// __Warning:__ the interval is only meaningful if the two distributions have the same shape
+/// (e.g., the same spread and skewness). Otherwise, the differences of the quantiles depend on the
+/// quantile and their mean is a mere summary of them; compare specific quantiles with
+/// [`quantile_diff_ci`] instead.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (two-sided or one-sided)
+/// * `data_a` - the first sample
+/// * `data_b` - the second sample
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if one of the samples is too small to estimate the standard error of its
+///   extreme deciles (about 55 observations are required)
+/// * [`CIError::InvalidInputData`] - if the data contains NaN values
+/// * [`CIError::FloatConversionError`] - if some data cannot be converted to a float
+///
+/// # Notes
+///
+/// Using all the deciles rather than only the median makes the estimate less variable than the
+/// difference of the medians (see [`quantile_diff_ci`]), at least for large samples, while the
+/// extreme tails, where the sample quantiles are the least reliable, are left out. The differences at neighboring deciles
+/// are strongly correlated, so they are not treated as a sample: the standard error of their mean
+/// relies on the asymptotic covariance of the sample quantiles \\( \hat{x}_p \\) and \\( \hat{x}_q \\),
+/// \\( \frac{p (1-q)}{n f(x_p) f(x_q)} \\) for \\( p \le q \\), where the density \\( f \\) is estimated from the
+/// standard error of each quantile as in [`quantile_diff_ci`]. The interval relies on the normal
+/// approximation and tends to be conservative for small samples.
+///
+/// The samples may have very different lengths. This is cheaper than the Hodges–Lehmann estimate
+/// with its Mann–Whitney interval, which involves all the pairwise differences of the samples.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // latencies with the same (exponential) shape, the candidate being delayed by 2 units
+/// let baseline = (0..500).map(|i| -(1. - (i as f64 + 0.5) / 500.).ln()).collect::<Vec<_>>();
+/// let candidate = (0..200).map(|i| 2. - (1. - (i as f64 + 0.5) / 200.).ln()).collect::<Vec<_>>();
+/// let ci = comparison::shift_ci(Confidence::new_two_sided(0.95), &candidate, &baseline)?;
+/// assert!(ci.contains(&2.));
+/// assert!(ci.low_f() > 1.8 && ci.high_f() < 2.2);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * E. L. Lehmann. Nonparametrics: Statistical Methods Based on Ranks. Holden-Day, 1975 (location-shift model).
+/// * [Wikipedia article on quantiles (estimating quantiles from a sample)](https://en.wikipedia.org/wiki/Quantile#Estimating_quantiles_from_a_sample)
+///
+pub fn shift_ci<T: Float>(
+    confidence: Confidence,
+    data_a: &[T],
+    data_b: &[T],
+) -> CIResult<Interval<T>> {
+    shift_ci_with_grid(confidence, data_a, data_b, &DECILES)
+}
+
+///
+/// Compute the confidence interval on the shift between two independent samples, assuming a
+/// location-shift model, from the differences of their quantiles at the given grid
+/// (see [`shift_ci`], which uses the deciles).
+///
+/// With a single quantile, the interval is that of [`quantile_diff_ci`]. Denser grids, or grids
+/// reaching further into the tails, require larger samples.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (two-sided or one-sided)
+/// * `data_a` - the first sample
+/// * `data_b` - the second sample
+/// * `grid` - the quantiles at which the samples are matched (each must be in (0, 1))
+///
+/// # Errors
+///
+/// * [`CIError::EmptyQuantileGrid`] - if the grid is empty
+/// * [`CIError::InvalidQuantile`] - if a quantile of the grid is not in (0, 1)
+/// * [`CIError::TooFewSamples`] - if one of the samples is too small to estimate the standard error of
+///   its quantile at some point of the grid
+/// * [`CIError::InvalidInputData`] - if the data contains NaN values
+/// * [`CIError::FloatConversionError`] - if some data cannot be converted to a float
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let baseline = (1..=100).map(|x| x as f64).collect::<Vec<_>>();
+/// let candidate = (1..=100).map(|x| x as f64 + 10.).collect::<Vec<_>>();
+/// let quartiles = [0.25, 0.5, 0.75];
+/// let confidence = Confidence::new_upper(0.95);
+/// let ci = comparison::shift_ci_with_grid(confidence, &candidate, &baseline, &quartiles)?;
+/// // the candidate is larger by at least a few units
+/// assert!(ci.low_f() > 0.);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn shift_ci_with_grid<T: Float>(
+    confidence: Confidence,
+    data_a: &[T],
+    data_b: &[T],
+    grid: &[f64],
+) -> CIResult<Interval<T>> {
+    if grid.is_empty() {
+        return Err(CIError::EmptyQuantileGrid);
+    }
+    if let Some(&quantile) = grid.iter().find(|&&q| !(0. < q && q < 1.)) {
+        return Err(CIError::InvalidQuantile(quantile));
+    }
+    let sorted_a = sorted_f64(data_a)?;
+    let sorted_b = sorted_f64(data_b)?;
+
+    // the variance of a sample quantile is q (1 - q) / (n f(x_q)^2), so dividing its standard
+    // error by sqrt(q (1 - q)) yields 1 / (sqrt(n) f(x_q)) for the covariances
+    let mut sum = 0.;
+    let mut deviations = Vec::with_capacity(grid.len());
+    for &quantile in grid {
+        let (quantile_a, std_err_a) = quantile_with_std_err(&sorted_a, quantile)?;
+        let (quantile_b, std_err_b) = quantile_with_std_err(&sorted_b, quantile)?;
+        sum += quantile_a - quantile_b;
+        let spread = (quantile * (1. - quantile)).sqrt();
+        deviations.push((quantile, std_err_a / spread, std_err_b / spread));
+    }
+    let mut variance = 0.;
+    for &(p, dev_a_p, dev_b_p) in &deviations {
+        for &(q, dev_a_q, dev_b_q) in &deviations {
+            let covariance = p.min(q) * (1. - p.max(q));
+            variance += covariance * (dev_a_p * dev_a_q + dev_b_p * dev_b_q);
+        }
+    }
+
+    let count = grid.len() as f64;
+    let shift = sum / count;
+    let span = z_value(confidence) * variance.sqrt() / count;
+    Interval::from_finite_bounds_for(
+        confidence,
+        (shift - span).try_narrow("low")?,
+        (shift + span).try_narrow("high")?,
+    )
+}
+
+///
+/// Collect the data into a sorted vector of `f64`.
+///
+fn sorted_f64<T, I: ?Sized>(data: &I) -> CIResult<Vec<f64>>
+where
+    T: Float,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+{
+    let mut sorted = data
+        .into_iter()
+        .map(|x| x.try_f64("data"))
+        .collect::<CIResult<Vec<_>>>()?;
+    if sorted.iter().any(|x| x.is_nan()) {
+        return Err(CIError::InvalidInputData);
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(sorted)
+}
+
+///
+/// Estimate a quantile of sorted data together with its standard error.
+///
+/// The standard error is derived from the width of the binomial-based 95% confidence
+/// interval of the quantile (see [`quantile_diff_ci`]).
+///
+fn quantile_with_std_err(sorted: &[f64], quantile: f64) -> CIResult<(f64, f64)> {
+    let n = sorted.len();
+    let estimate = sorted[quantile::Stats::new(n).index(quantile)?];
+
+    let z = z_value(Confidence::new_two_sided(0.95));
+    let center = quantile * n as f64;
+    let half_width = z * (center * (1. - quantile)).sqrt();
+    let lo = (center - half_width).floor();
+    let hi = (center + half_width).ceil();
+    if lo < 0. || hi > (n - 1) as f64 {
+        return Err(CIError::TooFewSamples(n));
+    }
+
+    let std_err = (sorted[hi as usize] - sorted[lo as usize]) / (2. * z);
+    Ok((estimate, std_err))
+}
+
+///
+/// Bootstrap confidence interval on the difference between the medians of two paired samples
+/// \\( \tilde{a} - \tilde{b} \\), given as pairs \\( (a_i, b_i) \\).
+///
+/// The pairs are resampled with replacement, so that the pairing (i.e., the correlation between
+/// the samples) is preserved, and the interval is the percentile interval of the differences
+/// \\( \tilde{a}^* - \tilde{b}^* \\) between the medians of the resampled first and second
+/// samples. The median of an even number of values is the mean of the two middle values.
+///
+/// The resamples are drawn from `rng`: with a seeded generator whose algorithm is portable
+/// (e.g., `rand_chacha::ChaCha8Rng`, unlike `rand::rngs::StdRng`), the interval is reproducible.
+///
+/// Complexity: \\( O(r (n + \log r)) \\), where \\( r \\) is the number of resamples and
+/// \\( n \\) the number of pairs
+///
+/// This function requires the `bootstrap` feature.
+///
+/// # Difference of the medians vs. median of the differences
+///
+/// For paired data, the difference of the medians is a different estimand from the median of
+/// the differences \\( \operatorname{median}(a_i - b_i) \\), whose interval is obtained without
+/// resampling as the interval on the median of the differences (e.g., [`quantile::ci`] applied
+/// to [`PairedRobust::differences`]). Unlike the means, the two do not coincide in general:
+///
+/// * the __difference of the medians__ compares the typical values of the two conditions
+///   (e.g., "the median latency went from 10 ms to 12 ms"), whatever the pairing;
+/// * the __median of the differences__ is the typical change of a single item (e.g., "for the
+///   typical request, the latency increased by 1 ms"), which is what a paired design measures.
+///
+/// For instance, if all items but a few get slightly faster while the others get much slower,
+/// the median of the differences is the (small) improvement, whereas the difference of the
+/// medians may be zero or even of the opposite sign.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (two-sided or one-sided)
+/// * `pairs` - the pairs of observations \\( (a_i, b_i) \\)
+/// * `resamples` - the number of resamples
+/// * `rng` - the random number generator used for resampling
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if there are fewer than 2 pairs
+/// * [`CIError::TooFewResamples`] - if `resamples` is too small for the confidence level, i.e.,
+///   if fewer than 25 resampled statistics are expected beyond each bound
+///   (at least 1000 resamples for a two-sided 95% interval, or 500 for a one-sided one)
+/// * [`CIError::InvalidInputData`] - if some observation is not finite
+/// * [`CIError::FloatConversionError`] - if some observation cannot be converted to `f64`
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use rand::SeedableRng;
+/// // the same 20 durations before and after a change, rotated by 5 positions: 15 items got
+/// // 5 units faster, and 5 items got 15 units slower
+/// let before = (1..=20).map(f64::from).collect::<Vec<_>>();
+/// let after = (0..20).map(|i| before[(i + 15) % 20]).collect::<Vec<_>>();
+/// let pairs = after.iter().copied().zip(before.iter().copied()).collect::<Vec<_>>();
+/// let confidence = Confidence::new_two_sided(0.95);
+///
+/// // the medians are the same...
+/// let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(42);
+/// let ci = comparison::paired_median_diff_ci(confidence, &pairs, 2_000, &mut rng)?;
+/// assert!(ci.contains(&0.));
+///
+/// // ...but the typical item got 5 units faster
+/// let mut stats = comparison::PairedRobust::default();
+/// stats.extend(&after, &before)?;
+/// let differences = stats.differences().to_vec();
+/// let ci = quantile::ci(confidence, &differences, 0.5)?;
+/// assert_eq!(ci, Interval::new(-5., -5.)?);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "bootstrap"))]
+pub fn paired_median_diff_ci<T: Float>(
+    confidence: Confidence,
+    pairs: &[(T, T)],
+    resamples: usize,
+    rng: &mut impl rand::Rng,
+) -> CIResult<Interval<f64>> {
+    if pairs.len() < 2 {
+        return Err(CIError::TooFewSamples(pairs.len()));
+    }
+    let pairs = pairs
+        .iter()
+        .map(|(a, b)| Ok((a.try_f64("data_a")?, b.try_f64("data_b")?)))
+        .collect::<CIResult<Vec<_>>>()?;
+    if pairs.iter().any(|(a, b)| !a.is_finite() || !b.is_finite()) {
+        return Err(CIError::InvalidInputData);
+    }
+    let required = mean::required_bootstrap_resamples(confidence);
+    if resamples < required {
+        return Err(CIError::TooFewResamples {
+            resamples,
+            required,
+        });
+    }
+
+    let n = pairs.len();
+    let (mut resample_a, mut resample_b) = (vec![0.; n], vec![0.; n]);
+    let mut statistics = Vec::with_capacity(resamples);
+    for _ in 0..resamples {
+        for (a, b) in resample_a.iter_mut().zip(resample_b.iter_mut()) {
+            (*a, *b) = pairs[rng.gen_range(0..n)];
+        }
+        statistics.push(median_in_place(&mut resample_a) - median_in_place(&mut resample_b));
+    }
+    statistics.sort_by(f64::total_cmp);
+
+    let quantile = confidence.quantile();
+    let low = mean::bootstrap_order_statistic(&statistics, 1. - quantile);
+    let high = mean::bootstrap_order_statistic(&statistics, quantile);
+    Interval::from_finite_bounds_for(confidence, low, high)
+}
+
+///
+/// Median of (non-empty) finite values, rearranging them in the process.
+///
+#[cfg(any(test, feature = "bootstrap"))]
+fn median_in_place(values: &mut [f64]) -> f64 {
+    let len = values.len();
+    let (lower, &mut upper, _) = values.select_nth_unstable_by(len / 2, f64::total_cmp);
+    if len % 2 == 1 {
+        upper
+    } else {
+        let lower = lower.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (lower + upper) / 2.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_paired() {
+        {
+            // Case 1
+            // based on example from https://online.stat.psu.edu/stat500/lesson/7/7.3/7.3.2
+
+            // Zinc concentration in water samples from a river
+            let data_bottom_water = [
+                0.430, 0.266, 0.567, 0.531, 0.707, 0.716, 0.651, 0.589, 0.469, 0.723,
+            ];
+            let data_surface_water = [
+                0.415, 0.238, 0.390, 0.410, 0.605, 0.609, 0.632, 0.523, 0.411, 0.612,
+            ];
+
+            let ci = Paired::ci(
+                Confidence::new_two_sided(0.95),
+                &data_bottom_water,
+                &data_surface_water,
+            )
+            .unwrap();
+
+            println!("ci = {} (ref: )", ci);
+            println!("reference: (0.04299, 0.11781)");
+            assert_abs_diff_eq!(ci, Interval::new(0.04299, 0.11781).unwrap(), epsilon = 1e-4);
+        }
+        {
+            // Case 2
+            // based on example from https://www.khanacademy.org/math/ap-statistics/xfb5d8e68:inference-quantitative-means/one-sample-t-interval-mean/a/one-sample-t-interval-paired-data
+
+            let data_watch_a = [9.8, 9.8, 10.1, 10.1, 10.2];
+            let data_watch_b = [10.1, 10., 10.2, 9.9, 10.1];
+            let ci = Paired::ci(
+                Confidence::new_two_sided(0.95),
+                &data_watch_b,
+                &data_watch_a,
+            )
+            .unwrap();
+
+            println!("ci = {}", ci);
+            println!("reference: (-0.20, 0.32)");
+            assert_abs_diff_eq!(ci, Interval::new(-0.20, 0.32).unwrap(), epsilon = 1e-2);
+
+            let data_pre = [140., 152., 153., 159., 150., 146.];
+            let data_post = [150., 159., 170., 164., 148., 166.];
+            let ci = Paired::ci(Confidence::new_two_sided(0.95), &data_post, &data_pre).unwrap();
+
+            println!("ci = {}", ci);
+            println!("reference: (1.03,17.97)");
+            assert_abs_diff_eq!(ci, Interval::new(1.03, 17.97).unwrap(), epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_degrees_of_freedom() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let data_a = [1., 2., 3., 5.];
+        let data_b = [4., 5., 6., 7.];
+
+        let mut paired = Paired::default();
+        paired.extend(&data_a, &data_b)?;
+        assert_eq!(paired.degrees_of_freedom(), 3.);
+        assert_eq!(
+            paired.degrees_of_freedom(),
+            paired.ci_mean_detailed(confidence)?.degrees_of_freedom
+        );
+        assert!(!paired.uses_normal_approximation());
+        assert_eq!(Paired::<f64>::default().degrees_of_freedom(), 0.);
+
+        let unpaired = Unpaired::from_iter(&[1., 2., 3.], &[4., 5., 6., 8.])?;
+        assert_abs_diff_eq!(unpaired.degrees_of_freedom(), 6.4175, epsilon = 1e-4);
+        assert_eq!(
+            unpaired.degrees_of_freedom(),
+            unpaired.ci_mean_detailed(confidence)?.degrees_of_freedom
+        );
+        assert!(!unpaired.uses_normal_approximation());
+        // constant samples, and too few samples
+        let constant = Unpaired::from_iter(&[1., 1., 1.], &[2., 2.])?;
+        assert_eq!(constant.degrees_of_freedom(), 3.);
+        assert_eq!(
+            constant.degrees_of_freedom(),
+            constant.ci_mean_detailed(confidence)?.degrees_of_freedom
+        );
+        assert_eq!(
+            Unpaired::from_iter(&[1.], &[2., 3.])?.degrees_of_freedom(),
+            0.
+        );
+
+        // effective degrees of freedom, as used by the interval
+        for stats in [unpaired, constant] {
+            assert_eq!(
+                stats.effective_dof()?,
+                stats.ci_mean_detailed(confidence)?.degrees_of_freedom
+            );
+        }
+        let unpaired_f32 = Unpaired::from_iter(&[1_f32, 2., 3.], &[4., 5., 6., 8.])?;
+        assert_eq!(
+            unpaired_f32.effective_dof()?,
+            unpaired_f32
+                .ci_mean_detailed(confidence)?
+                .degrees_of_freedom
+        );
+        assert_eq!(
+            Unpaired::from_iter(&[1., 2.], &[2.])?.effective_dof(),
+            Err(CIError::TooFewSamples(1))
+        );
+
+        // large samples use the normal distribution
+        let large = Unpaired::new(
+            mean::Arithmetic::from_counts([(1., 60_000), (2., 60_000)])?,
+            mean::Arithmetic::from_counts([(1., 60_000), (3., 60_000)])?,
+        );
+        assert!(large.degrees_of_freedom() > 100_000.);
+        assert!(large.uses_normal_approximation());
+        let mut large_paired = Paired::default();
+        large_paired.extend(
+            &vec![1.; 100_001],
+            &(0..100_001).map(|i| i as f64).collect::<Vec<_>>(),
+        )?;
+        assert_eq!(large_paired.degrees_of_freedom(), 100_000.);
+        assert!(large_paired.uses_normal_approximation());
+        Ok(())
+    }
+
+    #[test]
+    fn test_margin() -> CIResult<()> {
+        let data_a = [10.2, 9.7, 10.5, 9.9, 10.1, 9.8, 10.4, 10.0];
+        let data_b = [11.1, 10.9, 11.6, 10.8, 11.2, 11.0, 11.5, 10.9];
+        let stats = Paired::from_pairs(data_a.into_iter().zip(data_b))?;
+        let confidence = Confidence::new(0.95);
+
+        // the verdict flips once the margin exceeds the guaranteed difference
+        let upper = stats.ci_mean(Confidence::new_lower(0.95))?.high_f();
+        assert!(upper < 0.);
+        let mut verdicts = vec![];
+        for i in 0..=20 {
+            let margin = i as f64 * 0.1;
+            let result = stats.test_margin(confidence, margin, Direction::FirstSmaller)?;
+            assert_eq!(result.bound, upper);
+            assert_eq!(result.margin, margin);
+            assert_abs_diff_eq!(result.headroom, -upper - margin);
+            assert_eq!(result.passed, margin < -upper);
+            verdicts.push(result.passed);
+        }
+        assert!(verdicts[0]);
+        assert!(!verdicts[20]);
+        assert!(verdicts.windows(2).all(|w| w[0] || !w[1]));
+
+        // the opposite direction never passes with a non-negative margin
+        let result = stats.test_margin(confidence, 0., Direction::FirstLarger)?;
+        assert!(!result.passed);
+        assert_eq!(result.confidence, Confidence::new_upper(0.95));
+        assert_eq!(
+            result.bound,
+            stats.ci_mean(Confidence::new_upper(0.95))?.low_f()
+        );
+        // ... but a large enough negative margin (non-inferiority) does
+        assert!(
+            stats
+                .test_margin(confidence, -2., Direction::FirstLarger)?
+                .passed
+        );
+
+        // the kind of the confidence is ignored
+        for kind in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.95),
+            Confidence::new_lower(0.95),
+        ] {
+            assert_eq!(
+                stats.test_margin(kind, 0.5, Direction::FirstSmaller)?,
+                stats.test_margin(confidence, 0.5, Direction::FirstSmaller)?
+            );
+        }
+
+        assert!(matches!(
+            stats.test_margin(confidence, f64::NAN, Direction::FirstSmaller),
+            Err(CIError::InvalidMargin(_))
+        ));
+        assert_eq!(
+            Paired::<f64>::default().test_margin(confidence, 0., Direction::FirstSmaller),
+            Err(CIError::TooFewSamples(0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_variance_ratio() -> CIResult<()> {
+        // reference values: R
+        // > var.test(1:10, seq(2, 20, 2))$conf.int
+        // [1] 0.06209646 1.00649854
+        // > var.test(1:10, seq(2, 20, 2), alternative = "less")$conf.int
+        // [1] 0.0000000 0.7947233
+        // > var.test(1:10, seq(2, 20, 2), alternative = "greater")$conf.int
+        // [1] 0.07864373        Inf
+        let data_a = (1..=10).map(f64::from).collect::<Vec<_>>();
+        let data_b = data_a.iter().map(|x| 2. * x).collect::<Vec<_>>();
+        let stats = Unpaired::from_iter(&data_a, &data_b)?;
+        let ci = stats.ci_variance_ratio(Confidence::new_two_sided(0.95))?;
+        assert_abs_diff_eq!(ci, Interval::new(0.06209646, 1.00649854)?, epsilon = 1e-8);
+        let ci = stats.ci_variance_ratio(Confidence::new_lower(0.95))?;
+        assert!(ci.is_lower());
+        assert_abs_diff_eq!(ci.high_f(), 0.7947233, epsilon = 1e-7);
+        let ci = stats.ci_variance_ratio(Confidence::new_upper(0.95))?;
+        assert!(ci.is_upper());
+        assert_abs_diff_eq!(ci.low_f(), 0.07864373, epsilon = 1e-8);
+
+        // the interval on the inverse ratio is the inverse of the interval
+        let swapped = Unpaired::from_iter(&data_b, &data_a)?;
+        let ci = swapped.ci_variance_ratio(Confidence::new_two_sided(0.95))?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(1. / 1.00649854, 1. / 0.06209646)?,
+            epsilon = 1e-6
+        );
+
+        // f32 samples
+        let stats_f32 = Unpaired::from_iter(
+            &data_a.iter().map(|&x| x as f32).collect::<Vec<_>>(),
+            &data_b.iter().map(|&x| x as f32).collect::<Vec<_>>(),
+        )?;
+        let ci = stats_f32.ci_variance_ratio(Confidence::new_two_sided(0.95))?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(0.062_096_46, 1.006_498_6)?,
+            epsilon = 1e-6
+        );
+
+        // errors
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(
+            Unpaired::from_iter(&[1., 2.], &[2.])?.ci_variance_ratio(confidence),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert_eq!(
+            Unpaired::from_iter(&[1.], &[2., 3.])?.ci_variance_ratio(confidence),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert!(matches!(
+            Unpaired::from_iter(&[1., 2.], &[2., 2., 2.])?.ci_variance_ratio(confidence),
+            Err(CIError::ZeroVariance(_))
+        ));
+        // a constant first sample has a degenerate ratio
+        assert_eq!(
+            Unpaired::from_iter(&[2., 2., 2.], &[1., 2.])?.ci_variance_ratio(confidence)?,
+            Interval::new(0., 0.)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unpaired() {
+        // based on example from https://www.statsdirect.co.uk/help/parametric_methods/utt.htm
+        // itself based on Armitage P, Berry G. Statistical Methods in Medical Research (3rd edition). Blackwell 1994.
+        // Consider the gain in weight of 19 female rats between 28 and 84 days after birth. 12 were fed on a high protein diet and 7 on a low protein diet.
+        let data_high_protein = [
+            134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
+        ];
+        let data_low_protein = [70., 118., 101., 85., 107., 132., 94.];
+        let ci = Unpaired::ci(
+            Confidence::new_two_sided(0.95),
+            &data_high_protein,
+            &data_low_protein,
         )
         .unwrap();
 
@@ -987,6 +4208,761 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unpaired_detailed() -> CIResult<()> {
+        let data_high_protein = [
+            134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
+        ];
+        let data_low_protein = [70., 118., 101., 85., 107., 132., 94.];
+        let stats = Unpaired::from_iter(&data_high_protein, &data_low_protein)?;
+        let confidence = Confidence::new_two_sided(0.95);
+        let detail = stats.ci_mean_detailed(confidence)?;
+        assert_eq!(detail.interval, stats.ci_mean(confidence)?);
+        assert_eq!(
+            detail.estimate,
+            stats.stats_a().sample_mean() - stats.stats_b().sample_mean()
+        );
+        assert_eq!(detail.distribution, mean::CriticalDistribution::StudentT);
+        let span = detail.critical_value * detail.std_err;
+        assert_eq!(
+            detail.interval,
+            Interval::new(detail.estimate - span, detail.estimate + span)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_report() -> CIResult<()> {
+        let data_high_protein = [
+            134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
+        ];
+        let data_low_protein = [70., 118., 101., 85., 107., 132., 94.];
+        let stats = Unpaired::from_iter(&data_high_protein, &data_low_protein)?;
+        let confidence = Confidence::new_two_sided(0.95);
+        let report = stats.report(confidence)?;
+        assert_eq!(report.group_a.count, 12);
+        assert_eq!(report.group_b.count, 7);
+        assert_eq!(report.group_a.mean, stats.stats_a().sample_mean());
+        assert_eq!(report.group_b.std_dev, stats.stats_b().sample_std_dev());
+        assert_eq!(report.group_b.sem, stats.stats_b().sample_sem());
+        assert_eq!(report.difference, stats.ci_mean(confidence)?);
+        assert_eq!(report.confidence, confidence);
+        assert!(!report.significant);
+        assert!(report.to_string().ends_with(", not significant"));
+        assert_eq!(report.to_string().lines().count(), 3);
+
+        let mut paired = Paired::default();
+        let data_high_protein: [f64; 7] = data_high_protein[..7].try_into().unwrap();
+        paired.extend(&data_low_protein, &data_high_protein)?;
+        let report = paired.report(confidence)?;
+        assert_eq!(report.count, 7);
+        assert_eq!(report.mean_difference, paired.sample_mean());
+        assert_eq!(report.sem, paired.sample_sem());
+        assert_eq!(report.difference, paired.ci_mean(confidence)?);
+        assert_eq!(
+            report.significant,
+            paired.significance(confidence)?.is_significant()
+        );
+        assert_eq!(report.to_string().lines().count(), 2);
+
+        // too few samples
+        let stats = Unpaired::from_iter(&[1.], &[2., 3.])?;
+        assert!(stats.report(confidence).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_summaries() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // zinc concentration example from https://online.stat.psu.edu/stat500/lesson/7/7.3/7.3.2
+        // where the course works from d̄ = 0.0804, s_d = 0.0523 and n = 10:
+        // 0.0804 ± 2.262 * 0.0523 / sqrt(10) = (0.04299, 0.11781)
+        let ci = Paired::from_summary(10, 0.0804, 0.0523)?.ci_mean(confidence)?;
+        assert_abs_diff_eq!(ci, Interval::new(0.04299, 0.11781)?, epsilon = 1e-5);
+
+        // same data with exact summaries must match the raw-data computation
+        let data_bottom_water = [
+            0.430, 0.266, 0.567, 0.531, 0.707, 0.716, 0.651, 0.589, 0.469, 0.723,
+        ];
+        let data_surface_water = [
+            0.415, 0.238, 0.390, 0.410, 0.605, 0.609, 0.632, 0.523, 0.411, 0.612,
+        ];
+        let raw = Paired::ci(confidence, &data_bottom_water, &data_surface_water)?;
+        let ci = Paired::from_summary(10, 0.0804, 0.052_273_213_875_644_64)?.ci_mean(confidence)?;
+        assert_abs_diff_eq!(ci, raw, epsilon = 1e-12);
+
+        // rat diet example (see `test_unpaired`): summaries computed from the raw data
+        let data_high_protein = [
+            134., 146., 104., 119., 124., 161., 107., 83., 113., 129., 97., 123.,
+        ];
+        let data_low_protein = [70., 118., 101., 85., 107., 132., 94.];
+        let raw = Unpaired::ci(confidence, &data_high_protein, &data_low_protein)?;
+        let stats = Unpaired::from_summaries(
+            12,
+            120.,
+            21.388_187_053_945_117,
+            7,
+            101.,
+            20.623_611_064_344_026,
+        )?;
+        assert_eq!(stats.stats_a().sample_count(), 12);
+        assert_eq!(stats.stats_b().sample_count(), 7);
+        assert_abs_diff_eq!(stats.ci_mean(confidence)?, raw, epsilon = 1e-10);
+
+        assert_eq!(
+            Unpaired::from_summaries(1, 120., 21., 7, 101., 20.),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert_eq!(
+            Unpaired::from_summaries(12, 120., 21., 7, 101., -20.),
+            Err(CIError::InvalidInputData)
+        );
+        assert_eq!(
+            Paired::from_summary(10, f64::NAN, 0.05),
+            Err(CIError::InvalidInputData)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_owned_iterators() -> CIResult<()> {
+        let data_a = [
+            0.430, 0.266, 0.567, 0.531, 0.707, 0.716, 0.651, 0.589, 0.469, 0.723,
+        ];
+        let data_b = [
+            0.415, 0.238, 0.390, 0.410, 0.605, 0.609, 0.632, 0.523, 0.411, 0.612,
+        ];
+        let to_millis = |x: &f64| x * 1000.;
+
+        // reference path: collect into temporary collections
+        let tuples = data_a
+            .iter()
+            .zip(data_b.iter())
+            .map(|(a, b)| (to_millis(a), to_millis(b)))
+            .collect::<Vec<_>>();
+        let mut paired_ref = Paired::default();
+        paired_ref.extend_tuple(&tuples)?;
+
+        // zero-collect path
+        let mut paired = Paired::default();
+        paired.extend_pairs(
+            data_a
+                .iter()
+                .zip(data_b.iter())
+                .map(|(a, b)| (to_millis(a), to_millis(b))),
+        )?;
+        assert_eq!(paired, paired_ref);
+        let paired = Paired::from_pairs(
+            data_a
+                .iter()
+                .zip(data_b.iter())
+                .map(|(a, b)| (to_millis(a), to_millis(b))),
+        )?;
+        assert_eq!(paired, paired_ref);
+
+        let millis_a = data_a.iter().map(to_millis).collect::<Vec<_>>();
+        let millis_b = data_b.iter().map(to_millis).collect::<Vec<_>>();
+        let mut unpaired_ref = Unpaired::default();
+        unpaired_ref.extend_a(&millis_a)?;
+        unpaired_ref.extend_b(&millis_b)?;
+
+        let mut unpaired = Unpaired::default();
+        unpaired.extend_a_iter(data_a.iter().map(to_millis))?;
+        unpaired.extend_b_iter(data_b.iter().map(to_millis))?;
+        assert_eq!(unpaired, unpaired_ref);
+        Ok(())
+    }
+
+    fn exponential_sample<R: rand::Rng>(rng: &mut R, rate: f64, size: usize) -> Vec<f64> {
+        // inverse transform sampling
+        (0..size)
+            .map(|_| -(1. - rng.gen::<f64>()).ln() / rate)
+            .collect()
+    }
+
+    #[test]
+    fn test_quantile_diff_exponential() -> CIResult<()> {
+        use rand_chacha::ChaCha8Rng;
+        use rand_seeder::Seeder;
+
+        let mut rng: ChaCha8Rng = Seeder::from("quantile difference").make_rng();
+        let (rate_a, rate_b) = (1., 2.);
+        let repetitions = 400;
+        let sample_size = 1_000;
+
+        for quantile in [0.5, 0.9, 0.99] {
+            // quantile of the exponential distribution: -ln(1-q)/rate
+            let true_diff = -(1. - quantile).ln() * (1. / rate_a - 1. / rate_b);
+            let confidences = [
+                Confidence::new_two_sided(0.9),
+                Confidence::new_upper(0.9),
+                Confidence::new_lower(0.9),
+            ];
+            for confidence in confidences {
+                let mut hits = 0;
+                for _ in 0..repetitions {
+                    let data_a = exponential_sample(&mut rng, rate_a, sample_size);
+                    let data_b = exponential_sample(&mut rng, rate_b, sample_size);
+                    let ci = quantile_diff_ci(confidence, &data_a, &data_b, quantile)?;
+                    assert_eq!(ci.is_two_sided(), confidence.is_two_sided());
+                    assert_eq!(ci.is_upper(), confidence.is_upper());
+                    assert_eq!(ci.is_lower(), confidence.is_lower());
+                    if ci.contains(&true_diff) {
+                        hits += 1;
+                    }
+                }
+                let hit_rate = hits as f64 / repetitions as f64;
+                println!("quantile: {quantile}, confidence: {confidence:?}, hit rate: {hit_rate}");
+                assert!(hit_rate >= 0.85, "hit rate too low: {hit_rate}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_quantile_diff_errors() {
+        let data = (1..=50).map(|x| x as f64).collect::<Vec<_>>();
+        let confidence = Confidence::new_two_sided(0.95);
+        for quantile in [0., 1., -0.5, 1.5] {
+            assert!(matches!(
+                quantile_diff_ci(confidence, &data, &data, quantile),
+                Err(CIError::InvalidQuantile(_))
+            ));
+        }
+        assert_eq!(
+            quantile_diff_ci(confidence, &data, &data, 0.99),
+            Err(CIError::TooFewSamples(50))
+        );
+        assert_eq!(
+            quantile_diff_ci(confidence, &data, &data[..3].to_vec(), 0.5),
+            Err(CIError::TooFewSamples(3))
+        );
+        let mut with_nan = data.clone();
+        with_nan[10] = f64::NAN;
+        assert_eq!(
+            quantile_diff_ci(confidence, &data, &with_nan, 0.5),
+            Err(CIError::InvalidInputData)
+        );
+        let ci = quantile_diff_ci(confidence, &data, &data, 0.5).unwrap();
+        assert!(ci.contains(&0.));
+    }
+
+    #[test]
+    fn test_shift_ci_exponential() -> CIResult<()> {
+        use rand_chacha::ChaCha8Rng;
+        use rand_seeder::Seeder;
+
+        let mut rng: ChaCha8Rng = Seeder::from("location shift").make_rng();
+        let shift = 2.;
+        let repetitions = 400;
+        let confidences = [
+            Confidence::new_two_sided(0.9),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.9),
+        ];
+        // including samples of very different lengths
+        for (size_a, size_b) in [(200, 200), (100, 5_000), (5_000, 100)] {
+            for confidence in confidences {
+                let mut hits = 0;
+                for _ in 0..repetitions {
+                    let data_a = exponential_sample(&mut rng, 1., size_a)
+                        .into_iter()
+                        .map(|x| x + shift)
+                        .collect::<Vec<_>>();
+                    let data_b = exponential_sample(&mut rng, 1., size_b);
+                    let ci = shift_ci(confidence, &data_a, &data_b)?;
+                    assert_eq!(ci.is_two_sided(), confidence.is_two_sided());
+                    assert_eq!(ci.is_upper(), confidence.is_upper());
+                    if ci.contains(&shift) {
+                        hits += 1;
+                    }
+                }
+                let hit_rate = hits as f64 / repetitions as f64;
+                println!(
+                    "sizes: {size_a}/{size_b}, confidence: {confidence:?}, hit rate: {hit_rate}"
+                );
+                assert!(hit_rate >= 0.85, "hit rate too low: {hit_rate}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_unpaired() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // a single bucket is the plain comparison
+        let data_a = [1., 2., 3., 5.];
+        let data_b = [4., 5., 6., 8., 9.];
+        let mut stats = StreamingUnpaired::default();
+        for &x in &data_a {
+            stats.append(Variant::A, x, 7)?;
+        }
+        for &x in &data_b {
+            stats.append(Variant::B, x, 7)?;
+        }
+        assert_abs_diff_eq!(
+            stats.ci_mean_overall(confidence)?,
+            Unpaired::ci(confidence, &data_a, &data_b)?,
+            epsilon = 1e-12
+        );
+        assert_eq!(stats.pooled(), Unpaired::from_iter(&data_a, &data_b)?);
+
+        // stratified estimate, computed by hand:
+        // d = 0.5 (2 - 3) + 0.5 (11 - 13) = -1.5
+        // SE^2 = 0.25 (1/3 + 2/2) + 0.25 (2/2 + 4/3) = 0.916667
+        // dof = SE^4 / (0.083333^2 / 4 + 0.25^2 / 3 + 0.25^2 / 3 + 0.333333^2 / 4) - 2 = 9.804878
+        let mut stats = StreamingUnpaired::new(2);
+        for (variant, value, bucket) in [
+            (Variant::A, 1., 0),
+            (Variant::A, 2., 0),
+            (Variant::A, 3., 0),
+            (Variant::B, 2., 0),
+            (Variant::B, 4., 0),
+            (Variant::A, 10., 1),
+            (Variant::A, 12., 1),
+            (Variant::B, 11., 1),
+            (Variant::B, 13., 1),
+            (Variant::B, 15., 1),
+        ] {
+            stats.append(variant, value, bucket)?;
+        }
+        let (critical_value, _) = stats::critical_value(confidence, 9.804_878)?;
+        let half_width = critical_value * 0.916_667_f64.sqrt();
+        assert_abs_diff_eq!(
+            stats.ci_mean_overall(confidence)?,
+            Interval::new(-1.5 - half_width, -1.5 + half_width)?,
+            epsilon = 1e-5
+        );
+        let upper = stats.ci_mean_overall(Confidence::new_upper(0.95))?;
+        assert!(upper.is_upper());
+        assert!(upper.low_f() > -1.5 - half_width);
+
+        // constant buckets
+        let mut constant = StreamingUnpaired::new(2);
+        for bucket in [0, 1] {
+            for _ in 0..2 {
+                constant.append(Variant::A, 1. + bucket as f64, bucket)?;
+                constant.append(Variant::B, 3. + bucket as f64, bucket)?;
+            }
+        }
+        assert_eq!(
+            constant.ci_mean_overall(confidence)?,
+            Interval::new(-2., -2.)?
+        );
+
+        // exclusion of buckets with too few observations of either variant
+        stats.append(Variant::A, 100., 2)?;
+        stats.append(Variant::A, 101., 2)?;
+        stats.append(Variant::B, 100., 2)?;
+        stats.append(Variant::B, 1000., 3)?;
+        assert_abs_diff_eq!(
+            stats.ci_mean_overall(confidence)?.center().unwrap(),
+            -1.5,
+            epsilon = 1e-12
+        );
+        let report = stats.imbalance_report();
+        assert_eq!(report.included_buckets, vec![0, 1]);
+        assert_eq!(report.excluded_buckets, vec![2, 3]);
+        assert_eq!(report.included_observations, 10);
+        assert_eq!(report.excluded_observations, 4);
+        assert_abs_diff_eq!(report.fraction_dropped(), 4. / 14., epsilon = 1e-12);
+        assert_eq!(
+            report.to_string(),
+            "2 of 4 buckets excluded, 28.6% of the observations dropped"
+        );
+        let strict = StreamingUnpaired {
+            min_per_variant: 3,
+            ..stats.clone()
+        };
+        assert!(strict.imbalance_report().included_buckets.is_empty());
+        assert_eq!(
+            strict.ci_mean_overall(confidence),
+            Err(CIError::TooFewSamples(0))
+        );
+        assert_eq!(StreamingUnpaired::<f64>::new(0).min_per_variant(), 2);
+        assert_eq!(
+            StreamingUnpaired::<f64>::default().ci_mean_overall(confidence),
+            Err(CIError::TooFewSamples(0))
+        );
+        assert_eq!(
+            StreamingUnpaired::<f64>::default()
+                .imbalance_report()
+                .fraction_dropped(),
+            0.
+        );
+
+        // merging aligns the buckets
+        let mut first = StreamingUnpaired::new(2);
+        let mut second = StreamingUnpaired::new(2);
+        for (i, (variant, bucket)) in [(Variant::A, 0), (Variant::B, 1), (Variant::A, 1)]
+            .into_iter()
+            .cycle()
+            .take(30)
+            .enumerate()
+        {
+            let value = (i * 7 % 11) as f64;
+            if i % 2 == 0 {
+                first.append(variant, value, bucket + i as u32 % 3)?;
+            } else {
+                second.append(variant, value, bucket + i as u32 % 3)?;
+            }
+            stats.append(variant, value, bucket + i as u32 % 3)?;
+        }
+        let mut merged = StreamingUnpaired::new(2);
+        for (variant, value, bucket) in [
+            (Variant::A, 1., 0),
+            (Variant::A, 2., 0),
+            (Variant::A, 3., 0),
+            (Variant::B, 2., 0),
+            (Variant::B, 4., 0),
+            (Variant::A, 10., 1),
+            (Variant::A, 12., 1),
+            (Variant::B, 11., 1),
+            (Variant::B, 13., 1),
+            (Variant::B, 15., 1),
+            (Variant::A, 100., 2),
+            (Variant::A, 101., 2),
+            (Variant::B, 100., 2),
+            (Variant::B, 1000., 3),
+        ] {
+            merged.append(variant, value, bucket)?;
+        }
+        merged += first;
+        let merged = merged + second;
+        assert_eq!(merged.buckets().count(), stats.buckets().count());
+        for ((id, merged), (id_ref, reference)) in merged.buckets().zip(stats.buckets()) {
+            assert_eq!(id, id_ref);
+            for (a, b) in [
+                (merged.stats_a(), reference.stats_a()),
+                (merged.stats_b(), reference.stats_b()),
+            ] {
+                assert_eq!(a.sample_count(), b.sample_count());
+                assert_abs_diff_eq!(a.sample_mean(), b.sample_mean(), epsilon = 1e-12);
+                if a.sample_count() > 1 {
+                    assert_abs_diff_eq!(a.sample_std_dev(), b.sample_std_dev(), epsilon = 1e-12);
+                }
+            }
+        }
+
+        // merging states with different minimums is rejected
+        assert_eq!(
+            stats.clone().try_add(StreamingUnpaired::new(3)),
+            Err(CIError::DifferentMinPerVariant { min_a: 2, min_b: 3 })
+        );
+        assert_eq!(stats.clone().try_add(StreamingUnpaired::new(2))?, stats);
+
+        let closed = utils::Accumulator::reset(&mut stats);
+        assert_eq!(closed.buckets().count(), 4);
+        assert_eq!(stats, StreamingUnpaired::new(2));
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_streaming_unpaired_add_different_min() {
+        let _ = StreamingUnpaired::<f64>::new(2) + StreamingUnpaired::new(3);
+    }
+
+    #[test]
+    fn test_streaming_unpaired_drift() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1675);
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // A is faster by 2 units, but most of its traffic is at peak hours (bucket 1),
+        // when everything is 50 units slower; B is mostly observed off-peak (bucket 0)
+        let true_difference = -2.;
+        let mut stats = StreamingUnpaired::new(5);
+        for _ in 0..1000 {
+            for (variant, peak_fraction, effect) in
+                [(Variant::A, 0.9, true_difference), (Variant::B, 0.1, 0.)]
+            {
+                let bucket = u32::from(rng.gen::<f64>() < peak_fraction);
+                let value = 100. + 50. * bucket as f64 + effect + 10. * rng.gen::<f64>();
+                stats.append(variant, value, bucket)?;
+            }
+        }
+
+        // the naive comparison concludes that A is slower
+        let naive = stats.pooled().ci_mean(confidence)?;
+        assert!(naive.low_f() > 0., "{naive}");
+        // the bucketed comparison recovers the true difference
+        let bucketed = stats.ci_mean_overall(confidence)?;
+        assert!(bucketed.high_f() < 0., "{bucketed}");
+        assert!(bucketed.contains(&true_difference), "{bucketed}");
+        assert!(stats.imbalance_report().excluded_buckets.is_empty());
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_streaming_unpaired_serde() -> CIResult<()> {
+        let mut stats = StreamingUnpaired::new(3);
+        stats.append(Variant::A, 1., 4)?;
+        stats.append(Variant::B, 2., 4)?;
+        stats.append(Variant::B, 5., 9)?;
+        let serialized = serde_json::to_string(&stats).unwrap();
+        let deserialized: StreamingUnpaired<f64> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, stats);
+
+        let report = stats.imbalance_report();
+        let serialized = toml::to_string(&report).unwrap();
+        let deserialized: ImbalanceReport = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, report);
+        Ok(())
+    }
+
+    #[test]
+    fn test_paired_median_diff_ci() -> CIResult<()> {
+        use rand::SeedableRng;
+        let seeded = |seed| rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // same durations rotated by 5 positions: the medians are equal, but 15 out of 20
+        // differences are -5
+        let before = (1..=20).map(f64::from).collect::<Vec<_>>();
+        let after = (0..20).map(|i| before[(i + 15) % 20]).collect::<Vec<_>>();
+        let pairs = after
+            .iter()
+            .copied()
+            .zip(before.iter().copied())
+            .collect::<Vec<_>>();
+        let ci = paired_median_diff_ci(confidence, &pairs, 2_000, &mut seeded(1671))?;
+        assert!(ci.contains(&0.));
+        assert!(!ci.contains(&-5.));
+        let differences = pairs.iter().map(|(a, b)| a - b).collect::<Vec<_>>();
+        assert_eq!(
+            quantile::ci(confidence, &differences, 0.5)?,
+            Interval::new(-5., -5.)?
+        );
+
+        // deterministic under a fixed seed, whatever the float type
+        assert_eq!(
+            paired_median_diff_ci(confidence, &pairs, 2_000, &mut seeded(1671))?,
+            ci
+        );
+        let pairs_f32 = pairs
+            .iter()
+            .map(|&(a, b)| (a as f32, b as f32))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paired_median_diff_ci(confidence, &pairs_f32, 2_000, &mut seeded(1671))?,
+            ci
+        );
+
+        // one-sided intervals share the resamples of the two-sided interval
+        let upper = paired_median_diff_ci(
+            Confidence::new_upper(0.975),
+            &pairs,
+            2_000,
+            &mut seeded(1671),
+        )?;
+        assert!(upper.is_upper());
+        assert_eq!(upper.low_f(), ci.low_f());
+        let lower = paired_median_diff_ci(
+            Confidence::new_lower(0.975),
+            &pairs,
+            2_000,
+            &mut seeded(1671),
+        )?;
+        assert!(lower.is_lower());
+        assert_eq!(lower.high_f(), ci.high_f());
+
+        // a constant shift of every pair is the difference of the medians of every resample
+        let shifted = before.iter().map(|&b| (b + 3., b)).collect::<Vec<_>>();
+        assert_eq!(
+            paired_median_diff_ci(confidence, &shifted, 1_000, &mut seeded(0))?,
+            Interval::new(3., 3.)?
+        );
+
+        // errors
+        assert_eq!(
+            paired_median_diff_ci(confidence, &pairs[..1], 1_000, &mut seeded(0)),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert_eq!(
+            paired_median_diff_ci(confidence, &pairs, 999, &mut seeded(0)),
+            Err(CIError::TooFewResamples {
+                resamples: 999,
+                required: 1_000
+            })
+        );
+        assert_eq!(
+            paired_median_diff_ci(
+                confidence,
+                &[(1., 2.), (f64::NAN, 3.)],
+                1_000,
+                &mut seeded(0)
+            ),
+            Err(CIError::InvalidInputData)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_median_in_place() {
+        assert_eq!(median_in_place(&mut [3.]), 3.);
+        assert_eq!(median_in_place(&mut [4., 1., 3.]), 3.);
+        assert_eq!(median_in_place(&mut [4., 1., 3., 2.]), 2.5);
+        assert_eq!(median_in_place(&mut [2., 2., 5., 1., 2., 9.]), 2.);
+    }
+
+    #[test]
+    fn test_shift_ci() -> CIResult<()> {
+        let data_a = (1..=80).map(|x| x as f64).collect::<Vec<_>>();
+        let data_b = (1..=80).map(|x| x as f64 - 5.).collect::<Vec<_>>();
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // a single quantile is the same as the difference of the quantiles
+        let ci = shift_ci_with_grid(confidence, &data_a, &data_b, &[0.5])?;
+        let expected = quantile_diff_ci(confidence, &data_a, &data_b, 0.5)?;
+        assert_abs_diff_eq!(ci, expected, epsilon = 1e-12);
+
+        // an exact shift is estimated exactly, for any grid
+        let ci = shift_ci(confidence, &data_a, &data_b)?;
+        assert_abs_diff_eq!(ci.low_f() + ci.high_f(), 10., epsilon = 1e-12);
+        let ci = shift_ci_with_grid(confidence, &data_a, &data_b, &[0.3, 0.5, 0.5, 0.7])?;
+        assert_abs_diff_eq!(ci.low_f() + ci.high_f(), 10., epsilon = 1e-12);
+        let ci = shift_ci(confidence, &data_b, &data_a)?;
+        assert_abs_diff_eq!(ci.low_f() + ci.high_f(), -10., epsilon = 1e-12);
+
+        let data_a = data_a.iter().map(|&x| x as f32).collect::<Vec<_>>();
+        let data_b = data_b.iter().map(|&x| x as f32).collect::<Vec<_>>();
+        let ci = shift_ci(Confidence::new_lower(0.95), &data_a, &data_b)?;
+        assert!(ci.is_lower());
+        assert!(ci.contains(&5.));
+        Ok(())
+    }
+
+    #[test]
+    fn test_shift_ci_errors() {
+        let data = (1..=100).map(|x| x as f64).collect::<Vec<_>>();
+        let confidence = Confidence::new_two_sided(0.95);
+        for quantile in [0., 1., -0.5, 1.5, f64::NAN] {
+            assert!(matches!(
+                shift_ci_with_grid(confidence, &data, &data, &[0.5, quantile]),
+                Err(CIError::InvalidQuantile(_))
+            ));
+        }
+        assert_eq!(
+            shift_ci_with_grid(confidence, &data, &data, &[]),
+            Err(CIError::EmptyQuantileGrid)
+        );
+        // the extreme deciles require enough samples
+        assert!(shift_ci(confidence, &data, &data[..55]).is_ok());
+        assert_eq!(
+            shift_ci(confidence, &data, &data[..50]),
+            Err(CIError::TooFewSamples(50))
+        );
+        assert_eq!(
+            shift_ci(confidence, &data[..50], &data),
+            Err(CIError::TooFewSamples(50))
+        );
+        let mut with_nan = data.clone();
+        with_nan[10] = f64::NAN;
+        assert_eq!(
+            shift_ci(confidence, &data, &with_nan),
+            Err(CIError::InvalidInputData)
+        );
+    }
+
+    #[test]
+    fn test_unpaired_zero_variance() -> CIResult<()> {
+        // both samples constant: the difference is known exactly
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = Unpaired::ci(confidence, &[1., 1.], &[2., 2., 2.])?;
+        assert_eq!(ci, Interval::new(-1., -1.)?);
+        let detail = Unpaired::from_iter(&[1., 1.], &[2., 2., 2.])?.ci_mean_detailed(confidence)?;
+        assert_eq!(detail.std_err, 0.);
+        assert_eq!(detail.degrees_of_freedom, 3.);
+        let ci = Unpaired::ci(Confidence::new_lower(0.9), &[1., 1.], &[2., 2.])?;
+        assert_eq!(ci, Interval::new_lower(-1.));
+
+        // one constant sample: the uncertainty comes from the other sample only
+        let data_b = [4., 5., 6., 8.];
+        let ci = Unpaired::ci(confidence, &[1., 1., 1.], &data_b)?;
+        let ci_b = mean::Arithmetic::ci(confidence, &data_b)?;
+        assert_abs_diff_eq!(ci, -ci_b + 1., epsilon = 1e-12);
+
+        // paired samples with a constant difference
+        let ci = Paired::ci(confidence, &[2., 3., 5.], &[1., 2., 4.])?;
+        assert_eq!(ci, Interval::new(1., 1.)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_significance() -> CIResult<()> {
+        let data_a = [10.1, 11.3, 9.8, 10.7, 10.4, 11.0];
+        let data_b = [12.2, 12.9, 11.8, 13.1, 12.5, 12.4];
+        let overlapping = [10.3, 10.9, 10.0, 11.1, 10.2, 10.8];
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.95),
+            Confidence::new_lower(0.95),
+        ] {
+            let unpaired = Unpaired::from_iter(&data_a, &data_b)?;
+            let paired = Paired::from_pairs(data_a.into_iter().zip(data_b))?;
+            let expected = match confidence {
+                // an upper one-sided interval can only be positively significant
+                Confidence::UpperOneSided(_) => Significance::NotSignificant,
+                _ => Significance::NegativelySignificant,
+            };
+            assert_eq!(unpaired.significance(confidence)?, expected);
+            assert_eq!(paired.significance(confidence)?, expected);
+
+            let unpaired = Unpaired::from_iter(&data_b, &data_a)?;
+            let paired = Paired::from_pairs(data_b.into_iter().zip(data_a))?;
+            let expected = match confidence {
+                // a lower one-sided interval can only be negatively significant
+                Confidence::LowerOneSided(_) => Significance::NotSignificant,
+                _ => Significance::PositivelySignificant,
+            };
+            assert_eq!(unpaired.significance(confidence)?, expected);
+            assert_eq!(paired.significance(confidence)?, expected);
+
+            let unpaired = Unpaired::from_iter(&data_a, &overlapping)?;
+            let paired = Paired::from_pairs(data_a.into_iter().zip(overlapping))?;
+            assert_eq!(
+                unpaired.significance(confidence)?,
+                Significance::NotSignificant
+            );
+            assert_eq!(
+                paired.significance(confidence)?,
+                Significance::NotSignificant
+            );
+        }
+
+        assert_eq!(
+            Paired::<f64>::default().significance(Confidence::new_two_sided(0.95)),
+            Err(CIError::TooFewSamples(0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_too_few_samples() {
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(
+            Unpaired::ci(confidence, &[1.], &[2., 3., 4.]),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert_eq!(
+            Unpaired::ci(confidence, &[1., 2., 3.], &[]),
+            Err(CIError::TooFewSamples(0))
+        );
+        assert_eq!(
+            Paired::<f64>::ci(confidence, &[], &[]),
+            Err(CIError::TooFewSamples(0))
+        );
+        // a single pair gives a degenerate interval
+        assert_eq!(
+            Paired::ci(confidence, &[1.], &[2.]).unwrap(),
+            Interval::new(-1., -1.).unwrap()
+        );
+    }
+
     #[test]
     fn test_paired_diff_length() {
         let sample_size = 10;
@@ -999,14 +4975,260 @@ mod tests {
 
         let mut stats = comparison::Paired::default();
         let res = stats.extend(&data1, &data2);
-        assert!(res.is_err());
-        match res.unwrap_err() {
-            CIError::DifferentSampleSizes(a, b) => {
-                println!("DifferentSampleSizes({a},{b})");
-                assert_eq!(a, sample_size);
-                assert_eq!(b, sample_size + 1);
-            }
-            e => panic!("unexpected error: {}", e),
+        assert_eq!(
+            res,
+            Err(CIError::DifferentSampleSizes {
+                size_a: sample_size,
+                size_b: sample_size + 1,
+            })
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_construction() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1610);
+        let data_a: Vec<f64> = (0..100_003).map(|_| rng.gen_range(-1e3..1e6)).collect();
+        let data_b: Vec<f64> = (0..70_001).map(|_| rng.gen_range(-1e3..2e6)).collect();
+        let pairs: Vec<(f64, f64)> = data_a.iter().copied().zip(data_b.clone()).collect();
+
+        // parallel vs. sequential: equal within rounding errors
+        let parallel = Paired::from_pairs_parallel(&pairs);
+        let sequential = Paired::from_pairs(pairs.clone())?;
+        assert_eq!(parallel.sample_count(), sequential.sample_count());
+        assert_relative_eq!(
+            parallel.sample_mean(),
+            sequential.sample_mean(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            parallel.sample_sem(),
+            sequential.sample_sem(),
+            max_relative = 1e-12
+        );
+
+        let parallel = Unpaired::from_iters_parallel(&data_a, &data_b);
+        let sequential = Unpaired::from_iter(&data_a, &data_b)?;
+        for (par, seq) in [
+            (parallel.stats_a(), sequential.stats_a()),
+            (parallel.stats_b(), sequential.stats_b()),
+        ] {
+            assert_eq!(par.sample_count(), seq.sample_count());
+            assert_relative_eq!(par.sample_mean(), seq.sample_mean(), max_relative = 1e-12);
+            assert_relative_eq!(
+                par.sample_std_dev(),
+                seq.sample_std_dev(),
+                max_relative = 1e-12
+            );
+        }
+        let confidence = Confidence::new_two_sided(0.95);
+        let (par_ci, seq_ci) = (
+            parallel.ci_mean(confidence)?,
+            sequential.ci_mean(confidence)?,
+        );
+        assert_relative_eq!(par_ci.low_f(), seq_ci.low_f(), max_relative = 1e-9);
+        assert_relative_eq!(par_ci.high_f(), seq_ci.high_f(), max_relative = 1e-9);
+
+        // independent of the number of threads
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(3)
+            .build()
+            .unwrap();
+        assert_eq!(
+            pool.install(|| Paired::from_pairs_parallel(&pairs)),
+            Paired::from_pairs_parallel(&pairs)
+        );
+        assert_eq!(
+            pool.install(|| Unpaired::from_iters_parallel(&data_a, &data_b)),
+            parallel
+        );
+
+        // empty input
+        assert_eq!(Paired::<f64>::from_pairs_parallel(&[]), Paired::default());
+        assert_eq!(
+            Unpaired::<f64>::from_iters_parallel(&[], &[]),
+            Unpaired::default()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_paired_ratio() -> CIResult<()> {
+        // zinc concentration (see test_paired); reference values computed from the log-ratios
+        // with Student's t distribution, in arbitrary precision arithmetic
+        let data_bottom_water = [
+            0.430, 0.266, 0.567, 0.531, 0.707, 0.716, 0.651, 0.589, 0.469, 0.723,
+        ];
+        let data_surface_water = [
+            0.415, 0.238, 0.390, 0.410, 0.605, 0.609, 0.632, 0.523, 0.411, 0.612,
+        ];
+        let mut stats = PairedRatio::default();
+        stats.extend(&data_bottom_water, &data_surface_water)?;
+        assert_eq!(stats.sample_count(), 10);
+        assert_abs_diff_eq!(
+            stats.sample_ratio(),
+            1.167_000_208_313_244_7,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            stats.log_stats().sample_mean(),
+            0.154_436_531_807_611_84,
+            epsilon = 1e-12
+        );
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = stats.ci_ratio(confidence)?;
+        assert_abs_diff_eq!(ci.low_f(), 1.085_269_144_291_156_9, epsilon = 1e-9);
+        assert_abs_diff_eq!(ci.high_f(), 1.254_886_396_952_411, epsilon = 1e-9);
+        let change = stats.ci_percent_change(confidence)?;
+        assert_abs_diff_eq!(change.low_f(), 8.526_914_429_115_69, epsilon = 1e-7);
+        assert_abs_diff_eq!(change.high_f(), 25.488_639_695_241_14, epsilon = 1e-7);
+        assert_eq!(
+            stats.significance(confidence)?,
+            Significance::PositivelySignificant
+        );
+
+        let ci = stats.ci_ratio(Confidence::new_upper(0.9))?;
+        assert_abs_diff_eq!(ci.low_f(), 1.116_328_776_322_904_9, epsilon = 1e-9);
+        assert_eq!(ci.high(), None);
+        let ci = stats.ci_ratio(Confidence::new_lower(0.9))?;
+        assert_eq!(ci.low(), None);
+
+        // consistent with the other constructors and with merging
+        let pairs: Vec<_> = data_bottom_water
+            .iter()
+            .copied()
+            .zip(data_surface_water)
+            .collect();
+        let from_pairs = PairedRatio::from_pairs(pairs.clone())?;
+        assert_eq!(from_pairs, stats);
+        let mut merged = PairedRatio::default();
+        merged.extend_tuple(&pairs[..4].to_vec())?;
+        merged += PairedRatio::from_pairs(pairs[4..].iter().copied())?;
+        assert_abs_diff_eq!(merged.sample_ratio(), stats.sample_ratio(), epsilon = 1e-12);
+        assert_eq!(
+            PairedRatio::ci(confidence, &data_bottom_water, &data_surface_water)?,
+            stats.ci_ratio(confidence)?
+        );
+
+        // errors
+        let mut stats = PairedRatio::default();
+        assert!(matches!(
+            stats.extend(&[1., 2., 3.], &[1., -2., 3.]),
+            Err(CIError::NonPositiveValueAt { index: 1, value: x }) if x == -2.
+        ));
+        assert_eq!(stats.sample_count(), 1);
+        assert!(matches!(
+            stats.append_pair(f64::NAN, 1.),
+            Err(CIError::NonPositiveValueAt { index: 1, value: _ })
+        ));
+        assert_eq!(
+            PairedRatio::ci(confidence, &[1., 2.], &[1.]),
+            Err(CIError::DifferentSampleSizes {
+                size_a: 2,
+                size_b: 1
+            })
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_durations() -> CIResult<()> {
+        use std::time::Duration;
+        let confidence = Confidence::new_two_sided(0.95);
+        let data_a = [10_200_000, 11_050_000, 9_870_000, 12_000_001].map(Duration::from_nanos);
+        let data_b = [12_100_000, 14_000_000, 12_500_500, 13_900_000].map(Duration::from_nanos);
+        let secs_a = data_a.map(|d| d.as_secs_f64());
+        let secs_b = data_b.map(|d| d.as_secs_f64());
+
+        let mut paired = Paired::default();
+        let mut unpaired = Unpaired::default();
+        for (a, b) in data_a.into_iter().zip(data_b) {
+            paired.append_pair_durations(a, b)?;
+            unpaired.append_pair_durations(a, b)?;
         }
+        // the differences are in signed seconds
+        let ci = paired.ci_mean(confidence)?;
+        assert_eq!(ci, Paired::ci(confidence, &secs_a, &secs_b)?);
+        assert!(ci.high_f() < 0. && ci.low_f() > -0.01);
+        let ci = unpaired.ci_mean(confidence)?;
+        assert_eq!(ci, Unpaired::ci(confidence, &secs_a, &secs_b)?);
+        assert!(ci.high_f() < 0. && ci.low_f() > -0.01);
+        Ok(())
+    }
+
+    #[test]
+    fn test_paired_robust() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        // differences around 0.06 with a single wild pair (index 12)
+        let data_a = [
+            0.430, 0.266, 0.567, 0.531, 0.707, 0.716, 0.651, 0.589, 0.469, 0.723, 0.512, 0.498,
+            2.840, 0.605,
+        ];
+        let data_b = [
+            0.415, 0.238, 0.390, 0.410, 0.605, 0.609, 0.632, 0.523, 0.411, 0.612, 0.447, 0.430,
+            0.520, 0.540,
+        ];
+        let mut stats = PairedRobust::default();
+        stats.extend(&data_a, &data_b)?;
+        assert_eq!(stats.sample_count(), 14);
+        assert_eq!(
+            stats.ci_mean(confidence)?,
+            Paired::ci(confidence, &data_a, &data_b)?
+        );
+
+        let outliers = stats.outlier_pairs(1.5)?;
+        assert_eq!(outliers.len(), 1);
+        assert_eq!(outliers[0].0, 12);
+        assert_abs_diff_eq!(outliers[0].1, 2.32, epsilon = 1e-12);
+
+        let (ci, excluded) = stats.ci_mean_excluding_outliers(confidence, 1.5)?;
+        assert_eq!(excluded, 1);
+        let all = stats.ci_mean(confidence)?;
+        assert!(ci.width().unwrap() < all.width().unwrap() / 10.);
+        assert!(ci.low_f() > 0.);
+        assert!(all.contains(&0.));
+        let kept = (0..14).filter(|&i| i != 12);
+        let expected = Paired::ci(
+            confidence,
+            &kept.clone().map(|i| data_a[i]).collect::<Vec<_>>(),
+            &kept.map(|i| data_b[i]).collect::<Vec<_>>(),
+        )?;
+        assert_abs_diff_eq!(ci, expected, epsilon = 1e-12);
+
+        // a large enough factor keeps all the pairs
+        assert!(stats.outlier_pairs(100.)?.is_empty());
+        let (ci, excluded) = stats.ci_mean_excluding_outliers(confidence, 100.)?;
+        assert_eq!(excluded, 0);
+        assert_eq!(ci, all);
+
+        // errors
+        for k_iqr in [0., -1.5, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                stats.outlier_pairs(k_iqr),
+                Err(CIError::InvalidOutlierFactor(_))
+            ));
+            assert!(matches!(
+                stats.ci_mean_excluding_outliers(confidence, k_iqr),
+                Err(CIError::InvalidOutlierFactor(_))
+            ));
+        }
+        let empty = PairedRobust::<f64>::default();
+        assert_eq!(empty.outlier_pairs(1.5), Err(CIError::TooFewSamples(0)));
+        assert_eq!(
+            stats.clone().extend(&[1., 2.], &[1.]),
+            Err(CIError::DifferentSampleSizes {
+                size_a: 2,
+                size_b: 1
+            })
+        );
+        assert_eq!(
+            stats.append_pair(f64::NAN, 1.),
+            Err(CIError::InvalidInputData)
+        );
+        assert_eq!(stats.sample_count(), 14);
+        Ok(())
     }
 }
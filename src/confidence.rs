@@ -13,11 +13,13 @@
 /// * [`Confidence::new_two_sided`] - _idem_
 /// * [`Confidence::new_upper`] - create a new one-sided upper confidence interval with the given confidence level
 /// * [`Confidence::new_lower`] - create a new one-sided lower confidence interval with the given confidence level
+/// * [`Confidence::from_nines`], [`Confidence::upper_from_nines`], [`Confidence::lower_from_nines`] - _idem_ with the level given as a number of nines (e.g., 3 for 99.9%)
 ///
 /// ### Accessors
 ///
 /// * [`Confidence::level`] - return the confidence level of the interval as a number in the range (0, 1)
 /// * [`Confidence::percent`] - return the confidence level of the interval as a percentage
+/// * [`Confidence::nines`] - return the confidence level of the interval as a number of nines
 /// * [`Confidence::kind`] - return the kind of the confidence interval as a string (in English)
 ///
 /// ### Characteristics
@@ -30,6 +32,7 @@
 /// ### Conversions
 ///
 /// * [`Confidence::flipped`] - return the confidence interval with the same confidence level but flipped (e.g., upper to lower)
+/// * [`FromStr`](core::str::FromStr) / [`Display`](core::fmt::Display) - parse from and format to strings such as `"95%"`, `"0.99"`, or `"upper 90%"`
 ///
 /// ### Comparison
 ///
@@ -88,6 +91,18 @@
 /// assert_eq!(confidence.flipped(), Confidence::new_lower(0.95));
 /// ```
 ///
+/// ## Parsing and formatting
+///
+/// ```
+/// # use stats_ci::Confidence;
+/// #
+/// let confidence: Confidence = "upper 90%".parse()?;
+/// assert_eq!(confidence, Confidence::new_upper(0.9));
+/// assert_eq!(confidence.to_string(), "upper 90%");
+/// assert_eq!("0.95".parse::<Confidence>()?, Confidence::new(0.95));
+/// # Ok::<(),stats_ci::error::CIError>(())
+/// ```
+///
 /// ## Comparison
 ///
 /// ```
@@ -100,6 +115,10 @@
 ///
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(into = "ConfidenceWire", try_from = "ConfidenceRepr")
+)]
 pub enum Confidence {
     /// Confidence for a two-sided interval.
     TwoSided(f64),
@@ -112,6 +131,15 @@ pub enum Confidence {
 }
 
 impl Confidence {
+    /// Two-sided confidence with a level of 90%.
+    pub const P90: Confidence = Confidence::two_sided_unchecked(0.9);
+
+    /// Two-sided confidence with a level of 95% (the [default](Confidence::default)).
+    pub const P95: Confidence = Confidence::two_sided_unchecked(0.95);
+
+    /// Two-sided confidence with a level of 99%.
+    pub const P99: Confidence = Confidence::two_sided_unchecked(0.99);
+
     ///
     /// Create a new two-sided confidence interval with the given confidence level.
     /// This is the same as [`Confidence::new_two_sided`].
@@ -147,6 +175,31 @@ impl Confidence {
         }
     }
 
+    ///
+    /// Create a new two-sided confidence interval with the given confidence level without checking
+    /// it, which makes it usable in `const` contexts (e.g., to define constants).
+    ///
+    /// The caller must ensure that `confidence` is in the range (0, 1), which is otherwise checked
+    /// by [`Confidence::new_two_sided`]. With an invalid level, the intervals computed with the
+    /// resulting confidence are meaningless and the functions computing them may panic.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::Confidence;
+    /// const STRICT: Confidence = Confidence::two_sided_unchecked(0.999);
+    /// assert_eq!(STRICT, Confidence::new_two_sided(0.999));
+    /// assert_eq!(Confidence::P95, Confidence::default());
+    /// ```
+    ///
+    pub const fn two_sided_unchecked(confidence: f64) -> Self {
+        Confidence::TwoSided(confidence)
+    }
+
     ///
     /// Create a new one-sided upper confidence interval with the given confidence level.
     ///
@@ -185,6 +238,100 @@ impl Confidence {
         }
     }
 
+    ///
+    /// Create a new two-sided confidence interval with the confidence level given as a number
+    /// of nines, as used for availability objectives: the level is \\( 1 - 10^{-n} \\) for
+    /// \\( n \\) nines, so that three nines is 99.9% and four and a half nines is about 99.997%.
+    /// The number of nines need not be an integer.
+    ///
+    /// For an integer number of nines, the level is the same as the one written out in decimal,
+    /// e.g., `Confidence::from_nines(3.)` is exactly `Confidence::new(0.999)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `nines` - the number of nines of the confidence level, e.g. 3 for 99.9% confidence
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the resulting level is not in the range (0, 1),
+    ///   i.e., if `nines` is not positive or is so large (above about 16.25) that the level rounds
+    ///   to 1 in `f64`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// // the SLO promises "three nines"
+    /// let slo = Confidence::from_nines(3.)?;
+    /// assert_eq!(slo, Confidence::new(0.999));
+    /// assert_eq!(slo.to_string(), "two-sided 99.9%");
+    ///
+    /// // "four and a half nines"
+    /// let slo = Confidence::from_nines(4.5)?;
+    /// assert_eq!(format!("{:.4}%", slo.percent()), "99.9968%");
+    ///
+    /// // twenty nines cannot be represented: the level would round to 100%
+    /// assert!(Confidence::from_nines(20.).is_err());
+    /// assert!(Confidence::from_nines(0.).is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn from_nines(nines: f64) -> CIResult<Self> {
+        Ok(Confidence::TwoSided(level_from_nines(nines)?))
+    }
+
+    ///
+    /// Create a new one-sided upper confidence interval with the confidence level given as a
+    /// number of nines (see [`Confidence::from_nines`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `nines` - the number of nines of the confidence level, e.g. 3 for 99.9% confidence
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the resulting level is not in the range (0, 1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// // latency stays below the upper bound with "four nines"
+    /// let slo = Confidence::upper_from_nines(4.)?;
+    /// assert_eq!(slo, Confidence::new_upper(0.9999));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn upper_from_nines(nines: f64) -> CIResult<Self> {
+        Ok(Confidence::UpperOneSided(level_from_nines(nines)?))
+    }
+
+    ///
+    /// Create a new one-sided lower confidence interval with the confidence level given as a
+    /// number of nines (see [`Confidence::from_nines`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `nines` - the number of nines of the confidence level, e.g. 3 for 99.9% confidence
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceLevel`] - if the resulting level is not in the range (0, 1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// // availability stays above the lower bound with "two nines"
+    /// let slo = Confidence::lower_from_nines(2.)?;
+    /// assert_eq!(slo, Confidence::new_lower(0.99));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn lower_from_nines(nines: f64) -> CIResult<Self> {
+        Ok(Confidence::LowerOneSided(level_from_nines(nines)?))
+    }
+
     ///
     /// Return the confidence level of the interval as a number in the range (0, 1).
     ///
@@ -203,6 +350,27 @@ impl Confidence {
         self.level() * 100.
     }
 
+    ///
+    /// Return the confidence level of the interval as a number of nines, i.e.,
+    /// \\( -\\log_{10}(1 - c) \\) for a confidence level \\( c \\).
+    /// This is the inverse of [`Confidence::from_nines`].
+    ///
+    /// Since the level is stored as such, the number of nines loses precision as it grows:
+    /// it round-trips to about 1e-9 up to seven nines, but only to about 1e-2 at fifteen nines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let slo = Confidence::new(0.999);
+    /// assert!((slo.nines() - 3.).abs() < 1e-9);
+    /// assert!((Confidence::new(0.95).nines() - 1.3).abs() < 0.01);
+    /// ```
+    ///
+    pub fn nines(&self) -> f64 {
+        -(1. - self.level()).log10()
+    }
+
     ///
     /// Return the kind of the confidence interval as a string (in English).
     ///
@@ -256,6 +424,17 @@ impl Confidence {
         }
     }
 
+    ///
+    /// Return a confidence of the same kind with a different level.
+    ///
+    pub(crate) fn with_level(&self, level: f64) -> Self {
+        match self {
+            Confidence::TwoSided(_) => Confidence::new_two_sided(level),
+            Confidence::UpperOneSided(_) => Confidence::new_upper(level),
+            Confidence::LowerOneSided(_) => Confidence::new_lower(level),
+        }
+    }
+
     ///
     /// Return the quantile of the confidence interval.
     ///
@@ -285,6 +464,96 @@ impl Default for Confidence {
     }
 }
 
+///
+/// Wire format of [`Confidence`]: an object tagged by `kind` with the level as a field, e.g.,
+/// `{"kind":"two_sided","level":0.95}`, `{"kind":"upper_one_sided","level":0.95}`, or
+/// `{"kind":"lower_one_sided","level":0.95}`.
+///
+/// The names of the kinds and fields are part of the stable format and must not change.
+///
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)] // mirrors the variants of [`Confidence`]
+enum ConfidenceWire {
+    TwoSided { level: f64 },
+    UpperOneSided { level: f64 },
+    LowerOneSided { level: f64 },
+}
+
+///
+/// Legacy wire format of [`Confidence`], e.g., `{"TwoSided":0.95}`.
+/// It is still accepted when deserializing but no longer produced.
+///
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Confidence")]
+#[allow(clippy::enum_variant_names)] // mirrors the variants of [`Confidence`]
+enum LegacyConfidenceWire {
+    TwoSided(f64),
+    UpperOneSided(f64),
+    LowerOneSided(f64),
+}
+
+///
+/// Unvalidated mirror of [`Confidence`] in either wire format, used to check the level when
+/// deserializing.
+///
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum ConfidenceRepr {
+    Current(ConfidenceWire),
+    Legacy(LegacyConfidenceWire),
+}
+
+#[cfg(feature = "serde")]
+impl From<Confidence> for ConfidenceWire {
+    fn from(confidence: Confidence) -> Self {
+        match confidence {
+            Confidence::TwoSided(level) => ConfidenceWire::TwoSided { level },
+            Confidence::UpperOneSided(level) => ConfidenceWire::UpperOneSided { level },
+            Confidence::LowerOneSided(level) => ConfidenceWire::LowerOneSided { level },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ConfidenceRepr> for Confidence {
+    type Error = String;
+
+    fn try_from(repr: ConfidenceRepr) -> Result<Self, Self::Error> {
+        let (variant, level, confidence) = match repr {
+            ConfidenceRepr::Current(ConfidenceWire::TwoSided { level }) => {
+                ("two_sided", level, Confidence::TwoSided(level))
+            }
+            ConfidenceRepr::Current(ConfidenceWire::UpperOneSided { level }) => {
+                ("upper_one_sided", level, Confidence::UpperOneSided(level))
+            }
+            ConfidenceRepr::Current(ConfidenceWire::LowerOneSided { level }) => {
+                ("lower_one_sided", level, Confidence::LowerOneSided(level))
+            }
+            ConfidenceRepr::Legacy(LegacyConfidenceWire::TwoSided(level)) => {
+                ("TwoSided", level, Confidence::TwoSided(level))
+            }
+            ConfidenceRepr::Legacy(LegacyConfidenceWire::UpperOneSided(level)) => {
+                ("UpperOneSided", level, Confidence::UpperOneSided(level))
+            }
+            ConfidenceRepr::Legacy(LegacyConfidenceWire::LowerOneSided(level)) => {
+                ("LowerOneSided", level, Confidence::LowerOneSided(level))
+            }
+        };
+        // NaN fails both comparisons
+        if level > 0. && level < 1. {
+            Ok(confidence)
+        } else {
+            Err(format!(
+                "invalid confidence level in `{variant}`: {level} (must be in the range (0, 1))"
+            ))
+        }
+    }
+}
+
 impl PartialOrd for Confidence {
     // NB: the partial ordering obtained from derivation rule is unsound, so we need to
     // implement it manually.
@@ -298,7 +567,7 @@ impl PartialOrd for Confidence {
     }
 }
 
-use crate::error::CIError;
+use crate::error::{CIError, CIResult};
 impl TryFrom<f64> for Confidence {
     type Error = CIError;
 
@@ -311,6 +580,20 @@ impl TryFrom<f64> for Confidence {
     }
 }
 
+///
+/// Return the confidence level \\( 1 - 10^{-n} \\) for \\( n \\) nines, or an error if it is not
+/// in the range (0, 1).
+///
+fn level_from_nines(nines: f64) -> CIResult<f64> {
+    // `powf` is exact on integer powers of ten, so that, e.g., 3 nines is exactly 0.999
+    let level = 1. - 10_f64.powf(-nines);
+    if level > 0. && level < 1. {
+        Ok(level)
+    } else {
+        Err(CIError::InvalidConfidenceLevel(level))
+    }
+}
+
 impl TryFrom<f32> for Confidence {
     type Error = CIError;
 
@@ -319,11 +602,153 @@ impl TryFrom<f32> for Confidence {
     }
 }
 
+type KindConstructor = fn(f64) -> Confidence;
+
+///
+/// Prefixes accepted for each kind of confidence, longest first so that
+/// `"upper one-sided"` is matched before `"upper"`.
+///
+const KIND_PREFIXES: [(&str, KindConstructor); 7] = [
+    ("upper one-sided", Confidence::UpperOneSided),
+    ("lower one-sided", Confidence::LowerOneSided),
+    ("two-sided", Confidence::TwoSided),
+    ("two sided", Confidence::TwoSided),
+    ("upper", Confidence::UpperOneSided),
+    ("lower", Confidence::LowerOneSided),
+    ("", Confidence::TwoSided),
+];
+
+impl core::str::FromStr for Confidence {
+    type Err = CIError;
+
+    ///
+    /// Parse a confidence from a string.
+    ///
+    /// The string consists of an optional kind (`two-sided`, `upper`, `lower`, or the
+    /// longer `upper one-sided`/`lower one-sided` forms returned by [`Confidence::kind`])
+    /// followed by a level, either as a fraction (`0.95`) or as a percentage (`95%`).
+    /// Without a kind, the confidence is two-sided. Parsing ignores case and surrounding
+    /// whitespace, and does not depend on the locale: the decimal separator is always `.`.
+    ///
+    /// A number greater than one without a `%` sign (e.g., `"95"`) is rejected as ambiguous
+    /// rather than interpreted as a percentage.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidConfidenceSpec`] - if the string is not of the form above
+    /// * [`CIError::InvalidConfidenceLevel`] - if the level is not in the range (0, 1), e.g. `"100%"` or `"0"`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// assert_eq!("95%".parse::<Confidence>()?, Confidence::new(0.95));
+    /// assert_eq!(" Lower 0.9 ".parse::<Confidence>()?, Confidence::new_lower(0.9));
+    /// assert_eq!("two-sided 99.9%".parse::<Confidence>()?, Confidence::new(0.999));
+    /// assert!("95".parse::<Confidence>().is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spec = s.trim().to_lowercase();
+        let (constructor, value) = KIND_PREFIXES
+            .iter()
+            .find_map(|(prefix, constructor)| {
+                let rest = spec.strip_prefix(prefix)?;
+                // a non-empty prefix must be separated from the level
+                (prefix.is_empty() || rest.starts_with(char::is_whitespace))
+                    .then_some((constructor, rest.trim()))
+            })
+            .expect("the empty prefix always matches");
+
+        let level = match value.strip_suffix('%') {
+            // shift the decimal point textually so that "99.9%" is exactly 0.999
+            Some(percent) => parse_number(percent.trim_end(), "e-2", s)?,
+            None => {
+                let level = parse_number(value, "", s)?;
+                if level > 1. {
+                    return Err(CIError::InvalidConfidenceSpec(format!(
+                        "ambiguous level in {s:?}: write {value}% or a fraction in (0, 1)"
+                    )));
+                }
+                level
+            }
+        };
+        if level > 0. && level < 1. {
+            Ok(constructor(level))
+        } else {
+            Err(CIError::InvalidConfidenceLevel(level))
+        }
+    }
+}
+
+///
+/// Parse a finite, non-negative decimal number (with an optional exponent `suffix`).
+///
+fn parse_number(number: &str, suffix: &str, spec: &str) -> Result<f64, CIError> {
+    let invalid = || {
+        CIError::InvalidConfidenceSpec(format!(
+            "expected [two-sided|upper|lower] followed by a level such as 95% or 0.95, found {spec:?}"
+        ))
+    };
+    // only plain decimal notation (no sign, exponent, "inf" or "nan")
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return Err(invalid());
+    }
+    format!("{number}{suffix}")
+        .parse::<f64>()
+        .map_err(|_| invalid())
+}
+
+impl core::fmt::Display for Confidence {
+    ///
+    /// Format the confidence as its kind followed by its level as a percentage,
+    /// e.g. `"two-sided 95%"` or `"upper 99.9%"`. The result parses back to the same
+    /// confidence with [`str::parse`].
+    ///
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let kind = match self {
+            Confidence::TwoSided(_) => "two-sided",
+            Confidence::UpperOneSided(_) => "upper",
+            Confidence::LowerOneSided(_) => "lower",
+        };
+        let level = self.level();
+        // shortest percentage that parses back to exactly the same level
+        let percent = (0..=17)
+            .map(|precision| format!("{:.*}", precision, level * 100.))
+            .find(|percent| format!("{percent}e-2").parse::<f64>() == Ok(level));
+        match percent {
+            Some(percent) => write!(f, "{kind} {percent}%"),
+            None => write!(f, "{kind} {level}"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // constant confidences must remain constructible in `const` contexts
+    const STRICT: Confidence = Confidence::two_sided_unchecked(0.999);
+    const LEVELS: [Confidence; 3] = [Confidence::P90, Confidence::P95, Confidence::P99];
+
+    #[test]
+    fn test_const() {
+        assert_eq!(STRICT, Confidence::new_two_sided(0.999));
+        assert_eq!(
+            LEVELS,
+            [
+                Confidence::new(0.9),
+                Confidence::new(0.95),
+                Confidence::new(0.99)
+            ]
+        );
+        assert_eq!(Confidence::P95, Confidence::default());
+        assert!(LEVELS.iter().all(|c| c.is_two_sided()));
+    }
+
     #[test]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)] // testing incomparable values
     fn test_ordering() {
         let two_sided = Confidence::new_two_sided(0.95);
         let upper = Confidence::new_upper(0.95);
@@ -406,4 +831,256 @@ mod tests {
     fn test_invalid_lower_confidence_level_one() {
         Confidence::new_lower(1.);
     }
+
+    #[test]
+    fn test_nines() {
+        for n in 1..=15 {
+            let level = format!("0.{}", "9".repeat(n)).parse::<f64>().unwrap();
+            assert_eq!(Confidence::from_nines(n as f64), Ok(Confidence::new(level)));
+        }
+        assert_eq!(
+            Confidence::upper_from_nines(3.),
+            Ok(Confidence::new_upper(0.999))
+        );
+        assert_eq!(
+            Confidence::lower_from_nines(3.),
+            Ok(Confidence::new_lower(0.999))
+        );
+
+        // round trip nines -> level -> nines
+        for nines in [
+            0.01, 0.5, 1., 1.5, 2., 2.5, 3., 3.5, 4., 4.5, 5., 5.5, 6., 6.5, 7.,
+        ] {
+            for confidence in [
+                Confidence::from_nines(nines).unwrap(),
+                Confidence::upper_from_nines(nines).unwrap(),
+                Confidence::lower_from_nines(nines).unwrap(),
+            ] {
+                assert!(
+                    (confidence.nines() - nines).abs() < 1e-9,
+                    "{nines} nines: {}",
+                    confidence.nines()
+                );
+            }
+        }
+        assert!((Confidence::new(0.9).nines() - 1.).abs() < 1e-9);
+
+        // the level reaches 1 in f64 at 54 log10(2) ~ 16.2556 nines
+        let level = Confidence::from_nines(16.25).unwrap().level();
+        assert!(level < 1.);
+        for nines in [16.26, 17., 20., f64::INFINITY] {
+            for result in [
+                Confidence::from_nines(nines),
+                Confidence::upper_from_nines(nines),
+                Confidence::lower_from_nines(nines),
+            ] {
+                assert_eq!(result, Err(CIError::InvalidConfidenceLevel(1.)), "{nines}");
+            }
+        }
+        for nines in [0., -0., -1., f64::NEG_INFINITY] {
+            assert!(
+                matches!(Confidence::from_nines(nines), Err(CIError::InvalidConfidenceLevel(l)) if l <= 0.),
+                "{nines}"
+            );
+        }
+        assert!(matches!(
+            Confidence::from_nines(f64::NAN),
+            Err(CIError::InvalidConfidenceLevel(l)) if l.is_nan()
+        ));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let parse = |s: &str| s.parse::<Confidence>();
+        let valid = [
+            ("0.95", Confidence::new(0.95)),
+            ("95%", Confidence::new(0.95)),
+            ("  95 %\t", Confidence::new(0.95)),
+            ("99.9%", Confidence::new(0.999)),
+            (".5", Confidence::new(0.5)),
+            ("upper 95%", Confidence::new_upper(0.95)),
+            ("UPPER   0.9", Confidence::new_upper(0.9)),
+            ("lower 0.9", Confidence::new_lower(0.9)),
+            ("Lower One-Sided 80%", Confidence::new_lower(0.8)),
+            ("two-sided 99%", Confidence::new(0.99)),
+            ("Two Sided 0.99", Confidence::new(0.99)),
+        ];
+        for (s, expected) in valid {
+            assert_eq!(parse(s).unwrap(), expected, "parsing {s:?}");
+        }
+
+        for (s, level) in [
+            ("100%", 1.),
+            ("0", 0.),
+            ("0%", 0.),
+            ("1", 1.),
+            ("upper 1.0", 1.),
+        ] {
+            assert!(
+                matches!(parse(s), Err(CIError::InvalidConfidenceLevel(l)) if l == level),
+                "parsing {s:?}"
+            );
+        }
+
+        for s in [
+            "",
+            "95",
+            "upper 95",
+            "%",
+            "0,95",
+            "95%%",
+            "-0.95",
+            "1e-1",
+            "nan",
+            "inf",
+            "upper",
+            "upper95%",
+            "sideways 95%",
+            "95% upper",
+            "two-sided upper 95%",
+        ] {
+            assert!(
+                matches!(parse(s), Err(CIError::InvalidConfidenceSpec(_))),
+                "parsing {s:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Confidence::new(0.95).to_string(), "two-sided 95%");
+        assert_eq!(Confidence::new_upper(0.999).to_string(), "upper 99.9%");
+        assert_eq!(Confidence::new_lower(0.07).to_string(), "lower 7%");
+        assert_eq!(Confidence::new(0.5).to_string(), "two-sided 50%");
+
+        let levels = [
+            0.95,
+            0.999,
+            0.07,
+            0.123_456_789,
+            1e-9,
+            1. - 1e-12,
+            0.1 + 0.2 - 0.25,
+        ];
+        for level in levels {
+            for confidence in [
+                Confidence::new(level),
+                Confidence::new_upper(level),
+                Confidence::new_lower(level),
+            ] {
+                let s = confidence.to_string();
+                assert_eq!(
+                    s.parse::<Confidence>().unwrap(),
+                    confidence,
+                    "parsing {s:?}"
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Wrapper {
+        confidence: Confidence,
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        for confidence in [
+            Confidence::new(0.95),
+            Confidence::new_upper(0.999),
+            Confidence::new_lower(1e-9),
+        ] {
+            let wrapper = Wrapper { confidence };
+            let serialized = toml::to_string(&wrapper).unwrap();
+            let deserialized: Wrapper = toml::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, wrapper);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_invalid_level() {
+        for (payload, message) in [
+            ("{ TwoSided = 1.7 }", "`TwoSided`: 1.7"),
+            ("{ TwoSided = nan }", "`TwoSided`: NaN"),
+            ("{ UpperOneSided = 0.0 }", "`UpperOneSided`: 0"),
+            ("{ UpperOneSided = inf }", "`UpperOneSided`: inf"),
+            ("{ LowerOneSided = 1.0 }", "`LowerOneSided`: 1"),
+            ("{ LowerOneSided = -0.5 }", "`LowerOneSided`: -0.5"),
+        ] {
+            let error = toml::from_str::<Wrapper>(&format!("confidence = {payload}")).unwrap_err();
+            let error = error.to_string();
+            assert!(error.contains("invalid confidence level"), "{error}");
+            assert!(error.contains(message), "{error}");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_wire_format() {
+        for (confidence, json) in [
+            (
+                Confidence::new(0.95),
+                r#"{"kind":"two_sided","level":0.95}"#,
+            ),
+            (
+                Confidence::new_upper(0.9),
+                r#"{"kind":"upper_one_sided","level":0.9}"#,
+            ),
+            (
+                Confidence::new_lower(0.99),
+                r#"{"kind":"lower_one_sided","level":0.99}"#,
+            ),
+        ] {
+            assert_eq!(serde_json::to_string(&confidence).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<Confidence>(json).unwrap(),
+                confidence
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_legacy_format() {
+        for (json, confidence) in [
+            (r#"{"TwoSided":0.95}"#, Confidence::new(0.95)),
+            (r#"{"UpperOneSided":0.9}"#, Confidence::new_upper(0.9)),
+            (r#"{"LowerOneSided":0.99}"#, Confidence::new_lower(0.99)),
+        ] {
+            assert_eq!(
+                serde_json::from_str::<Confidence>(json).unwrap(),
+                confidence
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_wire_format_invalid() {
+        for (payload, message) in [
+            (r#"{ kind = "two_sided", level = 1.7 }"#, "`two_sided`: 1.7"),
+            (
+                r#"{ kind = "upper_one_sided", level = nan }"#,
+                "`upper_one_sided`: NaN",
+            ),
+            (
+                r#"{ kind = "lower_one_sided", level = 0.0 }"#,
+                "`lower_one_sided`: 0",
+            ),
+        ] {
+            let error = toml::from_str::<Wrapper>(&format!("confidence = {payload}")).unwrap_err();
+            let error = error.to_string();
+            assert!(error.contains("invalid confidence level"), "{error}");
+            assert!(error.contains(message), "{error}");
+        }
+        for json in [
+            r#"{"kind":"one_sided","level":0.95}"#,
+            r#"{"kind":"two_sided"}"#,
+        ] {
+            assert!(serde_json::from_str::<Confidence>(json).is_err(), "{json}");
+        }
+    }
 }
@@ -4,6 +4,24 @@
 //! The crate defines a type [`CIError`] to represent errors that can occur during the computation of confidence intervals.
 //! The type [`CIResult<T>`] is a type alias for [`Result<T, CIError>`].
 //!
+//! Errors can be compared with `==` (e.g., with `assert_eq!` in tests), or classified by
+//! category with [`CIError::kind`] and predicates such as [`CIError::is_too_few_samples`],
+//! regardless of the fields of the variant.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! use error::{CIError, Kind};
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let result = comparison::Paired::ci(confidence, &[1., 2., 3.], &[1., 2.]);
+//! assert_eq!(result, Err(CIError::DifferentSampleSizes { size_a: 3, size_b: 2 }));
+//!
+//! let error = mean::Arithmetic::<f64>::new().ci_mean(confidence).unwrap_err();
+//! assert!(error.is_too_few_samples());
+//! assert_eq!(error.kind(), Kind::TooFewSamples);
+//! ```
+//!
 
 ///
 /// Result type for confidence interval computations.
@@ -17,17 +35,28 @@ use num_traits::Float;
 ///
 /// Error types for confidence interval computations.
 ///
+/// Errors compare equal if they are the same variant with equal fields. As for floats, a
+/// variant holding a NaN value is not equal to itself.
+///
 #[allow(missing_docs)]
-#[derive(thiserror::Error, Debug)]
+#[derive(thiserror::Error, Debug, PartialEq)]
 pub enum CIError {
     #[error("Too few samples to compute: {0}")]
     TooFewSamples(usize),
 
-    #[error("Too few successes: {0} (population: {1}; n*p={2}))")]
-    TooFewSuccesses(usize, usize, f64),
+    #[error("Too few successes: {successes} (population: {population}; n*p={n_p}))")]
+    TooFewSuccesses {
+        successes: usize,
+        population: usize,
+        n_p: f64,
+    },
 
-    #[error("Too few failures: {0} (population: {1}; n*q={2}))")]
-    TooFewFailures(usize, usize, f64),
+    #[error("Too few failures: {failures} (population: {population}; n*q={n_q}))")]
+    TooFewFailures {
+        failures: usize,
+        population: usize,
+        n_q: f64,
+    },
 
     #[error("Invalid confidence level (must be )): {0}")]
     InvalidConfidenceLevel(f64),
@@ -35,20 +64,31 @@ pub enum CIError {
     #[error("Invalid quantile (must be in (0, 1)): {0}")]
     InvalidQuantile(f64),
 
-    #[error("Invalid number of successes: {0} (population: {1})")]
-    InvalidSuccesses(usize, usize),
+    #[error("Invalid number of successes: {successes} (population: {population})")]
+    InvalidSuccesses { successes: usize, population: usize },
+
+    #[error("Invalid success rate (must be in (0, 1)): {0}")]
+    InvalidSuccessRate(f64),
+
+    #[error("Empty cell in the 2x2 table: {0}")]
+    EmptyCell(String),
 
     #[error("Geometric/harmonic mean require strictly positive values: found {0}")]
     NonPositiveValue(f64),
 
+    #[error(
+        "Geometric/harmonic mean require strictly positive values: found {value} at index {index}"
+    )]
+    NonPositiveValueAt { index: usize, value: f64 },
+
     #[error("Invalid input data found")]
     InvalidInputData,
 
     #[error("Float type conversion error: {0}")]
     FloatConversionError(String),
 
-    #[error("Index error: {0} should be in [0, {1})")]
-    IndexError(f64, usize),
+    #[error("Index error: {index} should be in [0, {len})")]
+    IndexError { index: f64, len: usize },
 
     // wrapper errors
     #[error("String error: {0}")]
@@ -57,28 +97,274 @@ pub enum CIError {
     #[error("Interval error: {0}")]
     IntervalError(#[from] IntervalError),
 
-    #[error("Different sample sizes: {0} vs. {1}")]
-    DifferentSampleSizes(usize, usize),
+    #[error("Different sample sizes: {size_a} vs. {size_b}")]
+    DifferentSampleSizes { size_a: usize, size_b: usize },
+
+    #[error("Invalid row length: {len} (expected: {expected})")]
+    InvalidRowLength { len: usize, expected: usize },
+
+    #[error("Iterator too short: {len} elements (expected: {expected})")]
+    IteratorTooShort { len: usize, expected: usize },
+
+    #[error("Unknown column: {0}")]
+    UnknownColumn(String),
+
+    #[error("Invalid exposure (must be positive and finite): {0}")]
+    InvalidExposure(f64),
+
+    #[error("Invalid baseline: {0}")]
+    InvalidBaseline(String),
+
+    #[error("Invalid degrees of freedom (must be positive): {0}")]
+    InvalidDegreesOfFreedom(f64),
+
+    #[error("Parse error at row {row}: {message}")]
+    ParseError { row: usize, message: String },
+
+    #[error("Invalid confidence specification: {0}")]
+    InvalidConfidenceSpec(String),
+
+    #[error("Invalid standard error (must be positive and finite): {0}")]
+    InvalidStdError(f64),
+
+    #[error("Uninterpretable transformed interval: {0}")]
+    UninterpretableTransformedInterval(String),
+
+    #[error("Invalid derivative (must be non-zero and finite): {0}")]
+    InvalidDerivative(f64),
+
+    #[error("Decode error: {0}")]
+    DecodeError(String),
+
+    #[error("Invalid probability (must be in (0, 1)): {0}")]
+    InvalidProbability(f64),
+
+    #[error("Invalid critical value (must be non-negative and finite): {0}")]
+    InvalidCriticalValue(f64),
+
+    #[error("Invalid capacity (must be at least 2): {0}")]
+    InvalidCapacity(usize),
+
+    #[error("Invalid margin (must be finite): {0}")]
+    InvalidMargin(f64),
+
+    #[error(
+        "Too few resamples: {resamples} (at least {required} required for the confidence level)"
+    )]
+    TooFewResamples { resamples: usize, required: usize },
+
+    #[error("Invalid target (must be positive and finite): {0}")]
+    InvalidTarget(f64),
+
+    #[error("Coefficient of variation requires a strictly positive mean: found {0}")]
+    NonPositiveMean(f64),
+
+    #[error("Coefficient of variation too large to bound its interval from above: {0}")]
+    CoefficientOfVariationTooLarge(f64),
+
+    #[error("Invalid design effect (must be finite and at least 1): {0}")]
+    InvalidDesignEffect(f64),
+
+    #[error("Invalid outlier factor (must be positive and finite): {0}")]
+    InvalidOutlierFactor(f64),
+
+    #[error("Quantile too extreme for {population} samples: {quantile} (supported: [{min_supported}, {max_supported}])")]
+    QuantileTooExtreme {
+        quantile: f64,
+        population: usize,
+        min_supported: f64,
+        max_supported: f64,
+    },
+
+    #[error("Invalid category: {category} should be in [0, {categories})")]
+    InvalidCategory { category: usize, categories: usize },
+
+    #[error("Invalid smoothing factor (must be in (0, 1]): {0}")]
+    InvalidSmoothingFactor(f64),
+
+    #[error("Different smoothing factors: {alpha_a} vs. {alpha_b}")]
+    DifferentSmoothingFactors { alpha_a: f64, alpha_b: f64 },
+
+    #[error("Zero sample variance: {0}")]
+    ZeroVariance(String),
+
+    #[error("Invalid trim fraction (must be in [0, 0.5)): {0}")]
+    InvalidTrimFraction(f64),
+
+    #[error("Different minimum numbers of observations per variant: {min_a} vs. {min_b}")]
+    DifferentMinPerVariant { min_a: usize, min_b: usize },
+
+    #[error("Count overflow: the total count does not fit in its integer type")]
+    CountOverflow,
+
+    #[error("Too many required trials: the number exceeds the range of u64")]
+    TooManyRequiredTrials,
+
+    #[error("Invalid shape of the gamma distribution (must be positive and finite): {0}")]
+    InvalidShape(f64),
+
+    #[error("Empty grid of quantiles")]
+    EmptyQuantileGrid,
+
+    #[error("Different columns: {names_a:?} vs. {names_b:?}")]
+    DifferentColumns {
+        names_a: Vec<String>,
+        names_b: Vec<String>,
+    },
+}
+
+///
+/// Category of a [`CIError`], as returned by [`CIError::kind`].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// The data is too small to compute the result (e.g., [`CIError::TooFewSamples`] or
+    /// [`CIError::TooFewSuccesses`]); more data may resolve the error.
+    TooFewSamples,
+
+    /// The data is invalid (e.g., [`CIError::InvalidInputData`], [`CIError::NonPositiveValue`], or
+    /// [`CIError::DifferentSampleSizes`]).
+    InvalidInput,
+
+    /// A parameter is invalid (e.g., [`CIError::InvalidConfidenceLevel`] or [`CIError::InvalidQuantile`]).
+    InvalidParameter,
+
+    /// A value cannot be converted between float types ([`CIError::FloatConversionError`]).
+    Conversion,
+
+    /// An interval cannot be created ([`CIError::IntervalError`]).
+    Interval,
+
+    /// The result is not defined for the data (e.g., [`CIError::CoefficientOfVariationTooLarge`]).
+    UndefinedResult,
+
+    /// Any other error ([`CIError::Error`]).
+    Other,
+}
+
+impl CIError {
+    ///
+    /// Return the category of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// use error::{CIError, Kind};
+    /// assert_eq!(CIError::TooFewSamples(1).kind(), Kind::TooFewSamples);
+    /// assert_eq!(CIError::InvalidQuantile(1.5).kind(), Kind::InvalidParameter);
+    /// ```
+    ///
+    pub fn kind(&self) -> Kind {
+        match self {
+            CIError::TooFewSamples(_)
+            | CIError::TooFewSuccesses { .. }
+            | CIError::TooFewFailures { .. }
+            | CIError::QuantileTooExtreme { .. } => Kind::TooFewSamples,
+            CIError::InvalidSuccesses { .. }
+            | CIError::EmptyCell(_)
+            | CIError::NonPositiveValue(_)
+            | CIError::NonPositiveValueAt { .. }
+            | CIError::InvalidInputData
+            | CIError::DifferentSampleSizes { .. }
+            | CIError::InvalidRowLength { .. }
+            | CIError::IteratorTooShort { .. }
+            | CIError::ParseError { .. }
+            | CIError::DecodeError(_)
+            | CIError::NonPositiveMean(_)
+            | CIError::InvalidCategory { .. }
+            | CIError::DifferentSmoothingFactors { .. }
+            | CIError::DifferentMinPerVariant { .. }
+            | CIError::CountOverflow
+            | CIError::DifferentColumns { .. } => Kind::InvalidInput,
+            CIError::InvalidConfidenceLevel(_)
+            | CIError::InvalidQuantile(_)
+            | CIError::InvalidSuccessRate(_)
+            | CIError::IndexError { .. }
+            | CIError::UnknownColumn(_)
+            | CIError::InvalidExposure(_)
+            | CIError::InvalidBaseline(_)
+            | CIError::InvalidDegreesOfFreedom(_)
+            | CIError::InvalidConfidenceSpec(_)
+            | CIError::InvalidStdError(_)
+            | CIError::InvalidDerivative(_)
+            | CIError::InvalidProbability(_)
+            | CIError::InvalidCriticalValue(_)
+            | CIError::InvalidCapacity(_)
+            | CIError::InvalidMargin(_)
+            | CIError::TooFewResamples { .. }
+            | CIError::InvalidTarget(_)
+            | CIError::InvalidDesignEffect(_)
+            | CIError::InvalidOutlierFactor(_)
+            | CIError::InvalidSmoothingFactor(_)
+            | CIError::InvalidTrimFraction(_)
+            | CIError::InvalidShape(_)
+            | CIError::EmptyQuantileGrid => Kind::InvalidParameter,
+            CIError::FloatConversionError(_) => Kind::Conversion,
+            CIError::IntervalError(_) => Kind::Interval,
+            CIError::UninterpretableTransformedInterval(_)
+            | CIError::CoefficientOfVariationTooLarge(_)
+            | CIError::ZeroVariance(_)
+            | CIError::TooManyRequiredTrials => Kind::UndefinedResult,
+            CIError::Error(_) => Kind::Other,
+        }
+    }
+
+    ///
+    /// Test whether the data is too small to compute the result (see [`Kind::TooFewSamples`]).
+    ///
+    pub fn is_too_few_samples(&self) -> bool {
+        self.kind() == Kind::TooFewSamples
+    }
+
+    ///
+    /// Test whether the data is invalid (see [`Kind::InvalidInput`]).
+    ///
+    pub fn is_invalid_input(&self) -> bool {
+        self.kind() == Kind::InvalidInput
+    }
+
+    ///
+    /// Test whether a value cannot be converted between float types (see [`Kind::Conversion`]).
+    ///
+    pub fn is_conversion_error(&self) -> bool {
+        self.kind() == Kind::Conversion
+    }
 }
 
 ///
 /// An error type for interval creation.
 ///
 #[allow(missing_docs)]
-#[derive(thiserror::Error, Debug)]
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
 pub enum IntervalError {
     #[error("Invalid bounds: the left bound is greater than the right bound")]
     InvalidBounds,
 
     #[error("Empty interval")]
     EmptyInterval,
+
+    #[error("Invalid margin: the margin must be non-negative")]
+    InvalidMargin,
+
+    #[error("Invalid bound: NaN is not a valid bound")]
+    NaNBound,
+
+    #[error("Unbounded interval: both bounds are infinite")]
+    Unbounded,
+
+    #[error("Invalid interval syntax: {0}")]
+    ParseError(String),
+
+    #[error("Unrepresentable result: {0}")]
+    UnrepresentableResult(String),
 }
 
 ///
 /// Error types for conversion from a generic [`Float`] type to a [`CIResult<f64>`].
 ///
 #[allow(missing_docs)]
-#[derive(thiserror::Error, Debug)]
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
 pub enum ConversionError {
     #[error("Empty interval has no concrete bounds")]
     NoConcreteBoundsError,
@@ -127,6 +413,29 @@ impl<F: Float> FloatReverseConversion<F> for Option<F> {
     }
 }
 
+///
+/// Decorator trait used to convert an [`f64`] to a generic [`Float`] type, failing instead of
+/// silently saturating to infinity when a finite value is out of range (e.g., for `f32`).
+///
+pub(crate) trait FloatNarrowing {
+    fn try_narrow<F: Float>(self, var_name: &str) -> CIResult<F>;
+}
+
+impl FloatNarrowing for f64 {
+    #[inline]
+    fn try_narrow<F: Float>(self, var_name: &str) -> CIResult<F> {
+        match F::from(self) {
+            Some(value) if value.is_finite() || !self.is_finite() => Ok(value),
+            _ => Err(CIError::FloatConversionError(format!(
+                "Error converting {} ({}) to {}: out of range",
+                var_name,
+                self,
+                std::any::type_name::<F>()
+            ))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,11 +447,65 @@ mod tests {
     #[test]
     fn test_string_to_error() {
         let err = string_to_error();
-        assert!(err.is_err());
-        match err {
-            Err(CIError::Error(s)) => assert_eq!(s, "This is a string error"),
-            Err(e) => panic!("Unexpected error type: {:?}", e),
-            Ok(_) => panic!("Unexpected success"),
-        }
+        assert_eq!(
+            err,
+            Err(CIError::Error("This is a string error".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_kind() {
+        let err = CIError::TooFewSamples(1);
+        assert_eq!(err.kind(), Kind::TooFewSamples);
+        assert!(err.is_too_few_samples());
+        assert!(!err.is_invalid_input());
+        assert!(!err.is_conversion_error());
+
+        let err = CIError::NonPositiveValueAt {
+            index: 3,
+            value: -1.,
+        };
+        assert_eq!(err.kind(), Kind::InvalidInput);
+        assert!(err.is_invalid_input());
+
+        assert_eq!(CIError::CountOverflow.kind(), Kind::InvalidInput);
+        let err = CIError::DifferentColumns {
+            names_a: vec!["x".to_string()],
+            names_b: vec!["y".to_string()],
+        };
+        assert_eq!(err.kind(), Kind::InvalidInput);
+        assert_eq!(err.to_string(), "Different columns: [\"x\"] vs. [\"y\"]");
+
+        let err = CIError::FloatConversionError("x".to_string());
+        assert_eq!(err.kind(), Kind::Conversion);
+        assert!(err.is_conversion_error());
+
+        assert_eq!(
+            CIError::InvalidConfidenceLevel(2.).kind(),
+            Kind::InvalidParameter
+        );
+        assert_eq!(
+            CIError::from(IntervalError::EmptyInterval).kind(),
+            Kind::Interval
+        );
+
+        // NaN fields make an error unequal to itself
+        assert_ne!(
+            CIError::InvalidQuantile(f64::NAN),
+            CIError::InvalidQuantile(f64::NAN)
+        );
+        assert_eq!(CIError::InvalidQuantile(0.5), CIError::InvalidQuantile(0.5));
+    }
+
+    #[test]
+    fn test_try_narrow() -> CIResult<()> {
+        assert_eq!(1.5f64.try_narrow::<f32>("x")?, 1.5f32);
+        assert_eq!(1e300f64.try_narrow::<f64>("x")?, 1e300);
+        assert!(1e300f64
+            .try_narrow::<f32>("x")
+            .is_err_and(|e| e.is_conversion_error()));
+        assert_eq!(f64::INFINITY.try_narrow::<f32>("x")?, f32::INFINITY);
+        assert!(f64::NAN.try_narrow::<f32>("x")?.is_nan());
+        Ok(())
     }
 }
@@ -5,6 +5,8 @@
 //! Note that floating point numbers are only partially ordered because of `NaN` values.
 //!
 
+use crate::error::CIResult;
+use crate::Confidence;
 use core::ops::{Add, Div, Mul, Neg, Sub};
 use core::ops::{Bound, RangeBounds};
 use core::ops::{RangeFrom, RangeInclusive, RangeToInclusive};
@@ -64,6 +66,7 @@ use num_traits::Num;
 /// * [`Self::is_included_in(other)`](#method.is_included_in): Test whether the interval is included in another interval.
 /// * [`Self::includes(other)`](#method.includes): Test whether the interval includes another interval.
 /// * [`Self::contains(x)`](#method.contains): Test whether the interval contains a value.
+/// * [`Self::contains_total(x)`](#method.contains_total), [`Self::intersects_total(other)`](#method.intersects_total), [`Self::includes_total(other)`](#method.includes_total): Same as above for floating point types, with `None` when a NaN is involved.
 /// * approximate equality with [`approx`](https://docs.rs/approx/0.3.3/approx/) if the `approx` feature is enabled.
 ///
 /// ### Operators with a scalar value
@@ -73,6 +76,14 @@ use num_traits::Num;
 /// * [`Self::add(rhs)`](#method.add): Add a value to the interval.
 /// * [`Self::sub(rhs)`](#method.sub): Subtract a value from the interval.
 ///
+/// These only require the bound type to be `Clone`, not `Copy`.
+///
+/// ### Mapping
+///
+/// * [`Self::map(f)`](#method.map), [`Self::map_ref(f)`](#method.map_ref): Apply a non-decreasing function to the bounds (e.g., to unwrap a newtype into `f64` and back).
+/// * [`Self::map_reversed(f)`](#method.map_reversed): Apply a non-increasing function to the bounds, which swaps them.
+/// * [`Self::width_by(diff)`](#method.width_by): Compute the width of the interval through a function of its bounds.
+///
 /// ### Operators with another interval
 ///
 /// * [`Self::relative_to(reference)`](#method.relative_to): Given two intervals, compute the relative interval compared to the reference (argument). The relative interval is defined as the interval of the ratios of the two intervals.
@@ -157,7 +168,8 @@ use num_traits::Num;
 /// ```
 ///
 #[derive(Debug, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "IntervalRepr<T>"))]
 pub enum Interval<T>
 where
     T: PartialOrd,
@@ -211,6 +223,28 @@ impl<T: PartialOrd> Interval<T> {
         }
     }
 
+    ///
+    /// Create a new two-sided interval from its left and right bounds without checking them,
+    /// which makes it usable in `const` contexts (e.g., to define constants).
+    ///
+    /// The caller must ensure that `low <= high` (in particular, that neither bound is NaN),
+    /// which is otherwise checked by [`Interval::new`]. The operations on an interval that
+    /// breaks this invariant do not cause undefined behavior, but their results are unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::Interval;
+    /// const ACCEPTANCE: Interval<f64> = Interval::two_sided_unchecked(0.9, 1.1);
+    /// assert_eq!(ACCEPTANCE, Interval::new(0.9, 1.1)?);
+    /// assert!(ACCEPTANCE.contains(&1.));
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub const fn two_sided_unchecked(low: T, high: T) -> Self {
+        Interval::TwoSided(low, high)
+    }
+
     ///
     /// Create a new upper one-sided interval from its left bound.
     /// The interval is defined as [low, +∞).
@@ -224,10 +258,12 @@ impl<T: PartialOrd> Interval<T> {
     /// let interval = Interval::new_upper(0.);
     /// assert_eq!(interval.low(), Some(0.));
     /// assert_eq!(interval.high(), None);
+    /// const NON_NEGATIVE: Interval<f64> = Interval::new_upper(0.);
+    /// assert_eq!(NON_NEGATIVE, interval);
     /// # Ok::<(),stats_ci::error::IntervalError>(())
     /// ```
     ///
-    pub fn new_upper(low: T) -> Self {
+    pub const fn new_upper(low: T) -> Self {
         Interval::UpperOneSided(low)
     }
 
@@ -247,10 +283,43 @@ impl<T: PartialOrd> Interval<T> {
     /// # Ok::<(),stats_ci::error::IntervalError>(())
     /// ```
     ///
-    pub fn new_lower(high: T) -> Self {
+    pub const fn new_lower(high: T) -> Self {
         Interval::LowerOneSided(high)
     }
 
+    ///
+    /// Create the interval corresponding to the kind of the given confidence from its bounds:
+    /// * [`Confidence::TwoSided`] - the two-sided interval \\( [lo, hi] \\)
+    /// * [`Confidence::UpperOneSided`] - the upper one-sided interval \\( [lo, +\infty) \\)
+    /// * [`Confidence::LowerOneSided`] - the lower one-sided interval \\( (-\infty, hi] \\)
+    ///
+    /// The bound that is irrelevant for a one-sided confidence is ignored.
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::InvalidBounds`] - if the confidence is two-sided and `lo` is greater than `hi`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// assert_eq!(Interval::from_bounds_for(confidence, 1, 3)?, Interval::new(1, 3)?);
+    /// let confidence = Confidence::new_upper(0.95);
+    /// assert_eq!(Interval::from_bounds_for(confidence, 1, 3)?, Interval::new_upper(1));
+    /// let confidence = Confidence::new_lower(0.95);
+    /// assert_eq!(Interval::from_bounds_for(confidence, 1, 3)?, Interval::new_lower(3));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn from_bounds_for(confidence: Confidence, lo: T, hi: T) -> CIResult<Self> {
+        match confidence {
+            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
+            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
+            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+        }
+    }
+
     ///
     /// Test whether the interval is two-sided.
     ///
@@ -282,6 +351,15 @@ impl<T: PartialOrd> Interval<T> {
     ///
     /// Test whether the interval contains a value.
     ///
+    /// A value that is not comparable with the bounds (e.g., NaN) is never contained.
+    /// Use [`Interval::contains_total`] to tell such values apart from those outside the interval.
+    ///
+    /// # Panics
+    ///
+    /// * in debug builds, if a bound of the interval is not comparable with itself (e.g., NaN),
+    ///   in which case the result is unspecified; such intervals can be created with [`Interval::new`]
+    ///   or the unchecked constructors, and are rejected by [`Interval::try_new_finite`]
+    ///
     /// # Examples
     ///
     /// ```
@@ -289,10 +367,12 @@ impl<T: PartialOrd> Interval<T> {
     /// let interval = Interval::new(0., 1.)?;
     /// assert!(interval.contains(&0.5));
     /// assert!(!interval.contains(&2.));
+    /// assert!(!interval.contains(&f64::NAN));
     /// # Ok::<(),stats_ci::error::IntervalError>(())
     /// ```
     ///
     pub fn contains(&self, x: &T) -> bool {
+        self.debug_assert_comparable_bounds();
         match self {
             Interval::TwoSided(low, high) => low <= x && x <= high,
             Interval::UpperOneSided(low) => low <= x,
@@ -300,10 +380,97 @@ impl<T: PartialOrd> Interval<T> {
         }
     }
 
+    ///
+    /// Count the elements of a collection that are contained in the interval (see [`Interval::contains`]).
+    ///
+    /// Elements that are not comparable with the bounds (e.g., NaN) are never contained.
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::Interval;
+    /// let interval = Interval::new(0., 1.)?;
+    /// assert_eq!(interval.count_contained([-1., 0., 0.5, 1., 2., f64::NAN]), 3);
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn count_contained<I: IntoIterator<Item = T>>(&self, data: I) -> usize {
+        data.into_iter().filter(|x| self.contains(x)).count()
+    }
+
+    ///
+    /// Proportion of the elements of a collection that are contained in the interval (see [`Interval::contains`]).
+    ///
+    /// Elements that are not comparable with the bounds (e.g., NaN) are never contained but count in the total.
+    /// The proportion is NaN if the collection is empty.
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::Interval;
+    /// let interval = Interval::new_upper(0);
+    /// assert_eq!(interval.proportion_contained([-2, -1, 0, 1]), 0.5);
+    /// ```
+    ///
+    pub fn proportion_contained<I: IntoIterator<Item = T>>(&self, data: I) -> f64 {
+        let (contained, total) = data.into_iter().fold((0_usize, 0_usize), |(c, t), x| {
+            (c + self.contains(&x) as usize, t + 1)
+        });
+        contained as f64 / total as f64
+    }
+
+    ///
+    /// Split the elements of a slice into those below, inside, and above the interval, in that order.
+    ///
+    /// An element is inside if it is contained in the interval (see [`Interval::contains`]), below (resp. above)
+    /// if it is strictly smaller (resp. greater) than the lower (resp. upper) bound. One-sided intervals thus always
+    /// yield an empty side where they are unbounded. Elements that are not comparable with the bounds (e.g., NaN)
+    /// belong to none of the three parts.
+    /// The order of the elements is preserved within each part.
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::Interval;
+    /// let interval = Interval::new(0., 1.)?;
+    /// let (below, inside, above) = interval.partition(&[2., -1., 0.5, f64::NAN, 1.]);
+    /// assert_eq!(below, [&-1.]);
+    /// assert_eq!(inside, [&0.5, &1.]);
+    /// assert_eq!(above, [&2.]);
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn partition<'a>(&self, data: &'a [T]) -> (Vec<&'a T>, Vec<&'a T>, Vec<&'a T>) {
+        let mut below = Vec::new();
+        let mut inside = Vec::new();
+        let mut above = Vec::new();
+        for x in data {
+            if self.contains(x) {
+                inside.push(x);
+            } else if self.left().is_some_and(|low| x < low) {
+                below.push(x);
+            } else if self.right().is_some_and(|high| x > high) {
+                above.push(x);
+            }
+        }
+        (below, inside, above)
+    }
+
     ///
     /// Test whether the interval intersects another interval.
     /// Two intervals are considered to intersect even if they only have a single point in common (e.g., one of their bounds).
     ///
+    /// # Panics
+    ///
+    /// * in debug builds, if a bound of either interval is not comparable with itself (e.g., NaN),
+    ///   in which case the result is unspecified (see [`Interval::contains`])
+    ///
     /// # Examples
     ///
     /// ```
@@ -317,18 +484,19 @@ impl<T: PartialOrd> Interval<T> {
     /// ```
     ///
     pub fn intersects(&self, other: &Self) -> bool {
+        self.debug_assert_comparable_bounds();
+        other.debug_assert_comparable_bounds();
         match (self, other) {
             (Interval::UpperOneSided(_), Interval::UpperOneSided(_)) => true,
             (Interval::LowerOneSided(_), Interval::LowerOneSided(_)) => true,
             (Interval::UpperOneSided(x), Interval::LowerOneSided(y) | Interval::TwoSided(_, y)) => {
                 x <= y
             }
-            (Interval::LowerOneSided(x), Interval::UpperOneSided(y) | Interval::TwoSided(_, y)) => {
-                x <= y
-            }
-            (Interval::TwoSided(x, y), Interval::UpperOneSided(z) | Interval::LowerOneSided(z)) => {
-                x <= z && z <= y
+            (Interval::LowerOneSided(x), Interval::UpperOneSided(y) | Interval::TwoSided(y, _)) => {
+                y <= x
             }
+            (Interval::TwoSided(_, y), Interval::UpperOneSided(z)) => z <= y,
+            (Interval::TwoSided(x, _), Interval::LowerOneSided(z)) => x <= z,
             (Interval::TwoSided(x, y), Interval::TwoSided(a, b)) => x <= b && a <= y,
         }
     }
@@ -347,7 +515,14 @@ impl<T: PartialOrd> Interval<T> {
     ///
     /// The inclusion is not strict, i.e. an interval includes itself.
     ///
+    /// # Panics
+    ///
+    /// * in debug builds, if a bound of either interval is not comparable with itself (e.g., NaN),
+    ///   in which case the result is unspecified (see [`Interval::contains`])
+    ///
     pub fn includes(&self, other: &Self) -> bool {
+        self.debug_assert_comparable_bounds();
+        other.debug_assert_comparable_bounds();
         match (self, other) {
             (Interval::UpperOneSided(x), Interval::UpperOneSided(y)) => x <= y,
             (Interval::LowerOneSided(x), Interval::LowerOneSided(y)) => x >= y,
@@ -380,6 +555,24 @@ impl<T: PartialOrd> Interval<T> {
             Interval::UpperOneSided(_) => None,
         }
     }
+
+    ///
+    /// Test whether both bounds of the interval (if any) are comparable with themselves,
+    /// which is the case for all values of a total order and all floats but NaN.
+    ///
+    fn has_comparable_bounds(&self) -> bool {
+        let comparable = |x: &T| x.partial_cmp(x).is_some();
+        self.left().is_none_or(comparable) && self.right().is_none_or(comparable)
+    }
+
+    #[inline]
+    fn debug_assert_comparable_bounds(&self) {
+        debug_assert!(
+            self.has_comparable_bounds(),
+            "interval with a NaN bound: comparisons are unspecified; \
+             create float intervals with `Interval::try_new_finite` to reject NaN bounds"
+        );
+    }
 }
 
 impl<T: PartialOrd + PartialEq> Interval<T> {
@@ -414,9 +607,209 @@ impl<T: PartialOrd + Clone> Interval<T> {
     pub fn high(&self) -> Option<T> {
         self.right().cloned()
     }
+
+    ///
+    /// Intersection of the interval with another interval, or `None` if they do not intersect.
+    ///
+    /// Since bounds are inclusive, two intervals that only share a bound intersect at that single point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::Interval;
+    /// let interval = Interval::new(0., 10.)?;
+    /// assert_eq!(interval.intersection(&Interval::new(5., 15.)?), Some(Interval::new(5., 10.)?));
+    /// assert_eq!(interval.intersection(&Interval::new_lower(2.)), Some(Interval::new(0., 2.)?));
+    /// assert_eq!(interval.intersection(&Interval::new_upper(10.)), Some(Interval::new(10., 10.)?));
+    /// assert_eq!(interval.intersection(&Interval::new(11., 12.)?), None);
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        if !self.intersects(other) {
+            return None;
+        }
+        // the greatest of the left bounds and the least of the right bounds
+        let low = match (self.left(), other.left()) {
+            (Some(x), Some(y)) => Some(if x >= y { x } else { y }),
+            (x, y) => x.or(y),
+        };
+        let high = match (self.right(), other.right()) {
+            (Some(x), Some(y)) => Some(if x <= y { x } else { y }),
+            (x, y) => x.or(y),
+        };
+        Interval::try_from((low.cloned(), high.cloned())).ok()
+    }
+
+    ///
+    /// Difference of the interval with another interval, i.e., the values of the interval that are not in `other`.
+    ///
+    /// The result can consist of up to two disjoint intervals, hence it is returned as an [`IntervalSet`].
+    /// Since intervals are inclusive of their bounds, the result is the closure of the difference:
+    /// the bounds of `other` that fall within the interval are kept as bounds of the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::new(-10., 10.)?;
+    /// let difference = interval.difference(&Interval::new(-5., 5.)?);
+    /// assert_eq!(difference.to_string(), "[-10, -5] ∪ [5, 10]");
+    /// assert_eq!(interval.difference(&Interval::new_upper(0.)).to_string(), "[-10, 0]");
+    /// assert_eq!(Interval::new_upper(0.).difference(&interval).to_string(), "[10,->)");
+    /// assert!(interval.difference(&Interval::new_lower(20.)).is_empty());
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn difference(&self, other: &Self) -> IntervalSet<T> {
+        if !self.intersects(other) {
+            return IntervalSet {
+                members: vec![self.clone()],
+            };
+        }
+        let mut pieces = Vec::with_capacity(2);
+        // values of `self` left of `other`
+        if let Some(x) = other.left() {
+            if self.left().is_none_or(|low| low < x) {
+                pieces.push(Interval::try_from((self.low(), Some(x.clone()))));
+            }
+        }
+        // values of `self` right of `other`
+        if let Some(x) = other.right() {
+            if self.right().is_none_or(|high| x < high) {
+                pieces.push(Interval::try_from((Some(x.clone()), self.high())));
+            }
+        }
+        // pieces are included in `self`, hence valid and bounded on at least one side
+        let pieces = pieces.into_iter().filter_map(Result::ok).collect();
+        IntervalSet::from_intervals(pieces).expect("subsets of an interval are bounded")
+    }
 }
 
 impl<T: num_traits::Float> Interval<T> {
+    ///
+    /// Create a new interval from two floating-point bounds, rejecting NaN bounds.
+    ///
+    /// Unlike [`Interval::new`], for which a NaN bound yields an interval on which
+    /// comparisons are meaningless, this constructor checks the bounds explicitly.
+    /// Infinite bounds are normalized into one-sided intervals: \\( [low, +\infty) \\) becomes
+    /// [`Interval::UpperOneSided`] and \\( (-\infty, high] \\) becomes [`Interval::LowerOneSided`].
+    ///
+    /// # Arguments
+    ///
+    /// * `low` - the lower bound of the interval
+    /// * `high` - the upper bound of the interval
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::NaNBound`] - if either bound is NaN
+    /// * [`IntervalError::InvalidBounds`] - if `low` is greater than `high`
+    /// * [`IntervalError::Unbounded`] - if `low` is \\( -\infty \\) and `high` is \\( +\infty \\)
+    /// * [`IntervalError::EmptyInterval`] - if `low` is \\( +\infty \\) or `high` is \\( -\infty \\)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use stats_ci::error::IntervalError;
+    /// assert_eq!(Interval::try_new_finite(1., 2.)?, Interval::new(1., 2.)?);
+    /// assert_eq!(Interval::try_new_finite(2., f64::INFINITY)?, Interval::new_upper(2.));
+    /// assert_eq!(Interval::try_new_finite(f64::NEG_INFINITY, 2.)?, Interval::new_lower(2.));
+    /// assert_eq!(Interval::try_new_finite(f64::NAN, 2.), Err(IntervalError::NaNBound));
+    /// # Ok::<(),IntervalError>(())
+    /// ```
+    ///
+    pub fn try_new_finite(low: T, high: T) -> Result<Self, IntervalError> {
+        if low.is_nan() || high.is_nan() {
+            return Err(IntervalError::NaNBound);
+        }
+        if low > high {
+            return Err(IntervalError::InvalidBounds);
+        }
+        if low == T::infinity() || high == T::neg_infinity() {
+            return Err(IntervalError::EmptyInterval);
+        }
+        match (low == T::neg_infinity(), high == T::infinity()) {
+            (true, true) => Err(IntervalError::Unbounded),
+            (true, false) => Ok(Interval::LowerOneSided(high)),
+            (false, true) => Ok(Interval::UpperOneSided(low)),
+            (false, false) => Ok(Interval::TwoSided(low, high)),
+        }
+    }
+
+    ///
+    /// Test whether the interval contains a value, distinguishing values outside the interval
+    /// from values that cannot be compared with it.
+    ///
+    /// Returns `None` if `x` or a bound of the interval is NaN, and otherwise the same as
+    /// [`Interval::contains`]. Infinite values are ordered as usual, e.g., \\( +\\infty \\) is contained
+    /// in any upper one-sided interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::Interval;
+    /// let interval = Interval::new(0., 1.)?;
+    /// assert_eq!(interval.contains_total(&0.5), Some(true));
+    /// assert_eq!(interval.contains_total(&2.), Some(false));
+    /// assert_eq!(interval.contains_total(&f64::NAN), None);
+    /// assert_eq!(Interval::new_upper(0.).contains_total(&f64::INFINITY), Some(true));
+    /// // a NaN bound is not rejected by `Interval::new`
+    /// assert_eq!(Interval::new(f64::NAN, 1.)?.contains_total(&0.5), None);
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn contains_total(&self, x: &T) -> Option<bool> {
+        (!x.is_nan() && !self.has_nan_bound()).then(|| self.contains(x))
+    }
+
+    ///
+    /// Same as [`Interval::intersects`], with `None` if a bound of either interval is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::Interval;
+    /// let interval = Interval::new(0., 1.)?;
+    /// assert_eq!(interval.intersects_total(&Interval::new(1., 2.)?), Some(true));
+    /// assert_eq!(interval.intersects_total(&Interval::new(f64::NAN, 2.)?), None);
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn intersects_total(&self, other: &Self) -> Option<bool> {
+        (!self.has_nan_bound() && !other.has_nan_bound()).then(|| self.intersects(other))
+    }
+
+    ///
+    /// Same as [`Interval::includes`], with `None` if a bound of either interval is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::Interval;
+    /// let interval = Interval::new(0., 1.)?;
+    /// assert_eq!(interval.includes_total(&Interval::new(0.5, 1.)?), Some(true));
+    /// assert_eq!(interval.includes_total(&Interval::new_lower(f64::NAN)), None);
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn includes_total(&self, other: &Self) -> Option<bool> {
+        (!self.has_nan_bound() && !other.has_nan_bound()).then(|| self.includes(other))
+    }
+
+    fn has_nan_bound(&self) -> bool {
+        self.low_f().is_nan() || self.high_f().is_nan()
+    }
+
+    ///
+    /// Same as [`Interval::from_bounds_for`], with the bounds of the resulting interval
+    /// checked through [`Interval::try_new_finite`].
+    ///
+    pub(crate) fn from_finite_bounds_for(confidence: Confidence, lo: T, hi: T) -> CIResult<Self> {
+        let interval = Self::from_bounds_for(confidence, lo, hi)?;
+        Self::try_new_finite(interval.low_f(), interval.high_f()).map_err(|e| e.into())
+    }
+
     ///
     /// Get the lower bound of the interval (if any) for floating point types.
     /// This function returns the negative infinite value for `T` for lower one-sided intervals.
@@ -474,6 +867,60 @@ impl<T: num_traits::Float> Interval<T> {
             }
         }
     }
+
+    ///
+    /// Same as [`Interval::relative_to`], but returns an error instead of panicking, and wraps the
+    /// result in a [`RelativeInterval`] for reporting.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidBaseline`] - if a bound of the reference is not strictly positive, or
+    ///   if both intervals are one-sided in the same direction
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let reference = Interval::new(100., 110.)?;
+    /// let relative = Interval::new(90., 95.)?.try_relative_to(&reference)?;
+    /// assert_eq!(relative.to_string(), "-18.2% … -5.0%");
+    /// assert_eq!(relative.is_improvement(true), Some(true));
+    /// assert!(Interval::new(1., 2.)?.try_relative_to(&Interval::new(0., 1.)?).is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn try_relative_to(&self, reference: &Interval<T>) -> CIResult<RelativeInterval<T>> {
+        let invalid = |reason: &str| crate::error::CIError::InvalidBaseline(reason.to_string());
+        if [reference.left(), reference.right()]
+            .into_iter()
+            .flatten()
+            .any(|bound| bound.is_nan() || *bound <= T::zero())
+        {
+            return Err(invalid("the reference interval must be strictly positive"));
+        }
+        if (self.is_upper() && reference.is_upper()) || (self.is_lower() && reference.is_lower()) {
+            return Err(invalid(
+                "cannot compare one-sided intervals with the same direction",
+            ));
+        }
+        Ok(RelativeInterval(self.relative_to(reference)))
+    }
+
+    ///
+    /// Interval with both bounds multiplied by 100, e.g., to express a ratio or a relative change as a percentage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// assert_eq!(Interval::new(-0.5, 0.25)?.as_percent(), Interval::new(-50., 25.)?);
+    /// assert_eq!(Interval::new_lower(-0.5).as_percent(), Interval::new_lower(-50.));
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn as_percent(self) -> Interval<T> {
+        self * T::from(100.).unwrap()
+    }
 }
 
 impl<T: num_traits::PrimInt + num_traits::Signed> Interval<T> {
@@ -546,54 +993,320 @@ impl<T: PartialOrd> Interval<T> {
         self.right()
     }
 }
-impl<T: PartialOrd + Copy> Interval<T> {
-    fn applied<F>(&self, f_low: F, f_high: F) -> Self
+impl<T: PartialOrd> Interval<T> {
+    ///
+    /// Apply a non-decreasing function to the bounds, keeping the kind of the interval.
+    ///
+    /// This is the escape hatch for bound types that lack the traits required by other methods
+    /// (e.g., a units newtype that is neither `Copy` nor [`num_traits::Float`]): unwrap the bounds,
+    /// operate on the result, and wrap them again if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - the function to apply to each bound; it must be non-decreasing (use
+    ///   [`Self::map_reversed`] for a non-increasing function)
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if the resulting bounds are in the wrong order because `f` is not
+    /// non-decreasing. In release builds, the bounds are not checked and the operations on the
+    /// resulting interval are unspecified (see [`Self::two_sided_unchecked`]).
+    /// Use [`Self::try_map`] to check the bounds in all builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// #[derive(Debug, Clone, PartialEq, PartialOrd)]
+    /// struct Millis(f64);
+    ///
+    /// let interval = Interval::new(Millis(1500.), Millis(2500.))?;
+    /// let seconds = interval.clone().map(|Millis(ms)| ms / 1000.);
+    /// assert_eq!(seconds, Interval::new(1.5, 2.5)?);
+    /// assert_eq!(seconds.low_f(), 1.5);
+    /// assert_eq!(seconds.map(|s| Millis(s * 1000.)), interval);
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn map<U: PartialOrd, F>(self, f: F) -> Interval<U>
     where
-        F: FnOnce(T) -> T,
+        F: Fn(T) -> U,
     {
-        match self {
-            Interval::TwoSided(low, high) => Interval::TwoSided(f_low(*low), f_high(*high)),
-            Interval::LowerOneSided(low) => Interval::UpperOneSided(f_low(*low)),
-            Interval::UpperOneSided(high) => Interval::LowerOneSided(f_high(*high)),
-        }
+        let result = match self {
+            Interval::TwoSided(low, high) => Interval::TwoSided(f(low), f(high)),
+            Interval::UpperOneSided(low) => Interval::UpperOneSided(f(low)),
+            Interval::LowerOneSided(high) => Interval::LowerOneSided(f(high)),
+        };
+        debug_assert!(!result.has_reversed_bounds(), "`f` must be non-decreasing");
+        result
     }
 
-    fn applied_both<F>(&self, f: F) -> Self
+    ///
+    /// Apply a non-decreasing function to the bounds, keeping the kind of the interval, and check
+    /// the resulting bounds.
+    ///
+    /// Only the resulting bounds are checked, not the monotonicity of `f` between them.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - the function to apply to each bound
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::InvalidBounds`] - if the resulting bounds are in the wrong order
+    ///   (i.e., `f` is not non-decreasing)
+    /// * [`IntervalError::NaNBound`] - if a resulting bound is not comparable to itself (e.g., NaN)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::new(-2_f64, 3.)?;
+    /// assert_eq!(interval.try_map(|x| x * 2.)?, Interval::new(-4., 6.)?);
+    /// assert_eq!(interval.try_map(|x| -x), Err(error::IntervalError::InvalidBounds));
+    /// assert_eq!(interval.try_map(|x| x.ln()), Err(error::IntervalError::NaNBound));
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn try_map<U: PartialOrd, F>(self, f: F) -> Result<Interval<U>, IntervalError>
     where
-        F: Fn(T) -> T,
+        F: Fn(T) -> U,
     {
-        self.applied(&f, &f)
-    }
-}
-
-#[cfg(feature = "approx")]
-impl<T: approx::AbsDiffEq + PartialOrd> approx::AbsDiffEq for Interval<T>
-where
-    T::Epsilon: Copy,
-{
-    type Epsilon = T::Epsilon;
-
-    fn default_epsilon() -> T::Epsilon {
-        T::default_epsilon()
-    }
-
-    fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
-        match (self, other) {
-            (Interval::TwoSided(a, b), Interval::TwoSided(x, y)) => {
-                T::abs_diff_eq(a, x, epsilon) && T::abs_diff_eq(b, y, epsilon)
-            }
-            (Interval::UpperOneSided(a), Interval::UpperOneSided(x)) => {
-                T::abs_diff_eq(a, x, epsilon)
+        match self {
+            Interval::TwoSided(low, high) => Interval::checked_two_sided(f(low), f(high)),
+            Interval::UpperOneSided(low) => {
+                Interval::checked_bound(f(low)).map(Interval::UpperOneSided)
             }
-            (Interval::LowerOneSided(b), Interval::LowerOneSided(y)) => {
-                T::abs_diff_eq(b, y, epsilon)
+            Interval::LowerOneSided(high) => {
+                Interval::checked_bound(f(high)).map(Interval::LowerOneSided)
             }
-            _ => false,
         }
     }
-}
 
-#[cfg(feature = "approx")]
+    ///
+    /// Apply a non-increasing function to the bounds, which swaps them (and the direction of one-sided intervals).
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if the resulting bounds are in the wrong order because `f` is not
+    /// non-increasing (see [`Self::map`]). Use [`Self::try_map_reversed`] to check the bounds in
+    /// all builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::new(2., 4.)?;
+    /// assert_eq!(interval.map_reversed(|x| 1. / x), Interval::new(0.25, 0.5)?);
+    /// assert_eq!(Interval::new_upper(2.).map_reversed(|x| -x), Interval::new_lower(-2.));
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn map_reversed<U: PartialOrd, F>(self, f: F) -> Interval<U>
+    where
+        F: Fn(T) -> U,
+    {
+        let result = match self {
+            Interval::TwoSided(low, high) => Interval::TwoSided(f(high), f(low)),
+            Interval::UpperOneSided(low) => Interval::LowerOneSided(f(low)),
+            Interval::LowerOneSided(high) => Interval::UpperOneSided(f(high)),
+        };
+        debug_assert!(!result.has_reversed_bounds(), "`f` must be non-increasing");
+        result
+    }
+
+    ///
+    /// Apply a non-increasing function to the bounds, which swaps them (and the direction of
+    /// one-sided intervals), and check the resulting bounds.
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::InvalidBounds`] - if the resulting bounds are in the wrong order
+    ///   (i.e., `f` is not non-increasing)
+    /// * [`IntervalError::NaNBound`] - if a resulting bound is not comparable to itself (e.g., NaN)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::new(2., 4.)?;
+    /// assert_eq!(interval.try_map_reversed(|x| 1. / x)?, Interval::new(0.25, 0.5)?);
+    /// assert_eq!(interval.try_map_reversed(|x| x + 1.), Err(error::IntervalError::InvalidBounds));
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn try_map_reversed<U: PartialOrd, F>(self, f: F) -> Result<Interval<U>, IntervalError>
+    where
+        F: Fn(T) -> U,
+    {
+        match self {
+            Interval::TwoSided(low, high) => Interval::checked_two_sided(f(high), f(low)),
+            Interval::UpperOneSided(low) => {
+                Interval::checked_bound(f(low)).map(Interval::LowerOneSided)
+            }
+            Interval::LowerOneSided(high) => {
+                Interval::checked_bound(f(high)).map(Interval::UpperOneSided)
+            }
+        }
+    }
+
+    ///
+    /// Apply a non-decreasing function to references to the bounds, without consuming the interval.
+    ///
+    /// See [`Self::map`] for details.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if the resulting bounds are in the wrong order because `f` is not
+    /// non-decreasing (see [`Self::map`]). Use [`Self::try_map_ref`] to check the bounds in all
+    /// builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// #[derive(Debug, Clone, PartialEq, PartialOrd)]
+    /// struct Millis(f64);
+    ///
+    /// let interval = Interval::new_upper(Millis(1500.));
+    /// assert_eq!(interval.map_ref(|ms| ms.0).low_f(), 1500.);
+    /// assert!(interval.contains(&Millis(2000.)));
+    /// ```
+    ///
+    pub fn map_ref<U: PartialOrd, F>(&self, f: F) -> Interval<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        let result = match self {
+            Interval::TwoSided(low, high) => Interval::TwoSided(f(low), f(high)),
+            Interval::UpperOneSided(low) => Interval::UpperOneSided(f(low)),
+            Interval::LowerOneSided(high) => Interval::LowerOneSided(f(high)),
+        };
+        debug_assert!(!result.has_reversed_bounds(), "`f` must be non-decreasing");
+        result
+    }
+
+    ///
+    /// Apply a non-decreasing function to references to the bounds, without consuming the
+    /// interval, and check the resulting bounds.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::try_map`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::new_upper(-1_f64);
+    /// assert_eq!(interval.try_map_ref(|x| x.exp())?, Interval::new_upper((-1f64).exp()));
+    /// assert_eq!(interval.try_map_ref(|x| x.sqrt()), Err(error::IntervalError::NaNBound));
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn try_map_ref<U: PartialOrd, F>(&self, f: F) -> Result<Interval<U>, IntervalError>
+    where
+        F: Fn(&T) -> U,
+    {
+        match self {
+            Interval::TwoSided(low, high) => Interval::checked_two_sided(f(low), f(high)),
+            Interval::UpperOneSided(low) => {
+                Interval::checked_bound(f(low)).map(Interval::UpperOneSided)
+            }
+            Interval::LowerOneSided(high) => {
+                Interval::checked_bound(f(high)).map(Interval::LowerOneSided)
+            }
+        }
+    }
+
+    ///
+    /// Whether the interval is two-sided with its bounds in the wrong order.
+    ///
+    fn has_reversed_bounds(&self) -> bool {
+        matches!(self, Interval::TwoSided(low, high) if low > high)
+    }
+
+    ///
+    /// Check that a bound is comparable to itself (i.e., not NaN).
+    ///
+    fn checked_bound(bound: T) -> Result<T, IntervalError> {
+        if bound.partial_cmp(&bound).is_none() {
+            Err(IntervalError::NaNBound)
+        } else {
+            Ok(bound)
+        }
+    }
+
+    ///
+    /// Create a two-sided interval after checking that its bounds are comparable and in order.
+    ///
+    fn checked_two_sided(low: T, high: T) -> Result<Self, IntervalError> {
+        let low = Self::checked_bound(low)?;
+        let high = Self::checked_bound(high)?;
+        Self::new(low, high)
+    }
+
+    ///
+    /// Compute the width of the interval through a function of its bounds, for bound types that
+    /// do not support [`Self::width`] directly.
+    /// If the interval is one-sided, the function returns `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `diff` - the function computing the width from the lower and upper bounds (in that order)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// #[derive(Debug, Clone, PartialEq, PartialOrd)]
+    /// struct Millis(f64);
+    ///
+    /// let interval = Interval::new(Millis(1500.), Millis(2500.))?;
+    /// assert_eq!(interval.width_by(|low, high| high.0 - low.0), Some(1000.));
+    /// assert_eq!(Interval::new_lower(Millis(1.)).width_by(|low, high| high.0 - low.0), None);
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn width_by<U, F>(&self, diff: F) -> Option<U>
+    where
+        F: Fn(&T, &T) -> U,
+    {
+        match self {
+            Interval::LowerOneSided(_) | Interval::UpperOneSided(_) => None,
+            Interval::TwoSided(low, high) => Some(diff(low, high)),
+        }
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T: approx::AbsDiffEq + PartialOrd> approx::AbsDiffEq for Interval<T>
+where
+    T::Epsilon: Copy,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
+        match (self, other) {
+            (Interval::TwoSided(a, b), Interval::TwoSided(x, y)) => {
+                T::abs_diff_eq(a, x, epsilon) && T::abs_diff_eq(b, y, epsilon)
+            }
+            (Interval::UpperOneSided(a), Interval::UpperOneSided(x)) => {
+                T::abs_diff_eq(a, x, epsilon)
+            }
+            (Interval::LowerOneSided(b), Interval::LowerOneSided(y)) => {
+                T::abs_diff_eq(b, y, epsilon)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "approx")]
 impl<T: approx::RelativeEq + PartialOrd> approx::RelativeEq for Interval<T>
 where
     T::Epsilon: Copy,
@@ -644,100 +1357,243 @@ where
     }
 }
 
-impl<F: Mul<F, Output = F> + PartialOrd + Copy> Mul<F> for Interval<F> {
+///
+/// Multiplication by a scalar. A negative scalar reverses the order of the bounds, hence
+/// also the direction of one-sided intervals (e.g., \\( [2, +\infty) \times -1 = (-\infty, -2] \\)).
+///
+impl<F: Mul<F, Output = F> + num_traits::Zero + PartialOrd + Clone> Mul<F> for Interval<F> {
     type Output = Self;
 
     fn mul(self, rhs: F) -> Self::Output {
-        self.applied_both(|x| x * rhs)
+        if rhs < F::zero() {
+            self.map_reversed(|x| x * rhs.clone())
+        } else {
+            self.map(|x| x * rhs.clone())
+        }
     }
 }
 
-impl<F: Div<F, Output = F> + PartialOrd + Copy> Div<F> for Interval<F> {
+///
+/// Division by a scalar. A negative scalar reverses the order of the bounds, hence
+/// also the direction of one-sided intervals.
+///
+impl<F: Div<F, Output = F> + num_traits::Zero + PartialOrd + Clone> Div<F> for Interval<F> {
     type Output = Self;
 
     fn div(self, rhs: F) -> Self::Output {
-        self.applied_both(|x| x / rhs)
+        if rhs < F::zero() {
+            self.map_reversed(|x| x / rhs.clone())
+        } else {
+            self.map(|x| x / rhs.clone())
+        }
     }
 }
 
-impl<F: Add<F, Output = F> + PartialOrd + Copy> Add<F> for Interval<F> {
+impl<F: Add<F, Output = F> + PartialOrd + Clone> Add<F> for Interval<F> {
     type Output = Self;
 
     fn add(self, rhs: F) -> Self::Output {
-        self.applied_both(|x| x + rhs)
+        self.map(|x| x + rhs.clone())
     }
 }
 
-impl<F: Sub<F, Output = F> + PartialOrd + Copy> Sub<F> for Interval<F> {
+impl<F: Sub<F, Output = F> + PartialOrd + Clone> Sub<F> for Interval<F> {
     type Output = Self;
 
     fn sub(self, rhs: F) -> Self::Output {
-        self.applied_both(|x| x - rhs)
+        self.map(|x| x - rhs.clone())
     }
 }
 
-impl<F: Neg<Output = F> + PartialOrd + Copy> Neg for Interval<F> {
+impl<F: Neg<Output = F> + PartialOrd> Neg for Interval<F> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        self.applied_both(|x| -x)
+        self.map_reversed(|x| -x)
     }
 }
 
+///
+/// Interval arithmetic: the sum contains every `a + b` with `a` in the first interval and `b`
+/// in the second (see [`Interval::try_add`]).
+///
+/// __This is not a confidence interval on a sum.__ Adding two independent 95% confidence
+/// intervals yields an interval that is too wide (about 41% wider for intervals of similar
+/// widths, with an actual coverage of about 99.4%). Use [`crate::mean::sum_ci`] to combine
+/// the underlying statistics instead.
+///
+/// # Panics
+///
+/// * if the sum covers all values (one-sided intervals of opposite directions); use
+///   [`Interval::try_add`] when the intervals may be one-sided
+///
 impl<F: Num + PartialOrd + Copy> Add for Interval<F> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Interval::TwoSided(a, b), Interval::TwoSided(x, y)) => {
-                Interval::TwoSided(a + x, b + y)
-            }
-            (Interval::TwoSided(a, _) | Interval::UpperOneSided(a), Interval::UpperOneSided(x)) => {
-                Interval::UpperOneSided(a + x)
-            }
-            (Interval::TwoSided(_, b) | Interval::LowerOneSided(b), Interval::LowerOneSided(y)) => {
-                Interval::LowerOneSided(b + y)
-            }
-            (Interval::UpperOneSided(a), Interval::TwoSided(x, _)) => {
-                Interval::UpperOneSided(a + x)
-            }
-            (Interval::LowerOneSided(b), Interval::TwoSided(_, y)) => {
-                Interval::LowerOneSided(b + y)
-            }
-            (Interval::UpperOneSided(_), Interval::LowerOneSided(_))
-            | (Interval::LowerOneSided(_), Interval::UpperOneSided(_)) => {
-                panic!("Cannot add one-sided intervals with different directions (all values interval)")
-            }
-        }
+        self.try_add(rhs)
+            .unwrap_or_else(|e| panic!("cannot add intervals: {e}"))
     }
 }
 
+///
+/// Interval arithmetic: the difference contains every `a - b` with `a` in the first interval
+/// and `b` in the second (see [`Interval::try_sub`]).
+///
+/// __This is not a confidence interval on a difference.__ Subtracting two independent 95%
+/// confidence intervals yields an interval that is too wide (about 41% wider for intervals of
+/// similar widths, with an actual coverage of about 99.4%). Use [`crate::mean::diff_ci`] or
+/// [`crate::comparison::Unpaired`] to combine the underlying statistics instead.
+///
+/// # Panics
+///
+/// * if the difference covers all values (one-sided intervals of the same direction); use
+///   [`Interval::try_sub`] when the intervals may be one-sided
+///
 impl<F: Num + PartialOrd + Copy> Sub for Interval<F> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
+        self.try_sub(rhs)
+            .unwrap_or_else(|e| panic!("cannot subtract intervals: {e}"))
+    }
+}
+
+///
+/// Interval arithmetic: the product contains every `a * b` with `a` in the first interval and `b`
+/// in the second (see [`Interval::try_mul`]).
+///
+/// # Panics
+///
+/// * if the product covers all values (e.g., a one-sided interval times an interval containing
+///   both positive and negative values); use [`Interval::try_mul`] when the intervals may be one-sided
+///
+impl<F: Num + PartialOrd + Copy> Mul for Interval<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.try_mul(rhs)
+            .unwrap_or_else(|e| panic!("cannot multiply intervals: {e}"))
+    }
+}
+
+///
+/// Interval arithmetic: the quotient contains every `a / b` with `a` in the first interval and `b`
+/// in the second (see [`Interval::try_div`]).
+///
+/// # Panics
+///
+/// * if the divisor contains zero
+///
+impl<F: Num + PartialOrd + Copy> Div for Interval<F> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.try_div(rhs)
+            .unwrap_or_else(|e| panic!("cannot divide intervals: {e}"))
+    }
+}
+
+///
+/// A bound of an interval on the extended real line, used for interval arithmetic.
+///
+#[derive(Clone, Copy)]
+enum Extended<T> {
+    NegInf,
+    Finite(T),
+    PosInf,
+}
+
+impl<T: Num + PartialOrd + Copy> Extended<T> {
+    fn infinite(positive: bool) -> Self {
+        if positive {
+            Extended::PosInf
+        } else {
+            Extended::NegInf
+        }
+    }
+
+    ///
+    /// Sign of a non-zero bound (`None` for zero).
+    ///
+    fn is_positive(&self) -> Option<bool> {
+        match self {
+            Extended::NegInf => Some(false),
+            Extended::PosInf => Some(true),
+            Extended::Finite(x) if x.is_zero() => None,
+            Extended::Finite(x) => Some(*x > T::zero()),
+        }
+    }
+
+    ///
+    /// Product of two bounds, with \( 0 \times \pm\infty = 0 \) as is customary in interval arithmetic.
+    ///
+    fn mul(self, rhs: Self) -> Self {
         match (self, rhs) {
-            (Interval::TwoSided(a, b), Interval::TwoSided(x, y)) => {
-                Interval::TwoSided(a - y, b - x)
-            }
-            (Interval::TwoSided(_, b) | Interval::LowerOneSided(b), Interval::UpperOneSided(x)) => {
-                Interval::LowerOneSided(b - x)
-            }
-            (Interval::TwoSided(a, _) | Interval::UpperOneSided(a), Interval::LowerOneSided(y)) => {
-                Interval::UpperOneSided(a - y)
-            }
-            (Interval::UpperOneSided(a), Interval::TwoSided(_, y)) => {
-                Interval::UpperOneSided(a - y)
-            }
-            (Interval::LowerOneSided(b), Interval::TwoSided(x, _)) => {
-                Interval::LowerOneSided(b - x)
-            }
-            (Interval::UpperOneSided(_), Interval::UpperOneSided(_))
-            | (Interval::LowerOneSided(_), Interval::LowerOneSided(_)) => {
-                panic!(
-                    "Cannot subtract one-sided intervals of the same directions (empty interval)"
-                )
-            }
+            (Extended::Finite(a), Extended::Finite(b)) => Extended::Finite(a * b),
+            _ => match (self.is_positive(), rhs.is_positive()) {
+                (Some(a), Some(b)) => Self::infinite(a == b),
+                _ => Extended::Finite(T::zero()),
+            },
+        }
+    }
+
+    ///
+    /// Quotient of two bounds, where the divisor is non-zero, or `None` if both are infinite.
+    ///
+    fn div(self, rhs: Self) -> Option<Self> {
+        match (self, rhs) {
+            (Extended::Finite(a), Extended::Finite(b)) => Some(Extended::Finite(a / b)),
+            (Extended::Finite(_), _) => Some(Extended::Finite(T::zero())),
+            (_, Extended::Finite(_)) => match (self.is_positive(), rhs.is_positive()) {
+                (Some(a), Some(b)) => Some(Self::infinite(a == b)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn lt(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Extended::Finite(a), Extended::Finite(b)) => a < b,
+            (Extended::NegInf, Extended::NegInf) | (Extended::PosInf, _) => false,
+            (Extended::NegInf, _) | (_, Extended::PosInf) => true,
+            (_, Extended::NegInf) => false,
+        }
+    }
+}
+
+impl<T: Num + PartialOrd + Copy> Interval<T> {
+    fn extended_bounds(&self) -> (Extended<T>, Extended<T>) {
+        match *self {
+            Interval::TwoSided(low, high) => (Extended::Finite(low), Extended::Finite(high)),
+            Interval::UpperOneSided(low) => (Extended::Finite(low), Extended::PosInf),
+            Interval::LowerOneSided(high) => (Extended::NegInf, Extended::Finite(high)),
+        }
+    }
+
+    ///
+    /// Smallest interval containing the given (extended) values.
+    ///
+    fn hull_of_extended(
+        values: impl IntoIterator<Item = Extended<T>>,
+        operation: &str,
+    ) -> Result<Self, IntervalError> {
+        let mut values = values.into_iter();
+        let first = values.next().expect("at least one value");
+        let (low, high) = values.fold((first, first), |(low, high), x| {
+            (
+                if x.lt(&low) { x } else { low },
+                if high.lt(&x) { x } else { high },
+            )
+        });
+        match (low, high) {
+            (Extended::Finite(low), Extended::Finite(high)) => Interval::new(low, high),
+            (Extended::Finite(low), Extended::PosInf) => Ok(Interval::UpperOneSided(low)),
+            (Extended::NegInf, Extended::Finite(high)) => Ok(Interval::LowerOneSided(high)),
+            _ => Err(IntervalError::UnrepresentableResult(format!(
+                "the {operation} covers all values"
+            ))),
         }
     }
 }
@@ -778,6 +1634,97 @@ impl<T: PartialOrd> TryFrom<(Option<T>, Option<T>)> for Interval<T> {
     }
 }
 
+///
+/// Wire format of [`Interval`]: an object tagged by `kind` with the bounds of the interval as
+/// fields, e.g., `{"kind":"two_sided","low":1.0,"high":2.0}`, `{"kind":"upper_one_sided","low":1.0}`,
+/// or `{"kind":"lower_one_sided","high":2.0}`.
+///
+/// The names of the kinds and fields are part of the stable format and must not change.
+///
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)] // mirrors the variants of [`Interval`]
+enum IntervalWire<T> {
+    TwoSided { low: T, high: T },
+    UpperOneSided { low: T },
+    LowerOneSided { high: T },
+}
+
+///
+/// Legacy wire format of [`Interval`], e.g., `{"TwoSided":[1.0,2.0]}`.
+/// It is still accepted when deserializing but no longer produced.
+///
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Interval")]
+#[allow(clippy::enum_variant_names)] // mirrors the variants of [`Interval`]
+enum LegacyIntervalWire<T> {
+    TwoSided(T, T),
+    UpperOneSided(T),
+    LowerOneSided(T),
+}
+
+///
+/// Unvalidated mirror of [`Interval`] in either wire format, used to check the bounds when
+/// deserializing.
+///
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum IntervalRepr<T> {
+    Current(IntervalWire<T>),
+    Legacy(LegacyIntervalWire<T>),
+}
+
+#[cfg(feature = "serde")]
+impl<T: PartialOrd + serde::Serialize> serde::Serialize for Interval<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let wire = match self {
+            Interval::TwoSided(low, high) => IntervalWire::TwoSided { low, high },
+            Interval::UpperOneSided(low) => IntervalWire::UpperOneSided { low },
+            Interval::LowerOneSided(high) => IntervalWire::LowerOneSided { high },
+        };
+        wire.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: PartialOrd> TryFrom<IntervalRepr<T>> for Interval<T> {
+    type Error = String;
+
+    fn try_from(repr: IntervalRepr<T>) -> Result<Self, Self::Error> {
+        let (variant, low, high) = match repr {
+            IntervalRepr::Current(IntervalWire::TwoSided { low, high }) => {
+                ("two_sided", Some(low), Some(high))
+            }
+            IntervalRepr::Current(IntervalWire::UpperOneSided { low }) => {
+                ("upper_one_sided", Some(low), None)
+            }
+            IntervalRepr::Current(IntervalWire::LowerOneSided { high }) => {
+                ("lower_one_sided", None, Some(high))
+            }
+            IntervalRepr::Legacy(LegacyIntervalWire::TwoSided(low, high)) => {
+                ("TwoSided", Some(low), Some(high))
+            }
+            IntervalRepr::Legacy(LegacyIntervalWire::UpperOneSided(low)) => {
+                ("UpperOneSided", Some(low), None)
+            }
+            IntervalRepr::Legacy(LegacyIntervalWire::LowerOneSided(high)) => {
+                ("LowerOneSided", None, Some(high))
+            }
+        };
+        // only NaN is not comparable to itself
+        let is_nan = |bound: &Option<T>| bound.as_ref().is_some_and(|b| b.partial_cmp(b).is_none());
+        let result = if is_nan(&low) || is_nan(&high) {
+            Err(IntervalError::NaNBound)
+        } else {
+            Interval::try_from((low, high))
+        };
+        result.map_err(|err| format!("invalid `{variant}` interval: {err}"))
+    }
+}
+
 impl<T: PartialOrd + Clone> From<Interval<T>> for (Option<T>, Option<T>) {
     ///
     /// Convert an interval to a tuple of optional bounds.
@@ -899,15 +1846,342 @@ impl<T: PartialOrd + Sub<Output = T> + num_traits::Zero + Clone> Interval<T> {
     /// Compute the width of the interval.
     /// If the interval is one-sided, the function returns `None`.
     ///
+    /// See also [`Self::width_by`] for bound types without subtraction.
+    ///
     pub fn width(&self) -> Option<T> {
-        match self {
-            Interval::LowerOneSided(_) | Interval::UpperOneSided(_) => None,
-            Interval::TwoSided(low, high) => Some(high.clone() - low.clone()),
-        }
+        self.width_by(|low, high| high.clone() - low.clone())
     }
 }
 
-impl<T: PartialOrd + Clone> Clone for Interval<T> {
+impl<T: PartialOrd + num_traits::Zero> Interval<T> {
+    ///
+    /// Significance of the interval with respect to zero, typically for an interval on a difference
+    /// (see [`crate::comparison`]).
+    ///
+    /// * [`Significance::PositivelySignificant`] - the interval lies entirely above zero
+    /// * [`Significance::NegativelySignificant`] - the interval lies entirely below zero
+    /// * [`Significance::NotSignificant`] - the interval includes zero
+    ///
+    /// An upper one-sided interval \\( [low, +\infty) \\) can only be positively significant and,
+    /// conversely, a lower one-sided interval \\( (-\infty, high] \\) can only be negatively significant.
+    ///
+    /// # Notes
+    ///
+    /// The bounds are included in the interval, so an interval that touches zero (e.g., \\( [0, 2] \\))
+    /// includes zero and is therefore _not_ significant.
+    /// Likewise, an interval with a bound that cannot be compared to zero (e.g., NaN) is not significant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// assert_eq!(Interval::new(1., 2.)?.significance(), Significance::PositivelySignificant);
+    /// assert_eq!(Interval::new(-2., -1.)?.significance(), Significance::NegativelySignificant);
+    /// assert_eq!(Interval::new(-1., 2.)?.significance(), Significance::NotSignificant);
+    /// assert_eq!(Interval::new(0., 2.)?.significance(), Significance::NotSignificant);
+    /// assert_eq!(Interval::new_lower(1.).significance(), Significance::NotSignificant);
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn significance(&self) -> Significance {
+        let zero = T::zero();
+        if matches!(self.left(), Some(low) if *low > zero) {
+            Significance::PositivelySignificant
+        } else if matches!(self.right(), Some(high) if *high < zero) {
+            Significance::NegativelySignificant
+        } else {
+            Significance::NotSignificant
+        }
+    }
+}
+
+impl<T: Num + PartialOrd + Copy> Interval<T> {
+    ///
+    /// Create a new two-sided interval from its center and margin (i.e., half-width),
+    /// that is \\( [center - margin, center + margin] \\).
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::InvalidMargin`] - if the margin is negative or incomparable (e.g., NaN)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::from_margin(10., 2.5)?;
+    /// assert_eq!(interval, Interval::new(7.5, 12.5)?);
+    /// assert_eq!(interval.center(), Some(10.));
+    /// assert_eq!(interval.half_width(), Some(2.5));
+    ///
+    /// assert!(Interval::from_margin(10., -1.).is_err());
+    /// assert!(Interval::from_margin(10., f64::NAN).is_err());
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn from_margin(center: T, margin: T) -> Result<Self, IntervalError> {
+        let margin = Self::valid_margin(margin)?;
+        Interval::new(center - margin, center + margin)
+    }
+
+    ///
+    /// Create a new upper one-sided interval from an estimate and its margin,
+    /// that is \\( [center - margin, +\infty) \\).
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::InvalidMargin`] - if the margin is negative or incomparable (e.g., NaN)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::upper_from_margin(10, 3)?;
+    /// assert_eq!(interval, Interval::new_upper(7));
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn upper_from_margin(center: T, margin: T) -> Result<Self, IntervalError> {
+        let margin = Self::valid_margin(margin)?;
+        Ok(Interval::new_upper(center - margin))
+    }
+
+    ///
+    /// Create a new lower one-sided interval from an estimate and its margin,
+    /// that is \\( (-\infty, center + margin] \\).
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::InvalidMargin`] - if the margin is negative or incomparable (e.g., NaN)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::lower_from_margin(10, 3)?;
+    /// assert_eq!(interval, Interval::new_lower(13));
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn lower_from_margin(center: T, margin: T) -> Result<Self, IntervalError> {
+        let margin = Self::valid_margin(margin)?;
+        Ok(Interval::new_lower(center + margin))
+    }
+
+    ///
+    /// Interval arithmetic: the smallest interval containing every `a + b` with `a` in this
+    /// interval and `b` in `rhs`. This is the same as the `+` operator, without panicking.
+    ///
+    /// Adding a degenerate interval \\( [x, x] \\) is the same as adding the scalar `x`.
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::UnrepresentableResult`] - if the intervals are one-sided in opposite
+    ///   directions, in which case the sum covers all values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use stats_ci::error::IntervalError;
+    /// let a = Interval::new(5., 9.)?;
+    /// assert_eq!(a.try_add(Interval::new(2., 2.)?)?, a + 2.);
+    /// assert_eq!(a.try_add(Interval::new_upper(2.))?, Interval::new_upper(7.));
+    /// assert!(matches!(
+    ///     Interval::new_lower(1.).try_add(Interval::new_upper(2.)),
+    ///     Err(IntervalError::UnrepresentableResult(_))
+    /// ));
+    /// # Ok::<(),IntervalError>(())
+    /// ```
+    ///
+    pub fn try_add(self, rhs: Self) -> Result<Self, IntervalError> {
+        match (self, rhs) {
+            (Interval::TwoSided(a, b), Interval::TwoSided(x, y)) => {
+                Ok(Interval::TwoSided(a + x, b + y))
+            }
+            (Interval::TwoSided(a, _) | Interval::UpperOneSided(a), Interval::UpperOneSided(x))
+            | (Interval::UpperOneSided(a), Interval::TwoSided(x, _)) => {
+                Ok(Interval::UpperOneSided(a + x))
+            }
+            (Interval::TwoSided(_, b) | Interval::LowerOneSided(b), Interval::LowerOneSided(y))
+            | (Interval::LowerOneSided(b), Interval::TwoSided(_, y)) => {
+                Ok(Interval::LowerOneSided(b + y))
+            }
+            (Interval::UpperOneSided(_), Interval::LowerOneSided(_))
+            | (Interval::LowerOneSided(_), Interval::UpperOneSided(_)) => {
+                Err(IntervalError::UnrepresentableResult(
+                    "the sum of one-sided intervals of opposite directions covers all values"
+                        .to_string(),
+                ))
+            }
+        }
+    }
+
+    ///
+    /// Interval arithmetic: the smallest interval containing every `a - b` with `a` in this
+    /// interval and `b` in `rhs`. This is the same as the `-` operator, without panicking.
+    ///
+    /// Subtracting a degenerate interval \\( [x, x] \\) is the same as subtracting the scalar `x`.
+    /// Subtracting an upper (resp. lower) one-sided interval yields a lower (resp. upper) one-sided
+    /// interval, since the values of `rhs` are unbounded above (resp. below).
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::UnrepresentableResult`] - if the intervals are one-sided in the same
+    ///   direction, in which case the difference covers all values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use stats_ci::error::IntervalError;
+    /// let a = Interval::new(5., 9.)?;
+    /// assert_eq!(a.try_sub(Interval::new(2., 2.)?)?, Interval::new(3., 7.)?);
+    /// assert_eq!(a.try_sub(Interval::new_upper(2.))?, Interval::new_lower(7.));
+    /// assert!(matches!(
+    ///     Interval::new_upper(1.).try_sub(Interval::new_upper(2.)),
+    ///     Err(IntervalError::UnrepresentableResult(_))
+    /// ));
+    /// # Ok::<(),IntervalError>(())
+    /// ```
+    ///
+    pub fn try_sub(self, rhs: Self) -> Result<Self, IntervalError> {
+        match (self, rhs) {
+            (Interval::TwoSided(a, b), Interval::TwoSided(x, y)) => {
+                Ok(Interval::TwoSided(a - y, b - x))
+            }
+            (Interval::TwoSided(_, b) | Interval::LowerOneSided(b), Interval::UpperOneSided(x))
+            | (Interval::LowerOneSided(b), Interval::TwoSided(x, _)) => {
+                Ok(Interval::LowerOneSided(b - x))
+            }
+            (Interval::TwoSided(a, _) | Interval::UpperOneSided(a), Interval::LowerOneSided(y))
+            | (Interval::UpperOneSided(a), Interval::TwoSided(_, y)) => {
+                Ok(Interval::UpperOneSided(a - y))
+            }
+            (Interval::UpperOneSided(_), Interval::UpperOneSided(_))
+            | (Interval::LowerOneSided(_), Interval::LowerOneSided(_)) => {
+                Err(IntervalError::UnrepresentableResult(
+                    "the difference of one-sided intervals of the same direction covers all values"
+                        .to_string(),
+                ))
+            }
+        }
+    }
+
+    ///
+    /// Interval arithmetic: the smallest interval containing every `a * b` with `a` in this
+    /// interval and `b` in `rhs`. This is the same as the `*` operator, without panicking.
+    ///
+    /// The bounds are the smallest and largest products of the bounds of the two intervals,
+    /// with the usual convention \\( 0 \times \pm\infty = 0 \\) for one-sided intervals
+    /// (e.g., \\( [0, 0] \times [1, +\infty) = [0, 0] \\)).
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::UnrepresentableResult`] - if the product covers all values, e.g., for a
+    ///   one-sided interval times an interval containing both positive and negative values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use stats_ci::error::IntervalError;
+    /// let a = Interval::new(-1., 2.)?;
+    /// assert_eq!(a.try_mul(Interval::new(3., 4.)?)?, Interval::new(-4., 8.)?);
+    /// assert_eq!(a.try_mul(Interval::new(-3., -3.)?)?, a * -3.);
+    /// assert_eq!(
+    ///     Interval::new(1., 2.)?.try_mul(Interval::new_lower(-1.))?,
+    ///     Interval::new_lower(-1.)
+    /// );
+    /// assert!(matches!(
+    ///     a.try_mul(Interval::new_upper(1.)),
+    ///     Err(IntervalError::UnrepresentableResult(_))
+    /// ));
+    /// # Ok::<(),IntervalError>(())
+    /// ```
+    ///
+    pub fn try_mul(self, rhs: Self) -> Result<Self, IntervalError> {
+        let (a, b) = self.extended_bounds();
+        let (x, y) = rhs.extended_bounds();
+        Self::hull_of_extended([a.mul(x), a.mul(y), b.mul(x), b.mul(y)], "product")
+    }
+
+    ///
+    /// Interval arithmetic: the smallest interval containing every `a / b` with `a` in this
+    /// interval and `b` in `rhs`. This is the same as the `/` operator, without panicking.
+    ///
+    /// The bounds are the smallest and largest quotients of the bounds of the two intervals.
+    /// For integers, the quotients are truncated as with scalar division.
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::UnrepresentableResult`] - if `rhs` contains zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use stats_ci::error::IntervalError;
+    /// let a = Interval::new(-1., 2.)?;
+    /// assert_eq!(a.try_div(Interval::new(2., 4.)?)?, Interval::new(-0.5, 1.)?);
+    /// assert_eq!(a.try_div(Interval::new(-2., -2.)?)?, a / -2.);
+    /// assert_eq!(
+    ///     Interval::new(1., 2.)?.try_div(Interval::new_upper(4.))?,
+    ///     Interval::new(0., 0.5)?
+    /// );
+    /// assert!(matches!(
+    ///     a.try_div(Interval::new(-1., 1.)?),
+    ///     Err(IntervalError::UnrepresentableResult(_))
+    /// ));
+    /// # Ok::<(),IntervalError>(())
+    /// ```
+    ///
+    pub fn try_div(self, rhs: Self) -> Result<Self, IntervalError> {
+        if rhs.contains(&T::zero()) {
+            return Err(IntervalError::UnrepresentableResult(
+                "the divisor contains zero".to_string(),
+            ));
+        }
+        let (a, b) = self.extended_bounds();
+        let (x, y) = rhs.extended_bounds();
+        Self::hull_of_extended(
+            [a.div(x), a.div(y), b.div(x), b.div(y)]
+                .into_iter()
+                .flatten(),
+            "quotient",
+        )
+    }
+
+    ///
+    /// Compute the center of the interval, i.e. the midpoint between its bounds.
+    /// If the interval is one-sided, the function returns `None`.
+    ///
+    pub fn center(&self) -> Option<T> {
+        match self {
+            Interval::LowerOneSided(_) | Interval::UpperOneSided(_) => None,
+            Interval::TwoSided(low, high) => Some((*low + *high) / (T::one() + T::one())),
+        }
+    }
+
+    ///
+    /// Compute the half-width of the interval, i.e. its margin around the center.
+    /// If the interval is one-sided, the function returns `None`.
+    ///
+    pub fn half_width(&self) -> Option<T> {
+        match self {
+            Interval::LowerOneSided(_) | Interval::UpperOneSided(_) => None,
+            Interval::TwoSided(low, high) => Some((*high - *low) / (T::one() + T::one())),
+        }
+    }
+
+    fn valid_margin(margin: T) -> Result<T, IntervalError> {
+        match margin.partial_cmp(&T::zero()) {
+            Some(core::cmp::Ordering::Greater | core::cmp::Ordering::Equal) => Ok(margin),
+            _ => Err(IntervalError::InvalidMargin),
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone> Clone for Interval<T> {
     fn clone(&self) -> Self {
         match self {
             Interval::TwoSided(low, high) => Interval::TwoSided(low.clone(), high.clone()),
@@ -930,6 +2204,230 @@ impl<T: PartialOrd + Display> Display for Interval<T> {
     }
 }
 
+impl<T: PartialOrd + core::fmt::LowerExp> core::fmt::LowerExp for Interval<T> {
+    ///
+    /// Format the interval with its bounds in scientific notation, in the syntax of its
+    /// [`Display`] implementation. The precision, if any, applies to each bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::new(1.2e-20, 1.7e23)?;
+    /// assert_eq!(format!("{interval:e}"), "[1.2e-20, 1.7e23]");
+    /// assert_eq!(format!("{:.2e}", Interval::new_upper(-1234.5)), "[-1.23e3,->)");
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Interval::TwoSided(low, high) => {
+                f.write_str("[")?;
+                low.fmt(f)?;
+                f.write_str(", ")?;
+                high.fmt(f)?;
+                f.write_str("]")
+            }
+            Interval::UpperOneSided(low) => {
+                f.write_str("[")?;
+                low.fmt(f)?;
+                f.write_str(",->)")
+            }
+            Interval::LowerOneSided(high) => {
+                f.write_str("(<-,")?;
+                high.fmt(f)?;
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+impl<T: num_traits::Float> Interval<T> {
+    ///
+    /// Display the interval with a fixed number of significant figures, to keep the output short
+    /// whatever the magnitude of its bounds (e.g., in log lines).
+    ///
+    /// Bounds whose decimal exponent lies in the range [-3, 6) (i.e., with a magnitude from
+    /// 0.001 to 999999) are written in fixed notation, and other bounds in scientific
+    /// notation. The range can be changed with [`CompactDisplay::fixed_range`]. The output uses
+    /// the syntax of the [`Display`] implementation and can be parsed back.
+    ///
+    /// # Arguments
+    ///
+    /// * `sig_figs` - the number of significant figures of the bounds (0 is the same as 1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let interval = Interval::new(1.2e-20, 1.7e23)?;
+    /// assert_eq!(interval.display_compact(3).to_string(), "[1.20e-20, 1.70e23]");
+    /// let interval = Interval::new(0.123456, 98.7654)?;
+    /// assert_eq!(interval.display_compact(3).to_string(), "[0.123, 98.8]");
+    /// assert_eq!(
+    ///     interval.display_compact(3).fixed_range(0, 6).to_string(),
+    ///     "[1.23e-1, 98.8]"
+    /// );
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    pub fn display_compact(&self, sig_figs: usize) -> CompactDisplay<'_, T> {
+        CompactDisplay {
+            interval: self,
+            sig_figs: sig_figs.max(1),
+            min_exponent: -3,
+            max_exponent: 6,
+        }
+    }
+}
+
+///
+/// Compact display of an interval with a fixed number of significant figures
+/// (see [`Interval::display_compact`]).
+///
+#[derive(Debug, Clone, Copy)]
+pub struct CompactDisplay<'a, T: PartialOrd> {
+    interval: &'a Interval<T>,
+    sig_figs: usize,
+    min_exponent: i32,
+    max_exponent: i32,
+}
+
+impl<T: PartialOrd> CompactDisplay<'_, T> {
+    ///
+    /// Set the range of decimal exponents for which the bounds are written in fixed notation:
+    /// a bound \\( x \\) is written in fixed notation if
+    /// \\( 10^{min} \leq |x| < 10^{max} \\) (after rounding), and in scientific notation otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_exponent` - the smallest exponent written in fixed notation
+    /// * `max_exponent` - the exponent from which the bounds are written in scientific notation again
+    ///
+    pub fn fixed_range(self, min_exponent: i32, max_exponent: i32) -> Self {
+        Self {
+            min_exponent,
+            max_exponent,
+            ..self
+        }
+    }
+}
+
+impl<T: num_traits::Float> CompactDisplay<'_, T> {
+    fn write_bound(&self, f: &mut core::fmt::Formatter<'_>, bound: T) -> core::fmt::Result {
+        let x = bound.to_f64().unwrap_or(f64::NAN);
+        if !x.is_finite() {
+            return write!(f, "{x}");
+        }
+        let precision = self.sig_figs - 1;
+        let scientific = format!("{x:.precision$e}");
+        // the exponent is that of the rounded value (e.g., 9.99 rounds to 1.0e1)
+        let exponent: i32 = scientific
+            .split_once('e')
+            .and_then(|(_, exponent)| exponent.parse().ok())
+            .unwrap_or(0);
+        if self.min_exponent <= exponent && exponent < self.max_exponent {
+            let decimals = (precision as i32 - exponent).max(0) as usize;
+            let rounded: f64 = scientific.parse().unwrap_or(x);
+            write!(f, "{rounded:.decimals$}")
+        } else {
+            f.write_str(&scientific)
+        }
+    }
+}
+
+impl<T: num_traits::Float> Display for CompactDisplay<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self.interval {
+            Interval::TwoSided(low, high) => {
+                f.write_str("[")?;
+                self.write_bound(f, low)?;
+                f.write_str(", ")?;
+                self.write_bound(f, high)?;
+                f.write_str("]")
+            }
+            Interval::UpperOneSided(low) => {
+                f.write_str("[")?;
+                self.write_bound(f, low)?;
+                f.write_str(",->)")
+            }
+            Interval::LowerOneSided(high) => {
+                f.write_str("(<-,")?;
+                self.write_bound(f, high)?;
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+impl<T: PartialOrd + core::str::FromStr> core::str::FromStr for Interval<T> {
+    type Err = IntervalError;
+
+    ///
+    /// Parse an interval written in the syntax of its [`Display`] implementation:
+    /// * `[low, high]` for two-sided intervals;
+    /// * `[low,->)` for upper one-sided intervals;
+    /// * `(<-,high]` for lower one-sided intervals.
+    ///
+    /// Whitespace around the brackets, the bounds, and the separator is ignored.
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::ParseError`] - if the brackets or the separator are missing, or if a bound cannot be parsed
+    /// * [`IntervalError::NaNBound`] - if a bound is NaN
+    /// * [`IntervalError::InvalidBounds`] - if the lower bound of a two-sided interval is greater than its upper bound
+    /// * [`IntervalError::Unbounded`] - for `(<-,->)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// assert_eq!("[0.95, 1.05]".parse::<Interval<f64>>()?, Interval::new(0.95, 1.05)?);
+    /// assert_eq!(" ( <- , 250 ] ".parse::<Interval<f64>>()?, Interval::new_lower(250.));
+    /// assert_eq!("[3,->)".parse::<Interval<i32>>()?, Interval::new_upper(3));
+    /// let interval = Interval::new(-1.5, 2.)?;
+    /// assert_eq!(interval.to_string().parse::<Interval<f64>>()?, interval);
+    /// # Ok::<(),error::IntervalError>(())
+    /// ```
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let syntax_error = |reason: &str| {
+            IntervalError::ParseError(format!(
+                "{reason} in {s:?} (expected [low, high], [low,->), or (<-,high])"
+            ))
+        };
+        let trimmed = s.trim();
+        let mut chars = trimmed.chars();
+        let (open, close) = match (chars.next(), chars.next_back()) {
+            (Some(open @ ('[' | '(')), Some(close @ (']' | ')'))) => (open, close),
+            (Some('[' | '('), _) => return Err(syntax_error("missing closing bracket")),
+            _ => return Err(syntax_error("missing opening bracket")),
+        };
+        let (left, right) = chars
+            .as_str()
+            .split_once(',')
+            .ok_or_else(|| syntax_error("missing separator"))?;
+        let parse_bound = |bound: &str| {
+            let value = bound
+                .parse::<T>()
+                .map_err(|_| syntax_error(&format!("invalid bound {bound:?}")))?;
+            // only NaN is not comparable to itself
+            match value.partial_cmp(&value) {
+                Some(_) => Ok(value),
+                None => Err(IntervalError::NaNBound),
+            }
+        };
+
+        match (open, left.trim(), right.trim(), close) {
+            ('(', "<-", "->", ')') => Err(IntervalError::Unbounded),
+            ('[', low, "->", ')') => Ok(Interval::new_upper(parse_bound(low)?)),
+            ('(', "<-", high, ']') => Ok(Interval::new_lower(parse_bound(high)?)),
+            ('[', low, high, ']') => Interval::new(parse_bound(low)?, parse_bound(high)?),
+            _ => Err(syntax_error("mismatched brackets")),
+        }
+    }
+}
+
 use core::hash::Hash;
 impl<T: PartialOrd + Hash> Hash for Interval<T> {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
@@ -1001,26 +2499,554 @@ impl<T: PartialOrd> PartialOrd for Interval<T> {
     }
 }
 
-pub use crate::error::IntervalError;
-
-/*
- *      #   # #   # ### #####   ##### #####  #### #####  ####
- *      #   # ##  #  #    #       #   #     #       #   #
- *      #   # # # #  #    #       #   ###    ###    #    ###
- *      #   # #  ##  #    #       #   #         #   #       #
- *       ###  #   # ###   #       #   ##### ####    #   ####
- */
+impl<T: PartialOrd> Interval<T> {
+    ///
+    /// Compare two intervals by their bounds, as a total order suitable for sorting.
+    ///
+    /// Unlike [`PartialOrd`] for intervals, which only orders intervals that do not overlap, this
+    /// compares the lower bounds first and then the upper bounds (lexicographic order on `(low, high)`).
+    /// One-sided intervals are ordered as if their missing bound were infinite: the lower bound of
+    /// a lower one-sided interval is less than any value, and the upper bound of an upper one-sided
+    /// interval is greater than any value. Bounds that are not comparable with themselves (i.e., NaN)
+    /// are greater than any other value and equal to each other.
+    ///
+    /// The order is total for floating point numbers and integers. For other types, two values that
+    /// are not comparable (and are not NaN-like) are considered equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cmp::Ordering;
+    /// # use stats_ci::Interval;
+    /// let a = Interval::new(0., 10.)?;
+    /// let b = Interval::new(5., 6.)?;
+    /// assert_eq!(a.partial_cmp(&b), None); // overlapping intervals
+    /// assert_eq!(a.cmp_by_bounds(&b), Ordering::Less);
+    /// assert_eq!(a.cmp_by_bounds(&Interval::new(0., 20.)?), Ordering::Less);
+    /// assert_eq!(a.cmp_by_bounds(&Interval::new_lower(-5.)), Ordering::Greater);
+    /// assert_eq!(a.cmp_by_bounds(&Interval::new_upper(0.)), Ordering::Less);
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn cmp_by_bounds(&self, other: &Self) -> core::cmp::Ordering {
+        use core::cmp::Ordering::*;
+        // missing left bounds are -∞ and missing right bounds are +∞
+        let low = match (self.left(), other.left()) {
+            (None, None) => Equal,
+            (None, Some(_)) => Less,
+            (Some(_), None) => Greater,
+            (Some(x), Some(y)) => total_cmp(x, y),
+        };
+        low.then_with(|| match (self.right(), other.right()) {
+            (None, None) => Equal,
+            (None, Some(_)) => Greater,
+            (Some(_), None) => Less,
+            (Some(x), Some(y)) => total_cmp(x, y),
+        })
+    }
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+///
+/// Total order on partially ordered values where values not comparable with themselves (NaN) come last.
+///
+fn total_cmp<T: PartialOrd>(x: &T, y: &T) -> core::cmp::Ordering {
+    use core::cmp::Ordering::*;
+    x.partial_cmp(y).unwrap_or_else(|| {
+        let x_nan = x.partial_cmp(x).is_none();
+        let y_nan = y.partial_cmp(y).is_none();
+        match (x_nan, y_nan) {
+            (true, false) => Greater,
+            (false, true) => Less,
+            _ => Equal,
+        }
+    })
+}
 
-    #[test]
-    fn test_interval_new() -> Result<(), IntervalError> {
-        let interval = Interval::new(0., 1.)?;
-        assert_eq!(interval.low(), Some(0.));
-        assert_eq!(interval.high(), Some(1.));
-        assert_eq!(interval.low_f(), 0.);
+///
+/// Adapter ordering intervals by their bounds (see [`Interval::cmp_by_bounds`]), so that they
+/// can be used in sorted collections such as [`BTreeSet`](std::collections::BTreeSet).
+///
+/// The ordering and equality of the adapter are both defined by [`Interval::cmp_by_bounds`];
+/// the semantics of [`Interval`] itself are left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use std::collections::BTreeSet;
+/// # use stats_ci::*;
+/// let intervals = BTreeSet::from([
+///     ByBounds(Interval::new(5., 8.)?),
+///     ByBounds(Interval::new(0., 10.)?),
+///     ByBounds(Interval::new_lower(3.)),
+///     ByBounds(Interval::new(0., 2.)?),
+/// ]);
+/// let sorted: Vec<_> = intervals.into_iter().map(|ByBounds(interval)| interval).collect();
+/// assert_eq!(
+///     sorted,
+///     [
+///         Interval::new_lower(3.),
+///         Interval::new(0., 2.)?,
+///         Interval::new(0., 10.)?,
+///         Interval::new(5., 8.)?
+///     ]
+/// );
+/// # Ok::<(),stats_ci::error::IntervalError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy)]
+pub struct ByBounds<T: PartialOrd>(pub Interval<T>);
+
+impl<T: PartialOrd> PartialEq for ByBounds<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == core::cmp::Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd> Eq for ByBounds<T> {}
+
+impl<T: PartialOrd> PartialOrd for ByBounds<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> Ord for ByBounds<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.cmp_by_bounds(&other.0)
+    }
+}
+
+impl<T: PartialOrd> From<Interval<T>> for ByBounds<T> {
+    fn from(interval: Interval<T>) -> Self {
+        ByBounds(interval)
+    }
+}
+
+///
+/// Outcome of a significance test based on a confidence interval, as returned by [`Interval::significance`].
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let stats = comparison::Unpaired::from_iter(&[1., 2., 3., 4.], &[5., 6., 7., 8.])?;
+/// let significance = stats.significance(Confidence::new_two_sided(0.95))?;
+/// assert_eq!(significance, Significance::NegativelySignificant);
+/// assert!(significance.is_significant());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Significance {
+    ///
+    /// The interval lies entirely above zero.
+    ///
+    PositivelySignificant,
+
+    ///
+    /// The interval lies entirely below zero.
+    ///
+    NegativelySignificant,
+
+    ///
+    /// The interval includes zero.
+    ///
+    NotSignificant,
+}
+
+impl Significance {
+    ///
+    /// Whether the outcome is significant (positively or negatively).
+    ///
+    pub fn is_significant(&self) -> bool {
+        !matches!(self, Significance::NotSignificant)
+    }
+}
+
+///
+/// Set of disjoint intervals over a partially ordered type, e.g., to represent the result of
+/// the difference of two intervals or a union of plausible ranges.
+///
+/// The members of the set are kept ordered and do not intersect: overlapping intervals, as well
+/// as intervals that only share a bound (since bounds are inclusive), are merged upon construction.
+/// The empty set has no members. A set cannot cover all values, since neither can an [`Interval`].
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let set = IntervalSet::from_intervals(vec![
+///     Interval::new_upper(5),
+///     Interval::new(0, 1)?,
+///     Interval::new(1, 2)?,
+/// ])?;
+/// assert_eq!(set.len(), 2);
+/// assert_eq!(set.to_string(), "[0, 2] ∪ [5,->)");
+/// assert!(set.contains(&1));
+/// assert!(!set.contains(&3));
+/// let within = set.complement_within(&Interval::new(0, 10)?);
+/// assert_eq!(within.iter().collect::<Vec<_>>(), [&Interval::new(2, 5)?]);
+/// # Ok::<(),stats_ci::error::IntervalError>(())
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntervalSet<T: PartialOrd> {
+    members: Vec<Interval<T>>,
+}
+
+impl<T: PartialOrd> IntervalSet<T> {
+    ///
+    /// Create an empty set.
+    ///
+    pub fn new() -> Self {
+        IntervalSet {
+            members: Vec::new(),
+        }
+    }
+
+    ///
+    /// Create a set from a collection of intervals, in any order.
+    /// Intervals that intersect (including those that only share a bound) are merged.
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::Unbounded`] - if the union of the intervals covers all values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let set = IntervalSet::from_intervals(vec![
+    ///     Interval::new(4., 6.)?,
+    ///     Interval::new(0., 2.)?,
+    ///     Interval::new(1., 3.)?,
+    /// ])?;
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), [&Interval::new(0., 3.)?, &Interval::new(4., 6.)?]);
+    /// let all = IntervalSet::from_intervals(vec![Interval::new_lower(1.), Interval::new_upper(0.)]);
+    /// assert_eq!(all, Err(error::IntervalError::Unbounded));
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn from_intervals(mut intervals: Vec<Interval<T>>) -> Result<Self, IntervalError> {
+        intervals.sort_by(|a, b| a.cmp_by_bounds(b));
+        let mut members: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match members.pop() {
+                Some(last) if last.intersects(&interval) => members.push(hull(last, interval)?),
+                Some(last) => members.extend([last, interval]),
+                None => members.push(interval),
+            }
+        }
+        Ok(IntervalSet { members })
+    }
+
+    ///
+    /// Test whether the set has no members.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    ///
+    /// Number of (disjoint) intervals in the set.
+    ///
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    ///
+    /// Iterate over the intervals of the set, in increasing order.
+    ///
+    pub fn iter(&self) -> core::slice::Iter<'_, Interval<T>> {
+        self.members.iter()
+    }
+
+    ///
+    /// The intervals of the set as a slice, in increasing order.
+    ///
+    pub fn as_slice(&self) -> &[Interval<T>] {
+        &self.members
+    }
+
+    ///
+    /// Test whether a value is contained in one of the intervals of the set.
+    ///
+    pub fn contains(&self, x: &T) -> bool {
+        self.members.iter().any(|interval| interval.contains(x))
+    }
+}
+
+impl<T: PartialOrd + Clone> IntervalSet<T> {
+    ///
+    /// Intersection of the set with an interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let set = Interval::new(-10, 10)?.difference(&Interval::new(-5, 5)?);
+    /// let intersection = set.intersect(&Interval::new_upper(0));
+    /// assert_eq!(intersection.as_slice(), [Interval::new(5, 10)?]);
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn intersect(&self, interval: &Interval<T>) -> Self {
+        // subsets of disjoint members are still ordered and disjoint
+        IntervalSet {
+            members: self
+                .members
+                .iter()
+                .filter_map(|member| member.intersection(interval))
+                .collect(),
+        }
+    }
+
+    ///
+    /// Union of the set with another set.
+    ///
+    /// # Errors
+    ///
+    /// * [`IntervalError::Unbounded`] - if the union covers all values
+    ///
+    pub fn union(&self, other: &Self) -> Result<Self, IntervalError> {
+        Self::from_intervals(self.members.iter().chain(&other.members).cloned().collect())
+    }
+
+    ///
+    /// Complement of the set within a domain, i.e., the values of the domain that are not in the set.
+    ///
+    /// As with [`Interval::difference`], the result is the closure of the complement: the bounds
+    /// of the members of the set are kept as bounds of the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let set = IntervalSet::from_intervals(vec![Interval::new_lower(0.), Interval::new(2., 3.)?])?;
+    /// let complement = set.complement_within(&Interval::new(-1., 5.)?);
+    /// assert_eq!(complement.to_string(), "[0, 2] ∪ [3, 5]");
+    /// # Ok::<(),stats_ci::error::IntervalError>(())
+    /// ```
+    ///
+    pub fn complement_within(&self, domain: &Interval<T>) -> Self {
+        let mut pieces = vec![domain.clone()];
+        for member in &self.members {
+            pieces = pieces
+                .iter()
+                .flat_map(|piece| piece.difference(member))
+                .collect();
+        }
+        // pieces are included in the domain, hence bounded on at least one side
+        Self::from_intervals(pieces).expect("subsets of an interval are bounded")
+    }
+}
+
+///
+/// Smallest interval including two intersecting intervals, where `first` is not after `second` by their bounds.
+///
+fn hull<T: PartialOrd>(
+    first: Interval<T>,
+    second: Interval<T>,
+) -> Result<Interval<T>, IntervalError> {
+    let (low, first_high) = into_bounds(first);
+    let (_, second_high) = into_bounds(second);
+    let high = match (first_high, second_high) {
+        (Some(x), Some(y)) => Some(if x >= y { x } else { y }),
+        _ => None,
+    };
+    match (low, high) {
+        (None, None) => Err(IntervalError::Unbounded),
+        bounds => Interval::try_from(bounds),
+    }
+}
+
+fn into_bounds<T: PartialOrd>(interval: Interval<T>) -> (Option<T>, Option<T>) {
+    match interval {
+        Interval::TwoSided(low, high) => (Some(low), Some(high)),
+        Interval::UpperOneSided(low) => (Some(low), None),
+        Interval::LowerOneSided(high) => (None, Some(high)),
+    }
+}
+
+impl<T: PartialOrd> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        IntervalSet {
+            members: vec![interval],
+        }
+    }
+}
+
+impl<T: PartialOrd> IntoIterator for IntervalSet<T> {
+    type Item = Interval<T>;
+    type IntoIter = std::vec::IntoIter<Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members.into_iter()
+    }
+}
+
+impl<'a, T: PartialOrd> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = core::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members.iter()
+    }
+}
+
+impl<T: PartialOrd + Display> Display for IntervalSet<T> {
+    ///
+    /// Format the set as the union of its members (e.g., `[0, 2] ∪ [5,->)`), or `∅` if the set is empty.
+    ///
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.members.is_empty() {
+            return write!(f, "∅");
+        }
+        for (i, interval) in self.members.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ∪ ")?;
+            }
+            write!(f, "{}", interval)?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// Relative change of an interval with respect to a reference interval, as returned by
+/// [`Interval::try_relative_to`].
+///
+/// The wrapped interval holds ratios minus one (e.g., `-0.1` means 10% smaller than the reference).
+/// It is displayed as signed percentages (e.g., `-3.2% … +1.4%`), with one decimal by default;
+/// the precision of the formatter is used if specified (e.g., `{:.2}`).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let baseline = Interval::new(10., 11.)?;
+/// let relative = Interval::new(10.5, 11.)?.try_relative_to(&baseline)?;
+/// assert_eq!(relative.to_string(), "-4.5% … +10.0%");
+/// assert_eq!(format!("{:.2}", relative), "-4.55% … +10.00%");
+/// assert_eq!(relative.is_improvement(true), None); // not significant
+/// assert_eq!(relative.as_percent(), relative.interval().as_percent());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelativeInterval<T: num_traits::Float>(Interval<T>);
+
+impl<T: num_traits::Float> RelativeInterval<T> {
+    ///
+    /// The interval of the relative change (ratios minus one).
+    ///
+    pub fn interval(&self) -> Interval<T> {
+        self.0
+    }
+
+    ///
+    /// The interval of the relative change in percent.
+    ///
+    pub fn as_percent(&self) -> Interval<T> {
+        self.0.as_percent()
+    }
+
+    ///
+    /// Whether the change is significant in the desired direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `smaller_is_better` - whether a decrease is an improvement (e.g., for running times)
+    ///
+    /// # Returns
+    ///
+    /// * `Some(true)` - if the change is significant in the desired direction (an improvement)
+    /// * `Some(false)` - if the change is significant in the opposite direction (a regression)
+    /// * `None` - if the change is not significant, i.e., the interval includes zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let reference = Interval::new(10., 11.)?;
+    /// let faster = Interval::new(8., 9.)?.try_relative_to(&reference)?;
+    /// assert_eq!(faster.is_improvement(true), Some(true));
+    /// assert_eq!(faster.is_improvement(false), Some(false));
+    /// let similar = Interval::new(9., 12.)?.try_relative_to(&reference)?;
+    /// assert_eq!(similar.is_improvement(true), None);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn is_improvement(&self, smaller_is_better: bool) -> Option<bool> {
+        match self.0.significance() {
+            Significance::PositivelySignificant => Some(!smaller_is_better),
+            Significance::NegativelySignificant => Some(smaller_is_better),
+            Significance::NotSignificant => None,
+        }
+    }
+}
+
+impl<T: num_traits::Float> From<RelativeInterval<T>> for Interval<T> {
+    fn from(relative: RelativeInterval<T>) -> Self {
+        relative.0
+    }
+}
+
+impl<T: num_traits::Float + Display> Display for RelativeInterval<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let precision = f.precision().unwrap_or(1);
+        match self.as_percent() {
+            Interval::TwoSided(low, high) => {
+                write!(f, "{:+.*}% … {:+.*}%", precision, low, precision, high)
+            }
+            Interval::UpperOneSided(low) => write!(f, "{:+.*}% … ->", precision, low),
+            Interval::LowerOneSided(high) => write!(f, "<- … {:+.*}%", precision, high),
+        }
+    }
+}
+
+pub use crate::error::IntervalError;
+
+/*
+ *      #   # #   # ### #####   ##### #####  #### #####  ####
+ *      #   # ##  #  #    #       #   #     #       #   #
+ *      #   # # # #  #    #       #   ###    ###    #    ###
+ *      #   # #  ##  #    #       #   #         #   #       #
+ *       ###  #   # ###   #       #   ##### ####    #   ####
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // constant intervals must remain constructible in `const` contexts
+    const WINDOW: Interval<f64> = Interval::two_sided_unchecked(0.5, 1.5);
+    const FLOOR: Interval<i32> = Interval::new_upper(3);
+    const CEILING: Interval<&str> = Interval::new_lower("M");
+
+    #[test]
+    fn test_interval_const() -> Result<(), IntervalError> {
+        assert_eq!(WINDOW, Interval::new(0.5, 1.5)?);
+        assert!(WINDOW.contains(&1.));
+        assert_eq!(FLOOR, Interval::new_upper(3));
+        assert!(!FLOOR.contains(&2));
+        assert_eq!(CEILING.high(), Some("M"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_new() -> Result<(), IntervalError> {
+        let interval = Interval::new(0., 1.)?;
+        assert_eq!(interval.low(), Some(0.));
+        assert_eq!(interval.high(), Some(1.));
+        assert_eq!(interval.low_f(), 0.);
         assert_eq!(interval.high_f(), 1.);
         assert!(interval.contains(&0.5));
         assert!(!interval.contains(&2.));
@@ -1070,7 +3096,7 @@ mod tests {
         let interval = Interval::new_lower(10_i64);
         assert_eq!(interval.low(), None);
         assert_eq!(interval.high(), Some(10));
-        assert_eq!(interval.low_i(), std::i64::MIN);
+        assert_eq!(interval.low_i(), i64::MIN);
         assert_eq!(interval.high_i(), 10);
         assert!(!interval.is_degenerate());
         assert!(!interval.is_two_sided());
@@ -1234,6 +3260,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_intersects_one_sided_regression() -> Result<(), IntervalError> {
+        // lower one-sided vs. upper one-sided and two-sided: compare the high bound of the
+        // lower one-sided interval to the low bound of the other one
+        let lower = Interval::new_lower(5);
+        assert!(lower.intersects(&Interval::new_upper(5)));
+        assert!(!lower.intersects(&Interval::new_upper(6)));
+        assert!(lower.intersects(&Interval::new(0, 10)?));
+        assert!(lower.intersects(&Interval::new(5, 10)?));
+        assert!(!lower.intersects(&Interval::new(6, 10)?));
+        assert!(lower.intersects(&Interval::new(-10, -5)?));
+
+        // two-sided vs. one-sided: the one-sided interval need not end within the two-sided one
+        let two_sided = Interval::new(0, 10)?;
+        assert!(two_sided.intersects(&Interval::new_upper(-5)));
+        assert!(two_sided.intersects(&Interval::new_lower(15)));
+        assert!(!two_sided.intersects(&Interval::new_upper(11)));
+        assert!(!two_sided.intersects(&Interval::new_lower(-1)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_scalar_arithmetic_one_sided_regression() {
+        // scalar arithmetic keeps the direction of one-sided intervals
+        assert_eq!(Interval::new_upper(1) + 2, Interval::new_upper(3));
+        assert_eq!(Interval::new_lower(1) + 2, Interval::new_lower(3));
+        assert_eq!(Interval::new_upper(1) - 2, Interval::new_upper(-1));
+        assert_eq!(Interval::new_lower(1) - 2, Interval::new_lower(-1));
+        assert_eq!(Interval::new_upper(1.) * 2., Interval::new_upper(2.));
+        assert_eq!(Interval::new_lower(1.) / 2., Interval::new_lower(0.5));
+    }
+
+    #[test]
+    fn test_neg_regression() -> Result<(), IntervalError> {
+        // negation swaps the bounds, and the direction of one-sided intervals
+        assert_eq!(-Interval::new(1, 3)?, Interval::new(-3, -1)?);
+        assert_eq!(-Interval::new_upper(2), Interval::new_lower(-2));
+        assert_eq!(-Interval::new_lower(2), Interval::new_upper(-2));
+        Ok(())
+    }
+
     #[test]
     fn test_interval_equality() -> Result<(), IntervalError> {
         let interval1 = Interval::new(0, 10)?;
@@ -1292,15 +3359,1033 @@ mod tests {
     }
 
     #[test]
-    fn test_send() {
-        fn assert_send<T: Send>() {}
-        assert_send::<Interval<f64>>();
-    }
+    fn test_from_margin() -> Result<(), IntervalError> {
+        let interval = Interval::from_margin(5, 2)?;
+        assert_eq!(interval, Interval::new(3, 7)?);
+        assert_eq!(interval.center(), Some(5));
+        assert_eq!(interval.half_width(), Some(2));
 
-    #[test]
-    fn test_sync() {
-        fn assert_sync<T: Sync>() {}
-        assert_sync::<Interval<f64>>();
+        let interval = Interval::from_margin(1.5, 0.)?;
+        assert!(interval.is_degenerate());
+        assert_eq!(interval.half_width(), Some(0.));
+
+        assert_eq!(
+            Interval::upper_from_margin(1.5, 0.5)?,
+            Interval::new_upper(1.)
+        );
+        assert_eq!(
+            Interval::lower_from_margin(1.5, 0.5)?,
+            Interval::new_lower(2.)
+        );
+        assert_eq!(Interval::new_upper(1.).center(), None);
+        assert_eq!(Interval::new_lower(1.).half_width(), None);
+
+        for margin in [-1., f64::NAN] {
+            assert_eq!(
+                Interval::from_margin(0., margin),
+                Err(IntervalError::InvalidMargin)
+            );
+            assert_eq!(
+                Interval::upper_from_margin(0., margin),
+                Err(IntervalError::InvalidMargin)
+            );
+            assert_eq!(
+                Interval::lower_from_margin(0., margin),
+                Err(IntervalError::InvalidMargin)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_containment_queries() -> Result<(), IntervalError> {
+        let data = [-1., 0., 0.5, f64::NAN, 1., 2.];
+        let two_sided = Interval::new(0., 1.)?;
+        let upper = Interval::new_upper(0.5);
+        let lower = Interval::new_lower(0.5);
+
+        assert_eq!(two_sided.count_contained(data), 3);
+        assert_eq!(upper.count_contained(data), 3);
+        assert_eq!(lower.count_contained(data), 3);
+        assert_eq!(two_sided.count_contained(data.iter().copied()), 3);
+        assert_eq!(two_sided.proportion_contained(data), 0.5);
+        assert!(two_sided.proportion_contained([] as [f64; 0]).is_nan());
+        assert_eq!(Interval::new(0, 0)?.count_contained(0..10), 1);
+
+        let (below, inside, above) = two_sided.partition(&data);
+        assert_eq!(below, [&-1.]);
+        assert_eq!(inside, [&0., &0.5, &1.]);
+        assert_eq!(above, [&2.]);
+
+        let (below, inside, above) = upper.partition(&data);
+        assert_eq!(below, [&-1., &0.]);
+        assert_eq!(inside, [&0.5, &1., &2.]);
+        assert!(above.is_empty());
+
+        let (below, inside, above) = lower.partition(&data);
+        assert!(below.is_empty());
+        assert_eq!(inside, [&-1., &0., &0.5]);
+        assert_eq!(above, [&1., &2.]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cmp_by_bounds() -> Result<(), IntervalError> {
+        use core::cmp::Ordering::*;
+        let a = Interval::new(0., 10.)?;
+        let b = Interval::new(5., 6.)?;
+        assert_eq!(a.cmp_by_bounds(&a), Equal);
+        assert_eq!(a.cmp_by_bounds(&b), Less);
+        assert_eq!(b.cmp_by_bounds(&a), Greater);
+        assert_eq!(a.cmp_by_bounds(&Interval::new(0., 5.)?), Greater);
+        assert_eq!(a.cmp_by_bounds(&Interval::new_upper(0.)), Less);
+        assert_eq!(a.cmp_by_bounds(&Interval::new_lower(20.)), Greater);
+        assert_eq!(
+            Interval::new_lower(1.).cmp_by_bounds(&Interval::new_lower(2.)),
+            Less
+        );
+        assert_eq!(
+            Interval::new_upper(1.).cmp_by_bounds(&Interval::new_upper(2.)),
+            Less
+        );
+        assert_eq!(
+            Interval::new_lower(f64::INFINITY)
+                .cmp_by_bounds(&Interval::new_upper(f64::NEG_INFINITY)),
+            Less
+        );
+
+        // NaN bounds come last and are equal to each other
+        let nan = Interval::TwoSided(f64::NAN, f64::NAN);
+        assert_eq!(
+            nan.cmp_by_bounds(&Interval::new_upper(f64::INFINITY)),
+            Greater
+        );
+        assert_eq!(nan.cmp_by_bounds(&nan), Equal);
+        assert_eq!(ByBounds(nan), ByBounds(nan));
+
+        // the partial order of intervals is untouched
+        assert_eq!(a.partial_cmp(&b), None);
+        assert_eq!(b.partial_cmp(&a), None);
+        assert_eq!(
+            Interval::new(0., 1.)?.partial_cmp(&Interval::new(2., 3.)?),
+            Some(Less)
+        );
+        assert_ne!(nan, nan);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_by_bounds() {
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha8Rng;
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let mut intervals: Vec<(usize, Interval<f64>)> = (0..1_000)
+            .map(|i| {
+                // few distinct values so that many intervals are equal by bounds
+                let x = rng.gen_range(0..10) as f64;
+                let y = rng.gen_range(0..10) as f64;
+                let interval = match rng.gen_range(0..3) {
+                    0 => Interval::TwoSided(x.min(y), x.max(y)),
+                    1 => Interval::UpperOneSided(x),
+                    _ => Interval::LowerOneSided(x),
+                };
+                (i, interval)
+            })
+            .collect();
+        intervals.sort_by(|(_, a), (_, b)| a.cmp_by_bounds(b));
+        for pair in intervals.windows(2) {
+            let ((i, a), (j, b)) = (pair[0], pair[1]);
+            assert_ne!(a.cmp_by_bounds(&b), core::cmp::Ordering::Greater);
+            if a.cmp_by_bounds(&b) == core::cmp::Ordering::Equal {
+                assert!(i < j, "sort is not stable");
+                assert_eq!(a, b);
+            }
+        }
+
+        let set: std::collections::BTreeSet<_> = intervals
+            .iter()
+            .map(|(_, interval)| ByBounds(*interval))
+            .collect();
+        let mut deduped: Vec<_> = intervals.iter().map(|(_, interval)| *interval).collect();
+        deduped.dedup();
+        assert_eq!(
+            set.into_iter()
+                .map(|ByBounds(interval)| interval)
+                .collect::<Vec<_>>(),
+            deduped
+        );
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), IntervalError> {
+        for (text, expected) in [
+            ("[0.95, 1.05]", Interval::new(0.95, 1.05)?),
+            ("[0.95,1.05]", Interval::new(0.95, 1.05)?),
+            ("  [ -1e3 ,\t2 ]  ", Interval::new(-1000., 2.)?),
+            ("[1, 1]", Interval::new(1., 1.)?),
+            ("(<-,250]", Interval::new_lower(250.)),
+            ("( <- , 250 ]", Interval::new_lower(250.)),
+            ("[3,->)", Interval::new_upper(3.)),
+            ("[3, ->)", Interval::new_upper(3.)),
+            ("[-inf, 0]", Interval::new(f64::NEG_INFINITY, 0.)?),
+        ] {
+            assert_eq!(text.parse::<Interval<f64>>()?, expected, "{text:?}");
+        }
+
+        for text in [
+            "",
+            "0.95, 1.05]",
+            "[0.95, 1.05",
+            "[0.95 1.05]",
+            "[a, 1]",
+            "[1, b]",
+            "(1, 2]",
+            "[1, 2)",
+            "(<-,->]",
+            "[<-, 2]",
+            "[1, 2, 3]",
+        ] {
+            assert!(
+                matches!(
+                    text.parse::<Interval<f64>>(),
+                    Err(IntervalError::ParseError(_))
+                ),
+                "{text:?}"
+            );
+        }
+        assert_eq!(
+            "[2, 1]".parse::<Interval<f64>>(),
+            Err(IntervalError::InvalidBounds)
+        );
+        assert_eq!(
+            "(<-,->)".parse::<Interval<f64>>(),
+            Err(IntervalError::Unbounded)
+        );
+        for text in ["[NaN, 1]", "[0, NaN]", "[NaN,->)", "(<-,NaN]"] {
+            assert!(
+                matches!(text.parse::<Interval<f64>>(), Err(IntervalError::NaNBound)),
+                "{text:?}"
+            );
+        }
+
+        assert_eq!("[-3, 5]".parse::<Interval<i64>>()?, Interval::new(-3, 5)?);
+        assert!(matches!(
+            "[1.5, 2]".parse::<Interval<i64>>(),
+            Err(IntervalError::ParseError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Interval<f64>>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<Interval<f64>>();
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn scalar() -> impl Strategy<Value = i64> {
+            -1_000_000_i64..1_000_000
+        }
+
+        fn two_sided() -> impl Strategy<Value = Interval<i64>> {
+            (scalar(), scalar()).prop_map(|(x, y)| Interval::TwoSided(x.min(y), x.max(y)))
+        }
+
+        proptest! {
+            #[test]
+            fn test_intersects_is_symmetric(a in any::<Interval<i64>>(), b in any::<Interval<i64>>()) {
+                prop_assert_eq!(a.intersects(&b), b.intersects(&a));
+            }
+
+            #[test]
+            fn test_includes_is_reflexive(a in any::<Interval<f64>>()) {
+                prop_assert!(a.includes(&a));
+                prop_assert!(a.is_included_in(&a));
+            }
+
+            #[test]
+            fn test_inclusion_implies_intersection(a in any::<Interval<i64>>(), b in any::<Interval<i64>>()) {
+                if a.includes(&b) {
+                    prop_assert!(a.intersects(&b));
+                    prop_assert!(b.is_included_in(&a));
+                }
+            }
+
+            #[test]
+            fn test_display_from_str_round_trip(a in any::<Interval<f64>>(), b in any::<Interval<i64>>()) {
+                prop_assert_eq!(a.to_string().parse::<Interval<f64>>().unwrap(), a);
+                prop_assert_eq!(b.to_string().parse::<Interval<i64>>().unwrap(), b);
+            }
+
+            #[test]
+            fn test_bounds_are_contained(a in any::<Interval<f64>>()) {
+                if let Some(low) = a.low() {
+                    prop_assert!(a.contains(&low));
+                }
+                if let Some(high) = a.high() {
+                    prop_assert!(a.contains(&high));
+                }
+            }
+
+            #[test]
+            fn test_add_sub_scalar_round_trip(a in any::<Interval<i64>>(), x in scalar()) {
+                let shifted = a + x;
+                prop_assert_eq!(shifted.is_upper(), a.is_upper());
+                prop_assert_eq!(shifted.is_lower(), a.is_lower());
+                prop_assert_eq!(shifted.low(), a.low().map(|low| low + x));
+                prop_assert_eq!(shifted.high(), a.high().map(|high| high + x));
+                prop_assert_eq!(shifted - x, a);
+            }
+
+            #[test]
+            fn test_neg_round_trip(a in any::<Interval<i64>>()) {
+                let negated = -a;
+                prop_assert_eq!(negated.low(), a.high().map(|high| -high));
+                prop_assert_eq!(negated.high(), a.low().map(|low| -low));
+                prop_assert_eq!(-negated, a);
+            }
+
+            #[test]
+            fn test_from_margin_round_trip(center in scalar(), margin in 0_i64..1_000_000) {
+                let interval = Interval::from_margin(center * 2, margin * 2).unwrap();
+                prop_assert_eq!(interval.center(), Some(center * 2));
+                prop_assert_eq!(interval.half_width(), Some(margin * 2));
+            }
+
+            #[test]
+            fn test_partition_is_consistent(a in any::<Interval<i64>>(), data in proptest::collection::vec(scalar(), 0..50)) {
+                let (below, inside, above) = a.partition(&data);
+                prop_assert_eq!(below.len() + inside.len() + above.len(), data.len());
+                prop_assert_eq!(inside.len(), a.count_contained(data.iter().copied()));
+                prop_assert!(inside.iter().all(|x| a.contains(x)));
+                prop_assert!(below.iter().chain(above.iter()).all(|x| !a.contains(x)));
+                if a.is_upper() {
+                    prop_assert!(above.is_empty());
+                }
+                if a.is_lower() {
+                    prop_assert!(below.is_empty());
+                }
+            }
+
+            #[test]
+            fn test_cmp_by_bounds_is_consistent(a in any::<Interval<i64>>(), b in any::<Interval<i64>>()) {
+                prop_assert_eq!(a.cmp_by_bounds(&b), b.cmp_by_bounds(&a).reverse());
+                prop_assert_eq!(a.cmp_by_bounds(&b) == core::cmp::Ordering::Equal, a == b);
+                if let Some(ordering) = a.partial_cmp(&b) {
+                    if ordering != core::cmp::Ordering::Equal {
+                        prop_assert_eq!(a.cmp_by_bounds(&b), ordering);
+                    }
+                }
+            }
+
+            #[test]
+            fn test_add_sub_interval_round_trip(a in two_sided(), b in two_sided()) {
+                let sum = a + b;
+                prop_assert!(sum.includes(&(a + b.low_i())));
+                prop_assert!(sum.includes(&(a + b.high_i())));
+                prop_assert!((sum - b).includes(&a));
+            }
+
+            #[test]
+            fn test_arithmetic_contains_results(a in any::<Interval<i64>>(), b in any::<Interval<i64>>(), x in scalar(), y in scalar()) {
+                let point = |interval: &Interval<i64>, x: i64| {
+                    let x = interval.left().map_or(x, |&low| x.max(low));
+                    interval.right().map_or(x, |&high| x.min(high))
+                };
+                let (x, y) = (point(&a, x), point(&b, y));
+                let results = [
+                    (a.try_add(b), x + y),
+                    (a.try_sub(b), x - y),
+                    (a.try_mul(b), x * y),
+                ];
+                for (result, value) in results {
+                    if let Ok(result) = result {
+                        prop_assert!(result.contains(&value), "{:?} does not contain {}", result, value);
+                    }
+                }
+                match a.try_div(b) {
+                    Ok(result) => prop_assert!(result.contains(&(x / y))),
+                    Err(_) => prop_assert!(b.contains(&0)),
+                }
+                prop_assert_eq!(a.try_mul(b).ok(), b.try_mul(a).ok());
+                prop_assert_eq!(a.try_add(b).ok(), b.try_add(a).ok());
+            }
+
+            #[test]
+            fn test_interval_set_is_normalized(intervals in proptest::collection::vec(two_sided(), 0..20), x in scalar()) {
+                let set = IntervalSet::from_intervals(intervals.clone()).unwrap();
+                for pair in set.as_slice().windows(2) {
+                    prop_assert!(pair[0] < pair[1]);
+                    prop_assert!(!pair[0].intersects(&pair[1]));
+                }
+                for interval in &intervals {
+                    prop_assert!(set.iter().any(|member| member.includes(interval)));
+                }
+                prop_assert_eq!(set.contains(&x), intervals.iter().any(|interval| interval.contains(&x)));
+            }
+
+            #[test]
+            fn test_difference_is_consistent(a in any::<Interval<i64>>(), b in any::<Interval<i64>>(), x in scalar()) {
+                let difference = a.difference(&b);
+                if difference.contains(&x) {
+                    prop_assert!(a.contains(&x));
+                }
+                if a.contains(&x) && !b.contains(&x) {
+                    prop_assert!(difference.contains(&x));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nan_semantics() {
+        let (nan, inf) = (f64::NAN, f64::INFINITY);
+        let queries = [-inf, 0.5, 2., inf];
+        let (t, f) = (Some(true), Some(false));
+        // expected `contains_total` for the queries above, over {finite, ±inf, NaN} bounds
+        let cases = [
+            (Interval::TwoSided(0., 1.), [f, t, f, f]),
+            (Interval::TwoSided(-inf, 1.), [t, t, f, f]),
+            (Interval::TwoSided(0., inf), [f, t, t, t]),
+            (Interval::TwoSided(-inf, inf), [t, t, t, t]),
+            (Interval::TwoSided(inf, inf), [f, f, f, t]),
+            (Interval::TwoSided(nan, 1.), [None; 4]),
+            (Interval::TwoSided(0., nan), [None; 4]),
+            (Interval::TwoSided(-inf, nan), [None; 4]),
+            (Interval::TwoSided(nan, inf), [None; 4]),
+            (Interval::TwoSided(nan, nan), [None; 4]),
+            (Interval::UpperOneSided(0.), [f, t, t, t]),
+            (Interval::UpperOneSided(-inf), [t, t, t, t]),
+            (Interval::UpperOneSided(inf), [f, f, f, t]),
+            (Interval::UpperOneSided(nan), [None; 4]),
+            (Interval::LowerOneSided(1.), [t, t, f, f]),
+            (Interval::LowerOneSided(-inf), [t, f, f, f]),
+            (Interval::LowerOneSided(inf), [t, t, t, t]),
+            (Interval::LowerOneSided(nan), [None; 4]),
+        ];
+        let reference = Interval::TwoSided(0.5, 0.75);
+        for (interval, expected) in cases {
+            for (x, expected) in queries.iter().zip(expected) {
+                assert_eq!(interval.contains_total(x), expected, "{interval} ∋ {x}");
+            }
+            // NaN queries are incomparable, and never contained
+            assert_eq!(interval.contains_total(&nan), None, "{interval}");
+            let has_nan_bound = expected == [None; 4];
+            assert_eq!(
+                interval.intersects_total(&reference).is_none(),
+                has_nan_bound
+            );
+            assert_eq!(
+                reference.intersects_total(&interval).is_none(),
+                has_nan_bound
+            );
+            assert_eq!(interval.includes_total(&reference).is_none(), has_nan_bound);
+            assert_eq!(reference.includes_total(&interval).is_none(), has_nan_bound);
+            if !has_nan_bound {
+                for (x, expected) in queries.iter().zip(expected) {
+                    assert_eq!(Some(interval.contains(x)), expected, "{interval} ∋ {x}");
+                }
+                assert!(!interval.contains(&nan), "{interval}");
+                assert_eq!(
+                    interval.intersects_total(&reference),
+                    Some(interval.intersects(&reference))
+                );
+                assert_eq!(
+                    interval.includes_total(&reference),
+                    Some(interval.includes(&reference))
+                );
+            }
+        }
+        assert_eq!(
+            Interval::new(0., 1.)
+                .unwrap()
+                .intersects_total(&Interval::new(2., 3.).unwrap()),
+            Some(false)
+        );
+        assert_eq!(
+            Interval::new_upper(0.).includes_total(&Interval::new_upper(-1.)),
+            Some(false)
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "NaN bound")]
+    fn test_contains_nan_bound() {
+        Interval::TwoSided(f64::NAN, 1.).contains(&0.5);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "NaN bound")]
+    fn test_intersects_nan_bound() {
+        Interval::new(0., 1.)
+            .unwrap()
+            .intersects(&Interval::new_lower(f64::NAN));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "NaN bound")]
+    fn test_includes_nan_bound() {
+        Interval::new_upper(f64::NAN).includes(&Interval::new(0., 1.).unwrap());
+    }
+
+    #[test]
+    fn test_try_new_finite() -> Result<(), IntervalError> {
+        let nan = f64::NAN;
+        let inf = f64::INFINITY;
+        assert_eq!(
+            Interval::try_new_finite(nan, 1.),
+            Err(IntervalError::NaNBound)
+        );
+        assert_eq!(
+            Interval::try_new_finite(0., nan),
+            Err(IntervalError::NaNBound)
+        );
+        assert_eq!(
+            Interval::try_new_finite(nan, nan),
+            Err(IntervalError::NaNBound)
+        );
+        assert_eq!(
+            Interval::try_new_finite(2., 1.),
+            Err(IntervalError::InvalidBounds)
+        );
+        assert_eq!(
+            Interval::try_new_finite(-inf, inf),
+            Err(IntervalError::Unbounded)
+        );
+        assert_eq!(
+            Interval::try_new_finite(inf, inf),
+            Err(IntervalError::EmptyInterval)
+        );
+        assert_eq!(
+            Interval::try_new_finite(-inf, -inf),
+            Err(IntervalError::EmptyInterval)
+        );
+
+        assert_eq!(Interval::try_new_finite(0., 1.)?, Interval::new(0., 1.)?);
+        assert_eq!(Interval::try_new_finite(1., 1.)?, Interval::new(1., 1.)?);
+        assert_eq!(Interval::try_new_finite(0., inf)?, Interval::new_upper(0.));
+        assert_eq!(Interval::try_new_finite(-inf, 1.)?, Interval::new_lower(1.));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_bounds_for() -> CIResult<()> {
+        use crate::error::CIError;
+        let two_sided = Confidence::new_two_sided(0.95);
+        let upper = Confidence::new_upper(0.95);
+        let lower = Confidence::new_lower(0.95);
+
+        assert_eq!(
+            Interval::from_bounds_for(two_sided, 1, 2)?,
+            Interval::new(1, 2)?
+        );
+        assert_eq!(
+            Interval::from_bounds_for(upper, 1, 2)?,
+            Interval::new_upper(1)
+        );
+        assert_eq!(
+            Interval::from_bounds_for(lower, 1, 2)?,
+            Interval::new_lower(2)
+        );
+        assert!(matches!(
+            Interval::from_bounds_for(two_sided, 2, 1),
+            Err(CIError::IntervalError(IntervalError::InvalidBounds))
+        ));
+        // the irrelevant bound is ignored
+        assert_eq!(
+            Interval::from_bounds_for(upper, 2, 1)?,
+            Interval::new_upper(2)
+        );
+        assert_eq!(
+            Interval::from_bounds_for(lower, 2, 1)?,
+            Interval::new_lower(1)
+        );
+
+        let nan = f64::NAN;
+        assert!(matches!(
+            Interval::from_finite_bounds_for(upper, nan, 1.),
+            Err(CIError::IntervalError(IntervalError::NaNBound))
+        ));
+        assert!(matches!(
+            Interval::from_finite_bounds_for(two_sided, 0., nan),
+            Err(CIError::IntervalError(IntervalError::NaNBound))
+        ));
+        assert_eq!(
+            Interval::from_finite_bounds_for(upper, 0., nan)?,
+            Interval::new_upper(0.)
+        );
+        assert_eq!(
+            Interval::from_finite_bounds_for(lower, nan, 1.)?,
+            Interval::new_lower(1.)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_significance() -> Result<(), IntervalError> {
+        use Significance::*;
+        let cases = [
+            // two-sided
+            (Interval::new(1., 2.)?, PositivelySignificant),
+            (Interval::new(-2., -1.)?, NegativelySignificant),
+            (Interval::new(-1., 1.)?, NotSignificant),
+            (Interval::new(0., 1.)?, NotSignificant),
+            (Interval::new(-1., 0.)?, NotSignificant),
+            (Interval::new(0., 0.)?, NotSignificant),
+            (Interval::new(1., 1.)?, PositivelySignificant),
+            // upper one-sided: [low, +inf)
+            (Interval::new_upper(1.), PositivelySignificant),
+            (Interval::new_upper(0.), NotSignificant),
+            (Interval::new_upper(-1.), NotSignificant),
+            // lower one-sided: (-inf, high]
+            (Interval::new_lower(-1.), NegativelySignificant),
+            (Interval::new_lower(0.), NotSignificant),
+            (Interval::new_lower(1.), NotSignificant),
+        ];
+        for (interval, expected) in cases {
+            assert_eq!(interval.significance(), expected, "{}", interval);
+            assert_eq!(expected.is_significant(), expected != NotSignificant);
+        }
+
+        // integers and NaN bounds
+        assert_eq!(Interval::new(-3, -2)?.significance(), NegativelySignificant);
+        assert_eq!(Interval::new_upper(f64::NAN).significance(), NotSignificant);
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_set_normalization() -> Result<(), IntervalError> {
+        // empty
+        let set = IntervalSet::<i32>::from_intervals(vec![])?;
+        assert!(set.is_empty());
+        assert_eq!(set, IntervalSet::new());
+        assert_eq!(set.to_string(), "∅");
+
+        // disjoint intervals are sorted
+        let set = IntervalSet::from_intervals(vec![Interval::new(5, 6)?, Interval::new(0, 2)?])?;
+        assert_eq!(set.as_slice(), [Interval::new(0, 2)?, Interval::new(5, 6)?]);
+
+        // overlapping intervals are merged
+        let set = IntervalSet::from_intervals(vec![
+            Interval::new(3, 8)?,
+            Interval::new(0, 4)?,
+            Interval::new(5, 6)?,
+        ])?;
+        assert_eq!(set.as_slice(), [Interval::new(0, 8)?]);
+
+        // intervals touching at a bound are merged (bounds are inclusive)
+        let set = IntervalSet::from_intervals(vec![
+            Interval::new(2, 5)?,
+            Interval::new(0, 2)?,
+            Interval::new(5, 5)?,
+            Interval::new(6, 7)?,
+        ])?;
+        assert_eq!(set.as_slice(), [Interval::new(0, 5)?, Interval::new(6, 7)?]);
+        let set =
+            IntervalSet::from_intervals(vec![Interval::new_lower(1.), Interval::new(1., 2.)?])?;
+        assert_eq!(set.as_slice(), [Interval::new_lower(2.)]);
+        let set =
+            IntervalSet::from_intervals(vec![Interval::new_upper(2.), Interval::new(1., 2.)?])?;
+        assert_eq!(set.as_slice(), [Interval::new_upper(1.)]);
+
+        // degenerate and duplicate intervals
+        let set = IntervalSet::from_intervals(vec![
+            Interval::new(1, 1)?,
+            Interval::new(1, 1)?,
+            Interval::new(3, 3)?,
+        ])?;
+        assert_eq!(set.as_slice(), [Interval::new(1, 1)?, Interval::new(3, 3)?]);
+
+        // one-sided intervals absorb the intervals they intersect
+        let set = IntervalSet::from_intervals(vec![
+            Interval::new(8, 9)?,
+            Interval::new_upper(5),
+            Interval::new(-3, -1)?,
+            Interval::new_lower(-2),
+            Interval::new_upper(7),
+        ])?;
+        assert_eq!(
+            set.as_slice(),
+            [Interval::new_lower(-1), Interval::new_upper(5)]
+        );
+        assert_eq!(set.to_string(), "(<-,-1] ∪ [5,->)");
+
+        // the union cannot cover all values
+        assert_eq!(
+            IntervalSet::from_intervals(vec![Interval::new_lower(0), Interval::new_upper(0)]),
+            Err(IntervalError::Unbounded)
+        );
+        assert_eq!(
+            IntervalSet::from_intervals(vec![
+                Interval::new_lower(0),
+                Interval::new(0, 10)?,
+                Interval::new_upper(10),
+            ]),
+            Err(IntervalError::Unbounded)
+        );
+        assert!(
+            IntervalSet::from_intervals(vec![Interval::new_lower(0), Interval::new_upper(1)])
+                .is_ok()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_difference() -> Result<(), IntervalError> {
+        let interval = Interval::new(0, 10)?;
+        // disjoint
+        assert_eq!(
+            interval.difference(&Interval::new(11, 12)?).as_slice(),
+            [interval]
+        );
+        // inner interval
+        assert_eq!(
+            interval.difference(&Interval::new(3, 5)?).as_slice(),
+            [Interval::new(0, 3)?, Interval::new(5, 10)?]
+        );
+        // overlapping either side
+        assert_eq!(
+            interval.difference(&Interval::new(-5, 5)?).as_slice(),
+            [Interval::new(5, 10)?]
+        );
+        assert_eq!(
+            interval.difference(&Interval::new_upper(5)).as_slice(),
+            [Interval::new(0, 5)?]
+        );
+        // sharing a bound
+        assert_eq!(
+            interval.difference(&Interval::new(0, 5)?).as_slice(),
+            [Interval::new(5, 10)?]
+        );
+        assert_eq!(
+            interval.difference(&Interval::new(10, 15)?).as_slice(),
+            [Interval::new(0, 10)?]
+        );
+        // a degenerate interval leaves the closure unchanged
+        assert_eq!(
+            interval.difference(&Interval::new(5, 5)?).as_slice(),
+            [interval]
+        );
+        // covering
+        assert!(interval.difference(&interval).is_empty());
+        assert!(interval.difference(&Interval::new_lower(10)).is_empty());
+        // one-sided
+        assert_eq!(
+            Interval::new_upper(0).difference(&interval).as_slice(),
+            [Interval::new_upper(10)]
+        );
+        assert_eq!(
+            Interval::new_lower(0)
+                .difference(&Interval::new_lower(-5))
+                .as_slice(),
+            [Interval::new(-5, 0)?]
+        );
+        assert_eq!(
+            Interval::new_lower(0)
+                .difference(&Interval::new(-5, -3)?)
+                .as_slice(),
+            [Interval::new_lower(-5), Interval::new(-3, 0)?]
+        );
+        assert!(Interval::new_lower(0)
+            .difference(&Interval::new_lower(5))
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_set_operations() -> Result<(), IntervalError> {
+        let set = IntervalSet::from_intervals(vec![
+            Interval::new_lower(-5.),
+            Interval::new(0., 1.)?,
+            Interval::new_upper(5.),
+        ])?;
+        assert!(set.contains(&-10.));
+        assert!(set.contains(&1.));
+        assert!(!set.contains(&2.));
+        assert!(set.contains(&5.));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.to_string(), "(<-,-5] ∪ [0, 1] ∪ [5,->)");
+
+        // intersection
+        assert_eq!(
+            set.intersect(&Interval::new(-6., 6.)?).as_slice(),
+            [
+                Interval::new(-6., -5.)?,
+                Interval::new(0., 1.)?,
+                Interval::new(5., 6.)?
+            ]
+        );
+        assert_eq!(
+            set.intersect(&Interval::new(1., 5.)?).as_slice(),
+            [Interval::new(1., 1.)?, Interval::new(5., 5.)?]
+        );
+        assert!(set.intersect(&Interval::new(2., 3.)?).is_empty());
+
+        // union
+        let other =
+            IntervalSet::from_intervals(vec![Interval::new(1., 2.)?, Interval::new(3., 4.)?])?;
+        assert_eq!(
+            set.union(&other)?.as_slice(),
+            [
+                Interval::new_lower(-5.),
+                Interval::new(0., 2.)?,
+                Interval::new(3., 4.)?,
+                Interval::new_upper(5.)
+            ]
+        );
+        assert_eq!(set.union(&IntervalSet::new())?, set);
+        assert_eq!(
+            set.union(&Interval::new(-5., 5.)?.into()),
+            Err(IntervalError::Unbounded)
+        );
+
+        // complement
+        assert_eq!(
+            set.complement_within(&Interval::new(-10., 10.)?).as_slice(),
+            [Interval::new(-5., 0.)?, Interval::new(1., 5.)?]
+        );
+        assert_eq!(
+            set.complement_within(&Interval::new_upper(0.5)).as_slice(),
+            [Interval::new(1., 5.)?]
+        );
+        assert!(set.complement_within(&Interval::new(0.2, 0.8)?).is_empty());
+        assert_eq!(
+            IntervalSet::new().complement_within(&Interval::new_lower(3.)),
+            Interval::new_lower(3.).into()
+        );
+
+        // iteration
+        let members: Vec<_> = set.iter().cloned().collect();
+        assert_eq!(members, (&set).into_iter().cloned().collect::<Vec<_>>());
+        assert_eq!(members, set.into_iter().collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[test]
+    fn test_mul_div_scalar() -> Result<(), IntervalError> {
+        let interval = Interval::new(-1., 2.)?;
+        assert_eq!(interval * 2., Interval::new(-2., 4.)?);
+        assert_eq!(interval * -2., Interval::new(-4., 2.)?);
+        assert_eq!(interval / -2., Interval::new(-1., 0.5)?);
+        assert_eq!(interval * 0., Interval::new(0., 0.)?);
+        assert_eq!(Interval::new_upper(2.) * -1., Interval::new_lower(-2.));
+        assert_eq!(Interval::new_lower(2.) * -3., Interval::new_upper(-6.));
+        assert_eq!(Interval::new_upper(2) / -2, Interval::new_lower(-1));
+        assert_eq!(Interval::new_lower(4.) / 2., Interval::new_lower(2.));
+        assert_eq!(Interval::new(1_u32, 3)? * 2, Interval::new(2, 6)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_arithmetic() -> Result<(), IntervalError> {
+        let two = Interval::new(2, 3)?;
+        let upper = Interval::new_upper(1);
+        let lower = Interval::new_lower(-2);
+        let unrepresentable = |r: Result<Interval<i32>, IntervalError>| {
+            matches!(r, Err(IntervalError::UnrepresentableResult(_)))
+        };
+
+        // sums
+        assert_eq!(two.try_add(two)?, Interval::new(4, 6)?);
+        assert_eq!(two.try_add(upper)?, Interval::new_upper(3));
+        assert_eq!(two.try_add(lower)?, Interval::new_lower(1));
+        assert_eq!(upper.try_add(two)?, Interval::new_upper(3));
+        assert_eq!(upper.try_add(upper)?, Interval::new_upper(2));
+        assert!(unrepresentable(upper.try_add(lower)));
+        assert_eq!(lower.try_add(two)?, Interval::new_lower(1));
+        assert!(unrepresentable(lower.try_add(upper)));
+        assert_eq!(lower.try_add(lower)?, Interval::new_lower(-4));
+
+        // differences
+        assert_eq!(two.try_sub(two)?, Interval::new(-1, 1)?);
+        assert_eq!(two.try_sub(upper)?, Interval::new_lower(2));
+        assert_eq!(two.try_sub(lower)?, Interval::new_upper(4));
+        assert_eq!(upper.try_sub(two)?, Interval::new_upper(-2));
+        assert!(unrepresentable(upper.try_sub(upper)));
+        assert_eq!(upper.try_sub(lower)?, Interval::new_upper(3));
+        assert_eq!(lower.try_sub(two)?, Interval::new_lower(-4));
+        assert_eq!(lower.try_sub(upper)?, Interval::new_lower(-3));
+        assert!(unrepresentable(lower.try_sub(lower)));
+
+        // subtracting a degenerate interval is the same as subtracting a scalar
+        for interval in [two, upper, lower] {
+            assert_eq!(interval.try_sub(Interval::new(2, 2)?)?, interval - 2);
+            assert_eq!(interval.try_add(Interval::new(2, 2)?)?, interval + 2);
+            assert_eq!(interval.try_mul(Interval::new(-2, -2)?)?, interval * -2);
+            assert_eq!(interval.try_div(Interval::new(-2, -2)?)?, interval / -2);
+        }
+
+        // products
+        let mixed = Interval::new(-1, 4)?;
+        assert_eq!(two.try_mul(mixed)?, Interval::new(-3, 12)?);
+        assert_eq!(mixed.try_mul(mixed)?, Interval::new(-4, 16)?);
+        assert_eq!(two.try_mul(upper)?, Interval::new_upper(2));
+        assert_eq!(two.try_mul(lower)?, Interval::new_lower(-4));
+        assert_eq!(upper.try_mul(two)?, Interval::new_upper(2));
+        assert_eq!(upper.try_mul(upper)?, Interval::new_upper(1));
+        assert_eq!(upper.try_mul(lower)?, Interval::new_lower(-2));
+        assert_eq!(lower.try_mul(two)?, Interval::new_lower(-4));
+        assert_eq!(lower.try_mul(upper)?, Interval::new_lower(-2));
+        assert_eq!(lower.try_mul(lower)?, Interval::new_upper(4));
+        assert!(unrepresentable(mixed.try_mul(upper)));
+        assert!(unrepresentable(lower.try_mul(mixed)));
+        assert!(unrepresentable(
+            Interval::new_upper(-1).try_mul(Interval::new_upper(1))
+        ));
+        // zero times an unbounded interval
+        let zero = Interval::new(0, 0)?;
+        assert_eq!(zero.try_mul(upper)?, zero);
+        assert_eq!(lower.try_mul(zero)?, zero);
+        assert_eq!(
+            Interval::new_upper(0).try_mul(Interval::new(1, 2)?)?,
+            Interval::new_upper(0)
+        );
+
+        // quotients
+        let two = Interval::new(20, 30)?;
+        assert_eq!(two.try_div(Interval::new(2, 5)?)?, Interval::new(4, 15)?);
+        assert_eq!(
+            two.try_div(Interval::new(-5, -2)?)?,
+            Interval::new(-15, -4)?
+        );
+        assert_eq!(two.try_div(upper)?, Interval::new(0, 30)?);
+        assert_eq!(two.try_div(lower)?, Interval::new(-15, 0)?);
+        assert_eq!(
+            Interval::new_upper(10).try_div(Interval::new(2, 5)?)?,
+            Interval::new_upper(2)
+        );
+        assert_eq!(
+            Interval::new_upper(10).try_div(upper)?,
+            Interval::new_upper(0)
+        );
+        assert_eq!(
+            Interval::new_upper(10).try_div(lower)?,
+            Interval::new_lower(0)
+        );
+        assert_eq!(
+            Interval::new_lower(-10).try_div(Interval::new(2, 5)?)?,
+            Interval::new_lower(-2)
+        );
+        assert_eq!(
+            Interval::new_lower(-10).try_div(upper)?,
+            Interval::new_lower(0)
+        );
+        assert_eq!(
+            Interval::new_lower(-10).try_div(lower)?,
+            Interval::new_upper(0)
+        );
+        assert_eq!(
+            Interval::new_upper(-10).try_div(upper)?,
+            Interval::new_upper(-10)
+        );
+        for divisor in [mixed, zero, Interval::new_upper(0), Interval::new_lower(3)] {
+            assert!(unrepresentable(two.try_div(divisor)));
+        }
+
+        // floats
+        let a = Interval::new(-1., 2.)?;
+        assert_eq!(a * Interval::new(3., 4.)?, Interval::new(-4., 8.)?);
+        assert_eq!(a / Interval::new(2., 4.)?, Interval::new(-0.5, 1.)?);
+        assert_eq!(
+            Interval::new(1., 2.)? / Interval::new_lower(-4.),
+            Interval::new(-0.5, 0.)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot subtract intervals")]
+    fn test_interval_sub_panics() {
+        let _ = Interval::new_upper(1.) - Interval::new_upper(2.);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot divide intervals")]
+    fn test_interval_div_panics() {
+        let _ = Interval::new(1., 2.).unwrap() / Interval::new(-1., 1.).unwrap();
+    }
+
+    #[test]
+    fn test_relative_interval() -> CIResult<()> {
+        let reference = Interval::new(100., 125.)?;
+
+        // straddling zero
+        let relative = Interval::new(90., 110.)?.try_relative_to(&reference)?;
+        approx::assert_abs_diff_eq!(relative.interval(), Interval::new(-0.28, 0.1)?);
+        approx::assert_abs_diff_eq!(
+            relative.as_percent(),
+            Interval::new(-28., 10.)?,
+            epsilon = 1e-12
+        );
+        assert_eq!(relative.to_string(), "-28.0% … +10.0%");
+        assert_eq!(format!("{:.0}", relative), "-28% … +10%");
+        assert_eq!(relative.is_improvement(true), None);
+        assert_eq!(relative.is_improvement(false), None);
+
+        // significant changes
+        let smaller = Interval::new(50., 80.)?.try_relative_to(&reference)?;
+        assert_eq!(smaller.to_string(), "-60.0% … -20.0%");
+        assert_eq!(smaller.is_improvement(true), Some(true));
+        assert_eq!(smaller.is_improvement(false), Some(false));
+        let larger = Interval::new(150., 200.)?.try_relative_to(&reference)?;
+        assert_eq!(larger.to_string(), "+20.0% … +100.0%");
+        assert_eq!(larger.is_improvement(true), Some(false));
+        assert_eq!(larger.is_improvement(false), Some(true));
+
+        // upper one-sided
+        let upper = Interval::new_upper(150.).try_relative_to(&reference)?;
+        assert_eq!(upper.interval(), Interval::new_upper(0.2));
+        assert_eq!(upper.to_string(), "+20.0% … ->");
+        assert_eq!(upper.is_improvement(false), Some(true));
+        let upper = Interval::new_upper(110.).try_relative_to(&reference)?;
+        assert_eq!(upper.to_string(), "-12.0% … ->");
+        assert_eq!(upper.is_improvement(false), None);
+
+        // lower one-sided
+        let lower = Interval::new_lower(80.).try_relative_to(&reference)?;
+        assert_eq!(lower.interval(), Interval::new_lower(-0.2));
+        assert_eq!(lower.to_string(), "<- … -20.0%");
+        assert_eq!(lower.is_improvement(true), Some(true));
+        let lower = Interval::new_lower(110.).try_relative_to(&reference)?;
+        assert_eq!(lower.to_string(), "<- … +10.0%");
+        assert_eq!(lower.is_improvement(true), None);
+
+        // one-sided reference
+        let relative = Interval::new(90., 110.)?.try_relative_to(&Interval::new_upper(100.))?;
+        assert_eq!(relative.interval(), Interval::new_lower(0.1));
+
+        // invalid references
+        let interval = Interval::new(90., 110.)?;
+        for reference in [
+            Interval::new(0., 1.)?,
+            Interval::new(-1., 1.)?,
+            Interval::new_lower(-1.),
+            Interval::new_upper(0.),
+        ] {
+            assert!(matches!(
+                interval.try_relative_to(&reference),
+                Err(crate::error::CIError::InvalidBaseline(_))
+            ));
+        }
+        assert!(Interval::new_upper(1.)
+            .try_relative_to(&Interval::new_upper(1.))
+            .is_err());
+        assert!(Interval::new_lower(1.)
+            .try_relative_to(&Interval::new_lower(1.))
+            .is_err());
+        Ok(())
     }
 
     #[test]
@@ -1312,4 +4397,365 @@ mod tests {
         assert!(interval1.abs_diff_eq(&interval2, 1e-6));
         assert_abs_diff_eq!(interval1, interval2, epsilon = 1e-6);
     }
+
+    #[cfg(feature = "serde")]
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Wrapper {
+        interval: Interval<f64>,
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() -> Result<(), IntervalError> {
+        for interval in [
+            Interval::new(-1.5, 2.)?,
+            Interval::new(3., 3.)?,
+            Interval::new_upper(0.25),
+            Interval::new_lower(-10.),
+        ] {
+            let wrapper = Wrapper { interval };
+            let serialized = toml::to_string(&wrapper).unwrap();
+            let deserialized: Wrapper = toml::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, wrapper);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_invalid_bounds() {
+        for (payload, message) in [
+            (
+                "{ TwoSided = [2.0, 1.0] }",
+                "`TwoSided` interval: Invalid bounds",
+            ),
+            (
+                "{ TwoSided = [nan, 1.0] }",
+                "`TwoSided` interval: Invalid bound: NaN",
+            ),
+            (
+                "{ TwoSided = [0.0, nan] }",
+                "`TwoSided` interval: Invalid bound: NaN",
+            ),
+            (
+                "{ UpperOneSided = nan }",
+                "`UpperOneSided` interval: Invalid bound: NaN",
+            ),
+            (
+                "{ LowerOneSided = nan }",
+                "`LowerOneSided` interval: Invalid bound: NaN",
+            ),
+        ] {
+            let error = toml::from_str::<Wrapper>(&format!("interval = {payload}")).unwrap_err();
+            let error = error.to_string();
+            assert!(error.contains(message), "{error}");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_wire_format() -> Result<(), IntervalError> {
+        for (interval, json) in [
+            (
+                Interval::new(1., 2.)?,
+                r#"{"kind":"two_sided","low":1.0,"high":2.0}"#,
+            ),
+            (
+                Interval::new_upper(1.),
+                r#"{"kind":"upper_one_sided","low":1.0}"#,
+            ),
+            (
+                Interval::new_lower(2.),
+                r#"{"kind":"lower_one_sided","high":2.0}"#,
+            ),
+        ] {
+            assert_eq!(serde_json::to_string(&interval).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<Interval<f64>>(json).unwrap(),
+                interval
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&Interval::new(1_usize, 3)?).unwrap(),
+            r#"{"kind":"two_sided","low":1,"high":3}"#
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_legacy_format() -> Result<(), IntervalError> {
+        for (json, interval) in [
+            (r#"{"TwoSided":[1.0,2.0]}"#, Interval::new(1., 2.)?),
+            (r#"{"UpperOneSided":1.0}"#, Interval::new_upper(1.)),
+            (r#"{"LowerOneSided":2.0}"#, Interval::new_lower(2.)),
+        ] {
+            assert_eq!(
+                serde_json::from_str::<Interval<f64>>(json).unwrap(),
+                interval
+            );
+        }
+        let error = serde_json::from_str::<Interval<f64>>(r#"{"TwoSided":[2.0,1.0]}"#).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("`TwoSided` interval: Invalid bounds"));
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_wire_format_invalid() {
+        for (payload, message) in [
+            (
+                r#"{"kind":"two_sided","low":2.0,"high":1.0}"#,
+                "`two_sided` interval: Invalid bounds",
+            ),
+            (
+                r#"{ kind = "two_sided", low = nan, high = 1.0 }"#,
+                "`two_sided` interval: Invalid bound: NaN",
+            ),
+            (
+                r#"{ kind = "upper_one_sided", low = nan }"#,
+                "`upper_one_sided` interval: Invalid bound: NaN",
+            ),
+            (
+                r#"{ kind = "lower_one_sided", high = nan }"#,
+                "`lower_one_sided` interval: Invalid bound: NaN",
+            ),
+        ] {
+            let error = if payload.starts_with("{\"") {
+                serde_json::from_str::<Interval<f64>>(payload)
+                    .unwrap_err()
+                    .to_string()
+            } else {
+                toml::from_str::<Wrapper>(&format!("interval = {payload}"))
+                    .unwrap_err()
+                    .to_string()
+            };
+            assert!(error.contains(message), "{error}");
+        }
+        // unknown kinds and missing bounds are rejected
+        for json in [
+            r#"{"kind":"both_sided","low":1.0,"high":2.0}"#,
+            r#"{"kind":"two_sided","low":1.0}"#,
+            r#"{"kind":"lower_one_sided","low":1.0}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<Interval<f64>>(json).is_err(),
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_display_compact() -> Result<(), IntervalError> {
+        let cases = [
+            (Interval::new(1.2e-20, 1.7e23)?, "[1.20e-20, 1.70e23]"),
+            (Interval::new(0.123456, 98.7654)?, "[0.123, 98.8]"),
+            (Interval::new(-98.7654, -0.00123456)?, "[-98.8, -0.00123]"),
+            (Interval::new(-1.5e-9, 2.5e-9)?, "[-1.50e-9, 2.50e-9]"),
+            (Interval::new(123_456., 999_999.)?, "[123000, 1.00e6]"),
+            (Interval::new(0.0009996, 9.999)?, "[0.00100, 10.0]"),
+            (Interval::new(0., 1.)?, "[0.00, 1.00]"),
+            (Interval::new(42., 42.)?, "[42.0, 42.0]"),
+            (Interval::new_upper(-3.2e-7), "[-3.20e-7,->)"),
+            (Interval::new_lower(6.02e23), "(<-,6.02e23]"),
+            (Interval::new(f64::NEG_INFINITY, 1.)?, "[-inf, 1.00]"),
+            (
+                Interval::try_new_finite(f64::NEG_INFINITY, 1.)?,
+                "(<-,1.00]",
+            ),
+            (
+                Interval::try_new_finite(5e-5, f64::INFINITY)?,
+                "[5.00e-5,->)",
+            ),
+        ];
+        for (interval, expected) in cases {
+            assert_eq!(interval.display_compact(3).to_string(), expected);
+            if !expected.contains("inf") {
+                let parsed: Interval<f64> = expected.parse()?;
+                assert_eq!(parsed.display_compact(3).to_string(), expected);
+            }
+        }
+
+        let interval = Interval::new(0.000123456, 1_234_567.)?;
+        assert_eq!(interval.display_compact(1).to_string(), "[1e-4, 1e6]");
+        assert_eq!(interval.display_compact(0).to_string(), "[1e-4, 1e6]");
+        assert_eq!(
+            interval.display_compact(4).fixed_range(-5, 7).to_string(),
+            "[0.0001235, 1235000]"
+        );
+        assert_eq!(
+            interval.display_compact(2).fixed_range(0, 0).to_string(),
+            "[1.2e-4, 1.2e6]"
+        );
+        assert_eq!(
+            Interval::new(1.5_f32, 2.5e30)?
+                .display_compact(2)
+                .to_string(),
+            "[1.5, 2.5e30]"
+        );
+
+        // the default display is unchanged, scientific notation is opt-in
+        let interval = Interval::new(1.2e-20, 1.7e23)?;
+        assert_eq!(
+            interval.to_string(),
+            "[0.000000000000000000012, 170000000000000000000000]"
+        );
+        assert_eq!(format!("{interval:e}"), "[1.2e-20, 1.7e23]");
+        assert_eq!(format!("{interval:.3e}"), "[1.200e-20, 1.700e23]");
+        assert_eq!(format!("{:e}", Interval::new_lower(-0.5)), "(<-,-5e-1]");
+        Ok(())
+    }
+
+    // a units newtype that is neither `Copy` nor a float
+    #[derive(Debug, Clone, PartialEq, PartialOrd)]
+    struct Millis(f64);
+
+    impl Add for Millis {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            Millis(self.0 + rhs.0)
+        }
+    }
+    impl Sub for Millis {
+        type Output = Self;
+        fn sub(self, rhs: Self) -> Self {
+            Millis(self.0 - rhs.0)
+        }
+    }
+    impl Mul for Millis {
+        type Output = Self;
+        fn mul(self, rhs: Self) -> Self {
+            Millis(self.0 * rhs.0)
+        }
+    }
+    impl Div for Millis {
+        type Output = Self;
+        fn div(self, rhs: Self) -> Self {
+            Millis(self.0 / rhs.0)
+        }
+    }
+    impl Neg for Millis {
+        type Output = Self;
+        fn neg(self) -> Self {
+            Millis(-self.0)
+        }
+    }
+    impl num_traits::Zero for Millis {
+        fn zero() -> Self {
+            Millis(0.)
+        }
+        fn is_zero(&self) -> bool {
+            self.0 == 0.
+        }
+    }
+    impl Display for Millis {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "{}ms", self.0)
+        }
+    }
+
+    #[test]
+    fn test_try_map() -> Result<(), IntervalError> {
+        let interval = Interval::new(Millis(1500.), Millis(2500.))?;
+        assert_eq!(
+            interval.clone().try_map(|Millis(ms)| ms / 1000.)?,
+            Interval::new(1.5, 2.5)?
+        );
+        assert_eq!(
+            interval.try_map_ref(|ms| -ms.0),
+            Err(IntervalError::InvalidBounds)
+        );
+        assert_eq!(
+            interval.try_map_ref(|ms| ms.0 * f64::NAN),
+            Err(IntervalError::NaNBound)
+        );
+        assert_eq!(
+            interval.clone().try_map_reversed(|Millis(ms)| -ms)?,
+            Interval::new(-2500., -1500.)?
+        );
+        assert_eq!(
+            interval.try_map_reversed(|Millis(ms)| ms),
+            Err(IntervalError::InvalidBounds)
+        );
+
+        // one-sided intervals only check for NaN
+        let upper = Interval::new_upper(4.);
+        assert_eq!(upper.try_map(|x| -x)?, Interval::new_upper(-4.));
+        assert_eq!(upper.try_map_reversed(|x| -x)?, Interval::new_lower(-4.));
+        assert_eq!(
+            Interval::new_lower(-4_f64).try_map_ref(|x| x.sqrt()),
+            Err(IntervalError::NaNBound)
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn test_map_decreasing() {
+        let _ = Interval::new(1., 2.).unwrap().map(|x| -x);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn test_map_reversed_increasing() {
+        let _ = Interval::new(1., 2.).unwrap().map_reversed(|x| x);
+    }
+
+    #[test]
+    fn test_non_copy_newtype() -> Result<(), IntervalError> {
+        let interval = Interval::new(Millis(100.), Millis(300.))?;
+        assert!(Interval::new(Millis(2.), Millis(1.)).is_err());
+        assert!(interval.contains(&Millis(200.)));
+        assert!(!interval.contains(&Millis(301.)));
+        assert_eq!(interval.low(), Some(Millis(100.)));
+        assert_eq!(interval.to_string(), "[100ms, 300ms]");
+        assert_eq!(Interval::new_upper(Millis(5.)).to_string(), "[5ms,->)");
+
+        // scalar arithmetic clones the operand
+        let shift = Millis(50.);
+        assert_eq!(
+            interval.clone() + shift.clone(),
+            Interval::new(Millis(150.), Millis(350.))?
+        );
+        assert_eq!(
+            interval.clone() - shift,
+            Interval::new(Millis(50.), Millis(250.))?
+        );
+        assert_eq!(
+            interval.clone() * Millis(2.),
+            Interval::new(Millis(200.), Millis(600.))?
+        );
+        assert_eq!(
+            interval.clone() * Millis(-1.),
+            Interval::new(Millis(-300.), Millis(-100.))?
+        );
+        assert_eq!(
+            Interval::new_upper(Millis(10.)) / Millis(-2.),
+            Interval::new_lower(Millis(-5.))
+        );
+        assert_eq!(
+            -Interval::new_lower(Millis(10.)),
+            Interval::new_upper(Millis(-10.))
+        );
+
+        // width
+        assert_eq!(interval.width(), Some(Millis(200.)));
+        assert_eq!(interval.width_by(|low, high| high.0 - low.0), Some(200.));
+        assert_eq!(Interval::new_lower(Millis(1.)).width(), None);
+
+        // round trip through f64
+        let seconds = interval.map_ref(|ms| ms.0 / 1000.);
+        assert_eq!(seconds.low_f(), 0.1);
+        assert_eq!(seconds.high_f(), 0.3);
+        assert_eq!(seconds.map(|s| Millis(s * 1000.)), interval);
+        assert_eq!(
+            interval.map_reversed(|ms| Millis(1000. / ms.0)),
+            Interval::new(Millis(1000. / 300.), Millis(10.))?
+        );
+        Ok(())
+    }
 }
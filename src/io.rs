@@ -0,0 +1,563 @@
+//!
+//! Streaming ingestion of a numeric column from CSV or newline-delimited JSON (ndjson) data.
+//!
+//! The data is read row by row and never fully loaded into memory. Rows where the value is
+//! missing are skipped and rows that cannot be parsed are counted as malformed; both are
+//! reported in an [`IngestReport`] rather than aborting the ingestion. Only I/O errors abort it.
+//!
+//! This module requires the `io` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! let data = "\
+//! id,latency
+//! 1,12.5
+//! 2,13.1
+//! 3,
+//! 4,oops
+//! 5,12.9
+//! ";
+//! let (stats, report) = io::arithmetic_from_csv(data.as_bytes(), "latency")?;
+//! assert_eq!(stats.sample_count(), 3);
+//! assert_eq!(report.parsed, 3);
+//! assert_eq!(report.skipped, 1);
+//! assert_eq!(report.malformed, 1);
+//! assert_eq!(report.errors[0].0, 5); // row of "oops" (the header is row 1)
+//!
+//! let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+//! assert!(ci.contains(&12.8));
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+use super::*;
+use error::*;
+use std::io::{BufRead, BufReader, Read};
+
+///
+/// Maximum number of errors retained in [`IngestReport::errors`].
+///
+/// Further malformed rows are still counted in [`IngestReport::malformed`].
+///
+pub const MAX_REPORTED_ERRORS: usize = 100;
+
+///
+/// Summary of the rows read during an ingestion.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = "{\"x\": 1.5}\n{\"x\": null}\n{\"x\": 2.5}\nnot json\n";
+/// let (_, report) = io::arithmetic_from_ndjson(data.as_bytes(), "x")?;
+/// assert_eq!(report.rows(), 4);
+/// assert_eq!(report.to_string(), "parsed: 2, skipped: 1, malformed: 1");
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IngestReport {
+    /// Number of rows whose value was successfully parsed.
+    pub parsed: usize,
+
+    /// Number of rows skipped because the value was missing (empty field or null).
+    pub skipped: usize,
+
+    /// Number of rows that could not be parsed.
+    pub malformed: usize,
+
+    /// Row numbers and messages of the first [`MAX_REPORTED_ERRORS`] malformed rows.
+    pub errors: Vec<(usize, String)>,
+}
+
+impl IngestReport {
+    ///
+    /// Returns the total number of rows read (excluding the CSV header).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn rows(&self) -> usize {
+        self.parsed + self.skipped + self.malformed
+    }
+
+    ///
+    /// Records the outcome of reading one row.
+    ///
+    /// Returns the parsed value if any, or the error if it is fatal (i.e., not a parse error).
+    ///
+    fn record(&mut self, row: CIResult<Option<f64>>) -> CIResult<Option<f64>> {
+        match row {
+            Ok(Some(x)) => {
+                self.parsed += 1;
+                Ok(Some(x))
+            }
+            Ok(None) => {
+                self.skipped += 1;
+                Ok(None)
+            }
+            Err(CIError::ParseError { row, message }) => {
+                self.malformed += 1;
+                if self.errors.len() < MAX_REPORTED_ERRORS {
+                    self.errors.push((row, message));
+                }
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl core::fmt::Display for IngestReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "parsed: {}, skipped: {}, malformed: {}",
+            self.parsed, self.skipped, self.malformed
+        )
+    }
+}
+
+///
+/// Computes the statistics for the arithmetic mean of a column of CSV data.
+///
+/// The first row of the data must be a header naming the columns.
+/// Fields are trimmed of surrounding whitespace; empty fields are skipped, while fields that do
+/// not hold a finite number and rows with an inconsistent number of fields are malformed.
+///
+/// Complexity: \\( O(n) \\) time and \\( O(1) \\) memory, where \\( n \\) is the size of the data
+///
+/// # Arguments
+///
+/// * `reader` - the source of the CSV data
+/// * `column` - the name of the column to read
+///
+/// # Errors
+///
+/// * `UnknownColumn` - if the header does not contain the column
+/// * `ParseError` - if the header cannot be parsed
+/// * `Error` - if an I/O error occurs while reading
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = "a,b\n1,10\n2,20\n3,30\n";
+/// let (stats, report) = io::arithmetic_from_csv(data.as_bytes(), "b")?;
+/// assert_eq!(stats.sample_mean(), 20.);
+/// assert_eq!(report.parsed, 3);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn arithmetic_from_csv<R: Read>(
+    reader: R,
+    column: &str,
+) -> CIResult<(mean::Arithmetic<f64>, IngestReport)> {
+    ingest(csv_column(reader, column)?)
+}
+
+///
+/// Computes the statistics for the arithmetic mean of a field of newline-delimited JSON data.
+///
+/// Each non-blank line must hold a JSON object. A missing or `null` field is skipped, while lines
+/// that are not valid JSON objects or whose field is not a finite number (or a string holding one)
+/// are malformed.
+///
+/// Complexity: \\( O(n) \\) time and \\( O(1) \\) memory, where \\( n \\) is the size of the data
+///
+/// # Arguments
+///
+/// * `reader` - the source of the ndjson data
+/// * `field` - the name of the field to read
+///
+/// # Errors
+///
+/// * `Error` - if an I/O error occurs while reading
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = "{\"x\": 1}\n{\"x\": 2}\n{\"x\": \"3\"}\n";
+/// let (stats, report) = io::arithmetic_from_ndjson(data.as_bytes(), "x")?;
+/// assert_eq!(stats.sample_mean(), 2.);
+/// assert_eq!(report.parsed, 3);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn arithmetic_from_ndjson<R: Read>(
+    reader: R,
+    field: &str,
+) -> CIResult<(mean::Arithmetic<f64>, IngestReport)> {
+    ingest(ndjson_column(reader, field))
+}
+
+///
+/// Creates an iterator over the values of a column of CSV data, for custom accumulation.
+///
+/// Each item corresponds to one row: `Ok(Some(x))` for a value, `Ok(None)` for a missing value,
+/// and `Err(CIError::ParseError { row, message })` for a malformed row, after which the iteration
+/// can continue. Any other error is an I/O error and ends the iteration.
+/// Rows are numbered from 1 by the line on which they start, the header being row 1.
+///
+/// # Arguments
+///
+/// * `reader` - the source of the CSV data
+/// * `column` - the name of the column to read
+///
+/// # Errors
+///
+/// * `UnknownColumn` - if the header does not contain the column
+/// * `ParseError` - if the header cannot be parsed
+/// * `Error` - if an I/O error occurs while reading the header
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = "name,value\nfoo,3\nbar,x\nbaz,5\n";
+/// let mut stats = mean::Geometric::new();
+/// for value in io::csv_column(data.as_bytes(), "value")? {
+///     match value {
+///         Ok(Some(x)) => stats.append(x)?,
+///         Ok(None) => {}
+///         Err(error::CIError::ParseError { row, message: _ }) => assert_eq!(row, 3),
+///         Err(e) => return Err(e),
+///     }
+/// }
+/// assert_eq!(stats.sample_count(), 2);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn csv_column<R: Read>(reader: R, column: &str) -> CIResult<CsvColumn<R>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let index = reader
+        .headers()
+        .map_err(|e| csv_error(e, 1))?
+        .iter()
+        .position(|name| name == column)
+        .ok_or_else(|| CIError::UnknownColumn(column.to_string()))?;
+    Ok(CsvColumn {
+        reader,
+        index,
+        record: csv::StringRecord::new(),
+        done: false,
+    })
+}
+
+///
+/// Creates an iterator over the values of a field of newline-delimited JSON data, for custom accumulation.
+///
+/// Items are as described in [`csv_column`]; blank lines are ignored.
+/// Rows are numbered from 1 by their line.
+///
+/// # Arguments
+///
+/// * `reader` - the source of the ndjson data
+/// * `field` - the name of the field to read
+///
+pub fn ndjson_column<R: Read>(reader: R, field: &str) -> NdjsonColumn<R> {
+    NdjsonColumn {
+        reader: BufReader::new(reader),
+        field: field.to_string(),
+        line: 0,
+        buffer: Vec::new(),
+        done: false,
+    }
+}
+
+///
+/// Iterator over the values of a column of CSV data, created by [`csv_column`].
+///
+#[derive(Debug)]
+pub struct CsvColumn<R: Read> {
+    reader: csv::Reader<R>,
+    index: usize,
+    record: csv::StringRecord,
+    done: bool,
+}
+
+impl<R: Read> Iterator for CsvColumn<R> {
+    type Item = CIResult<Option<f64>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let line = self.reader.position().line() as usize;
+        match self.reader.read_record(&mut self.record) {
+            Ok(false) => {
+                self.done = true;
+                None
+            }
+            Ok(true) => {
+                let row = self
+                    .record
+                    .position()
+                    .map_or(line, |pos| pos.line() as usize);
+                // the number of fields is consistent with the header, which contains the column
+                Some(parse_value(&self.record[self.index], row))
+            }
+            Err(e) => {
+                self.done = e.is_io_error();
+                Some(Err(csv_error(e, line)))
+            }
+        }
+    }
+}
+
+///
+/// Iterator over the values of a field of newline-delimited JSON data, created by [`ndjson_column`].
+///
+#[derive(Debug)]
+pub struct NdjsonColumn<R: Read> {
+    reader: BufReader<R>,
+    field: String,
+    line: usize,
+    buffer: Vec<u8>,
+    done: bool,
+}
+
+impl<R: Read> Iterator for NdjsonColumn<R> {
+    type Item = CIResult<Option<f64>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.buffer.clear();
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    self.line += 1;
+                    if self.buffer.iter().all(u8::is_ascii_whitespace) {
+                        continue;
+                    }
+                    return Some(self.parse_line());
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(CIError::Error(e.to_string())));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<R: Read> NdjsonColumn<R> {
+    fn parse_line(&self) -> CIResult<Option<f64>> {
+        let row = self.line;
+        let value: serde_json::Value =
+            serde_json::from_slice(&self.buffer).map_err(|e| CIError::ParseError {
+                row,
+                message: e.to_string(),
+            })?;
+        let object = value.as_object().ok_or_else(|| CIError::ParseError {
+            row,
+            message: "expected a JSON object".to_string(),
+        })?;
+        match object.get(&self.field) {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(serde_json::Value::Number(x)) => match x.as_f64() {
+                Some(x) if x.is_finite() => Ok(Some(x)),
+                _ => Err(CIError::ParseError {
+                    row,
+                    message: format!("invalid value {}", x),
+                }),
+            },
+            Some(serde_json::Value::String(s)) => parse_value(s, row),
+            Some(other) => Err(CIError::ParseError {
+                row,
+                message: format!("invalid value {}", other),
+            }),
+        }
+    }
+}
+
+///
+/// Accumulates the values of an iterator created by [`csv_column`] or [`ndjson_column`].
+///
+fn ingest<I>(values: I) -> CIResult<(mean::Arithmetic<f64>, IngestReport)>
+where
+    I: Iterator<Item = CIResult<Option<f64>>>,
+{
+    let mut stats = mean::Arithmetic::new();
+    let mut report = IngestReport::default();
+    for value in values {
+        if let Some(x) = report.record(value)? {
+            stats.append(x)?;
+        }
+    }
+    Ok((stats, report))
+}
+
+///
+/// Parses a (trimmed) field as a finite number; an empty field is a missing value.
+///
+fn parse_value(field: &str, row: usize) -> CIResult<Option<f64>> {
+    if field.is_empty() {
+        return Ok(None);
+    }
+    match field.parse::<f64>() {
+        Ok(x) if x.is_finite() => Ok(Some(x)),
+        _ => Err(CIError::ParseError {
+            row,
+            message: format!("invalid value {:?}", field),
+        }),
+    }
+}
+
+///
+/// Converts a CSV error into a fatal `Error` (I/O) or a `ParseError` at the given row.
+///
+fn csv_error(e: csv::Error, row: usize) -> CIError {
+    if e.is_io_error() {
+        return CIError::Error(e.to_string());
+    }
+    let row = e.position().map_or(row, |pos| pos.line() as usize);
+    CIError::ParseError {
+        row,
+        message: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    const CSV: &str = "\
+id, name, value
+1, a, 1.5
+2, b, 2.5
+3, c,
+4, d, abc
+5, e, NaN
+6, f
+7, g, 3.5
+";
+
+    #[test]
+    fn test_csv() -> CIResult<()> {
+        let (stats, report) = arithmetic_from_csv(CSV.as_bytes(), "value")?;
+        assert_eq!(stats.sample_count(), 3);
+        assert_abs_diff_eq!(stats.sample_mean(), 2.5);
+        assert_eq!(report.parsed, 3);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.malformed, 3);
+        assert_eq!(report.rows(), 7);
+        let rows: Vec<_> = report.errors.iter().map(|(row, _)| *row).collect();
+        assert_eq!(rows, [5, 6, 7]);
+
+        let (stats, report) = arithmetic_from_csv(CSV.as_bytes(), "id")?;
+        assert_eq!(stats.sample_count(), 6);
+        assert_eq!(report.malformed, 1);
+
+        assert!(matches!(
+            arithmetic_from_csv(CSV.as_bytes(), "missing"),
+            Err(CIError::UnknownColumn(_))
+        ));
+        let (stats, report) = arithmetic_from_csv("value\n".as_bytes(), "value")?;
+        assert_eq!(stats.sample_count(), 0);
+        assert_eq!(report, IngestReport::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_quoted_rows() -> CIResult<()> {
+        // a quoted field spanning two lines shifts the row numbers of the following rows
+        let data = "note,value\n\"multi\nline\",1\nok,x\n";
+        let values: Vec<_> = csv_column(data.as_bytes(), "value")?.collect();
+        assert!(matches!(values[0], Ok(Some(x)) if x == 1.));
+        assert!(matches!(
+            values[1],
+            Err(CIError::ParseError { row: 4, message: _ })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_invalid_utf8() -> CIResult<()> {
+        let data = b"value\n1\n\xff\xfe\n2\n";
+        let (stats, report) = arithmetic_from_csv(&data[..], "value")?;
+        assert_eq!(stats.sample_count(), 2);
+        assert_eq!(report.malformed, 1);
+        assert_eq!(report.errors[0].0, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ndjson() -> CIResult<()> {
+        let data = "\
+{\"value\": 1.5, \"name\": \"a\"}
+{\"value\": 2.5}
+
+{\"name\": \"c\"}
+{\"value\": null}
+{\"value\": \"3.5\"}
+{\"value\": [1]}
+[1, 2]
+{\"value\": 4
+";
+        let (stats, report) = arithmetic_from_ndjson(data.as_bytes(), "value")?;
+        assert_eq!(stats.sample_count(), 3);
+        assert_abs_diff_eq!(stats.sample_mean(), 2.5);
+        assert_eq!(report.parsed, 3);
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.malformed, 3);
+        let rows: Vec<_> = report.errors.iter().map(|(row, _)| *row).collect();
+        assert_eq!(rows, [7, 8, 9]);
+        Ok(())
+    }
+
+    struct FailingReader<'a> {
+        data: &'a [u8],
+    }
+
+    impl Read for FailingReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.data.is_empty() {
+                return Err(std::io::Error::other("failure"));
+            }
+            self.data.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_io_error() {
+        let reader = FailingReader {
+            data: b"value\n1\n2\n",
+        };
+        assert!(matches!(
+            arithmetic_from_csv(reader, "value"),
+            Err(CIError::Error(_))
+        ));
+        let reader = FailingReader {
+            data: b"{\"value\": 1}\n",
+        };
+        assert!(matches!(
+            arithmetic_from_ndjson(reader, "value"),
+            Err(CIError::Error(_))
+        ));
+
+        let reader = FailingReader {
+            data: b"value\n1\n",
+        };
+        let values: Vec<_> = csv_column(reader, "value").unwrap().collect();
+        assert_eq!(values.len(), 2);
+        assert!(matches!(values[1], Err(CIError::Error(_))));
+    }
+
+    #[test]
+    fn test_reported_errors_capped() -> CIResult<()> {
+        let data: String = core::iter::once("value\n".to_string())
+            .chain((0..2 * MAX_REPORTED_ERRORS).map(|_| "x\n".to_string()))
+            .collect();
+        let (_, report) = arithmetic_from_csv(data.as_bytes(), "value")?;
+        assert_eq!(report.malformed, 2 * MAX_REPORTED_ERRORS);
+        assert_eq!(report.errors.len(), MAX_REPORTED_ERRORS);
+        Ok(())
+    }
+}
@@ -5,25 +5,49 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::private_intra_doc_links)]
 #![warn(missing_docs)]
+#![doc(test(attr(deny(unused_must_use))))]
 #![cfg_attr(all(not(test), not(feature = "std")), no_std)]
 
+#[cfg(feature = "std")]
+pub mod analysis;
 pub mod comparison;
 pub mod error;
+#[cfg(feature = "io")]
+pub mod io;
 pub mod mean;
+#[cfg(feature = "persist")]
+pub mod persist;
+pub mod prelude;
 pub mod proportion;
 pub mod quantile;
+pub mod rate;
+pub mod report;
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod testing;
 
 pub mod utils;
 
+#[cfg(any(test, feature = "proptest"))]
+mod arbitrary;
 mod confidence;
 mod interval;
-mod stats;
+#[cfg(any(test, not(feature = "statrs"), feature = "internal-quantiles"))]
+mod special;
 
 pub use confidence::Confidence;
 pub use error::CIResult;
+pub use interval::ByBounds;
+pub use interval::CompactDisplay;
 pub use interval::Interval;
+pub use interval::IntervalSet;
+pub use interval::RelativeInterval;
+pub use interval::Significance;
 pub use mean::MeanCI;
 pub use mean::StatisticsOps;
+pub use proportion::Stats as ProportionStats;
+pub use quantile::Stats as QuantileStats;
+pub use utils::Accumulator;
 
 #[cfg(test)]
 mod tests {
@@ -56,4 +80,620 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_interval_kind_matches_confidence() -> CIResult<()> {
+        fn check<T: PartialOrd + core::fmt::Debug>(
+            name: &str,
+            confidence: Confidence,
+            interval: Interval<T>,
+        ) {
+            let consistent = match confidence {
+                Confidence::TwoSided(_) => interval.is_two_sided(),
+                Confidence::UpperOneSided(_) => interval.is_upper(),
+                Confidence::LowerOneSided(_) => interval.is_lower(),
+            };
+            assert!(consistent, "{name}: {interval:?} for {confidence:?}");
+        }
+
+        let data_a = [
+            10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4,
+        ];
+        let data_b = [
+            10.7, 0.4, 10.1, 4.5, 7.1, 4.3, 37.4, 0.9, 10.1, 12.6, 21.7, 21.9, 2.0, 8.4, 9.3,
+        ];
+        let stats_a = mean::Arithmetic::from_iter(&data_a)?;
+        let stats_b = mean::Arithmetic::from_iter(&data_b)?;
+        let int_data = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9, 3, 2, 3, 8, 4];
+        let weighted = [(1, 3_u64), (2, 5), (3, 8), (4, 2), (5, 4)];
+
+        for confidence in [
+            Confidence::new_two_sided(0.9),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.9),
+        ] {
+            check("Arithmetic", confidence, stats_a.ci_mean(confidence)?);
+            check(
+                "Arithmetic (std. dev.)",
+                confidence,
+                stats_a.ci_std_dev(confidence)?,
+            );
+            check(
+                "Geometric",
+                confidence,
+                mean::Geometric::ci(confidence, &data_a)?,
+            );
+            check(
+                "Harmonic",
+                confidence,
+                mean::Harmonic::ci(confidence, &data_a)?,
+            );
+            check(
+                "sum_ci",
+                confidence,
+                mean::sum_ci(confidence, &stats_a, &stats_b)?,
+            );
+            check(
+                "diff_ci",
+                confidence,
+                mean::diff_ci(confidence, &stats_a, &stats_b)?,
+            );
+            check(
+                "Paired",
+                confidence,
+                comparison::Paired::ci(confidence, &data_a, &data_b)?,
+            );
+            check(
+                "Unpaired",
+                confidence,
+                comparison::Unpaired::ci(confidence, &data_a, &data_b)?,
+            );
+            check(
+                "quantile_diff_ci",
+                confidence,
+                comparison::quantile_diff_ci(confidence, &data_a, &data_b, 0.5)?,
+            );
+            check(
+                "proportion::ci",
+                confidence,
+                proportion::ci(confidence, 100, 40)?,
+            );
+            check(
+                "proportion::ci_z_normal",
+                confidence,
+                proportion::ci_z_normal(confidence, 100, 40)?,
+            );
+            check("rate::ci", confidence, rate::ci(confidence, 10, 2.)?);
+            check(
+                "quantile::ci",
+                confidence,
+                quantile::ci(confidence, &int_data, 0.5)?,
+            );
+            check(
+                "quantile::ci_max_size",
+                confidence,
+                quantile::ci_max_size::<_, _, 32>(confidence, &int_data, 0.5)?,
+            );
+            check(
+                "quantile::ci_weighted",
+                confidence,
+                quantile::ci_weighted(confidence, &weighted, 0.5)?,
+            );
+            check(
+                "quantile::Stats",
+                confidence,
+                quantile::Stats::new(int_data.len()).ci(confidence, 0.5)?,
+            );
+        }
+        Ok(())
+    }
+
+    // every public entry point computing an interval from data reports empty input the same way;
+    // new entry points should be added to the list below
+    #[test]
+    fn test_empty_input() {
+        let confidence = Confidence::new_two_sided(0.95);
+        let empty: [f64; 0] = [];
+        let empty_f32: [f32; 0] = [];
+        let empty_bool: [bool; 0] = [];
+        let empty_weighted: [(f64, u64); 0] = [];
+
+        let results: [(&str, CIResult<()>); 56] = [
+            (
+                "mean::Arithmetic::ci",
+                mean::Arithmetic::ci(confidence, &empty).map(|_| ()),
+            ),
+            (
+                "mean::ArithmeticF32::ci",
+                mean::ArithmeticF32::ci(confidence, &empty_f32).map(|_| ()),
+            ),
+            (
+                "mean::Harmonic::ci",
+                mean::Harmonic::ci(confidence, &empty).map(|_| ()),
+            ),
+            (
+                "mean::Geometric::ci",
+                mean::Geometric::ci(confidence, &empty).map(|_| ()),
+            ),
+            (
+                "mean::Arithmetic::ci_mean",
+                mean::Arithmetic::<f64>::new()
+                    .ci_mean(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "mean::ArithmeticF32::ci_mean",
+                mean::ArithmeticF32::new().ci_mean(confidence).map(|_| ()),
+            ),
+            (
+                "mean::Harmonic::ci_mean",
+                mean::Harmonic::<f64>::new().ci_mean(confidence).map(|_| ()),
+            ),
+            (
+                "mean::Geometric::ci_mean",
+                mean::Geometric::<f64>::new()
+                    .ci_mean(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "mean::Arithmetic::ci_std_dev",
+                mean::Arithmetic::<f64>::new()
+                    .ci_std_dev(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "mean::Arithmetic::ci_cv",
+                mean::Arithmetic::<f64>::new().ci_cv(confidence).map(|_| ()),
+            ),
+            (
+                "mean::Combined::ci_arithmetic",
+                mean::Combined::<f64>::new()
+                    .ci_arithmetic(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "mean::Combined::ci_geometric",
+                mean::Combined::<f64>::new()
+                    .ci_geometric(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "mean::Combined::ci_harmonic",
+                mean::Combined::<f64>::new()
+                    .ci_harmonic(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "mean::Arithmetic::ci_of_fn",
+                mean::Arithmetic::<f64>::new()
+                    .ci_of_fn(confidence, |x| x, |_| 1.)
+                    .map(|_| ()),
+            ),
+            (
+                "mean::Trimmed::ci_mean",
+                mean::Trimmed::from_iter_with(&empty, 0.1)
+                    .and_then(|stats| stats.ci_mean(confidence))
+                    .map(|_| ()),
+            ),
+            (
+                "mean::ci_bootstrap_t",
+                mean::ci_bootstrap_t(confidence, &empty, 1000, &mut rand::thread_rng()).map(|_| ()),
+            ),
+            (
+                "proportion::ci_true",
+                proportion::ci_true(confidence, &empty_bool).map(|_| ()),
+            ),
+            (
+                "proportion::ci_if",
+                proportion::ci_if(confidence, &empty, |&x| x > 0.).map(|_| ()),
+            ),
+            (
+                "proportion::ci",
+                proportion::ci(confidence, 0, 0).map(|_| ()),
+            ),
+            (
+                "proportion::ci_wilson",
+                proportion::ci_wilson(confidence, 0, 0).map(|_| ()),
+            ),
+            (
+                "proportion::ci_z_normal",
+                proportion::ci_z_normal(confidence, 0, 0).map(|_| ()),
+            ),
+            (
+                "proportion::ci_agresti_coull",
+                proportion::ci_agresti_coull(confidence, 0, 0).map(|_| ()),
+            ),
+            (
+                "proportion::ci_arcsine",
+                proportion::ci_arcsine(confidence, 0, 0).map(|_| ()),
+            ),
+            (
+                "proportion::ci_arcsine_transformed",
+                proportion::ci_arcsine_transformed(confidence, 0, 0).map(|_| ()),
+            ),
+            (
+                "proportion::Stats::ci",
+                proportion::Stats::default().ci(confidence).map(|_| ()),
+            ),
+            (
+                "proportion::MultinomialStats::ci",
+                proportion::MultinomialStats::new(3)
+                    .ci(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "proportion::MultinomialStats::ci_unadjusted",
+                proportion::MultinomialStats::new(3)
+                    .ci_unadjusted(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "proportion::Stats::ci_with_design_effect",
+                proportion::Stats::default()
+                    .ci_with_design_effect(confidence, 2.)
+                    .map(|_| ()),
+            ),
+            (
+                "quantile::ci",
+                quantile::ci(confidence, &empty, 0.5).map(|_| ()),
+            ),
+            (
+                "quantile::ci_sorted_unchecked",
+                quantile::ci_sorted_unchecked(confidence, &empty, 0.5).map(|_| ()),
+            ),
+            (
+                "quantile::ci_many",
+                quantile::ci_many(confidence, &empty, &[0.5]).map(|_| ()),
+            ),
+            (
+                "quantile::ci_many_sorted_unchecked",
+                quantile::ci_many_sorted_unchecked(confidence, &empty, &[0.5]).map(|_| ()),
+            ),
+            (
+                "quantile::ci_of_sorted_iter",
+                quantile::ci_of_sorted_iter(confidence, empty, 0, 0.5).map(|_| ()),
+            ),
+            (
+                "quantile::ci_weighted",
+                quantile::ci_weighted(confidence, &empty_weighted, 0.5).map(|_| ()),
+            ),
+            (
+                "quantile::ci_max_size",
+                quantile::ci_max_size::<f64, _, 8>(confidence, &empty, 0.5).map(|_| ()),
+            ),
+            (
+                "quantile::ci_indices",
+                quantile::ci_indices(confidence, 0, 0.5).map(|_| ()),
+            ),
+            (
+                "quantile::Stats::ci",
+                quantile::Stats::new(0).ci(confidence, 0.5).map(|_| ()),
+            ),
+            (
+                "quantile::MergeableSketch::ci",
+                quantile::MergeableSketch::<f64>::exact()
+                    .ci(confidence, 0.5)
+                    .map(|_| ()),
+            ),
+            (
+                "mean::Arithmetic::measurement_ci",
+                mean::Arithmetic::<f64>::new()
+                    .measurement_ci(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "proportion::ci_wilson_ratio",
+                proportion::ci_wilson_ratio(confidence, 0, 0.5).map(|_| ()),
+            ),
+            (
+                "comparison::Paired::ci_mean",
+                comparison::Paired::<f64>::default()
+                    .ci_mean(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "comparison::Unpaired::ci_mean",
+                comparison::Unpaired::<f64>::default()
+                    .ci_mean(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "comparison::Unpaired::effective_dof",
+                comparison::Unpaired::<f64>::default()
+                    .effective_dof()
+                    .map(|_| ()),
+            ),
+            (
+                "comparison::Unpaired::ci_variance_ratio",
+                comparison::Unpaired::<f64>::default()
+                    .ci_variance_ratio(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "comparison::Paired::ci",
+                comparison::Paired::ci(confidence, &empty, &empty).map(|_| ()),
+            ),
+            (
+                "comparison::shift_ci",
+                comparison::shift_ci(confidence, &empty, &empty).map(|_| ()),
+            ),
+            (
+                "comparison::PairedRatio::ci",
+                comparison::PairedRatio::ci(confidence, &empty, &empty).map(|_| ()),
+            ),
+            (
+                "comparison::paired_median_diff_ci",
+                comparison::paired_median_diff_ci::<f64>(
+                    confidence,
+                    &[],
+                    1000,
+                    &mut rand::thread_rng(),
+                )
+                .map(|_| ()),
+            ),
+            (
+                "comparison::Unpaired::ci",
+                comparison::Unpaired::ci(confidence, &empty, &empty).map(|_| ()),
+            ),
+            (
+                "comparison::StreamingUnpaired::ci_mean_overall",
+                comparison::StreamingUnpaired::<f64>::default()
+                    .ci_mean_overall(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "comparison::PairedRobust::ci_mean",
+                comparison::PairedRobust::<f64>::default()
+                    .ci_mean(confidence)
+                    .map(|_| ()),
+            ),
+            (
+                "comparison::PairedRobust::ci_mean_excluding_outliers",
+                comparison::PairedRobust::<f64>::default()
+                    .ci_mean_excluding_outliers(confidence, 1.5)
+                    .map(|_| ()),
+            ),
+            (
+                "mean::Arithmetic::p_value_vs",
+                mean::Arithmetic::<f64>::new().p_value_vs(0.).map(|_| ()),
+            ),
+            (
+                "proportion::Stats::p_value_vs",
+                proportion::Stats::default().p_value_vs(0.5).map(|_| ()),
+            ),
+            (
+                "comparison::Paired::p_value",
+                comparison::Paired::<f64>::default().p_value().map(|_| ()),
+            ),
+            (
+                "comparison::Unpaired::p_value",
+                comparison::Unpaired::<f64>::default().p_value().map(|_| ()),
+            ),
+        ];
+        for (name, result) in results {
+            assert!(
+                matches!(result, Err(error::CIError::TooFewSamples(0))),
+                "{name}: {result:?}"
+            );
+        }
+    }
+
+    // p < α iff the (1 - α) interval excludes the hypothesized value, for two-sided and one-sided
+    // p-values alike
+    #[test]
+    fn test_p_value_duality() -> CIResult<()> {
+        use comparison::Direction;
+        use stats::Alternative;
+
+        let alphas = [0.001, 0.01, 0.05, 0.1, 0.2, 0.5, 0.9];
+        // (p-value, confidence, interval, hypothesized value)
+        let mut cases: Vec<(f64, Confidence, Interval<f64>, f64)> = Vec::new();
+
+        let datasets: [&[f64]; 5] = [
+            &[1., 2.],
+            &[3.2, 4.1, 2.7, 3.9],
+            &[1., 2., 3., 4., 5., 6., 7., 8., 9., 10.],
+            &[
+                10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3,
+            ],
+            &[
+                -0.5, 0.25, 0.1, -0.3, 0.4, 0.05, -0.1, 0.2, 0.0, -0.2, 0.3, 0.15,
+            ],
+        ];
+        for data in datasets {
+            let stats = mean::Arithmetic::from_iter(data)?;
+            let mean = stats.sample_mean();
+            let spread = 3. * stats.sample_std_dev();
+            for step in -20..=20 {
+                let h = mean + spread * step as f64 / 20.;
+                for alpha in alphas {
+                    let confidence = Confidence::new_two_sided(1. - alpha);
+                    cases.push((
+                        stats.p_value_vs(h)?,
+                        confidence,
+                        stats.ci_mean(confidence)?,
+                        h,
+                    ));
+                    for (alternative, confidence) in [
+                        (Alternative::Greater, Confidence::new_upper(1. - alpha)),
+                        (Alternative::Less, Confidence::new_lower(1. - alpha)),
+                    ] {
+                        let p = stats.p_value_one_sided_vs(h, alternative)?;
+                        cases.push((p, confidence, stats.ci_mean(confidence)?, h));
+                    }
+                }
+            }
+        }
+
+        let pairs: [(&[f64], &[f64]); 4] = [
+            (&[1., 2., 3., 5.], &[4., 5., 6., 7.]),
+            (&[1., 2., 3., 4.], &[1.5, 1.8, 3.5, 4.2]),
+            (&[10., 12., 9., 11., 13.], &[9.5, 12.5, 8., 11.5, 12.]),
+            (
+                &[0.1, 0.4, 0.3, 0.2, 0.6, 0.5],
+                &[0.3, 0.2, 0.5, 0.1, 0.4, 0.7],
+            ),
+        ];
+        for (data_a, data_b) in pairs {
+            let paired =
+                comparison::Paired::from_pairs(data_a.iter().copied().zip(data_b.iter().copied()))?;
+            let unpaired = comparison::Unpaired::from_iter(data_a, data_b)?;
+            for alpha in alphas {
+                let confidence = Confidence::new_two_sided(1. - alpha);
+                cases.push((
+                    paired.p_value()?,
+                    confidence,
+                    paired.ci_mean(confidence)?,
+                    0.,
+                ));
+                cases.push((
+                    unpaired.p_value()?,
+                    confidence,
+                    unpaired.ci_mean(confidence)?,
+                    0.,
+                ));
+                for (direction, confidence) in [
+                    (Direction::FirstLarger, Confidence::new_upper(1. - alpha)),
+                    (Direction::FirstSmaller, Confidence::new_lower(1. - alpha)),
+                ] {
+                    let p = paired.p_value_one_sided(direction)?;
+                    cases.push((p, confidence, paired.ci_mean(confidence)?, 0.));
+                    let p = unpaired.p_value_one_sided(direction)?;
+                    cases.push((p, confidence, unpaired.ci_mean(confidence)?, 0.));
+                }
+            }
+        }
+
+        for (population, successes) in [(10, 2), (20, 9), (50, 45), (200, 37), (1_000, 500)] {
+            let stats = proportion::Stats::new(population, successes);
+            for step in 1..100 {
+                let h = step as f64 / 100.;
+                for alpha in alphas {
+                    let confidence = Confidence::new_two_sided(1. - alpha);
+                    cases.push((stats.p_value_vs(h)?, confidence, stats.ci(confidence)?, h));
+                    for (alternative, confidence) in [
+                        (Alternative::Greater, Confidence::new_upper(1. - alpha)),
+                        (Alternative::Less, Confidence::new_lower(1. - alpha)),
+                    ] {
+                        let p = stats.p_value_one_sided_vs(h, alternative)?;
+                        cases.push((p, confidence, stats.ci(confidence)?, h));
+                    }
+                }
+            }
+        }
+
+        let mut rejected = 0;
+        for (p, confidence, interval, h) in &cases {
+            assert!((0. ..=1.).contains(p), "{p}");
+            let alpha = 1. - confidence.level();
+            if (p - alpha).abs() < 1e-9 {
+                // too close to the boundary to be decided reliably
+                continue;
+            }
+            assert_eq!(
+                *p < alpha,
+                !interval.contains(h),
+                "p = {p}, {confidence}: {interval} vs {h}"
+            );
+            rejected += usize::from(*p < alpha);
+        }
+        // both outcomes are exercised
+        assert!(rejected > cases.len() / 10 && rejected < cases.len() * 9 / 10);
+        Ok(())
+    }
+
+    // every accumulator takes borrowed collections with `extend` and owned iterators with
+    // `extend_iter`; the same kinds of collections must be accepted by all of them
+    #[test]
+    fn test_extend_forms() -> CIResult<()> {
+        macro_rules! check_single {
+            ($stats:ty, $float:ty) => {{
+                let vector: Vec<$float> = vec![1., 2., 3.];
+                let array: [$float; 2] = [4., 5.];
+                let slice: &[$float] = &[6., 7.];
+                let mut stats = <$stats>::default();
+                stats.extend(&vector)?;
+                stats.extend(&array)?;
+                stats.extend(slice)?;
+                stats.extend_iter(vector.clone())?;
+                stats.extend_iter(array)?;
+                stats.extend_iter(slice.iter().copied())?;
+                stats.extend_iter(vector.iter().map(|x| x + 1.))?;
+                assert_eq!(stats.sample_count(), 17, stringify!($stats));
+                assert_eq!(<$stats>::from_iter(slice)?.sample_count(), 2);
+            }};
+        }
+        check_single!(mean::Arithmetic<f64>, f64);
+        check_single!(mean::Arithmetic<f32>, f32);
+        check_single!(mean::ArithmeticF32, f32);
+        check_single!(mean::Harmonic<f64>, f64);
+        check_single!(mean::Geometric<f64>, f64);
+        check_single!(mean::Combined<f64>, f64);
+
+        macro_rules! check_paired {
+            ($stats:ty) => {{
+                let vector: Vec<f64> = vec![1., 2., 3.];
+                let array = [4., 5.];
+                let slice: &[f64] = &[6., 7.];
+                let mut stats = <$stats>::default();
+                stats.extend(&vector, &vector)?;
+                stats.extend(&array, &array)?;
+                stats.extend(slice, slice)?;
+                stats.extend_iter(vector.clone(), vector.clone())?;
+                stats.extend_iter(array, array)?;
+                stats.extend_iter(slice.iter().copied(), slice.iter().copied())?;
+                stats.extend_iter(vector.iter().map(|x| x + 1.), vector.iter().map(|x| x * 2.))?;
+                assert_eq!(stats.sample_count(), 17, stringify!($stats));
+            }};
+        }
+        check_paired!(comparison::Paired<f64>);
+        check_paired!(comparison::PairedRatio<f64>);
+        check_paired!(comparison::PairedRobust<f64>);
+
+        let vector: Vec<f64> = vec![1., 2., 3.];
+        let array = [4., 5.];
+        let slice: &[f64] = &[6., 7.];
+        let mut stats = comparison::Unpaired::default();
+        stats.extend(&vector, &array)?;
+        stats.extend(slice, &vector)?;
+        stats.extend_iter(vector.clone(), array)?;
+        stats.extend_iter(slice.iter().copied(), vector.iter().map(|x| x + 1.))?;
+        assert_eq!(stats.stats_a().sample_count(), 10);
+        assert_eq!(stats.stats_b().sample_count(), 10);
+        assert_eq!(
+            comparison::Unpaired::from_iter(slice, &array)?
+                .stats_b()
+                .sample_count(),
+            2
+        );
+
+        let bools = vec![true, false, true];
+        let bool_array = [false, false];
+        let bool_slice: &[bool] = &[true, true];
+        let mut stats = proportion::Stats::default();
+        stats.extend(&bools);
+        stats.extend(&bool_array);
+        stats.extend(bool_slice);
+        stats.extend_iter(bools.clone());
+        stats.extend_iter(bool_array);
+        stats.extend_iter(bool_slice.iter().copied());
+        stats.extend_iter(vector.iter().map(|&x| x > 2.));
+        assert_eq!(stats, proportion::Stats::new(17, 9));
+
+        let mut tracker = quantile::SloTracker::new(3., 0.9)?;
+        tracker.extend_iter(vector.clone());
+        tracker.extend_iter(array);
+        tracker.extend_iter(slice.iter().copied());
+        tracker.extend_iter(vector.iter().map(|x| x + 1.));
+        assert_eq!(tracker.population(), 10);
+        assert_eq!(tracker.within_threshold(), 5);
+
+        let mut sketch = quantile::MergeableSketch::exact();
+        sketch.extend_iter(vector.clone());
+        sketch.extend_iter(array);
+        sketch.extend_iter(slice.iter().copied());
+        sketch.extend_iter(vector.iter().map(|x| x + 1.));
+        assert_eq!(sketch.len(), 10);
+        Ok(())
+    }
 }
@@ -89,6 +89,9 @@
 //! # Ok::<(),error::CIError>(())
 //! ```
 //!
+//! The structure [`Combined`] collects the states of the three means in a single pass, so that
+//! the interpretation can be chosen after the data has been ingested.
+//!
 use super::*;
 use crate::utils;
 
@@ -101,6 +104,7 @@ use num_traits::Float;
 /// - [`mean::Arithmetic`] for arithmetic calculations
 /// - [`mean::Geometric`] for geometric calculations (logarithmic space)
 /// - [`mean::Harmonic`] for harmonic calculations (reciprocal space)
+/// - [`mean::ArithmeticF32`] for arithmetic calculations on `f32` samples, accumulated in `f64`
 ///
 /// # Example
 /// ```
@@ -109,13 +113,79 @@ use num_traits::Float;
 /// let stats = mean::Arithmetic::from_iter(&data)?;
 /// assert_eq!(stats.sample_count(), 10);
 /// assert_eq!(stats.sample_mean(), 5.5);
-/// assert_abs_diff_eq!(stats.sample_sem(), 1.0092, epsilon = 1e-4);
+/// assert_abs_diff_eq!(stats.sample_sem(), 0.9574, epsilon = 1e-4);
 /// let confidence = Confidence::new_two_sided(0.95);
 /// let ci = stats.ci_mean(confidence)?;
 /// # use approx::*;
 /// assert_abs_diff_eq!(ci, Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
 /// # Ok::<(),error::CIError>(())
 /// ```
+///
+/// # Implementing the trait
+///
+/// Statistics types defined outside the crate integrate with the rest of the crate (e.g.,
+/// `report::Record::from_stats` with the `export` feature) by implementing the required methods
+/// ([`Self::append`], [`Self::sample_mean`], [`Self::sample_sem`], [`Self::sample_count`], and
+/// [`Self::ci_mean`]); the others are derived from them. Implementations are expected to uphold
+/// the following:
+///
+/// * [`Default::default`] is the state without any sample.
+/// * [`Self::append`] either records the sample or returns an error and leaves the state
+///   unchanged.
+/// * [`Self::ci_mean`] returns [`CIError::TooFewSamples`] when there are too few samples.
+/// * If the type implements [`core::ops::Add`] to merge states, as the types of the crate do,
+///   then `a + b` is the state obtained by appending the samples of `a` and then those of `b`
+///   (up to rounding), and the empty state is the identity. This is what makes the states of
+///   partitions of the data (e.g., computed in parallel) mergeable.
+///
+/// ```
+/// use stats_ci::*;
+/// // mean of durations, which must not be negative
+/// #[derive(Debug, Default, Clone, Copy)]
+/// struct Durations(mean::Arithmetic<f64>);
+///
+/// impl StatisticsOps<f64> for Durations {
+///     fn append(&mut self, x: f64) -> error::CIResult<()> {
+///         if x < 0. {
+///             // the state is left unchanged
+///             return Err(error::CIError::InvalidInputData);
+///         }
+///         self.0.append(x)
+///     }
+///     fn sample_mean(&self) -> f64 {
+///         self.0.sample_mean()
+///     }
+///     fn sample_sem(&self) -> f64 {
+///         self.0.sample_sem()
+///     }
+///     fn sample_count(&self) -> usize {
+///         self.0.sample_count()
+///     }
+///     fn ci_mean(&self, confidence: Confidence) -> error::CIResult<Interval<f64>> {
+///         // the mean of non-negative values is non-negative
+///         let ci = self.0.ci_mean(confidence)?;
+///         Ok(ci.map(|x| x.max(0.)))
+///     }
+/// }
+///
+/// impl core::ops::Add for Durations {
+///     type Output = Self;
+///     fn add(self, rhs: Self) -> Self {
+///         Durations(self.0 + rhs.0)
+///     }
+/// }
+///
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let stats = Durations::from_iter(&[1., 2., 3., 4.])? + Durations::from_iter(&[5., 6.])?;
+/// assert_eq!(stats.sample_count(), 6);
+/// assert_eq!(stats.ci_mean(confidence)?, Durations::ci_from_data(confidence, &[1., 2., 3., 4., 5., 6.])?);
+///
+/// let mut stats = Durations::default();
+/// assert!(stats.extend(&[1., -2., 3.]).is_err());
+/// assert_eq!(stats.sample_count(), 1);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
 pub trait StatisticsOps<F: Float>: Default {
     ///
     /// Create a new state and "populates" it with data from an iterator
@@ -138,7 +208,7 @@ pub trait StatisticsOps<F: Float>: Default {
     /// let stats = mean::Arithmetic::from_iter(&data)?;
     /// assert_eq!(stats.sample_count(), 10);
     /// assert_eq!(stats.sample_mean(), 5.5);
-    /// assert_abs_diff_eq!(stats.sample_sem(), 1.0092, epsilon = 1e-4);
+    /// assert_abs_diff_eq!(stats.sample_sem(), 0.9574, epsilon = 1e-4);
     /// # Ok::<(),error::CIError>(())
     /// ```
     ///
@@ -152,7 +222,7 @@ pub trait StatisticsOps<F: Float>: Default {
     /// # Ok::<(),error::CIError>(())
     /// ```
     ///
-    fn from_iter<I>(data: &I) -> CIResult<Self>
+    fn from_iter<I: ?Sized>(data: &I) -> CIResult<Self>
     where
         for<'a> &'a I: IntoIterator<Item = &'a F>,
     {
@@ -182,6 +252,36 @@ pub trait StatisticsOps<F: Float>: Default {
     ///
     fn sample_count(&self) -> usize;
 
+    ///
+    /// Degrees of freedom of the interval on the mean, i.e., the number of samples minus one
+    /// (or zero if there are no samples).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let stats = mean::Geometric::from_iter(&[1., 2., 4., 8.])?;
+    /// assert_eq!(stats.degrees_of_freedom(), 3.);
+    /// assert!(!stats.uses_normal_approximation());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    fn degrees_of_freedom(&self) -> f64 {
+        self.sample_count().saturating_sub(1) as f64
+    }
+
+    ///
+    /// Whether the interval on the mean is computed with the normal distribution rather than
+    /// Student's t distribution, which depends on the degrees of freedom
+    /// (see [`stats::uses_normal_approximation`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    fn uses_normal_approximation(&self) -> bool {
+        stats::uses_normal_approximation(self.degrees_of_freedom())
+    }
+
     ///
     /// Confidence interval of the sample mean
     ///
@@ -194,6 +294,10 @@ pub trait StatisticsOps<F: Float>: Default {
     ///
     /// Complexity: \\( O(1) \\)
     ///
+    /// Implementations must leave the state unchanged when they return an error, as
+    /// [`Self::extend`] and [`Self::try_extend_partial`] rely on it.
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
     fn append(&mut self, x: F) -> CIResult<()>;
 
     ///
@@ -205,7 +309,7 @@ pub trait StatisticsOps<F: Float>: Default {
     ///
     /// # Arguments
     ///
-    /// * `data` - The data to append as an array or an iterator
+    /// * `data` - The data to append, borrowed from a collection such as an array, a slice, or a vector
     ///
     /// # Output
     ///
@@ -214,19 +318,77 @@ pub trait StatisticsOps<F: Float>: Default {
     /// # Errors
     ///
     /// * [`CIError::NonPositiveValue`] - If the input data is invalid (for harmonic/geometric means).
+    ///   The values before the first invalid one are appended and the following ones are not, so the
+    ///   number of values consumed is the increase of [`Self::sample_count`]
+    ///   (see also [`Self::try_extend_partial`]).
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let vector = vec![1., 2., 3.];
+    /// let slice: &[f64] = &[4., 5.];
+    /// let mut stats = mean::Arithmetic::new();
+    /// stats.extend(&vector)?;
+    /// stats.extend(slice)?;
+    /// stats.extend(&[6., 7.])?;
+    /// assert_eq!(stats.sample_count(), 7);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
     ///
-    fn extend<I>(&mut self, data: &I) -> CIResult<()>
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    fn extend<I: ?Sized>(&mut self, data: &I) -> CIResult<()>
     where
         for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        self.extend_iter(data.into_iter().copied())
+    }
+
+    ///
+    /// Extend the data with sample data consumed from an iterator, such as an iterator adapter.
+    ///
+    /// Unlike [`Self::extend`], the values are taken by value, which avoids collecting them into
+    /// a temporary collection.
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to append
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - If the input data is invalid (for harmonic/geometric means).
+    ///   The values before the first invalid one are appended and the following ones are not, so the
+    ///   number of values consumed is the increase of [`Self::sample_count`]
+    ///   (see also [`Self::try_extend_partial`]).
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let mut stats = mean::Arithmetic::new();
+    /// stats.extend_iter((1..=10).map(f64::from))?;
+    /// stats.extend_iter(vec![11., 12.])?;
+    /// assert_eq!(stats.sample_count(), 12);
+    /// assert_eq!(stats.sample_mean(), 6.5);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    fn extend_iter<I>(&mut self, data: I) -> CIResult<()>
+    where
+        I: IntoIterator<Item = F>,
     {
         for x_i in data {
-            self.append(*x_i)?;
+            self.append(x_i)?;
         }
         Ok(())
     }
 
     ///
-    /// Compute the confidence interval on the mean of a sample
+    /// Compute the confidence interval on the mean of a sample, in one shot.
+    ///
+    /// This is simply a shortcut for [`Self::from_iter`] followed by [`Self::ci_mean`], and
+    /// implementors need not override it.
     ///
     /// # Arguments
     ///
@@ -244,9 +406,168 @@ pub trait StatisticsOps<F: Float>: Default {
     /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
     /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
     ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// let ci = <mean::Harmonic<f64> as StatisticsOps<f64>>::ci_from_data(confidence, &data)?;
+    /// assert_eq!(ci, mean::Harmonic::from_iter(&data)?.ci_mean(confidence)?);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    fn ci_from_data<I: ?Sized>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample.
+    ///
+    /// Deprecated in favor of [`Self::ci_from_data`], since the name collides with the inherent
+    /// `ci` methods that implementors may define.
+    ///
+    #[deprecated(
+        note = "use `ci_from_data`; the trait method `ci` collides with inherent `ci` methods of implementors"
+    )]
     fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
     where
-        for<'a> &'a I: IntoIterator<Item = &'a F>;
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::ci_from_data(confidence, data)
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample, and also return the state built
+    /// from the data (e.g., to query other statistics or to append more data later), in a single
+    /// pass over the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::ci_from_data`].
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// let (ci, stats) = mean::Arithmetic::ci_and_state(confidence, data)?;
+    /// assert_eq!(ci, mean::Arithmetic::ci_from_data(confidence, &data)?);
+    /// assert_eq!(stats.sample_count(), 10);
+    /// assert_eq!(stats.sample_mean(), 5.5);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    fn ci_and_state<I>(confidence: Confidence, data: I) -> CIResult<(Interval<F>, Self)>
+    where
+        I: IntoIterator<Item = F>,
+    {
+        let stats = Self::try_from_iter_partial(data).map_err(|partial| partial.error)?;
+        Ok((stats.ci_mean(confidence)?, stats))
+    }
+
+    ///
+    /// Create a new state from the data of an iterator, stopping at the first element that
+    /// cannot be appended. Unlike [`Self::from_iter`], the error then carries the state built
+    /// from the elements before the faulty one, together with its index (see [`PartialState`]).
+    ///
+    /// Passing an iterator by reference (with [`Iterator::by_ref`]) leaves it positioned just
+    /// after the faulty element, so that ingestion can be resumed with [`Self::try_extend_partial`].
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`PartialState`] - wrapping the error of [`Self::append`] for the faulty element
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let data = [1., 2., -3., 4., 0., 5.];
+    /// let mut iter = data.into_iter();
+    /// let mut stats = mean::Geometric::new();
+    /// let mut skipped = vec![];
+    /// loop {
+    ///     match stats.try_extend_partial(iter.by_ref()) {
+    ///         Ok(complete) => {
+    ///             stats = complete;
+    ///             break;
+    ///         }
+    ///         Err(partial) => {
+    ///             // log the faulty element, drop it, and continue from where we were
+    ///             skipped.push(partial.error.to_string());
+    ///             stats = partial.state;
+    ///         }
+    ///     }
+    /// }
+    /// assert_eq!(skipped.len(), 2);
+    /// assert_eq!(stats.sample_count(), 4);
+    ///
+    /// let partial = mean::Geometric::try_from_iter_partial(data).unwrap_err();
+    /// assert_eq!(partial.index, 2);
+    /// assert_eq!(partial.state.sample_count(), 2);
+    /// ```
+    ///
+    fn try_from_iter_partial<I>(data: I) -> Result<Self, PartialState<Self>>
+    where
+        I: IntoIterator<Item = F>,
+    {
+        Self::default().try_extend_partial(data)
+    }
+
+    ///
+    /// Append the data of an iterator to the state, stopping at the first element that cannot be
+    /// appended, as with [`Self::try_from_iter_partial`].
+    ///
+    /// The index in the returned [`PartialState`] is relative to the first element consumed
+    /// by this call.
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`PartialState`] - wrapping the error of [`Self::append`] for the faulty element
+    ///
+    fn try_extend_partial<I>(mut self, data: I) -> Result<Self, PartialState<Self>>
+    where
+        I: IntoIterator<Item = F>,
+    {
+        for (index, x) in data.into_iter().enumerate() {
+            if let Err(error) = self.append(x) {
+                return Err(PartialState {
+                    error,
+                    index,
+                    state: self,
+                });
+            }
+        }
+        Ok(self)
+    }
+}
+
+///
+/// Error returned by [`StatisticsOps::try_from_iter_partial`] and
+/// [`StatisticsOps::try_extend_partial`], carrying the state accumulated before the faulty
+/// element so that the caller may skip that element and resume.
+///
+#[derive(thiserror::Error, Debug)]
+#[error("cannot append the element at index {index}: {error}")]
+pub struct PartialState<S> {
+    /// The error raised when appending the faulty element.
+    pub error: CIError,
+
+    /// The index of the faulty element (relative to the start of the iteration).
+    pub index: usize,
+
+    /// The state with all elements before the faulty one.
+    pub state: S,
 }
 
 macro_rules! impl_statistics_ops_for {
@@ -272,13 +593,6 @@ macro_rules! impl_statistics_ops_for {
             fn sample_count(&self) -> usize {
                 self.sample_count()
             }
-            #[inline]
-            fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
-            where
-                for<'a> &'a I: IntoIterator<Item = &'a F>,
-            {
-                <$x>::ci(confidence, data)
-            }
         }
     };
 }
@@ -288,207 +602,487 @@ impl_statistics_ops_for!(Harmonic<F>);
 impl_statistics_ops_for!(Geometric<F>);
 
 ///
-/// Represents the state of the computation of the arithmetic mean.
-/// This is a simple implementation that accumulates information about the samples, such as sum and sum of squares.
+/// Number of independent accumulators used by the slice-based fast paths
+/// (e.g., [`Geometric::extend_slice`]).
 ///
-/// It is best used through the [`StatisticsOps`] trait.
+const SLICE_LANES: usize = 4;
+
+///
+/// Find the first non-positive value in `data`, as required by the geometric and harmonic means.
+///
+fn check_positive<F: Float>(data: &[F]) -> CIResult<()> {
+    match data.iter().position(|&x| x <= F::zero()) {
+        Some(index) => Err(CIError::NonPositiveValueAt {
+            index,
+            value: data[index].to_f64().unwrap_or(f64::NAN),
+        }),
+        None => Ok(()),
+    }
+}
+
+///
+/// Interval for the given confidence from the bounds `lo` and `hi` of a mean obtained by
+/// transforming back the bounds computed in another space (e.g., \\( e^x \\) for the geometric mean).
+///
+/// A bound that is required by the confidence and that overflows to infinity is handled as follows:
+/// * with a two-sided confidence, an infinite upper bound turns the result into the upper
+///   one-sided interval \\( [lo, +\infty) \\), keeping the lower bound that remains meaningful;
+/// * otherwise, the interval cannot be represented and an error naming the bound is returned.
+///
+/// # Errors
+///
+/// * [`CIError::UninterpretableTransformedInterval`] - if a required bound overflows, except as above
+///
+fn transformed_interval<F: Float>(
+    confidence: Confidence,
+    lo: F,
+    hi: F,
+    mean_name: &str,
+) -> CIResult<Interval<F>> {
+    let overflow = |bound: &str| {
+        CIError::UninterpretableTransformedInterval(format!(
+            "{bound} bound of the {mean_name} mean overflows to infinity"
+        ))
+    };
+    if !confidence.is_lower() && lo == F::infinity() {
+        return Err(overflow("lower"));
+    }
+    if !confidence.is_upper() && hi == F::infinity() {
+        return match confidence {
+            Confidence::TwoSided(_) => Interval::try_new_finite(lo, hi).map_err(|e| e.into()),
+            _ => Err(overflow("upper")),
+        };
+    }
+    Interval::from_finite_bounds_for(confidence, lo, hi)
+}
+
+///
+/// Higher moments of a sample, used for its skewness and kurtosis.
+///
+/// The state holds the sums of the first four powers of the differences of the values to a
+/// reference value (the "shift"), initially the first value of the sample. Close to the mean, the
+/// shift avoids most of the cancellation that would occur with sums of powers of the values,
+/// while appending a value remains cheap (no division, unlike Welford's update).
+/// States are merged through their central moments with the pairwise formulas of Pébay; the
+/// merged state is shifted by its mean.
+/// The sample count is kept by the enclosing [`Arithmetic`] state.
+///
+/// # References
+///
+/// * P. Pébay. "Formulas for Robust, One-Pass Parallel Computation of Covariances and Arbitrary-Order Statistical Moments." Sandia Report SAND2008-6212, 2008.
+/// * [Wikipedia article on algorithms for calculating variance](https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance)
 ///
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Arithmetic<F: Float> {
-    sum: utils::KahanSum<F>,
-    sum_sq: utils::KahanSum<F>,
-    count: usize,
+struct ShiftedMoments<F: Float> {
+    shift: F,
+    sums: [F; 4],
 }
 
-impl<F: Float> Default for Arithmetic<F> {
-    fn default() -> Self {
+///
+/// Central moments of a sample: its mean and the sums of the powers of the deviations to the mean.
+///
+struct CentralMoments<F: Float> {
+    mean: F,
+    m2: F,
+    m3: F,
+    m4: F,
+}
+
+impl<F: Float> ShiftedMoments<F> {
+    fn new(shift: F) -> Self {
         Self {
-            sum: utils::KahanSum::default(),
-            sum_sq: utils::KahanSum::default(),
-            count: 0,
+            shift,
+            sums: [F::zero(); 4],
         }
     }
-}
 
-impl<F: Float> Arithmetic<F> {
-    ///
-    /// Create a new empty state
     ///
-    /// # Example
-    /// ```
-    /// use stats_ci::*;
-    /// let mut stats = mean::Arithmetic::new();
-    /// stats.append(10.);
-    /// assert_eq!(stats.sample_count(), 1);
-    /// assert_eq!(stats.sample_mean(), 10.);
-    /// ```
+    /// Append a value to a state of `count` values.
     ///
-    pub fn new() -> Self {
-        Default::default()
+    #[inline]
+    fn append(&mut self, count: usize, x: F) {
+        if count == 0 {
+            self.shift = x;
+        }
+        self.accumulate(x);
     }
 
     ///
-    /// Variance of the sample
-    /// \\( \frac{1}{n-1}\left(\sum_{i=1}^n x_i^2 - \frac{1}{n} \left(\sum_{i=1}^n x_i\right)^2 \right) \\)
-    ///
-    /// Complexity: \\( O(1) \\)
+    /// Append `weight` copies of a value to a state of `count` values.
     ///
-    pub fn sample_variance(&self) -> F {
-        let mean = self.sample_mean();
-        (self.sum_sq.value() - mean * self.sum.value()) / F::from(self.count - 1).unwrap()
+    #[inline]
+    fn append_counted(&mut self, count: usize, x: F, weight: F) {
+        if count == 0 {
+            self.shift = x;
+        }
+        let d = x - self.shift;
+        let d2 = d * d;
+        self.sums[0] = self.sums[0] + weight * d;
+        self.sums[1] = self.sums[1] + weight * d2;
+        self.sums[2] = self.sums[2] + weight * d2 * d;
+        self.sums[3] = self.sums[3] + weight * d2 * d2;
     }
 
     ///
-    /// Standard deviation of the sample
-    ///
-    /// Complexity: \\( O(1) \\)
+    /// Add the powers of the difference of a value to the shift.
     ///
-    pub fn sample_std_dev(&self) -> F {
-        self.sample_variance().sqrt()
+    #[inline]
+    fn accumulate(&mut self, x: F) {
+        let d = x - self.shift;
+        let d2 = d * d;
+        self.sums[0] = self.sums[0] + d;
+        self.sums[1] = self.sums[1] + d2;
+        self.sums[2] = self.sums[2] + d2 * d;
+        self.sums[3] = self.sums[3] + d2 * d2;
     }
 
     ///
-    /// Append a new sample to the data
-    ///
-    /// Complexity: \\( O(1) \\)
+    /// Central moments of a state of `count` values.
     ///
-    fn append(&mut self, x: F) -> CIResult<()> {
-        self.sum += x;
-        self.sum_sq += x * x;
-        self.count += 1;
-        Ok(())
+    fn central(&self, count: usize) -> CentralMoments<F> {
+        if count == 0 {
+            return CentralMoments {
+                mean: self.shift,
+                m2: F::zero(),
+                m3: F::zero(),
+                m4: F::zero(),
+            };
+        }
+        let n = F::from(count).unwrap();
+        let (two, three, four, six) = (
+            F::from(2).unwrap(),
+            F::from(3).unwrap(),
+            F::from(4).unwrap(),
+            F::from(6).unwrap(),
+        );
+        let [s1, s2, s3, s4] = self.sums;
+        let d = s1 / n;
+        let d2 = d * d;
+        CentralMoments {
+            mean: self.shift + d,
+            m2: s2 - n * d2,
+            m3: s3 - three * d * s2 + two * n * d2 * d,
+            m4: s4 - four * d * s3 + six * d2 * s2 - three * n * d2 * d2,
+        }
     }
 
     ///
-    /// Mean of the sample
+    /// Merge a state of `count` values with a state of `rhs_count` values.
     ///
-    /// Complexity: \\( O(1) \\)
+    fn merge(self, count: usize, rhs: Self, rhs_count: usize) -> Self {
+        if rhs_count == 0 {
+            return self;
+        }
+        if count == 0 {
+            return rhs;
+        }
+        let lhs = self.central(count);
+        let rhs = rhs.central(rhs_count);
+        let n_a = F::from(count).unwrap();
+        let n_b = F::from(rhs_count).unwrap();
+        let n = n_a + n_b;
+        let (three, four, six) = (
+            F::from(3).unwrap(),
+            F::from(4).unwrap(),
+            F::from(6).unwrap(),
+        );
+        let delta = rhs.mean - lhs.mean;
+        let delta2 = delta * delta;
+        let n_ab = n_a * n_b;
+        Self {
+            shift: lhs.mean + delta * n_b / n,
+            sums: [
+                F::zero(),
+                lhs.m2 + rhs.m2 + delta2 * n_ab / n,
+                lhs.m3
+                    + rhs.m3
+                    + delta2 * delta * n_ab * (n_a - n_b) / (n * n)
+                    + three * delta * (n_a * rhs.m2 - n_b * lhs.m2) / n,
+                lhs.m4
+                    + rhs.m4
+                    + delta2 * delta2 * n_ab * (n_a * n_a - n_ab + n_b * n_b) / (n * n * n)
+                    + six * delta2 * (n_a * n_a * rhs.m2 + n_b * n_b * lhs.m2) / (n * n)
+                    + four * delta * (n_a * rhs.m3 - n_b * lhs.m3) / n,
+            ],
+        }
+    }
+
     ///
-    pub fn sample_mean(&self) -> F {
-        self.sum.value() / F::from(self.count).unwrap()
+    /// Multiply the underlying values by \\( 2^{-exp} \\), i.e., the shift by that factor and
+    /// the sums of the \\( k \\)-th powers by its \\( k \\)-th power.
+    ///
+    fn rescale(&mut self, exp: i32) {
+        self.shift = ldexp(self.shift, -exp);
+        for (power, sum) in (1..).zip(self.sums.iter_mut()) {
+            *sum = ldexp(*sum, -power * exp);
+        }
     }
+}
 
+///
+/// Outcome of the check of [`Arithmetic::normality_advisory`] on whether the sample is large
+/// enough, given its skewness and kurtosis, for the sample mean to be approximately normal.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Advisory<F: Float> {
     ///
-    /// Standard error of the sample mean
+    /// The sample is large enough for its skewness and kurtosis.
     ///
-    /// Complexity: \\( O(1) \\)
+    Adequate,
+
     ///
-    pub fn sample_sem(&self) -> F {
-        self.sample_std_dev() / F::from(self.count - 1).unwrap().sqrt()
-    }
+    /// The sample is too small for its skewness: the interval may undercover,
+    /// on one side in particular.
+    ///
+    Skewed {
+        /// the sample skewness
+        skewness: F,
+        /// the sample count recommended by the rule of thumb
+        recommended_count: usize,
+    },
 
     ///
-    /// Confidence interval of the sample mean
+    /// The sample is too small for the weight of its tails: the interval may undercover.
     ///
-    /// Complexity: \\( O(1) \\)
+    HeavyTailed {
+        /// the sample excess kurtosis
+        kurtosis: F,
+        /// the sample count recommended by the rule of thumb
+        recommended_count: usize,
+    },
+
     ///
-    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
-        let n = self.count as f64;
-        let mean = self.sample_mean().try_f64("stats.mean")?;
-        let std_dev = self.sample_std_dev().try_f64("stats.std_dev")?;
-        let std_err_mean = std_dev / n.sqrt();
-        let degrees_of_freedom = n - 1.;
-        let (lo, hi) = stats::interval_bounds(confidence, mean, std_err_mean, degrees_of_freedom);
-        let (lo, hi) = (F::from(lo).convert("lo")?, F::from(hi).convert("hi")?);
-        match confidence {
-            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
-            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
-            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
-        }
-    }
-
-    ///
-    /// Number of samples
-    ///
-    /// Complexity: \\( O(1) \\)
+    /// The check cannot be made: fewer than four samples, zero variance, or a state whose
+    /// higher moments are unknown (see [`Arithmetic::sample_skewness`]).
     ///
-    pub fn sample_count(&self) -> usize {
-        self.count
+    Undetermined,
+}
+
+///
+/// Rule of thumb on the skewness \\( g_1 \\): the sample count should be at least \\( 30 g_1^2 \\).
+///
+const SKEWNESS_COUNT_FACTOR: f64 = 30.;
+
+///
+/// Rule of thumb on the excess kurtosis \\( g_2 \\): the sample count should be at least \\( 10 g_2 \\),
+/// so that the excess kurtosis of the sample mean, \\( g_2 / n \\), is at most 0.1.
+///
+const KURTOSIS_COUNT_FACTOR: f64 = 10.;
+
+///
+/// Represents the state of the computation of the arithmetic mean.
+/// This is a simple implementation that accumulates information about the samples, such as sum and sum of squares.
+///
+/// It is best used through the [`StatisticsOps`] trait.
+///
+/// Two states are equal when they have the same count and the same (compensated) sums of the
+/// values and of their squares, which is the case after ingesting the same data in the same order.
+/// The higher moments are not compared, as their representation depends on how states were merged.
+/// With the `approx` feature, states can also be compared approximately (e.g., after ingesting the
+/// same data in a different order) through `approx::AbsDiffEq`.
+///
+/// The [`Debug`](core::fmt::Debug) output includes the mean and the variance of the sample.
+///
+/// # Rescaled mode
+///
+/// When the sum of squares would overflow to infinity although the values are finite (e.g.,
+/// values around `1e200` with `f64`, or `1e20` with `f32`), the state switches to a rescaled mode:
+/// the sums are divided by a power of two, and so are the values appended afterwards. The scale
+/// is only ever increased, by a quarter of the exponent range of `F` at a time (\\( 2^{256} \\) for `f64`).
+/// The mean and the standard deviation are recovered by multiplying back at read time, which is
+/// exact since the factors are powers of two, so that the interval on the mean remains available
+/// even when the variance itself is not representable (it is then reported as infinite).
+/// Merging states rescales them to the larger of their scales first.
+/// States that never overflow are not affected.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let stats = mean::Arithmetic::from_iter(&[1., 2., 3.])?;
+/// assert_eq!(stats, mean::Arithmetic::from_iter(&[1., 2., 3.])?);
+/// assert!(format!("{:?}", stats).starts_with("Arithmetic { count: 3, mean: 2.0, variance: 1.0,"));
+/// # use approx::*;
+/// let reversed = mean::Arithmetic::from_iter(&[0.3, 0.2, 0.1])?;
+/// assert_abs_diff_eq!(mean::Arithmetic::from_iter(&[0.1, 0.2, 0.3])?, reversed);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Arithmetic<F: Float> {
+    sum: utils::KahanSum<F>,
+    sum_sq: utils::KahanSum<F>,
+    count: usize,
+    // `None` when the higher moments are unknown (e.g., state created from a summary)
+    moments: Option<ShiftedMoments<F>>,
+    // the values are accumulated multiplied by `2^-scale` (see "Rescaled mode" above)
+    #[cfg_attr(feature = "serde", serde(default))]
+    scale: i32,
+}
+
+impl<F: Float> PartialEq for Arithmetic<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count
+            && self.scale == other.scale
+            && self.sum == other.sum
+            && self.sum_sq == other.sum_sq
     }
+}
 
-    ///
-    /// Combine two states
-    ///
-    /// Complexity: \\( O(1) \\)
-    ///
-    #[allow(clippy::should_implement_trait)]
-    pub fn add(self, rhs: Self) -> Self {
-        let mut sum = self.sum;
-        let mut sum_sq = self.sum_sq;
-        sum += rhs.sum;
-        sum_sq += rhs.sum_sq;
-        let count = self.count + rhs.count;
-        Self { sum, sum_sq, count }
+#[cfg(feature = "approx")]
+impl<F: Float + approx::AbsDiffEq<Epsilon = F>> approx::AbsDiffEq for Arithmetic<F> {
+    type Epsilon = F;
+
+    fn default_epsilon() -> F {
+        F::default_epsilon()
     }
 
     ///
-    /// Compute the confidence interval on the mean of a sample
-    ///
-    /// # Arguments
-    ///
-    /// * `confidence` - The confidence level of the interval
-    /// * `data` - The data to compute the confidence interval on
-    ///
-    /// # Output
-    ///
-    /// * `Ok(interval)` - The confidence interval on the mean of the sample
-    ///
-    /// # Errors
-    ///
-    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
-    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
-    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
-    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    /// Compare the counts exactly and the sums scaled by the count (i.e., the means of the values
+    /// and of their squares) approximately. States in rescaled mode are compared at the larger
+    /// of their scales.
     ///
-    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
-    where
-        for<'a> &'a I: IntoIterator<Item = &'a F>,
-    {
-        Self::from_iter(data)?.ci_mean(confidence)
+    fn abs_diff_eq(&self, other: &Self, epsilon: F) -> bool {
+        if self.count != other.count {
+            return false;
+        }
+        if self.count == 0 {
+            return true;
+        }
+        let scale = self.scale.max(other.scale);
+        let (mut lhs, mut rhs) = (*self, *other);
+        lhs.rescale(scale);
+        rhs.rescale(scale);
+        let count = F::from(self.count).unwrap();
+        F::abs_diff_eq(
+            &(lhs.sum.value() / count),
+            &(rhs.sum.value() / count),
+            epsilon,
+        ) && F::abs_diff_eq(
+            &(lhs.sum_sq.value() / count),
+            &(rhs.sum_sq.value() / count),
+            epsilon,
+        )
     }
 }
 
-impl<F: Float> core::ops::Add for Arithmetic<F> {
-    type Output = Self;
+impl<F: Float + core::fmt::Debug> core::fmt::Debug for Arithmetic<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let variance = if self.count == 0 {
+            F::nan()
+        } else {
+            self.sample_variance()
+        };
+        f.debug_struct("Arithmetic")
+            .field("count", &self.count)
+            .field("mean", &self.sample_mean())
+            .field("variance", &variance)
+            .field("sum", &self.sum)
+            .field("sum_sq", &self.sum_sq)
+            .field("moments", &self.moments)
+            .field("scale", &self.scale)
+            .finish()
+    }
+}
 
-    #[inline]
-    fn add(self, rhs: Self) -> Self::Output {
-        self.add(rhs)
+impl<F: Float> Default for Arithmetic<F> {
+    fn default() -> Self {
+        Self {
+            sum: utils::KahanSum::default(),
+            sum_sq: utils::KahanSum::default(),
+            count: 0,
+            moments: Some(ShiftedMoments::new(F::zero())),
+            scale: 0,
+        }
     }
 }
 
-impl<F: Float> core::ops::AddAssign for Arithmetic<F> {
-    #[inline]
-    fn add_assign(&mut self, rhs: Self) {
-        *self = self.add(rhs);
+///
+/// Increment of the scale of [`Arithmetic`] when its sums would overflow: a quarter of the
+/// exponent range of `F` (256 for `f64`, 32 for `f32`).
+///
+fn scale_step<F: Float>() -> i32 {
+    F::max_value().log2().round().to_i32().unwrap_or(1) / 4
+}
+
+///
+/// Multiply `value` by \\( 2^{exp} \\), by steps of at most [`scale_step`] so that the factors
+/// are representable in `F`. The result is exact unless it overflows or underflows.
+///
+fn ldexp<F: Float>(value: F, exp: i32) -> F {
+    if exp == 0 {
+        return value;
+    }
+    let step = scale_step::<F>();
+    let two = F::from(2).unwrap();
+    let (mut value, mut exp) = (value, exp);
+    while exp != 0 {
+        let factor = exp.clamp(-step, step);
+        value = value * two.powi(factor);
+        exp -= factor;
     }
+    value
 }
 
 ///
-/// Represents the state of the computation related to the harmonic mean.
-/// This is a simple implementation that accumulates information about the samples, such as sum and sum of squares.
-/// It is implemented as a wrapper around [`Arithmetic`] to compute the arithmetic mean of the reciprocals of the samples.
+/// Convert a count to `F`, if it is exactly representable (e.g., up to \\( 2^{24} \\) for `f32`).
 ///
-/// It is best used through the [`StatisticsOps`] trait.
+fn exact_count<F: Float>(count: usize) -> Option<F> {
+    F::from(count).filter(|n| n.to_usize() == Some(count))
+}
+
 ///
-#[derive(Debug, Clone, Copy, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Harmonic<F: Float> {
-    recip_space: Arithmetic<F>,
+/// Convert a count to `f64`, if it is exactly representable (i.e., up to \\( 2^{53} \\)).
+///
+fn exact_f64_count(count: usize) -> CIResult<f64> {
+    exact_count(count).ok_or_else(|| {
+        CIError::FloatConversionError(format!(
+            "count {} is not exactly representable in f64",
+            count
+        ))
+    })
 }
 
-impl<F: Float> Harmonic<F> {
+///
+/// Divide `value` by `count`.
+///
+/// The division is carried out in `F` if the count is exactly representable in `F`, and in `f64`
+/// otherwise (e.g., counts above \\( 2^{24} \\) for `f32`), so that rounding the count does not
+/// bias the result.
+///
+fn div_count<F: Float>(value: F, count: usize) -> CIResult<F> {
+    match exact_count::<F>(count) {
+        Some(n) => Ok(value / n),
+        None => F::from(value.try_f64("value")? / exact_f64_count(count)?).convert("value / count"),
+    }
+}
+
+///
+/// Divide `value` by the square root of `count`, as in [`div_count`].
+///
+fn div_sqrt_count<F: Float>(value: F, count: usize) -> CIResult<F> {
+    match exact_count::<F>(count) {
+        Some(n) => Ok(value / n.sqrt()),
+        None => F::from(value.try_f64("value")? / exact_f64_count(count)?.sqrt())
+            .convert("value / sqrt(count)"),
+    }
+}
+
+impl<F: Float> Arithmetic<F> {
     ///
     /// Create a new empty state
     ///
     /// # Example
     /// ```
     /// use stats_ci::*;
-    /// let mut stats = mean::Harmonic::new();
-    /// stats.append(10.);
+    /// let mut stats = mean::Arithmetic::new();
+    /// stats.append(10.)?;
     /// assert_eq!(stats.sample_count(), 1);
     /// assert_eq!(stats.sample_mean(), 10.);
+    /// # Ok::<(),error::CIError>(())
     /// ```
     ///
     pub fn new() -> Self {
@@ -496,627 +1090,7756 @@ impl<F: Float> Harmonic<F> {
     }
 
     ///
-    /// Append a new sample to the data
+    /// Create a state from summary statistics of a sample, when the raw data is not available
+    /// (e.g., values reported in a paper or a textbook problem).
     ///
-    /// Complexity: \\( O(1) \\)
+    /// The internal sums are reconstructed so that [`Arithmetic::sample_mean`] and
+    /// [`Arithmetic::sample_std_dev`] return the given values (up to rounding errors).
     ///
-    pub fn append(&mut self, x: F) -> CIResult<()> {
-        if x <= F::zero() {
-            return Err(error::CIError::NonPositiveValue(
-                x.to_f64().unwrap_or(f64::NAN),
-            ));
+    /// # Arguments
+    ///
+    /// * `count` - the number of samples
+    /// * `mean` - the sample mean
+    /// * `std_dev` - the sample standard deviation (with Bessel's correction)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if `count` is less than 2
+    /// * [`CIError::InvalidInputData`] - if `mean` is not finite, or if `std_dev` is negative or not finite
+    /// * [`CIError::FloatConversionError`] - if `count` cannot be converted to `F`
+    ///
+    /// # Notes
+    ///
+    /// As with raw data, the standard deviation is only recovered accurately when it is not
+    /// negligible relative to the mean, since the state stores the sum of squares.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let stats = mean::Arithmetic::from_summary(12, 120., 21.39)?;
+    /// assert_eq!(stats.sample_count(), 12);
+    /// assert_abs_diff_eq!(stats.sample_mean(), 120., epsilon = 1e-10);
+    /// assert_abs_diff_eq!(stats.sample_std_dev(), 21.39, epsilon = 1e-10);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn from_summary(count: usize, mean: F, std_dev: F) -> CIResult<Self> {
+        if count < 2 {
+            return Err(CIError::TooFewSamples(count));
         }
-        self.recip_space.append(F::one() / x)?;
-        Ok(())
+        if !mean.is_finite() || !std_dev.is_finite() || std_dev < F::zero() {
+            return Err(CIError::InvalidInputData);
+        }
+        let n = F::from(count).convert("count")?;
+        let sum = n * mean;
+        // `sample_variance` subtracts `sample_mean() * sum`; building `sum_sq` from that exact
+        // product makes the subtraction cancel exactly, leaving only the spread term.
+        let mut sum_sq = utils::KahanSum::new(sum / n * sum);
+        sum_sq += (n - F::one()) * std_dev * std_dev;
+        Ok(Self {
+            sum: utils::KahanSum::new(sum),
+            sum_sq,
+            count,
+            moments: None,
+            scale: 0,
+        })
     }
 
     ///
-    /// Harmonic mean of the sample
-    /// \\( H = \left( \frac{1}{n} \sum_i \frac{1}{x_i} \right)^{-1} \\)
-    ///
-    /// Complexity: \\( O(1) \\)
+    /// Create a state directly from its accumulated values.
     ///
-    pub fn sample_mean(&self) -> F {
-        F::one() / self.recip_space.sample_mean()
+    #[cfg(any(test, feature = "proptest"))]
+    pub(crate) fn from_raw(count: usize, sum: F, sum_sq: F) -> Self {
+        Self {
+            sum: utils::KahanSum::new(sum),
+            sum_sq: utils::KahanSum::new(sum_sq),
+            count,
+            moments: None,
+            scale: 0,
+        }
     }
 
     ///
-    /// Standard error of the harmonic mean
-    /// \\( s_H = \frac{1}{\alpha^2} \frac{s_{1/x_i}}{\sqrt{n-1}} \\)
+    /// Variance of the sample
+    /// \\( \frac{1}{n-1}\left(\sum_{i=1}^n x_i^2 - \frac{1}{n} \left(\sum_{i=1}^n x_i\right)^2 \right) \\)
     ///
-    /// where
-    /// * the estimate of \\( \alpha \\) is given by \\( \alpha = \frac{1}{n} \sum_i 1/x_i \\);
-    /// * \\( s_{1/x_i} \\) is the estimate of the standard deviation of the reciprocals of the samples;
-    /// * and \\( n-1 \\) is the degree of freedom of the sample data.
+    /// The variance is NaN if the count cannot be converted to `F` (see [`Arithmetic::sample_mean`]),
+    /// and infinite if it is too large to be represented in `F` (see the rescaled mode of [`Arithmetic`]).
     ///
-    /// # Reference
+    /// Complexity: \\( O(1) \\)
     ///
-    /// * Nilan Noris. "The standard errors of the geometric and harmonic means and their application to index numbers." Ann. Math. Statist. 11(4): 445-448 (December, 1940). DOI: [10.1214/aoms/1177731830](https://doi.org/10.1214/aoms/1177731830) [JSTOR](https://www.jstor.org/stable/2235727)
+    pub fn sample_variance(&self) -> F {
+        self.try_sample_variance().unwrap_or_else(|_| F::nan())
+    }
+
+    fn try_sample_variance(&self) -> CIResult<F> {
+        Ok(ldexp(self.try_scaled_variance()?, 2 * self.scale))
+    }
+
     ///
-    pub fn sample_sem(&self) -> F {
-        let harm_mean = self.sample_mean();
-        let recip_std_dev = self.recip_space.sample_std_dev();
-        harm_mean * harm_mean * recip_std_dev
-            / F::from(self.recip_space.sample_count() - 1).unwrap().sqrt()
+    /// Variance of the values as accumulated, i.e., multiplied by \\( 2^{-scale} \\).
+    ///
+    /// When the higher moments are known, the sum of the squared deviations is taken from them:
+    /// unlike the difference of the sums of the values and of their squares, it does not cancel
+    /// out when the mean is large relative to the standard deviation, and it is exactly zero when
+    /// all the values are identical.
+    ///
+    fn try_scaled_variance(&self) -> CIResult<F> {
+        let dof = self.count - 1;
+        let sum_sq_dev = match self.moments {
+            Some(moments) => moments.central(self.count).m2,
+            None => self.sum_sq.value() - self.try_scaled_mean()? * self.sum.value(),
+        };
+        // rounding errors may make the sum of the squared deviations slightly negative
+        div_count(sum_sq_dev.max(F::zero()), dof)
     }
 
     ///
-    /// Number of samples
+    /// Standard deviation of the sample
     ///
     /// Complexity: \\( O(1) \\)
     ///
-    pub fn sample_count(&self) -> usize {
-        self.recip_space.sample_count()
+    pub fn sample_std_dev(&self) -> F {
+        self.try_scaled_variance()
+            .map(|variance| ldexp(variance.sqrt(), self.scale))
+            .unwrap_or_else(|_| F::nan())
     }
 
     ///
-    /// Confidence interval for the harmonic mean
+    /// Skewness of the sample
+    /// \\( g_1 = \frac{m_3}{m_2^{3/2}} \\)
     ///
-    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
-        let arith_ci = self.recip_space.ci_mean(confidence.flipped())?;
-        let (lo, hi) = (F::one() / arith_ci.high_f(), F::one() / arith_ci.low_f());
-        match confidence {
-            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
-            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
-            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
+    /// where \\( m_k = \frac{1}{n} \sum_{i=1}^n (x_i - \bar{x})^k \\) is the \\( k \\)-th central moment of the sample.
+    /// This is the (biased) estimator computed by `scipy.stats.skew` with its default arguments.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Notes
+    ///
+    /// The skewness is NaN when the sample variance is zero, or when the state was created from
+    /// summary statistics (see [`Arithmetic::from_summary`]), since the higher moments are then unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let stats = mean::Arithmetic::from_iter(&[1., 2., 3., 10.])?;
+    /// assert_abs_diff_eq!(stats.sample_skewness(), 1.0182, epsilon = 1e-4);
+    /// let symmetric = mean::Arithmetic::from_iter(&[1., 2., 3., 4.])?;
+    /// assert_abs_diff_eq!(symmetric.sample_skewness(), 0., epsilon = 1e-12);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn sample_skewness(&self) -> F {
+        match self.moments {
+            Some(moments) => {
+                let moments = moments.central(self.count);
+                let n = F::from(self.count).unwrap();
+                n.sqrt() * moments.m3 / moments.m2.powf(F::from(1.5).unwrap())
+            }
+            None => F::nan(),
         }
     }
 
     ///
-    /// Combine two states
+    /// Excess kurtosis of the sample
+    /// \\( g_2 = \frac{m_4}{m_2^2} - 3 \\)
+    ///
+    /// where \\( m_k = \frac{1}{n} \sum_{i=1}^n (x_i - \bar{x})^k \\) is the \\( k \\)-th central moment of the sample.
+    /// This is the (biased) estimator computed by `scipy.stats.kurtosis` with its default arguments,
+    /// such that the excess kurtosis of a normal distribution is zero.
     ///
     /// Complexity: \\( O(1) \\)
     ///
-    #[allow(clippy::should_implement_trait)]
-    pub fn add(self, rhs: Self) -> Self {
-        Self {
-            recip_space: self.recip_space + rhs.recip_space,
+    /// # Notes
+    ///
+    /// As for [`Arithmetic::sample_skewness`], the kurtosis is NaN when the sample variance is zero,
+    /// or when the higher moments are unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let stats = mean::Arithmetic::from_iter(&[1., 2., 3., 4., 5., 6., 7., 8., 9., 10.])?;
+    /// assert_abs_diff_eq!(stats.sample_kurtosis(), -1.2242, epsilon = 1e-4);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn sample_kurtosis(&self) -> F {
+        match self.moments {
+            Some(moments) => {
+                let moments = moments.central(self.count);
+                let n = F::from(self.count).unwrap();
+                n * moments.m4 / (moments.m2 * moments.m2) - F::from(3).unwrap()
+            }
+            None => F::nan(),
         }
     }
 
     ///
-    /// Compute the confidence interval on the mean of a sample
-    ///
-    /// # Arguments
+    /// Check whether the sample is large enough, given its skewness and kurtosis, for the
+    /// t-interval computed by [`Arithmetic::ci_mean`] to be reliable. That interval assumes that
+    /// the sample mean is approximately normal, which may take many samples for skewed or
+    /// heavy-tailed data; otherwise, the interval tends to undercover.
     ///
-    /// * `confidence` - The confidence level of the interval
-    /// * `data` - The data to compute the confidence interval on
+    /// The check relies on the following rules of thumb, where \\( n \\) is the sample count:
+    /// * [`Advisory::Skewed`] - if \\( n < 30 g_1^2 \\), where \\( g_1 \\) is the [skewness](Arithmetic::sample_skewness);
+    /// * [`Advisory::HeavyTailed`] - otherwise, if \\( n < 10 g_2 \\), where \\( g_2 \\) is the
+    ///   [excess kurtosis](Arithmetic::sample_kurtosis) (i.e., the excess kurtosis of the sample mean,
+    ///   \\( g_2 / n \\), is larger than 0.1).
     ///
-    /// # Output
+    /// This is only an advisory: the interval itself is not affected.
     ///
-    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    /// Complexity: \\( O(1) \\)
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
-    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
-    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
-    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    /// ```
+    /// # use stats_ci::*;
+    /// let skewed = [1., 1., 1., 1., 1., 1., 1., 1., 1., 100.];
+    /// let stats = mean::Arithmetic::from_iter(&skewed)?;
+    /// assert!(matches!(
+    ///     stats.normality_advisory(),
+    ///     mean::Advisory::Skewed { recommended_count: 214, .. }
+    /// ));
+    /// let stats = mean::Arithmetic::from_iter(&[1., 2., 3., 4., 5., 6., 7., 8.])?;
+    /// assert_eq!(stats.normality_advisory(), mean::Advisory::Adequate);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
     ///
-    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
-    where
-        for<'a> &'a I: IntoIterator<Item = &'a F>,
-    {
-        Self::from_iter(data)?.ci_mean(confidence)
-    }
-}
+    pub fn normality_advisory(&self) -> Advisory<F> {
+        let skewness = self.sample_skewness();
+        let kurtosis = self.sample_kurtosis();
+        if self.count < 4 || !skewness.is_finite() || !kurtosis.is_finite() {
+            return Advisory::Undetermined;
+        }
+        let recommended = |count: f64| count.ceil() as usize;
+        let g1 = skewness.to_f64().unwrap_or(f64::NAN);
+        let g2 = kurtosis.to_f64().unwrap_or(f64::NAN);
 
-impl<F: Float> Default for Harmonic<F> {
-    fn default() -> Self {
-        Self {
-            recip_space: Arithmetic::default(),
+        let recommended_count = recommended(SKEWNESS_COUNT_FACTOR * g1 * g1);
+        if self.count < recommended_count {
+            return Advisory::Skewed {
+                skewness,
+                recommended_count,
+            };
         }
+        let recommended_count = recommended(KURTOSIS_COUNT_FACTOR * g2);
+        if self.count < recommended_count {
+            return Advisory::HeavyTailed {
+                kurtosis,
+                recommended_count,
+            };
+        }
+        Advisory::Adequate
     }
-}
 
-impl<F: Float> core::ops::Add for Harmonic<F> {
-    type Output = Self;
+    ///
+    /// Append a new sample to the data
+    ///
+    /// If the sums would overflow to infinity although `x` is finite (e.g., very large values
+    /// with `f32`), the state switches to the rescaled mode (see [`Arithmetic`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    fn append(&mut self, x: F) -> CIResult<()> {
+        self.push(x);
+        Ok(())
+    }
 
+    ///
+    /// Check whether appending `weight` copies of the finite value `x` would make the finite sums
+    /// infinite at the current scale.
+    ///
+    /// Only the sum of squares needs checking: the sum cannot overflow before it,
+    /// unless the number of samples is itself of the order of the largest float.
+    ///
     #[inline]
-    fn add(self, rhs: Self) -> Self::Output {
-        self.add(rhs)
+    fn overflows_with(&self, x: F, weight: F) -> bool {
+        let x = ldexp(x, -self.scale);
+        let sum_sq = self.sum_sq.value();
+        !(sum_sq + x * x * weight).is_finite() && x.is_finite() && sum_sq.is_finite()
     }
-}
 
-impl<F: Float> core::ops::AddAssign for Harmonic<F> {
-    #[inline]
-    fn add_assign(&mut self, rhs: Self) {
-        *self = self.add(rhs);
+    ///
+    /// Increase the scale of the state until appending `weight` copies of `x` does not overflow.
+    ///
+    fn rescale_for(&mut self, x: F, weight: F) {
+        while self.overflows_with(x, weight) {
+            self.rescale(self.scale + scale_step::<F>());
+        }
     }
-}
 
-///
-/// Represents the state of the computation of the geometric mean.
-/// This is a simple implementation that accumulates information about the samples, such as sum and sum of squares.
-/// It is implemented as a wrapper around [`Arithmetic`] to compute the arithmetic mean of the logarithms of the samples.
-///
-/// It is best used through the [`StatisticsOps`] trait.
-///
-#[derive(Debug, Clone, Copy, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Geometric<F: Float> {
-    log_space: Arithmetic<F>,
-}
+    ///
+    /// Set the scale of the state to `scale`, which must not be smaller than the current one,
+    /// dividing the sums accordingly.
+    ///
+    fn rescale(&mut self, scale: i32) {
+        let exp = scale - self.scale;
+        if exp == 0 {
+            return;
+        }
+        self.sum = self.sum.map_parts(|sum| ldexp(sum, -exp));
+        self.sum_sq = self.sum_sq.map_parts(|sum| ldexp(sum, -2 * exp));
+        if let Some(moments) = &mut self.moments {
+            moments.rescale(exp);
+        }
+        self.scale = scale;
+    }
+
+    fn push(&mut self, x: F) {
+        self.rescale_for(x, F::one());
+        let x = ldexp(x, -self.scale);
+        self.sum += x;
+        self.sum_sq += x * x;
+        if let Some(moments) = &mut self.moments {
+            moments.append(self.count, x);
+        }
+        self.count += 1;
+    }
 
-impl<F: Float> Geometric<F> {
     ///
-    /// Create a new empty state
+    /// Mean of the sample
+    ///
+    /// When the count is not exactly representable in `F` (above \\( 2^{24} \\) for `f32`), the
+    /// sum is divided by the count in `f64` rather than by the rounded count. The mean is NaN if
+    /// the count is not exactly representable in `f64` either (above \\( 2^{53} \\)) and cannot
+    /// be converted to `F`; [`Arithmetic::ci_mean`] reports a [`CIError::FloatConversionError`] instead.
+    ///
+    /// Complexity: \\( O(1) \\)
     ///
     /// # Example
     /// ```
     /// # use stats_ci::*;
-    /// # use approx::*;
-    /// let mut stats = mean::Geometric::new();
-    /// stats.append(10.)?;
-    /// assert_eq!(stats.sample_count(), 1);
-    /// assert_abs_diff_eq!(stats.sample_mean(), 10., epsilon = 1e-10);
+    /// let mut stats = mean::Arithmetic::<f32>::new();
+    /// stats.extend_counted(1., 1 << 24)?;
+    /// stats.append(0.)?;
+    /// // the count 2^24 + 1 rounds to 2^24 in f32
+    /// assert_eq!(stats.sample_mean(), 1. - f32::EPSILON / 2.);
     /// # Ok::<(),error::CIError>(())
     /// ```
     ///
-    pub fn new() -> Self {
-        Default::default()
+    pub fn sample_mean(&self) -> F {
+        self.try_sample_mean().unwrap_or_else(|_| F::nan())
     }
 
-    ///
-    /// Append a new sample to the data
-    ///
-    /// Complexity: \\( O(1) \\)
-    ///
-    pub fn append(&mut self, x: F) -> CIResult<()> {
-        if x <= F::zero() {
-            return Err(error::CIError::NonPositiveValue(
-                x.to_f64().unwrap_or(f64::NAN),
-            ));
-        }
-        self.log_space.append(x.ln())?;
-        Ok(())
+    fn try_sample_mean(&self) -> CIResult<F> {
+        Ok(ldexp(self.try_scaled_mean()?, self.scale))
     }
 
     ///
-    /// Geometric mean of the sample
+    /// Mean of the values as accumulated, i.e., multiplied by \\( 2^{-scale} \\).
     ///
-    pub fn sample_mean(&self) -> F {
-        self.log_space.sample_mean().exp()
+    fn try_scaled_mean(&self) -> CIResult<F> {
+        div_count(self.sum.value(), self.count)
     }
 
     ///
-    /// Standard error of the geometric mean
+    /// Standard error of the sample mean \\( s / \sqrt{n} \\), where \\( s \\) is the sample standard
+    /// deviation (with Bessel's correction) and \\( n \\) is the number of samples.
     ///
-    /// Computed as: \\( G \frac{s_{\log x_i}}{\sqrt{n-1}} \\)
-    /// where \\( G \\) is the geometric mean of the sample;
-    /// \\( s_{\log x_i} \\) is the estimate of the standard deviation of the logarithms of the samples;
-    /// and \\( n-1 \\) is the degree of freedom of the sample data.
+    /// This is the standard error used by [`Arithmetic::ci_mean`], so that the two-sided interval
+    /// is \\( \bar{x} \pm t_{n-1} \, s / \sqrt{n} \\) (see [`Arithmetic::ci_mean_detailed`]).
     ///
-    ///  # Reference
+    /// Complexity: \\( O(1) \\)
     ///
-    /// * Nilan Noris. "The standard errors of the geometric and harmonic means and their application to index numbers." Ann. Math. Statist. 11(4): 445-448 (December, 1940). DOI: [10.1214/aoms/1177731830](https://doi.org/10.1214/aoms/1177731830) [JSTOR](https://www.jstor.org/stable/2235727)
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+    /// let stats = mean::Arithmetic::from_iter(&data)?;
+    /// let detail = stats.ci_mean_detailed(Confidence::new_two_sided(0.95))?;
+    /// assert_eq!(stats.sample_sem(), stats.sample_std_dev() / 10_f64.sqrt());
+    /// assert_eq!(stats.sample_sem(), detail.std_err);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
     ///
     pub fn sample_sem(&self) -> F {
-        let geom_mean = self.sample_mean();
-        let log_std_dev = self.log_space.sample_std_dev();
-        geom_mean * log_std_dev / F::from(self.log_space.sample_count() - 1).unwrap().sqrt()
+        div_sqrt_count(self.sample_std_dev(), self.count).unwrap_or_else(|_| F::nan())
     }
 
     ///
-    /// Number of samples
+    /// Confidence interval of the sample mean
     ///
     /// Complexity: \\( O(1) \\)
     ///
-    pub fn sample_count(&self) -> usize {
-        self.log_space.sample_count()
-    }
-
+    /// # Errors
     ///
-    /// Confidence interval for the geometric mean
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    ///
+    /// # Notes
+    ///
+    /// When the sample variance is zero (all samples are identical, or there is a single sample),
+    /// the interval is degenerate at the sample mean \\( x \\), for any confidence level:
+    /// \\( [x, x] \\) if two-sided, or \\( [x, +\infty) \\) (resp. \\( (-\infty, x] \\))
+    /// if upper (resp. lower) one-sided.
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = mean::Arithmetic::from_iter(&[3., 3., 3.])?;
+    /// assert_eq!(stats.ci_mean(Confidence::new(0.99))?, Interval::new(3., 3.)?);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
     ///
     pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
-        let arith_ci = self.log_space.ci_mean(confidence)?;
-        let (lo, hi) = (arith_ci.low_f().exp(), arith_ci.high_f().exp());
-        match confidence {
-            Confidence::TwoSided(_) => Interval::new(lo, hi).map_err(|e| e.into()),
-            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo)),
-            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi)),
-        }
+        self.ci_mean_detailed(confidence)
+            .map(|detail| detail.interval)
     }
 
     ///
-    /// Combine two states
+    /// Confidence interval of the sample mean, together with the quantities used to compute it
+    /// (point estimate, standard error, degrees of freedom, and critical value).
     ///
     /// Complexity: \\( O(1) \\)
     ///
-    #[allow(clippy::should_implement_trait)]
-    pub fn add(self, rhs: Self) -> Self {
-        Self {
-            log_space: self.log_space + rhs.log_space,
-        }
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+    /// let stats = mean::Arithmetic::from_iter(&data)?;
+    /// let detail = stats.ci_mean_detailed(Confidence::new_two_sided(0.95))?;
+    /// assert_eq!(detail.estimate, 5.5);
+    /// assert_eq!(detail.degrees_of_freedom, 9.);
+    /// assert_eq!(detail.distribution, mean::CriticalDistribution::StudentT);
+    /// assert_eq!(detail.interval, stats.ci_mean(Confidence::new_two_sided(0.95))?);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_mean_detailed(&self, confidence: Confidence) -> CIResult<CiDetail<F>> {
+        self.ci_mean_detailed_as(confidence)
     }
 
     ///
-    /// Compute the confidence interval on the mean of a sample
+    /// Confidence interval of the sample mean with the first-order skewness correction of
+    /// Johnson's modified t, which shifts the interval of [`Arithmetic::ci_mean`] by
+    /// \\( \frac{\hat{\mu}_3}{6 s^2 n} \\), where \\( \hat{\mu}_3 = \frac{n}{(n-1)(n-2)} \sum_{i=1}^n (x_i - \bar{x})^3 \\)
+    /// is the unbiased estimate of the third central moment and \\( s^2 \\) the sample variance.
     ///
-    /// # Arguments
+    /// For skewed data, the sample mean and the sample variance are correlated and the
+    /// t-interval misses the mean more often on the side of the longer tail. The shift moves the
+    /// interval toward that tail, so that the interval is no longer symmetric around the sample
+    /// mean and its coverage is closer to the nominal level for small samples.
     ///
-    /// * `confidence` - The confidence level of the interval
-    /// * `data` - The data to compute the confidence interval on
+    /// Complexity: \\( O(1) \\)
     ///
-    /// # Output
+    /// # Errors
     ///
-    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
     ///
-    /// # Errors
+    /// # Notes
     ///
-    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
-    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
-    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
-    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    /// The interval falls back to the symmetric interval of [`Arithmetic::ci_mean`] when the
+    /// third moment is unknown (the state was created from summary statistics, see
+    /// [`Arithmetic::from_summary`]) or overflows (e.g., for very large values), when there are fewer than three samples or the sample
+    /// variance is zero, and when the sample is large enough for the normal approximation
+    /// (see [`stats::uses_normal_approximation`]), where the shift, of order \\( 1/n \\), is negligible.
     ///
-    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
-    where
-        for<'a> &'a I: IntoIterator<Item = &'a F>,
-    {
-        Self::from_iter(data)?.ci_mean(confidence)
-    }
-}
-
-impl<F: Float> Default for Geometric<F> {
-    fn default() -> Self {
-        Self {
-            log_space: Arithmetic::default(),
+    /// # References
+    ///
+    /// * N. J. Johnson. "Modified t Tests and Confidence Intervals for Asymmetrical Populations." Journal of the American Statistical Association, 73(363):536–544, 1978.
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// let data = [0.1, 0.2, 0.2, 0.3, 0.5, 0.7, 1.1, 1.6, 2.4, 4.9];
+    /// let stats = mean::Arithmetic::from_iter(&data)?;
+    /// let confidence = Confidence::new(0.95);
+    /// let symmetric = stats.ci_mean(confidence)?;
+    /// let corrected = stats.ci_mean_skew_corrected(confidence)?;
+    /// // right-skewed data: the interval is shifted toward the upper tail
+    /// assert!(corrected.low() > symmetric.low());
+    /// assert!(corrected.high() > symmetric.high());
+    /// assert_eq!(corrected.width(), symmetric.width());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_mean_skew_corrected(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let detail = self.ci_mean_detailed(confidence)?;
+        let degrees_of_freedom = (self.count as f64) - 1.;
+        let moments = match self.moments {
+            Some(moments)
+                if self.count >= 3 && !stats::uses_normal_approximation(degrees_of_freedom) =>
+            {
+                moments.central(self.count)
+            }
+            _ => return Ok(detail.interval),
+        };
+        if moments.m2 <= F::zero() {
+            return Ok(detail.interval);
         }
+        // \hat{\mu}_3 / (6 s^2 n) with the sums of powers of the deviations
+        let shift = moments.m3 / (F::from(6 * (self.count - 2)).unwrap() * moments.m2);
+        if !shift.is_finite() {
+            return Ok(detail.interval);
+        }
+        let shift = ldexp(shift, self.scale);
+        let interval = stats::interval_bounds_adjusted(
+            confidence,
+            detail.estimate.try_f64("estimate")?,
+            detail.std_err.try_f64("std_err")?,
+            degrees_of_freedom,
+            shift.try_f64("shift")?,
+        )?;
+        Interval::from_finite_bounds_for(
+            confidence,
+            interval.low_f().try_narrow("low")?,
+            interval.high_f().try_narrow("high")?,
+        )
     }
-}
-
-impl<F: Float> core::ops::Add for Geometric<F> {
-    type Output = Self;
-
-    #[inline]
-    fn add(self, rhs: Self) -> Self::Output {
-        self.add(rhs)
-    }
-}
 
-impl<F: Float> core::ops::AddAssign for Geometric<F> {
-    #[inline]
-    fn add_assign(&mut self, rhs: Self) {
-        *self = self.add(rhs);
+    ///
+    /// Two-sided p-value of the one-sample t-test of the hypothesis that the mean of the population
+    /// is `hypothesized_mean`, i.e., \( 2 P(T > |t|) \) where \( t = (\bar{x} - \mu_0) / (s / \sqrt{n}) \)
+    /// and \( T \) follows the same distribution as the critical value of [`Arithmetic::ci_mean`].
+    ///
+    /// The p-value is the dual of the confidence interval on the mean: for any \( \alpha \),
+    /// \( p < \alpha \) if and only if the two-sided interval with confidence \( 1 - \alpha \)
+    /// excludes `hypothesized_mean`. When the sample variance is zero, the p-value is one if
+    /// `hypothesized_mean` equals the sample mean and zero otherwise.
+    ///
+    /// Complexity: \( O(1) \)
+    ///
+    /// # Arguments
+    ///
+    /// * `hypothesized_mean` - the mean of the population under the null hypothesis
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+    /// let stats = mean::Arithmetic::from_iter(&data)?;
+    /// let p_value = stats.p_value_vs(3.)?;
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(p_value, 0.02822, epsilon = 1e-5);
+    /// // significant at 5% but not at 1%
+    /// assert!(!stats.ci_mean(Confidence::new_two_sided(0.95))?.contains(&3.));
+    /// assert!(stats.ci_mean(Confidence::new_two_sided(0.99))?.contains(&3.));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn p_value_vs(&self, hypothesized_mean: F) -> CIResult<f64> {
+        self.ci_mean_detailed_as::<f64>(Confidence::default())?
+            .p_value(hypothesized_mean.try_f64("hypothesized_mean")?, None)
     }
-}
 
-///
-/// Trait for computing confidence intervals on the mean of a sample.
-///
-/// It is superseded by the [`StatisticsOps`] trait which allows incremental statistics.
-/// It is retained for backwards compatibility and will be deprecated in the future, as
-/// it brings no advantage over [`StatisticsOps`] and is less flexible.
-///
-/// # Examples
-///
-/// ```
-/// use stats_ci::*;
-/// let data = [
-///    82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
-///    15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
-///    71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
-///    98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
-///    49., 23., 26., 55., 26., 3., 23., 47., 27., 58., 27., 97., 32., 29., 56., 28., 23.,
-///    37., 72., 62., 77., 63., 100., 40., 84., 77., 39., 71., 61., 17., 77.,
-/// ];
-/// let confidence = Confidence::new_two_sided(0.95);
-/// let ci = mean::Arithmetic::ci(confidence, &data)?;
-/// // arithmetic mean: 52.5
-///
-/// use num_traits::Float;
-/// use approx::*;
-/// assert_abs_diff_eq!(ci, Interval::new(48.094823990767836, 59.24517600923217)?, epsilon = 1e-3);
-/// # Ok::<(),error::CIError>(())
-/// ```
-///
-pub trait MeanCI<T: PartialOrd> {
     ///
-    /// Compute the confidence interval on the mean of a sample
+    /// One-sided p-value of the one-sample t-test of the hypothesis that the mean of the population
+    /// is `hypothesized_mean`, against the given alternative: \( P(T > t) \) for
+    /// [`stats::Alternative::Greater`] and \( P(T < t) \) for [`stats::Alternative::Less`]
+    /// (see [`Arithmetic::p_value_vs`]).
     ///
-    /// # Arguments
+    /// For any \( \alpha \), \( p < \alpha \) if and only if the one-sided interval with
+    /// confidence \( 1 - \alpha \) excludes `hypothesized_mean`, where the interval is upper
+    /// one-sided (see [`Confidence::new_upper`]) for [`stats::Alternative::Greater`] and lower
+    /// one-sided (see [`Confidence::new_lower`]) for [`stats::Alternative::Less`].
     ///
-    /// * `confidence` - The confidence level of the interval
-    /// * `data` - The data to compute the confidence interval on
+    /// Complexity: \( O(1) \)
     ///
-    /// # Output
+    /// # Arguments
     ///
-    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    /// * `hypothesized_mean` - the mean of the population under the null hypothesis
+    /// * `alternative` - the alternative hypothesis
     ///
     /// # Errors
     ///
-    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
-    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
-    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
-    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
     ///
-    fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<T>>
-    where
-        for<'a> &'a I: IntoIterator<Item = &'a T>;
-}
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// use stats::Alternative;
+    /// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+    /// let stats = mean::Arithmetic::from_iter(&data)?;
+    /// let greater = stats.p_value_one_sided_vs(3., Alternative::Greater)?;
+    /// let less = stats.p_value_one_sided_vs(3., Alternative::Less)?;
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(greater, 0.01411, epsilon = 1e-5);
+    /// assert_abs_diff_eq!(greater + less, 1., epsilon = 1e-12);
+    /// assert!(!stats.ci_mean(Confidence::new_upper(0.98))?.contains(&3.));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn p_value_one_sided_vs(
+        &self,
+        hypothesized_mean: F,
+        alternative: stats::Alternative,
+    ) -> CIResult<f64> {
+        self.ci_mean_detailed_as::<f64>(Confidence::default())?
+            .p_value(
+                hypothesized_mean.try_f64("hypothesized_mean")?,
+                Some(alternative),
+            )
+    }
 
-macro_rules! impl_mean_ci_for {
-    ( $x:ty ) => {
-        impl<F: Float> MeanCI<F> for $x {
-            fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
-            where
-                for<'a> &'a I: IntoIterator<Item = &'a F>,
+    ///
+    /// Confidence interval of the sample mean using a critical value supplied by the caller.
+    ///
+    /// This is a low-level variant of [`Arithmetic::ci_mean`] that avoids computing the quantile of
+    /// Student's t distribution, which dominates the cost of [`Arithmetic::ci_mean`] for small samples.
+    /// It is meant for computing many intervals with the same confidence and sample size, with the
+    /// critical value computed once up front with [`stats::critical_value`].
+    ///
+    /// The caller is responsible for the critical value matching both `confidence` and the degrees of
+    /// freedom (the number of samples minus one); the result is the same as [`Arithmetic::ci_mean`] when it does.
+    /// Only the kind of `confidence` (two-sided or one-sided) is used to build the interval.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::InvalidCriticalValue`] - if `critical_value` is negative, infinite, or NaN
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// let (critical, _) = stats::critical_value(confidence, 9.)?;
+    /// for offset in 0..10 {
+    ///     let data = (0..10).map(|x| (x + offset) as f64).collect::<Vec<_>>();
+    ///     let stats = mean::Arithmetic::from_iter(&data)?;
+    ///     assert_eq!(stats.ci_mean_with_critical(confidence, critical)?, stats.ci_mean(confidence)?);
+    /// }
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_mean_with_critical(
+        &self,
+        confidence: Confidence,
+        critical_value: f64,
+    ) -> CIResult<Interval<F>> {
+        if self.count == 0 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        if !critical_value.is_finite() || critical_value < 0. {
+            return Err(CIError::InvalidCriticalValue(critical_value));
+        }
+        let mean = self.sample_mean().try_f64("stats.mean")?;
+        if self.count == 1 || self.sample_variance() == F::zero() {
+            let mean = mean.try_narrow("estimate")?;
+            return Interval::from_finite_bounds_for(confidence, mean, mean);
+        }
+        let std_dev = self.sample_std_dev().try_f64("stats.std_dev")?;
+        let std_err_mean = std_dev / (self.count as f64).sqrt();
+        interval_from_critical(
+            confidence,
+            mean.try_narrow("estimate")?,
+            std_err_mean.try_narrow("std_err")?,
+            critical_value.try_narrow("critical_value")?,
+        )
+    }
+
+    ///
+    /// Same as [`Arithmetic::ci_mean_detailed`], with the result expressed in another float type.
+    ///
+    fn ci_mean_detailed_as<G: Float>(&self, confidence: Confidence) -> CIResult<CiDetail<G>> {
+        if self.count == 0 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let mean = self.try_sample_mean()?.try_f64("stats.mean")?;
+        if self.count == 1 {
+            return CiDetail::degenerate(confidence, mean, 0.);
+        }
+        let variance = self.try_scaled_variance()?;
+        if variance == F::zero() {
+            return CiDetail::degenerate(confidence, mean, (self.count - 1) as f64);
+        }
+        let n = self.count as f64;
+        let std_dev = ldexp(variance.sqrt(), self.scale).try_f64("stats.std_dev")?;
+        let std_err_mean = std_dev / n.sqrt();
+        let degrees_of_freedom = n - 1.;
+        CiDetail::new(confidence, mean, std_err_mean, degrees_of_freedom)
+    }
+
+    ///
+    /// Confidence interval of the standard deviation of the population, assuming that the
+    /// population is normally distributed.
+    ///
+    /// The interval is obtained from the chi-squared distribution with \\( n-1 \\) degrees of freedom:
+    /// \\( \left[ \sqrt{\frac{(n-1)s^2}{\chi^2_{n-1}(q)}}, \sqrt{\frac{(n-1)s^2}{\chi^2_{n-1}(1-q)}} \right] \\)
+    /// where \\( s \\) is the sample standard deviation.
+    ///
+    /// Unlike the interval on the mean, this interval is sensitive to departures from normality.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are fewer than two samples
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+    /// let stats = mean::Arithmetic::from_iter(&data)?;
+    /// let ci = stats.ci_std_dev(Confidence::new_two_sided(0.95))?;
+    /// assert!(ci.contains(&stats.sample_std_dev()));
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(ci, Interval::new(2.0825, 5.5273)?, epsilon = 1e-4);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_std_dev(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let degrees_of_freedom = (self.count - 1) as f64;
+        // the bounds are computed at the scale of the state and multiplied back at the end
+        let variance = self
+            .try_scaled_variance()
+            .unwrap_or_else(|_| F::nan())
+            .try_f64("stats.variance")?;
+        let q = confidence.quantile();
+        let scaled = degrees_of_freedom * variance;
+        let low = (scaled / stats::chi_squared_value(q, degrees_of_freedom)?).sqrt();
+        let high = (scaled / stats::chi_squared_value(1. - q, degrees_of_freedom)?).sqrt();
+        let (low, high) = (ldexp(low, self.scale), ldexp(high, self.scale));
+        let low = low.try_narrow("low")?;
+        let high = high.try_narrow("high")?;
+        Interval::from_finite_bounds_for(confidence, low, high)
+    }
+
+    ///
+    /// Coefficient of variation of the sample \\( K = s / \bar{x} \\), where \\( s \\) is the sample
+    /// standard deviation and \\( \bar{x} \\) the sample mean.
+    ///
+    /// The coefficient of variation is only meaningful for data measured on a ratio scale, with a
+    /// strictly positive mean (e.g., durations or sizes); it is not defined otherwise.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are fewer than two samples
+    /// * [`CIError::NonPositiveMean`] - if the sample mean is zero or negative
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let stats = mean::Arithmetic::from_iter(&[9., 10., 11., 12., 8.])?;
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.sample_cv()?, 0.15811, epsilon = 1e-5);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn sample_cv(&self) -> CIResult<F> {
+        if self.count < 2 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        // the ratio does not depend on the scale of the state
+        let mean = self.try_scaled_mean()?;
+        if mean <= F::zero() || mean.is_nan() {
+            return Err(CIError::NonPositiveMean(
+                ldexp(mean, self.scale).try_f64("stats.mean")?,
+            ));
+        }
+        Ok(self.try_scaled_variance()?.sqrt() / mean)
+    }
+
+    ///
+    /// Confidence interval of the coefficient of variation of the population \\( \sigma / \mu \\),
+    /// assuming that the population is normally distributed with a positive mean.
+    ///
+    /// The interval is Vangel's modification of McKay's approximation. With \\( K \\) the sample
+    /// coefficient of variation (see [`Arithmetic::sample_cv`]) and \\( \nu = n - 1 \\), the bounds are
+    /// \\( K \left[ \left( \frac{u+2}{n} - 1 \right) K^2 + \frac{u}{\nu} \right]^{-1/2} \\)
+    /// with \\( u = \chi^2_{\nu}(q) \\) for the lower bound and \\( u = \chi^2_{\nu}(1-q) \\) for the upper bound.
+    /// The approximation is accurate for coefficients of variation up to about 0.3.
+    ///
+    /// The upper bound does not exist when the coefficient of variation is large relative to
+    /// the number of samples. In that case, a two-sided confidence yields the upper one-sided
+    /// interval \\( [lo, +\infty) \\), whereas a lower one-sided confidence is an error.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are fewer than two samples
+    /// * [`CIError::NonPositiveMean`] - if the sample mean is zero or negative
+    /// * [`CIError::CoefficientOfVariationTooLarge`] - if the required upper bound does not exist
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let data = [
+    ///     326., 302., 307., 299., 329., 297., 315., 313., 306., 292., 311., 305.,
+    /// ];
+    /// let stats = mean::Arithmetic::from_iter(&data)?;
+    /// let ci = stats.ci_cv(Confidence::new_two_sided(0.95))?;
+    /// assert!(ci.contains(&stats.sample_cv()?));
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(ci, Interval::new(0.02546, 0.06110)?, epsilon = 1e-5);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # References
+    ///
+    /// * A. T. McKay. Distribution of the coefficient of variation and the extended "t" distribution.
+    ///   Journal of the Royal Statistical Society, 95(4):695–698, 1932.
+    /// * M. G. Vangel. Confidence intervals for a normal coefficient of variation.
+    ///   The American Statistician, 50(1):21–26, 1996.
+    ///
+    pub fn ci_cv(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let cv = self.sample_cv()?.try_f64("stats.cv")?;
+        let n = self.count as f64;
+        let degrees_of_freedom = n - 1.;
+        let q = confidence.quantile();
+        let bound = |u: f64| {
+            let denominator = ((u + 2.) / n - 1.) * cv * cv + u / degrees_of_freedom;
+            if denominator > 0. {
+                cv / denominator.sqrt()
+            } else {
+                f64::INFINITY
+            }
+        };
+        let low = bound(stats::chi_squared_value(q, degrees_of_freedom)?);
+        let high = bound(stats::chi_squared_value(1. - q, degrees_of_freedom)?);
+        if !confidence.is_upper() && high == f64::INFINITY {
+            return match confidence {
+                Confidence::TwoSided(_) => Ok(Interval::new_upper(low.try_narrow("low")?)),
+                _ => Err(CIError::CoefficientOfVariationTooLarge(cv)),
+            };
+        }
+        let low = low.try_narrow("low")?;
+        let high = high.try_narrow("high")?;
+        Interval::from_finite_bounds_for(confidence, low, high)
+    }
+
+    ///
+    /// Confidence interval of a smooth function \\( f \\) of the mean of the population, computed
+    /// with the delta method.
+    ///
+    /// The point estimate is \\( f(\bar{x}) \\) and its standard error is that of the sample mean
+    /// scaled by \\( |f'(\bar{x})| \\). The interval is symmetric around the point estimate, so
+    /// that a decreasing function (negative derivative) yields the same interval as its mirror image.
+    /// The approximation is first-order and is accurate when the function is close to linear over
+    /// the confidence interval of the mean.
+    ///
+    /// As with [`Arithmetic::ci_mean`], the interval is degenerate at \\( f(\bar{x}) \\) when all
+    /// samples are identical.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    /// * `f` - the function of the mean
+    /// * `df` - the derivative of `f`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::InvalidDerivative`] - if the derivative at the mean is zero or not finite
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let stats = mean::Arithmetic::from_iter(&[1., 2., 3., 4., 5., 6., 7., 8., 9., 10.])?;
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// // interval on the square of the mean
+    /// let ci = stats.ci_of_fn(confidence, |x| x * x, |x| 2. * x)?;
+    /// assert!(ci.contains(&(5.5 * 5.5)));
+    /// // a linear function simply maps the interval on the mean
+    /// # use approx::*;
+    /// let ci = stats.ci_of_fn(confidence, |x| 3. * x + 1., |_| 3.)?;
+    /// assert_abs_diff_eq!(ci, stats.ci_mean(confidence)? * 3. + 1., epsilon = 1e-10);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # References
+    ///
+    /// * [Wikipedia article on the delta method](https://en.wikipedia.org/wiki/Delta_method)
+    ///
+    pub fn ci_of_fn(
+        &self,
+        confidence: Confidence,
+        f: impl Fn(F) -> F,
+        df: impl Fn(F) -> F,
+    ) -> CIResult<Interval<F>> {
+        let detail = self.ci_mean_detailed_as::<f64>(confidence)?;
+        let mean = self.sample_mean();
+        let estimate = f(mean).try_f64("f(mean)")?;
+        let slope = df(mean).try_f64("df(mean)")?;
+        if !slope.is_finite() || slope == 0. {
+            return Err(CIError::InvalidDerivative(slope));
+        }
+        if detail.std_err == 0. {
+            return CiDetail::degenerate(confidence, estimate, detail.degrees_of_freedom)
+                .map(|detail| detail.interval);
+        }
+        CiDetail::new(
+            confidence,
+            estimate,
+            slope.abs() * detail.std_err,
+            detail.degrees_of_freedom,
+        )
+        .map(|detail| detail.interval)
+    }
+
+    ///
+    /// Confidence interval of the reciprocal of the mean of the population \\( 1/\mu \\),
+    /// computed with the delta method (see [`Arithmetic::ci_of_fn`]).
+    ///
+    /// This is the quantity of interest when, for instance, measuring times per operation and
+    /// reporting operations per unit of time. Note that this differs from the harmonic mean of
+    /// the samples (see [`Harmonic`]), which is the reciprocal of the mean of the reciprocals.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::InvalidDerivative`] - if the mean is zero
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// // seconds per operation
+    /// let stats = mean::Arithmetic::from_iter(&[0.0101, 0.0098, 0.0103, 0.0099, 0.0100])?;
+    /// // operations per second
+    /// let ci = stats.ci_reciprocal_mean(Confidence::new_two_sided(0.95))?;
+    /// assert!(ci.contains(&(1. / stats.sample_mean())));
+    /// assert!(ci.contains(&100.));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_reciprocal_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.ci_of_fn(confidence, |x| x.recip(), |x| -(x * x).recip())
+    }
+
+    ///
+    /// Confidence interval of the logarithm of the mean of the population \\( \ln \mu \\),
+    /// computed with the delta method (see [`Arithmetic::ci_of_fn`]).
+    ///
+    /// Note that this differs from the logarithm of the geometric mean (see [`Geometric`]),
+    /// which is the mean of the logarithms.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::NonPositiveValue`] - if the mean is not strictly positive
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let stats = mean::Arithmetic::from_iter(&[1., 2., 3., 4., 5., 6., 7., 8., 9., 10.])?;
+    /// let ci = stats.ci_log_mean(Confidence::new_two_sided(0.95))?;
+    /// assert!(ci.contains(&5.5_f64.ln()));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_log_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let mean = self.sample_mean();
+        if self.count > 0 && mean <= F::zero() {
+            return Err(CIError::NonPositiveValue(mean.to_f64().unwrap_or(f64::NAN)));
+        }
+        self.ci_of_fn(confidence, |x| x.ln(), |x| x.recip())
+    }
+
+    ///
+    /// Confidence intervals on both the mean and the standard deviation of the sample,
+    /// bundled for reporting (see [`report::MeasurementCi`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are fewer than two samples
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    pub fn measurement_ci(&self, confidence: Confidence) -> CIResult<report::MeasurementCi<F>> {
+        Ok(report::MeasurementCi {
+            mean: self.ci_mean(confidence)?,
+            std_dev: self.ci_std_dev(confidence)?,
+            count: self.count,
+            confidence,
+        })
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Append `count` copies of the same value at once, e.g., from a histogram or a table of
+    /// frequencies.
+    ///
+    /// The state is the same, up to rounding, as appending `value` repeatedly `count` times.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the value to append
+    /// * `count` - the number of occurrences of `value` (nothing is appended if zero)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::CountOverflow`] - if the total number of samples does not fit in a `usize`;
+    ///   the state is left unchanged
+    ///
+    /// # Notes
+    ///
+    /// As with [`StatisticsOps::append`], the state switches to the rescaled mode (see [`Arithmetic`])
+    /// if the sums would overflow to infinity although `value` is finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = mean::Arithmetic::new();
+    /// stats.extend_counted(1., 3)?;
+    /// stats.extend_counted(5., 1)?;
+    /// assert_eq!(stats.sample_count(), 4);
+    /// assert_eq!(stats.sample_mean(), 2.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_counted(&mut self, value: F, count: u64) -> CIResult<()> {
+        if count == 0 {
+            return Ok(());
+        }
+        let total = usize::try_from(count)
+            .ok()
+            .and_then(|count| self.count.checked_add(count))
+            .ok_or(CIError::CountOverflow)?;
+        let weight = F::from(count).convert("count")?;
+        self.rescale_for(value, weight);
+        let value = ldexp(value, -self.scale);
+        self.sum += value * weight;
+        self.sum_sq += value * value * weight;
+        if let Some(moments) = &mut self.moments {
+            moments.append_counted(self.count, value, weight);
+        }
+        self.count = total;
+        Ok(())
+    }
+
+    ///
+    /// Create a new state from pairs of a value and its number of occurrences.
+    ///
+    /// See [`Arithmetic::extend_counted`].
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::CountOverflow`] - if the total number of samples does not fit in a `usize`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = mean::Arithmetic::from_counts([(1., 3), (5., 1)])?;
+    /// assert_eq!(stats, mean::Arithmetic::from_iter(&[1., 1., 1., 5.])?);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn from_counts(pairs: impl IntoIterator<Item = (F, u64)>) -> CIResult<Self> {
+        let mut stats = Self::new();
+        for (value, count) in pairs {
+            stats.extend_counted(value, count)?;
+        }
+        Ok(stats)
+    }
+
+    ///
+    /// Combine two states
+    ///
+    /// States in rescaled mode (see [`Arithmetic`]) are brought to the larger of their scales,
+    /// which is increased further if the combined sums would overflow.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, rhs: Self) -> Self {
+        let mut scale = self.scale.max(rhs.scale);
+        loop {
+            let (mut lhs, mut rhs) = (self, rhs);
+            lhs.rescale(scale);
+            rhs.rescale(scale);
+            let merged = lhs.add_same_scale(rhs);
+            if merged.sum_sq.value().is_finite()
+                || !lhs.sum_sq.value().is_finite()
+                || !rhs.sum_sq.value().is_finite()
             {
-                <$x>::ci(confidence, data)
+                return merged;
             }
+            scale += scale_step::<F>();
+        }
+    }
+
+    ///
+    /// Combine two states, failing instead of overflowing the number of samples
+    ///
+    /// See [`Arithmetic::add`].
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::CountOverflow`] - if the total number of samples does not fit in a `usize`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = mean::Arithmetic::from_iter(&[1., 2.])?;
+    /// let merged = stats.try_add(mean::Arithmetic::from_iter(&[3.])?)?;
+    /// assert_eq!(merged.sample_count(), 3);
+    /// assert_eq!(merged.sample_mean(), 2.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn try_add(self, rhs: Self) -> CIResult<Self> {
+        if self.count.checked_add(rhs.count).is_none() {
+            return Err(CIError::CountOverflow);
+        }
+        Ok(self.add(rhs))
+    }
+
+    fn add_same_scale(self, rhs: Self) -> Self {
+        let mut sum = self.sum;
+        let mut sum_sq = self.sum_sq;
+        sum += rhs.sum;
+        sum_sq += rhs.sum_sq;
+        let moments = match (self.moments, rhs.moments) {
+            (Some(lhs), Some(rhs_moments)) => Some(lhs.merge(self.count, rhs_moments, rhs.count)),
+            _ => None,
+        };
+        let count = self.count + rhs.count;
+        Self {
+            sum,
+            sum_sq,
+            count,
+            moments,
+            scale: self.scale,
+        }
+    }
+
+    ///
+    /// Create a state from a slice of data, accumulating fixed-size chunks separately and then
+    /// combining the chunk states strictly from left to right.
+    ///
+    /// With the `rayon` feature, the chunks are accumulated in parallel. In all cases, the
+    /// result depends only on `data` and `chunk_size`: it is bit-for-bit identical across runs,
+    /// regardless of the number of threads or of their scheduling.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the samples
+    /// * `chunk_size` - the number of samples per chunk (the last chunk may be shorter)
+    ///
+    /// # Panics
+    ///
+    /// * if `chunk_size` is zero
+    ///
+    /// # Notes
+    ///
+    /// Changing `chunk_size` changes the order of the floating-point operations and hence, slightly,
+    /// the result (at the level of rounding errors). Likewise, the result may differ slightly from
+    /// that of [`Arithmetic::from_iter`] unless `chunk_size` is at least the length of `data`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let data = (1..=10_000).map(|x| x as f64).collect::<Vec<_>>();
+    /// let stats = mean::Arithmetic::from_chunks_deterministic(&data, 1_024);
+    /// assert_eq!(stats.sample_count(), 10_000);
+    /// assert_eq!(stats.sample_mean(), 5_000.5);
+    /// assert_eq!(stats, mean::Arithmetic::from_chunks_deterministic(&data, 1_024));
+    /// ```
+    ///
+    pub fn from_chunks_deterministic(data: &[F], chunk_size: usize) -> Self
+    where
+        F: Send + Sync,
+    {
+        Self::from_chunks_mapped(data, chunk_size, |&x| x)
+    }
+
+    ///
+    /// Same as [`Arithmetic::from_chunks_deterministic`], but accumulating the values of `data`
+    /// transformed by `map` (e.g., the differences of pairs of observations).
+    ///
+    /// # Panics
+    ///
+    /// * if `chunk_size` is zero
+    ///
+    pub(crate) fn from_chunks_mapped<X, M>(data: &[X], chunk_size: usize, map: M) -> Self
+    where
+        F: Send + Sync,
+        X: Sync,
+        M: Fn(&X) -> F + Sync,
+    {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        let accumulate = |chunk: &[X]| {
+            let mut stats = Self::new();
+            for x in chunk {
+                stats.push(map(x));
+            }
+            stats
+        };
+
+        #[cfg(feature = "rayon")]
+        let chunks: Vec<Self> = {
+            use rayon::prelude::*;
+            // collecting an indexed parallel iterator preserves the order of the chunks
+            data.par_chunks(chunk_size).map(accumulate).collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let chunks: Vec<Self> = data.chunks(chunk_size).map(accumulate).collect();
+
+        chunks.into_iter().fold(Self::new(), Self::add)
+    }
+
+    ///
+    /// Append a chunk of samples from a slice, e.g., a block of a memory-mapped column.
+    ///
+    /// This is the slice-based counterpart of [`StatisticsOps::extend`], without the overhead of
+    /// an iterator adaptor.
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `chunk`
+    ///
+    /// # Notes
+    ///
+    /// The sums are accumulated in several independent registers that are combined at the end.
+    /// The result is therefore not bit-identical to appending the values one by one, but it is
+    /// just as accurate and remains deterministic.
+    ///
+    /// If the sums would overflow to infinity although the values are finite, the chunk is
+    /// appended value by value instead, switching the state to the rescaled mode (see [`Arithmetic`]).
+    /// The result is then always `Ok`, as for [`StatisticsOps::append`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = mean::Arithmetic::new();
+    /// stats.append_chunk(&[1., 2., 3.])?;
+    /// stats.append_chunk(&[4., 5.])?;
+    /// assert_eq!(stats.sample_count(), 5);
+    /// assert_eq!(stats.sample_mean(), 3.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_chunk(&mut self, chunk: &[F]) -> CIResult<()> {
+        self.extend_mapped(chunk, |x| x);
+        Ok(())
+    }
+
+    ///
+    /// Create a state by pulling chunks of samples from a source until it is exhausted, i.e.,
+    /// until `next_chunk` returns `None`.
+    ///
+    /// This suits data that is produced in blocks borrowed from a buffer or a memory map, which
+    /// do not fit in an [`IntoIterator`] of values. Each chunk is appended with
+    /// [`Arithmetic::append_chunk`].
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the total number of samples
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let column = (1..=1_000).map(|x| x as f64).collect::<Vec<_>>();
+    /// let mut blocks = column.chunks(256);
+    /// let stats = mean::Arithmetic::from_chunked_source(|| blocks.next())?;
+    /// assert_eq!(stats.sample_count(), 1_000);
+    /// assert_eq!(stats.sample_mean(), 500.5);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn from_chunked_source<'a>(
+        mut next_chunk: impl FnMut() -> Option<&'a [F]>,
+    ) -> CIResult<Self>
+    where
+        F: 'a,
+    {
+        let mut stats = Self::new();
+        while let Some(chunk) = next_chunk() {
+            stats.append_chunk(chunk)?;
+        }
+        Ok(stats)
+    }
+
+    ///
+    /// Append the values of `data`, transformed by `map`, in a tight loop.
+    ///
+    /// The sums (including those used for the higher moments) are accumulated in [`SLICE_LANES`]
+    /// independent lanes, which breaks the dependency chain of a single register.
+    /// The lanes are combined into the state at the end, in a fixed order, so the result is
+    /// deterministic (although not bit-identical to appending the values one by one).
+    /// If the sums overflow at the current scale, the values are appended one by one instead,
+    /// which rescales the state as needed.
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    pub(crate) fn extend_mapped(&mut self, data: &[F], map: impl Fn(F) -> F) {
+        let mut stats = *self;
+        stats.accumulate_lanes(data, &map);
+        if stats.sum_sq.value().is_finite() || !self.sum_sq.value().is_finite() {
+            *self = stats;
+        } else {
+            data.iter().for_each(|&x| self.push(map(x)));
+        }
+    }
+
+    ///
+    /// Accumulate the values of `data`, transformed by `map`, in lanes (see [`Arithmetic::extend_mapped`]).
+    ///
+    fn accumulate_lanes(&mut self, data: &[F], map: impl Fn(F) -> F) {
+        let Some(&first) = data.first() else {
+            return;
+        };
+        let scale = self.scale;
+        let shift = ldexp(map(first), -scale);
+        let mut sums = [utils::KahanSum::default(); SLICE_LANES];
+        let mut sums_sq = [utils::KahanSum::default(); SLICE_LANES];
+        let mut lane_moments = [ShiftedMoments::new(shift); SLICE_LANES];
+        // the last chunk may be shorter and then only fills the first lanes
+        for chunk in data.chunks(SLICE_LANES) {
+            for (lane, &x) in chunk.iter().enumerate() {
+                let x = ldexp(map(x), -scale);
+                sums[lane] += x;
+                sums_sq[lane] += x * x;
+                lane_moments[lane].accumulate(x);
+            }
+        }
+        let mut batch = ShiftedMoments::new(shift);
+        for lane in 0..SLICE_LANES {
+            self.sum += sums[lane];
+            self.sum_sq += sums_sq[lane];
+            for (total, lane_sum) in batch.sums.iter_mut().zip(lane_moments[lane].sums) {
+                *total = *total + lane_sum;
+            }
+        }
+        if let Some(moments) = self.moments {
+            self.moments = Some(moments.merge(self.count, batch, data.len()));
+        }
+        self.count += data.len();
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    ///
+    /// # Output
+    ///
+    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+}
+
+///
+/// Samples of durations, e.g., measured with [`std::time::Instant::elapsed`], collected as seconds.
+///
+#[cfg(feature = "std")]
+impl Arithmetic<f64> {
+    ///
+    /// Append a duration to the data, as a number of seconds (see [`std::time::Duration::as_secs_f64`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - the duration to append
+    ///
+    /// # Errors
+    ///
+    /// Same as [`StatisticsOps::append`].
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// use std::time::Duration;
+    /// let mut stats = mean::Arithmetic::new();
+    /// stats.append_duration(Duration::from_millis(250))?;
+    /// assert_eq!(stats.sample_mean(), 0.25);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_duration(&mut self, duration: std::time::Duration) -> CIResult<()> {
+        self.append(duration.as_secs_f64())
+    }
+
+    ///
+    /// Create a new state from durations consumed from an iterator, as numbers of seconds
+    /// (see [`Arithmetic::append_duration`]).
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of durations
+    ///
+    /// # Errors
+    ///
+    /// Same as [`StatisticsOps::append`].
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// use std::time::Duration;
+    /// let stats = mean::Arithmetic::from_durations((1..=3).map(Duration::from_secs))?;
+    /// assert_eq!(stats.sample_mean(), 2.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn from_durations<I>(durations: I) -> CIResult<Self>
+    where
+        I: IntoIterator<Item = std::time::Duration>,
+    {
+        let mut stats = Self::new();
+        stats.extend_iter(durations.into_iter().map(|duration| duration.as_secs_f64()))?;
+        Ok(stats)
+    }
+
+    ///
+    /// Confidence interval of the mean duration (see [`Arithmetic::ci_mean`]), with the bounds
+    /// converted back from seconds to durations, rounded to the nearest nanosecond.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::FloatConversionError`] - if a bound is too large to be represented as a duration
+    ///
+    /// # Notes
+    ///
+    /// Although durations are never negative, the interval on their mean is symmetric and its lower
+    /// bound is negative when the variance is large relative to the mean. Since a negative bound
+    /// cannot be represented as a duration, it saturates at [`std::time::Duration::ZERO`]; the
+    /// interval in seconds computed by [`Arithmetic::ci_mean`] retains the negative bound.
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// use std::time::Duration;
+    /// let durations = [10, 12, 11, 13, 9].map(Duration::from_millis);
+    /// let stats = mean::Arithmetic::from_durations(durations)?;
+    /// let ci = stats.ci_mean_duration(Confidence::new_two_sided(0.95))?;
+    /// assert!(ci.contains(&Duration::from_millis(11)));
+    /// assert_eq!(ci.low(), Some(Duration::from_nanos(9_036_757)));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_mean_duration(
+        &self,
+        confidence: Confidence,
+    ) -> CIResult<Interval<std::time::Duration>> {
+        Ok(match self.ci_mean(confidence)? {
+            Interval::TwoSided(low, high) => {
+                Interval::new(duration_from_secs(low)?, duration_from_secs(high)?)?
+            }
+            Interval::UpperOneSided(low) => Interval::new_upper(duration_from_secs(low)?),
+            Interval::LowerOneSided(high) => Interval::new_lower(duration_from_secs(high)?),
+        })
+    }
+}
+
+///
+/// Convert a number of seconds to a duration rounded to the nearest nanosecond, saturating at zero
+/// for negative values.
+///
+#[cfg(feature = "std")]
+fn duration_from_secs(secs: f64) -> CIResult<std::time::Duration> {
+    std::time::Duration::try_from_secs_f64(secs.max(0.)).map_err(|_| {
+        CIError::FloatConversionError(format!("Error converting {} seconds to Duration", secs))
+    })
+}
+
+///
+/// Flag of the encoded [`Arithmetic`] payload telling that the higher moments follow.
+///
+#[cfg(feature = "persist")]
+const PAYLOAD_MOMENTS: u8 = 1;
+
+///
+/// Flag of the encoded [`Arithmetic`] payload telling that the scale of the rescaled mode follows.
+///
+#[cfg(feature = "persist")]
+const PAYLOAD_RESCALED: u8 = 2;
+
+#[cfg(feature = "persist")]
+impl<F: Float> Arithmetic<F> {
+    ///
+    /// Encode the state with the stable binary format of [`persist`] (see the module for the layout).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let stats = mean::Arithmetic::from_iter(&[1., 2., 3.])?;
+    /// let restored = mean::Arithmetic::from_bytes(&stats.to_bytes())?;
+    /// assert_eq!(restored, stats);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = persist::Writer::new(persist::Kind::Arithmetic);
+        self.write_payload(&mut writer);
+        writer.finish()
+    }
+
+    ///
+    /// Decode a state encoded with [`Arithmetic::to_bytes`].
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DecodeError`] - if the input is truncated, has trailing bytes, has a wrong header
+    ///   (magic bytes, version, or kind of state), or contains a non-finite value
+    /// * [`CIError::FloatConversionError`] - if a value is out of range for `F`
+    ///
+    pub fn from_bytes(bytes: &[u8]) -> CIResult<Self> {
+        let mut reader = persist::Reader::new(bytes, persist::Kind::Arithmetic)?;
+        let stats = Self::read_payload(&mut reader)?;
+        reader.finish()?;
+        Ok(stats)
+    }
+
+    ///
+    /// Decode a state encoded with [`Arithmetic::to_bytes`] and combine it with this one
+    /// (see [`Arithmetic::try_add`]). The state is left unchanged if decoding or combining fails.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Arithmetic::from_bytes`], and:
+    ///
+    /// * [`CIError::CountOverflow`] - if the total number of samples does not fit in a `usize`
+    ///
+    pub fn merge_from_bytes(&mut self, bytes: &[u8]) -> CIResult<()> {
+        *self = self.try_add(Self::from_bytes(bytes)?)?;
+        Ok(())
+    }
+
+    pub(crate) fn write_payload(&self, writer: &mut persist::Writer) {
+        writer.usize(self.count);
+        for sum in [self.sum, self.sum_sq] {
+            writer.float(sum.sum_raw());
+            writer.float(sum.compensation());
+        }
+        let rescaled = if self.scale != 0 { PAYLOAD_RESCALED } else { 0 };
+        // higher moments that overflowed (e.g., fourth powers of very large values) are unknown
+        match self.moments.filter(|moments| {
+            moments.shift.is_finite() && moments.sums.iter().all(|x| x.is_finite())
+        }) {
+            Some(moments) => {
+                writer.u8(PAYLOAD_MOMENTS | rescaled);
+                writer.float(moments.shift);
+                moments.sums.iter().for_each(|&sum| writer.float(sum));
+            }
+            None => writer.u8(rescaled),
+        }
+        if self.scale != 0 {
+            writer.usize(self.scale as usize);
+        }
+    }
+
+    pub(crate) fn read_payload(reader: &mut persist::Reader) -> CIResult<Self> {
+        let count = reader.usize("count")?;
+        let sum = utils::KahanSum::from_raw_parts(reader.float("sum")?, reader.float("sum")?);
+        let sum_sq =
+            utils::KahanSum::from_raw_parts(reader.float("sum_sq")?, reader.float("sum_sq")?);
+        let flags = reader.u8()?;
+        if flags & !(PAYLOAD_MOMENTS | PAYLOAD_RESCALED) != 0 {
+            return Err(CIError::DecodeError(format!(
+                "invalid flags of the payload: {}",
+                flags
+            )));
+        }
+        let moments = if flags & PAYLOAD_MOMENTS != 0 {
+            let shift = reader.float("shift")?;
+            let mut sums = [F::zero(); 4];
+            for sum in sums.iter_mut() {
+                *sum = reader.float("moments")?;
+            }
+            Some(ShiftedMoments { shift, sums })
+        } else {
+            None
+        };
+        let scale = if flags & PAYLOAD_RESCALED != 0 {
+            let scale = reader.usize("scale")?;
+            // the scale must be positive and its factor must not underflow to zero
+            match i32::try_from(scale) {
+                Ok(scale) if scale > 0 && ldexp(F::one(), -scale) > F::zero() => scale,
+                _ => {
+                    return Err(CIError::DecodeError(format!(
+                        "scale out of range: {}",
+                        scale
+                    )))
+                }
+            }
+        } else {
+            0
+        };
+        Ok(Self {
+            sum,
+            sum_sq,
+            count,
+            moments,
+            scale,
+        })
+    }
+}
+
+impl<F: Float> core::ops::Add for Arithmetic<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for Arithmetic<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs);
+    }
+}
+
+impl<F: Float> utils::Accumulator for Arithmetic<F> {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+///
+/// Represents the state of the computation of the arithmetic mean of `f32` samples, with all
+/// sums accumulated in `f64` (mixed precision).
+///
+/// With [`Arithmetic<f32>`], the sums are accumulated in `f32`. This is enough for moderate sample
+/// sizes, but the rounding errors of the sums (notably of the squared deviations) grow with the
+/// sample size, and large values require rescaling the sums.
+/// This type keeps the `f32` interface (samples, results, and intervals) while accumulating in an
+/// [`Arithmetic<f64>`], so that the mean and variance of millions of `f32` samples remain accurate.
+/// The results are rounded to `f32` only at the end.
+///
+/// It is best used through the [`StatisticsOps`] trait.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // one million samples in [1000, 1001): the standard deviation is small relative to the mean
+/// let data = (0..1_000_000)
+///     .map(|i| 1_000. + (i % 1_000) as f32 / 1_000.)
+///     .collect::<Vec<f32>>();
+/// let stats = mean::ArithmeticF32::from_iter(&data)?;
+/// let reference = 0.288_675_1_f32; // sqrt((1 - 1e-6) / 12)
+/// assert!((stats.sample_std_dev() - reference).abs() < 1e-6);
+///
+/// // the same computation with f32 accumulators loses several significant digits
+/// let narrow = mean::Arithmetic::<f32>::from_iter(&data)?;
+/// assert!((narrow.sample_std_dev() - reference).abs() > 1e-4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArithmeticF32 {
+    wide: Arithmetic<f64>,
+}
+
+impl ArithmeticF32 {
+    ///
+    /// Create a new empty state
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Append a new sample to the data
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append(&mut self, x: f32) -> CIResult<()> {
+        self.wide.append(x as f64)
+    }
+
+    ///
+    /// The underlying state, with the sums accumulated in `f64`.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn wide(&self) -> &Arithmetic<f64> {
+        &self.wide
+    }
+
+    ///
+    /// Mean of the sample
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_mean(&self) -> f32 {
+        self.wide.sample_mean() as f32
+    }
+
+    ///
+    /// Sample variance (with Bessel's correction)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_variance(&self) -> f32 {
+        self.wide.sample_variance() as f32
+    }
+
+    ///
+    /// Sample standard deviation (with Bessel's correction)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_std_dev(&self) -> f32 {
+        self.wide.sample_std_dev() as f32
+    }
+
+    ///
+    /// Standard error of the sample mean
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_sem(&self) -> f32 {
+        self.wide.sample_sem() as f32
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.wide.sample_count()
+    }
+
+    ///
+    /// Confidence interval of the sample mean
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<f32>> {
+        self.ci_mean_detailed(confidence)
+            .map(|detail| detail.interval)
+    }
+
+    ///
+    /// Confidence interval of the sample mean, together with the quantities used to compute it
+    /// (see [`Arithmetic::ci_mean_detailed`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn ci_mean_detailed(&self, confidence: Confidence) -> CIResult<CiDetail<f32>> {
+        self.wide.ci_mean_detailed_as(confidence)
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<f32>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a f32>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+}
+
+impl StatisticsOps<f32> for ArithmeticF32 {
+    #[inline]
+    fn append(&mut self, x: f32) -> CIResult<()> {
+        self.append(x)
+    }
+    #[inline]
+    fn sample_mean(&self) -> f32 {
+        self.sample_mean()
+    }
+    #[inline]
+    fn sample_sem(&self) -> f32 {
+        self.sample_sem()
+    }
+    #[inline]
+    fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<f32>> {
+        self.ci_mean(confidence)
+    }
+    #[inline]
+    fn sample_count(&self) -> usize {
+        self.sample_count()
+    }
+}
+
+impl MeanCI<f32> for ArithmeticF32 {
+    fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<f32>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a f32>,
+    {
+        Self::ci(confidence, data)
+    }
+}
+
+impl core::ops::Add for ArithmeticF32 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            wide: self.wide + rhs.wide,
+        }
+    }
+}
+
+impl core::ops::AddAssign for ArithmeticF32 {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.wide += rhs.wide;
+    }
+}
+
+impl utils::Accumulator for ArithmeticF32 {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+///
+/// Represents the state of the computation related to the harmonic mean.
+/// This is a simple implementation that accumulates information about the samples, such as sum and sum of squares.
+/// It is implemented as a wrapper around [`Arithmetic`] to compute the arithmetic mean of the reciprocals of the samples.
+///
+/// It is best used through the [`StatisticsOps`] trait.
+///
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Harmonic<F: Float> {
+    recip_space: Arithmetic<F>,
+}
+
+impl<F: Float> Harmonic<F> {
+    ///
+    /// Create a new empty state
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let mut stats = mean::Harmonic::new();
+    /// stats.append(10.)?;
+    /// assert_eq!(stats.sample_count(), 1);
+    /// assert_eq!(stats.sample_mean(), 10.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Append a new sample to the data
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append(&mut self, x: F) -> CIResult<()> {
+        if x <= F::zero() {
+            return Err(error::CIError::NonPositiveValue(
+                x.to_f64().unwrap_or(f64::NAN),
+            ));
+        }
+        self.recip_space.append(F::one() / x)?;
+        Ok(())
+    }
+
+    ///
+    /// Append all the samples of a slice.
+    ///
+    /// This is an alternative to [`StatisticsOps::extend`] for data held in a slice:
+    /// the values are validated in a first pass, and their reciprocals are then accumulated in a
+    /// tight loop (see the notes below).
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to append
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValueAt`] - If `data` contains a non-positive value, with the index of the first one.
+    ///   In that case, the state is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let mut stats = mean::Harmonic::new();
+    /// stats.extend_slice(&[1., 2., 4.])?;
+    /// assert_eq!(stats.sample_count(), 3);
+    /// assert!(matches!(
+    ///     stats.extend_slice(&[1., 0., -1.]),
+    ///     Err(error::CIError::NonPositiveValueAt { index: 1, value: _ })
+    /// ));
+    /// assert_eq!(stats.sample_count(), 3);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The sums are accumulated in several independent registers that are combined at the end.
+    /// The result is therefore not bit-identical to appending the values one by one, but it is
+    /// just as accurate and remains deterministic.
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_slice(&mut self, data: &[F]) -> CIResult<()> {
+        check_positive(data)?;
+        self.recip_space.extend_mapped(data, |x| F::one() / x);
+        Ok(())
+    }
+
+    ///
+    /// Create a new state from the samples of a slice.
+    ///
+    /// This is the slice-based counterpart of [`StatisticsOps::from_iter`]; see [`Self::extend_slice`].
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValueAt`] - If `data` contains a non-positive value, with the index of the first one.
+    ///
+    pub fn from_slice(data: &[F]) -> CIResult<Self> {
+        let mut stats = Self::new();
+        stats.extend_slice(data)?;
+        Ok(stats)
+    }
+
+    ///
+    /// Append `count` copies of the same value at once, e.g., from a histogram or a table of
+    /// frequencies.
+    ///
+    /// The reciprocal of `value` is accumulated with [`Arithmetic::extend_counted`].
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - if `value` is not strictly positive
+    /// * [`CIError::CountOverflow`] - if the total number of samples does not fit in a `usize`
+    ///
+    /// In both cases, the state is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = mean::Harmonic::new();
+    /// stats.extend_counted(2., 3)?;
+    /// assert_eq!(stats.sample_count(), 3);
+    /// assert_eq!(stats, mean::Harmonic::from_iter(&[2., 2., 2.])?);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_counted(&mut self, value: F, count: u64) -> CIResult<()> {
+        if value <= F::zero() {
+            return Err(error::CIError::NonPositiveValue(
+                value.to_f64().unwrap_or(f64::NAN),
+            ));
+        }
+        self.recip_space.extend_counted(F::one() / value, count)
+    }
+
+    ///
+    /// Create a new state from pairs of a value and its number of occurrences.
+    ///
+    /// See [`Harmonic::extend_counted`].
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - if a value is not strictly positive
+    /// * [`CIError::CountOverflow`] - if the total number of samples does not fit in a `usize`
+    ///
+    pub fn from_counts(pairs: impl IntoIterator<Item = (F, u64)>) -> CIResult<Self> {
+        let mut stats = Self::new();
+        for (value, count) in pairs {
+            stats.extend_counted(value, count)?;
+        }
+        Ok(stats)
+    }
+
+    ///
+    /// Harmonic mean of the sample
+    /// \\( H = \left( \frac{1}{n} \sum_i \frac{1}{x_i} \right)^{-1} \\)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_mean(&self) -> F {
+        F::one() / self.recip_space.sample_mean()
+    }
+
+    ///
+    /// Standard error of the harmonic mean
+    /// \\( s_H = \frac{1}{\alpha^2} \frac{s_{1/x_i}}{\sqrt{n}} \\)
+    ///
+    /// where
+    /// * the estimate of \\( \alpha \\) is given by \\( \alpha = \frac{1}{n} \sum_i 1/x_i \\);
+    /// * \\( s_{1/x_i} \\) is the estimate of the standard deviation of the reciprocals of the samples;
+    /// * and \\( n \\) is the number of samples, as for [`Arithmetic::sample_sem`].
+    ///
+    /// # Reference
+    ///
+    /// * Nilan Noris. "The standard errors of the geometric and harmonic means and their application to index numbers." Ann. Math. Statist. 11(4): 445-448 (December, 1940). DOI: [10.1214/aoms/1177731830](https://doi.org/10.1214/aoms/1177731830) [JSTOR](https://www.jstor.org/stable/2235727)
+    ///
+    pub fn sample_sem(&self) -> F {
+        div_sqrt_count(self.harmonic_std_dev(), self.recip_space.sample_count())
+            .unwrap_or_else(|_| F::nan())
+    }
+
+    ///
+    /// Dispersion of the sample around the harmonic mean
+    /// \\( s_H^* = H^2 s_{1/x_i} \\)
+    ///
+    /// where \\( H \\) is the harmonic mean of the sample and \\( s_{1/x_i} \\) is the estimate of the
+    /// standard deviation of the reciprocals of the samples.
+    /// This is the first-order (delta method) approximation of the standard deviation mapped back from the
+    /// reciprocal space, such that the standard error of the harmonic mean is \\( s_H^* / \sqrt{n-1} \\)
+    /// (see [`Harmonic::sample_sem`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let stats = mean::Harmonic::from_iter(&[1., 2., 4.])?;
+    /// let h = stats.sample_mean();
+    /// let recip_std_dev = stats.reciprocal_space().sample_std_dev();
+    /// assert_abs_diff_eq!(stats.harmonic_std_dev(), h * h * recip_std_dev, epsilon = 1e-10);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn harmonic_std_dev(&self) -> F {
+        let harm_mean = self.sample_mean();
+        harm_mean * harm_mean * self.recip_space.sample_std_dev()
+    }
+
+    ///
+    /// Statistics of the reciprocals of the samples, on which the harmonic mean is computed.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = mean::Harmonic::from_iter(&[1., 2., 4.])?;
+    /// assert_eq!(stats.reciprocal_space().sample_mean(), 1.75 / 3.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn reciprocal_space(&self) -> &Arithmetic<F> {
+        &self.recip_space
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.recip_space.sample_count()
+    }
+
+    ///
+    /// Confidence interval for the harmonic mean
+    ///
+    /// As with [`Arithmetic::ci_mean`], the interval is degenerate at the harmonic mean when all
+    /// samples are identical.
+    ///
+    /// The interval is obtained by inverting the bounds of the interval on the mean of the
+    /// reciprocals. With very dispersed data, the lower bound of the latter can reach zero or
+    /// below, in which case no upper bound on the harmonic mean can be derived:
+    /// * with a two-sided confidence, the result falls back to the upper one-sided interval
+    ///   \( [lo, +\infty) \), keeping the lower bound that remains meaningful;
+    /// * with a lower one-sided confidence, no bound remains and an error is returned.
+    ///
+    /// The same applies when the inversion of a bound overflows to infinity (e.g., with `f32`
+    /// and a lower bound on the mean of the reciprocals that is positive but tiny).
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::UninterpretableTransformedInterval`] - if the confidence is lower one-sided and the
+    ///   interval on the mean of the reciprocals crosses zero, or if a bound required by the
+    ///   confidence overflows to infinity (except for the upper bound of a two-sided interval, as above)
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = mean::Harmonic::from_iter(&[0.01, 100., 100.])?;
+    /// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+    /// assert!(ci.is_upper());
+    /// assert!(ci.low_f() > 0.);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let arith_ci = self.recip_space.ci_mean(confidence.flipped())?;
+        let (lo, hi) = (F::one() / arith_ci.high_f(), F::one() / arith_ci.low_f());
+        if !confidence.is_upper() && arith_ci.low_f() <= F::zero() {
+            // the interval in reciprocal space crosses zero: the upper bound is unbounded
+            return match confidence {
+                Confidence::LowerOneSided(_) => {
+                    Err(CIError::UninterpretableTransformedInterval(format!(
+                        "lower bound of the mean of the reciprocals is not positive: {}",
+                        arith_ci.low_f().to_f64().unwrap_or(f64::NAN)
+                    )))
+                }
+                _ => Interval::try_new_finite(lo, F::infinity()).map_err(|e| e.into()),
+            };
+        }
+        transformed_interval(confidence, lo, hi, "harmonic")
+    }
+
+    ///
+    /// Combine two states
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, rhs: Self) -> Self {
+        Self {
+            recip_space: self.recip_space + rhs.recip_space,
+        }
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    ///
+    /// # Output
+    ///
+    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+}
+
+impl<F: Float + core::fmt::Debug> core::fmt::Debug for Harmonic<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Harmonic")
+            .field("count", &self.sample_count())
+            .field("mean", &self.sample_mean())
+            .field("reciprocal_space", &self.recip_space)
+            .finish()
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<F: Float + approx::AbsDiffEq<Epsilon = F>> approx::AbsDiffEq for Harmonic<F> {
+    type Epsilon = F;
+
+    fn default_epsilon() -> F {
+        F::default_epsilon()
+    }
+
+    ///
+    /// Compare the states in reciprocal space (see [`Arithmetic`]).
+    ///
+    fn abs_diff_eq(&self, other: &Self, epsilon: F) -> bool {
+        self.recip_space.abs_diff_eq(&other.recip_space, epsilon)
+    }
+}
+
+impl<F: Float> Default for Harmonic<F> {
+    fn default() -> Self {
+        Self {
+            recip_space: Arithmetic::default(),
+        }
+    }
+}
+
+impl<F: Float> core::ops::Add for Harmonic<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for Harmonic<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs);
+    }
+}
+
+impl<F: Float> utils::Accumulator for Harmonic<F> {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+///
+/// Represents the state of the computation of the geometric mean.
+/// This is a simple implementation that accumulates information about the samples, such as sum and sum of squares.
+/// It is implemented as a wrapper around [`Arithmetic`] to compute the arithmetic mean of the logarithms of the samples.
+///
+/// It is best used through the [`StatisticsOps`] trait.
+///
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Geometric<F: Float> {
+    log_space: Arithmetic<F>,
+}
+
+impl<F: Float> Geometric<F> {
+    ///
+    /// Create a new empty state
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let mut stats = mean::Geometric::new();
+    /// stats.append(10.)?;
+    /// assert_eq!(stats.sample_count(), 1);
+    /// assert_abs_diff_eq!(stats.sample_mean(), 10., epsilon = 1e-10);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Append a new sample to the data
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append(&mut self, x: F) -> CIResult<()> {
+        if x <= F::zero() {
+            return Err(error::CIError::NonPositiveValue(
+                x.to_f64().unwrap_or(f64::NAN),
+            ));
+        }
+        self.log_space.append(x.ln())?;
+        Ok(())
+    }
+
+    ///
+    /// Append all the samples of a slice.
+    ///
+    /// This is an alternative to [`StatisticsOps::extend`] for data held in a slice:
+    /// the values are validated in a first pass, and their logarithms are then accumulated in a
+    /// tight loop (see the notes below).
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data to append
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValueAt`] - If `data` contains a non-positive value, with the index of the first one.
+    ///   In that case, the state is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let mut stats = mean::Geometric::new();
+    /// stats.extend_slice(&[1., 2., 4.])?;
+    /// assert_eq!(stats.sample_count(), 3);
+    /// assert!(matches!(
+    ///     stats.extend_slice(&[1., 0., -1.]),
+    ///     Err(error::CIError::NonPositiveValueAt { index: 1, value: _ })
+    /// ));
+    /// assert_eq!(stats.sample_count(), 3);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// The sums are accumulated in several independent registers that are combined at the end.
+    /// The result is therefore not bit-identical to appending the values one by one, but it is
+    /// just as accurate and remains deterministic.
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_slice(&mut self, data: &[F]) -> CIResult<()> {
+        check_positive(data)?;
+        self.log_space.extend_mapped(data, F::ln);
+        Ok(())
+    }
+
+    ///
+    /// Create a new state from the samples of a slice.
+    ///
+    /// This is the slice-based counterpart of [`StatisticsOps::from_iter`]; see [`Self::extend_slice`].
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValueAt`] - If `data` contains a non-positive value, with the index of the first one.
+    ///
+    pub fn from_slice(data: &[F]) -> CIResult<Self> {
+        let mut stats = Self::new();
+        stats.extend_slice(data)?;
+        Ok(stats)
+    }
+
+    ///
+    /// Append `count` copies of the same value at once, e.g., from a histogram or a table of
+    /// frequencies.
+    ///
+    /// The logarithm of `value` is accumulated with [`Arithmetic::extend_counted`].
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - if `value` is not strictly positive
+    /// * [`CIError::CountOverflow`] - if the total number of samples does not fit in a `usize`
+    ///
+    /// In both cases, the state is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = mean::Geometric::new();
+    /// stats.extend_counted(2., 3)?;
+    /// assert_eq!(stats.sample_count(), 3);
+    /// assert_eq!(stats, mean::Geometric::from_iter(&[2., 2., 2.])?);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_counted(&mut self, value: F, count: u64) -> CIResult<()> {
+        if value <= F::zero() {
+            return Err(error::CIError::NonPositiveValue(
+                value.to_f64().unwrap_or(f64::NAN),
+            ));
+        }
+        self.log_space.extend_counted(value.ln(), count)
+    }
+
+    ///
+    /// Create a new state from pairs of a value and its number of occurrences.
+    ///
+    /// See [`Geometric::extend_counted`].
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of pairs
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValue`] - if a value is not strictly positive
+    /// * [`CIError::CountOverflow`] - if the total number of samples does not fit in a `usize`
+    ///
+    pub fn from_counts(pairs: impl IntoIterator<Item = (F, u64)>) -> CIResult<Self> {
+        let mut stats = Self::new();
+        for (value, count) in pairs {
+            stats.extend_counted(value, count)?;
+        }
+        Ok(stats)
+    }
+
+    ///
+    /// Geometric mean of the sample
+    ///
+    pub fn sample_mean(&self) -> F {
+        self.log_space.sample_mean().exp()
+    }
+
+    ///
+    /// Standard error of the geometric mean
+    ///
+    /// Computed as: \\( G \frac{s_{\log x_i}}{\sqrt{n}} \\)
+    /// where \\( G \\) is the geometric mean of the sample;
+    /// \\( s_{\log x_i} \\) is the estimate of the standard deviation of the logarithms of the samples;
+    /// and \\( n \\) is the number of samples, as for [`Arithmetic::sample_sem`].
+    ///
+    ///  # Reference
+    ///
+    /// * Nilan Noris. "The standard errors of the geometric and harmonic means and their application to index numbers." Ann. Math. Statist. 11(4): 445-448 (December, 1940). DOI: [10.1214/aoms/1177731830](https://doi.org/10.1214/aoms/1177731830) [JSTOR](https://www.jstor.org/stable/2235727)
+    ///
+    pub fn sample_sem(&self) -> F {
+        let geom_mean = self.sample_mean();
+        let log_std_dev = self.log_space.sample_std_dev();
+        div_sqrt_count(geom_mean * log_std_dev, self.log_space.sample_count())
+            .unwrap_or_else(|_| F::nan())
+    }
+
+    ///
+    /// Geometric standard deviation of the sample
+    /// \\( \sigma_G = \exp(s_{\log x_i}) \\)
+    ///
+    /// where \\( s_{\log x_i} \\) is the estimate of the standard deviation of the logarithms of the samples
+    /// (same as `scipy.stats.gstd` with its default `ddof=1`).
+    /// It is a multiplicative factor: about 68% of log-normally distributed data lies within
+    /// \\( [G / \sigma_G, G \cdot \sigma_G] \\).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let stats = mean::Geometric::from_iter(&[1., 10., 100.])?;
+    /// assert_abs_diff_eq!(stats.sample_mean(), 10., epsilon = 1e-10);
+    /// assert_abs_diff_eq!(stats.geometric_std_dev(), 10., epsilon = 1e-10);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn geometric_std_dev(&self) -> F {
+        self.log_space.sample_std_dev().exp()
+    }
+
+    ///
+    /// Statistics of the logarithms of the samples, on which the geometric mean is computed.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let stats = mean::Geometric::from_iter(&[1., 10., 100.])?;
+    /// assert_abs_diff_eq!(stats.log_space().sample_mean(), 10_f64.ln(), epsilon = 1e-10);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn log_space(&self) -> &Arithmetic<F> {
+        &self.log_space
+    }
+
+    ///
+    /// Number of samples
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.log_space.sample_count()
+    }
+
+    ///
+    /// Confidence interval for the geometric mean
+    ///
+    /// As with [`Arithmetic::ci_mean`], the interval is degenerate at the geometric mean when all
+    /// samples are identical.
+    ///
+    /// The interval is obtained by exponentiating the bounds of the interval on the mean of the
+    /// logarithms, which can overflow to infinity for large bounds (especially with `f32`).
+    /// In that case, consistently with [`Harmonic::ci_mean`]:
+    /// * with a two-sided confidence, the result falls back to the upper one-sided interval
+    ///   \( [lo, +\infty) \), keeping the lower bound that remains meaningful;
+    /// * with a lower one-sided confidence, no bound remains and an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::UninterpretableTransformedInterval`] - if a bound required by the confidence
+    ///   overflows to infinity (except for the upper bound of a two-sided interval, as above)
+    ///
+    /// # Example
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = mean::Geometric::from_iter(&[1e30_f32, 1e38, 1e37])?;
+    /// let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+    /// assert!(ci.is_upper());
+    /// assert!(ci.low_f().is_finite());
+    /// assert!(matches!(
+    ///     stats.ci_mean(Confidence::new_lower(0.95)),
+    ///     Err(error::CIError::UninterpretableTransformedInterval(_))
+    /// ));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        let arith_ci = self.log_space.ci_mean(confidence)?;
+        let (lo, hi) = (arith_ci.low_f().exp(), arith_ci.high_f().exp());
+        transformed_interval(confidence, lo, hi, "geometric")
+    }
+
+    ///
+    /// Combine two states
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, rhs: Self) -> Self {
+        Self {
+            log_space: self.log_space + rhs.log_space,
+        }
+    }
+
+    ///
+    /// Compute the confidence interval on the mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    ///
+    /// # Output
+    ///
+    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    pub fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        Self::from_iter(data)?.ci_mean(confidence)
+    }
+}
+
+impl<F: Float + core::fmt::Debug> core::fmt::Debug for Geometric<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Geometric")
+            .field("count", &self.sample_count())
+            .field("mean", &self.sample_mean())
+            .field("log_space", &self.log_space)
+            .finish()
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<F: Float + approx::AbsDiffEq<Epsilon = F>> approx::AbsDiffEq for Geometric<F> {
+    type Epsilon = F;
+
+    fn default_epsilon() -> F {
+        F::default_epsilon()
+    }
+
+    ///
+    /// Compare the states in log space (see [`Arithmetic`]).
+    ///
+    fn abs_diff_eq(&self, other: &Self, epsilon: F) -> bool {
+        self.log_space.abs_diff_eq(&other.log_space, epsilon)
+    }
+}
+
+impl<F: Float> Default for Geometric<F> {
+    fn default() -> Self {
+        Self {
+            log_space: Arithmetic::default(),
+        }
+    }
+}
+
+impl<F: Float> core::ops::Add for Geometric<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for Geometric<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs);
+    }
+}
+
+impl<F: Float> utils::Accumulator for Geometric<F> {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+///
+/// Represents the state of the computation of the arithmetic, geometric, and harmonic means of
+/// the same samples, collected in a single pass.
+///
+/// Each sample is appended to an [`Arithmetic`], a [`Geometric`], and a [`Harmonic`] state,
+/// so that any of the three intervals can be computed without ingesting the data again.
+/// Non-positive samples are still counted in the arithmetic mean, but cannot be represented in
+/// log space and reciprocal space: they are rejected from the geometric and harmonic states,
+/// whose intervals then fail since they would no longer describe the same samples.
+///
+/// # Example
+/// ```
+/// use stats_ci::*;
+/// let data = [82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7.];
+/// let mut stats = mean::Combined::new();
+/// stats.extend(&data)?;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// assert_eq!(stats.ci_arithmetic(confidence)?, mean::Arithmetic::ci(confidence, &data)?);
+/// assert_eq!(stats.ci_geometric(confidence)?, mean::Geometric::ci(confidence, &data)?);
+/// assert_eq!(stats.ci_harmonic(confidence)?, mean::Harmonic::ci(confidence, &data)?);
+///
+/// // a non-positive value poisons the geometric and harmonic means only
+/// stats.append(0.)?;
+/// assert_eq!(stats.non_positive_count(), 1);
+/// assert!(stats.ci_arithmetic(confidence).is_ok());
+/// assert!(stats.ci_geometric(confidence).is_err());
+/// assert!(stats.ci_harmonic(confidence).is_err());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Combined<F: Float> {
+    arithmetic: Arithmetic<F>,
+    geometric: Geometric<F>,
+    harmonic: Harmonic<F>,
+    non_positive_count: usize,
+    first_non_positive: Option<(usize, F)>,
+}
+
+impl<F: Float> Combined<F> {
+    ///
+    /// Create a new empty state
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Append a new sample to the data.
+    ///
+    /// Non-positive samples (and NaN) are only appended to the arithmetic state, and poison the
+    /// geometric and harmonic states (see [`Combined::non_positive_count`]).
+    ///
+    /// The sums of the states are rescaled when they would overflow (see [`Arithmetic`]),
+    /// so appending a sample always succeeds.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append(&mut self, x: F) -> CIResult<()> {
+        let mut next = *self;
+        next.arithmetic.append(x)?;
+        if x > F::zero() {
+            next.geometric.append(x)?;
+            next.harmonic.append(x)?;
+        } else {
+            next.non_positive_count += 1;
+            if next.first_non_positive.is_none() {
+                next.first_non_positive = Some((self.arithmetic.sample_count(), x));
+            }
+        }
+        *self = next;
+        Ok(())
+    }
+
+    ///
+    /// Extend the data with additional sample data, borrowed from a collection such as an array,
+    /// a slice, or a vector.
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend<I: ?Sized>(&mut self, data: &I) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        self.extend_iter(data.into_iter().copied())
+    }
+
+    ///
+    /// Extend the data with samples consumed from an iterator, such as an iterator adapter.
+    ///
+    /// Complexity: \\( O(n) \\), where \\( n \\) is the number of elements in `data`
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_iter<I>(&mut self, data: I) -> CIResult<()>
+    where
+        I: IntoIterator<Item = F>,
+    {
+        for x in data {
+            self.append(x)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Create a new state from sample data borrowed from a collection.
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I: ?Sized>(data: &I) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        let mut stats = Self::new();
+        stats.extend(data)?;
+        Ok(stats)
+    }
+
+    ///
+    /// Number of samples, including the non-positive ones.
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.arithmetic.sample_count()
+    }
+
+    ///
+    /// Number of non-positive samples (or NaN), which are excluded from the geometric and
+    /// harmonic states. The number of samples in these states is
+    /// `sample_count() - non_positive_count()`.
+    ///
+    pub fn non_positive_count(&self) -> usize {
+        self.non_positive_count
+    }
+
+    ///
+    /// State of the arithmetic mean of all the samples.
+    ///
+    pub fn arithmetic(&self) -> &Arithmetic<F> {
+        &self.arithmetic
+    }
+
+    ///
+    /// State of the geometric mean of the positive samples.
+    ///
+    pub fn geometric(&self) -> &Geometric<F> {
+        &self.geometric
+    }
+
+    ///
+    /// State of the harmonic mean of the positive samples.
+    ///
+    pub fn harmonic(&self) -> &Harmonic<F> {
+        &self.harmonic
+    }
+
+    ///
+    /// Confidence interval of the arithmetic mean (see [`Arithmetic::ci_mean`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Arithmetic::ci_mean`].
+    ///
+    pub fn ci_arithmetic(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.arithmetic.ci_mean(confidence)
+    }
+
+    ///
+    /// Confidence interval of the geometric mean (see [`Geometric::ci_mean`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValueAt`] - if some sample is not strictly positive, with the
+    ///   index and the value of the first one
+    /// * other errors are the same as [`Geometric::ci_mean`]
+    ///
+    pub fn ci_geometric(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.check_positive()?;
+        self.geometric.ci_mean(confidence)
+    }
+
+    ///
+    /// Confidence interval of the harmonic mean (see [`Harmonic::ci_mean`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::NonPositiveValueAt`] - if some sample is not strictly positive, with the
+    ///   index and the value of the first one
+    /// * other errors are the same as [`Harmonic::ci_mean`]
+    ///
+    pub fn ci_harmonic(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.check_positive()?;
+        self.harmonic.ci_mean(confidence)
+    }
+
+    fn check_positive(&self) -> CIResult<()> {
+        match self.first_non_positive {
+            Some((index, x)) => Err(CIError::NonPositiveValueAt {
+                index,
+                value: x.to_f64().unwrap_or(f64::NAN),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<F: Float> Default for Combined<F> {
+    fn default() -> Self {
+        Self {
+            arithmetic: Arithmetic::default(),
+            geometric: Geometric::default(),
+            harmonic: Harmonic::default(),
+            non_positive_count: 0,
+            first_non_positive: None,
+        }
+    }
+}
+
+impl<F: Float + core::fmt::Debug> core::fmt::Debug for Combined<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Combined")
+            .field("arithmetic", &self.arithmetic)
+            .field("geometric", &self.geometric)
+            .field("harmonic", &self.harmonic)
+            .field("non_positive_count", &self.non_positive_count)
+            .field("first_non_positive", &self.first_non_positive)
+            .finish()
+    }
+}
+
+///
+/// Merge the states of two disjoint sets of samples, as if all samples had been appended to a
+/// single instance, those of `self` first.
+///
+impl<F: Float> core::ops::Add for Combined<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        let first_non_positive = self.first_non_positive.or(rhs
+            .first_non_positive
+            .map(|(index, x)| (self.arithmetic.sample_count() + index, x)));
+        Self {
+            arithmetic: self.arithmetic + rhs.arithmetic,
+            geometric: self.geometric + rhs.geometric,
+            harmonic: self.harmonic + rhs.harmonic,
+            non_positive_count: self.non_positive_count + rhs.non_positive_count,
+            first_non_positive,
+        }
+    }
+}
+
+impl<F: Float> core::ops::AddAssign for Combined<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<F: Float> utils::Accumulator for Combined<F> {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+///
+/// Represents the trimmed and winsorized statistics of a sample, which are robust to outliers.
+///
+/// With a trim fraction \\( \gamma \\), the \\( g = \lfloor \gamma n \rfloor \\) smallest and
+/// \\( g \\) largest values of the sample are either dropped (trimmed mean, computed on the
+/// \\( h = n - 2g \\) retained values) or replaced by the nearest retained value (winsorized
+/// sample). The confidence interval on the trimmed mean is Yuen's interval:
+/// \\[
+/// \bar{x}_t \pm t_{h-1} \frac{s_w}{(1 - 2\gamma) \sqrt{n}}
+/// \\]
+/// where \\( s_w^2 \\) is the variance of the winsorized sample. With \\( \gamma = 0 \\), this is
+/// the interval of [`Arithmetic::ci_mean`].
+///
+/// Unlike the other statistics of this module, the state cannot be built incrementally: the whole
+/// sample must be buffered and sorted when the state is created, which requires \\( O(n) \\)
+/// memory and \\( O(n \log n) \\) time. The state then only keeps the summaries of the trimmed and
+/// winsorized samples.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// // benchmark timings with an outlier
+/// let data = [10.2, 10.4, 9.9, 10.1, 10.3, 10.0, 10.2, 9.8, 10.1, 42.0];
+/// let stats = mean::Trimmed::from_iter_with(&data, 0.1)?;
+/// assert_eq!(stats.retained_count(), 8);
+/// assert_abs_diff_eq!(stats.sample_trimmed_mean(), 10.15, epsilon = 1e-10);
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let ci = stats.ci_mean(confidence)?;
+/// assert!(ci.high_f() < 10.5);
+/// // the outlier drags the plain interval away
+/// assert!(mean::Arithmetic::ci(confidence, &data)?.high_f() > 15.);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * K. K. Yuen. The two-sample trimmed t for unequal population variances. Biometrika, 61(1):165–170, 1974.
+/// * R. R. Wilcox. Introduction to Robust Estimation and Hypothesis Testing. Academic Press, 2012.
+/// * R package `WRS2`, functions `trimci` and `winci`
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trimmed<F: Float> {
+    trim_fraction: f64,
+    count: usize,
+    is_constant: bool,
+    trimmed: Arithmetic<F>,
+    winsorized: Arithmetic<F>,
+}
+
+impl<F: Float> Trimmed<F> {
+    ///
+    /// Create the state from a sample and a trim fraction.
+    ///
+    /// Complexity: \\( O(n \log n) \\) time and \\( O(n) \\) memory, where \\( n \\) is the
+    /// number of elements in `data`
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the sample
+    /// * `trim_fraction` - the fraction \\( \gamma \\) of the values trimmed on each side
+    ///   (e.g., 0.1 for the 10% trimmed mean, or 0.2 as recommended by Wilcox)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidTrimFraction`] - if the trim fraction is not in \\( [0, 0.5) \\)
+    /// * [`CIError::InvalidInputData`] - if the data contains NaN values
+    ///
+    pub fn from_iter_with<I: ?Sized>(data: &I, trim_fraction: f64) -> CIResult<Self>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a F>,
+    {
+        if !(0. ..0.5).contains(&trim_fraction) {
+            return Err(CIError::InvalidTrimFraction(trim_fraction));
+        }
+        let mut sorted = data.into_iter().copied().collect::<Vec<_>>();
+        if sorted.iter().any(|x| x.is_nan()) {
+            return Err(CIError::InvalidInputData);
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let count = sorted.len();
+        let trimmed_count = (trim_fraction * count as f64).floor() as usize;
+        let retained = &sorted[trimmed_count..count - trimmed_count];
+        let mut trimmed = Arithmetic::new();
+        trimmed.extend_iter(retained.iter().copied())?;
+        let mut winsorized = Arithmetic::new();
+        if let (Some(&low), Some(&high)) = (retained.first(), retained.last()) {
+            winsorized.extend_iter(sorted.iter().map(|&x| x.max(low).min(high)))?;
+        }
+        Ok(Self {
+            trim_fraction,
+            count,
+            is_constant: sorted.first() == sorted.last(),
+            trimmed,
+            winsorized,
+        })
+    }
+
+    ///
+    /// Return the fraction of the values trimmed on each side.
+    ///
+    pub fn trim_fraction(&self) -> f64 {
+        self.trim_fraction
+    }
+
+    ///
+    /// Return the number of samples \\( n \\).
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Return the number of values retained by the trimming \\( h = n - 2 \lfloor \gamma n \rfloor \\).
+    ///
+    pub fn retained_count(&self) -> usize {
+        self.trimmed.sample_count()
+    }
+
+    ///
+    /// Trimmed mean of the sample, i.e., the mean of the retained values.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_trimmed_mean(&self) -> F {
+        self.trimmed.sample_mean()
+    }
+
+    ///
+    /// Winsorized mean of the sample, i.e., the mean of the sample where the trimmed values are
+    /// replaced by the nearest retained value.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_winsorized_mean(&self) -> F {
+        self.winsorized.sample_mean()
+    }
+
+    ///
+    /// Variance of the winsorized sample \\( s_w^2 \\).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_winsorized_variance(&self) -> F {
+        self.winsorized.sample_variance()
+    }
+
+    ///
+    /// Confidence interval of the trimmed mean (Yuen's interval).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples, or if a single value is retained
+    ///   from a sample that is not constant
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+    /// let confidence = Confidence::new_upper(0.95);
+    /// let ci = mean::Trimmed::from_iter_with(&data, 0.)?.ci_mean(confidence)?;
+    /// assert_eq!(ci, mean::Arithmetic::from_iter(&data)?.ci_mean(confidence)?);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.ci_detailed(confidence, &self.trimmed)
+            .map(|detail| detail.interval)
+    }
+
+    ///
+    /// Confidence interval of the winsorized mean.
+    ///
+    /// The interval is centered on the winsorized mean with the same standard error and degrees of
+    /// freedom as [`Self::ci_mean`].
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples, or if a single value is retained
+    ///   from a sample that is not constant
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    pub fn ci_winsorized_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.ci_detailed(confidence, &self.winsorized)
+            .map(|detail| detail.interval)
+    }
+
+    ///
+    /// Interval centered on the mean of `center` with Yuen's standard error.
+    ///
+    fn ci_detailed(&self, confidence: Confidence, center: &Arithmetic<F>) -> CIResult<CiDetail<F>> {
+        if self.count == 0 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let estimate = center.try_sample_mean()?.try_f64("estimate")?;
+        let retained = self.retained_count();
+        if retained == 1 {
+            // the winsorized sample is constant even if the data is not
+            if !self.is_constant {
+                return Err(CIError::TooFewSamples(retained));
+            }
+            return CiDetail::degenerate(confidence, estimate, 0.);
+        }
+        let std_dev = self
+            .winsorized
+            .sample_std_dev()
+            .try_f64("winsorized.std_dev")?;
+        let degrees_of_freedom = (retained - 1) as f64;
+        if std_dev == 0. {
+            return CiDetail::degenerate(confidence, estimate, degrees_of_freedom);
+        }
+        let n = self.count as f64;
+        let std_err = std_dev / ((1. - 2. * self.trim_fraction) * n.sqrt());
+        CiDetail::new(confidence, estimate, std_err, degrees_of_freedom)
+    }
+}
+
+///
+/// Represents the state of the computation of the arithmetic means of several named columns
+/// (e.g., the fields of a struct) collected in a single pass over the data.
+///
+/// Each row appended must contain exactly one value per column, so that all columns always
+/// hold the same number of samples.
+///
+/// # Example
+/// ```
+/// use stats_ci::*;
+/// struct BenchResult {
+///     wall_time: f64,
+///     cycles: f64,
+/// }
+/// let results = [
+///     BenchResult { wall_time: 1.2, cycles: 1_000. },
+///     BenchResult { wall_time: 1.4, cycles: 1_150. },
+///     BenchResult { wall_time: 1.1, cycles: 980. },
+///     BenchResult { wall_time: 1.3, cycles: 1_070. },
+/// ];
+/// let mut stats = mean::Columns::new(&["wall_time", "cycles"]);
+/// for result in &results {
+///     stats.append_with(result, &[|r: &BenchResult| r.wall_time, |r: &BenchResult| r.cycles])?;
+/// }
+/// assert_eq!(stats.sample_count(), 4);
+///
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let summary = stats.summary(confidence)?;
+/// let (mean, ci) = summary["wall_time"];
+/// # use approx::*;
+/// assert_abs_diff_eq!(mean, 1.25, epsilon = 1e-10);
+/// assert!(ci.contains(&mean));
+/// assert_eq!(ci, stats.ci_mean("wall_time", confidence)?);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Columns<F: Float> {
+    names: Vec<String>,
+    columns: Vec<Arithmetic<F>>,
+    count: usize,
+}
+
+impl<F: Float> Default for Columns<F> {
+    fn default() -> Self {
+        Self {
+            names: Vec::new(),
+            columns: Vec::new(),
+            count: 0,
+        }
+    }
+}
+
+impl<F: Float> Columns<F> {
+    ///
+    /// Create a new empty state with one column for each of the given names
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let stats = mean::Columns::<f64>::new(&["x", "y"]);
+    /// assert_eq!(stats.names(), &["x", "y"]);
+    /// assert_eq!(stats.sample_count(), 0);
+    /// ```
+    ///
+    pub fn new<S: AsRef<str>>(names: &[S]) -> Self {
+        Self {
+            names: names.iter().map(|name| name.as_ref().to_string()).collect(),
+            columns: vec![Arithmetic::new(); names.len()],
+            count: 0,
+        }
+    }
+
+    ///
+    /// Names of the columns, in order
+    ///
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    ///
+    /// Number of columns
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    ///
+    /// Number of rows appended so far (i.e., number of samples in every column)
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// State of the column with the given name, if any
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of columns
+    ///
+    pub fn column(&self, name: &str) -> Option<&Arithmetic<F>> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|index| &self.columns[index])
+    }
+
+    ///
+    /// Append a row of values, one for each column and in the order of the columns
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of columns
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidRowLength`] - if the row does not have exactly one value per column;
+    ///   in that case, the state is left unchanged
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_row(&mut self, row: &[F]) -> CIResult<()> {
+        if row.len() != self.columns.len() {
+            return Err(CIError::InvalidRowLength {
+                len: row.len(),
+                expected: self.columns.len(),
+            });
+        }
+        for (column, &x) in self.columns.iter_mut().zip(row) {
+            column.append(x)?;
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    ///
+    /// Append a row obtained by applying each projection to the given item.
+    /// The projections are given in the order of the columns.
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of columns
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidRowLength`] - if there is not exactly one projection per column;
+    ///   in that case, the state is left unchanged
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append_with<T, P>(&mut self, item: &T, projections: &[P]) -> CIResult<()>
+    where
+        P: Fn(&T) -> F,
+    {
+        if projections.len() != self.columns.len() {
+            return Err(CIError::InvalidRowLength {
+                len: projections.len(),
+                expected: self.columns.len(),
+            });
+        }
+        for (column, projection) in self.columns.iter_mut().zip(projections) {
+            column.append(projection(item))?;
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    ///
+    /// Confidence interval of the mean of the column with the given name
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of columns
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::UnknownColumn`] - if there is no column with that name
+    ///
+    pub fn ci_mean(&self, name: &str, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.column(name)
+            .ok_or_else(|| CIError::UnknownColumn(name.to_string()))?
+            .ci_mean(confidence)
+    }
+
+    ///
+    /// Sample mean and confidence interval of the mean of every column, indexed by column name
+    ///
+    /// Complexity: \\( O(k \log k) \\) where \\( k \\) is the number of columns
+    ///
+    pub fn summary(
+        &self,
+        confidence: Confidence,
+    ) -> CIResult<std::collections::BTreeMap<String, (F, Interval<F>)>> {
+        self.names
+            .iter()
+            .zip(&self.columns)
+            .map(|(name, column)| {
+                Ok((
+                    name.clone(),
+                    (column.sample_mean(), column.ci_mean(confidence)?),
+                ))
+            })
+            .collect()
+    }
+
+    ///
+    /// Combine two states over the same columns
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of columns
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two states do not have the same column names; see [`Self::try_add`] for a
+    /// fallible combination.
+    ///
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, rhs: Self) -> Self {
+        assert_eq!(
+            self.names, rhs.names,
+            "Cannot combine states over different columns"
+        );
+        let columns = self
+            .columns
+            .into_iter()
+            .zip(rhs.columns)
+            .map(|(a, b)| a + b)
+            .collect();
+        Self {
+            names: self.names,
+            columns,
+            count: self.count + rhs.count,
+        }
+    }
+
+    ///
+    /// Combine two states over the same columns, or report that their columns differ
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of columns
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentColumns`] - if the two states do not have the same column names,
+    ///   in the same order
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let mut stats = mean::Columns::new(&["x", "y"]);
+    /// stats.append_row(&[1., 2.])?;
+    /// let mut other = mean::Columns::new(&["x", "y"]);
+    /// other.append_row(&[3., 4.])?;
+    /// let merged = stats.clone().try_add(other)?;
+    /// assert_eq!(merged.sample_count(), 2);
+    ///
+    /// assert!(matches!(
+    ///     stats.try_add(mean::Columns::new(&["x", "z"])),
+    ///     Err(error::CIError::DifferentColumns { .. })
+    /// ));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn try_add(self, rhs: Self) -> CIResult<Self> {
+        if self.names != rhs.names {
+            return Err(CIError::DifferentColumns {
+                names_a: self.names,
+                names_b: rhs.names,
+            });
+        }
+        Ok(self.add(rhs))
+    }
+}
+
+///
+/// Combination of two states over the same columns.
+///
+/// # Panics
+///
+/// Panics if the two states do not have the same column names; see [`Columns::try_add`] for a
+/// fallible combination.
+///
+impl<F: Float> core::ops::Add for Columns<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+///
+/// In-place combination of two states over the same columns.
+///
+/// # Panics
+///
+/// Panics if the two states do not have the same column names; see [`Columns::try_add`] for a
+/// fallible combination.
+///
+impl<F: Float> core::ops::AddAssign for Columns<F> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = core::mem::take(self).add(rhs);
+    }
+}
+
+impl<F: Float> utils::Accumulator for Columns<F> {
+    fn reset(&mut self) -> Self {
+        let empty = Self::new(&self.names);
+        core::mem::replace(self, empty)
+    }
+}
+
+///
+/// Represents the state of the computation of an exponentially weighted moving average (EWMA)
+/// of a sample, with an approximate confidence interval on the current level of a process that
+/// drifts over time.
+///
+/// Each new sample \\( x_t \\) updates the average as \\( m_t = (1 - \alpha) m_{t-1} + \alpha x_t \\),
+/// starting from the first sample, so that a sample \\( k \\) steps old has a weight proportional
+/// to \\( (1 - \alpha)^k \\). A large smoothing factor \\( \alpha \\) follows changes quickly but
+/// averages over few samples, and a small one the converse.
+///
+/// The effective sample size is \\( n_{\text{eff}} = 1 / \sum_i w_i^2 \\) for the normalized weights
+/// \\( w_i \\) of the samples: it is the number of equally weighted samples whose mean has the
+/// same variance as the weighted mean. It grows with the number of samples toward
+/// \\( (2 - \alpha) / \alpha \\) (see [`Ewma::effective_sample_size`]).
+///
+/// After a shift of the level of the process, the weight of the samples before the shift decays
+/// as \\( (1 - \alpha)^k \\) after \\( k \\) steps: the average has covered 95% of the shift after
+/// about \\( 3 / \alpha \\) steps, and 99% after about \\( 4.6 / \alpha \\) steps, whereas the mean of
+/// all the samples (e.g., [`Arithmetic`]) is only ever moved by the fraction of samples taken since the shift.
+///
+/// # Approximation
+///
+/// The confidence interval of [`Ewma::ci_mean`] treats the samples as independent with a common
+/// mean and variance, i.e., it assumes that the level is (nearly) constant over the last
+/// \\( n_{\text{eff}} \\) samples or so. The variance is estimated by the exponentially weighted
+/// variance, corrected for bias as for reliability weights, and the critical value is taken from
+/// Student's t distribution with \\( n_{\text{eff}} - 1 \\) degrees of freedom.
+/// The interval is therefore only approximate: it ignores the bias of the average while the level
+/// drifts, and it is too narrow if successive samples are positively correlated.
+///
+/// # Combining states
+///
+/// Unlike the other means, the weighted average depends on the order of the samples, so two
+/// states accumulated separately cannot, in general, be combined: the state does not record when
+/// each sample was taken, and hence how the samples of the two states would interleave.
+/// The only exception is that of replicas of the process observed in lockstep, i.e., states with
+/// the same smoothing factor and the same number of samples, where the \\( t \\)-th samples of both
+/// states were taken at the same time. [`Ewma::try_add`] combines such states and returns an error
+/// otherwise.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new(0.95);
+/// let mut stats = mean::Ewma::new(0.1)?;
+/// let mut all_history = mean::Arithmetic::new();
+/// // the level of the process shifts from about 10 to about 20
+/// for i in 0..200 {
+///     let noise = [-0.3, 0.1, 0.4, -0.2][i % 4];
+///     let x = if i < 100 { 10. } else { 20. } + noise;
+///     stats.append(x)?;
+///     all_history.append(x)?;
+/// }
+/// assert!(stats.ci_mean(confidence)?.contains(&20.));
+/// assert!(!all_history.ci_mean(confidence)?.contains(&20.));
+/// # use approx::*;
+/// assert_abs_diff_eq!(stats.effective_sample_size(), 19., epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "EwmaRepr<F>"))]
+pub struct Ewma<F: Float> {
+    alpha: F,
+    count: usize,
+    mean: F,
+    /// exponentially weighted variance, biased (divided by the sum of the weights)
+    variance: F,
+    /// sum of the squares of the normalized weights of the samples
+    sum_sq_weights: F,
+}
+
+///
+/// Unvalidated mirror of [`Ewma`], used to check the smoothing factor when deserializing.
+///
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Ewma")]
+struct EwmaRepr<F> {
+    alpha: F,
+    count: usize,
+    mean: F,
+    variance: F,
+    sum_sq_weights: F,
+}
+
+#[cfg(feature = "serde")]
+impl<F: Float> TryFrom<EwmaRepr<F>> for Ewma<F> {
+    type Error = CIError;
+
+    fn try_from(repr: EwmaRepr<F>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            count: repr.count,
+            mean: repr.mean,
+            variance: repr.variance,
+            sum_sq_weights: repr.sum_sq_weights,
+            ..Self::new(repr.alpha)?
+        })
+    }
+}
+
+impl<F: Float> Ewma<F> {
+    ///
+    /// Create a new empty state with the smoothing factor `alpha`, i.e., the weight of each new
+    /// sample relative to the current average.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - the smoothing factor, in the range (0, 1]
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidSmoothingFactor`] - if `alpha` is not in the range (0, 1]
+    ///
+    pub fn new(alpha: F) -> CIResult<Self> {
+        if alpha > F::zero() && alpha <= F::one() {
+            Ok(Self {
+                alpha,
+                count: 0,
+                mean: F::zero(),
+                variance: F::zero(),
+                sum_sq_weights: F::zero(),
+            })
+        } else {
+            Err(CIError::InvalidSmoothingFactor(
+                alpha.to_f64().unwrap_or(f64::NAN),
+            ))
+        }
+    }
+
+    ///
+    /// Return the smoothing factor of the state.
+    ///
+    pub fn alpha(&self) -> F {
+        self.alpha
+    }
+
+    ///
+    /// Append a new sample to the data.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if `x` is not finite (the state is left unchanged)
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn append(&mut self, x: F) -> CIResult<()> {
+        if !x.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        if self.count == 0 {
+            self.mean = x;
+            self.variance = F::zero();
+            self.sum_sq_weights = F::one();
+        } else {
+            let alpha = self.alpha;
+            let decay = F::one() - alpha;
+            let diff = x - self.mean;
+            self.mean = self.mean + alpha * diff;
+            self.variance = decay * (self.variance + alpha * diff * diff);
+            self.sum_sq_weights = decay * decay * self.sum_sq_weights + alpha * alpha;
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    ///
+    /// Append the samples of `data`, in order.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidInputData`] - if some sample is not finite (the samples before it are appended)
+    ///
+    pub fn extend<I: IntoIterator<Item = F>>(&mut self, data: I) -> CIResult<()> {
+        data.into_iter().try_for_each(|x| self.append(x))
+    }
+
+    ///
+    /// Return the number of samples appended so far.
+    ///
+    pub fn sample_count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Return the exponentially weighted moving average of the samples (NaN if there are none).
+    ///
+    pub fn mean(&self) -> F {
+        if self.count == 0 {
+            F::nan()
+        } else {
+            self.mean
+        }
+    }
+
+    ///
+    /// Return the effective sample size \\( n_{\text{eff}} = 1 / \sum_i w_i^2 \\) of the weighted
+    /// average (zero if there are no samples).
+    ///
+    /// It is exact for the number of samples appended so far: it is 1 for a single sample and
+    /// increases toward \\( (2 - \alpha) / \alpha \\) as samples are appended, e.g., 39 for
+    /// \\( \alpha = 0.05 \\).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = mean::Ewma::new(0.05)?;
+    /// stats.extend((0..1000).map(|i| i as f64))?;
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.effective_sample_size(), 39., epsilon = 1e-9);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn effective_sample_size(&self) -> F {
+        if self.count == 0 {
+            F::zero()
+        } else {
+            self.sum_sq_weights.recip()
+        }
+    }
+
+    ///
+    /// Return the exponentially weighted variance of the samples, corrected for bias
+    /// (NaN if there are fewer than two samples or if \\( \alpha = 1 \\)).
+    ///
+    /// The weighted sum of squared deviations is divided by \\( 1 - \sum_i w_i^2 \\), which
+    /// amounts to the usual \\( n - 1 \\) denominator for equal weights.
+    ///
+    pub fn sample_variance(&self) -> F {
+        self.variance / (F::one() - self.sum_sq_weights)
+    }
+
+    ///
+    /// Return the standard error of the weighted average, i.e.,
+    /// \\( \sqrt{s^2 / n_{\text{eff}}} \\) for the corrected variance \\( s^2 \\).
+    ///
+    pub fn sample_sem(&self) -> F {
+        (self.sample_variance() * self.sum_sq_weights).sqrt()
+    }
+
+    ///
+    /// Approximate confidence interval of the current level of the process, centered on the
+    /// weighted average (see the approximation made in [`Ewma`]).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - if there are no samples
+    /// * [`CIError::FloatConversionError`] - if some value cannot be converted to/from a float
+    ///
+    /// # Notes
+    ///
+    /// As for [`Arithmetic::ci_mean`], the interval is degenerate (a single point) if the
+    /// weighted variance is zero, which is always the case with a single sample or with
+    /// \\( \alpha = 1 \\).
+    ///
+    pub fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<F>> {
+        self.ci_mean_detailed(confidence)
+            .map(|detail| detail.interval)
+    }
+
+    ///
+    /// Same as [`Ewma::ci_mean`], together with the quantities used to compute the interval.
+    /// The degrees of freedom are \\( n_{\text{eff}} - 1 \\).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Ewma::ci_mean`].
+    ///
+    pub fn ci_mean_detailed(&self, confidence: Confidence) -> CIResult<CiDetail<F>> {
+        if self.count == 0 {
+            return Err(CIError::TooFewSamples(self.count));
+        }
+        let mean = self.mean.try_f64("stats.mean")?;
+        let sum_sq_weights = self.sum_sq_weights.try_f64("stats.sum_sq_weights")?;
+        let degrees_of_freedom = (1. - sum_sq_weights) / sum_sq_weights;
+        if self.variance == F::zero() || sum_sq_weights >= 1. {
+            return CiDetail::degenerate(confidence, mean, degrees_of_freedom.max(0.));
+        }
+        let std_err = self.sample_sem().try_f64("stats.std_err")?;
+        CiDetail::new(confidence, mean, std_err, degrees_of_freedom)
+    }
+
+    ///
+    /// Combine the states of replicas of the process observed in lockstep, i.e., such that the
+    /// \\( t \\)-th samples of both states were taken at the same time.
+    ///
+    /// The result is the state of the average of the replicas: its average is the average of
+    /// the two states, each step counting the samples of both replicas with equal weights.
+    /// New samples appended to the result are weighted as a single replica.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DifferentSmoothingFactors`] - if the states have different smoothing factors
+    /// * [`CIError::DifferentSampleSizes`] - if the states have different numbers of samples
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut a = mean::Ewma::new(0.2)?;
+    /// let mut b = mean::Ewma::new(0.2)?;
+    /// a.extend([1., 2., 3.])?;
+    /// b.extend([3., 4., 5.])?;
+    /// let merged = a.try_add(b)?;
+    /// assert_eq!(merged.sample_count(), 6);
+    /// assert_eq!(merged.mean(), (a.mean() + b.mean()) / 2.);
+    ///
+    /// b.append(6.)?;
+    /// assert!(a.try_add(b).is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn try_add(self, rhs: Self) -> CIResult<Self> {
+        if self.alpha != rhs.alpha {
+            return Err(CIError::DifferentSmoothingFactors {
+                alpha_a: self.alpha.try_f64("alpha_a")?,
+                alpha_b: rhs.alpha.try_f64("alpha_b")?,
+            });
+        }
+        if self.count != rhs.count {
+            return Err(CIError::DifferentSampleSizes {
+                size_a: self.count,
+                size_b: rhs.count,
+            });
+        }
+        if self.count == 0 {
+            return Ok(self);
+        }
+        let two = F::one() + F::one();
+        let half_diff = (self.mean - rhs.mean) / two;
+        Ok(Self {
+            alpha: self.alpha,
+            count: self.count + rhs.count,
+            mean: (self.mean + rhs.mean) / two,
+            variance: (self.variance + rhs.variance) / two + half_diff * half_diff,
+            sum_sq_weights: (self.sum_sq_weights + rhs.sum_sq_weights) / (two * two),
+        })
+    }
+}
+
+impl<F: Float> utils::Accumulator for Ewma<F> {
+    fn reset(&mut self) -> Self {
+        let empty = Self {
+            count: 0,
+            mean: F::zero(),
+            variance: F::zero(),
+            sum_sq_weights: F::zero(),
+            ..*self
+        };
+        core::mem::replace(self, empty)
+    }
+}
+
+///
+/// Distribution from which the critical value of an interval is taken.
+///
+/// Student's t distribution is used for up to 100'000 degrees of freedom, and the normal
+/// distribution beyond that.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CriticalDistribution {
+    /// Student's t distribution (t-value).
+    StudentT,
+
+    /// Standard normal distribution (z-value).
+    Normal,
+}
+
+impl core::fmt::Display for CriticalDistribution {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CriticalDistribution::StudentT => write!(f, "t"),
+            CriticalDistribution::Normal => write!(f, "z"),
+        }
+    }
+}
+
+///
+/// Confidence interval on a mean together with the quantities used to compute it.
+///
+/// The bounds of the interval are obtained as
+/// \\( \text{estimate} \pm \text{critical\_value} \times \text{std\_err} \\)
+/// (only one of the two bounds for one-sided intervals).
+///
+/// # Example
+/// ```
+/// use stats_ci::*;
+/// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+/// let stats = mean::Arithmetic::from_iter(&data)?;
+/// let detail = stats.ci_mean_detailed(Confidence::new_two_sided(0.95))?;
+/// let span = detail.critical_value * detail.std_err;
+/// assert_eq!(detail.interval, Interval::new(detail.estimate - span, detail.estimate + span)?);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CiDetail<F: Float> {
+    /// The confidence interval.
+    pub interval: Interval<F>,
+
+    /// The point estimate (e.g., the sample mean or the difference between means).
+    pub estimate: F,
+
+    /// The standard error of the estimate.
+    pub std_err: F,
+
+    /// The (possibly effective) degrees of freedom.
+    pub degrees_of_freedom: F,
+
+    /// The critical value by which the standard error is multiplied.
+    pub critical_value: F,
+
+    /// The distribution from which the critical value is taken.
+    pub distribution: CriticalDistribution,
+}
+
+impl<F: Float> CiDetail<F> {
+    pub(crate) fn new(
+        confidence: Confidence,
+        estimate: f64,
+        std_err: f64,
+        degrees_of_freedom: f64,
+    ) -> CIResult<Self> {
+        let (critical_value, distribution) = stats::critical_value(confidence, degrees_of_freedom)?;
+        let estimate = estimate.try_narrow("estimate")?;
+        let std_err = std_err.try_narrow("std_err")?;
+        let degrees_of_freedom = degrees_of_freedom.try_narrow("degrees_of_freedom")?;
+        let critical_value = critical_value.try_narrow("critical_value")?;
+        let interval = interval_from_critical(confidence, estimate, std_err, critical_value)?;
+        Ok(Self {
+            interval,
+            estimate,
+            std_err,
+            degrees_of_freedom,
+            critical_value,
+            distribution,
+        })
+    }
+}
+
+///
+/// Interval \\( \text{estimate} \pm \text{critical\_value} \times \text{std\_err} \\) for the given confidence.
+///
+fn interval_from_critical<F: Float>(
+    confidence: Confidence,
+    estimate: F,
+    std_err: F,
+    critical_value: F,
+) -> CIResult<Interval<F>> {
+    let span = critical_value * std_err;
+    Interval::from_finite_bounds_for(confidence, estimate - span, estimate + span)
+}
+
+impl<F: Float> CiDetail<F> {
+    ///
+    /// Degenerate interval at `estimate`, for samples with zero variance (zero standard error).
+    ///
+    /// The critical value is infinite with zero degrees of freedom (a single sample).
+    ///
+    pub(crate) fn degenerate(
+        confidence: Confidence,
+        estimate: f64,
+        degrees_of_freedom: f64,
+    ) -> CIResult<Self> {
+        let (critical_value, distribution) = if degrees_of_freedom > 0. {
+            stats::critical_value(confidence, degrees_of_freedom)?
+        } else {
+            (f64::INFINITY, CriticalDistribution::StudentT)
+        };
+        let estimate = estimate.try_narrow("estimate")?;
+        let interval = Interval::from_finite_bounds_for(confidence, estimate, estimate)?;
+        Ok(Self {
+            interval,
+            estimate,
+            std_err: F::zero(),
+            degrees_of_freedom: degrees_of_freedom.try_narrow("degrees_of_freedom")?,
+            critical_value: critical_value.try_narrow("critical_value")?,
+            distribution,
+        })
+    }
+}
+
+impl<F: Float> CiDetail<F> {
+    ///
+    /// p-value of `hypothesized` for the estimate, standard error, and distribution of the interval
+    /// (two-sided if `alternative` is `None`), so that \( p < \alpha \) if and only if the interval
+    /// with confidence \( 1 - \alpha \) excludes `hypothesized`.
+    ///
+    pub(crate) fn p_value(
+        &self,
+        hypothesized: f64,
+        alternative: Option<stats::Alternative>,
+    ) -> CIResult<f64> {
+        Ok(stats::p_value(
+            self.estimate.try_f64("estimate")?,
+            hypothesized,
+            self.std_err.try_f64("std_err")?,
+            self.distribution,
+            self.degrees_of_freedom.try_f64("degrees_of_freedom")?,
+            alternative,
+        ))
+    }
+}
+
+impl<F: Float + core::fmt::Display> core::fmt::Display for CiDetail<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} (estimate: {}, std. err.: {}, {}-value: {}, dof: {})",
+            self.interval,
+            self.estimate,
+            self.std_err,
+            self.distribution,
+            self.critical_value,
+            self.degrees_of_freedom
+        )
+    }
+}
+
+///
+/// Confidence interval on the sum of the means of two independent samples.
+///
+/// The standard errors are combined as \\( \sqrt{se_a^2 + se_b^2} \\) and the critical value
+/// uses the effective degrees of freedom (Welch-Satterthwaite), computed as in
+/// [`comparison::Unpaired`].
+///
+/// This differs from adding two confidence intervals (`ci_a + ci_b`), which is plain interval
+/// arithmetic: with two 95% intervals of similar widths, the resulting interval is about 41%
+/// wider than this one and has an actual coverage of about 99.4%.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `stats_a` - the statistics of the first sample
+/// * `stats_b` - the statistics of the second sample
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let stats_a = mean::Arithmetic::from_iter(&[1., 2., 3., 4., 5.])?;
+/// let stats_b = mean::Arithmetic::from_iter(&[10., 12., 14., 16.])?;
+/// let ci = mean::sum_ci(confidence, &stats_a, &stats_b)?;
+/// assert!(ci.contains(&16.));
+/// // narrower than plain interval arithmetic
+/// let naive = stats_a.ci_mean(confidence)? + stats_b.ci_mean(confidence)?;
+/// assert!(ci.width().unwrap() < naive.width().unwrap());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn sum_ci<F: Float>(
+    confidence: Confidence,
+    stats_a: &Arithmetic<F>,
+    stats_b: &Arithmetic<F>,
+) -> CIResult<Interval<F>> {
+    let diff = comparison::Unpaired::new(*stats_a, *stats_b).ci_mean_detailed(confidence)?;
+    let estimate = stats_a.sample_mean() + stats_b.sample_mean();
+    CiDetail::<F>::new(
+        confidence,
+        estimate.try_f64("estimate")?,
+        diff.std_err.try_f64("std_err")?,
+        diff.degrees_of_freedom.try_f64("degrees_of_freedom")?,
+    )
+    .map(|detail| detail.interval)
+}
+
+///
+/// Confidence interval on the difference between the means of two independent samples
+/// (first minus second).
+///
+/// The standard errors are combined as \\( \sqrt{se_a^2 + se_b^2} \\) with the effective
+/// degrees of freedom (Welch-Satterthwaite). This is the same interval as
+/// [`comparison::Unpaired::ci_mean`].
+///
+/// This differs from subtracting two confidence intervals (`ci_a - ci_b`), which is plain
+/// interval arithmetic: with two 95% intervals of similar widths, the resulting interval is
+/// about 41% wider than this one and has an actual coverage of about 99.4%.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `stats_a` - the statistics of the first sample
+/// * `stats_b` - the statistics of the second sample
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if one of the two samples has less than 2 observations
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let stats_a = mean::Arithmetic::from_iter(&[10., 12., 14., 16.])?;
+/// let stats_b = mean::Arithmetic::from_iter(&[1., 2., 3., 4., 5.])?;
+/// let ci = mean::diff_ci(confidence, &stats_a, &stats_b)?;
+/// assert!(ci.contains(&10.));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn diff_ci<F: Float>(
+    confidence: Confidence,
+    stats_a: &Arithmetic<F>,
+    stats_b: &Arithmetic<F>,
+) -> CIResult<Interval<F>> {
+    comparison::Unpaired::new(*stats_a, *stats_b).ci_mean(confidence)
+}
+
+///
+/// Minimum expected number of resampled statistics beyond the bound of a bootstrap interval
+/// (e.g., 25 out of 1000 resamples for a two-sided 95% interval).
+///
+#[cfg(any(test, feature = "bootstrap"))]
+const MIN_BOOTSTRAP_TAIL_COUNT: f64 = 25.;
+
+///
+/// Minimum number of resamples of a bootstrap interval with the given confidence
+/// (see [`MIN_BOOTSTRAP_TAIL_COUNT`]).
+///
+#[cfg(any(test, feature = "bootstrap"))]
+pub(crate) fn required_bootstrap_resamples(confidence: Confidence) -> usize {
+    (MIN_BOOTSTRAP_TAIL_COUNT / (1. - confidence.quantile())).ceil() as usize
+}
+
+///
+/// Empirical \\( p \\)-quantile of the (sorted, non-empty) resampled statistics of a bootstrap,
+/// as the order statistic of rank \\( (r + 1) p \\), clamped to the range of ranks.
+///
+#[cfg(any(test, feature = "bootstrap"))]
+pub(crate) fn bootstrap_order_statistic(sorted: &[f64], p: f64) -> f64 {
+    let rank = ((sorted.len() + 1) as f64 * p).floor() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+///
+/// Bootstrap-t (studentized bootstrap) confidence interval on the mean.
+///
+/// The data is resampled with replacement, and the distribution of the studentized statistic
+/// \\( t^* = (\bar{x}^* - \bar{x}) / s^*_{\bar{x}} \\) over the resamples replaces the Student
+/// t-distribution: the two-sided interval is
+/// \\( [\bar{x} - t^*_{1-\alpha/2} s_{\bar{x}}, \bar{x} - t^*_{\alpha/2} s_{\bar{x}}] \\),
+/// where \\( t^*_p \\) is the \\( p \\)-quantile of the resampled statistics and
+/// \\( s_{\bar{x}} \\) is the standard error of the mean of `data`.
+///
+/// For small samples (e.g., 5 to 15 values) from skewed distributions, the coverage of this
+/// interval is much closer to the nominal level than that of [`Arithmetic::ci_mean`].
+/// The interval is not symmetric around the mean.
+///
+/// Complexity: \\( O(r (n + \log r)) \\), where \\( r \\) is the number of resamples and
+/// \\( n \\) the number of samples
+///
+/// This function requires the `bootstrap` feature.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level of the interval
+/// * `data` - the samples
+/// * `resamples` - the number of resamples
+/// * `rng` - the random number generator used for resampling
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if `data` has fewer than 2 samples
+/// * [`CIError::TooFewResamples`] - if `resamples` is too small for the confidence level, i.e.,
+///   if fewer than 25 resampled statistics are expected beyond each bound
+///   (at least 1000 resamples for a two-sided 95% interval, or 500 for a one-sided one)
+/// * [`CIError::InvalidInputData`] - if `data` contains values that are not finite
+///
+/// # Notes
+///
+/// A resample that consists of a single repeated value has a zero standard error, hence an
+/// undefined statistic: such resamples are skipped. This is only likely with very few samples,
+/// and an error is returned if so many resamples are skipped that too few remain.
+/// When the variance of `data` itself is zero, the interval is degenerate at the mean,
+/// as with [`Arithmetic::ci_mean`].
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use rand::SeedableRng;
+/// let data = [0.5, 0.8, 1.1, 1.2, 1.4, 2.0, 2.3, 3.1, 5.8, 9.7];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+/// let ci = mean::ci_bootstrap_t(confidence, &data, 2_000, &mut rng)?;
+/// let mean = mean::Arithmetic::from_iter(&data)?.sample_mean();
+/// assert!(ci.contains(&mean));
+///
+/// // the interval is skewed to the right, like the data
+/// assert!(ci.high_f() - mean > mean - ci.low_f());
+///
+/// assert!(matches!(
+///     mean::ci_bootstrap_t(confidence, &data, 500, &mut rng),
+///     Err(error::CIError::TooFewResamples { resamples: 500, required: 1000 })
+/// ));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[cfg(any(test, feature = "bootstrap"))]
+pub fn ci_bootstrap_t(
+    confidence: Confidence,
+    data: &[f64],
+    resamples: usize,
+    rng: &mut impl rand::Rng,
+) -> CIResult<Interval<f64>> {
+    if data.len() < 2 {
+        return Err(CIError::TooFewSamples(data.len()));
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(CIError::InvalidInputData);
+    }
+    let quantile = confidence.quantile();
+    let required = required_bootstrap_resamples(confidence);
+    if resamples < required {
+        return Err(CIError::TooFewResamples {
+            resamples,
+            required,
+        });
+    }
+
+    let mut stats = Arithmetic::new();
+    for &x in data {
+        stats.append(x)?;
+    }
+    if stats.sample_variance() == 0. {
+        return stats.ci_mean(confidence);
+    }
+    let n = data.len() as f64;
+    let mean = stats.sample_mean();
+    let std_err = stats.sample_std_dev() / n.sqrt();
+
+    let mut statistics = Vec::with_capacity(resamples);
+    for _ in 0..resamples {
+        let mut resample = Arithmetic::new();
+        for _ in 0..data.len() {
+            resample.push(data[rng.gen_range(0..data.len())]);
+        }
+        let resample_std_err = resample.sample_std_dev() / n.sqrt();
+        if resample_std_err > 0. {
+            statistics.push((resample.sample_mean() - mean) / resample_std_err);
+        }
+    }
+    if statistics.len() < required {
+        return Err(CIError::TooFewResamples {
+            resamples: statistics.len(),
+            required,
+        });
+    }
+    statistics.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let low = mean - bootstrap_order_statistic(&statistics, quantile) * std_err;
+    let high = mean - bootstrap_order_statistic(&statistics, 1. - quantile) * std_err;
+    Interval::from_finite_bounds_for(confidence, low, high)
+}
+
+///
+/// Rule to stop an experiment once the confidence interval on the mean is precise enough, i.e.,
+/// once its half-width falls below a target (absolute, or relative to the mean).
+///
+/// Checking the interval after every new sample and stopping as soon as it is narrow enough
+/// is both costly and biased: the more often the interval is looked at, the more likely it is
+/// to be narrow by chance, and the actual coverage of the final interval drops below the nominal
+/// level. The rule limits the number of looks in two ways:
+/// * it does not evaluate the interval before a minimum number of samples (by default
+///   [`StoppingRule::DEFAULT_MIN_SAMPLES`]), and
+/// * it then only evaluates the interval on a geometric schedule, once the number of samples
+///   has grown by a factor (by default [`StoppingRule::DEFAULT_GROWTH_FACTOR`]) since the
+///   previous evaluation.
+///
+/// The number of evaluations made so far is available through [`StoppingRule::evaluations`].
+///
+/// The half-width is the distance between the sample mean and the bound(s) of the interval, so
+/// that one-sided intervals are also supported.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // stop once the interval is within ±1% of the mean
+/// let rule = mean::StoppingRule::relative_half_width(0.01)?;
+/// let mut stats = mean::Arithmetic::new();
+/// let mut x = 1_u64;
+/// while !rule.should_stop(&stats, confidence)? {
+///     // pseudo-random values in [95, 105)
+///     x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+///     stats.append(95. + (x >> 11) as f64 / (1_u64 << 53) as f64 * 10.)?;
+/// }
+/// let ci = stats.ci_mean(confidence)?;
+/// assert!(ci.width().unwrap() / 2. <= 0.01 * stats.sample_mean());
+/// assert!(rule.evaluations() < 30);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct StoppingRule<F: Float> {
+    target: StoppingTarget<F>,
+    growth_factor: f64,
+    next_evaluation: core::cell::Cell<usize>,
+    evaluations: core::cell::Cell<usize>,
+}
+
+///
+/// Target of a [`StoppingRule`].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StoppingTarget<F: Float> {
+    HalfWidth(F),
+    RelativeHalfWidth(F),
+}
+
+impl<F: Float> StoppingRule<F> {
+    ///
+    /// Default minimum number of samples before the interval is first evaluated.
+    ///
+    pub const DEFAULT_MIN_SAMPLES: usize = 30;
+
+    ///
+    /// Default factor by which the number of samples must grow between two evaluations.
+    ///
+    pub const DEFAULT_GROWTH_FACTOR: f64 = 1.1;
+
+    ///
+    /// Create a rule that stops once the half-width of the interval is at most `target`,
+    /// in the unit of the data.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidTarget`] - if the target is not strictly positive and finite
+    ///
+    pub fn half_width(target: F) -> CIResult<Self> {
+        Self::check_target(target)?;
+        Ok(Self::with_target(StoppingTarget::HalfWidth(target)))
+    }
+
+    ///
+    /// Create a rule that stops once the half-width of the interval is at most `target_fraction`
+    /// times the absolute value of the sample mean (e.g., `0.01` for ±1% of the mean).
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidTarget`] - if the target fraction is not strictly positive and finite
+    ///
+    pub fn relative_half_width(target_fraction: F) -> CIResult<Self> {
+        Self::check_target(target_fraction)?;
+        Ok(Self::with_target(StoppingTarget::RelativeHalfWidth(
+            target_fraction,
+        )))
+    }
+
+    ///
+    /// Set the minimum number of samples before the interval is first evaluated (at least 2).
+    ///
+    pub fn min_samples(self, min_samples: usize) -> Self {
+        self.next_evaluation.set(min_samples.max(2));
+        self
+    }
+
+    ///
+    /// Set the factor by which the number of samples must grow between two evaluations.
+    /// A factor of 1 (or less) evaluates the interval on every new sample.
+    ///
+    pub fn growth_factor(mut self, growth_factor: f64) -> Self {
+        self.growth_factor = growth_factor.max(1.);
+        self
+    }
+
+    ///
+    /// Return whether the experiment can stop, i.e., whether the interval on the mean of `stats`
+    /// meets the target.
+    ///
+    /// The interval is only evaluated when the number of samples has reached the next point of
+    /// the schedule; otherwise, the result is `false`.
+    ///
+    /// # Arguments
+    ///
+    /// * `stats` - the statistics of the samples collected so far
+    /// * `confidence` - the confidence level of the interval
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Arithmetic::ci_mean_detailed`].
+    ///
+    pub fn should_stop(&self, stats: &Arithmetic<F>, confidence: Confidence) -> CIResult<bool> {
+        let count = stats.sample_count();
+        if count < self.next_evaluation.get() {
+            return Ok(false);
+        }
+        let next = (count as f64 * self.growth_factor).ceil() as usize;
+        self.next_evaluation.set(next.max(count + 1));
+        self.evaluations.set(self.evaluations.get() + 1);
+
+        let detail = stats.ci_mean_detailed(confidence)?;
+        let half_width = detail.critical_value * detail.std_err;
+        let target = match self.target {
+            StoppingTarget::HalfWidth(target) => target,
+            StoppingTarget::RelativeHalfWidth(fraction) => fraction * detail.estimate.abs(),
+        };
+        Ok(half_width <= target)
+    }
+
+    ///
+    /// Return the number of times the interval has been evaluated by [`Self::should_stop`].
+    ///
+    pub fn evaluations(&self) -> usize {
+        self.evaluations.get()
+    }
+
+    fn with_target(target: StoppingTarget<F>) -> Self {
+        Self {
+            target,
+            growth_factor: Self::DEFAULT_GROWTH_FACTOR,
+            next_evaluation: core::cell::Cell::new(Self::DEFAULT_MIN_SAMPLES),
+            evaluations: core::cell::Cell::new(0),
+        }
+    }
+
+    fn check_target(target: F) -> CIResult<()> {
+        if target > F::zero() && target.is_finite() {
+            Ok(())
+        } else {
+            Err(CIError::InvalidTarget(target.to_f64().unwrap_or(f64::NAN)))
+        }
+    }
+}
+
+///
+/// Confidence interval on the pooled mean of several independent estimates, with a
+/// fixed-effect meta-analysis (inverse-variance weighting).
+///
+/// Each estimate is given as a pair `(mean, standard_error)` and is weighted by
+/// \\( w_i = 1 / se_i^2 \\). The pooled mean is \\( \sum_i w_i \bar{x}_i / \sum_i w_i \\)
+/// and its standard error is \\( 1 / \sqrt{\sum_i w_i} \\). The critical value is taken from
+/// the normal distribution.
+///
+/// The fixed-effect model assumes that all estimates measure the same true mean (e.g., the
+/// same benchmark on identical machines). When the estimates are heterogeneous, use
+/// [`pool_random_effects`] instead.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `estimates` - the pairs `(mean, standard_error)` of the individual estimates
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if less than 2 estimates are provided
+/// * [`CIError::InvalidStdError`] - if a standard error is not strictly positive and finite
+/// * [`CIError::InvalidInputData`] - if a mean is not finite
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let estimates = [(10.2, 0.4), (9.8, 0.2), (10.5, 0.8)];
+/// let ci = mean::pool_fixed_effect(confidence, &estimates)?;
+/// assert!(ci.contains(&10.));
+/// // narrower than the most precise estimate alone
+/// assert!(ci.width().unwrap() < 2. * 1.96 * 0.2);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * M. Borenstein, L. V. Hedges, J. P. T. Higgins, H. R. Rothstein. Introduction to Meta-Analysis. Wiley, 2009. (Chapter 11)
+///
+pub fn pool_fixed_effect(
+    confidence: Confidence,
+    estimates: &[(f64, f64)],
+) -> CIResult<Interval<f64>> {
+    let (estimate, std_err) = fixed_effect(estimates)?.pooled();
+    let span = stats::z_value(confidence) * std_err;
+    Interval::from_finite_bounds_for(confidence, estimate - span, estimate + span)
+}
+
+///
+/// Confidence interval on the pooled mean of several independent samples, with a fixed-effect
+/// meta-analysis (inverse-variance weighting).
+///
+/// This is the same as [`pool_fixed_effect`], with the means and standard errors taken from
+/// the statistics of each sample.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `stats` - the statistics of the individual samples
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if less than 2 samples are provided, or if a sample has less than 2 observations
+/// * [`CIError::InvalidStdError`] - if a sample has zero variance
+/// * [`CIError::FloatConversionError`] - if the bounds cannot be represented in `F`
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let machine_a = mean::Arithmetic::from_iter(&[10.1, 10.4, 9.9, 10.2])?;
+/// let machine_b = mean::Arithmetic::from_iter(&[9.7, 10.0, 10.3, 9.9, 10.1])?;
+/// let ci = mean::pool_fixed_effect_stats(confidence, &[&machine_a, &machine_b])?;
+/// assert!(ci.contains(&10.));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn pool_fixed_effect_stats<F: Float>(
+    confidence: Confidence,
+    stats: &[&Arithmetic<F>],
+) -> CIResult<Interval<F>> {
+    let estimates = stats
+        .iter()
+        .map(|stats| {
+            if stats.sample_count() < 2 {
+                return Err(CIError::TooFewSamples(stats.sample_count()));
+            }
+            Ok((
+                stats.sample_mean().try_f64("mean")?,
+                stats.sample_sem().try_f64("std_err")?,
+            ))
+        })
+        .collect::<CIResult<Vec<_>>>()?;
+    let (estimate, std_err) = fixed_effect(&estimates)?.pooled();
+    let span = stats::z_value(confidence) * std_err;
+    Interval::from_finite_bounds_for(
+        confidence,
+        (estimate - span).try_narrow("low")?,
+        (estimate + span).try_narrow("high")?,
+    )
+}
+
+///
+/// Result of a random-effects meta-analysis, as computed by [`pool_random_effects`].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RandomEffects {
+    /// The confidence interval on the pooled mean.
+    pub interval: Interval<f64>,
+
+    /// The pooled mean.
+    pub estimate: f64,
+
+    /// The standard error of the pooled mean.
+    pub std_err: f64,
+
+    /// The estimated between-estimate variance \\( \tau^2 \\).
+    pub tau_squared: f64,
+
+    /// Cochran's \\( Q \\) statistic of heterogeneity.
+    pub q: f64,
+
+    /// The proportion \\( I^2 \\) of the total variation due to heterogeneity, in `[0, 1]`.
+    pub i_squared: f64,
+}
+
+///
+/// Confidence interval on the pooled mean of several independent estimates, with a
+/// random-effects meta-analysis (DerSimonian-Laird).
+///
+/// Unlike [`pool_fixed_effect`], the true means are allowed to differ between estimates
+/// (e.g., the same benchmark on different hardware), with a between-estimate variance
+/// \\( \tau^2 \\) estimated with the method of moments from Cochran's \\( Q \\) statistic.
+/// Each estimate is then weighted by \\( 1 / (se_i^2 + \tau^2) \\).
+/// When \\( \tau^2 = 0 \\), the result coincides with that of the fixed-effect model.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level
+/// * `estimates` - the pairs `(mean, standard_error)` of the individual estimates
+///
+/// # Errors
+///
+/// * [`CIError::TooFewSamples`] - if less than 2 estimates are provided
+/// * [`CIError::InvalidStdError`] - if a standard error is not strictly positive and finite
+/// * [`CIError::InvalidInputData`] - if a mean is not finite
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let estimates = [(10.2, 0.1), (11.8, 0.2), (9.5, 0.1)];
+/// let pooled = mean::pool_random_effects(confidence, &estimates)?;
+/// assert!(pooled.tau_squared > 0.);
+/// assert!(pooled.i_squared > 0.9);
+/// // wider than the fixed-effect interval
+/// let fixed = mean::pool_fixed_effect(confidence, &estimates)?;
+/// assert!(pooled.interval.width().unwrap() > fixed.width().unwrap());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * R. DerSimonian, N. Laird. Meta-analysis in clinical trials. Controlled Clinical Trials, 7(3):177–188, 1986.
+/// * M. Borenstein, L. V. Hedges, J. P. T. Higgins, H. R. Rothstein. Introduction to Meta-Analysis. Wiley, 2009. (Chapters 12 and 16)
+///
+pub fn pool_random_effects(
+    confidence: Confidence,
+    estimates: &[(f64, f64)],
+) -> CIResult<RandomEffects> {
+    let fixed = fixed_effect(estimates)?;
+    let (fixed_estimate, _) = fixed.pooled();
+    let degrees_of_freedom = (estimates.len() - 1) as f64;
+    let q = estimates
+        .iter()
+        .map(|&(mean, std_err)| (mean - fixed_estimate).powi(2) / (std_err * std_err))
+        .sum::<f64>();
+    let c = fixed.sum_weights - fixed.sum_squared_weights / fixed.sum_weights;
+    let tau_squared = ((q - degrees_of_freedom) / c).max(0.);
+    let i_squared = if q > degrees_of_freedom {
+        (q - degrees_of_freedom) / q
+    } else {
+        0.
+    };
+
+    let random = fixed_effect_with(estimates, tau_squared)?;
+    let (estimate, std_err) = random.pooled();
+    let span = stats::z_value(confidence) * std_err;
+    let interval = Interval::from_finite_bounds_for(confidence, estimate - span, estimate + span)?;
+    Ok(RandomEffects {
+        interval,
+        estimate,
+        std_err,
+        tau_squared,
+        q,
+        i_squared,
+    })
+}
+
+///
+/// Weighted sums of an inverse-variance meta-analysis.
+///
+struct InverseVariance {
+    sum_weights: f64,
+    sum_squared_weights: f64,
+    sum_weighted_means: f64,
+}
+
+impl InverseVariance {
+    ///
+    /// Return the pooled mean and its standard error.
+    ///
+    fn pooled(&self) -> (f64, f64) {
+        (
+            self.sum_weighted_means / self.sum_weights,
+            self.sum_weights.sqrt().recip(),
+        )
+    }
+}
+
+fn fixed_effect(estimates: &[(f64, f64)]) -> CIResult<InverseVariance> {
+    fixed_effect_with(estimates, 0.)
+}
+
+///
+/// Inverse-variance weighted sums, with the variance of each estimate increased by `tau_squared`.
+///
+fn fixed_effect_with(estimates: &[(f64, f64)], tau_squared: f64) -> CIResult<InverseVariance> {
+    if estimates.len() < 2 {
+        return Err(CIError::TooFewSamples(estimates.len()));
+    }
+    let mut sums = InverseVariance {
+        sum_weights: 0.,
+        sum_squared_weights: 0.,
+        sum_weighted_means: 0.,
+    };
+    for &(mean, std_err) in estimates {
+        if !std_err.is_finite() || std_err <= 0. {
+            return Err(CIError::InvalidStdError(std_err));
+        }
+        if !mean.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        let weight = (std_err * std_err + tau_squared).recip();
+        sums.sum_weights += weight;
+        sums.sum_squared_weights += weight * weight;
+        sums.sum_weighted_means += weight * mean;
+    }
+    Ok(sums)
+}
+
+///
+/// Trait for computing confidence intervals on the mean of a sample.
+///
+/// It is superseded by the [`StatisticsOps`] trait which allows incremental statistics.
+/// It is retained for backwards compatibility and will be deprecated in the future, as
+/// it brings no advantage over [`StatisticsOps`] and is less flexible.
+///
+/// It is implemented for the statistics types of the crate only, not for every implementor
+/// of [`StatisticsOps`], so that it does not interfere with the methods of statistics types
+/// defined elsewhere (see [`StatisticsOps::ci_from_data`] for the one-shot interval).
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// let data = [
+///    82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
+///    15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
+///    71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
+///    98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
+///    49., 23., 26., 55., 26., 3., 23., 47., 27., 58., 27., 97., 32., 29., 56., 28., 23.,
+///    37., 72., 62., 77., 63., 100., 40., 84., 77., 39., 71., 61., 17., 77.,
+/// ];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let ci = mean::Arithmetic::ci(confidence, &data)?;
+/// // arithmetic mean: 52.5
+///
+/// use num_traits::Float;
+/// use approx::*;
+/// assert_abs_diff_eq!(ci, Interval::new(48.094823990767836, 59.24517600923217)?, epsilon = 1e-3);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub trait MeanCI<T: PartialOrd> {
+    ///
+    /// Compute the confidence interval on the mean of a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - The confidence level of the interval
+    /// * `data` - The data to compute the confidence interval on
+    ///
+    /// # Output
+    ///
+    /// * `Ok(interval)` - The confidence interval on the mean of the sample
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::TooFewSamples`] - If the input data has too few samples to compute the confidence interval
+    /// * [`CIError::NonPositiveValue`] - If the input data contains non-positive values when computing harmonic/geometric means.
+    /// * [`CIError::InvalidInputData`] - If the input data contains invalid values (e.g. NaN)
+    /// * [`CIError::FloatConversionError`] - If some data cannot be converted to a float
+    ///
+    fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<T>>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a T>;
+}
+
+macro_rules! impl_mean_ci_for {
+    ( $x:ty ) => {
+        impl<F: Float> MeanCI<F> for $x {
+            fn ci<I>(confidence: Confidence, data: &I) -> CIResult<Interval<F>>
+            where
+                for<'a> &'a I: IntoIterator<Item = &'a F>,
+            {
+                <$x>::ci(confidence, data)
+            }
+        }
+    };
+}
+
+impl_mean_ci_for!(Arithmetic<F>);
+impl_mean_ci_for!(Harmonic<F>);
+impl_mean_ci_for!(Geometric<F>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    // statistics defined outside the crate, with an inherent `ci` method of its own
+    #[derive(Default)]
+    struct Clipped(Arithmetic<f64>);
+
+    impl Clipped {
+        fn ci(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+            Ok(self.ci_mean(confidence)?.map(|x| x.clamp(0., 1.)))
+        }
+    }
+
+    impl StatisticsOps<f64> for Clipped {
+        fn append(&mut self, x: f64) -> CIResult<()> {
+            self.0.append(x.clamp(0., 1.))
+        }
+        fn sample_mean(&self) -> f64 {
+            self.0.sample_mean()
+        }
+        fn sample_sem(&self) -> f64 {
+            self.0.sample_sem()
+        }
+        fn sample_count(&self) -> usize {
+            self.0.sample_count()
+        }
+        fn ci_mean(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+            self.0.ci_mean(confidence)
+        }
+    }
+
+    #[test]
+    fn test_trimmed() -> CIResult<()> {
+        let mpg = [
+            21.0, 21.0, 22.8, 21.4, 18.7, 18.1, 14.3, 24.4, 22.8, 19.2, 17.8, 16.4, 17.3, 15.2,
+            10.4, 10.4, 14.7, 32.4, 30.4, 33.9, 21.5, 15.5, 15.2, 13.3, 19.2, 27.3, 26.0, 30.4,
+            15.8, 19.7, 15.0, 21.4,
+        ];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // reference values: `mtcars$mpg`, to be reproduced in R with Wilcox's functions as
+        //   `trimci(mtcars$mpg, tr = 0.1)` / `winci(mtcars$mpg, tr = 0.1)` (and `tr = 0.2`);
+        // they were computed with mpmath by transcribing the formulas of these functions
+        // (g = floor(tr * n) values trimmed on each side, winsorized standard error
+        // sqrt(winvar) / ((1 - 2 tr) sqrt(n)), Student's t with n - 2g - 1 degrees of freedom),
+        // not by running R
+        for (
+            trim,
+            retained,
+            trimmed_mean,
+            winsorized_var,
+            winsorized_mean,
+            trimmed_ci,
+            winsorized_ci,
+        ) in [
+            (
+                0.1,
+                26,
+                19.696_153_846_2,
+                27.829_637_096_8,
+                20.193_75,
+                (17.295_338_705, 22.096_968_987_3),
+                (17.792_934_858_9, 22.594_565_141_1),
+            ),
+            (
+                0.2,
+                20,
+                19.22,
+                13.006_935_483_9,
+                19.437_5,
+                (16.995_996_465_7, 21.444_003_534_3),
+                (17.213_496_465_7, 21.661_503_534_3),
+            ),
+        ] {
+            let stats = Trimmed::from_iter_with(&mpg, trim)?;
+            assert_eq!(stats.sample_count(), 32);
+            assert_eq!(stats.retained_count(), retained);
+            assert_eq!(stats.trim_fraction(), trim);
+            assert_abs_diff_eq!(stats.sample_trimmed_mean(), trimmed_mean, epsilon = 1e-9);
+            assert_abs_diff_eq!(
+                stats.sample_winsorized_variance(),
+                winsorized_var,
+                epsilon = 1e-9
+            );
+            assert_abs_diff_eq!(
+                stats.sample_winsorized_mean(),
+                winsorized_mean,
+                epsilon = 1e-9
+            );
+            assert_abs_diff_eq!(
+                stats.ci_mean(confidence)?,
+                Interval::new(trimmed_ci.0, trimmed_ci.1)?,
+                epsilon = 1e-8
+            );
+            assert_abs_diff_eq!(
+                stats.ci_winsorized_mean(confidence)?,
+                Interval::new(winsorized_ci.0, winsorized_ci.1)?,
+                epsilon = 1e-8
+            );
+
+            // one-sided intervals
+            let upper = stats.ci_mean(Confidence::new_upper(0.975))?;
+            assert!(upper.is_upper());
+            assert_abs_diff_eq!(upper.low_f(), trimmed_ci.0, epsilon = 1e-8);
+            let lower = stats.ci_mean(Confidence::new_lower(0.975))?;
+            assert!(lower.is_lower());
+            assert_abs_diff_eq!(lower.high_f(), trimmed_ci.1, epsilon = 1e-8);
+        }
+
+        // no trimming is the plain interval on the mean (the winsorized variance is accumulated
+        // over the sorted values, so it may differ in the last bits)
+        let large = (0..2_000)
+            .map(|i| (i * 37 % 101) as f64)
+            .collect::<Vec<_>>();
+        for data in [&mpg[..], &[1., 2.], &[3., 3., 3.], &[5.], &large] {
+            let stats = Trimmed::from_iter_with(data, 0.)?;
+            let arithmetic = Arithmetic::from_iter(data)?;
+            assert_eq!(stats.sample_trimmed_mean(), arithmetic.sample_mean());
+            if data.len() > 1 {
+                assert_relative_eq!(
+                    stats.sample_winsorized_variance(),
+                    arithmetic.sample_variance(),
+                    max_relative = 1e-14
+                );
+            }
+            for confidence in [
+                confidence,
+                Confidence::new_upper(0.9),
+                Confidence::new_lower(0.99),
+            ] {
+                assert_relative_eq!(
+                    stats.ci_mean(confidence)?,
+                    arithmetic.ci_mean(confidence)?,
+                    max_relative = 1e-14
+                );
+                assert_relative_eq!(
+                    stats.ci_winsorized_mean(confidence)?,
+                    arithmetic.ci_mean(confidence)?,
+                    max_relative = 1e-14
+                );
+            }
+        }
+
+        // robustness to outliers
+        let mut contaminated = mpg.to_vec();
+        contaminated[0] = 1e6;
+        let stats = Trimmed::from_iter_with(&contaminated, 0.1)?;
+        assert!(stats.ci_mean(confidence)?.high_f() < 25.);
+
+        // a single retained value
+        assert_eq!(
+            Trimmed::from_iter_with(&[1., 2., 3.], 0.4)?.ci_mean(confidence),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert_eq!(
+            Trimmed::from_iter_with(&[1., 2., 3.], 0.4)?.ci_winsorized_mean(confidence),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert_eq!(
+            Trimmed::from_iter_with(&[2., 2., 2.], 0.4)?.ci_mean(confidence)?,
+            Interval::new(2., 2.)?
+        );
+
+        // constant retained values
+        let stats = Trimmed::from_iter_with(&[1., 5., 5., 5., 9.], 0.2)?;
+        assert_eq!(stats.ci_mean(confidence)?, Interval::new(5., 5.)?);
+
+        // errors
+        for trim in [0.5, -0.1, f64::NAN] {
+            assert!(matches!(
+                Trimmed::from_iter_with(&mpg, trim),
+                Err(CIError::InvalidTrimFraction(_))
+            ));
+        }
+        assert_eq!(
+            Trimmed::from_iter_with(&[1., f64::NAN], 0.1),
+            Err(CIError::InvalidInputData)
+        );
+        let empty = Trimmed::<f64>::from_iter_with(&[], 0.1)?;
+        assert_eq!(empty.ci_mean(confidence), Err(CIError::TooFewSamples(0)));
+        assert_eq!(
+            empty.ci_winsorized_mean(confidence),
+            Err(CIError::TooFewSamples(0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_statistics_ops() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let data = [0.2, 1.5, 0.9, -0.3, 0.7, 0.95];
+        let stats = Clipped::from_iter(&data)?;
+        assert_eq!(stats.sample_count(), 6);
+        assert_eq!(
+            Clipped::ci_from_data(confidence, &data)?,
+            stats.ci_mean(confidence)?
+        );
+        // the inherent method is not ambiguous with the trait
+        let ci = Clipped::ci(&stats, confidence)?;
+        assert_eq!(ci.high_f(), 1.);
+        #[allow(deprecated)]
+        let ci_deprecated = <Clipped as StatisticsOps<f64>>::ci(confidence, &data)?;
+        assert_eq!(ci_deprecated, stats.ci_mean(confidence)?);
+
+        // the one-shot interval of the types of the crate is unchanged
+        let slice: &[f64] = &data;
+        assert_eq!(
+            <Arithmetic<f64> as StatisticsOps<f64>>::ci_from_data(confidence, slice)?,
+            Arithmetic::ci(confidence, &data)?
+        );
+        assert_eq!(
+            <ArithmeticF32 as StatisticsOps<f32>>::ci_from_data(confidence, &[1_f32, 2., 4.])?,
+            ArithmeticF32::ci(confidence, &[1_f32, 2., 4.])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_mean_ci() -> CIResult<()> {
+        let data = [
+            82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
+            15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
+            71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
+            98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
+            49., 23., 26., 55., 26., 3., 23., 47., 27., 58., 27., 97., 32., 29., 56., 28., 23.,
+            37., 72., 62., 77., 63., 100., 40., 84., 77., 39., 71., 61., 17., 77.,
+        ];
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = Arithmetic::ci(confidence, &data)?;
+        // mean: 53.67
+        // stddev: 28.097613040716798
+        // reference values computed in python
+        // [48.094823990767836, 59.24517600923217]
+        // ```python
+        // import numpy as np
+        // import scipy.stats as st
+        // st.t.interval(confidence=0.95, df=len(data)-1, loc=np.mean(data), scale=st.sem(data))
+        // ```
+        assert_abs_diff_eq!(ci.low_f(), 48.094823990767836, epsilon = 1e-8);
+        assert_abs_diff_eq!(ci.high_f(), 59.24517600923217, epsilon = 1e-8);
+        assert_abs_diff_eq!(ci.low_f() + ci.high_f(), 2. * 53.67);
+
+        let one_sided_ci = Arithmetic::ci(Confidence::UpperOneSided(0.975), &data)?;
+        assert_abs_diff_eq!(one_sided_ci.low_f(), ci.low_f());
+        assert_eq!(one_sided_ci.high_f(), f64::INFINITY);
+
+        let one_sided_ci = Arithmetic::ci(Confidence::LowerOneSided(0.975), &data)?;
+        assert_abs_diff_eq!(one_sided_ci.high_f(), ci.high_f());
+        assert_eq!(one_sided_ci.low_f(), f64::NEG_INFINITY);
+
+        let mut stats = Arithmetic::default();
+        stats.extend(&data)?;
+        let ci = stats.ci_mean(confidence)?;
+
+        assert_abs_diff_eq!(ci.low_f(), 48.094823990767836, epsilon = 1e-8);
+        assert_abs_diff_eq!(ci.high_f(), 59.24517600923217, epsilon = 1e-8);
+        assert_abs_diff_eq!(ci.low_f() + ci.high_f(), 2. * 53.67, epsilon = 1e-8);
+
+        let one_sided_ci = stats.ci_mean(Confidence::UpperOneSided(0.975))?;
+        assert_abs_diff_eq!(one_sided_ci.low_f(), ci.low_f());
+        assert_eq!(one_sided_ci.high_f(), f64::INFINITY);
+
+        let one_sided_ci = stats.ci_mean(Confidence::LowerOneSided(0.975))?;
+        assert_abs_diff_eq!(one_sided_ci.high_f(), ci.high_f());
+        assert_eq!(one_sided_ci.low_f(), f64::NEG_INFINITY);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_geometric_ci() -> CIResult<()> {
+        let data = [
+            82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
+            15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
+            71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
+            98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
+            49., 23., 26., 55., 26., 3., 23., 47., 27., 58., 27., 97., 32., 29., 56., 28., 23.,
+            37., 72., 62., 77., 63., 100., 40., 84., 77., 39., 71., 61., 17., 77.,
+        ];
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = Geometric::ci(confidence, &data)?;
+        // geometric mean: 43.7268032829256
+        //
+        // reference values computed in python:
+        // in log space: (3.630483364286656, 3.9254391587458475)
+        // [37.731050052224354, 50.67532768627392]
+        assert_abs_diff_eq!(ci.low_f(), 37.731050052224354, epsilon = 1e-8);
+        assert_abs_diff_eq!(ci.high_f(), 50.67532768627392, epsilon = 1e-8);
+
+        let one_sided_ci = Geometric::ci(Confidence::UpperOneSided(0.975), &data)?;
+        assert_abs_diff_eq!(one_sided_ci.low_f(), ci.low_f());
+        assert_eq!(one_sided_ci.high_f(), f64::INFINITY);
+
+        let one_sided_ci = Geometric::ci(Confidence::LowerOneSided(0.975), &data)?;
+        assert_abs_diff_eq!(one_sided_ci.high_f(), ci.high_f());
+        assert_eq!(one_sided_ci.low_f(), f64::NEG_INFINITY);
+
+        let mut stats = Geometric::default();
+        stats.extend(&data)?;
+        let ci = stats.ci_mean(confidence)?;
+        assert_abs_diff_eq!(stats.sample_mean(), 43.7268032829256, epsilon = 1e-8);
+        assert_abs_diff_eq!(ci.low_f(), 37.731050052224354, epsilon = 1e-8);
+        assert_abs_diff_eq!(ci.high_f(), 50.67532768627392, epsilon = 1e-8);
+
+        let one_sided_ci = stats.ci_mean(Confidence::UpperOneSided(0.975))?;
+        assert_abs_diff_eq!(one_sided_ci.low_f(), ci.low_f());
+        assert_eq!(one_sided_ci.high_f(), f64::INFINITY);
+
+        let one_sided_ci = stats.ci_mean(Confidence::LowerOneSided(0.975))?;
+        assert_abs_diff_eq!(one_sided_ci.high_f(), ci.high_f());
+        assert_eq!(one_sided_ci.low_f(), f64::NEG_INFINITY);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_harmonic_ci() -> CIResult<()> {
+        let data = [
+            82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
+            15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
+            71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
+            98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
+            49., 23., 26., 55., 26., 3., 23., 47., 27., 58., 27., 97., 32., 29., 56., 28., 23.,
+            37., 72., 62., 77., 63., 100., 40., 84., 77., 39., 71., 61., 17., 77.,
+        ];
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = Harmonic::ci(confidence, &data)?;
+        // harmonic mean: 30.031313156339586
+        //
+        // reference values computed in python:
+        // in reciprocal space: (0.02424956057996111, 0.042347593849757906)
+        // [41.237860649168255, 23.614092539657168]  (reversed by conversion from reciprocal space)
+        assert_abs_diff_eq!(ci.low_f(), 23.614092539657168, epsilon = 1e-8);
+        assert_abs_diff_eq!(ci.high_f(), 41.237860649168255, epsilon = 1e-8);
+
+        let one_sided_ci = Harmonic::ci(Confidence::UpperOneSided(0.975), &data)?;
+        assert_abs_diff_eq!(one_sided_ci.low_f(), ci.low_f());
+        assert_eq!(one_sided_ci.high_f(), f64::INFINITY);
+
+        let one_sided_ci = Harmonic::ci(Confidence::LowerOneSided(0.975), &data)?;
+        assert_abs_diff_eq!(one_sided_ci.high_f(), ci.high_f());
+        assert_eq!(one_sided_ci.low_f(), f64::NEG_INFINITY);
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let data = [
+            1.81600583, 0.07498389, 1.29092744, 0.62023863, 0.09345327, 1.94670997, 2.27687339,
+            0.9251231, 1.78173864, 0.4391542, 1.36948099, 1.5191194, 0.42286756, 1.48463176,
+            0.17621009, 2.31810064, 0.15633061, 2.55137878, 1.11043948, 1.35923319, 1.58385561,
+            0.63431437, 0.49993148, 0.49168534, 0.11533354,
+        ];
+        let ci = Harmonic::ci(confidence, &data)?;
+        // harmonic mean: 0.38041820166550844
+        //
+        // reference values computed in python:
+        // in reciprocal space: (1.1735238063066096, 4.083848080632111)
+        // [0.8521343961033607, 0.2448670911003175]
+        assert_abs_diff_eq!(ci.low_f(), 0.2448670911003175, epsilon = 1e-6);
+        assert_abs_diff_eq!(ci.high_f(), 0.8521343961033607, epsilon = 1e-6);
+
+        let mut stats = Harmonic::default();
+        stats.extend(&data)?;
+        let ci = stats.ci_mean(confidence)?;
+        assert_abs_diff_eq!(stats.sample_mean(), 0.38041820166550844, epsilon = 1e-8);
+        assert_abs_diff_eq!(ci.low_f(), 0.2448670911003175, epsilon = 1e-6);
+        assert_abs_diff_eq!(ci.high_f(), 0.8521343961033607, epsilon = 1e-6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_transformed_bound_overflow() -> CIResult<()> {
+        let two_sided = Confidence::new_two_sided(0.95);
+        let upper = Confidence::new_upper(0.95);
+        let lower = Confidence::new_lower(0.95);
+
+        // the upper bound overflows when exponentiated in f32, but not in f64
+        let data = [1e30, 1e38, 1e37];
+        let stats = Geometric::<f32>::from_slice(&data)?;
+        let log_ci = stats.log_space().ci_mean(two_sided)?;
+        assert_eq!(log_ci.high_f().exp(), f32::INFINITY);
+        let ci = stats.ci_mean(two_sided)?;
+        assert_eq!(ci, Interval::new_upper(log_ci.low_f().exp()));
+        assert!(ci.low_f().is_finite());
+        let log_ci = stats.log_space().ci_mean(upper)?;
+        assert_eq!(
+            stats.ci_mean(upper)?,
+            Interval::new_upper(log_ci.low_f().exp())
+        );
+        assert!(matches!(
+            stats.ci_mean(lower),
+            Err(CIError::UninterpretableTransformedInterval(msg)) if msg.contains("upper bound")
+        ));
+
+        let stats = Geometric::<f64>::from_slice(&data.map(f64::from))?;
+        let log_ci = stats.log_space().ci_mean(two_sided)?;
+        assert_eq!(
+            stats.ci_mean(two_sided)?,
+            Interval::new(log_ci.low_f().exp(), log_ci.high_f().exp())?
+        );
+
+        // inverted or exponentiated bounds that overflow
+        let inf = f32::INFINITY;
+        assert_eq!(
+            transformed_interval(two_sided, 1., inf, "harmonic")?,
+            Interval::new_upper(1.)
+        );
+        assert_eq!(
+            transformed_interval(upper, 1., inf, "harmonic")?,
+            Interval::new_upper(1.)
+        );
+        assert_eq!(
+            transformed_interval(lower, inf, 2., "harmonic")?,
+            Interval::new_lower(2.)
+        );
+        for (confidence, lo, hi, bound) in [
+            (two_sided, inf, inf, "lower"),
+            (upper, inf, inf, "lower"),
+            (lower, 0., inf, "upper"),
+        ] {
+            assert!(matches!(
+                transformed_interval(confidence, lo, hi, "harmonic"),
+                Err(CIError::UninterpretableTransformedInterval(msg))
+                    if msg == format!("{bound} bound of the harmonic mean overflows to infinity")
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_harmonic_ci_crossing_zero() -> CIResult<()> {
+        // very dispersed data: the interval on the mean of the reciprocals crosses zero
+        // (previously failed with `InvalidBounds`)
+        let data = [0.01, 100., 100.];
+        let stats = Harmonic::from_iter(&data)?;
+        let confidence = Confidence::new_two_sided(0.95);
+        let recip_ci = stats.reciprocal_space().ci_mean(confidence)?;
+        assert!(recip_ci.low_f() < 0.);
+
+        let ci = stats.ci_mean(confidence)?;
+        assert_eq!(ci, Interval::new_upper(1. / recip_ci.high_f()));
+        assert!(ci.contains(&stats.sample_mean()));
+        assert_eq!(ci, Harmonic::ci(confidence, &data)?);
+
+        // the upper one-sided interval is unaffected
+        let upper = Confidence::new_upper(0.975);
+        assert_abs_diff_eq!(stats.ci_mean(upper)?, ci, epsilon = 1e-10);
+
+        // no bound remains for the lower one-sided interval
+        assert!(matches!(
+            stats.ci_mean(Confidence::new_lower(0.975)),
+            Err(CIError::UninterpretableTransformedInterval(_))
+        ));
+
+        // the geometric mean is not affected
+        let ci = Geometric::ci(confidence, &data)?;
+        assert!(ci.is_two_sided());
+        assert!(ci.low_f() > 0.);
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)] // testing `Add` explicitly
+    fn test_arithmetic_add() {
+        const VALUE: f32 = 0.1;
+        let size = 1_000_000;
+
+        let mut stats_ref = Arithmetic::default();
+        let mut stats_summed = Arithmetic::default();
+        let mut stats_summed_in_place = Arithmetic::default();
+        for _ in 0..size {
+            stats_ref.append(VALUE).unwrap();
+
+            let mut new_stat = Arithmetic::default();
+            new_stat.append(VALUE).unwrap();
+            stats_summed = stats_summed + new_stat;
+            stats_summed_in_place += new_stat;
+        }
+
+        assert_eq!(stats_ref.sample_count(), size);
+        assert_eq!(stats_summed.sample_count(), size);
+        assert_eq!(stats_summed_in_place.sample_count(), size);
+
+        assert_eq!(stats_ref.sample_mean(), stats_summed.sample_mean());
+        assert_eq!(stats_ref.sample_variance(), stats_summed.sample_variance());
+        assert_eq!(stats_ref.sample_std_dev(), stats_summed.sample_std_dev());
+        assert_eq!(stats_ref.sample_sem(), stats_summed.sample_sem());
+
+        assert_eq!(stats_ref.sample_mean(), stats_summed_in_place.sample_mean());
+        assert_eq!(
+            stats_ref.sample_variance(),
+            stats_summed_in_place.sample_variance()
+        );
+        assert_eq!(
+            stats_ref.sample_std_dev(),
+            stats_summed_in_place.sample_std_dev()
+        );
+        assert_eq!(stats_ref.sample_sem(), stats_summed_in_place.sample_sem());
+    }
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)] // testing `Add` explicitly
+    fn test_geometric_add() {
+        const VALUE: f32 = 0.1;
+        let size = 1_000_000;
+
+        let mut stats_ref = Geometric::default();
+        let mut stats_summed = Geometric::default();
+        let mut stats_summed_in_place = Geometric::default();
+        for _ in 0..size {
+            stats_ref.append(VALUE).unwrap();
+
+            let mut new_stat = Geometric::default();
+            new_stat.append(VALUE).unwrap();
+            stats_summed = stats_summed + new_stat;
+            stats_summed_in_place += new_stat;
+        }
+
+        assert_eq!(stats_ref.sample_count(), size);
+        assert_eq!(stats_summed.sample_count(), size);
+        assert_eq!(stats_summed_in_place.sample_count(), size);
+
+        assert_eq!(stats_ref.sample_mean(), stats_summed.sample_mean());
+        assert_eq!(stats_ref.sample_sem(), stats_summed.sample_sem());
+
+        assert_eq!(stats_ref.sample_mean(), stats_summed_in_place.sample_mean());
+        assert_eq!(stats_ref.sample_sem(), stats_summed_in_place.sample_sem());
+    }
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)] // testing `Add` explicitly
+    fn test_harmonic_add() {
+        const VALUE: f32 = 0.1;
+        let size = 1_000_000;
+
+        let mut stats_ref = Harmonic::default();
+        let mut stats_summed = Harmonic::default();
+        let mut stats_summed_in_place = Harmonic::default();
+        for _ in 0..size {
+            stats_ref.append(VALUE).unwrap();
+
+            let mut new_stat = Harmonic::default();
+            new_stat.append(VALUE).unwrap();
+            stats_summed = stats_summed + new_stat;
+            stats_summed_in_place += new_stat;
+        }
+
+        assert_eq!(stats_ref.sample_count(), size);
+        assert_eq!(stats_summed.sample_count(), size);
+        assert_eq!(stats_summed_in_place.sample_count(), size);
+
+        assert_eq!(stats_ref.sample_mean(), stats_summed.sample_mean());
+        assert_eq!(stats_ref.sample_sem(), stats_summed.sample_sem());
+
+        assert_eq!(stats_ref.sample_mean(), stats_summed_in_place.sample_mean());
+        assert_eq!(stats_ref.sample_sem(), stats_summed_in_place.sample_sem());
+    }
+
+    #[test]
+    fn test_from_summary() -> CIResult<()> {
+        let data = [
+            82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
+            15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
+            71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
+            98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
+            49., 23., 2., 10., 67., 2., 53., 7., 5., 25., 68., 29., 77., 30., 54., 62., 51., 75.,
+            21., 35., 61., 89., 47., 81., 97., 84., 24., 56., 88., 23., 72.,
+        ];
+        let raw = Arithmetic::from_iter(&data)?;
+        let stats =
+            Arithmetic::from_summary(raw.sample_count(), raw.sample_mean(), raw.sample_std_dev())?;
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_abs_diff_eq!(
+            stats.ci_mean(confidence)?,
+            raw.ci_mean(confidence)?,
+            epsilon = 1e-10
+        );
+
+        // large mean relative to the spread
+        let stats = Arithmetic::from_summary(25, 1e4, 0.25)?;
+        assert_eq!(stats.sample_mean(), 1e4);
+        assert_relative_eq!(stats.sample_std_dev(), 0.25, max_relative = 1e-6);
+
+        let stats = Arithmetic::from_summary(5, 3., 0.)?;
+        assert_eq!(stats.sample_std_dev(), 0.);
+
+        assert_eq!(
+            Arithmetic::from_summary(1, 3., 1.),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert_eq!(
+            Arithmetic::from_summary(5, 3., -1.),
+            Err(CIError::InvalidInputData)
+        );
+        assert_eq!(
+            Arithmetic::from_summary(5, f64::INFINITY, 1.),
+            Err(CIError::InvalidInputData)
+        );
+        assert_eq!(
+            Arithmetic::from_summary(5, 3., f64::NAN),
+            Err(CIError::InvalidInputData)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sum_diff_ci() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let stats_a = Arithmetic::from_iter(&[134., 146., 104., 119., 124., 161., 107., 83.])?;
+        let stats_b = Arithmetic::from_iter(&[70., 118., 101., 85., 107., 132., 94.])?;
+
+        let diff = diff_ci(confidence, &stats_a, &stats_b)?;
+        assert_eq!(
+            diff,
+            comparison::Unpaired::new(stats_a, stats_b).ci_mean(confidence)?
+        );
+        let sum = sum_ci(confidence, &stats_a, &stats_b)?;
+        let shift = stats_a.sample_mean() + stats_b.sample_mean()
+            - (stats_a.sample_mean() - stats_b.sample_mean());
+        assert_abs_diff_eq!(sum, diff + shift, epsilon = 1e-10);
+        assert_abs_diff_eq!(sum.width().unwrap(), diff.width().unwrap(), epsilon = 1e-10);
+
+        // with equal standard errors and many samples, interval arithmetic is sqrt(2) wider,
+        // which corresponds to a coverage of P(|Z| < 1.96 * sqrt(2)) ≈ 99.4%
+        let stats_a = Arithmetic::from_summary(100_000, 10., 2.)?;
+        let stats_b = Arithmetic::from_summary(100_000, 3., 2.)?;
+        let naive = stats_a.ci_mean(confidence)? + stats_b.ci_mean(confidence)?;
+        let sum = sum_ci(confidence, &stats_a, &stats_b)?;
+        assert_relative_eq!(
+            naive.width().unwrap() / sum.width().unwrap(),
+            2_f64.sqrt(),
+            max_relative = 1e-4
+        );
+        let naive = stats_a.ci_mean(confidence)? - stats_b.ci_mean(confidence)?;
+        let diff = diff_ci(confidence, &stats_a, &stats_b)?;
+        assert_relative_eq!(
+            naive.width().unwrap() / diff.width().unwrap(),
+            2_f64.sqrt(),
+            max_relative = 1e-4
+        );
+        let z = 1.959_963_984_540_054;
+        let coverage = 2. * crate::special::normal_cdf(z * 2_f64.sqrt()) - 1.;
+        assert_abs_diff_eq!(coverage, 0.9944, epsilon = 1e-4);
+
+        let single = Arithmetic::from_iter(&[1.])?;
+        assert_eq!(
+            sum_ci(confidence, &single, &stats_b),
+            Err(CIError::TooFewSamples(1))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_of_fn() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1619);
+        // times per operation, around 10ms
+        let data = (0..400)
+            .map(|_| 0.008 + 0.004 * rng.gen::<f64>())
+            .collect::<Vec<_>>();
+        let stats = Arithmetic::from_iter(&data)?;
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = stats.ci_reciprocal_mean(confidence)?;
+        assert!(ci.contains(&(1. / stats.sample_mean())));
+
+        // percentile bootstrap of the reciprocal of the mean
+        let mut resampled = (0..2000)
+            .map(|_| {
+                let sum = (0..data.len())
+                    .map(|_| data[rng.gen_range(0..data.len())])
+                    .sum::<f64>();
+                data.len() as f64 / sum
+            })
+            .collect::<Vec<_>>();
+        resampled.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let bootstrap = Interval::new(resampled[50], resampled[1949])?;
+        assert_relative_eq!(ci.low_f(), bootstrap.low_f(), max_relative = 2e-3);
+        assert_relative_eq!(ci.high_f(), bootstrap.high_f(), max_relative = 2e-3);
+        assert_relative_eq!(
+            ci.width().unwrap(),
+            bootstrap.width().unwrap(),
+            max_relative = 0.1
+        );
+
+        // one-sided intervals keep their direction
+        let upper = stats.ci_reciprocal_mean(Confidence::new_upper(0.975))?;
+        assert!(upper.is_upper());
+        assert_abs_diff_eq!(upper.low_f(), ci.low_f(), epsilon = 1e-10);
+        let lower = stats.ci_reciprocal_mean(Confidence::new_lower(0.975))?;
+        assert!(lower.is_lower());
+        assert_abs_diff_eq!(lower.high_f(), ci.high_f(), epsilon = 1e-10);
+
+        // logarithm of the mean
+        let ci_log = stats.ci_log_mean(confidence)?;
+        let ci_mean = stats.ci_mean(confidence)?;
+        assert!(ci_log.contains(&stats.sample_mean().ln()));
+        assert_relative_eq!(
+            ci_log.width().unwrap(),
+            ci_mean.width().unwrap() / stats.sample_mean(),
+            max_relative = 1e-10
+        );
+
+        // degenerate
+        let constant = Arithmetic::from_iter(&[4., 4., 4.])?;
+        assert_eq!(
+            constant.ci_reciprocal_mean(confidence)?,
+            Interval::new(0.25, 0.25)?
+        );
+
+        // errors
+        let centered = Arithmetic::from_iter(&[-1., 1.])?;
+        assert!(matches!(
+            centered.ci_reciprocal_mean(confidence),
+            Err(CIError::InvalidDerivative(x)) if x.is_infinite()
+        ));
+        assert!(matches!(
+            stats.ci_of_fn(confidence, |x| x * x, |_| 0.),
+            Err(CIError::InvalidDerivative(x)) if x == 0.
+        ));
+        assert!(matches!(
+            stats.ci_of_fn(confidence, |x| x * x, |_| f64::NAN),
+            Err(CIError::InvalidDerivative(_))
+        ));
+        assert!(matches!(
+            Arithmetic::from_iter(&[-1., -2.])?.ci_log_mean(confidence),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert_eq!(
+            Arithmetic::<f64>::new().ci_reciprocal_mean(confidence),
+            Err(CIError::TooFewSamples(0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_equality_and_debug() -> CIResult<()> {
+        use rand::{seq::SliceRandom, Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1622);
+        let data = (0..1000)
+            .map(|_| rng.gen_range(0.1..1000.))
+            .collect::<Vec<f64>>();
+        let mut shuffled = data.clone();
+        shuffled.shuffle(&mut rng);
+
+        // identical ingestion orders
+        assert_eq!(Arithmetic::from_iter(&data)?, Arithmetic::from_iter(&data)?);
+        assert_eq!(Geometric::from_iter(&data)?, Geometric::from_iter(&data)?);
+        assert_eq!(Harmonic::from_iter(&data)?, Harmonic::from_iter(&data)?);
+        let mut merged = Arithmetic::from_iter(&data[..500].to_vec())?;
+        merged += Arithmetic::from_iter(&data[500..].to_vec())?;
+        assert_ne!(
+            Arithmetic::from_iter(&data)?,
+            Arithmetic::from_iter(&data[1..].to_vec())?
+        );
+
+        // different ingestion orders
+        assert_abs_diff_eq!(
+            Arithmetic::from_iter(&data)?,
+            Arithmetic::from_iter(&shuffled)?,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(merged, Arithmetic::from_iter(&shuffled)?, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            Geometric::from_iter(&data)?,
+            Geometric::from_iter(&shuffled)?,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            Harmonic::from_iter(&data)?,
+            Harmonic::from_iter(&shuffled)?,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_ne!(
+            Arithmetic::from_iter(&data)?,
+            Arithmetic::from_iter(&data[1..].to_vec())?
+        );
+        assert_abs_diff_ne!(
+            Arithmetic::from_iter(&[1., 2.])?,
+            Arithmetic::from_iter(&[1., 2.1])?,
+            epsilon = 1e-3
+        );
+        assert_abs_diff_eq!(Arithmetic::<f64>::new(), Arithmetic::new());
+
+        // human-readable debug output
+        let stats = Arithmetic::from_iter(&[2., 4., 6.])?;
+        let debug = format!("{:?}", stats);
+        assert!(
+            debug.starts_with("Arithmetic { count: 3, mean: 4.0, variance: 4.0, sum: "),
+            "{}",
+            debug
+        );
+        let debug = format!("{:?}", Geometric::from_iter(&[2., 8.])?);
+        assert!(
+            debug.starts_with("Geometric { count: 2, mean: 4.0, log_space: Arithmetic {"),
+            "{}",
+            debug
+        );
+        let debug = format!("{:?}", Harmonic::from_iter(&[1., 1.])?);
+        assert!(
+            debug.starts_with(
+                "Harmonic { count: 2, mean: 1.0, reciprocal_space: Arithmetic { count: 2, mean: 1.0, variance: 0.0,"
+            ),
+            "{}",
+            debug
+        );
+        let debug = format!("{:?}", Arithmetic::<f32>::new());
+        assert!(
+            debug.starts_with("Arithmetic { count: 0, mean: NaN, variance: NaN,"),
+            "{}",
+            debug
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_pool_meta_analysis() -> CIResult<()> {
+        // log relative risks and standard errors of the BCG vaccine trials (`dat.bcg` in R's
+        // metafor); reference values from `rma(yi, vi, method="FE")` and `method="DL"`
+        let estimates = [
+            (-0.8893113339, 0.5706003549),
+            (-1.5853886572, 0.4411135017),
+            (-1.3480731483, 0.6444904696),
+            (-1.44155119, 0.1414568199),
+            (-0.2175473222, 0.2262966464),
+            (-0.7861155858, 0.0831000509),
+            (-1.6208982236, 0.4722470197),
+            (0.0119523335, 0.062941078),
+            (-0.4694176487, 0.2375588568),
+            (-1.3713448035, 0.2702310005),
+            (-0.3393588283, 0.1114101161),
+            (0.4459134006, 0.729729981),
+            (-0.0173139482, 0.2672165034),
+        ];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        let fixed = pool_fixed_effect(confidence, &estimates)?;
+        assert_abs_diff_eq!(fixed, Interval::new(-0.5097, -0.3509)?, epsilon = 1e-4);
+
+        let random = pool_random_effects(confidence, &estimates)?;
+        assert_abs_diff_eq!(random.estimate, -0.7141, epsilon = 1e-4);
+        assert_abs_diff_eq!(random.std_err, 0.1787, epsilon = 1e-4);
+        assert_abs_diff_eq!(random.tau_squared, 0.3088, epsilon = 1e-4);
+        assert_abs_diff_eq!(random.q, 152.2330, epsilon = 1e-4);
+        assert_abs_diff_eq!(random.i_squared, 0.9212, epsilon = 1e-4);
+        assert_abs_diff_eq!(
+            random.interval,
+            Interval::new(-1.0644, -0.3638)?,
+            epsilon = 1e-4
+        );
+
+        // homogeneous estimates: no heterogeneity, same as fixed effect
+        let homogeneous = [(1., 0.5), (1.1, 0.4), (0.9, 0.6)];
+        let random = pool_random_effects(confidence, &homogeneous)?;
+        assert_eq!(random.tau_squared, 0.);
+        assert_eq!(random.i_squared, 0.);
+        assert_eq!(
+            random.interval,
+            pool_fixed_effect(confidence, &homogeneous)?
+        );
+
+        // one-sided
+        let upper = pool_fixed_effect(Confidence::new_upper(0.95), &estimates)?;
+        assert!(upper.is_upper());
+        assert_abs_diff_eq!(upper.low_f(), -0.4303 - 1.6449 * 0.0405, epsilon = 1e-3);
+
+        // from the statistics of each sample
+        let stats_a = Arithmetic::from_iter(&[10.1, 10.4, 9.9, 10.2, 10.0])?;
+        let stats_b = Arithmetic::from_iter(&[9.7, 10.0, 10.3, 9.9, 10.1, 10.2])?;
+        let ci = pool_fixed_effect_stats(confidence, &[&stats_a, &stats_b])?;
+        assert_eq!(
+            ci,
+            pool_fixed_effect(
+                confidence,
+                &[
+                    (stats_a.sample_mean(), stats_a.sample_sem()),
+                    (stats_b.sample_mean(), stats_b.sample_sem())
+                ]
+            )?
+        );
+        let ci_f32 = pool_fixed_effect_stats(
+            confidence,
+            &[
+                &Arithmetic::from_iter(&[10.1_f32, 10.4, 9.9, 10.2, 10.0])?,
+                &Arithmetic::from_iter(&[9.7_f32, 10.0, 10.3, 9.9, 10.1, 10.2])?,
+            ],
+        )?;
+        assert_abs_diff_eq!(ci_f32.low_f() as f64, ci.low_f(), epsilon = 1e-5);
+
+        // errors
+        assert_eq!(
+            pool_fixed_effect(confidence, &[(1., 0.1)]),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert_eq!(
+            pool_random_effects(confidence, &[]),
+            Err(CIError::TooFewSamples(0))
+        );
+        assert!(matches!(
+            pool_fixed_effect(confidence, &[(1., 0.1), (2., 0.)]),
+            Err(CIError::InvalidStdError(x)) if x == 0.
+        ));
+        assert!(matches!(
+            pool_random_effects(confidence, &[(1., -0.1), (2., 0.1)]),
+            Err(CIError::InvalidStdError(_))
+        ));
+        assert_eq!(
+            pool_fixed_effect(confidence, &[(f64::NAN, 0.1), (2., 0.1)]),
+            Err(CIError::InvalidInputData)
+        );
+        let single = Arithmetic::from_iter(&[1.])?;
+        assert_eq!(
+            pool_fixed_effect_stats(confidence, &[&stats_a, &single]),
+            Err(CIError::TooFewSamples(1))
+        );
+        let constant = Arithmetic::from_iter(&[1., 1., 1.])?;
+        assert!(matches!(
+            pool_fixed_effect_stats(confidence, &[&stats_a, &constant]),
+            Err(CIError::InvalidStdError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_chunks_deterministic() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1599);
+        let data: Vec<f64> = (0..100_003).map(|_| rng.gen_range(-1e3..1e6)).collect();
+
+        let stats = Arithmetic::from_chunks_deterministic(&data, 4_096);
+        let sequential = data
+            .chunks(4_096)
+            .map(|chunk| Arithmetic::from_iter(&chunk.to_vec()).unwrap())
+            .fold(Arithmetic::new(), Arithmetic::add);
+        assert_eq!(format!("{stats:?}"), format!("{sequential:?}"));
+        assert_eq!(stats.sample_count(), data.len());
+
+        let reference = Arithmetic::from_iter(&data)?;
+        assert_eq!(
+            Arithmetic::from_chunks_deterministic(&data, data.len()),
+            reference
+        );
+        assert_relative_eq!(
+            Arithmetic::from_chunks_deterministic(&data, 7).sample_mean(),
+            reference.sample_mean(),
+            max_relative = 1e-12
+        );
+        assert_eq!(
+            Arithmetic::<f64>::from_chunks_deterministic(&[], 16),
+            Arithmetic::new()
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_chunks_deterministic_zero_chunk() {
+        Arithmetic::from_chunks_deterministic(&[1., 2.], 0);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_from_chunks_deterministic_threads() {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1599);
+        let data: Vec<f64> = (0..200_000).map(|_| rng.gen_range(-1e3..1e6)).collect();
+
+        // the Debug output exposes every internal register (sums and Kahan compensations)
+        let serialized = |stats: &Arithmetic<f64>| {
+            #[cfg(feature = "serde")]
+            return toml::to_string(stats).unwrap();
+            #[cfg(not(feature = "serde"))]
+            return format!("{stats:?}");
+        };
+        let expected = serialized(&Arithmetic::from_chunks_deterministic(&data, 1_000));
+        let pools = [1, 2, 3, 4, 8].map(|n| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .unwrap()
+        });
+        for run in 0..100 {
+            let stats = pools[run % pools.len()]
+                .install(|| Arithmetic::from_chunks_deterministic(&data, 1_000));
+            assert_eq!(serialized(&stats), expected, "run {run}");
+        }
+    }
+
+    #[test]
+    fn test_dispersion() -> CIResult<()> {
+        // data of the README; the reference values were computed from the definitions
+        // (exp of the sample standard deviation of the logs, as with `scipy.stats.gstd`;
+        // squared harmonic mean times the sample standard deviation of the reciprocals)
+        let data = [
+            10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4, 10.7,
+            0.4, 10.1, 4.5, 7.1, 4.3, 37.4, 0.9, 10.1, 12.6, 21.7, 21.9, 2.0, 8.4, 9.3,
+        ];
+        let geometric = Geometric::from_iter(&data)?;
+        assert_abs_diff_eq!(
+            geometric.geometric_std_dev(),
+            4.207528867630886,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            geometric.geometric_std_dev().ln(),
+            geometric.log_space().sample_std_dev(),
+            epsilon = 1e-12
+        );
+        assert_eq!(geometric.log_space().sample_count(), data.len());
+
+        let harmonic = Harmonic::from_iter(&data)?;
+        assert_abs_diff_eq!(harmonic.sample_mean(), 1.6690530671873192, epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            harmonic.harmonic_std_dev(),
+            2.726687958343928,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            harmonic.sample_sem(),
+            harmonic.harmonic_std_dev() / (data.len() as f64).sqrt(),
+            epsilon = 1e-12
+        );
+        assert_eq!(harmonic.reciprocal_space().sample_count(), data.len());
+
+        // consistent under merging
+        let (head, tail) = data.split_at(12);
+        let merged = Geometric::from_iter(&head.to_vec())? + Geometric::from_iter(&tail.to_vec())?;
+        assert_abs_diff_eq!(
+            merged.geometric_std_dev(),
+            geometric.geometric_std_dev(),
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            merged.log_space().sample_mean(),
+            geometric.log_space().sample_mean(),
+            epsilon = 1e-12
+        );
+        let merged = Harmonic::from_iter(&head.to_vec())? + Harmonic::from_iter(&tail.to_vec())?;
+        assert_abs_diff_eq!(
+            merged.harmonic_std_dev(),
+            harmonic.harmonic_std_dev(),
+            epsilon = 1e-10
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_mean_detailed() -> CIResult<()> {
+        let data = [
+            82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
+            15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
+        ];
+        let stats = Arithmetic::from_iter(&data)?;
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ] {
+            let detail = stats.ci_mean_detailed(confidence)?;
+            assert_eq!(detail.interval, stats.ci_mean(confidence)?);
+            assert_eq!(detail.estimate, stats.sample_mean());
+            assert_eq!(detail.degrees_of_freedom, (data.len() - 1) as f64);
+            assert_eq!(detail.distribution, CriticalDistribution::StudentT);
+            assert_eq!(
+                detail.critical_value,
+                stats::t_value(confidence, detail.degrees_of_freedom)?
+            );
+
+            // the interval is reconstructed exactly from the reported quantities
+            let span = detail.critical_value * detail.std_err;
+            let expected = Interval::from_bounds_for(
+                confidence,
+                detail.estimate - span,
+                detail.estimate + span,
+            )?;
+            assert_eq!(detail.interval, expected);
+        }
+
+        // beyond 100'000 samples, the critical value comes from the normal distribution
+        let mut stats = Arithmetic::new();
+        for i in 0..100_002 {
+            stats.append((i % 10) as f64)?;
+        }
+        let confidence = Confidence::new_two_sided(0.95);
+        let detail = stats.ci_mean_detailed(confidence)?;
+        assert_eq!(detail.distribution, CriticalDistribution::Normal);
+        assert_eq!(detail.critical_value, stats::z_value(confidence));
+        let span = detail.critical_value * detail.std_err;
+        assert_eq!(
+            detail.interval,
+            Interval::new(detail.estimate - span, detail.estimate + span)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_huge_count_f32() -> CIResult<()> {
+        // 2^24 + 1 values summing to 2^24: the mean is 1 - 2^-24, but dividing by the count
+        // rounded to f32 (2^24) gives 1
+        let count = (1 << 24) + 1;
+        let sum = (1 << 24) as f32;
+        let sum_sq = (1 << 26) as f32;
+        assert_eq!(sum / count as f32, 1.);
+
+        let stats = Arithmetic::from_raw(count, sum, sum_sq);
+        assert_eq!(stats.sample_mean(), 1. - f32::EPSILON / 2.);
+        let wide = Arithmetic::from_raw(count, sum as f64, sum_sq as f64);
+        assert_abs_diff_eq!(
+            stats.sample_variance() as f64,
+            wide.sample_variance(),
+            epsilon = 1e-7
+        );
+        assert_abs_diff_eq!(
+            stats.sample_sem() as f64,
+            wide.sample_sem(),
+            epsilon = 1e-10
+        );
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = stats.ci_mean(confidence)?;
+        let wide_ci = wide.ci_mean(confidence)?;
+        assert_abs_diff_eq!(ci.low_f() as f64, wide_ci.low_f(), epsilon = 1e-7);
+        assert_abs_diff_eq!(ci.high_f() as f64, wide_ci.high_f(), epsilon = 1e-7);
+
+        // counts that are not exactly representable in f64 either
+        let stats = Arithmetic::from_raw((1 << 53) + 1, 1., 1.);
+        assert!(stats.sample_mean().is_nan());
+        assert!(stats
+            .ci_mean(confidence)
+            .is_err_and(|e| e.is_conversion_error()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_conversion_failure() {
+        let confidence = Confidence::new_two_sided(0.95);
+        let small = Arithmetic::from_raw(10, Bounded(10.), Bounded(12.));
+        assert_eq!(small.sample_mean(), Bounded(1.));
+        assert!(small.ci_mean(confidence).is_ok());
+
+        // neither the count nor the sum can be converted
+        let count = (Bounded::LIMIT as usize) * 2;
+        let large = Arithmetic::from_raw(count, Bounded(4e6), Bounded(1e7));
+        assert!(large.sample_mean().0.is_nan());
+        assert!(large.sample_sem().0.is_nan());
+        assert!(large
+            .ci_mean(confidence)
+            .is_err_and(|e| e.is_conversion_error()));
+    }
+
+    ///
+    /// Float type that fails to convert from and to values beyond a limit.
+    ///
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+    struct Bounded(f64);
+
+    impl Bounded {
+        const LIMIT: f64 = 1e6;
+
+        fn checked(value: f64) -> Option<f64> {
+            (value.is_nan() || value.abs() <= Self::LIMIT).then_some(value)
+        }
+    }
+
+    macro_rules! bounded_ops {
+        ($($trait:ident $method:ident),*) => {$(
+            impl core::ops::$trait for Bounded {
+                type Output = Self;
+                fn $method(self, rhs: Self) -> Self {
+                    Bounded(core::ops::$trait::$method(self.0, rhs.0))
+                }
+            }
+        )*};
+    }
+    bounded_ops!(Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+    impl core::ops::Neg for Bounded {
+        type Output = Self;
+        fn neg(self) -> Self {
+            Bounded(-self.0)
+        }
+    }
+
+    impl num_traits::Zero for Bounded {
+        fn zero() -> Self {
+            Bounded(0.)
+        }
+        fn is_zero(&self) -> bool {
+            self.0 == 0.
+        }
+    }
+
+    impl num_traits::One for Bounded {
+        fn one() -> Self {
+            Bounded(1.)
+        }
+    }
+
+    impl num_traits::Num for Bounded {
+        type FromStrRadixErr = num_traits::ParseFloatError;
+        fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+            f64::from_str_radix(s, radix).map(Bounded)
+        }
+    }
+
+    impl num_traits::ToPrimitive for Bounded {
+        fn to_i64(&self) -> Option<i64> {
+            Self::checked(self.0).and_then(|x| x.to_i64())
+        }
+        fn to_u64(&self) -> Option<u64> {
+            Self::checked(self.0).and_then(|x| x.to_u64())
+        }
+        fn to_f64(&self) -> Option<f64> {
+            Self::checked(self.0)
+        }
+    }
+
+    impl num_traits::NumCast for Bounded {
+        fn from<T: num_traits::ToPrimitive>(n: T) -> Option<Self> {
+            n.to_f64().and_then(Self::checked).map(Bounded)
+        }
+    }
+
+    macro_rules! bounded_float {
+        (
+            consts: $($const:ident),*;
+            unary: $($unary:ident),*;
+            binary: $($binary:ident),*;
+            predicates: $($predicate:ident),*
+        ) => {
+            impl num_traits::Float for Bounded {
+                $(fn $const() -> Self { Bounded(<f64 as num_traits::Float>::$const()) })*
+                $(fn $unary(self) -> Self { Bounded(<f64 as num_traits::Float>::$unary(self.0)) })*
+                $(fn $binary(self, other: Self) -> Self {
+                    Bounded(<f64 as num_traits::Float>::$binary(self.0, other.0))
+                })*
+                $(fn $predicate(self) -> bool { <f64 as num_traits::Float>::$predicate(self.0) })*
+                fn classify(self) -> core::num::FpCategory {
+                    self.0.classify()
+                }
+                fn mul_add(self, a: Self, b: Self) -> Self {
+                    Bounded(self.0.mul_add(a.0, b.0))
+                }
+                fn powi(self, n: i32) -> Self {
+                    Bounded(self.0.powi(n))
+                }
+                fn sin_cos(self) -> (Self, Self) {
+                    let (sin, cos) = self.0.sin_cos();
+                    (Bounded(sin), Bounded(cos))
+                }
+                fn integer_decode(self) -> (u64, i16, i8) {
+                    num_traits::Float::integer_decode(self.0)
+                }
+            }
+        };
+    }
+    bounded_float!(
+        consts: nan, infinity, neg_infinity, neg_zero, min_value, min_positive_value, max_value;
+        unary: floor, ceil, round, trunc, fract, abs, signum, recip, sqrt, exp, exp2, ln, log2,
+            log10, cbrt, sin, cos, tan, asin, acos, atan, exp_m1, ln_1p, sinh, cosh, tanh, asinh,
+            acosh, atanh;
+        binary: powf, log, max, min, abs_sub, hypot, atan2;
+        predicates: is_nan, is_infinite, is_finite, is_normal, is_sign_positive, is_sign_negative
+    );
+
+    #[test]
+    fn test_stopping_rule() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // known standard deviation of 1: the half-width is about 1.96 / sqrt(n),
+        // which reaches 0.1 at n = 384
+        let expected = (1.96_f64 / 0.1).powi(2);
+        let rule = StoppingRule::half_width(0.1)?;
+        let mut stats = Arithmetic::new();
+        let mut sign = 1.;
+        while !rule.should_stop(&stats, confidence)? {
+            stats.append(10. + sign)?;
+            sign = -sign;
+        }
+        let stopped = stats.sample_count() as f64;
+        assert!(stopped >= 0.95 * expected, "stopped at {stopped}");
+        assert!(stopped <= 1.1 * expected + 1., "stopped at {stopped}");
+        // geometric schedule from 30 samples: 30, 33, 37, ...
+        assert!(rule.evaluations() <= 30);
+        assert!(rule.evaluations() >= 20);
+
+        // normal data with standard deviation 5 and mean 100: ±0.5% of the mean is a
+        // half-width of 0.5, reached at n = 384
+        use rand::{distributions::Distribution, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1638);
+        let normal = statrs::distribution::Normal::new(100., 5.).unwrap();
+        let rule = StoppingRule::relative_half_width(0.005)?;
+        let mut stats = Arithmetic::new();
+        while !rule.should_stop(&stats, confidence)? {
+            stats.append(normal.sample(&mut rng))?;
+        }
+        let stopped = stats.sample_count() as f64;
+        assert!(stopped >= 0.8 * expected, "stopped at {stopped}");
+        assert!(stopped <= 1.3 * expected, "stopped at {stopped}");
+
+        // no evaluation before the minimum number of samples, then on every sample
+        let rule = StoppingRule::half_width(100.)?
+            .min_samples(10)
+            .growth_factor(1.);
+        let mut stats = Arithmetic::new();
+        for i in 0..9 {
+            stats.append(i as f64)?;
+            assert!(!rule.should_stop(&stats, confidence)?);
+        }
+        assert_eq!(rule.evaluations(), 0);
+        stats.append(9.)?;
+        assert!(rule.should_stop(&stats, confidence)?);
+        // not re-evaluated until a new sample is added
+        assert!(!rule.should_stop(&stats, confidence)?);
+        stats.append(10.)?;
+        assert!(rule.should_stop(&stats, confidence)?);
+        assert_eq!(rule.evaluations(), 2);
+
+        assert!(matches!(
+            StoppingRule::half_width(0.),
+            Err(CIError::InvalidTarget(_))
+        ));
+        assert!(matches!(
+            StoppingRule::relative_half_width(f64::NAN),
+            Err(CIError::InvalidTarget(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_bootstrap_t() -> CIResult<()> {
+        use rand::SeedableRng;
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1633);
+        let data = [0.5, 0.8, 1.1, 1.2, 1.4, 2.0, 2.3, 3.1, 5.8, 9.7];
+        let mean = Arithmetic::from_iter(&data)?.sample_mean();
+
+        // with the same resamples, the one-sided 95% bounds are those of the two-sided 90% interval
+        let seeded = |seed| rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+        let two_sided =
+            ci_bootstrap_t(Confidence::new_two_sided(0.9), &data, 2_000, &mut seeded(7))?;
+        let upper = ci_bootstrap_t(Confidence::new_upper(0.95), &data, 2_000, &mut seeded(7))?;
+        let lower = ci_bootstrap_t(Confidence::new_lower(0.95), &data, 2_000, &mut seeded(7))?;
+        assert!(two_sided.contains(&mean));
+        assert_eq!(upper, Interval::new_upper(two_sided.low_f()));
+        assert_eq!(lower, Interval::new_lower(two_sided.high_f()));
+        // skewed to the right, like the data, unlike the t interval
+        assert!(two_sided.high_f() - mean > mean - two_sided.low_f());
+        let t_ci = Arithmetic::ci(Confidence::new_two_sided(0.9), &data)?;
+        assert!(two_sided.high_f() > t_ci.high_f());
+
+        // degenerate and invalid inputs
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(
+            ci_bootstrap_t(confidence, &[3., 3., 3.], 1_000, &mut rng)?,
+            Interval::new(3., 3.)?
+        );
+        assert_eq!(
+            ci_bootstrap_t(confidence, &[1.], 1_000, &mut rng),
+            Err(CIError::TooFewSamples(1))
+        );
+        assert_eq!(
+            ci_bootstrap_t(confidence, &[1., f64::NAN], 1_000, &mut rng),
+            Err(CIError::InvalidInputData)
+        );
+        assert_eq!(
+            ci_bootstrap_t(confidence, &data, 999, &mut rng),
+            Err(CIError::TooFewResamples {
+                resamples: 999,
+                required: 1000
+            })
+        );
+        assert_eq!(
+            ci_bootstrap_t(Confidence::new_upper(0.95), &data, 499, &mut rng),
+            Err(CIError::TooFewResamples {
+                resamples: 499,
+                required: 500
+            })
+        );
+        // with two samples, half of the resamples have zero variance
+        assert!(matches!(
+            ci_bootstrap_t(confidence, &[1., 2.], 1_000, &mut rng),
+            Err(CIError::TooFewResamples {
+                resamples: _,
+                required: 1000
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_bootstrap_t_coverage() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        use statrs::distribution::LogNormal;
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1633);
+        let distribution = LogNormal::new(0., 1.).unwrap();
+        let true_mean = 0.5_f64.exp();
+        let confidence = Confidence::new_two_sided(0.95);
+        let repetitions = 400;
+
+        let (mut hits_t, mut hits_bootstrap) = (0, 0);
+        for _ in 0..repetitions {
+            let data: Vec<f64> = (0..10).map(|_| rng.sample(distribution)).collect();
+            if Arithmetic::ci(confidence, &data)?.contains(&true_mean) {
+                hits_t += 1;
+            }
+            if ci_bootstrap_t(confidence, &data, 1_000, &mut rng)?.contains(&true_mean) {
+                hits_bootstrap += 1;
+            }
+        }
+        let coverage_t = hits_t as f64 / repetitions as f64;
+        let coverage_bootstrap = hits_bootstrap as f64 / repetitions as f64;
+        println!("coverage: t = {coverage_t}, bootstrap-t = {coverage_bootstrap}");
+        assert!(coverage_t < 0.9);
+        assert!(coverage_bootstrap > coverage_t + 0.03);
+        Ok(())
+    }
+
+    #[test]
+    fn test_geometric_extend_stops_at_invalid() -> CIResult<()> {
+        let data = [4., 8., -1., 16., 0.];
+        let mut stats = Geometric::from_iter(&[1., 2.])?;
+        let before = stats.sample_count();
+        assert_eq!(stats.extend(&data), Err(CIError::NonPositiveValue(-1.)));
+        let consumed = stats.sample_count() - before;
+        assert_eq!(consumed, 2);
+        assert_abs_diff_eq!(stats.sample_mean(), 64_f64.powf(0.25), epsilon = 1e-12);
+
+        // skip the faulty element and resume
+        assert_eq!(
+            stats.extend_iter(data[consumed + 1..].iter().copied()),
+            Err(CIError::NonPositiveValue(0.))
+        );
+        assert_eq!(stats.sample_count(), before + consumed + 1);
+
+        // the same position is reported by `try_extend_partial`
+        let partial = Geometric::from_iter(&[1., 2.])?
+            .try_extend_partial(data)
+            .unwrap_err();
+        assert_eq!(partial.index, consumed);
+        assert_eq!(partial.error, CIError::NonPositiveValue(-1.));
+        assert_eq!(partial.state.sample_count(), before + consumed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_mean_skew_corrected() -> CIResult<()> {
+        let data = [0.1, 0.2, 0.2, 0.3, 0.5, 0.7, 1.1, 1.6, 2.4, 4.9];
+        let stats = Arithmetic::from_iter(&data)?;
+        // shift: mu3 / (6 s^2 n) with the unbiased third moment
+        let n = data.len() as f64;
+        let mean = stats.sample_mean();
+        let mu3 = n / ((n - 1.) * (n - 2.)) * data.iter().map(|x| (x - mean).powi(3)).sum::<f64>();
+        let shift = mu3 / (6. * stats.sample_variance() * n);
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ] {
+            let symmetric = stats.ci_mean(confidence)?;
+            let corrected = stats.ci_mean_skew_corrected(confidence)?;
+            assert_abs_diff_eq!(corrected, symmetric + shift, epsilon = 1e-12);
         }
-    };
-}
 
-impl_mean_ci_for!(Arithmetic<F>);
-impl_mean_ci_for!(Harmonic<F>);
-impl_mean_ci_for!(Geometric<F>);
+        // fallbacks to the symmetric interval
+        let confidence = Confidence::new(0.95);
+        let summary = Arithmetic::from_summary(stats.sample_count(), mean, stats.sample_std_dev())?;
+        assert_eq!(
+            summary.ci_mean_skew_corrected(confidence)?,
+            summary.ci_mean(confidence)?
+        );
+        let pair = Arithmetic::from_iter(&[1., 5.])?;
+        assert_eq!(
+            pair.ci_mean_skew_corrected(confidence)?,
+            pair.ci_mean(confidence)?
+        );
+        let constant = Arithmetic::from_iter(&[2., 2., 2.])?;
+        assert_eq!(
+            constant.ci_mean_skew_corrected(confidence)?,
+            Interval::new(2., 2.)?
+        );
+        let symmetric = Arithmetic::from_iter(&[1., 2., 3., 4., 5.])?;
+        assert_abs_diff_eq!(
+            symmetric.ci_mean_skew_corrected(confidence)?,
+            symmetric.ci_mean(confidence)?,
+            epsilon = 1e-12
+        );
+        assert_eq!(
+            Arithmetic::<f64>::new().ci_mean_skew_corrected(confidence),
+            Err(CIError::TooFewSamples(0))
+        );
+        Ok(())
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use approx::*;
+    #[test]
+    fn test_ci_mean_skew_corrected_coverage() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        use statrs::distribution::Exp;
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1655);
+        let distribution = Exp::new(1.).unwrap();
+        let true_mean = 1.;
+        let repetitions = 20_000;
+
+        // for right-skewed data, the t-interval undercovers through its upper bound
+        // (upper one-sided intervals are already conservative)
+        // for right-skewed data, the t-interval undercovers through its upper bound
+        // (upper one-sided intervals are already conservative)
+        for confidence in [Confidence::new_two_sided(0.95), Confidence::new_lower(0.95)] {
+            let (mut hits_t, mut hits_corrected) = (0, 0);
+            for _ in 0..repetitions {
+                let data: Vec<f64> = (0..20).map(|_| rng.sample(distribution)).collect();
+                let stats = Arithmetic::from_iter(&data)?;
+                if stats.ci_mean(confidence)?.contains(&true_mean) {
+                    hits_t += 1;
+                }
+                if stats
+                    .ci_mean_skew_corrected(confidence)?
+                    .contains(&true_mean)
+                {
+                    hits_corrected += 1;
+                }
+            }
+            let coverage_t = hits_t as f64 / repetitions as f64;
+            let coverage_corrected = hits_corrected as f64 / repetitions as f64;
+            println!("{confidence}: t = {coverage_t}, skew-corrected = {coverage_corrected}");
+            assert!(coverage_t < confidence.level());
+            assert!(
+                (confidence.level() - coverage_corrected).abs()
+                    < (confidence.level() - coverage_t).abs()
+            );
+        }
+        Ok(())
+    }
 
     #[test]
-    fn test_mean_ci() -> CIResult<()> {
+    fn test_ci_mean_with_critical() -> CIResult<()> {
         let data = [
             82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
             15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
-            71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
-            98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
-            49., 23., 26., 55., 26., 3., 23., 47., 27., 58., 27., 97., 32., 29., 56., 28., 23.,
-            37., 72., 62., 77., 63., 100., 40., 84., 77., 39., 71., 61., 17., 77.,
         ];
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ] {
+            for size in 1..=data.len() {
+                let stats = Arithmetic::from_iter(&data[..size].to_vec())?;
+                let stats_f32 = Arithmetic::<f32>::from_iter(
+                    &data[..size].iter().map(|&x| x as f32).collect::<Vec<_>>(),
+                )?;
+                // the critical value is irrelevant for a single sample
+                let critical = match size {
+                    1 => 0.,
+                    _ => stats::critical_value(confidence, (size - 1) as f64)?.0,
+                };
+                assert_eq!(
+                    stats.ci_mean_with_critical(confidence, critical)?,
+                    stats.ci_mean(confidence)?
+                );
+                assert_eq!(
+                    stats_f32.ci_mean_with_critical(confidence, critical)?,
+                    stats_f32.ci_mean(confidence)?
+                );
+            }
+        }
 
+        // degenerate
+        let stats = Arithmetic::from_iter(&[3., 3., 3.])?;
         let confidence = Confidence::new_two_sided(0.95);
-        let ci = Arithmetic::ci(confidence, &data)?;
-        // mean: 53.67
-        // stddev: 28.097613040716798
-        // reference values computed in python
-        // [48.094823990767836, 59.24517600923217]
-        // ```python
-        // import numpy as np
-        // import scipy.stats as st
-        // st.t.interval(confidence=0.95, df=len(data)-1, loc=np.mean(data), scale=st.sem(data))
-        // ```
-        assert_abs_diff_eq!(ci.low_f(), 48.094823990767836, epsilon = 1e-8);
-        assert_abs_diff_eq!(ci.high_f(), 59.24517600923217, epsilon = 1e-8);
-        assert_abs_diff_eq!(ci.low_f() + ci.high_f(), 2. * 53.67);
+        assert_eq!(
+            stats.ci_mean_with_critical(confidence, 1.)?,
+            Interval::new(3., 3.)?
+        );
+
+        // errors
+        assert_eq!(
+            Arithmetic::<f64>::new().ci_mean_with_critical(confidence, 1.),
+            Err(CIError::TooFewSamples(0))
+        );
+        for critical in [-1., f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                stats.ci_mean_with_critical(confidence, critical),
+                Err(CIError::InvalidCriticalValue(_))
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_chunk() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1641);
+        let data = (0..10_007)
+            .map(|_| rng.gen_range(-5.0..20.0_f64).powi(3))
+            .collect::<Vec<_>>();
+        let expected = Arithmetic::from_iter(&data)?;
+
+        for chunk_size in [1, 3, 4, 5, 64, 1_000, 10_007] {
+            let mut stats = Arithmetic::new();
+            for chunk in data.chunks(chunk_size) {
+                stats.append_chunk(chunk)?;
+            }
+            stats.append_chunk(&[])?;
+            assert_eq!(stats.sample_count(), expected.sample_count());
+            assert_abs_diff_eq!(stats, expected, epsilon = 1e-9);
+            assert_relative_eq!(stats.sample_mean(), expected.sample_mean(), epsilon = 1e-12);
+            assert_relative_eq!(
+                stats.sample_variance(),
+                expected.sample_variance(),
+                max_relative = 1e-12
+            );
+            // the higher moments of the lanes are merged correctly
+            assert_relative_eq!(
+                stats.sample_skewness(),
+                expected.sample_skewness(),
+                epsilon = 1e-9
+            );
+            assert_relative_eq!(
+                stats.sample_kurtosis(),
+                expected.sample_kurtosis(),
+                epsilon = 1e-9
+            );
+
+            let mut chunks = data.chunks(chunk_size);
+            let pulled = Arithmetic::from_chunked_source(|| chunks.next())?;
+            assert_eq!(pulled, stats);
+        }
+
+        // appending to a non-empty state
+        let mut stats = Arithmetic::new();
+        for &x in &data[..100] {
+            stats.append(x)?;
+        }
+        stats.append_chunk(&data[100..])?;
+        assert_abs_diff_eq!(stats, expected, epsilon = 1e-9);
+        assert_relative_eq!(
+            stats.sample_kurtosis(),
+            expected.sample_kurtosis(),
+            epsilon = 1e-9
+        );
+
+        // values that overflow the sums are appended one by one, rescaling the state
+        let mut stats = Arithmetic::<f32>::from_iter(&[1., 2.])?;
+        stats.append_chunk(&[1e20, 1e20])?;
+        assert_eq!(stats, Arithmetic::from_iter(&[1., 2., 1e20, 1e20])?);
+        let overflowing = [1e20_f32, 1e20];
+        let mut chunks = [&overflowing[..]].into_iter();
+        assert_eq!(
+            Arithmetic::from_chunked_source(|| chunks.next())?,
+            Arithmetic::from_iter(&overflowing)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_extend_counted() -> CIResult<()> {
+        let pairs = [(82., 3), (6., 1), (39.5, 7), (97., 2), (10., 5)];
+        let naive = pairs
+            .iter()
+            .flat_map(|&(x, count)| core::iter::repeat_n(x, count as usize))
+            .collect::<Vec<f64>>();
+
+        let stats = Arithmetic::from_counts(pairs)?;
+        let expected = Arithmetic::from_iter(&naive)?;
+        assert_eq!(stats.sample_count(), expected.sample_count());
+        assert_abs_diff_eq!(stats, expected, epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            stats.sample_skewness(),
+            expected.sample_skewness(),
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            stats.sample_kurtosis(),
+            expected.sample_kurtosis(),
+            epsilon = 1e-10
+        );
+        let confidence = Confidence::new_two_sided(0.95);
+        let (ci, expected_ci) = (stats.ci_mean(confidence)?, expected.ci_mean(confidence)?);
+        assert_abs_diff_eq!(ci.low_f(), expected_ci.low_f(), epsilon = 1e-10);
+        assert_abs_diff_eq!(ci.high_f(), expected_ci.high_f(), epsilon = 1e-10);
+
+        let stats = Harmonic::from_counts(pairs)?;
+        let expected = Harmonic::from_iter(&naive)?;
+        assert_abs_diff_eq!(stats, expected, epsilon = 1e-10);
+        assert_abs_diff_eq!(stats.sample_mean(), expected.sample_mean(), epsilon = 1e-10);
+
+        let stats = Geometric::from_counts(pairs)?;
+        let expected = Geometric::from_iter(&naive)?;
+        assert_abs_diff_eq!(stats, expected, epsilon = 1e-10);
+        assert_abs_diff_eq!(stats.sample_mean(), expected.sample_mean(), epsilon = 1e-10);
+
+        // zero counts are ignored
+        let mut stats = Arithmetic::from_counts([(1., 2), (1000., 0)])?;
+        assert_eq!(stats, Arithmetic::from_iter(&[1., 1.])?);
+
+        // counts beyond u32
+        let large = u64::from(u32::MAX) + 10;
+        stats.extend_counted(4., large)?;
+        assert_eq!(stats.sample_count(), large as usize + 2);
+        assert_abs_diff_eq!(stats.sample_mean(), 4., epsilon = 1e-8);
+
+        // non-positive values
+        assert!(matches!(
+            Geometric::from_counts([(1., 2), (0., 1)]),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert!(matches!(
+            Harmonic::<f64>::new().extend_counted(-1., 1),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_extend_counted_overflow() -> CIResult<()> {
+        let mut stats = Arithmetic::new();
+        stats.extend_counted(1., usize::MAX as u64 - 1)?;
+        let before = stats;
+        assert!(matches!(
+            stats.extend_counted(1., 2),
+            Err(CIError::CountOverflow)
+        ));
+        assert_eq!(stats, before);
+        assert_eq!(stats.sample_count(), usize::MAX - 1);
+        stats.extend_counted(1., 1)?;
+        assert_eq!(stats.sample_count(), usize::MAX);
+
+        // the sums overflow through the count although the value and its square are finite,
+        // and the state is rescaled
+        let mut stats = Arithmetic::<f32>::new();
+        stats.extend_counted(1e10, 10)?;
+        stats.extend_counted(1e19, 10)?;
+        assert_eq!(stats.sample_count(), 20);
+        assert_relative_eq!(stats.sample_mean(), 5e18, max_relative = 1e-6);
+        assert_relative_eq!(stats.sample_std_dev(), 5.129_892e18, max_relative = 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_too_few_samples() {
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut stats = Arithmetic::new();
+        assert_eq!(stats.ci_mean(confidence), Err(CIError::TooFewSamples(0)));
+        stats.append(1.).unwrap();
+        assert_eq!(
+            stats.ci_mean(confidence).unwrap(),
+            Interval::new(1., 1.).unwrap()
+        );
+        stats.append(2.).unwrap();
+        assert!(stats.ci_mean(confidence).is_ok());
+    }
+
+    #[test]
+    fn test_skewness_kurtosis() -> CIResult<()> {
+        // reference values computed as scipy.stats.skew and scipy.stats.kurtosis (default arguments)
+        let cases: [(&[f64], f64, f64); 4] = [
+            (&[1., 2., 3., 10.], 1.018_233_764_908_628_4, -0.7696),
+            (
+                &[1., 2., 3., 4., 5., 6., 7., 8., 9., 10.],
+                0.,
+                -1.224_242_424_242_424_2,
+            ),
+            (
+                &[2., 8., 0., 4., 1., 9., 9., 0.],
+                0.265_055_412_269_857_3,
+                -1.666_001_075_283_850_8,
+            ),
+            (
+                &[1., 1., 1., 1., 1., 1., 1., 1., 1., 100.],
+                2.666_666_666_666_666_5,
+                5.111_111_111_111_111,
+            ),
+        ];
+        for (data, skewness, kurtosis) in cases {
+            let stats = Arithmetic::from_iter(&data.to_vec())?;
+            assert_abs_diff_eq!(stats.sample_skewness(), skewness, epsilon = 1e-12);
+            assert_abs_diff_eq!(stats.sample_kurtosis(), kurtosis, epsilon = 1e-12);
+        }
+
+        // heavily skewed: quantiles of the exponential distribution
+        let exponential = (0..200)
+            .map(|i| -(1. - (i as f64 + 0.5) / 200.).ln())
+            .collect::<Vec<_>>();
+        let (skewness, kurtosis) = (1.844_935_162_979_990_3, 4.343_275_758_664_279);
+        let stats = Arithmetic::from_iter(&exponential)?;
+        assert_relative_eq!(stats.sample_skewness(), skewness, max_relative = 1e-12);
+        assert_relative_eq!(stats.sample_kurtosis(), kurtosis, max_relative = 1e-12);
+
+        // no catastrophic cancellation with a large offset
+        let shifted = exponential.iter().map(|x| x + 1e6).collect::<Vec<_>>();
+        let stats = Arithmetic::from_iter(&shifted)?;
+        assert_relative_eq!(stats.sample_skewness(), skewness, max_relative = 1e-6);
+        assert_relative_eq!(stats.sample_kurtosis(), kurtosis, max_relative = 1e-6);
+
+        // merging states, in any split, as well as the slice-based paths
+        for split in [0, 1, 7, 100, 199, 200] {
+            let merged = Arithmetic::from_iter(&exponential[..split].to_vec())?
+                + Arithmetic::from_iter(&exponential[split..].to_vec())?;
+            assert_relative_eq!(merged.sample_skewness(), skewness, max_relative = 1e-12);
+            assert_relative_eq!(merged.sample_kurtosis(), kurtosis, max_relative = 1e-12);
+        }
+        let chunked = Arithmetic::from_chunks_deterministic(&exponential, 16);
+        assert_relative_eq!(chunked.sample_skewness(), skewness, max_relative = 1e-12);
+        assert_relative_eq!(chunked.sample_kurtosis(), kurtosis, max_relative = 1e-12);
+        let reciprocals = exponential.iter().map(|x| 1. / x).collect::<Vec<_>>();
+        let reference = Arithmetic::from_iter(&reciprocals)?;
+        let harmonic = Harmonic::from_slice(&exponential)?;
+        assert_relative_eq!(
+            harmonic.reciprocal_space().sample_skewness(),
+            reference.sample_skewness(),
+            max_relative = 1e-10
+        );
+        assert_relative_eq!(
+            harmonic.reciprocal_space().sample_kurtosis(),
+            reference.sample_kurtosis(),
+            max_relative = 1e-10
+        );
+
+        // unknown higher moments
+        let summary = Arithmetic::from_summary(10, 5., 1.)?;
+        assert!(summary.sample_skewness().is_nan());
+        assert!(summary.sample_kurtosis().is_nan());
+        assert!((summary + stats).sample_skewness().is_nan());
+        assert!(Arithmetic::from_iter(&[2., 2., 2.])?
+            .sample_skewness()
+            .is_nan());
+        Ok(())
+    }
+
+    #[test]
+    fn test_normality_advisory() -> CIResult<()> {
+        let exponential = (0..200)
+            .map(|i| -(1. - (i as f64 + 0.5) / 200.).ln())
+            .collect::<Vec<_>>();
+        // 30 g1^2 = 102.1: too skewed for 50 samples, but adequate for 200
+        let every_fourth = exponential.iter().step_by(4).copied().collect::<Vec<_>>();
+        assert!(matches!(
+            Arithmetic::from_iter(&every_fourth)?.normality_advisory(),
+            Advisory::Skewed { .. }
+        ));
+        assert_eq!(
+            Arithmetic::from_iter(&exponential)?.normality_advisory(),
+            Advisory::Adequate
+        );
+
+        // symmetric, but heavy-tailed
+        let mut heavy_tailed = vec![0.; 40];
+        heavy_tailed[0] = -10.;
+        heavy_tailed[39] = 10.;
+        let stats = Arithmetic::from_iter(&heavy_tailed)?;
+        assert_abs_diff_eq!(stats.sample_skewness(), 0., epsilon = 1e-12);
+        assert!(matches!(
+            stats.normality_advisory(),
+            Advisory::HeavyTailed {
+                recommended_count: 170,
+                ..
+            }
+        ));
+
+        assert_eq!(
+            Arithmetic::from_iter(&[1., 2., 3.])?.normality_advisory(),
+            Advisory::Undetermined
+        );
+        assert_eq!(
+            Arithmetic::from_iter(&[1., 1., 1., 1.])?.normality_advisory(),
+            Advisory::Undetermined
+        );
+        assert_eq!(
+            Arithmetic::from_summary(100, 1., 1.)?.normality_advisory(),
+            Advisory::Undetermined
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_extend_slice() -> CIResult<()> {
+        let data = (1..=103)
+            .map(|i| (i as f64 * 0.37).sin() + 1.5)
+            .collect::<Vec<_>>();
+        for len in [0, 1, 2, 3, 4, 5, 17, 103] {
+            let data = data[..len].to_vec();
+            let geometric = Geometric::from_slice(&data)?;
+            let reference = Geometric::from_iter(&data)?;
+            assert_eq!(geometric.sample_count(), len);
+            if len > 0 {
+                assert_relative_eq!(
+                    geometric.sample_mean(),
+                    reference.sample_mean(),
+                    max_relative = 1e-14
+                );
+            }
+            if len > 1 {
+                assert_relative_eq!(
+                    geometric.sample_sem(),
+                    reference.sample_sem(),
+                    max_relative = 1e-12
+                );
+            }
+
+            let harmonic = Harmonic::from_slice(&data)?;
+            let reference = Harmonic::from_iter(&data)?;
+            assert_eq!(harmonic.sample_count(), len);
+            if len > 0 {
+                assert_relative_eq!(
+                    harmonic.sample_mean(),
+                    reference.sample_mean(),
+                    max_relative = 1e-14
+                );
+            }
+            if len > 1 {
+                assert_relative_eq!(
+                    harmonic.sample_sem(),
+                    reference.sample_sem(),
+                    max_relative = 1e-12
+                );
+            }
+        }
+
+        // appending to an existing state
+        let mut geometric = Geometric::from_slice(&data[..10])?;
+        geometric.extend_slice(&data[10..])?;
+        assert_relative_eq!(
+            geometric.sample_mean(),
+            Geometric::from_iter(&data)?.sample_mean(),
+            max_relative = 1e-14
+        );
+
+        // the first offender is reported and the state is unchanged
+        let mut harmonic = Harmonic::from_slice(&[1., 2.])?;
+        assert!(matches!(
+            harmonic.extend_slice(&[3., 4., -1., 0., 5.]),
+            Err(CIError::NonPositiveValueAt { index: 2, value: x }) if x == -1.
+        ));
+        assert_eq!(harmonic, Harmonic::from_slice(&[1., 2.])?);
+        assert!(matches!(
+            Geometric::from_slice(&[1., 2., 3., 4., 5., 0.]),
+            Err(CIError::NonPositiveValueAt { index: 5, value: x }) if x == 0.
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_nan_data() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let stats = Arithmetic::from_iter(&[1., f64::NAN, 2.])?;
+        assert!(matches!(
+            stats.ci_mean(confidence),
+            Err(CIError::IntervalError(IntervalError::NaNBound))
+        ));
+        let stats = Harmonic::from_iter(&[1., f64::NAN, 2.])?;
+        assert!(matches!(
+            stats.ci_mean(confidence),
+            Err(CIError::IntervalError(IntervalError::NaNBound))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_degenerate() -> CIResult<()> {
+        for level in [0.5, 0.95, 0.999_999] {
+            let two_sided = Confidence::new_two_sided(level);
+            let upper = Confidence::new_upper(level);
+            let lower = Confidence::new_lower(level);
+
+            // single sample
+            let stats = Arithmetic::from_iter(&[4.2])?;
+            assert_eq!(stats.ci_mean(two_sided)?, Interval::new(4.2, 4.2)?);
+            assert_eq!(stats.ci_mean(upper)?, Interval::new_upper(4.2));
+            assert_eq!(stats.ci_mean(lower)?, Interval::new_lower(4.2));
+            let detail = stats.ci_mean_detailed(two_sided)?;
+            assert_eq!(detail.std_err, 0.);
+            assert_eq!(detail.degrees_of_freedom, 0.);
+            assert_eq!(detail.critical_value, f64::INFINITY);
+
+            // two identical samples
+            let stats = Arithmetic::from_iter(&[4.2, 4.2])?;
+            assert_eq!(stats.ci_mean(two_sided)?, Interval::new(4.2, 4.2)?);
+            let detail = stats.ci_mean_detailed(two_sided)?;
+            assert_eq!(detail.std_err, 0.);
+            assert_eq!(detail.degrees_of_freedom, 1.);
+            assert!(detail.critical_value.is_finite());
+
+            // many identical samples whose sums are inexact
+            let data = [0.1; 1_000];
+            let stats = Arithmetic::from_iter(&data)?;
+            assert_eq!(stats.sample_variance(), 0.);
+            let ci = stats.ci_mean(two_sided)?;
+            assert_eq!(ci.low_f(), ci.high_f());
+            assert_abs_diff_eq!(ci.low_f(), 0.1, epsilon = 1e-15);
+
+            let ci = Geometric::from_iter(&[3., 3., 3.])?.ci_mean(two_sided)?;
+            assert_eq!(ci.low_f(), ci.high_f());
+            assert_abs_diff_eq!(ci.low_f(), 3., epsilon = 1e-12);
+            let ci = Harmonic::from_iter(&[3., 3., 3.])?.ci_mean(two_sided)?;
+            assert_eq!(ci.low_f(), ci.high_f());
+            assert_abs_diff_eq!(ci.low_f(), 3., epsilon = 1e-12);
+            let ci = Harmonic::from_iter(&[3.])?.ci_mean(upper)?;
+            assert_eq!(ci, Interval::new_upper(3.));
+        }
+
+        // a large offset with a small variance is not degenerate
+        let data = [1e8, 1e8 + 1., 1e8 + 2., 1e8 + 3.];
+        let mut chunked = Arithmetic::from_iter(&data[..1])?;
+        chunked.append_chunk(&data[1..])?;
+        let merged = Arithmetic::from_iter(&data[..2])? + Arithmetic::from_iter(&data[2..])?;
+        for stats in [Arithmetic::from_iter(&data)?, chunked, merged] {
+            assert_relative_eq!(stats.sample_variance(), 5. / 3., max_relative = 1e-12);
+            let detail = stats.ci_mean_detailed(Confidence::new_two_sided(0.95))?;
+            assert!(detail.std_err > 0.);
+            assert!(detail.interval.low_f() < detail.interval.high_f());
+            // reference value: 1.5 + t(0.975, 3) * sqrt(5 / 12)
+            assert_abs_diff_eq!(detail.interval.high_f(), 1e8 + 3.554_260_3, epsilon = 1e-6);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_std_dev() -> CIResult<()> {
+        let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+        let stats = Arithmetic::from_iter(&data)?;
+        // reference values: s * sqrt(9 / q) with q the 0.975 and 0.025 quantiles of chi2(9)
+        let ci = stats.ci_std_dev(Confidence::new_two_sided(0.95))?;
+        assert_abs_diff_eq!(ci, Interval::new(2.0825245, 5.5273093)?, epsilon = 1e-6);
+        let ci = stats.ci_std_dev(Confidence::new_upper(0.975))?;
+        assert!(ci.is_upper());
+        assert_abs_diff_eq!(ci.low_f(), 2.0825245, epsilon = 1e-6);
+        let ci = stats.ci_std_dev(Confidence::new_lower(0.975))?;
+        assert!(ci.is_lower());
+        assert_abs_diff_eq!(ci.high_f(), 5.5273093, epsilon = 1e-6);
+
+        let mut stats = Arithmetic::new();
+        stats.append(1.)?;
+        assert_eq!(
+            stats.ci_std_dev(Confidence::new_two_sided(0.95)),
+            Err(CIError::TooFewSamples(1))
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_durations() -> CIResult<()> {
+        use std::time::Duration;
+
+        // round trip with the manual conversion to seconds
+        let durations =
+            [1_250_000, 980_500, 1_100_250, 1_310_001, 1_005_999].map(Duration::from_nanos);
+        let stats = Arithmetic::from_durations(durations)?;
+        let secs = durations.map(|d| d.as_secs_f64());
+        assert_eq!(stats, Arithmetic::from_iter(&secs)?);
+        let mut appended = Arithmetic::new();
+        for d in durations {
+            appended.append_duration(d)?;
+        }
+        assert_eq!(appended, stats);
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ] {
+            let ci = stats.ci_mean(confidence)?;
+            let ci_duration = stats.ci_mean_duration(confidence)?;
+            assert_eq!(ci_duration.low(), ci.low().map(Duration::from_secs_f64));
+            assert_eq!(ci_duration.high(), ci.high().map(Duration::from_secs_f64));
+        }
+
+        // sub-nanosecond bounds are rounded to the nearest nanosecond
+        let stats = Arithmetic::from_durations(
+            [1, 2]
+                .map(Duration::from_nanos)
+                .into_iter()
+                .cycle()
+                .take(2_000),
+        )?;
+        let ci = stats.ci_mean(Confidence::new_two_sided(0.95))?;
+        assert!(1.4e-9 < ci.low_f() && ci.high_f() < 1.6e-9);
+        let ci_duration = stats.ci_mean_duration(Confidence::new_two_sided(0.95))?;
+        assert_eq!(
+            ci_duration,
+            Interval::new(Duration::from_nanos(1), Duration::from_nanos(2))?
+        );
+        for (bound, secs) in [
+            (ci_duration.low(), ci.low_f()),
+            (ci_duration.high(), ci.high_f()),
+        ] {
+            assert!((bound.unwrap().as_secs_f64() - secs).abs() <= 0.5e-9);
+        }
+
+        // negative lower bounds saturate at zero
+        let stats = Arithmetic::from_durations([
+            Duration::from_nanos(1),
+            Duration::from_secs(1),
+            Duration::from_nanos(1),
+        ])?;
+        for confidence in [Confidence::new_two_sided(0.95), Confidence::new_upper(0.95)] {
+            assert!(stats.ci_mean(confidence)?.low_f() < 0.);
+            let ci_duration = stats.ci_mean_duration(confidence)?;
+            assert_eq!(ci_duration.low(), Some(Duration::ZERO));
+            assert!(ci_duration.contains(&Duration::from_secs_f64(stats.sample_mean())));
+        }
+
+        // errors
+        assert_eq!(
+            Arithmetic::new().ci_mean_duration(Confidence::new_two_sided(0.95)),
+            Err(CIError::TooFewSamples(0))
+        );
+        let stats = Arithmetic::from_durations([Duration::MAX, Duration::ZERO])?;
+        assert!(stats
+            .ci_mean_duration(Confidence::new_two_sided(0.95))
+            .is_err_and(|e| e.is_conversion_error()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_cv() -> CIResult<()> {
+        // reference values: Vangel's formula evaluated with arbitrary precision (mpmath)
+        let data = [98.2, 101.5, 99.8, 102.3, 97.6, 100.9, 103.1, 99.4];
+        let stats = Arithmetic::from_iter(&data)?;
+        assert_abs_diff_eq!(stats.sample_cv()?, 0.0193451009, epsilon = 1e-9);
+        let ci = stats.ci_cv(Confidence::new_two_sided(0.95))?;
+        assert_abs_diff_eq!(
+            ci,
+            Interval::new(0.0127891713, 0.0393889961)?,
+            epsilon = 1e-8
+        );
+        let ci = stats.ci_cv(Confidence::new_upper(0.9))?;
+        assert!(ci.is_upper());
+        assert_abs_diff_eq!(ci.low_f(), 0.0147633775, epsilon = 1e-8);
+        let ci = stats.ci_cv(Confidence::new_lower(0.9))?;
+        assert!(ci.is_lower());
+        assert_abs_diff_eq!(ci.high_f(), 0.0304136143, epsilon = 1e-8);
+
+        // large coefficient of variation: the upper bound only exists at low confidence
+        let data = [
+            10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4,
+        ];
+        let stats = Arithmetic::from_iter(&data)?;
+        let ci = stats.ci_cv(Confidence::new_two_sided(0.8))?;
+        assert_abs_diff_eq!(ci, Interval::new(0.748116122, 2.954423157)?, epsilon = 1e-6);
+        let ci = stats.ci_cv(Confidence::new_two_sided(0.9))?;
+        assert!(ci.is_upper());
+        assert_abs_diff_eq!(ci.low_f(), 0.6873119558, epsilon = 1e-8);
+        assert!(matches!(
+            stats.ci_cv(Confidence::new_lower(0.95)),
+            Err(CIError::CoefficientOfVariationTooLarge(_))
+        ));
+
+        // error cases
+        let confidence = Confidence::new_two_sided(0.95);
+        let zero_mean = Arithmetic::from_iter(&[-1., 1., -2., 2.])?;
+        assert!(matches!(
+            zero_mean.sample_cv(),
+            Err(CIError::NonPositiveMean(x)) if x == 0.
+        ));
+        assert!(matches!(
+            zero_mean.ci_cv(confidence),
+            Err(CIError::NonPositiveMean(_))
+        ));
+        let negative_mean = Arithmetic::from_iter(&[-5., 1., 2.])?;
+        assert!(matches!(
+            negative_mean.ci_cv(confidence),
+            Err(CIError::NonPositiveMean(x)) if x < 0.
+        ));
+        let mut single = Arithmetic::new();
+        single.append(1.)?;
+        assert_eq!(single.ci_cv(confidence), Err(CIError::TooFewSamples(1)));
+        assert_eq!(
+            Arithmetic::<f64>::new().sample_cv(),
+            Err(CIError::TooFewSamples(0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_columns() -> CIResult<()> {
+        let rows = [[1., 10.], [2., 30.], [3., 20.], [4., 50.], [5., 40.]];
+        let confidence = Confidence::new_two_sided(0.95);
+
+        let mut stats = Columns::new(&["a", "b"]);
+        for row in &rows {
+            stats.append_row(row)?;
+        }
+        assert_eq!(stats.column_count(), 2);
+        assert_eq!(stats.sample_count(), rows.len());
+
+        let a = Arithmetic::from_iter(&rows.map(|row| row[0]))?;
+        let b = Arithmetic::from_iter(&rows.map(|row| row[1]))?;
+        assert_eq!(stats.column("a"), Some(&a));
+        assert_eq!(stats.column("b"), Some(&b));
+        assert_eq!(stats.column("c"), None);
+        assert_eq!(stats.ci_mean("b", confidence)?, b.ci_mean(confidence)?);
+        assert!(matches!(
+            stats.ci_mean("c", confidence),
+            Err(CIError::UnknownColumn(name)) if name == "c"
+        ));
+
+        let summary = stats.summary(confidence)?;
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary["a"], (a.sample_mean(), a.ci_mean(confidence)?));
+        assert_eq!(summary["b"], (b.sample_mean(), b.ci_mean(confidence)?));
+
+        let mut projected = Columns::new(&["a", "b"]);
+        for row in &rows {
+            projected.append_with(row, &[|r: &[f64; 2]| r[0], |r: &[f64; 2]| r[1]])?;
+        }
+        assert_eq!(projected, stats);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_columns_row_length() {
+        let mut stats = Columns::new(&["a", "b"]);
+        stats.append_row(&[1., 2.]).unwrap();
+        assert_eq!(
+            stats.append_row(&[1.]),
+            Err(CIError::InvalidRowLength {
+                len: 1,
+                expected: 2
+            })
+        );
+        assert_eq!(
+            stats.append_row(&[1., 2., 3.]),
+            Err(CIError::InvalidRowLength {
+                len: 3,
+                expected: 2
+            })
+        );
+        assert_eq!(
+            stats.append_with(&1., &[|x: &f64| *x]),
+            Err(CIError::InvalidRowLength {
+                len: 1,
+                expected: 2
+            })
+        );
+        assert_eq!(stats.sample_count(), 1);
+        assert_eq!(stats.column("a").unwrap().sample_count(), 1);
+        assert_eq!(stats.column("b").unwrap().sample_count(), 1);
+    }
+
+    #[test]
+    fn test_columns_add() -> CIResult<()> {
+        let mut stats_ref = Columns::new(&["a", "b"]);
+        let mut stats_a = Columns::new(&["a", "b"]);
+        let mut stats_b = Columns::new(&["a", "b"]);
+        for i in 0..100 {
+            let row = [i as f64, (i * i) as f64];
+            stats_ref.append_row(&row)?;
+            if i % 3 == 0 {
+                stats_a.append_row(&row)?;
+            } else {
+                stats_b.append_row(&row)?;
+            }
+        }
+        let mut stats_in_place = stats_a.clone();
+        stats_in_place += stats_b.clone();
+        let stats_summed = stats_a + stats_b;
+        assert_eq!(stats_summed.sample_count(), stats_ref.sample_count());
+        assert_eq!(
+            stats_summed.summary(Confidence::new_two_sided(0.9))?,
+            stats_ref.summary(Confidence::new_two_sided(0.9))?
+        );
+        assert_eq!(stats_in_place, stats_summed);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_columns_add_different_columns() {
+        let _ = Columns::<f64>::new(&["a", "b"]) + Columns::new(&["a", "c"]);
+    }
+
+    #[test]
+    fn test_columns_try_add() -> CIResult<()> {
+        let mut stats_a = Columns::new(&["a", "b"]);
+        let mut stats_b = Columns::new(&["a", "b"]);
+        stats_a.append_row(&[1., 2.])?;
+        stats_b.append_row(&[3., 4.])?;
+        stats_b.append_row(&[5., 6.])?;
+        let merged = stats_a.clone().try_add(stats_b.clone())?;
+        assert_eq!(merged, stats_a.clone() + stats_b);
+
+        for names in [&["a", "c"][..], &["b", "a"], &["a"], &["a", "b", "c"]] {
+            let err = stats_a.clone().try_add(Columns::new(names));
+            assert_eq!(
+                err,
+                Err(CIError::DifferentColumns {
+                    names_a: vec!["a".to_string(), "b".to_string()],
+                    names_b: names.iter().map(|name| name.to_string()).collect(),
+                })
+            );
+            assert_eq!(err.unwrap_err().kind(), error::Kind::InvalidInput);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_misc() -> CIResult<()> {
+        let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+        let stats = mean::Arithmetic::from_iter(&data)?;
+        assert_eq!(stats.sample_count(), 10);
+        assert_eq!(stats.sample_mean(), 5.5);
+        assert_abs_diff_eq!(stats.sample_sem(), 0.9574, epsilon = 1e-4);
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = stats.ci_mean(confidence)?;
+        assert_abs_diff_eq!(ci, Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sem_reproduces_ci() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1667);
+        for n in [2, 3, 10, 31, 1_000] {
+            let data = (0..n).map(|_| rng.gen::<f64>() * 10.).collect::<Vec<_>>();
+            let stats = Arithmetic::from_iter(&data)?;
+            let mean = stats.sample_mean();
+            let sem = stats.sample_sem();
+            assert_eq!(sem, stats.sample_std_dev() / (n as f64).sqrt());
+            for confidence in [
+                Confidence::new_two_sided(0.95),
+                Confidence::new_upper(0.9),
+                Confidence::new_lower(0.99),
+            ] {
+                let (t, _) = stats::critical_value(confidence, (n - 1) as f64)?;
+                let expected =
+                    Interval::from_finite_bounds_for(confidence, mean - t * sem, mean + t * sem)?;
+                assert_eq!(stats.ci_mean(confidence)?, expected, "n = {n}");
+                assert_eq!(stats.ci_mean_detailed(confidence)?.std_err, sem);
+                assert_eq!(stats.ci_mean_with_critical(confidence, t)?, expected);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_rescaled() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let small = (1..=10).map(f64::from).collect::<Vec<_>>();
+        let reference = Arithmetic::from_iter(&small)?;
+        let large = small.iter().map(|x| x * 1e200).collect::<Vec<_>>();
+        let stats = Arithmetic::from_iter(&large)?;
+        assert_eq!(stats.sample_count(), 10);
+        assert_relative_eq!(stats.sample_mean(), 5.5e200, max_relative = 1e-14);
+        assert_relative_eq!(
+            stats.sample_std_dev(),
+            reference.sample_std_dev() * 1e200,
+            max_relative = 1e-14
+        );
+        assert_relative_eq!(
+            stats.sample_sem(),
+            reference.sample_sem() * 1e200,
+            max_relative = 1e-14
+        );
+        // the variance itself is not representable
+        assert_eq!(stats.sample_variance(), f64::INFINITY);
+        assert_relative_eq!(
+            stats.sample_cv()?,
+            reference.sample_cv()?,
+            max_relative = 1e-14
+        );
+        for (ci, expected) in [
+            (stats.ci_mean(confidence)?, reference.ci_mean(confidence)?),
+            (
+                stats.ci_std_dev(confidence)?,
+                reference.ci_std_dev(confidence)?,
+            ),
+            (
+                stats.ci_mean_skew_corrected(confidence)?,
+                reference.ci_mean_skew_corrected(confidence)?,
+            ),
+        ] {
+            assert_relative_eq!(ci.low_f(), expected.low_f() * 1e200, max_relative = 1e-12);
+            assert_relative_eq!(ci.high_f(), expected.high_f() * 1e200, max_relative = 1e-12);
+        }
+
+        // the same state in one chunk or with counts
+        let mut chunked = Arithmetic::new();
+        chunked.append_chunk(&large)?;
+        assert_eq!(chunked, stats);
+        let counted = Arithmetic::from_counts(large.iter().map(|&x| (x, 1)))?;
+        assert_eq!(counted, stats);
+
+        // merging states with different scales, in both orders
+        let huge = [1e300, 2e300, 3e300];
+        let huge_stats = Arithmetic::from_iter(&huge)?;
+        let all = small
+            .iter()
+            .chain(&large)
+            .chain(&huge)
+            .copied()
+            .collect::<Vec<_>>();
+        let expected_mean = all.iter().map(|x| x / 23.).sum::<f64>();
+        for merged in [
+            reference + stats + huge_stats,
+            huge_stats + stats + reference,
+            reference + (huge_stats + stats),
+        ] {
+            assert_eq!(merged.sample_count(), 23);
+            assert_relative_eq!(merged.sample_mean(), expected_mean, max_relative = 1e-14);
+            assert_abs_diff_eq!(merged, Arithmetic::from_iter(&all)?, epsilon = 1e-12);
+        }
+        // states whose merged sums overflow at their common scale
+        let x = 2_f64.powi(767);
+        let pair = Arithmetic::from_iter(&[x, x])?;
+        let merged = pair + pair;
+        assert_eq!(merged, Arithmetic::from_iter(&[x, x, x, x])?);
+        assert_eq!(merged.sample_mean(), x);
 
-        let one_sided_ci = Arithmetic::ci(Confidence::UpperOneSided(0.975), &data)?;
-        assert_abs_diff_eq!(one_sided_ci.low_f(), ci.low_f());
-        assert_eq!(one_sided_ci.high_f(), f64::INFINITY);
+        // the rescaled state survives serialization
+        #[cfg(feature = "persist")]
+        assert_eq!(Arithmetic::<f64>::from_bytes(&stats.to_bytes())?, stats);
+        Ok(())
+    }
 
-        let one_sided_ci = Arithmetic::ci(Confidence::LowerOneSided(0.975), &data)?;
-        assert_abs_diff_eq!(one_sided_ci.high_f(), ci.high_f());
-        assert_eq!(one_sided_ci.low_f(), f64::NEG_INFINITY);
+    #[test]
+    fn test_unscaled_bit_identical() -> CIResult<()> {
+        // results of the state before the rescaled mode was introduced: mean, variance, standard
+        // deviation, skewness, and the bounds of the intervals on the mean, on the standard
+        // deviation, and with the skewness correction (the bounds depend on the implementation of
+        // the quantile functions, so they are only checked with statrs); the variance and the
+        // skewness are computed from the shifted moments, which may differ in the last bit when the
+        // states are merged
+        let bounds_checked = cfg!(all(feature = "statrs", not(feature = "internal-quantiles")));
+        let data = [
+            10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3,
+        ];
+        let expected: [u64; 10] = [
+            0x401b333333333333,
+            0x404e9d1745d1745d,
+            0x401f4c94a5b38c17,
+            0x3ff74ec529854a36,
+            0x3ffd40ff1d62d4d0,
+            0x40278b134f86d899,
+            0x40162c12c2872fb7,
+            0x402a9231392701da,
+            0x40001501bec03d86,
+            0x4027e833db8a8d60,
+        ];
+        let mut chunked = Arithmetic::from_iter(&data[..5])?;
+        chunked.append_chunk(&data[5..])?;
+        let merged = Arithmetic::from_iter(&data[..7])? + Arithmetic::from_iter(&data[7..])?;
+        let confidence = Confidence::new(0.95);
+        for (stats, variance, skewness) in [
+            (
+                Arithmetic::from_iter(&data)?,
+                0x404e9d1745d1745d,
+                0x3ff74ec529854a36,
+            ),
+            (chunked, 0x404e9d1745d1745d, 0x3ff74ec529854a36),
+            (merged, 0x404e9d1745d1745c, 0x3ff74ec529854a38),
+        ] {
+            let ci = stats.ci_mean(confidence)?;
+            let ci_std_dev = stats.ci_std_dev(confidence)?;
+            let ci_corrected = stats.ci_mean_skew_corrected(confidence)?;
+            let results = [
+                stats.sample_mean(),
+                stats.sample_variance(),
+                stats.sample_std_dev(),
+                stats.sample_skewness(),
+                ci.low_f(),
+                ci.high_f(),
+                ci_std_dev.low_f(),
+                ci_std_dev.high_f(),
+                ci_corrected.low_f(),
+                ci_corrected.high_f(),
+            ];
+            let mut expected = expected;
+            expected[1] = variance;
+            expected[3] = skewness;
+            let checked = if bounds_checked { 10 } else { 4 };
+            assert_eq!(results.map(f64::to_bits)[..checked], expected[..checked]);
+        }
 
-        let mut stats = Arithmetic::default();
-        stats.extend(&data)?;
+        let stats = Arithmetic::<f32>::from_iter(&[10.6, 6.6, 26.7, 0.4, 5.7])?;
         let ci = stats.ci_mean(confidence)?;
+        let checked = if bounds_checked { 4 } else { 2 };
+        assert_eq!(
+            [
+                stats.sample_mean(),
+                stats.sample_variance(),
+                ci.low_f(),
+                ci.high_f()
+            ]
+            .map(f32::to_bits)[..checked],
+            [0x41200000, 0x42c8bae3, 0xc01c1d2c, 0x41b383a6][..checked]
+        );
+        Ok(())
+    }
 
-        assert_abs_diff_eq!(ci.low_f(), 48.094823990767836, epsilon = 1e-8);
-        assert_abs_diff_eq!(ci.high_f(), 59.24517600923217, epsilon = 1e-8);
-        assert_abs_diff_eq!(ci.low_f() + ci.high_f(), 2. * 53.67, epsilon = 1e-8);
+    #[test]
+    fn test_append_overflow() -> CIResult<()> {
+        // the sum of squares overflows: the state is rescaled
+        let mut stats = Arithmetic::<f32>::from_iter(&[1., 2.])?;
+        stats.append(1e20)?;
+        let wide = Arithmetic::<f64>::from_iter(&[1., 2., 1e20])?;
+        assert_relative_eq!(
+            stats.sample_mean(),
+            wide.sample_mean() as f32,
+            max_relative = 1e-6
+        );
+        assert_relative_eq!(
+            stats.sample_std_dev(),
+            wide.sample_std_dev() as f32,
+            max_relative = 1e-6
+        );
 
-        let one_sided_ci = stats.ci_mean(Confidence::UpperOneSided(0.975))?;
-        assert_abs_diff_eq!(one_sided_ci.low_f(), ci.low_f());
-        assert_eq!(one_sided_ci.high_f(), f64::INFINITY);
+        // the sum of squares overflows after a few values
+        let mut stats = Arithmetic::<f32>::new();
+        for _ in 0..4 {
+            stats.append(1e19)?;
+        }
+        assert_eq!(stats.sample_count(), 4);
+        assert_eq!(stats.sample_mean(), 1e19);
+        assert_eq!(stats.sample_std_dev(), 0.);
 
-        let one_sided_ci = stats.ci_mean(Confidence::LowerOneSided(0.975))?;
-        assert_abs_diff_eq!(one_sided_ci.high_f(), ci.high_f());
-        assert_eq!(one_sided_ci.low_f(), f64::NEG_INFINITY);
+        // the same values are fine with f64 or with mixed precision
+        let mut stats = Arithmetic::<f64>::from_iter(&[1., 2.])?;
+        stats.append(1e20)?;
+        let mut stats = ArithmeticF32::from_iter(&[f32::MAX / 2., f32::MAX])?;
+        stats.append(f32::MAX)?;
+        assert_eq!(stats.sample_count(), 3);
 
+        // non-finite data is still accepted (and reported when computing the interval)
+        let mut stats = Arithmetic::<f32>::from_iter(&[1., 2.])?;
+        stats.append(f32::INFINITY)?;
+        stats.append(1.)?;
+        stats.append(f32::NAN)?;
         Ok(())
     }
 
     #[test]
-    fn test_geometric_ci() -> CIResult<()> {
-        let data = [
-            82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
-            15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
-            71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
-            98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
-            49., 23., 26., 55., 26., 3., 23., 47., 27., 58., 27., 97., 32., 29., 56., 28., 23.,
-            37., 72., 62., 77., 63., 100., 40., 84., 77., 39., 71., 61., 17., 77.,
-        ];
+    fn test_degrees_of_freedom() -> CIResult<()> {
+        fn check<S: StatisticsOps<f64>>(stats: &S, degrees_of_freedom: f64, normal: bool) {
+            assert_eq!(stats.degrees_of_freedom(), degrees_of_freedom);
+            assert_eq!(stats.uses_normal_approximation(), normal);
+        }
+        let data = [1., 2., 4., 8., 16.];
+        check(&Arithmetic::from_iter(&data)?, 4., false);
+        check(&Harmonic::from_iter(&data)?, 4., false);
+        check(&Geometric::from_iter(&data)?, 4., false);
+        check(&Arithmetic::new(), 0., false);
+        check(&Arithmetic::from_iter(&[1.])?, 0., false);
 
+        let stats = ArithmeticF32::from_iter(&[1_f32, 2., 3.])?;
+        assert_eq!(stats.degrees_of_freedom(), 2.);
+        assert!(!stats.uses_normal_approximation());
+
+        // the interval switches to the normal distribution from 100'001 samples
+        let mut stats = Arithmetic::from_counts([(1., 50_000), (2., 50_000)])?;
+        check(&stats, 99_999., false);
+        stats.append(3.)?;
+        check(&stats, 100_000., true);
         let confidence = Confidence::new_two_sided(0.95);
-        let ci = Geometric::ci(confidence, &data)?;
-        // geometric mean: 43.7268032829256
-        //
-        // reference values computed in python:
-        // in log space: (3.630483364286656, 3.9254391587458475)
-        // [37.731050052224354, 50.67532768627392]
-        assert_abs_diff_eq!(ci.low_f(), 37.731050052224354, epsilon = 1e-8);
-        assert_abs_diff_eq!(ci.high_f(), 50.67532768627392, epsilon = 1e-8);
+        assert_eq!(
+            stats.ci_mean_detailed(confidence)?.distribution,
+            CriticalDistribution::Normal
+        );
+        Ok(())
+    }
 
-        let one_sided_ci = Geometric::ci(Confidence::UpperOneSided(0.975), &data)?;
-        assert_abs_diff_eq!(one_sided_ci.low_f(), ci.low_f());
-        assert_eq!(one_sided_ci.high_f(), f64::INFINITY);
+    #[test]
+    fn test_arithmetic_f32() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1613);
+        let data: Vec<f32> = (0..2_000_000)
+            .map(|_| 10_000. + rng.gen_range(0f32..1.))
+            .collect();
+        let data_f64: Vec<f64> = data.iter().map(|&x| x as f64).collect();
+        let reference = Arithmetic::<f64>::from_iter(&data_f64)?;
 
-        let one_sided_ci = Geometric::ci(Confidence::LowerOneSided(0.975), &data)?;
-        assert_abs_diff_eq!(one_sided_ci.high_f(), ci.high_f());
-        assert_eq!(one_sided_ci.low_f(), f64::NEG_INFINITY);
+        let mixed = ArithmeticF32::from_iter(&data)?;
+        assert_eq!(mixed.sample_count(), data.len());
+        assert_eq!(mixed.sample_mean(), reference.sample_mean() as f32);
+        assert_eq!(mixed.sample_std_dev(), reference.sample_std_dev() as f32);
+        assert_relative_eq!(mixed.sample_std_dev(), 0.288_675, max_relative = 1e-2);
 
-        let mut stats = Geometric::default();
-        stats.extend(&data)?;
-        let ci = stats.ci_mean(confidence)?;
-        assert_abs_diff_eq!(stats.sample_mean(), 43.7268032829256, epsilon = 1e-8);
-        assert_abs_diff_eq!(ci.low_f(), 37.731050052224354, epsilon = 1e-8);
-        assert_abs_diff_eq!(ci.high_f(), 50.67532768627392, epsilon = 1e-8);
+        let confidence = Confidence::new_two_sided(0.95);
+        let ci = mixed.ci_mean(confidence)?;
+        let ci_reference = reference.ci_mean(confidence)?;
+        assert_eq!(ci.low_f(), ci_reference.low_f() as f32);
+        assert_eq!(ci.high_f(), ci_reference.high_f() as f32);
+        let ci = mixed.ci_mean(Confidence::new_lower(0.9))?;
+        assert_eq!(ci.low(), None);
 
-        let one_sided_ci = stats.ci_mean(Confidence::UpperOneSided(0.975))?;
-        assert_abs_diff_eq!(one_sided_ci.low_f(), ci.low_f());
-        assert_eq!(one_sided_ci.high_f(), f64::INFINITY);
+        // with f32 accumulators, the variance accumulates rounding errors
+        let narrow = Arithmetic::<f32>::from_iter(&data)?;
+        assert_relative_eq!(
+            narrow.sample_mean(),
+            reference.sample_mean() as f32,
+            max_relative = 1e-6
+        );
+        assert!((narrow.sample_std_dev() - mixed.sample_std_dev()).abs() > 1e-4);
 
-        let one_sided_ci = stats.ci_mean(Confidence::LowerOneSided(0.975))?;
-        assert_abs_diff_eq!(one_sided_ci.high_f(), ci.high_f());
-        assert_eq!(one_sided_ci.low_f(), f64::NEG_INFINITY);
+        // merging
+        let (left, right) = data.split_at(1_000_000);
+        let merged =
+            ArithmeticF32::from_iter(&left.to_vec())? + ArithmeticF32::from_iter(&right.to_vec())?;
+        assert_relative_eq!(merged.sample_mean(), mixed.sample_mean());
+        assert_relative_eq!(
+            merged.sample_std_dev(),
+            mixed.sample_std_dev(),
+            max_relative = 1e-6
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_and_state() -> CIResult<()> {
+        let data = [2., 4., 8., 16., 32.];
+        let confidence = Confidence::new_two_sided(0.9);
+        let (ci, stats) = Geometric::ci_and_state(confidence, data)?;
+        assert_eq!(ci, Geometric::ci_from_data(confidence, &data)?);
+        assert_eq!(stats, Geometric::from_iter(&data)?);
+
+        let (ci, stats) = ArithmeticF32::ci_and_state(confidence, data.map(|x| x as f32))?;
+        assert_eq!(ci.low_f(), stats.ci_mean(confidence)?.low_f());
+        assert_eq!(stats.sample_count(), 5);
 
+        // errors are those of appending or of the interval
+        assert!(matches!(
+            Harmonic::ci_and_state(confidence, [1., -1., 2.]),
+            Err(CIError::NonPositiveValue(_))
+        ));
+        assert_eq!(
+            Arithmetic::<f64>::ci_and_state(confidence, []),
+            Err(CIError::TooFewSamples(0))
+        );
         Ok(())
     }
 
     #[test]
-    fn test_harmonic_ci() -> CIResult<()> {
-        let data = [
-            82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
-            15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
-            71., 18., 51., 65., 87., 12., 44., 89., 67., 28., 86., 62., 22., 90., 18., 50., 25.,
-            98., 24., 61., 62., 86., 100., 96., 27., 36., 82., 90., 55., 26., 38., 97., 73., 16.,
-            49., 23., 26., 55., 26., 3., 23., 47., 27., 58., 27., 97., 32., 29., 56., 28., 23.,
-            37., 72., 62., 77., 63., 100., 40., 84., 77., 39., 71., 61., 17., 77.,
-        ];
+    fn test_partial_ingestion() -> CIResult<()> {
+        let data = [1., 2., 0., 4., -5., 6.];
+        let partial = Harmonic::try_from_iter_partial(data).unwrap_err();
+        assert!(matches!(partial.error, CIError::NonPositiveValue(x) if x == 0.));
+        assert_eq!(partial.index, 2);
+        assert_eq!(partial.state, Harmonic::from_iter(&[1., 2.])?);
+        assert_eq!(
+            partial.to_string(),
+            "cannot append the element at index 2: Geometric/harmonic mean require strictly positive values: found 0"
+        );
 
-        let confidence = Confidence::new_two_sided(0.95);
-        let ci = Harmonic::ci(confidence, &data)?;
-        // harmonic mean: 30.031313156339586
-        //
-        // reference values computed in python:
-        // in reciprocal space: (0.02424956057996111, 0.042347593849757906)
-        // [41.237860649168255, 23.614092539657168]  (reversed by conversion from reciprocal space)
-        assert_abs_diff_eq!(ci.low_f(), 23.614092539657168, epsilon = 1e-8);
-        assert_abs_diff_eq!(ci.high_f(), 41.237860649168255, epsilon = 1e-8);
+        // resume after each faulty element
+        let mut iter = data.into_iter();
+        let mut indices = vec![];
+        let mut stats = Harmonic::new();
+        loop {
+            match stats.try_extend_partial(iter.by_ref()) {
+                Ok(complete) => {
+                    stats = complete;
+                    break;
+                }
+                Err(partial) => {
+                    indices.push(partial.index);
+                    stats = partial.state;
+                }
+            }
+        }
+        // indices are relative to the start of each call
+        assert_eq!(indices, [2, 1]);
+        assert_eq!(stats, Harmonic::from_iter(&[1., 2., 4., 6.])?);
 
-        let one_sided_ci = Harmonic::ci(Confidence::UpperOneSided(0.975), &data)?;
-        assert_abs_diff_eq!(one_sided_ci.low_f(), ci.low_f());
-        assert_eq!(one_sided_ci.high_f(), f64::INFINITY);
+        // f32 sums that overflow are rescaled
+        let stats = Arithmetic::<f32>::try_from_iter_partial([1e19, 1e19, 1e19, 1e19, 1.]).unwrap();
+        assert_eq!(stats.sample_count(), 5);
 
-        let one_sided_ci = Harmonic::ci(Confidence::LowerOneSided(0.975), &data)?;
-        assert_abs_diff_eq!(one_sided_ci.high_f(), ci.high_f());
-        assert_eq!(one_sided_ci.low_f(), f64::NEG_INFINITY);
+        assert_eq!(
+            Arithmetic::try_from_iter_partial(data).unwrap(),
+            Arithmetic::from_iter(&data)?
+        );
+        Ok(())
+    }
 
+    #[test]
+    fn test_combined() -> CIResult<()> {
         let confidence = Confidence::new_two_sided(0.95);
         let data = [
-            1.81600583, 0.07498389, 1.29092744, 0.62023863, 0.09345327, 1.94670997, 2.27687339,
-            0.9251231, 1.78173864, 0.4391542, 1.36948099, 1.5191194, 0.42286756, 1.48463176,
-            0.17621009, 2.31810064, 0.15633061, 2.55137878, 1.11043948, 1.35923319, 1.58385561,
-            0.63431437, 0.49993148, 0.49168534, 0.11533354,
+            82., 94., 68., 6., 39., 80., 10., 97., 34., 66., 62., 7., 39., 68., 93., 64., 10., 74.,
+            15., 34., 4., 48., 88., 94., 17., 99., 81., 37., 68., 66., 40., 23., 67., 72., 63.,
         ];
-        let ci = Harmonic::ci(confidence, &data)?;
-        // harmonic mean: 0.38041820166550844
-        //
-        // reference values computed in python:
-        // in reciprocal space: (1.1735238063066096, 4.083848080632111)
-        // [0.8521343961033607, 0.2448670911003175]
-        assert_abs_diff_eq!(ci.low_f(), 0.2448670911003175, epsilon = 1e-6);
-        assert_abs_diff_eq!(ci.high_f(), 0.8521343961033607, epsilon = 1e-6);
+        let stats = Combined::from_iter(&data)?;
+        assert_eq!(stats.sample_count(), data.len());
+        assert_eq!(stats.non_positive_count(), 0);
+        assert_eq!(stats.arithmetic(), &Arithmetic::from_iter(&data)?);
+        assert_eq!(stats.geometric(), &Geometric::from_iter(&data)?);
+        assert_eq!(stats.harmonic(), &Harmonic::from_iter(&data)?);
+        for confidence in [
+            confidence,
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ] {
+            assert_eq!(
+                stats.ci_arithmetic(confidence)?,
+                Arithmetic::ci(confidence, &data)?
+            );
+            assert_eq!(
+                stats.ci_geometric(confidence)?,
+                Geometric::ci(confidence, &data)?
+            );
+            assert_eq!(
+                stats.ci_harmonic(confidence)?,
+                Harmonic::ci(confidence, &data)?
+            );
+        }
 
-        let mut stats = Harmonic::default();
-        stats.extend(&data)?;
-        let ci = stats.ci_mean(confidence)?;
-        assert_abs_diff_eq!(stats.sample_mean(), 0.38041820166550844, epsilon = 1e-8);
-        assert_abs_diff_eq!(ci.low_f(), 0.2448670911003175, epsilon = 1e-6);
-        assert_abs_diff_eq!(ci.high_f(), 0.8521343961033607, epsilon = 1e-6);
+        // merging
+        let (head, tail) = data.split_at(13);
+        let merged = Combined::from_iter(head)? + Combined::from_iter(tail)?;
+        assert_eq!(merged.sample_count(), data.len());
+        assert_abs_diff_eq!(
+            merged.ci_arithmetic(confidence)?,
+            stats.ci_arithmetic(confidence)?,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            merged.ci_geometric(confidence)?,
+            stats.ci_geometric(confidence)?,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            merged.ci_harmonic(confidence)?,
+            stats.ci_harmonic(confidence)?,
+            epsilon = 1e-10
+        );
+
+        // non-positive values are counted arithmetically only
+        let mixed = [3., 1., 0., 4., -1., 5., 9.];
+        let positive = [3., 1., 4., 5., 9.];
+        let mut stats = Combined::new();
+        stats.extend_iter(mixed)?;
+        assert_eq!(stats.sample_count(), 7);
+        assert_eq!(stats.non_positive_count(), 2);
+        assert_eq!(stats.geometric().sample_count(), 5);
+        assert_eq!(stats.harmonic().sample_count(), 5);
+        assert_eq!(
+            stats.ci_arithmetic(confidence)?,
+            Arithmetic::ci(confidence, &mixed)?
+        );
+        assert_eq!(stats.geometric(), &Geometric::from_iter(&positive)?);
+        assert_eq!(stats.harmonic(), &Harmonic::from_iter(&positive)?);
+        assert!(matches!(
+            stats.ci_geometric(confidence),
+            Err(CIError::NonPositiveValueAt { index: 2, value: x }) if x == 0.
+        ));
+        assert!(matches!(
+            stats.ci_harmonic(confidence),
+            Err(CIError::NonPositiveValueAt { index: 2, value: x }) if x == 0.
+        ));
 
+        // the index of the first non-positive value accounts for the merged samples
+        let mut merged = Combined::from_iter(&[1., 2., 3.])?;
+        merged += Combined::from_iter(&[4., -5., 0.])?;
+        assert_eq!(merged.non_positive_count(), 2);
+        assert!(matches!(
+            merged.ci_geometric(confidence),
+            Err(CIError::NonPositiveValueAt { index: 4, value: x }) if x == -5.
+        ));
+        let merged = stats + Combined::from_iter(&[-2.])?;
+        assert!(matches!(
+            merged.ci_harmonic(confidence),
+            Err(CIError::NonPositiveValueAt { index: 2, value: _ })
+        ));
+
+        // overflowing values (or reciprocals) are rescaled
+        let mut stats = Combined::<f32>::from_iter(&[1., 2., 3.])?;
+        stats.append(1e30)?;
+        stats.append(1e-30)?;
+        assert_eq!(stats.sample_count(), 5);
+        assert_relative_eq!(stats.arithmetic().sample_mean(), 2e29, max_relative = 1e-6);
+        assert_relative_eq!(stats.harmonic().sample_mean(), 5e-30, max_relative = 1e-6);
         Ok(())
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_arithmetic_add() {
-        const VALUE: f32 = 0.1;
-        let size = 1_000_000;
+    fn test_combined_serde() -> CIResult<()> {
+        for data in [&[1., 2.5, 4.][..], &[1., 0., 3.5, -2.][..]] {
+            let stats = Combined::from_iter(data)?;
+            let serialized = toml::to_string(&stats).unwrap();
+            let deserialized: Combined<f64> = toml::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, stats);
+        }
+        Ok(())
+    }
 
-        let mut stats_ref = Arithmetic::default();
-        let mut stats_summed = Arithmetic::default();
-        let mut stats_summed_in_place = Arithmetic::default();
-        for _ in 0..size {
-            stats_ref.append(VALUE).unwrap();
+    /// normalized weights of the samples of an EWMA with smoothing factor `alpha`
+    fn ewma_weights(alpha: f64, count: usize) -> Vec<f64> {
+        (0..count)
+            .map(|i| {
+                let age = (count - 1 - i) as i32;
+                let weight = (1. - alpha).powi(age);
+                if i == 0 {
+                    weight
+                } else {
+                    alpha * weight
+                }
+            })
+            .collect()
+    }
 
-            let mut new_stat = Arithmetic::default();
-            new_stat.append(VALUE).unwrap();
-            stats_summed = stats_summed + new_stat;
-            stats_summed_in_place += new_stat;
-        }
+    #[test]
+    fn test_ewma() -> CIResult<()> {
+        let data = [3.1, 2.7, 5.2, 4.4, 3.9, 6.3, 5.8, 4.1];
+        let alpha = 0.3;
+        let mut stats = Ewma::new(alpha)?;
+        assert!(stats.mean().is_nan());
+        assert_eq!(stats.effective_sample_size(), 0.);
+        assert_eq!(
+            stats.ci_mean(Confidence::new(0.95)),
+            Err(CIError::TooFewSamples(0))
+        );
+        stats.extend(data)?;
+        assert_eq!(stats.sample_count(), data.len());
+        assert_eq!(stats.alpha(), alpha);
 
-        assert_eq!(stats_ref.sample_count(), size);
-        assert_eq!(stats_summed.sample_count(), size);
-        assert_eq!(stats_summed_in_place.sample_count(), size);
+        // same as the explicitly weighted statistics
+        let weights = ewma_weights(alpha, data.len());
+        assert_abs_diff_eq!(weights.iter().sum::<f64>(), 1., epsilon = 1e-12);
+        let mean = weights.iter().zip(&data).map(|(w, x)| w * x).sum::<f64>();
+        let sum_sq_weights = weights.iter().map(|w| w * w).sum::<f64>();
+        let variance = weights
+            .iter()
+            .zip(&data)
+            .map(|(w, x)| w * (x - mean) * (x - mean))
+            .sum::<f64>()
+            / (1. - sum_sq_weights);
+        assert_abs_diff_eq!(stats.mean(), mean, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            stats.effective_sample_size(),
+            1. / sum_sq_weights,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(stats.sample_variance(), variance, epsilon = 1e-12);
+        let detail = stats.ci_mean_detailed(Confidence::new(0.95))?;
+        assert_abs_diff_eq!(
+            detail.std_err,
+            (variance * sum_sq_weights).sqrt(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            detail.degrees_of_freedom,
+            1. / sum_sq_weights - 1.,
+            epsilon = 1e-12
+        );
 
-        assert_eq!(stats_ref.sample_mean(), stats_summed.sample_mean());
-        assert_eq!(stats_ref.sample_variance(), stats_summed.sample_variance());
-        assert_eq!(stats_ref.sample_std_dev(), stats_summed.sample_std_dev());
-        assert_eq!(stats_ref.sample_sem(), stats_summed.sample_sem());
+        // the effective sample size converges to (2 - alpha) / alpha
+        stats.extend(core::iter::repeat_n(4., 200))?;
+        assert_abs_diff_eq!(stats.effective_sample_size(), 1.7 / 0.3, epsilon = 1e-9);
 
-        assert_eq!(stats_ref.sample_mean(), stats_summed_in_place.sample_mean());
+        // degenerate intervals
+        let mut stats = Ewma::new(0.5)?;
+        stats.append(2.)?;
         assert_eq!(
-            stats_ref.sample_variance(),
-            stats_summed_in_place.sample_variance()
+            stats.ci_mean(Confidence::new(0.95))?,
+            Interval::new(2., 2.)?
         );
+        let mut stats = Ewma::new(1.)?;
+        stats.extend([1., 2., 3.])?;
+        assert_eq!(stats.mean(), 3.);
+        assert_eq!(stats.effective_sample_size(), 1.);
         assert_eq!(
-            stats_ref.sample_std_dev(),
-            stats_summed_in_place.sample_std_dev()
+            stats.ci_mean(Confidence::new(0.95))?,
+            Interval::new(3., 3.)?
         );
-        assert_eq!(stats_ref.sample_sem(), stats_summed_in_place.sample_sem());
+
+        // invalid parameters and data
+        for alpha in [0., -0.1, 1.5, f64::NAN] {
+            assert!(matches!(
+                Ewma::new(alpha),
+                Err(CIError::InvalidSmoothingFactor(_))
+            ));
+        }
+        let mut stats = Ewma::new(0.5)?;
+        stats.append(1.)?;
+        assert_eq!(stats.append(f64::NAN), Err(CIError::InvalidInputData));
+        assert_eq!(stats.append(f64::INFINITY), Err(CIError::InvalidInputData));
+        assert_eq!(stats.sample_count(), 1);
+        assert_eq!(stats.mean(), 1.);
+        Ok(())
     }
 
     #[test]
-    fn test_geometric_add() {
-        const VALUE: f32 = 0.1;
-        let size = 1_000_000;
-
-        let mut stats_ref = Geometric::default();
-        let mut stats_summed = Geometric::default();
-        let mut stats_summed_in_place = Geometric::default();
-        for _ in 0..size {
-            stats_ref.append(VALUE).unwrap();
+    fn test_ewma_stationary() -> CIResult<()> {
+        use rand::{distributions::Distribution, SeedableRng};
+        // n_eff = 99: the interval is close to the t-interval of the last 99 samples
+        let alpha = 0.02;
+        let confidence = Confidence::new(0.95);
+        let normal = statrs::distribution::Normal::new(100., 5.).unwrap();
+        let mut hits = 0;
+        for seed in 0..20 {
+            let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1663 + seed);
+            let data = (0..2_000)
+                .map(|_| normal.sample(&mut rng))
+                .collect::<Vec<_>>();
+            let mut stats = Ewma::new(alpha)?;
+            stats.extend(data.iter().copied())?;
+            let n_eff = stats.effective_sample_size();
+            assert_abs_diff_eq!(n_eff, 99., epsilon = 1e-6);
 
-            let mut new_stat = Geometric::default();
-            new_stat.append(VALUE).unwrap();
-            stats_summed = stats_summed + new_stat;
-            stats_summed_in_place += new_stat;
+            let ci = stats.ci_mean(confidence)?;
+            let plain = Arithmetic::from_iter(&data[data.len() - 99..])?.ci_mean(confidence)?;
+            let ratio = ci.width().unwrap() / plain.width().unwrap();
+            assert!((0.75..1.33).contains(&ratio), "seed {seed}: {ratio}");
+            assert!(ci.contains(&stats.mean()));
+            if ci.contains(&100.) {
+                hits += 1;
+            }
         }
-
-        assert_eq!(stats_ref.sample_count(), size);
-        assert_eq!(stats_summed.sample_count(), size);
-        assert_eq!(stats_summed_in_place.sample_count(), size);
-
-        assert_eq!(stats_ref.sample_mean(), stats_summed.sample_mean());
-        assert_eq!(stats_ref.sample_sem(), stats_summed.sample_sem());
-
-        assert_eq!(stats_ref.sample_mean(), stats_summed_in_place.sample_mean());
-        assert_eq!(stats_ref.sample_sem(), stats_summed_in_place.sample_sem());
+        assert!(hits >= 16, "{hits} hits out of 20");
+        Ok(())
     }
 
     #[test]
-    fn test_harmonic_add() {
-        const VALUE: f32 = 0.1;
-        let size = 1_000_000;
-
-        let mut stats_ref = Harmonic::default();
-        let mut stats_summed = Harmonic::default();
-        let mut stats_summed_in_place = Harmonic::default();
-        for _ in 0..size {
-            stats_ref.append(VALUE).unwrap();
-
-            let mut new_stat = Harmonic::default();
-            new_stat.append(VALUE).unwrap();
-            stats_summed = stats_summed + new_stat;
-            stats_summed_in_place += new_stat;
+    fn test_ewma_level_shift() -> CIResult<()> {
+        use rand::{distributions::Distribution, SeedableRng};
+        // the level shifts from 0 to 5 (5 standard deviations): with alpha = 0.05, the EWMA has
+        // covered 95% of the shift after 3 / alpha = 60 steps, and 99.3% after 100 steps
+        let alpha = 0.05;
+        let confidence = Confidence::new(0.95);
+        let normal = statrs::distribution::Normal::new(0., 1.).unwrap();
+        let mut hits = 0;
+        for seed in 0..20 {
+            let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(16630 + seed);
+            let mut stats = Ewma::new(alpha)?;
+            let mut plain = Arithmetic::new();
+            for _ in 0..1_000 {
+                let x = normal.sample(&mut rng);
+                stats.append(x)?;
+                plain.append(x)?;
+            }
+            for step in 1..=100 {
+                let x = 5. + normal.sample(&mut rng);
+                stats.append(x)?;
+                plain.append(x)?;
+                if step == 20 {
+                    // after 1 / alpha steps, 36% of the shift remains
+                    assert!(!stats.ci_mean(confidence)?.contains(&5.), "seed {seed}");
+                }
+            }
+            let ci = stats.ci_mean(confidence)?;
+            assert!(!ci.contains(&0.), "seed {seed}");
+            if ci.contains(&5.) {
+                hits += 1;
+            }
+            // the mean of all the samples has moved by about 100 / 1'100 of the shift
+            let plain = plain.ci_mean(confidence)?;
+            assert!(!plain.contains(&5.) && !plain.contains(&0.), "seed {seed}");
+            assert!(plain.high_f() < 1.);
         }
+        assert!(hits >= 16, "{hits} hits out of 20");
+        Ok(())
+    }
 
-        assert_eq!(stats_ref.sample_count(), size);
-        assert_eq!(stats_summed.sample_count(), size);
-        assert_eq!(stats_summed_in_place.sample_count(), size);
+    #[test]
+    fn test_ewma_try_add() -> CIResult<()> {
+        let replica_a = [3.1, 2.7, 5.2, 4.4, 3.9];
+        let replica_b = [2.2, 3.5, 4.8, 6.1, 3.0];
+        let alpha = 0.25;
+        let mut a = Ewma::new(alpha)?;
+        let mut b = Ewma::new(alpha)?;
+        a.extend(replica_a)?;
+        b.extend(replica_b)?;
+        let merged = a.try_add(b)?;
+        assert_eq!(merged.sample_count(), 10);
 
-        assert_eq!(stats_ref.sample_mean(), stats_summed.sample_mean());
-        assert_eq!(stats_ref.sample_sem(), stats_summed.sample_sem());
+        // same as the explicitly weighted statistics of both replicas, with half weights
+        let weights = ewma_weights(alpha, replica_a.len());
+        let pooled = weights
+            .iter()
+            .zip(replica_a.iter().zip(&replica_b))
+            .flat_map(|(w, (x, y))| [(w / 2., *x), (w / 2., *y)])
+            .collect::<Vec<_>>();
+        let mean = pooled.iter().map(|(w, x)| w * x).sum::<f64>();
+        let sum_sq_weights = pooled.iter().map(|(w, _)| w * w).sum::<f64>();
+        let variance = pooled
+            .iter()
+            .map(|(w, x)| w * (x - mean) * (x - mean))
+            .sum::<f64>()
+            / (1. - sum_sq_weights);
+        assert_abs_diff_eq!(merged.mean(), mean, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            merged.effective_sample_size(),
+            1. / sum_sq_weights,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            merged.effective_sample_size(),
+            2. * a.effective_sample_size(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(merged.sample_variance(), variance, epsilon = 1e-12);
 
-        assert_eq!(stats_ref.sample_mean(), stats_summed_in_place.sample_mean());
-        assert_eq!(stats_ref.sample_sem(), stats_summed_in_place.sample_sem());
+        // empty states
+        let empty = Ewma::<f64>::new(alpha)?;
+        assert_eq!(empty.try_add(empty)?, empty);
+
+        // states that cannot be combined
+        assert_eq!(
+            a.try_add(Ewma::new(0.5)?),
+            Err(CIError::DifferentSmoothingFactors {
+                alpha_a: 0.25,
+                alpha_b: 0.5
+            })
+        );
+        b.append(1.)?;
+        assert_eq!(
+            a.try_add(b),
+            Err(CIError::DifferentSampleSizes {
+                size_a: 5,
+                size_b: 6
+            })
+        );
+        Ok(())
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_misc() -> CIResult<()> {
-        let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
-        let stats = mean::Arithmetic::from_iter(&data)?;
-        assert_eq!(stats.sample_count(), 10);
-        assert_eq!(stats.sample_mean(), 5.5);
-        assert_abs_diff_eq!(stats.sample_sem(), 1.0092, epsilon = 1e-4);
-        let confidence = Confidence::new_two_sided(0.95);
-        let ci = stats.ci_mean(confidence)?;
-        assert_abs_diff_eq!(ci, Interval::new(3.3341, 7.6659)?, epsilon = 1e-4);
+    fn test_ewma_serde() -> CIResult<()> {
+        let mut stats = Ewma::new(0.1)?;
+        stats.extend([1., 2.5, 4., 3.])?;
+        let serialized = toml::to_string(&stats).unwrap();
+        let deserialized: Ewma<f64> = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, stats);
+
+        // the smoothing factor is validated
+        for alpha in ["0.0", "1.5", "-0.1", "nan"] {
+            let invalid = serialized.replace("alpha = 0.1", &format!("alpha = {alpha}"));
+            assert_ne!(invalid, serialized);
+            let error = toml::from_str::<Ewma<f64>>(&invalid).unwrap_err();
+            assert!(
+                error.to_string().contains("Invalid smoothing factor"),
+                "{error}"
+            );
+        }
         Ok(())
     }
 }
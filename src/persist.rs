@@ -0,0 +1,432 @@
+//!
+//! Stable binary encoding of accumulated statistics, for checkpointing states between the stages
+//! of a pipeline (e.g., in a key-value store).
+//!
+//! Unlike the serde representations, the encoding does not depend on the names of the fields, and
+//! every blob starts with a header identifying its format:
+//!
+//! | offset | size | content                                                      |
+//! |--------|------|--------------------------------------------------------------|
+//! | 0      | 2    | magic bytes [`MAGIC`] (`"SC"`)                               |
+//! | 2      | 1    | format version ([`VERSION`])                                 |
+//! | 3      | 1    | kind of state (see below)                                    |
+//! | 4      | ...  | payload                                                      |
+//!
+//! All numbers in the payload are little-endian; integers are stored as `u64` and floating point
+//! values as `f64` (whatever the float type of the state).
+//!
+//! Version 1 defines the following payloads:
+//!
+//! * [`mean::Arithmetic`] (kind 1): the count, the Kahan sums of the values and of their squares
+//!   (each as the running sum followed by its compensation), and a flag byte whose bit 0 tells
+//!   whether the higher moments are known, followed, if so, by their shift and their four sums.
+//!   Bit 1 of the flag byte is set for states in rescaled mode, and the (positive) exponent of
+//!   their scale then follows as an integer; the other bits are zero.
+//! * [`proportion::Stats`] (kind 2): the population and the number of successes.
+//! * [`comparison::Paired`] (kind 3): the payload of the [`mean::Arithmetic`] state of the differences.
+//! * [`comparison::Unpaired`] (kind 4): the payloads of the [`mean::Arithmetic`] states of both samples.
+//!
+//! Future versions of the crate will keep decoding version 1 blobs.
+//!
+//! This module requires the `persist` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! let stats = mean::Arithmetic::from_iter(&[1., 2., 3.])?;
+//! let bytes = stats.to_bytes();
+//! assert_eq!(&bytes[..4], &[b'S', b'C', persist::VERSION, 1]);
+//! assert_eq!(mean::Arithmetic::<f64>::from_bytes(&bytes)?, stats);
+//!
+//! // merge a checkpoint into an ongoing state
+//! let mut stats = mean::Arithmetic::from_iter(&[4., 5.])?;
+//! stats.merge_from_bytes(&bytes)?;
+//! assert_eq!(stats.sample_count(), 5);
+//! assert_eq!(stats.sample_mean(), 3.);
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Magic bytes at the start of every encoded state.
+///
+pub const MAGIC: [u8; 2] = *b"SC";
+
+///
+/// Current version of the encoding.
+///
+pub const VERSION: u8 = 1;
+
+///
+/// Kind of the encoded state, stored after the version.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Arithmetic = 1,
+    Proportion = 2,
+    Paired = 3,
+    Unpaired = 4,
+}
+
+///
+/// Encoder of a state into bytes.
+///
+pub(crate) struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub(crate) fn new(kind: Kind) -> Self {
+        let mut bytes = Vec::with_capacity(64);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.push(kind as u8);
+        Self { bytes }
+    }
+
+    pub(crate) fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub(crate) fn usize(&mut self, value: usize) {
+        self.bytes.extend_from_slice(&(value as u64).to_le_bytes());
+    }
+
+    ///
+    /// Write a float as an `f64`; a value that cannot be converted is written as NaN, which
+    /// fails decoding.
+    ///
+    pub(crate) fn float<F: Float>(&mut self, value: F) {
+        let value = value.to_f64().unwrap_or(f64::NAN);
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+///
+/// Decoder of a state from bytes.
+///
+/// All reads fail with [`CIError::DecodeError`] instead of panicking on malformed input.
+///
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    ///
+    /// Check the header of the encoded state and return a reader positioned on its payload.
+    ///
+    pub(crate) fn new(bytes: &'a [u8], kind: Kind) -> CIResult<Self> {
+        let mut reader = Self { bytes, position: 0 };
+        let header = reader.take(4)?;
+        if header[..2] != MAGIC {
+            return Err(CIError::DecodeError(format!(
+                "bad magic bytes: {:?}",
+                &header[..2]
+            )));
+        }
+        if header[2] != VERSION {
+            return Err(CIError::DecodeError(format!(
+                "unsupported version: {}",
+                header[2]
+            )));
+        }
+        if header[3] != kind as u8 {
+            return Err(CIError::DecodeError(format!(
+                "wrong kind of state: {} (expected: {})",
+                header[3], kind as u8
+            )));
+        }
+        Ok(reader)
+    }
+
+    fn take(&mut self, count: usize) -> CIResult<&'a [u8]> {
+        let end = self.position + count;
+        if end > self.bytes.len() {
+            return Err(CIError::DecodeError(format!(
+                "truncated input: {} bytes (expected at least: {})",
+                self.bytes.len(),
+                end
+            )));
+        }
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> CIResult<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    pub(crate) fn u8(&mut self) -> CIResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn usize(&mut self, var_name: &str) -> CIResult<usize> {
+        let value = u64::from_le_bytes(self.array()?);
+        usize::try_from(value)
+            .map_err(|_| CIError::DecodeError(format!("{} out of range: {}", var_name, value)))
+    }
+
+    ///
+    /// Read a float, which must be finite.
+    ///
+    pub(crate) fn float<F: Float>(&mut self, var_name: &str) -> CIResult<F> {
+        let value = f64::from_le_bytes(self.array()?);
+        if !value.is_finite() {
+            return Err(CIError::DecodeError(format!(
+                "{} is not finite: {}",
+                var_name, value
+            )));
+        }
+        value.try_narrow(var_name)
+    }
+
+    ///
+    /// Check that the whole input has been read.
+    ///
+    pub(crate) fn finish(self) -> CIResult<()> {
+        if self.position != self.bytes.len() {
+            return Err(CIError::DecodeError(format!(
+                "trailing bytes: {} (expected: {})",
+                self.bytes.len(),
+                self.position
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> CIResult<()> {
+        let data = [1.5, 2.25, -3., 4.125, 10., 0.1];
+        let stats = mean::Arithmetic::from_iter(&data)?;
+        let bytes = stats.to_bytes();
+        // header, count, two Kahan sums, flag, shift and four sums of the moments
+        assert_eq!(bytes.len(), 4 + 8 + 4 * 8 + 1 + 5 * 8);
+        let restored = mean::Arithmetic::<f64>::from_bytes(&bytes)?;
+        assert_eq!(restored, stats);
+        assert_eq!(restored.sample_variance(), stats.sample_variance());
+        assert_eq!(restored.sample_skewness(), stats.sample_skewness());
+
+        // unknown higher moments
+        let stats = mean::Arithmetic::from_summary(10, 3., 1.5)?;
+        let bytes = stats.to_bytes();
+        assert_eq!(bytes.len(), 4 + 8 + 4 * 8 + 1);
+        assert_eq!(mean::Arithmetic::<f64>::from_bytes(&bytes)?, stats);
+
+        // f32 states are encoded as f64 without loss
+        let stats = mean::Arithmetic::from_iter(&[1.5_f32, 2.1, 3.7])?;
+        let restored = mean::Arithmetic::<f32>::from_bytes(&stats.to_bytes())?;
+        assert_eq!(restored, stats);
+        let wide = mean::Arithmetic::<f64>::from_bytes(&stats.to_bytes())?;
+        assert_eq!(wide.sample_mean() as f32, stats.sample_mean());
+
+        let stats = proportion::Stats::new(1000, 3);
+        assert_eq!(proportion::Stats::from_bytes(&stats.to_bytes())?, stats);
+
+        let stats = comparison::Paired::from_pairs([(1., 2.), (3., 5.5), (4., 4.)])?;
+        assert_eq!(comparison::Paired::from_bytes(&stats.to_bytes())?, stats);
+
+        let stats = comparison::Unpaired::from_iter(&[1., 2., 3.], &[4., 6., 9., 1.])?;
+        assert_eq!(comparison::Unpaired::from_bytes(&stats.to_bytes())?, stats);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stable_layout() -> CIResult<()> {
+        // version 1 blob of `Arithmetic::from_iter(&[1., 3.])`, which must remain readable
+        let mut bytes = vec![b'S', b'C', 1, 1];
+        bytes.extend_from_slice(&2_u64.to_le_bytes());
+        for value in [4., 0., 10., 0.] {
+            bytes.extend_from_slice(&f64::to_le_bytes(value));
+        }
+        bytes.push(1);
+        for value in [1., 2., 4., 8., 16.] {
+            bytes.extend_from_slice(&f64::to_le_bytes(value));
+        }
+        let stats = mean::Arithmetic::from_iter(&[1., 3.])?;
+        assert_eq!(stats.to_bytes(), bytes);
+        assert_eq!(mean::Arithmetic::<f64>::from_bytes(&bytes)?, stats);
+
+        let mut bytes = vec![b'S', b'C', 1, 2];
+        bytes.extend_from_slice(&20_u64.to_le_bytes());
+        bytes.extend_from_slice(&7_u64.to_le_bytes());
+        assert_eq!(proportion::Stats::new(20, 7).to_bytes(), bytes);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_from_bytes() -> CIResult<()> {
+        let mut stats = mean::Arithmetic::from_iter(&[1., 2., 3.])?;
+        let other = mean::Arithmetic::from_iter(&[4., 5.])?;
+        stats.merge_from_bytes(&other.to_bytes())?;
+        let expected = mean::Arithmetic::from_iter(&[1., 2., 3., 4., 5.])?;
+        assert_eq!(stats.sample_count(), expected.sample_count());
+        assert_eq!(stats.sample_mean(), expected.sample_mean());
+        assert_eq!(stats.sample_variance(), expected.sample_variance());
+
+        // unchanged on error
+        let before = stats;
+        assert!(stats.merge_from_bytes(b"SC").is_err());
+        assert_eq!(stats, before);
+
+        let mut stats = proportion::Stats::new(10, 4);
+        stats.merge_from_bytes(&proportion::Stats::new(5, 1).to_bytes())?;
+        assert_eq!(stats, proportion::Stats::new(15, 5));
+
+        // the higher moments are merged, so only the sums are compared
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut stats = comparison::Paired::from_pairs([(1., 2.), (3., 5.)])?;
+        stats.merge_from_bytes(&comparison::Paired::from_pairs([(4., 4.)])?.to_bytes())?;
+        let expected = comparison::Paired::from_pairs([(1., 2.), (3., 5.), (4., 4.)])?;
+        assert_eq!(stats.ci_mean(confidence)?, expected.ci_mean(confidence)?);
+
+        let mut stats = comparison::Unpaired::from_iter(&[1., 2.], &[4.])?;
+        stats.merge_from_bytes(&comparison::Unpaired::from_iter(&[3.], &[6., 9.])?.to_bytes())?;
+        let expected = comparison::Unpaired::from_iter(&[1., 2., 3.], &[4., 6., 9.])?;
+        assert_eq!(stats.ci_mean(confidence)?, expected.ci_mean(confidence)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_from_bytes_overflow() -> CIResult<()> {
+        // a blob with a count of u64::MAX decodes, but cannot be merged into a non-empty state
+        let with_max_count = |mut bytes: Vec<u8>, offset: usize| {
+            bytes[offset..offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+            bytes
+        };
+
+        let bytes = with_max_count(mean::Arithmetic::from_iter(&[1.])?.to_bytes(), 4);
+        assert_eq!(
+            mean::Arithmetic::<f64>::from_bytes(&bytes)?.sample_count() as u64,
+            u64::MAX
+        );
+        let mut stats = mean::Arithmetic::from_iter(&[1., 2.])?;
+        let before = stats;
+        assert_eq!(stats.merge_from_bytes(&bytes), Err(CIError::CountOverflow));
+        assert_eq!(stats, before);
+
+        let bytes = with_max_count(proportion::Stats::new(1, 0).to_bytes(), 4);
+        let mut stats = proportion::Stats::new(10, 4);
+        assert_eq!(stats.merge_from_bytes(&bytes), Err(CIError::CountOverflow));
+        assert_eq!(stats, proportion::Stats::new(10, 4));
+
+        let bytes = with_max_count(comparison::Paired::from_pairs([(1., 2.)])?.to_bytes(), 4);
+        let mut stats = comparison::Paired::from_pairs([(1., 2.), (3., 5.)])?;
+        let before = stats.clone();
+        assert_eq!(stats.merge_from_bytes(&bytes), Err(CIError::CountOverflow));
+        assert_eq!(stats, before);
+
+        // overflow of either sample; the payloads of both samples have the same length
+        let bytes = comparison::Unpaired::from_iter(&[1.], &[2.])?.to_bytes();
+        let offset_b = 4 + (bytes.len() - 4) / 2;
+        for offset in [4, offset_b] {
+            let bytes = with_max_count(bytes.clone(), offset);
+            let mut stats = comparison::Unpaired::from_iter(&[1., 2.], &[4.])?;
+            let before = stats.clone();
+            assert_eq!(stats.merge_from_bytes(&bytes), Err(CIError::CountOverflow));
+            assert_eq!(stats, before);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_corrupted_input() -> CIResult<()> {
+        fn is_decode_error<T>(result: CIResult<T>) -> bool {
+            matches!(result, Err(CIError::DecodeError(_)))
+        }
+
+        let bytes = mean::Arithmetic::from_iter(&[1., 2., 3.])?.to_bytes();
+        // every truncation fails
+        for length in 0..bytes.len() {
+            assert!(is_decode_error(mean::Arithmetic::<f64>::from_bytes(
+                &bytes[..length]
+            )));
+        }
+        // trailing bytes
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert!(is_decode_error(mean::Arithmetic::<f64>::from_bytes(
+            &longer
+        )));
+        // bad magic
+        let mut corrupted = bytes.clone();
+        corrupted[0] = b'X';
+        assert!(is_decode_error(mean::Arithmetic::<f64>::from_bytes(
+            &corrupted
+        )));
+        // wrong version
+        let mut corrupted = bytes.clone();
+        corrupted[2] = VERSION + 1;
+        assert!(is_decode_error(mean::Arithmetic::<f64>::from_bytes(
+            &corrupted
+        )));
+        let mut corrupted = bytes.clone();
+        corrupted[2] = 0;
+        assert!(is_decode_error(mean::Arithmetic::<f64>::from_bytes(
+            &corrupted
+        )));
+        // wrong kind of state
+        assert!(is_decode_error(comparison::Paired::<f64>::from_bytes(
+            &bytes
+        )));
+        assert!(is_decode_error(proportion::Stats::from_bytes(&bytes)));
+        // invalid flag of the higher moments
+        let mut corrupted = bytes.clone();
+        corrupted[4 + 8 + 4 * 8] = 2;
+        assert!(is_decode_error(mean::Arithmetic::<f64>::from_bytes(
+            &corrupted
+        )));
+        // non-finite value
+        let mut corrupted = bytes.clone();
+        corrupted[12..20].copy_from_slice(&f64::NAN.to_le_bytes());
+        assert!(is_decode_error(mean::Arithmetic::<f64>::from_bytes(
+            &corrupted
+        )));
+        // out of range for f32
+        let mut corrupted = bytes.clone();
+        corrupted[12..20].copy_from_slice(&1e300_f64.to_le_bytes());
+        assert!(
+            mean::Arithmetic::<f32>::from_bytes(&corrupted).is_err_and(|e| e.is_conversion_error())
+        );
+
+        let bytes = proportion::Stats::new(10, 5).to_bytes();
+        for length in 0..bytes.len() {
+            assert!(is_decode_error(proportion::Stats::from_bytes(
+                &bytes[..length]
+            )));
+        }
+        let mut corrupted = bytes.clone();
+        corrupted[12..20].copy_from_slice(&11_u64.to_le_bytes());
+        assert_eq!(
+            proportion::Stats::from_bytes(&corrupted),
+            Err(CIError::InvalidSuccesses {
+                successes: 11,
+                population: 10
+            })
+        );
+
+        let bytes = comparison::Unpaired::from_iter(&[1., 2.], &[3., 4.])?.to_bytes();
+        for length in 0..bytes.len() {
+            assert!(is_decode_error(comparison::Unpaired::<f64>::from_bytes(
+                &bytes[..length]
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+//!
+//! Commonly used items, re-exported under names that are unlikely to clash with those of the
+//! importing crate.
+//!
+//! Unlike `use stats_ci::*`, importing the prelude does not bring the modules of the crate
+//! (`mean`, `proportion`, `quantile`, ...) into scope, so it can be glob-imported alongside
+//! modules or items of the same name. The statistics objects are re-exported with their kind
+//! as a prefix, e.g., [`ArithmeticStats`] for [`mean::Arithmetic`](crate::mean::Arithmetic) and
+//! [`ProportionStats`] for [`proportion::Stats`](crate::proportion::Stats).
+//!
+//! The contents of the prelude do not depend on the enabled features.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::prelude::*;
+//!
+//! // a module of the importing crate with the same name as one of the crate
+//! mod mean {
+//!     pub fn mean(data: &[f64]) -> f64 {
+//!         data.iter().sum::<f64>() / data.len() as f64
+//!     }
+//! }
+//!
+//! let data = [10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3];
+//! let confidence = Confidence::new(0.95);
+//! let stats = ArithmeticStats::from_iter(&data)?;
+//! let ci = stats.ci_mean(confidence)?;
+//! assert!(ci.contains(&mean::mean(&data)));
+//!
+//! let mut proportion = ProportionStats::default();
+//! proportion.extend_if(&data, |&x| x > 5.);
+//! let ci = proportion.ci(confidence)?;
+//! assert!(ci.contains(&0.5));
+//!
+//! let quantile = QuantileStats::new(data.len());
+//! let ci = quantile.ci(confidence, 0.5)?;
+//! assert!(ci.contains(&6));
+//! # Ok::<(),CIError>(())
+//! ```
+//!
+//! The modules of the crate are not part of the prelude:
+//!
+//! ```compile_fail
+//! use stats_ci::prelude::*;
+//!
+//! let stats = mean::Arithmetic::<f64>::new();
+//! ```
+//!
+
+pub use crate::comparison::Paired as PairedStats;
+pub use crate::comparison::Unpaired as UnpairedStats;
+pub use crate::confidence::Confidence;
+pub use crate::error::{CIError, CIResult};
+pub use crate::interval::{Interval, Significance};
+pub use crate::mean::Arithmetic as ArithmeticStats;
+pub use crate::mean::Geometric as GeometricStats;
+pub use crate::mean::Harmonic as HarmonicStats;
+pub use crate::mean::{MeanCI, StatisticsOps};
+pub use crate::proportion::Stats as ProportionStats;
+pub use crate::quantile::Stats as QuantileStats;
+pub use crate::rate::Stats as RateStats;
+pub use crate::utils::{Accumulator, Epoched};
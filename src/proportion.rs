@@ -32,6 +32,12 @@
 //! # Ok::<(),error::CIError>(())
 //! ```
 //!
+//! For samples classified into more than two categories, [`MultinomialStats`] computes
+//! simultaneous confidence intervals on the shares of all the categories.
+//!
+//! The function [`non_inferiority`] tests whether the proportion of a candidate (e.g., an error
+//! rate) is not worse than that of a baseline by more than a margin, e.g., for a release gate.
+//!
 //! # References
 //!
 //! * [Wikipedia - Confidence interval](https://en.wikipedia.org/wiki/Confidence_interval)
@@ -85,17 +91,66 @@ impl FromIterator<bool> for Stats {
         I: IntoIterator<Item = bool>,
     {
         let mut stats = Stats::default();
-        for value in iter {
-            if value {
-                stats.add_success();
-            } else {
-                stats.add_failure();
-            }
+        stats.extend_iter(iter);
+        stats
+    }
+}
+
+impl FromIterator<(usize, usize)> for Stats {
+    ///
+    /// Creates a new statistics object from an iterator of batches given as pairs `(population, successes)`.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of batches in `iter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - an iterator of batches
+    ///
+    /// # Panics
+    ///
+    /// * if the number of successes of a batch is larger than its population size, or if the
+    ///   total population size does not fit in a `usize`
+    ///   (use [`Stats::extend_batches`] to handle invalid batches as errors)
+    ///
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        let mut stats = Stats::default();
+        if let Err(error) = stats.extend_batches(iter) {
+            panic!("Invalid batch: {error}");
         }
         stats
     }
 }
 
+impl TryFrom<(usize, usize)> for Stats {
+    type Error = CIError;
+
+    ///
+    /// Creates a new statistics object from a pair `(population, successes)`, as in [`Stats::add_batch`].
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidSuccesses` - if the number of successes is larger than the population size
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = proportion::Stats::try_from((1_000, 12))?;
+    /// assert_eq!(stats, proportion::Stats::new(1_000, 12));
+    /// assert!(proportion::Stats::try_from((10, 11)).is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    fn try_from((population, successes): (usize, usize)) -> Result<Self, Self::Error> {
+        let mut stats = Stats::default();
+        stats.add_batch(population, successes)?;
+        Ok(stats)
+    }
+}
+
 impl Stats {
     ///
     /// Creates a new statistics object with initial values for the population size and the number of successes.
@@ -135,6 +190,48 @@ impl Stats {
         self.successes
     }
 
+    ///
+    /// Returns the number of failures (number of `false` values found in the sample).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn failures(&self) -> usize {
+        self.population - self.successes
+    }
+
+    ///
+    /// Returns the proportion of successes in the sample, or NaN if the sample is empty.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn success_rate(&self) -> f64 {
+        self.successes as f64 / self.population as f64
+    }
+
+    ///
+    /// Returns the odds of success, i.e. the number of successes divided by the number of failures,
+    /// or `None` if there are no failures.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = proportion::Stats::new(10, 8);
+    /// assert_eq!(stats.failures(), 2);
+    /// assert_eq!(stats.success_rate(), 0.8);
+    /// assert_eq!(stats.odds(), Some(4.));
+    /// assert_eq!(proportion::Stats::new(10, 10).odds(), None);
+    /// ```
+    ///
+    pub fn odds(&self) -> Option<f64> {
+        match self.failures() {
+            0 => None,
+            failures => Some(self.successes as f64 / failures as f64),
+        }
+    }
+
     ///
     /// Add a success to the statistics and updates the population accordingly.
     ///
@@ -154,6 +251,256 @@ impl Stats {
         self.population += 1;
     }
 
+    ///
+    /// Add counts of successes and failures, e.g., as tallied by another system.
+    ///
+    /// Unlike [`Stats::add_batch`], the counts cannot be inconsistent.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::CountOverflow`] - if the total population size does not fit in a `usize`, in
+    ///   which case the statistics are left unchanged
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = proportion::Stats::default();
+    /// stats.add_counts(12, 988)?;
+    /// stats.add_counts(3, 797)?;
+    /// assert_eq!(stats, proportion::Stats::new(1_800, 15));
+    /// assert_eq!(stats.failures(), 1_785);
+    /// assert!(stats.add_counts(usize::MAX, 0).is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn add_counts(&mut self, successes: usize, failures: usize) -> CIResult<()> {
+        let population = successes
+            .checked_add(failures)
+            .ok_or(CIError::CountOverflow)?;
+        self.add_checked(population, successes)
+    }
+
+    ///
+    /// Add a population and its successes (at most the population), checking for overflow.
+    /// The statistics are left unchanged on error.
+    ///
+    fn add_checked(&mut self, population: usize, successes: usize) -> CIResult<()> {
+        let population = self
+            .population
+            .checked_add(population)
+            .ok_or(CIError::CountOverflow)?;
+        // cannot overflow, since the successes are at most the population
+        self.population = population;
+        self.successes += successes;
+        Ok(())
+    }
+
+    ///
+    /// Add a batch of samples given by its population size and number of successes
+    /// (e.g., the number of requests and the number of errors observed during one minute).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `population` - the number of samples in the batch
+    /// * `successes` - the number of successes in the batch
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidSuccesses` - if the number of successes is larger than the population size, in which case the statistics are left unchanged
+    /// * `CountOverflow` - if the total population size does not fit in a `usize`, in which case the statistics are left unchanged
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = proportion::Stats::default();
+    /// stats.add_batch(1_000, 12)?;
+    /// stats.add_batch(800, 3)?;
+    /// assert_eq!(stats, proportion::Stats::new(1_800, 15));
+    /// assert!(stats.add_batch(10, 11).is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn add_batch(&mut self, population: usize, successes: usize) -> CIResult<()> {
+        if successes > population {
+            return Err(CIError::InvalidSuccesses {
+                successes,
+                population,
+            });
+        }
+        self.add_checked(population, successes)
+    }
+
+    ///
+    /// Add several batches of samples, each given as a pair `(population, successes)`.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of batches in `iter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - an iterator of batches
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidSuccesses` - if the number of successes of a batch is larger than its population size,
+    ///   in which case the statistics are left unchanged (none of the batches are added)
+    /// * `CountOverflow` - if the total population size does not fit in a `usize`, in which case the
+    ///   statistics are left unchanged
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let per_minute = [(1_000, 12), (800, 3), (1_200, 9)];
+    /// let mut stats = proportion::Stats::default();
+    /// stats.extend_batches(per_minute)?;
+    /// assert_eq!(stats, proportion::Stats::new(3_000, 24));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_batches<I>(&mut self, iter: I) -> CIResult<()>
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        let mut stats = *self;
+        for (population, successes) in iter {
+            stats.add_batch(population, successes)?;
+        }
+        *self = stats;
+        Ok(())
+    }
+
+    ///
+    /// Append a chunk of Boolean samples from a slice, where `true` denotes a success.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of elements in `chunk`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = proportion::Stats::default();
+    /// stats.append_chunk(&[true, false, false]);
+    /// stats.append_chunk(&[true]);
+    /// assert_eq!(stats, proportion::Stats::new(4, 2));
+    /// ```
+    pub fn append_chunk(&mut self, chunk: &[bool]) {
+        self.population += chunk.len();
+        self.successes += chunk.iter().filter(|&&success| success).count();
+    }
+
+    ///
+    /// Create a new statistics object by pulling chunks of Boolean samples from a source until it
+    /// is exhausted, i.e., until `next_chunk` returns `None`.
+    ///
+    /// See [`mean::Arithmetic::from_chunked_source`] for the motivation.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the total number of samples.
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let outcomes = (0..1_000).map(|i| i % 10 == 0).collect::<Vec<_>>();
+    /// let mut blocks = outcomes.chunks(64);
+    /// let stats = proportion::Stats::from_chunked_source(|| blocks.next());
+    /// assert_eq!(stats, proportion::Stats::new(1_000, 100));
+    /// ```
+    pub fn from_chunked_source<'a>(mut next_chunk: impl FnMut() -> Option<&'a [bool]>) -> Self {
+        let mut stats = Stats::default();
+        while let Some(chunk) = next_chunk() {
+            stats.append_chunk(chunk);
+        }
+        stats
+    }
+
+    ///
+    /// Append Boolean samples packed in a bitmap of 64-bit words, where a set bit denotes a success.
+    ///
+    /// The samples are numbered from the least significant bit of the first word: sample \\( i \\)
+    /// is bit \\( i \bmod 64 \\) of `words[i / 64]`, as in the bitmaps of Apache Arrow or of
+    /// Roaring bitmaps. Only the first `valid_bits` samples are counted, so that the bits of the last
+    /// word beyond `valid_bits` are ignored.
+    ///
+    /// This is much faster than appending the samples one at a time, since the successes are
+    /// counted 64 at a time with [`u64::count_ones`].
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of words spanned by `valid_bits`.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - the bitmap
+    /// * `valid_bits` - the number of samples in the bitmap
+    ///
+    /// # Panics
+    ///
+    /// * if `valid_bits` is larger than the number of bits in `words`
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let mut stats = proportion::Stats::default();
+    /// // 70 samples: all successes in the first word, then `true, false, true, false, false, false`
+    /// stats.extend_from_bitmap_u64(&[u64::MAX, 0b1111_0000_0101], 70);
+    /// assert_eq!(stats, proportion::Stats::new(70, 66));
+    /// ```
+    pub fn extend_from_bitmap_u64(&mut self, words: &[u64], valid_bits: usize) {
+        let (full_words, trailing_bits) = (valid_bits / 64, valid_bits % 64);
+        assert!(
+            full_words + usize::from(trailing_bits > 0) <= words.len(),
+            "Number of valid bits ({valid_bits}) must not exceed the size of the bitmap ({} words).",
+            words.len()
+        );
+        let mut successes = words[..full_words]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum::<usize>();
+        if trailing_bits > 0 {
+            let mask = (1_u64 << trailing_bits) - 1;
+            successes += (words[full_words] & mask).count_ones() as usize;
+        }
+        self.population += valid_bits;
+        self.successes += successes;
+    }
+
+    ///
+    /// Extend the data with weighted sample data, given as pairs of a value and its number of occurrences,
+    /// and a condition that must be satisfied for the value to be counted as a success.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of pairs in `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the sample given as an iterator or slice of pairs `(value, count)`
+    /// * `is_success` - a function that returns `true` if a value is a success
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::CountOverflow`] - if the total population size does not fit in a `usize`, in
+    ///   which case the statistics are left unchanged
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let status_codes = [(200, 9_500), (404, 300), (500, 150), (503, 50)];
+    /// let mut stats = proportion::Stats::default();
+    /// stats.extend_counts_if(&status_codes, |&code| code >= 500)?;
+    /// assert_eq!(stats, proportion::Stats::new(10_000, 200));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    #[must_use = "the samples are not recorded if an error is returned"]
+    pub fn extend_counts_if<T, I: ?Sized, F>(&mut self, data: &I, is_success: F) -> CIResult<()>
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a (T, usize)>,
+        F: Fn(&T) -> bool,
+    {
+        let mut stats = *self;
+        for (x_i, count) in data {
+            let successes = if is_success(x_i) { *count } else { 0 };
+            stats.add_checked(*count, successes)?;
+        }
+        *self = stats;
+        Ok(())
+    }
+
     ///
     /// Tests if the conditions for the validity of the Wilson score interval are met.
     /// The conditions for the validity of the Wilson score interval are stated as follows:
@@ -174,6 +521,7 @@ impl Stats {
     ///
     /// # Errors
     ///
+    /// * `TooFewSamples` - if there are no samples
     /// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
     /// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
     /// * `InvalidSuccesses` - if the number of successes is larger than the population size
@@ -204,80 +552,301 @@ impl Stats {
     }
 
     ///
-    /// Extend the data with additional sample data.
+    /// Two-sided p-value of the score test of the hypothesis that the proportion of successes in the
+    /// population is `hypothesized_p`, i.e., \( 2 P(Z > |z|) \) where
+    /// \( z = (\hat{p} - p_0) / \sqrt{p_0 (1 - p_0) / n} \) and \( Z \) follows the standard normal distribution.
     ///
-    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+    /// The score test is the dual of the Wilson score interval (see [`ci_wilson`]): for any \( \alpha \),
+    /// \( p < \alpha \) if and only if the two-sided interval computed by [`Stats::ci`] with
+    /// confidence \( 1 - \alpha \) excludes `hypothesized_p`. Unlike the interval, the p-value is
+    /// also defined when there are fewer than two successes or failures.
+    ///
+    /// Complexity: \( O(1) \)
     ///
     /// # Arguments
     ///
-    /// * `data` - the sample given as a boolean iterator or slice
+    /// * `hypothesized_p` - the proportion of successes under the null hypothesis (must be in (0, 1))
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if there are no samples
+    /// * `InvalidSuccesses` - if the number of successes is larger than the population size
+    /// * `InvalidProbability` - if `hypothesized_p` is not in (0, 1)
     ///
     /// # Examples
+    ///
     /// ```
     /// # use stats_ci::*;
-    /// let data = [true, false, true, true, false, true, true, false, true, true];
-    /// let mut stats = proportion::Stats::default();
-    /// stats.extend(&data);
-    /// assert_eq!(stats, proportion::Stats::new(10, 7));
+    /// let stats = proportion::Stats::new(100, 62);
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.p_value_vs(0.5)?, 0.016395, epsilon = 1e-6);
+    /// assert!(!stats.ci(Confidence::new_two_sided(0.95))?.contains(&0.5));
+    /// assert!(stats.ci(Confidence::new_two_sided(0.99))?.contains(&0.5));
+    /// # Ok::<(),error::CIError>(())
     /// ```
-    pub fn extend<I>(&mut self, data: &I)
-    where
-        for<'a> &'a I: IntoIterator<Item = &'a bool>,
-    {
-        for &x_i in data {
-            if x_i {
-                self.add_success();
-            } else {
-                self.add_failure();
-            }
-        }
+    ///
+    pub fn p_value_vs(&self, hypothesized_p: f64) -> CIResult<f64> {
+        self.score_p_value(hypothesized_p, None)
     }
 
     ///
-    /// Extend the data with additional sample data and a condition that must be satisfied to be counted as a success.
+    /// One-sided p-value of the score test of the hypothesis that the proportion of successes in the
+    /// population is `hypothesized_p`, against the given alternative: \( P(Z > z) \) for
+    /// [`stats::Alternative::Greater`] and \( P(Z < z) \) for [`stats::Alternative::Less`]
+    /// (see [`Stats::p_value_vs`]).
     ///
-    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+    /// For any \( \alpha \), \( p < \alpha \) if and only if the one-sided interval computed by
+    /// [`Stats::ci`] with confidence \( 1 - \alpha \) excludes `hypothesized_p`, where the interval
+    /// is upper one-sided for [`stats::Alternative::Greater`] and lower one-sided for [`stats::Alternative::Less`].
+    ///
+    /// Complexity: \( O(1) \)
     ///
     /// # Arguments
     ///
-    /// * `data` - the sample given as an iterator or slice
-    /// * `is_success` - a function that returns `true` if a sample value is a success
+    /// * `hypothesized_p` - the proportion of successes under the null hypothesis (must be in (0, 1))
+    /// * `alternative` - the alternative hypothesis
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if there are no samples
+    /// * `InvalidSuccesses` - if the number of successes is larger than the population size
+    /// * `InvalidProbability` - if `hypothesized_p` is not in (0, 1)
     ///
     /// # Examples
+    ///
     /// ```
     /// # use stats_ci::*;
-    /// let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
-    /// let mut stats = proportion::Stats::default();
-    /// stats.extend_if(&data, |&x| x <= 5);
-    /// assert_eq!(stats, proportion::Stats::new(10, 5));
+    /// use stats::Alternative;
+    /// let stats = proportion::Stats::new(100, 62);
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(stats.p_value_one_sided_vs(0.5, Alternative::Greater)?, 0.008198, epsilon = 1e-6);
+    /// assert!(stats.p_value_one_sided_vs(0.5, Alternative::Less)? > 0.99);
+    /// # Ok::<(),error::CIError>(())
     /// ```
-    pub fn extend_if<T, I, F>(&mut self, data: &I, is_success: F)
-    where
-        for<'a> &'a I: IntoIterator<Item = &'a T>,
-        F: Fn(&T) -> bool,
-    {
-        for x_i in data {
-            if is_success(x_i) {
-                self.add_success();
-            } else {
-                self.add_failure();
-            }
-        }
+    ///
+    pub fn p_value_one_sided_vs(
+        &self,
+        hypothesized_p: f64,
+        alternative: stats::Alternative,
+    ) -> CIResult<f64> {
+        self.score_p_value(hypothesized_p, Some(alternative))
     }
-}
 
-impl core::ops::Add for Stats {
-    type Output = Self;
+    fn score_p_value(
+        &self,
+        hypothesized_p: f64,
+        alternative: Option<stats::Alternative>,
+    ) -> CIResult<f64> {
+        if self.population == 0 {
+            return Err(CIError::TooFewSamples(self.population));
+        }
+        if self.successes > self.population {
+            return Err(CIError::InvalidSuccesses {
+                successes: self.successes,
+                population: self.population,
+            });
+        }
+        if hypothesized_p.is_nan() || hypothesized_p <= 0. || hypothesized_p >= 1. {
+            return Err(CIError::InvalidProbability(hypothesized_p));
+        }
+        let n = self.population as f64;
+        let estimate = self.successes as f64 / n;
+        let std_err = (hypothesized_p * (1. - hypothesized_p) / n).sqrt();
+        Ok(stats::p_value(
+            estimate,
+            hypothesized_p,
+            std_err,
+            mean::CriticalDistribution::Normal,
+            f64::INFINITY,
+            alternative,
+        ))
+    }
 
     ///
-    /// Combines two statistics objects by adding the number of samples and the number of successes.
+    /// Computes the confidence interval over the proportion of successes for observations
+    /// collected in clusters (e.g., requests grouped by user), using the Wilson score interval
+    /// (see [`ci_wilson`]) with the population reduced to its effective size.
+    ///
+    /// Observations within a cluster tend to be correlated, so that they carry less information
+    /// than independent observations and the plain interval is too narrow. The design effect
+    /// \\( D \\) is the ratio of the variance of the estimated proportion to that of a simple random
+    /// sample of the same size. The effective population and number of successes are \\( n / D \\)
+    /// and \\( n_S / D \\), which preserves the estimated proportion. The design effect can be
+    /// estimated from the sizes of the clusters with [`design_effect_from_cluster_sizes`].
+    ///
+    /// With \\( D = 1 \\) (no clustering), the interval is identical to that of [`Stats::ci`].
     ///
     /// Complexity: \\( O(1) \\)
     ///
-    /// # Examples
-    /// ```
-    /// # use stats_ci::*;
-    /// let stats1 = proportion::Stats::new(100, 50);
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level (must be in (0, 1))
+    /// * `design_effect` - the design effect (must be at least 1)
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidDesignEffect` - if the design effect is less than 1 or not finite
+    /// * `TooFewSamples` - if there are no samples
+    /// * `TooFewSuccesses` - if the effective number of successes is too small to compute a confidence interval
+    /// * `TooFewFailures` - if the effective number of failures is too small to compute a confidence interval
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// // 2000 requests from 100 users (20 requests each), 300 of which failed
+    /// let stats = proportion::Stats::new(2_000, 300);
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// let deff = proportion::design_effect_from_cluster_sizes(&[20; 100], 0.05);
+    /// # use approx::*;
+    /// assert_abs_diff_eq!(deff, 1.95);
+    /// let plain = stats.ci(confidence)?;
+    /// let clustered = stats.ci_with_design_effect(confidence, deff)?;
+    /// assert!(clustered.includes(&plain));
+    /// assert_abs_diff_eq!(clustered, Interval::new(0.1295, 0.1732)?, epsilon = 1e-4);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # References
+    ///
+    /// * L. Kish. Survey Sampling. Wiley, 1965.
+    /// * [Wikipedia article on design effect](https://en.wikipedia.org/wiki/Design_effect)
+    ///
+    pub fn ci_with_design_effect(
+        &self,
+        confidence: Confidence,
+        design_effect: f64,
+    ) -> CIResult<Interval<f64>> {
+        if !(design_effect >= 1. && design_effect.is_finite()) {
+            return Err(CIError::InvalidDesignEffect(design_effect));
+        }
+        if self.population == 0 {
+            return Err(CIError::TooFewSamples(self.population));
+        }
+        let n = self.population as f64 / design_effect;
+        let n_s = self.successes as f64 / design_effect;
+        let n_f = n - n_s;
+        // same (permissive) conditions as for `ci_wilson`, on the effective counts
+        if n_s < 2. {
+            return Err(CIError::TooFewSuccesses {
+                successes: self.successes,
+                population: self.population,
+                n_p: n_s,
+            });
+        }
+        if n_f < 2. {
+            return Err(CIError::TooFewFailures {
+                failures: self.population - self.successes,
+                population: self.population,
+                n_q: n_f,
+            });
+        }
+        let (low, high) = wilson_bounds_f(z_value(confidence), n, n_s);
+        Interval::from_finite_bounds_for(confidence, low, high)
+    }
+
+    ///
+    /// Extend the data with additional sample data.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the sample given as a boolean iterator or slice
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let data = [true, false, true, true, false, true, true, false, true, true];
+    /// let mut stats = proportion::Stats::default();
+    /// stats.extend(&data);
+    /// assert_eq!(stats, proportion::Stats::new(10, 7));
+    /// ```
+    pub fn extend<I: ?Sized>(&mut self, data: &I)
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a bool>,
+    {
+        self.extend_iter(data.into_iter().copied())
+    }
+
+    ///
+    /// Extend the data with sample data consumed from an iterator, such as an iterator adapter.
+    ///
+    /// Unlike [`Stats::extend`], the values are taken by value, which avoids collecting them into
+    /// a temporary collection.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the sample given as a boolean iterator
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let latencies = [12., 250., 31., 18., 420., 25.];
+    /// let mut stats = proportion::Stats::default();
+    /// stats.extend_iter(latencies.iter().map(|&x| x > 100.));
+    /// assert_eq!(stats, proportion::Stats::new(6, 2));
+    /// ```
+    pub fn extend_iter<I>(&mut self, data: I)
+    where
+        I: IntoIterator<Item = bool>,
+    {
+        for x_i in data {
+            if x_i {
+                self.add_success();
+            } else {
+                self.add_failure();
+            }
+        }
+    }
+
+    ///
+    /// Extend the data with additional sample data and a condition that must be satisfied to be counted as a success.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the sample given as an iterator or slice
+    /// * `is_success` - a function that returns `true` if a sample value is a success
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    /// let mut stats = proportion::Stats::default();
+    /// stats.extend_if(&data, |&x| x <= 5);
+    /// assert_eq!(stats, proportion::Stats::new(10, 5));
+    /// ```
+    pub fn extend_if<T, I: ?Sized, F>(&mut self, data: &I, is_success: F)
+    where
+        for<'a> &'a I: IntoIterator<Item = &'a T>,
+        F: Fn(&T) -> bool,
+    {
+        for x_i in data {
+            if is_success(x_i) {
+                self.add_success();
+            } else {
+                self.add_failure();
+            }
+        }
+    }
+}
+
+impl core::ops::Add for Stats {
+    type Output = Self;
+
+    ///
+    /// Combines two statistics objects by adding the number of samples and the number of successes.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats1 = proportion::Stats::new(100, 50);
     /// let stats2 = proportion::Stats::new(200, 100);
     /// let stats = stats1 + stats2;
     /// assert_eq!(stats, proportion::Stats::new(300, 150));
@@ -310,357 +879,1599 @@ impl core::ops::AddAssign for Stats {
     }
 }
 
+impl utils::Accumulator for Stats {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+#[cfg(feature = "persist")]
+impl Stats {
+    ///
+    /// Encode the statistics with the stable binary format of [`persist`] (see the module for the layout).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Example
+    /// ```
+    /// use stats_ci::*;
+    /// let stats = proportion::Stats::new(100, 42);
+    /// assert_eq!(proportion::Stats::from_bytes(&stats.to_bytes())?, stats);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = persist::Writer::new(persist::Kind::Proportion);
+        writer.usize(self.population);
+        writer.usize(self.successes);
+        writer.finish()
+    }
+
+    ///
+    /// Decode statistics encoded with [`Stats::to_bytes`].
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::DecodeError`] - if the input is truncated, has trailing bytes, or has a wrong header
+    ///   (magic bytes, version, or kind of state)
+    /// * [`CIError::InvalidSuccesses`] - if the number of successes is larger than the population
+    ///
+    pub fn from_bytes(bytes: &[u8]) -> CIResult<Self> {
+        let mut reader = persist::Reader::new(bytes, persist::Kind::Proportion)?;
+        let population = reader.usize("population")?;
+        let successes = reader.usize("successes")?;
+        reader.finish()?;
+        if successes > population {
+            return Err(CIError::InvalidSuccesses {
+                successes,
+                population,
+            });
+        }
+        Ok(Self {
+            population,
+            successes,
+        })
+    }
+
+    ///
+    /// Decode statistics encoded with [`Stats::to_bytes`] and combine them with these ones.
+    /// The statistics are left unchanged if decoding or combining fails.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Stats::from_bytes`], and:
+    ///
+    /// * [`CIError::CountOverflow`] - if the total population size does not fit in a `usize`
+    ///
+    pub fn merge_from_bytes(&mut self, bytes: &[u8]) -> CIResult<()> {
+        let stats = Self::from_bytes(bytes)?;
+        self.add_checked(stats.population, stats.successes)
+    }
+}
+
+///
+/// Represents the state of the computation of simultaneous confidence intervals for the shares of
+/// several categories (e.g., hit/miss/error/timeout), where each sample falls into exactly one
+/// category identified by its index.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// const HIT: usize = 0;
+/// const MISS: usize = 1;
+/// const ERROR: usize = 2;
+/// let mut stats = proportion::MultinomialStats::new(3);
+/// stats.extend_from_labels([HIT, HIT, MISS, HIT, ERROR, MISS, HIT, HIT])?;
+/// stats.add(HIT)?;
+/// assert_eq!(stats.counts(), &[6, 2, 1]);
+/// assert_eq!(stats.population(), 9);
+///
+/// let intervals = stats.ci(Confidence::new_two_sided(0.95))?;
+/// assert_eq!(intervals.len(), 3);
+/// for (category, interval) in intervals.iter().enumerate() {
+///     assert!(interval.contains(&stats.share(category)));
+/// }
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultinomialStats {
+    counts: Vec<usize>,
+    population: usize,
+}
+
+impl MultinomialStats {
+    ///
+    /// Creates a new statistics object for the given number of categories, with no samples.
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of categories.
+    ///
+    pub fn new(categories: usize) -> Self {
+        MultinomialStats {
+            counts: vec![0; categories],
+            population: 0,
+        }
+    }
+
+    ///
+    /// Creates a new statistics object with the given counts, one per category.
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of categories.
+    ///
+    pub fn from_counts(counts: &[usize]) -> Self {
+        MultinomialStats {
+            counts: counts.to_vec(),
+            population: counts.iter().sum(),
+        }
+    }
+
+    ///
+    /// Returns the number of categories.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn categories(&self) -> usize {
+        self.counts.len()
+    }
+
+    ///
+    /// Returns the population size (total number of samples across all categories).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    ///
+    /// Returns the number of samples in each category, indexed by category.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    ///
+    /// Returns the number of samples in the given category, or zero if the category is out of range.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn count(&self, category: usize) -> usize {
+        self.counts.get(category).copied().unwrap_or(0)
+    }
+
+    ///
+    /// Returns the share of the samples in the given category, or NaN if the sample is empty.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn share(&self, category: usize) -> f64 {
+        self.count(category) as f64 / self.population as f64
+    }
+
+    ///
+    /// Returns the statistics of the proportion of samples in the given category, i.e., where a
+    /// success is a sample in that category and a failure a sample in any other category.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn category_stats(&self, category: usize) -> Stats {
+        Stats::new(self.population, self.count(category))
+    }
+
+    ///
+    /// Add a sample in the given category.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidCategory` - if `category` is not smaller than the number of categories,
+    ///   in which case the statistics are left unchanged
+    ///
+    #[must_use = "the sample is not recorded if an error is returned"]
+    pub fn add(&mut self, category: usize) -> CIResult<()> {
+        let categories = self.categories();
+        let count = self
+            .counts
+            .get_mut(category)
+            .ok_or(CIError::InvalidCategory {
+                category,
+                categories,
+            })?;
+        *count += 1;
+        self.population += 1;
+        Ok(())
+    }
+
+    ///
+    /// Add the samples of an iterator of category indices (labels).
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `labels`.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidCategory` - if a label is not smaller than the number of categories.
+    ///   The labels before the first invalid one are added and the following ones are not, so the
+    ///   number of labels consumed is the increase of [`MultinomialStats::population`].
+    ///
+    #[must_use = "some samples may not be recorded if an error is returned"]
+    pub fn extend_from_labels<I>(&mut self, labels: I) -> CIResult<()>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        for category in labels {
+            self.add(category)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Computes simultaneous confidence intervals for the shares of all categories with the method
+    /// of Goodman (1965), in the order of the categories.
+    ///
+    /// The intervals hold simultaneously: asymptotically, the probability that all the shares lie in their
+    /// respective intervals is at least the confidence level.
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of categories.
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the simultaneous confidence level
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if there are no samples
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// # use approx::*;
+    /// let stats = proportion::MultinomialStats::from_counts(&[35, 74, 22, 69]);
+    /// let intervals = stats.ci(Confidence::new_two_sided(0.95))?;
+    /// assert_abs_diff_eq!(intervals[0], Interval::new(0.1180, 0.2516)?, epsilon = 1e-4);
+    /// assert_abs_diff_eq!(intervals[1], Interval::new(0.2899, 0.4580)?, epsilon = 1e-4);
+    /// // each interval is wider than the interval on the share of its category alone
+    /// let unadjusted = stats.ci_unadjusted(Confidence::new_two_sided(0.95))?;
+    /// assert!(unadjusted[0].is_included_in(&intervals[0]));
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// Goodman's interval for a category with \\( n_i \\) samples among \\( n \\) is
+    /// \\[
+    /// \frac{A + 2 n_i \pm \sqrt{A \left(A + 4 n_i (n - n_i) / n\right)}}{2 (n + A)}
+    /// \\]
+    /// where \\( A \\) is the quantile \\( 1 - \alpha / k \\) of the chi-squared distribution with one degree of
+    /// freedom, \\( \alpha \\) is one minus the confidence level, and \\( k \\) the number of categories.
+    /// This is the Wilson score interval (see [`ci_wilson`]) of each share at the confidence level
+    /// \\( 1 - \alpha / k \\), so that the simultaneous coverage follows from the Bonferroni inequality.
+    /// For one-sided confidence, the one-sided Wilson bounds are adjusted in the same way.
+    ///
+    /// Unlike [`ci_wilson`], there is no check on the number of samples in each category: the bounds
+    /// are well defined for any count, including empty categories (whose lower bound is zero).
+    /// As for the Wilson interval, the coverage is only approximate for small samples.
+    ///
+    /// # References
+    ///
+    /// * L. A. Goodman. "On Simultaneous Confidence Intervals for Multinomial Proportions." Technometrics, 7(2):247–254, 1965.
+    /// * C. P. Sison and J. Glaz. "Simultaneous Confidence Intervals and Sample Size Determination for Multinomial Proportions." Journal of the American Statistical Association, 90(429):366–369, 1995.
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Vec<Interval<f64>>> {
+        let categories = self.categories().max(1) as f64;
+        let adjusted = confidence.with_level(1. - (1. - confidence.level()) / categories);
+        self.ci_wilson_each(adjusted)
+    }
+
+    ///
+    /// Computes the Wilson score interval on the share of each category separately, in the order
+    /// of the categories, without adjustment for multiplicity.
+    ///
+    /// Each interval covers the share of its category with the given confidence, but unlike
+    /// [`MultinomialStats::ci`], the intervals do not hold simultaneously.
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of categories.
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if there are no samples
+    ///
+    /// # Notes
+    ///
+    /// The bounds are those of [`ci_wilson`], without its check on the number of successes and failures.
+    ///
+    pub fn ci_unadjusted(&self, confidence: Confidence) -> CIResult<Vec<Interval<f64>>> {
+        self.ci_wilson_each(confidence)
+    }
+
+    fn ci_wilson_each(&self, confidence: Confidence) -> CIResult<Vec<Interval<f64>>> {
+        if self.population == 0 {
+            return Err(CIError::TooFewSamples(self.population));
+        }
+        let z = z_value(confidence);
+        self.counts
+            .iter()
+            .map(|&count| {
+                let (low, high) = wilson_bounds(z, self.population, count);
+                Interval::from_finite_bounds_for(confidence, low.max(0.), high.min(1.))
+            })
+            .collect()
+    }
+}
+
+impl core::ops::Add for MultinomialStats {
+    type Output = Self;
+
+    ///
+    /// Combines two statistics objects by adding the counts of each category.
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of categories.
+    ///
+    /// # Panics
+    ///
+    /// * if the numbers of categories differ
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats1 = proportion::MultinomialStats::from_counts(&[10, 5, 1]);
+    /// let stats2 = proportion::MultinomialStats::from_counts(&[20, 0, 3]);
+    /// let stats = stats1 + stats2;
+    /// assert_eq!(stats, proportion::MultinomialStats::from_counts(&[30, 5, 4]));
+    /// ```
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl core::ops::AddAssign for MultinomialStats {
+    ///
+    /// Combines two statistics objects by adding the counts of each category.
+    ///
+    /// Complexity: \\( O(k) \\) where \\( k \\) is the number of categories.
+    ///
+    /// # Panics
+    ///
+    /// * if the numbers of categories differ
+    ///
+    fn add_assign(&mut self, rhs: Self) {
+        assert_eq!(
+            self.categories(),
+            rhs.categories(),
+            "Number of categories must be the same."
+        );
+        for (count, rhs_count) in self.counts.iter_mut().zip(rhs.counts) {
+            *count += rhs_count;
+        }
+        self.population += rhs.population;
+    }
+}
+
+impl utils::Accumulator for MultinomialStats {
+    fn reset(&mut self) -> Self {
+        let empty = Self::new(self.categories());
+        core::mem::replace(self, empty)
+    }
+}
+
+///
+/// Computes the (two sided) confidence interval over the proportion of true values in a given sample.
+///
+/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data` - the sample given as a boolean iterator or slice
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the data is empty
+/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
+/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
+/// * `InvalidSuccesses` - if the number of successes is larger than the population size
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// # use approx::*;
+/// let data = [
+///     true, false, true, true, false, true, true, false, true, true,
+///     false, false, false, true, false, true, false, false, true, false
+/// ];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = proportion::ci_true(confidence, &data)?;
+/// assert_abs_diff_eq!(interval, Interval::new(0.299, 0.701)?, epsilon = 1e-2);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # Notes
+///
+/// The confidence interval is computed using the function [`ci_wilson`] (Wilson score interval).
+///
+pub fn ci_true<I>(confidence: Confidence, data: &I) -> CIResult<Interval<f64>>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a bool>,
+{
+    ci_true_counted(confidence, data).map(|(interval, _)| interval)
+}
+
+///
+/// Same as [`ci_true`], also returning the counts of the sample (population size and number of
+/// successes) obtained in the same pass over the data.
+///
+/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+///
+/// # Errors
+///
+/// Same as [`ci_true`].
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// let data = [
+///     true, false, true, true, false, true, true, false, true, true,
+///     false, false, false, true, false, true, false, false, true, false
+/// ];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let (interval, stats) = proportion::ci_true_counted(confidence, &data)?;
+/// assert_eq!(stats, proportion::Stats::new(20, 10));
+/// assert_eq!(
+///     format!("{}/{} → {}", stats.successes(), stats.population(), interval.display_compact(3)),
+///     "10/20 → [0.299, 0.701]"
+/// );
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_true_counted<I>(confidence: Confidence, data: &I) -> CIResult<(Interval<f64>, Stats)>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a bool>,
+{
+    let mut stats = Stats::default();
+    stats.extend(data);
+    Ok((stats.ci(confidence)?, stats))
+}
+
+///
+/// Computes the (two sided) confidence interval over the proportion of a given sample that satisfies a given condition.
+///
+/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data` - the sample given as a boolean iterator or slice
+/// * `condition` - the condition that must be satisfied to be counted as a success
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the data is empty
+/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
+/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
+/// * `InvalidSuccesses` - if the number of successes is larger than the population size
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// # use approx::*;
+/// let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = proportion::ci_if(confidence, &data, |&x| x <= 10)?;
+/// assert_abs_diff_eq!(interval, Interval::new(0.299, 0.701)?, epsilon = 1e-2);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_if<T, I, F>(confidence: Confidence, data: &I, cond: F) -> CIResult<Interval<f64>>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+    F: Fn(&T) -> bool,
+{
+    ci_if_counted(confidence, data, cond).map(|(interval, _)| interval)
+}
+
+///
+/// Same as [`ci_if`], also returning the counts of the sample (population size and number of
+/// successes) obtained in the same pass over the data.
+///
+/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+///
+/// # Errors
+///
+/// Same as [`ci_if`].
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// // response times (ms) of 500 requests
+/// let response_times: Vec<u32> = (0..500).map(|i| (i * 37) % 500).collect();
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let (interval, stats) = proportion::ci_if_counted(confidence, &response_times, |&t| t < 421)?;
+/// let summary = format!(
+///     "{}/{} → {}",
+///     stats.successes(),
+///     stats.population(),
+///     interval.display_compact(3)
+/// );
+/// assert_eq!(summary, "421/500 → [0.807, 0.871]");
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_if_counted<T, I, F>(
+    confidence: Confidence,
+    data: &I,
+    cond: F,
+) -> CIResult<(Interval<f64>, Stats)>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+    F: Fn(&T) -> bool,
+{
+    let mut stats = Stats::default();
+    stats.extend_if(data, cond);
+    Ok((stats.ci(confidence)?, stats))
+}
+
+///
+/// Computes the (two sided) confidence interval over the proportion of successes a given sample.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `population` - the size of the population
+/// * `successes` - the number of successes in the sample
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the population is empty
+/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
+/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
+/// * `InvalidSuccesses` - if the number of successes is larger than the population size
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This function is an alias for [`ci_wilson`].
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// # use approx::*;
+/// let population = 500;
+/// let successes = 421;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = proportion::ci(confidence, population, successes)?;
+/// assert_abs_diff_eq!(interval, Interval::new(0.81, 0.87)?, epsilon = 1e-2);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci(confidence: Confidence, population: usize, successes: usize) -> CIResult<Interval<f64>> {
+    ci_wilson(confidence, population, successes)
+}
+
+///
+/// Computes the design effect of cluster sampling \\( D = 1 + (\bar{m} - 1) \rho \\), where
+/// \\( \bar{m} \\) is the average size of the clusters and \\( \rho \\) is the intraclass
+/// correlation coefficient (ICC), i.e., the correlation between two observations of the same cluster.
+///
+/// The formula assumes that the clusters have similar sizes and that the intraclass correlation
+/// is the same in all clusters. It is typically used with [`Stats::ci_with_design_effect`].
+///
+/// # Arguments
+///
+/// * `cluster_sizes` - the number of observations in each cluster
+/// * `icc` - the intraclass correlation coefficient (must be in [0, 1])
+///
+/// # Panics
+///
+/// * if `cluster_sizes` is empty
+/// * if `icc` is not in the range [0, 1]
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// assert_eq!(proportion::design_effect_from_cluster_sizes(&[5, 10, 15], 0.25), 3.25);
+/// // independent observations
+/// assert_eq!(proportion::design_effect_from_cluster_sizes(&[10, 20, 30], 0.), 1.);
+/// ```
+///
+pub fn design_effect_from_cluster_sizes(cluster_sizes: &[usize], icc: f64) -> f64 {
+    assert!(
+        !cluster_sizes.is_empty(),
+        "There must be at least one cluster."
+    );
+    assert!(
+        (0. ..=1.).contains(&icc),
+        "Intraclass correlation must be in the range [0, 1]."
+    );
+    let total: f64 = cluster_sizes.iter().map(|&size| size as f64).sum();
+    let mean_size = total / cluster_sizes.len() as f64;
+    1. + (mean_size - 1.) * icc
+}
+
+///
+/// Check if the conditions for the validity of the Wilson score interval are met.
+/// The conditions for the validity of hypothesis tests (from which the Wilson score is derived) are stated as follows:
+/// <https://www.itl.nist.gov/div898/handbook/prc/section2/prc24.htm>
+/// 1. The sample size is large enough to ensure that the sampling distribution of the sample proportion is approximately normal (N > 30)
+/// 2. The number of successes and failures are large enough to ensure that the sampling distribution of the sample proportion is approximately normal (x > 5 and n - x > 5)
+///
+/// # Arguments
+///
+/// * `population` - the size of the population
+/// * `successes` - the number of successes in the sample
+///
+/// # Returns
+///
+/// `true` if the conditions are met, `false` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// assert!(proportion::is_significant(500, 10));
+/// assert!(! proportion::is_significant(10, 5));
+/// assert!(! proportion::is_significant(1000, 1));
+/// ```
+pub fn is_significant(population: usize, successes: usize) -> bool {
+    // significance criteria for Wilson score intervals.
+    // see https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval#Wilson_score_interval
+    // The conditions for the validity of hypothesis tests (from which the Wilson score is derived) are stated as follows:
+    // https://www.itl.nist.gov/div898/handbook/prc/section2/prc24.htm
+    // 1. The sample size is large enough to ensure that the sampling distribution of the sample proportion is approximately normal (N > 30)
+    (population > 30)
+    // 2. The number of successes and failures are large enough to ensure that the sampling distribution of the sample proportion is approximately normal (x > 5 and n - x > 5)
+    && (successes > 5)
+    && (population - successes > 5)
+}
+
+///
+/// Computes the (two sided) confidence interval over the proportion of successes a given sample using the Wilson score interval.
+/// This is the method used by default when calling the function [`ci`] of this module.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `population` - the size of the population
+/// * `successes` - the number of successes in the sample
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the population is empty
+/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
+/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
+/// * `InvalidSuccesses` - if the number of successes is larger than the population size
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This method is based on the Wilson score interval, which is a modification of the normal approximation interval.
+/// It is more robust than the normal approximation interval, but it is also more conservative.
+/// In particular, it is more conservative when the sample size is small.
+/// It is also more conservative when the sample size is large and the proportion is close to 0 or 1.
+///
+/// Using the Wilson score interval, the probability of success \\( p \\) is estimated by:
+/// \\[
+/// p \approx  \frac{n_S+\frac{1}{2}z^2}{n+z^2} \pm \frac{z}{n+z^2} \sqrt{\frac{n_S ~ n_F}{n}+\frac{z^2}{4}}
+/// \\]
+/// where
+/// * \\( n_S \\) is the number of successes,
+/// * \\( n_F \\) is the number of failures,
+/// * \\( n = n_S + n_F \\) is the sample size, and
+/// * \\( z \\) is the z-value corresponding to the confidence level.
+///
+/// The conditions for the validity of the Wilson score interval can be checked with the function [`is_significant`].
+/// However, the significance check for this function is much more permissive. It is the caller's responsibility to check for the stricter conditions for statistical significance if necessary.
+/// One advantage of using the Wilson score interval is that it is still reasonably accurate for small sample sizes and when the proportion of successes is close to 0 or 1.
+///
+/// # References
+///
+/// * [Wikipedia article on Wilson score interval](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval#Wilson_score_interval)
+/// * Francis J. DiTraglia. [Blog post: The Wilson Confidence Interval for a Proportion](https://www.econometrics.blog/post/the-wilson-confidence-interval-for-a-proportion/). Feb 2022.
+///
+pub fn ci_wilson(
+    confidence: Confidence,
+    population: usize,
+    successes: usize,
+) -> CIResult<Interval<f64>> {
+    if population == 0 {
+        return Err(CIError::TooFewSamples(population));
+    }
+    if successes > population {
+        return Err(CIError::InvalidSuccesses {
+            successes,
+            population,
+        });
+    }
+
+    let n = population as f64;
+    let n_s = successes as f64;
+    let n_f = n - n_s;
+
+    // conditions for statistical significance:
+    // n p > 5 and n (1 - p) > 5
+    // however, we are more permissive here and rely on the user to check for the stricter conditions for statistical significance.
+    if successes < 2 {
+        // too few successes for statistical significance
+        return Err(CIError::TooFewSuccesses {
+            successes,
+            population,
+            n_p: n_s,
+        });
+    }
+    if population - successes < 2 {
+        // too few failures for statistical significance
+        return Err(CIError::TooFewFailures {
+            failures: population - successes,
+            population,
+            n_q: n_f,
+        });
+    }
+
+    let (low, high) = wilson_bounds(z_value(confidence), population, successes);
+    Interval::from_finite_bounds_for(confidence, low, high)
+}
+
+///
+/// Computes the bounds of the Wilson score interval for a given z-value, without any check on
+/// the number of successes or failures (the bounds are well-defined for any non-empty population).
+///
+pub(crate) fn wilson_bounds(z: f64, population: usize, successes: usize) -> (f64, f64) {
+    wilson_bounds_f(z, population as f64, successes as f64)
+}
+
+///
+/// Same as [`wilson_bounds`], with possibly fractional (e.g., effective) counts.
+///
+fn wilson_bounds_f(z: f64, n: f64, n_s: f64) -> (f64, f64) {
+    let n_f = n - n_s;
+    let z_sq = z * z;
+
+    let mean = (n_s + z_sq / 2.) / (n + z_sq);
+    let span = (z / (n + z_sq)) * ((n_s * n_f / n) + (z_sq / 4.)).sqrt();
+    (mean - span, mean + span)
+}
+
+///
+/// Computes the (two sided) confidence interval over the proportion of successes in a given sample using the Wilson score interval.
+/// This is the method used by default when calling the function [`ci`] of this module.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `population` - the size of the population
+/// * `success_rate` - the proportion of successes in the sample (must be in (0, 1))
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the population is empty
+/// * `InvalidSuccessRate` - if the success rate is not in (0, 1) (or is NaN)
+/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
+/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This method is simply a front for [`ci_wilson`], which takes the number of successes as an argument.
+///
+/// The number of successes is obtained by rounding \\( p \cdot n \\) to the nearest integer
+/// (halfway cases are rounded away from zero), where \\( p \\) is the success rate and \\( n \\)
+/// the population. Hence, rates that differ by less than \\( 1/n \\) may yield the same interval,
+/// and a rate close enough to 0 (resp. 1) may round to no successes (resp. no failures), in which
+/// case the errors of [`ci_wilson`] apply.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// // 0.8333 * 120 = 99.996, rounded to 100 successes
+/// assert_eq!(
+///     proportion::ci_wilson_ratio(confidence, 120, 0.8333)?,
+///     proportion::ci_wilson(confidence, 120, 100)?
+/// );
+/// assert!(proportion::ci_wilson_ratio(confidence, 120, 1.).is_err());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_wilson_ratio(
+    confidence: Confidence,
+    population: usize,
+    success_rate: f64,
+) -> CIResult<Interval<f64>> {
+    if population == 0 {
+        return Err(CIError::TooFewSamples(population));
+    }
+    if !(0. < success_rate && success_rate < 1.) {
+        return Err(CIError::InvalidSuccessRate(success_rate));
+    }
+    let successes = (success_rate * population as f64).round() as usize;
+
+    ci_wilson(confidence, population, successes)
+}
+
+///
+/// Computes the confidence interval over the proportion of successes in a given sample using the normal approximation interval (Wald interval).
+///
+/// Using the normal approximation interval (Wald method), the probability of success \\( p \\) is estimated by:
+/// \\[
+/// p \approx  \frac{n_S}{n} \pm z \sqrt{\frac{n_S ~ n_F}{n^3}} = \hat{p} \pm z \sqrt{\frac{\hat{p} ~ (1 - \hat{p})}{n}}
+/// \\]
+/// where
+/// * \\( n_S \\) is the number of successes,
+/// * \\( n_F \\) is the number of failures,
+/// * \\( n = n_S + n_F \\) is the sample size,
+/// * \\( z \\) is the z-value corresponding to the confidence level, and
+/// * \\( \hat{p} = \frac{n_S}{n} \\) is the estimated probability of success.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `population` - the size of the population
+/// * `successes` - the number of successes in the sample
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the population is empty
+/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
+/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
+/// * `InvalidSuccesses` - if the number of successes is larger than the population size
+/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// This method is based on the normal approximation interval.
+/// It is less robust than the Wilson score interval, but it is also less conservative.
+///
+/// # References
+///
+/// * [Wikipedia article on normal approximation interval](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval#Normal_approximation_interval)
+///
+pub fn ci_z_normal(
+    confidence: Confidence,
+    population: usize,
+    successes: usize,
+) -> CIResult<Interval<f64>> {
+    if population == 0 {
+        return Err(CIError::TooFewSamples(population));
+    }
+    if successes > population {
+        return Err(CIError::InvalidSuccesses {
+            successes,
+            population,
+        });
+    }
+
+    let n = population as f64;
+    let x = successes as f64;
+    let p = x / n;
+    let q = 1. - p;
+
+    if n * p < 10. {
+        // too few successes for statistical significance
+        return Err(CIError::TooFewSuccesses {
+            successes,
+            population,
+            n_p: n * p,
+        });
+    }
+    if n * q < 10. {
+        // too few failures for statistical significance
+        return Err(CIError::TooFewFailures {
+            failures: population - successes,
+            population,
+            n_q: n * q,
+        });
+    }
+
+    let std_dev = (p * q / n).sqrt();
+    let z = z_value(confidence);
+    let mean = p;
+    let span = z * std_dev;
+    Interval::from_finite_bounds_for(confidence, mean - span, mean + span)
+}
+
+///
+/// Computes the confidence interval over the proportion of successes of a given sample using the
+/// Agresti-Coull interval.
+///
+/// The interval adds \\( z^2/2 \\) pseudo-successes and \\( z^2/2 \\) pseudo-failures to the
+/// sample and computes the normal approximation (Wald) interval on the adjusted counts:
+/// \\[
+/// \tilde{p} \pm z \sqrt{\frac{\tilde{p} (1 - \tilde{p})}{\tilde{n}}}
+/// \\]
+/// where
+/// * \\( \tilde{n} = n + z^2 \\) is the adjusted sample size,
+/// * \\( \tilde{p} = \frac{n_S + z^2/2}{\tilde{n}} \\) is the adjusted probability of success, and
+/// * \\( z \\) is the z-value corresponding to the confidence level.
+///
+/// The bounds are clamped to \\( [0, 1] \\).
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `population` - the size of the population
+/// * `successes` - the number of successes in the sample
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the population is empty
+/// * `InvalidSuccesses` - if the number of successes is larger than the population size
+///
+/// # Notes
+///
+/// Unlike [`ci_wilson`] and [`ci_z_normal`], the interval is defined for any number of successes,
+/// including none or all of the population. It is centered on the same point as the Wilson score
+/// interval but is slightly wider, hence somewhat conservative, and is recommended by Brown, Cai,
+/// and DasGupta for moderate to large samples (\\( n \geq 40 \\)).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let ci = proportion::ci_agresti_coull(confidence, 29, 10)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.1985, 0.5274)?, epsilon = 1e-4);
+/// // no failure observed
+/// let ci = proportion::ci_agresti_coull(confidence, 20, 0)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0., 0.1898)?, epsilon = 1e-4);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * A. Agresti, B. A. Coull. Approximate is better than "exact" for interval estimation of binomial proportions. The American Statistician, 52(2):119–126, 1998.
+/// * L. D. Brown, T. T. Cai, A. DasGupta. Interval estimation for a binomial proportion. Statistical Science, 16(2):101–133, 2001.
+/// * [Wikipedia article on Agresti-Coull interval](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval#Agresti%E2%80%93Coull_interval)
+///
+pub fn ci_agresti_coull(
+    confidence: Confidence,
+    population: usize,
+    successes: usize,
+) -> CIResult<Interval<f64>> {
+    if population == 0 {
+        return Err(CIError::TooFewSamples(population));
+    }
+    if successes > population {
+        return Err(CIError::InvalidSuccesses {
+            successes,
+            population,
+        });
+    }
+
+    let z = z_value(confidence);
+    let z_sq = z * z;
+    let n = population as f64 + z_sq;
+    let p = (successes as f64 + z_sq / 2.) / n;
+    let span = z * (p * (1. - p) / n).sqrt();
+    Interval::from_finite_bounds_for(confidence, (p - span).max(0.), (p + span).min(1.))
+}
+
 ///
-/// Computes the (two sided) confidence interval over the proportion of true values in a given sample.
+/// Computes the confidence interval over the proportion of successes of a given sample using the
+/// variance-stabilizing arcsine transform.
 ///
-/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+/// The transform \( \theta = \arcsin \sqrt{\hat{p}} \) has an approximate variance of
+/// \( \frac{1}{4n} \) regardless of the proportion, and the interval is mapped back to the
+/// scale of proportions:
+/// \[
+/// \sin^2\left( \arcsin \sqrt{\hat{p}} \pm \frac{z}{2\sqrt{n}} \right)
+/// \]
+/// where \( \hat{p} = n_S / n \) and \( z \) is the z-value corresponding to the confidence level.
+/// The transformed bounds are clamped to \( [0, \pi/2] \), hence the bounds to \( [0, 1] \).
 ///
 /// # Arguments
 ///
 /// * `confidence` - the confidence level (must be in (0, 1))
-/// * `data` - the sample given as a boolean iterator or slice
+/// * `population` - the size of the population
+/// * `successes` - the number of successes in the sample
 ///
 /// # Errors
 ///
-/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
-/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
+/// * `TooFewSamples` - if the population is empty
 /// * `InvalidSuccesses` - if the number of successes is larger than the population size
-/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+///
+/// # Notes
+///
+/// Like [`ci_agresti_coull`], the interval is defined for any number of successes, including none
+/// or all of the population, where the Wald interval ([`ci_z_normal`]) degenerates.
+/// See [`ci_arcsine_transformed`] for the interval on the transformed scale.
 ///
 /// # Examples
 ///
 /// ```
-/// use stats_ci::*;
+/// # use stats_ci::*;
 /// # use approx::*;
-/// let data = [
-///     true, false, true, true, false, true, true, false, true, true,
-///     false, false, false, true, false, true, false, false, true, false
-/// ];
 /// let confidence = Confidence::new_two_sided(0.95);
-/// let interval = proportion::ci_true(confidence, &data)?;
-/// assert_abs_diff_eq!(interval, Interval::new(0.299, 0.701)?, epsilon = 1e-2);
+/// let ci = proportion::ci_arcsine(confidence, 29, 10)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.1858, 0.5242)?, epsilon = 1e-4);
+/// // no success observed
+/// let ci = proportion::ci_arcsine(confidence, 20, 0)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0., 0.0473)?, epsilon = 1e-4);
 /// # Ok::<(),error::CIError>(())
 /// ```
 ///
-/// # Notes
+/// # References
 ///
-/// The confidence interval is computed using the function [`ci_wilson`] (Wilson score interval).
+/// * F. J. Anscombe. The transformation of Poisson, binomial and negative-binomial data. Biometrika, 35(3/4):246–254, 1948.
+/// * [Wikipedia article on variance-stabilizing transformation](https://en.wikipedia.org/wiki/Variance-stabilizing_transformation)
 ///
-pub fn ci_true<I>(confidence: Confidence, data: &I) -> CIResult<Interval<f64>>
-where
-    for<'a> &'a I: IntoIterator<Item = &'a bool>,
-{
-    let mut stats = Stats::default();
-    stats.extend(data);
-    stats.ci(confidence)
+pub fn ci_arcsine(
+    confidence: Confidence,
+    population: usize,
+    successes: usize,
+) -> CIResult<Interval<f64>> {
+    let interval = ci_arcsine_transformed(confidence, population, successes)?;
+    Ok(interval.map(|theta| theta.clamp(0., core::f64::consts::FRAC_PI_2).sin().powi(2)))
 }
 
 ///
-/// Computes the (two sided) confidence interval over the proportion of a given sample that satisfies a given condition.
+/// Computes the confidence interval on the arcsine-square-root transform
+/// \( \theta = \arcsin \sqrt{\hat{p}} \) of the proportion of successes, as used to pool
+/// proportions in meta-analyses.
 ///
-/// Complexity: \\( O(n) \\) where \\( n \\) is the number of samples in `data`.
+/// The interval is \( \theta \pm \frac{z}{2\sqrt{n}} \), where \( \frac{1}{4n} \) is the
+/// approximate variance of \( \theta \). Unlike [`ci_arcsine`], the bounds are not clamped, so
+/// that the interval remains centered on \( \theta \) (or at distance \( \frac{z}{2\sqrt{n}} \)
+/// from it for one-sided intervals).
 ///
 /// # Arguments
 ///
 /// * `confidence` - the confidence level (must be in (0, 1))
-/// * `data` - the sample given as a boolean iterator or slice
-/// * `condition` - the condition that must be satisfied to be counted as a success
+/// * `population` - the size of the population
+/// * `successes` - the number of successes in the sample
 ///
 /// # Errors
 ///
-/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
-/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
+/// * `TooFewSamples` - if the population is empty
 /// * `InvalidSuccesses` - if the number of successes is larger than the population size
-/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
 ///
 /// # Examples
 ///
 /// ```
-/// use stats_ci::*;
+/// # use stats_ci::*;
 /// # use approx::*;
-/// let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20];
 /// let confidence = Confidence::new_two_sided(0.95);
-/// let interval = proportion::ci_if(confidence, &data, |&x| x <= 10)?;
-/// assert_abs_diff_eq!(interval, Interval::new(0.299, 0.701)?, epsilon = 1e-2);
+/// let ci = proportion::ci_arcsine_transformed(confidence, 29, 10)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.4456, 0.8096)?, epsilon = 1e-4);
+/// let theta = ci.center().unwrap();
+/// assert_abs_diff_eq!(theta, (10_f64 / 29.).sqrt().asin(), epsilon = 1e-12);
 /// # Ok::<(),error::CIError>(())
 /// ```
 ///
-pub fn ci_if<T, I, F>(confidence: Confidence, data: &I, cond: F) -> CIResult<Interval<f64>>
-where
-    for<'a> &'a I: IntoIterator<Item = &'a T>,
-    F: Fn(&T) -> bool,
-{
-    let mut stats = Stats::default();
-    stats.extend_if(data, cond);
-    stats.ci(confidence)
+pub fn ci_arcsine_transformed(
+    confidence: Confidence,
+    population: usize,
+    successes: usize,
+) -> CIResult<Interval<f64>> {
+    if population == 0 {
+        return Err(CIError::TooFewSamples(population));
+    }
+    if successes > population {
+        return Err(CIError::InvalidSuccesses {
+            successes,
+            population,
+        });
+    }
+
+    let n = population as f64;
+    let theta = (successes as f64 / n).sqrt().asin();
+    let span = z_value(confidence) / (2. * n.sqrt());
+    Interval::from_finite_bounds_for(confidence, theta - span, theta + span)
 }
 
 ///
-/// Computes the (two sided) confidence interval over the proportion of successes a given sample.
+/// Computes the confidence interval on the odds ratio of two samples, i.e., the odds of success
+/// in the first sample divided by the odds of success in the second sample.
 ///
-/// Complexity: \\( O(1) \\)
+/// With the 2x2 table of counts where \\( a \\) and \\( b \\) (resp. \\( c \\) and \\( d \\)) are the
+/// successes and failures of the first (resp. second) sample, the odds ratio is estimated by
+/// \\( OR = \frac{a d}{b c} \\) and the interval is computed on its logarithm (Wald interval):
+/// \\[
+/// \exp\left( \ln OR \pm z \sqrt{\frac{1}{a} + \frac{1}{b} + \frac{1}{c} + \frac{1}{d}} \right)
+/// \\]
 ///
 /// # Arguments
 ///
-/// * `confidence` - the confidence level (must be in (0, 1))
-/// * `population` - the size of the population
-/// * `successes` - the number of successes in the sample
+/// * `confidence` - the confidence level
+/// * `stats_a` - the counts of the first sample
+/// * `stats_b` - the counts of the second (reference) sample
 ///
 /// # Errors
 ///
-/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
-/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
-/// * `InvalidSuccesses` - if the number of successes is larger than the population size
-/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * [`CIError::EmptyCell`] - if any of the four counts is zero (see [`odds_ratio_ci_haldane`])
 ///
-/// # Notes
+/// # Examples
 ///
-/// This function is an alias for [`ci_wilson`].
+/// ```
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let exposed = proportion::Stats::new(100, 20);
+/// let unexposed = proportion::Stats::new(100, 10);
+/// let ci = proportion::odds_ratio_ci(Confidence::new_two_sided(0.95), &exposed, &unexposed)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.9943, 5.0915)?, epsilon = 1e-4);
+/// assert!(ci.contains(&1.)); // not significant
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// # References
+///
+/// * [Wikipedia article on odds ratio](https://en.wikipedia.org/wiki/Odds_ratio#Statistical_inference)
+/// * R package `epitools`, function `oddsratio.wald`
+///
+pub fn odds_ratio_ci(
+    confidence: Confidence,
+    stats_a: &Stats,
+    stats_b: &Stats,
+) -> CIResult<Interval<f64>> {
+    check_cells(&[
+        (stats_a.successes(), "no successes in the first sample"),
+        (stats_a.failures(), "no failures in the first sample"),
+        (stats_b.successes(), "no successes in the second sample"),
+        (stats_b.failures(), "no failures in the second sample"),
+    ])?;
+    odds_ratio_ci_with(confidence, stats_a, stats_b, 0.)
+}
+
+///
+/// Computes the confidence interval on the odds ratio of two samples as [`odds_ratio_ci`], with
+/// the Haldane-Anscombe correction: 0.5 is added to each of the four counts, such that the interval
+/// is also defined when some count is zero.
 ///
 /// # Examples
 ///
 /// ```
-/// use stats_ci::*;
-/// # use approx::*;
-/// let population = 500;
-/// let successes = 421;
+/// # use stats_ci::*;
+/// let treated = proportion::Stats::new(20, 0);
+/// let control = proportion::Stats::new(20, 12);
 /// let confidence = Confidence::new_two_sided(0.95);
-/// let interval = proportion::ci(confidence, population, successes)?;
-/// assert_abs_diff_eq!(interval, Interval::new(0.81, 0.87)?, epsilon = 1e-2);
+/// assert!(proportion::odds_ratio_ci(confidence, &treated, &control).is_err());
+/// let ci = proportion::odds_ratio_ci_haldane(confidence, &treated, &control)?;
+/// assert!(ci.high_f() < 1.);
 /// # Ok::<(),error::CIError>(())
 /// ```
 ///
-pub fn ci(confidence: Confidence, population: usize, successes: usize) -> CIResult<Interval<f64>> {
-    ci_wilson(confidence, population, successes)
+/// # References
+///
+/// * J. B. S. Haldane. "The estimation and significance of the logarithm of a ratio of frequencies." Annals of Human Genetics 20(4): 309-311 (1956).
+///
+pub fn odds_ratio_ci_haldane(
+    confidence: Confidence,
+    stats_a: &Stats,
+    stats_b: &Stats,
+) -> CIResult<Interval<f64>> {
+    odds_ratio_ci_with(confidence, stats_a, stats_b, 0.5)
+}
+
+fn odds_ratio_ci_with(
+    confidence: Confidence,
+    stats_a: &Stats,
+    stats_b: &Stats,
+    correction: f64,
+) -> CIResult<Interval<f64>> {
+    let a = stats_a.successes() as f64 + correction;
+    let b = stats_a.failures() as f64 + correction;
+    let c = stats_b.successes() as f64 + correction;
+    let d = stats_b.failures() as f64 + correction;
+
+    let log_ratio = (a * d / (b * c)).ln();
+    let std_err = (1. / a + 1. / b + 1. / c + 1. / d).sqrt();
+    let span = z_value(confidence) * std_err;
+    Interval::from_finite_bounds_for(
+        confidence,
+        (log_ratio - span).exp(),
+        (log_ratio + span).exp(),
+    )
 }
 
 ///
-/// Check if the conditions for the validity of the Wilson score interval are met.
-/// The conditions for the validity of hypothesis tests (from which the Wilson score is derived) are stated as follows:
-/// <https://www.itl.nist.gov/div898/handbook/prc/section2/prc24.htm>
-/// 1. The sample size is large enough to ensure that the sampling distribution of the sample proportion is approximately normal (N > 30)
-/// 2. The number of successes and failures are large enough to ensure that the sampling distribution of the sample proportion is approximately normal (x > 5 and n - x > 5)
+/// Computes the confidence interval on the relative risk (risk ratio) of two samples, i.e., the
+/// proportion of successes in the first sample divided by that in the second sample.
+///
+/// With \\( a \\) successes out of \\( n_a \\) in the first sample and \\( c \\) successes out of
+/// \\( n_c \\) in the second sample, the relative risk is estimated by \\( RR = \frac{a / n_a}{c / n_c} \\)
+/// and the interval is computed on its logarithm (Wald interval):
+/// \\[
+/// \exp\left( \ln RR \pm z \sqrt{\frac{1}{a} - \frac{1}{n_a} + \frac{1}{c} - \frac{1}{n_c}} \right)
+/// \\]
 ///
 /// # Arguments
 ///
-/// * `population` - the size of the population
-/// * `successes` - the number of successes in the sample
+/// * `confidence` - the confidence level
+/// * `stats_a` - the counts of the first sample
+/// * `stats_b` - the counts of the second (reference) sample
 ///
-/// # Returns
+/// # Errors
 ///
-/// `true` if the conditions are met, `false` otherwise.
+/// * [`CIError::EmptyCell`] - if either sample has no successes (see [`relative_risk_ci_haldane`])
 ///
 /// # Examples
 ///
 /// ```
-/// use stats_ci::*;
-/// assert!(proportion::is_significant(500, 10));
-/// assert!(! proportion::is_significant(10, 5));
-/// assert!(! proportion::is_significant(1000, 1));
+/// # use stats_ci::*;
+/// # use approx::*;
+/// let exposed = proportion::Stats::new(100, 20);
+/// let unexposed = proportion::Stats::new(100, 10);
+/// let ci = proportion::relative_risk_ci(Confidence::new_two_sided(0.95), &exposed, &unexposed)?;
+/// assert_abs_diff_eq!(ci, Interval::new(0.9866, 4.0545)?, epsilon = 1e-4);
+/// # Ok::<(),error::CIError>(())
 /// ```
-pub fn is_significant(population: usize, successes: usize) -> bool {
-    // significance criteria for Wilson score intervals.
-    // see https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval#Wilson_score_interval
-    // The conditions for the validity of hypothesis tests (from which the Wilson score is derived) are stated as follows:
-    // https://www.itl.nist.gov/div898/handbook/prc/section2/prc24.htm
-    // 1. The sample size is large enough to ensure that the sampling distribution of the sample proportion is approximately normal (N > 30)
-    (population > 30)
-    // 2. The number of successes and failures are large enough to ensure that the sampling distribution of the sample proportion is approximately normal (x > 5 and n - x > 5)
-    && (successes > 5)
-    && (population - successes > 5)
+///
+/// # References
+///
+/// * [Wikipedia article on relative risk](https://en.wikipedia.org/wiki/Relative_risk#Inference)
+/// * R package `epitools`, function `riskratio.wald`
+///
+pub fn relative_risk_ci(
+    confidence: Confidence,
+    stats_a: &Stats,
+    stats_b: &Stats,
+) -> CIResult<Interval<f64>> {
+    check_cells(&[
+        (stats_a.successes(), "no successes in the first sample"),
+        (stats_b.successes(), "no successes in the second sample"),
+    ])?;
+    relative_risk_ci_with(confidence, stats_a, stats_b, 0.)
 }
 
 ///
-/// Computes the (two sided) confidence interval over the proportion of successes a given sample using the Wilson score interval.
-/// This is the method used by default when calling the function [`ci`] of this module.
+/// Computes the confidence interval on the relative risk of two samples as [`relative_risk_ci`],
+/// with the Haldane-Anscombe correction: 0.5 is added to each of the four counts of the 2x2 table
+/// (i.e., 0.5 to the successes and 1 to the population of each sample), such that the interval is
+/// also defined when a sample has no successes.
 ///
-/// # Arguments
+pub fn relative_risk_ci_haldane(
+    confidence: Confidence,
+    stats_a: &Stats,
+    stats_b: &Stats,
+) -> CIResult<Interval<f64>> {
+    relative_risk_ci_with(confidence, stats_a, stats_b, 0.5)
+}
+
+fn relative_risk_ci_with(
+    confidence: Confidence,
+    stats_a: &Stats,
+    stats_b: &Stats,
+    correction: f64,
+) -> CIResult<Interval<f64>> {
+    let a = stats_a.successes() as f64 + correction;
+    let n_a = stats_a.population() as f64 + 2. * correction;
+    let c = stats_b.successes() as f64 + correction;
+    let n_c = stats_b.population() as f64 + 2. * correction;
+
+    let log_ratio = (a / n_a / (c / n_c)).ln();
+    let std_err = (1. / a - 1. / n_a + 1. / c - 1. / n_c).sqrt();
+    let span = z_value(confidence) * std_err;
+    Interval::from_finite_bounds_for(
+        confidence,
+        (log_ratio - span).exp(),
+        (log_ratio + span).exp(),
+    )
+}
+
 ///
-/// * `confidence` - the confidence level (must be in (0, 1))
-/// * `population` - the size of the population
-/// * `successes` - the number of successes in the sample
+/// Tests whether the proportion of successes (e.g., errors) of a candidate is not worse than that
+/// of a baseline by more than a margin, i.e., whether \\( p_{candidate} - p_{baseline} < \delta \\)
+/// for the margin \\( \delta \\) (non-inferiority test on a difference of proportions).
 ///
-/// # Errors
+/// The test is based on the one-sided upper bound of the confidence interval on the difference
+/// \\( p_{candidate} - p_{baseline} \\), computed with Newcombe's hybrid score method from the Wilson
+/// score intervals of both proportions at the one-sided level: with \\( \hat{p}_1 \\) (resp.
+/// \\( \hat{p}_2 \\)) the proportion of the candidate (resp. baseline), \\( u_1 \\) the upper bound of
+/// the interval on \\( p_1 \\), and \\( l_2 \\) the lower bound of that on \\( p_2 \\), the bound is
+/// \\[
+/// \hat{p}_1 - \hat{p}_2 + \sqrt{(u_1 - \hat{p}_1)^2 + (\hat{p}_2 - l_2)^2}
+/// \\]
+/// The candidate is non-inferior if the bound is strictly smaller than the margin.
+/// Unlike the Wald interval, the bound remains well-behaved when a sample has no successes.
 ///
-/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
-/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
-/// * `InvalidSuccesses` - if the number of successes is larger than the population size
-/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// # Arguments
 ///
-/// # Notes
+/// * `confidence` - the confidence level; only the level is used and the kind (two-sided or one-sided)
+///   is ignored, since the test is always one-sided (the interval \\( (-\infty, bound] \\) of the difference)
+/// * `baseline` - the counts of the baseline
+/// * `candidate` - the counts of the candidate
+/// * `margin` - the largest acceptable increase of the proportion, as a fraction (e.g., 0.002 for
+///   0.2 percentage points); must be in (0, 1)
 ///
-/// This method is based on the Wilson score interval, which is a modification of the normal approximation interval.
-/// It is more robust than the normal approximation interval, but it is also more conservative.
-/// In particular, it is more conservative when the sample size is small.
-/// It is also more conservative when the sample size is large and the proportion is close to 0 or 1.
+/// # Errors
 ///
-/// Using the Wilson score interval, the probability of success \\( p \\) is estimated by:
-/// \\[
-/// p \approx  \frac{n_S+\frac{1}{2}z^2}{n+z^2} \pm \frac{z}{n+z^2} \sqrt{\frac{n_S ~ n_F}{n}+\frac{z^2}{4}}
-/// \\]
-/// where
-/// * \\( n_S \\) is the number of successes,
-/// * \\( n_F \\) is the number of failures,
-/// * \\( n = n_S + n_F \\) is the sample size, and
-/// * \\( z \\) is the z-value corresponding to the confidence level.
+/// * [`CIError::InvalidMargin`] - if the margin is not in (0, 1)
+/// * [`CIError::TooFewSamples`] - if either population is empty
 ///
-/// The conditions for the validity of the Wilson score interval can be checked with the function [`is_significant`].
-/// However, the significance check for this function is much more permissive. It is the caller's responsibility to check for the stricter conditions for statistical significance if necessary.
-/// One advantage of using the Wilson score interval is that it is still reasonably accurate for small sample sizes and when the proportion of successes is close to 0 or 1.
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // release gate: the error rate of the new build may be at most 0.2 percentage points
+/// // worse than that of the baseline, at 95% confidence
+/// let baseline = proportion::Stats::new(10_000, 89);
+/// let candidate = proportion::Stats::new(11_000, 112);
+/// let confidence = Confidence::new(0.95);
+/// let result = proportion::non_inferiority(confidence, &baseline, &candidate, 0.002)?;
+/// # use approx::*;
+/// assert_abs_diff_eq!(result.difference, 0.00128, epsilon = 1e-5);
+/// assert_abs_diff_eq!(result.bound, 0.00349, epsilon = 1e-5);
+/// assert_eq!(result.headroom, result.margin - result.bound);
+/// assert!(!result.non_inferior);
+/// assert_eq!(
+///     result.to_string(),
+///     "difference (candidate - baseline): +0.128 pp, upper bound +0.349 pp (lower 95%) \
+///      vs. margin 0.200 pp: inferior (headroom -0.149 pp)"
+/// );
+///
+/// // the same data passes a gate of 0.4 percentage points
+/// let result = proportion::non_inferiority(confidence, &baseline, &candidate, 0.004)?;
+/// assert!(result.non_inferior);
+/// # Ok::<(),error::CIError>(())
+/// ```
 ///
 /// # References
 ///
-/// * [Wikipedia article on Wilson score interval](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval#Wilson_score_interval)
-/// * Francis J. DiTraglia. [Blog post: The Wilson Confidence Interval for a Proportion](https://www.econometrics.blog/post/the-wilson-confidence-interval-for-a-proportion/). Feb 2022.
+/// * R. G. Newcombe. "Interval Estimation for the Difference Between Independent Proportions: Comparison of Eleven Methods." Statistics in Medicine, 17(8):873–890, 1998.
 ///
-pub fn ci_wilson(
+pub fn non_inferiority(
     confidence: Confidence,
-    population: usize,
-    successes: usize,
-) -> CIResult<Interval<f64>> {
-    if successes > population {
-        return Err(CIError::InvalidSuccesses(successes, population));
+    baseline: &Stats,
+    candidate: &Stats,
+    margin: f64,
+) -> CIResult<NonInferiorityResult> {
+    if !(margin > 0. && margin < 1.) {
+        return Err(CIError::InvalidMargin(margin));
+    }
+    for stats in [baseline, candidate] {
+        if stats.population() == 0 {
+            return Err(CIError::TooFewSamples(0));
+        }
     }
+    let confidence = Confidence::new_lower(confidence.level());
+    let z = z_value(confidence);
+    let p_candidate = candidate.success_rate();
+    let p_baseline = baseline.success_rate();
+    let (_, high_candidate) = wilson_bounds(z, candidate.population(), candidate.successes());
+    let (low_baseline, _) = wilson_bounds(z, baseline.population(), baseline.successes());
 
-    let n = population as f64;
-    let n_s = successes as f64;
-    let n_f = n - n_s;
+    let difference = p_candidate - p_baseline;
+    let span = (high_candidate - p_candidate).hypot(p_baseline - low_baseline);
+    let bound = difference + span;
+    let headroom = margin - bound;
+    Ok(NonInferiorityResult {
+        confidence,
+        difference,
+        bound,
+        margin,
+        headroom,
+        non_inferior: headroom > 0.,
+    })
+}
 
-    // conditions for statistical significance:
-    // n p > 5 and n (1 - p) > 5
-    // however, we are more permissive here and rely on the user to check for the stricter conditions for statistical significance.
-    if successes < 2 {
-        // too few successes for statistical significance
-        return Err(CIError::TooFewSuccesses(successes, population, n_s));
-    }
-    if population - successes < 2 {
-        // too few failures for statistical significance
-        return Err(CIError::TooFewFailures(
-            population - successes,
-            population,
-            n_f,
-        ));
-    }
+///
+/// Outcome of a non-inferiority test on a difference of proportions, as returned by
+/// [`non_inferiority`].
+///
+/// The [`Display`](core::fmt::Display) implementation renders the proportions in percentage points.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NonInferiorityResult {
+    /// The one-sided confidence used for the test.
+    pub confidence: Confidence,
 
-    let z = z_value(confidence);
-    let z_sq = z * z;
+    /// The observed difference of the proportions (candidate minus baseline).
+    pub difference: f64,
 
-    let mean = (n_s + z_sq / 2.) / (n + z_sq);
-    let span = (z / (n + z_sq)) * ((n_s * n_f / n) + (z_sq / 4.)).sqrt();
+    /// The upper bound of the one-sided interval of the difference (candidate minus baseline).
+    pub bound: f64,
+
+    /// The margin that the bound is tested against.
+    pub margin: f64,
 
-    match confidence {
-        Confidence::TwoSided(_) => Interval::new(mean - span, mean + span).map_err(|e| e.into()),
-        Confidence::UpperOneSided(_) => Interval::new(mean - span, 1.).map_err(|e| e.into()),
-        Confidence::LowerOneSided(_) => Interval::new(0., mean + span).map_err(|e| e.into()),
+    /// The amount by which the bound is below the margin (\\( margin - bound \\)); positive when
+    /// the candidate is non-inferior.
+    pub headroom: f64,
+
+    /// Whether the candidate is non-inferior to the baseline, i.e., whether the bound is smaller than the margin.
+    pub non_inferior: bool,
+}
+
+impl core::fmt::Display for NonInferiorityResult {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "difference (candidate - baseline): {:+.3} pp, upper bound {:+.3} pp ({}) \
+             vs. margin {:.3} pp: {} (headroom {:+.3} pp)",
+            self.difference * 100.,
+            self.bound * 100.,
+            self.confidence,
+            self.margin * 100.,
+            if self.non_inferior {
+                "non-inferior"
+            } else {
+                "inferior"
+            },
+            self.headroom * 100.,
+        )
     }
 }
 
 ///
-/// Computes the (two sided) confidence interval over the proportion of successes in a given sample using the Wilson score interval.
-/// This is the method used by default when calling the function [`ci`] of this module.
+/// Number of consecutive failure-free trials required to claim, with the given confidence, that
+/// the probability of failure is at most `max_failure_prob`.
+///
+/// This is the smallest \\( n \\) such that \\( (1 - p_0)^n \leq 1 - c \\), that is,
+/// \\( n = \left\lceil \frac{\ln(1 - c)}{\ln(1 - p_0)} \right\rceil \\), where \\( c \\) is the
+/// confidence level and \\( p_0 \\) is `max_failure_prob`. After \\( n \\) successful trials,
+/// the exact (Clopper-Pearson) one-sided upper bound on the probability of failure is at most
+/// \\( p_0 \\). For a confidence of 95%, this is the well-known "rule of three": \\( n \approx 3 / p_0 \\).
+///
+/// The bound is one-sided at the level of `confidence` (the kind of `confidence` is irrelevant).
 ///
 /// # Arguments
 ///
-/// * `confidence` - the confidence level (must be in (0, 1))
-/// * `population` - the size of the population
-/// * `success_rate` - the proportion of successes in the sample
+/// * `confidence` - the confidence level
+/// * `max_failure_prob` - the maximal probability of failure to demonstrate (must be in (0, 1))
 ///
 /// # Errors
 ///
-/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
-/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
-/// * `InvalidSuccesses` - if the number of successes is larger than the population size
-/// * `NonPositiveValue` - if the success rate is not in positive or null.
-/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * [`CIError::InvalidProbability`] - if `max_failure_prob` is not in (0, 1)
+/// * [`CIError::TooManyRequiredTrials`] - if the number of trials exceeds the range of `u64`
 ///
-/// # Notes
+/// # Examples
 ///
-/// This method is simply a front for [`ci_wilson`], which takes the number of successes as an argument.
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new(0.95);
+/// assert_eq!(proportion::required_trials_for_zero_failures(confidence, 0.01)?, 299);
+/// # Ok::<(),error::CIError>(())
+/// ```
 ///
-pub fn ci_wilson_ratio(
+/// # References
+///
+/// * [Wikipedia article on the rule of three](https://en.wikipedia.org/wiki/Rule_of_three_(statistics))
+///
+pub fn required_trials_for_zero_failures(
     confidence: Confidence,
-    population: usize,
-    success_rate: f64,
-) -> CIResult<Interval<f64>> {
-    if success_rate <= 0. {
-        return Err(CIError::NonPositiveValue(success_rate));
+    max_failure_prob: f64,
+) -> CIResult<u64> {
+    check_probability(max_failure_prob)?;
+    let trials = ((1. - confidence.level()).ln() / (-max_failure_prob).ln_1p()).ceil();
+    if trials >= u64::MAX as f64 {
+        return Err(CIError::TooManyRequiredTrials);
     }
-    let successes = (success_rate * population as f64) as usize;
-
-    ci_wilson(confidence, population, successes)
+    Ok(trials as u64)
 }
 
 ///
-/// Computes the confidence interval over the proportion of successes in a given sample using the normal approximation interval (Wald interval).
+/// Number of trials required to claim, with the given confidence, that the probability of failure
+/// is at most `max_failure_prob`, when up to `allowed_failures` failures may occur among them.
 ///
-/// Using the normal approximation interval (Wald method), the probability of success \\( p \\) is estimated by:
-/// \\[
-/// p \approx  \frac{n_S}{n} \pm z \sqrt{\frac{n_S ~ n_F}{n^3}} = \hat{p} \pm z \sqrt{\frac{\hat{p} ~ (1 - \hat{p})}{n}}
-/// \\]
-/// where
-/// * \\( n_S \\) is the number of successes,
-/// * \\( n_F \\) is the number of failures,
-/// * \\( n = n_S + n_F \\) is the sample size,
-/// * \\( z \\) is the z-value corresponding to the confidence level, and
-/// * \\( \hat{p} = \frac{n_S}{n} \\) is the estimated probability of success.
+/// This is the smallest \\( n \\) such that the exact (Clopper-Pearson) one-sided upper bound on
+/// the probability of failure, with `allowed_failures` failures in \\( n \\) trials, is at most
+/// \\( p_0 \\), i.e., such that the probability of observing at most `allowed_failures` failures
+/// when the probability of failure is \\( p_0 \\) is at most \\( 1 - c \\).
+/// With no allowed failures, this is the same as [`required_trials_for_zero_failures`].
+///
+/// The bound is one-sided at the level of `confidence` (the kind of `confidence` is irrelevant).
+///
+/// Complexity: \\( O(k \log n) \\), where \\( k \\) is `allowed_failures` and \\( n \\) the result.
 ///
 /// # Arguments
 ///
-/// * `confidence` - the confidence level (must be in (0, 1))
-/// * `population` - the size of the population
-/// * `successes` - the number of successes in the sample
+/// * `confidence` - the confidence level
+/// * `max_failure_prob` - the maximal probability of failure to demonstrate (must be in (0, 1))
+/// * `allowed_failures` - the number of failures that may occur during the trials
 ///
 /// # Errors
 ///
-/// * `TooFewSuccesses` - if the number of successes is too small to compute a confidence interval
-/// * `TooFewFailures` - if the number of failures is too small to compute a confidence interval
-/// * `InvalidSuccesses` - if the number of successes is larger than the population size
-/// * `InvalidConfidenceLevel` - if the confidence level is not in (0, 1)
+/// * [`CIError::InvalidProbability`] - if `max_failure_prob` is not in (0, 1)
+/// * [`CIError::TooManyRequiredTrials`] - if the number of trials exceeds the range of `u64`
 ///
-/// # Notes
+/// # Examples
 ///
-/// This method is based on the normal approximation interval.
-/// It is less robust than the Wilson score interval, but it is also less conservative.
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new(0.95);
+/// assert_eq!(proportion::required_trials(confidence, 0.01, 0)?, 299);
+/// assert_eq!(proportion::required_trials(confidence, 0.01, 1)?, 473);
+/// assert_eq!(proportion::required_trials(confidence, 0.01, 2)?, 628);
+/// # Ok::<(),error::CIError>(())
+/// ```
 ///
 /// # References
 ///
-/// * [Wikipedia article on normal approximation interval](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval#Normal_approximation_interval)
+/// * C. J. Clopper, E. S. Pearson. The use of confidence or fiducial limits illustrated in the case of the binomial. Biometrika, 26(4):404–413, 1934.
+/// * [NIST/SEMATECH e-Handbook of Statistical Methods, 8.3.1.2. Bayesian and non-Bayesian demonstration tests](https://www.itl.nist.gov/div898/handbook/apr/section3/apr312.htm)
 ///
-pub fn ci_z_normal(
+pub fn required_trials(
     confidence: Confidence,
-    population: usize,
-    successes: usize,
-) -> CIResult<Interval<f64>> {
-    if successes > population {
-        return Err(CIError::InvalidSuccesses(successes, population));
+    max_failure_prob: f64,
+    allowed_failures: u32,
+) -> CIResult<u64> {
+    if allowed_failures == 0 {
+        return required_trials_for_zero_failures(confidence, max_failure_prob);
     }
+    check_probability(max_failure_prob)?;
+    let log_alpha = (1. - confidence.level()).ln();
+    let is_enough =
+        |trials: u64| binomial_log_cdf(allowed_failures, trials, max_failure_prob) <= log_alpha;
 
-    let n = population as f64;
-    let x = successes as f64;
-    let p = x / n;
-    let q = 1. - p;
+    // with at most as many trials as allowed failures, the probability is one
+    let mut low = allowed_failures as u64;
+    let mut high = low + 1;
+    while !is_enough(high) {
+        low = high;
+        high = high.checked_mul(2).ok_or(CIError::TooManyRequiredTrials)?;
+    }
+    // invariant: `low` is not enough and `high` is
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if is_enough(middle) {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    Ok(high)
+}
 
-    if n * p < 10. {
-        // too few successes for statistical significance
-        return Err(CIError::TooFewSuccesses(successes, population, n * p));
+///
+/// Logarithm of the probability of at most `successes` successes in `trials` Bernoulli trials
+/// with probability `prob`, summed term by term in log space to avoid underflow.
+///
+fn binomial_log_cdf(successes: u32, trials: u64, prob: f64) -> f64 {
+    let n = trials as f64;
+    let log_odds = prob.ln() - (-prob).ln_1p();
+    let mut log_term = n * (-prob).ln_1p();
+    let mut log_cdf = log_term;
+    for i in 0..(successes as u64).min(trials) {
+        let i = i as f64;
+        log_term += ((n - i) / (i + 1.)).ln() + log_odds;
+        // log(exp(log_cdf) + exp(log_term))
+        let (max, min) = if log_cdf > log_term {
+            (log_cdf, log_term)
+        } else {
+            (log_term, log_cdf)
+        };
+        log_cdf = max + (min - max).exp().ln_1p();
     }
-    if n * q < 10. {
-        // too few failures for statistical significance
-        return Err(CIError::TooFewFailures(
-            population - successes,
-            population,
-            n * q,
-        ));
+    log_cdf
+}
+
+fn check_probability(prob: f64) -> CIResult<()> {
+    if prob > 0. && prob < 1. {
+        Ok(())
+    } else {
+        Err(CIError::InvalidProbability(prob))
     }
+}
 
-    let std_dev = (p * q / n).sqrt();
-    let z = z_value(confidence);
-    let mean = p;
-    let span = z * std_dev;
-    match confidence {
-        Confidence::TwoSided(_) => Interval::new(mean - span, mean + span).map_err(|e| e.into()),
-        Confidence::UpperOneSided(_) => Interval::new(mean - span, 1.).map_err(|e| e.into()),
-        Confidence::LowerOneSided(_) => Interval::new(0., mean + span).map_err(|e| e.into()),
+///
+/// Check that none of the given counts of a 2x2 table is zero.
+///
+fn check_cells(cells: &[(usize, &str)]) -> CIResult<()> {
+    match cells.iter().find(|(count, _)| *count == 0) {
+        Some((_, reason)) => Err(CIError::EmptyCell(reason.to_string())),
+        None => Ok(()),
     }
 }
 
@@ -678,16 +2489,131 @@ mod tests {
         assert_abs_diff_eq!(ci, Interval::new(0.81, 0.87)?, epsilon = 1e-2);
 
         let ci2 = proportion::ci(Confidence::UpperOneSided(0.975), population, successes)?;
-        assert_eq!(ci2.high_f(), 1.);
+        assert!(ci2.is_upper());
+        assert_eq!(ci2.high(), None);
         assert_abs_diff_eq!(ci2.low_f(), ci.low_f(), epsilon = 1e-2);
 
         let ci2 = proportion::ci(Confidence::LowerOneSided(0.975), population, successes)?;
-        assert_eq!(ci2.low_f(), 0.);
+        assert!(ci2.is_lower());
+        assert_eq!(ci2.low(), None);
         assert_abs_diff_eq!(ci2.high_f(), ci.high_f(), epsilon = 1e-2);
 
         Ok(())
     }
 
+    #[test]
+    fn test_one_sided_variants() -> CIResult<()> {
+        let (population, successes) = (500, 421);
+        for method in [ci_wilson, ci_z_normal] {
+            let ci = method(Confidence::new_two_sided(0.95), population, successes)?;
+            assert!(ci.is_two_sided());
+            let ci = method(Confidence::new_upper(0.95), population, successes)?;
+            assert!(ci.is_upper());
+            let ci = method(Confidence::new_lower(0.95), population, successes)?;
+            assert!(ci.is_lower());
+        }
+        let stats = Stats::new(population, successes);
+        assert!(stats.ci(Confidence::new_upper(0.95))?.is_upper());
+        assert!(stats.ci(Confidence::new_lower(0.95))?.is_lower());
+        Ok(())
+    }
+
+    #[test]
+    fn test_arcsine() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        // reference values: computed by hand as sin²(asin(√p) ± z/(2√n)), with z = 1.959964
+        for (population, successes, low, high) in [
+            (29, 10, 0.185793, 0.524191),
+            (20, 0, 0., 0.047255),
+            (20, 20, 0.952745, 1.),
+            (100, 3, 0.005778, 0.072219),
+        ] {
+            let ci = ci_arcsine(confidence, population, successes)?;
+            assert_abs_diff_eq!(ci, Interval::new(low, high)?, epsilon = 1e-6);
+        }
+
+        // transformed scale: centered on the transform, with a half-width of z/(2√n)
+        let ci = ci_arcsine_transformed(confidence, 20, 0)?;
+        assert_abs_diff_eq!(ci, Interval::new(-0.219131, 0.219131)?, epsilon = 1e-6);
+        let ci = ci_arcsine_transformed(confidence, 20, 20)?;
+        assert_abs_diff_eq!(ci, Interval::new(1.351666, 1.789927)?, epsilon = 1e-6);
+
+        // stays within [0, 1] where the Wald interval does not
+        let p: f64 = 0.03;
+        let wald_low = p - z_value(confidence) * (p * (1. - p) / 100.).sqrt();
+        assert!(wald_low < 0.);
+        for population in [10, 20, 50, 100, 1000] {
+            for successes in 0..=population.min(10) {
+                for s in [successes, population - successes] {
+                    let ci = ci_arcsine(confidence, population, s)?;
+                    assert!(ci.low_f() >= 0. && ci.high_f() <= 1.);
+                    assert!(ci.contains(&(s as f64 / population as f64)));
+                }
+            }
+        }
+
+        // one-sided
+        let ci = ci_arcsine(Confidence::new_upper(0.95), 100, 3)?;
+        assert!(ci.is_upper());
+        assert_abs_diff_eq!(ci.low_f(), 0.008411, epsilon = 1e-6);
+        let ci = ci_arcsine(Confidence::new_lower(0.95), 20, 20)?;
+        assert!(ci.is_lower());
+        assert_eq!(ci.high_f(), 1.);
+
+        assert_eq!(ci_arcsine(confidence, 0, 0), Err(CIError::TooFewSamples(0)));
+        assert_eq!(
+            ci_arcsine_transformed(confidence, 10, 11),
+            Err(CIError::InvalidSuccesses {
+                successes: 11,
+                population: 10
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_agresti_coull() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        // reference values: statsmodels, `proportion_confint(successes, population, method="agresti_coull")`
+        for (population, successes, low, high) in [
+            (29, 10, 0.198520, 0.527436),
+            (20, 0, 0., 0.189810),
+            (20, 20, 0.810190, 1.),
+            (10, 1, 0., 0.425968),
+        ] {
+            let ci = ci_agresti_coull(confidence, population, successes)?;
+            assert_abs_diff_eq!(ci, Interval::new(low, high)?, epsilon = 1e-6);
+        }
+
+        // same center as the Wilson score interval, but wider
+        let wilson = ci_wilson(confidence, 29, 10)?;
+        let agresti_coull = ci_agresti_coull(confidence, 29, 10)?;
+        assert_abs_diff_eq!(
+            wilson.low_f() + wilson.high_f(),
+            agresti_coull.low_f() + agresti_coull.high_f(),
+            epsilon = 1e-12
+        );
+        assert!(agresti_coull.includes(&wilson));
+
+        // one-sided
+        let ci = ci_agresti_coull(Confidence::new_lower(0.95), 20, 0)?;
+        assert!(ci.is_lower());
+        assert!(ci.high_f() < 0.1898);
+
+        assert_eq!(
+            ci_agresti_coull(confidence, 0, 0),
+            Err(CIError::TooFewSamples(0))
+        );
+        assert_eq!(
+            ci_agresti_coull(confidence, 10, 11),
+            Err(CIError::InvalidSuccesses {
+                successes: 11,
+                population: 10
+            })
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_proportion_ci_if() {
         let data = [
@@ -698,6 +2624,145 @@ mod tests {
         assert_abs_diff_eq!(ci, Interval::new(0.299, 0.701).unwrap(), epsilon = 1e-2);
     }
 
+    #[test]
+    fn test_required_trials() -> CIResult<()> {
+        // standard success-run tables (reliability demonstration)
+        let table = [
+            (0.95, 0.01, [299, 473, 628, 773]),
+            (0.9, 0.1, [22, 38, 52, 65]),
+            (0.95, 0.05, [59, 93, 124, 153]),
+            (0.99, 0.01, [459, 662, 838, 1001]),
+        ];
+        for (level, max_failure_prob, expected) in table {
+            let confidence = Confidence::new(level);
+            assert_eq!(
+                required_trials_for_zero_failures(confidence, max_failure_prob)?,
+                expected[0]
+            );
+            for (allowed_failures, &trials) in expected.iter().enumerate() {
+                assert_eq!(
+                    required_trials(confidence, max_failure_prob, allowed_failures as u32)?,
+                    trials
+                );
+            }
+        }
+        // the kind of confidence is irrelevant
+        assert_eq!(required_trials(Confidence::new_lower(0.95), 0.01, 1)?, 473);
+        assert_eq!(required_trials(Confidence::new_upper(0.95), 0.01, 1)?, 473);
+
+        // rule of three
+        let trials = required_trials_for_zero_failures(Confidence::new(0.95), 1e-6)?;
+        assert_relative_eq!(trials as f64, 3e6, max_relative = 2e-3);
+        // large numbers of trials and failures
+        let trials = required_trials(Confidence::new(0.95), 1e-9, 100)?;
+        assert_abs_diff_eq!(trials as f64, 1.18e11, epsilon = 1e9);
+
+        // errors
+        for max_failure_prob in [0., 1., -0.5, 1.5, f64::NAN] {
+            assert!(matches!(
+                required_trials_for_zero_failures(Confidence::new(0.95), max_failure_prob),
+                Err(CIError::InvalidProbability(_))
+            ));
+            assert!(matches!(
+                required_trials(Confidence::new(0.95), max_failure_prob, 2),
+                Err(CIError::InvalidProbability(_))
+            ));
+        }
+        // too many trials, with or without allowed failures
+        for allowed_failures in [0, 1, 5] {
+            assert_eq!(
+                required_trials(Confidence::new(0.95), 1e-300, allowed_failures),
+                Err(CIError::TooManyRequiredTrials)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_required_trials_monotonic() -> CIResult<()> {
+        let levels = [0.5, 0.8, 0.9, 0.95, 0.99, 0.999];
+        let probs = [0.2, 0.1, 0.05, 0.01, 0.001];
+        for allowed_failures in 0..6 {
+            for &prob in &probs {
+                // increasing with the confidence level
+                let trials = levels
+                    .iter()
+                    .map(|&level| required_trials(Confidence::new(level), prob, allowed_failures))
+                    .collect::<CIResult<Vec<_>>>()?;
+                assert!(trials.windows(2).all(|w| w[0] <= w[1]), "{:?}", trials);
+            }
+            for &level in &levels {
+                // increasing as the maximal probability of failure decreases
+                let trials = probs
+                    .iter()
+                    .map(|&prob| required_trials(Confidence::new(level), prob, allowed_failures))
+                    .collect::<CIResult<Vec<_>>>()?;
+                assert!(trials.windows(2).all(|w| w[0] < w[1]), "{:?}", trials);
+            }
+        }
+        // increasing with the number of allowed failures
+        for &level in &levels {
+            for &prob in &probs {
+                let trials = (0..10)
+                    .map(|k| required_trials(Confidence::new(level), prob, k))
+                    .collect::<CIResult<Vec<_>>>()?;
+                assert!(trials.windows(2).all(|w| w[0] < w[1]), "{:?}", trials);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_chunk() {
+        let data = (0..1_003).map(|i| i % 7 < 2).collect::<Vec<_>>();
+        let expected = Stats::from_iter(data.iter().copied());
+        for chunk_size in [1, 3, 64, 1_003] {
+            let mut stats = Stats::default();
+            for chunk in data.chunks(chunk_size) {
+                stats.append_chunk(chunk);
+            }
+            assert_eq!(stats, expected);
+
+            let mut chunks = data.chunks(chunk_size);
+            assert_eq!(Stats::from_chunked_source(|| chunks.next()), expected);
+        }
+    }
+
+    #[test]
+    fn test_extend_from_bitmap() {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1668);
+        for num_words in [0, 1, 2, 3, 17] {
+            let words = (0..num_words).map(|_| rng.gen::<u64>()).collect::<Vec<_>>();
+            let bits = (0..num_words * 64)
+                .map(|i| words[i / 64] >> (i % 64) & 1 == 1)
+                .collect::<Vec<_>>();
+            let mut valid_bits = vec![0, 1, 63, 64, 65, 127, 128, rng.gen_range(0..=bits.len())];
+            valid_bits.retain(|&valid_bits| valid_bits <= bits.len());
+            for valid_bits in valid_bits {
+                let mut stats = Stats::new(10, 3);
+                stats.extend_from_bitmap_u64(&words, valid_bits);
+                let mut expected = Stats::new(10, 3);
+                expected.extend_iter(bits[..valid_bits].iter().copied());
+                assert_eq!(stats, expected, "{valid_bits} bits of {num_words} words");
+            }
+        }
+
+        // the bits beyond the valid ones are ignored
+        let mut stats = Stats::default();
+        stats.extend_from_bitmap_u64(&[u64::MAX; 2], 65);
+        assert_eq!(stats, Stats::new(65, 65));
+        let mut stats = Stats::default();
+        stats.extend_from_bitmap_u64(&[u64::MAX, 0], 63);
+        assert_eq!(stats, Stats::new(63, 63));
+    }
+
+    #[test]
+    #[should_panic(expected = "must not exceed the size of the bitmap")]
+    fn test_extend_from_bitmap_too_many_bits() {
+        Stats::default().extend_from_bitmap_u64(&[u64::MAX; 2], 129);
+    }
+
     #[test]
     fn test_proportion_add() {
         let stats1 = proportion::Stats::new(100, 50);
@@ -710,6 +2775,102 @@ mod tests {
         assert_eq!(stats, proportion::Stats::new(300, 150));
     }
 
+    #[test]
+    fn test_proportion_batches() -> CIResult<()> {
+        let batches = [(60, 3), (0, 0), (45, 45), (120, 0), (75, 10)];
+
+        // equivalent boolean-by-boolean accumulation
+        let mut reference = proportion::Stats::default();
+        for &(population, successes) in &batches {
+            for i in 0..population {
+                if i < successes {
+                    reference.add_success();
+                } else {
+                    reference.add_failure();
+                }
+            }
+        }
+
+        let mut stats = proportion::Stats::default();
+        for &(population, successes) in &batches {
+            stats.add_batch(population, successes)?;
+        }
+        assert_eq!(stats, reference);
+
+        let mut stats = proportion::Stats::default();
+        stats.extend_batches(batches)?;
+        assert_eq!(stats, reference);
+        assert_eq!(
+            batches.into_iter().collect::<proportion::Stats>(),
+            reference
+        );
+
+        let counts = [(true, 58), (false, 242)];
+        let mut stats = proportion::Stats::default();
+        stats.extend_counts_if(&counts, |&x| x)?;
+        assert_eq!(stats, reference);
+
+        let mut stats = proportion::Stats::default();
+        for &(population, successes) in &batches {
+            stats.add_counts(successes, population - successes)?;
+        }
+        assert_eq!(stats, reference);
+        assert_eq!(proportion::Stats::try_from((300, 58))?, reference);
+        Ok(())
+    }
+
+    #[test]
+    fn test_proportion_invalid_batch() {
+        let mut stats = proportion::Stats::new(100, 10);
+        assert_eq!(
+            stats.add_batch(5, 6),
+            Err(CIError::InvalidSuccesses {
+                successes: 6,
+                population: 5
+            })
+        );
+        assert_eq!(stats, proportion::Stats::new(100, 10));
+
+        // none of the batches are added if one is invalid
+        assert_eq!(
+            stats.extend_batches([(10, 1), (5, 6), (10, 2)]),
+            Err(CIError::InvalidSuccesses {
+                successes: 6,
+                population: 5
+            })
+        );
+        assert_eq!(stats, proportion::Stats::new(100, 10));
+
+        // overflowing counts are rejected and leave the statistics unchanged
+        let overflow = Err(CIError::CountOverflow);
+        assert_eq!(stats.add_batch(usize::MAX, 0), overflow);
+        assert_eq!(stats.add_counts(usize::MAX - 50, 100), overflow);
+        assert_eq!(stats.add_counts(usize::MAX, 1), overflow);
+        assert_eq!(
+            stats.extend_batches([(10, 1), (usize::MAX - 100, 2)]),
+            overflow
+        );
+        assert_eq!(
+            stats.extend_counts_if(&[(true, 10), (false, usize::MAX - 100)], |&x| x),
+            overflow
+        );
+        assert_eq!(stats, proportion::Stats::new(100, 10));
+
+        assert_eq!(
+            proportion::Stats::try_from((5, 6)),
+            Err(CIError::InvalidSuccesses {
+                successes: 6,
+                population: 5
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_proportion_from_invalid_batches() {
+        let _ = [(10, 1), (5, 6)].into_iter().collect::<proportion::Stats>();
+    }
+
     #[test]
     fn test_main_example() -> CIResult<()> {
         let grades = [
@@ -737,4 +2898,437 @@ mod tests {
         let ci = proportion::ci(confidence, messages, losses).unwrap();
         println!("Loss rate less than: {}", ci);
     }
+
+    #[test]
+    fn test_odds_ratio_relative_risk() -> CIResult<()> {
+        // reference values computed with the Wald formulas of the R package `epitools`
+        // (`oddsratio.wald` and `riskratio.wald`), in arbitrary precision arithmetic
+        let confidence = Confidence::new_two_sided(0.95);
+
+        let stats_a = Stats::new(18, 12);
+        let stats_b = Stats::new(31, 2);
+        let ci = odds_ratio_ci(confidence, &stats_a, &stats_b)?;
+        assert_abs_diff_eq!(ci.low_f(), 5.110_695_577_009_915, epsilon = 1e-10);
+        assert_abs_diff_eq!(ci.high_f(), 164.556_856_758_046, epsilon = 1e-9);
+        let ci = relative_risk_ci(confidence, &stats_a, &stats_b)?;
+        assert_abs_diff_eq!(ci.low_f(), 2.600_475_493_091_589_6, epsilon = 1e-10);
+        assert_abs_diff_eq!(ci.high_f(), 41.060_866_776_650_31, epsilon = 1e-9);
+
+        let stats_a = Stats::new(257, 178);
+        let stats_b = Stats::new(2897, 1411);
+        let ci = odds_ratio_ci(confidence, &stats_a, &stats_b)?;
+        assert_abs_diff_eq!(ci.low_f(), 1.802_771_546_333_008_8, epsilon = 1e-10);
+        assert_abs_diff_eq!(ci.high_f(), 3.123_408_001_411_034_4, epsilon = 1e-10);
+        let ci = relative_risk_ci(confidence, &stats_a, &stats_b)?;
+        assert_abs_diff_eq!(ci.low_f(), 1.300_139_977_609_598_7, epsilon = 1e-10);
+        assert_abs_diff_eq!(ci.high_f(), 1.555_344_537_902_363, epsilon = 1e-10);
+
+        // zero cells
+        let stats_a = Stats::new(10, 0);
+        let stats_b = Stats::new(10, 5);
+        assert!(matches!(
+            odds_ratio_ci(confidence, &stats_a, &stats_b),
+            Err(CIError::EmptyCell(_))
+        ));
+        assert!(matches!(
+            relative_risk_ci(confidence, &stats_a, &stats_b),
+            Err(CIError::EmptyCell(_))
+        ));
+        assert!(matches!(
+            odds_ratio_ci(confidence, &stats_b, &Stats::new(10, 10)),
+            Err(CIError::EmptyCell(_))
+        ));
+        let ci = odds_ratio_ci_haldane(confidence, &stats_a, &stats_b)?;
+        assert_abs_diff_eq!(ci.low_f(), 0.002_203_072_073_967_129_3, epsilon = 1e-12);
+        assert_abs_diff_eq!(ci.high_f(), 1.029_278_035_403_465_3, epsilon = 1e-10);
+        let ci = relative_risk_ci_haldane(confidence, &stats_a, &stats_b)?;
+        assert_abs_diff_eq!(ci.low_f(), 0.005_686_257_833_282_944, epsilon = 1e-12);
+        assert_abs_diff_eq!(ci.high_f(), 1.453_409_791_153_612_2, epsilon = 1e-10);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_accessors() {
+        let stats = Stats::new(10, 8);
+        assert_eq!(stats.failures(), 2);
+        assert_eq!(stats.success_rate(), 0.8);
+        assert_eq!(stats.odds(), Some(4.));
+        assert_eq!(Stats::new(10, 10).odds(), None);
+        assert_eq!(Stats::new(10, 0).odds(), Some(0.));
+    }
+
+    #[test]
+    fn test_ci_wilson_ratio() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+
+        // out of range
+        for rate in [0., 1., -0.1, 1.5, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                ci_wilson_ratio(confidence, 100, rate),
+                Err(CIError::InvalidSuccessRate(_))
+            ));
+        }
+
+        // rounding to nearest rather than truncating (e.g., 9997.5 -> 9998 rather than 9997)
+        assert_eq!(
+            ci_wilson_ratio(confidence, 10_000, 0.99975)?,
+            ci_wilson(confidence, 10_000, 9_998)?
+        );
+        assert_eq!(
+            ci_wilson_ratio(confidence, 100, 0.029)?,
+            ci_wilson(confidence, 100, 3)?
+        );
+        assert_eq!(
+            ci_wilson_ratio(confidence, 100, 0.021)?,
+            ci_wilson(confidence, 100, 2)?
+        );
+        assert_eq!(
+            ci_wilson_ratio(confidence, 1_000, 0.4567)?,
+            ci_wilson(confidence, 1_000, 457)?
+        );
+        assert_eq!(
+            ci_wilson_ratio(confidence, 1_000, 0.4564)?,
+            ci_wilson(confidence, 1_000, 456)?
+        );
+        // halfway cases round away from zero
+        assert_eq!(
+            ci_wilson_ratio(confidence, 100, 0.025)?,
+            ci_wilson(confidence, 100, 3)?
+        );
+
+        // near 0: 0.005 * 100 rounds to a single success, which is too few
+        assert!(matches!(
+            ci_wilson_ratio(confidence, 100, 0.005),
+            Err(CIError::TooFewSuccesses {
+                successes: 1,
+                population: 100,
+                n_p: _
+            })
+        ));
+        assert!(matches!(
+            ci_wilson_ratio(confidence, 100, 0.001),
+            Err(CIError::TooFewSuccesses {
+                successes: 0,
+                population: 100,
+                n_p: _
+            })
+        ));
+        // near 1: 0.999 * 100 rounds to 100 successes, hence no failures
+        assert!(matches!(
+            ci_wilson_ratio(confidence, 100, 0.999),
+            Err(CIError::TooFewFailures {
+                failures: 0,
+                population: 100,
+                n_q: _
+            })
+        ));
+        assert_eq!(
+            ci_wilson_ratio(confidence, 100, 0.98)?,
+            ci_wilson(confidence, 100, 98)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_design_effect() -> CIResult<()> {
+        // no clustering: identical to the plain interval
+        for (population, successes) in [(20, 7), (500, 421), (10_000, 3)] {
+            let stats = Stats::new(population, successes);
+            for confidence in [
+                Confidence::new_two_sided(0.95),
+                Confidence::new_upper(0.9),
+                Confidence::new_lower(0.99),
+            ] {
+                assert_eq!(
+                    stats.ci_with_design_effect(confidence, 1.)?,
+                    stats.ci(confidence)?
+                );
+            }
+        }
+
+        // the interval widens with the design effect, around the same proportion
+        let stats = Stats::new(2_000, 300);
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut previous = stats.ci(confidence)?;
+        for deff in [1.01, 1.5, 2., 5., 20., 100.] {
+            let interval = stats.ci_with_design_effect(confidence, deff)?;
+            assert!(interval.low_f() < previous.low_f(), "deff = {deff}");
+            assert!(interval.high_f() > previous.high_f(), "deff = {deff}");
+            assert!(interval.contains(&0.15));
+            previous = interval;
+        }
+
+        // errors
+        for deff in [0.99, 0., -1., f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                stats.ci_with_design_effect(confidence, deff),
+                Err(CIError::InvalidDesignEffect(_))
+            ));
+        }
+        assert_eq!(
+            Stats::default().ci_with_design_effect(confidence, 2.),
+            Err(CIError::TooFewSamples(0))
+        );
+        // 300 successes are fewer than 2 effective successes
+        assert!(matches!(
+            stats.ci_with_design_effect(confidence, 200.),
+            Err(CIError::TooFewSuccesses {
+                successes: 300,
+                population: 2_000,
+                n_p: _
+            })
+        ));
+        assert!(matches!(
+            Stats::new(100, 96).ci_with_design_effect(confidence, 2.5),
+            Err(CIError::TooFewFailures {
+                failures: 4,
+                population: 100,
+                n_q: _
+            })
+        ));
+
+        // design effect from the cluster sizes
+        assert_eq!(design_effect_from_cluster_sizes(&[1; 50], 0.3), 1.);
+        assert_eq!(design_effect_from_cluster_sizes(&[8, 12], 1.), 10.);
+        assert_eq!(design_effect_from_cluster_sizes(&[4, 4, 4, 8], 0.5), 3.);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ci_counted() -> CIResult<()> {
+        // data that counts the passes over it
+        struct Counting {
+            data: Vec<u32>,
+            passes: core::cell::Cell<usize>,
+        }
+        impl<'a> IntoIterator for &'a Counting {
+            type Item = &'a u32;
+            type IntoIter = core::slice::Iter<'a, u32>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.passes.set(self.passes.get() + 1);
+                self.data.iter()
+            }
+        }
+
+        let confidence = Confidence::new_two_sided(0.95);
+        let data = Counting {
+            data: (0..500).collect(),
+            passes: Default::default(),
+        };
+        let (interval, stats) = ci_if_counted(confidence, &data, |&x| x < 421)?;
+        assert_eq!(data.passes.get(), 1);
+        assert_eq!(stats, Stats::new(500, 421));
+        assert_eq!(interval, ci(confidence, 500, 421)?);
+        assert_eq!(interval, ci_if(confidence, &data.data, |&x| x < 421)?);
+
+        let bools = data.data.iter().map(|&x| x % 4 == 0).collect::<Vec<_>>();
+        let (interval, stats) = ci_true_counted(confidence, &bools)?;
+        assert_eq!(stats, Stats::new(500, 125));
+        assert_eq!(interval, ci_true(confidence, &bools)?);
+
+        assert_eq!(
+            ci_if_counted(confidence, &[1, 2, 3], |&x| x > 5),
+            Err(CIError::TooFewSuccesses {
+                successes: 0,
+                population: 3,
+                n_p: 0.
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_multinomial_goodman() -> CIResult<()> {
+        // reference values computed in python with the formula of Goodman (1965):
+        // ```python
+        // from mpmath import mp, mpf, sqrt, erfinv
+        // mp.dps = 30
+        // def goodman(counts, level, k):
+        //     n = sum(counts)
+        //     a = 2 * erfinv(1 - (1 - mpf(level)) / k) ** 2  # chi-squared quantile
+        //     return [((a + 2*c - sqrt(a*(a + 4*c*(n - c)/n))) / (2*(n + a)),
+        //              (a + 2*c + sqrt(a*(a + 4*c*(n - c)/n))) / (2*(n + a))) for c in counts]
+        // ```
+        let confidence = Confidence::new_two_sided(0.95);
+        let stats = MultinomialStats::from_counts(&[35, 74, 22, 69]);
+        let expected = [
+            (0.11801881106309044, 0.25164311225523656),
+            (0.289869718501908, 0.4579950508254228),
+            (0.06611447279517645, 0.17747983518681595),
+            (0.2668784298282068, 0.4324987951389953),
+        ];
+        for (ci, (low, high)) in stats.ci(confidence)?.iter().zip(expected) {
+            assert_abs_diff_eq!(*ci, Interval::new(low, high)?, epsilon = 1e-10);
+        }
+        let expected = [
+            (0.12295399412307378, 0.2429735452601726),
+            (0.29754155159241086, 0.4488294641608877),
+            (0.06965402254960028, 0.16945902471029536),
+            (0.2742884019349997, 0.4233078091554716),
+        ];
+        let intervals = stats.ci(Confidence::new_two_sided(0.9))?;
+        for (ci, (low, high)) in intervals.iter().zip(expected) {
+            assert_abs_diff_eq!(*ci, Interval::new(low, high)?, epsilon = 1e-10);
+        }
+
+        // with an empty category
+        let stats = MultinomialStats::from_counts(&[91, 49, 37, 43, 0]);
+        let expected = [
+            (0.3318619263381584, 0.5004675137881917),
+            (0.1591920153892851, 0.3024972397532071),
+            (0.11316628079678137, 0.24262577863603713),
+            (0.1359367371515341, 0.27280392013612126),
+            (0.0, 0.029275705994658004),
+        ];
+        let intervals = stats.ci(confidence)?;
+        assert_eq!(intervals.len(), 5);
+        for (ci, (low, high)) in intervals.iter().zip(expected) {
+            assert_abs_diff_eq!(*ci, Interval::new(low, high)?, epsilon = 1e-10);
+        }
+        let expected = [
+            (0.3505849874464605, 0.47965200171451744),
+            (0.17277151386965528, 0.28219987238401084),
+            (0.12454816756739973, 0.223204474998463),
+            (0.14846989936573654, 0.25289211504402787),
+            (0.0, 0.017161516195135618),
+        ];
+        let unadjusted = stats.ci_unadjusted(confidence)?;
+        for (ci, (low, high)) in unadjusted.iter().zip(expected) {
+            assert_abs_diff_eq!(*ci, Interval::new(low, high)?, epsilon = 1e-10);
+        }
+        // the unadjusted intervals are the Wilson intervals of each category
+        for (category, ci) in unadjusted.iter().enumerate().take(4) {
+            assert_abs_diff_eq!(
+                *ci,
+                stats.category_stats(category).ci(confidence)?,
+                epsilon = 1e-12
+            );
+        }
+
+        // one-sided intervals keep their kind
+        for ci in stats.ci(Confidence::new_upper(0.95))? {
+            assert!(ci.is_upper());
+        }
+        let lower = stats.ci(Confidence::new_lower(0.95))?;
+        assert!(lower.iter().all(|ci| ci.is_lower()));
+        assert!(lower[4].high_f() < intervals[4].high_f());
+        Ok(())
+    }
+
+    #[test]
+    fn test_multinomial_stats() -> CIResult<()> {
+        let mut stats = MultinomialStats::new(4);
+        assert_eq!(stats.categories(), 4);
+        assert_eq!(
+            stats.ci(Confidence::new_two_sided(0.95)),
+            Err(CIError::TooFewSamples(0))
+        );
+        stats.extend_from_labels([0, 1, 1, 3, 1])?;
+        assert_eq!(stats.counts(), &[1, 3, 0, 1]);
+        assert_eq!(stats.population(), 5);
+        assert_eq!(stats.share(1), 0.6);
+
+        // invalid categories leave the state unchanged
+        let invalid = || {
+            Err(CIError::InvalidCategory {
+                category: 4,
+                categories: 4,
+            })
+        };
+        assert_eq!(stats.add(4), invalid());
+        assert_eq!(stats.population(), 5);
+        // labels are added up to the first invalid one
+        assert_eq!(stats.extend_from_labels([2, 2, 4, 0]), invalid());
+        assert_eq!(stats, MultinomialStats::from_counts(&[1, 3, 2, 1]));
+
+        // merging
+        let other = MultinomialStats::from_counts(&[4, 0, 1, 2]);
+        assert_eq!(
+            stats.clone() + other.clone(),
+            MultinomialStats::from_counts(&[5, 3, 3, 3])
+        );
+        stats += other;
+        assert_eq!(stats.counts(), &[5, 3, 3, 3]);
+        assert_eq!(stats.population(), 14);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_multinomial_add_mismatched_categories() {
+        let _ = MultinomialStats::new(3) + MultinomialStats::new(4);
+    }
+
+    #[test]
+    fn test_non_inferiority() -> CIResult<()> {
+        // Newcombe (1998), example (a): 56/70 vs. 48/80, 95% two-sided interval (0.0524, 0.3339),
+        // whose upper bound is the one-sided bound at 97.5%
+        let candidate = Stats::new(70, 56);
+        let baseline = Stats::new(80, 48);
+        let result = non_inferiority(Confidence::new(0.975), &baseline, &candidate, 0.5)?;
+        assert_abs_diff_eq!(result.difference, 0.2, epsilon = 1e-12);
+        assert_abs_diff_eq!(result.bound, 0.3339, epsilon = 1e-4);
+        assert!(result.non_inferior);
+
+        // the verdict flips as the margin shrinks below the bound
+        let baseline = Stats::new(10_000, 89);
+        let candidate = Stats::new(11_000, 112);
+        let confidence = Confidence::new(0.95);
+        let bound = non_inferiority(confidence, &baseline, &candidate, 0.01)?.bound;
+        assert_abs_diff_eq!(bound, 0.0034946928, epsilon = 1e-6);
+        for (margin, non_inferior) in [
+            (0.01, true),
+            (0.004, true),
+            (bound + 1e-9, true),
+            (bound, false),
+            (0.003, false),
+            (0.002, false),
+            (0.0001, false),
+        ] {
+            let result = non_inferiority(confidence, &baseline, &candidate, margin)?;
+            assert_eq!(result.non_inferior, non_inferior, "margin {margin}");
+            assert_eq!(result.headroom > 0., non_inferior, "margin {margin}");
+            assert_eq!(result.margin, margin);
+            assert_eq!(result.bound, bound);
+        }
+
+        // only the level of the confidence matters
+        let expected = non_inferiority(confidence, &baseline, &candidate, 0.002)?;
+        assert_eq!(expected.confidence, Confidence::new_lower(0.95));
+        for confidence in [Confidence::new_upper(0.95), Confidence::new_lower(0.95)] {
+            assert_eq!(
+                non_inferiority(confidence, &baseline, &candidate, 0.002)?,
+                expected
+            );
+        }
+        // a higher confidence gives a larger bound
+        let strict = non_inferiority(Confidence::new(0.99), &baseline, &candidate, 0.002)?;
+        assert!(strict.bound > expected.bound);
+
+        // no errors in the candidate
+        let result = non_inferiority(confidence, &baseline, &Stats::new(1_000, 0), 0.002)?;
+        assert!(result.bound.is_finite());
+        assert!(result.difference < 0. && result.bound > result.difference);
+
+        // invalid arguments
+        for margin in [0., -0.002, 1., 2., f64::NAN, f64::INFINITY] {
+            assert!(
+                matches!(
+                    non_inferiority(confidence, &baseline, &candidate, margin),
+                    Err(CIError::InvalidMargin(_))
+                ),
+                "margin {margin}"
+            );
+        }
+        assert_eq!(
+            non_inferiority(confidence, &Stats::default(), &candidate, 0.002),
+            Err(CIError::TooFewSamples(0))
+        );
+        assert_eq!(
+            non_inferiority(confidence, &baseline, &Stats::default(), 0.002),
+            Err(CIError::TooFewSamples(0))
+        );
+        Ok(())
+    }
 }
@@ -31,9 +31,19 @@ use super::*;
 /// and `no_alloc` environments (unlike [`ci`]]).
 pub const DATA_CAP: usize = 1024;
 
+///
+/// Smallest rank (from either end of the sorted data) of a quantile for which an interval can be
+/// computed, i.e., the minimum number of successes and failures of [`proportion::ci_wilson`].
+///
+const MIN_RANK: usize = 2;
+
 ///
 /// Running statistics for quantiles
 ///
+/// The statistics only consist of the population size, from which the interval on the indices
+/// (ranks) of the sorted data is computed. Adding instances pools their population counts; see
+/// [`MergeableSketch`] for quantile intervals over data split across shards.
+///
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Stats {
     population: usize,
@@ -63,8 +73,17 @@ impl Stats {
     ///
     /// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
     /// * `InvalidQuantile` - if the quantile is not in the range [0, 1]
+    /// * `QuantileTooExtreme` - if the quantile is too close to 0 or 1 for the number of samples
+    ///   (see [`Self::supported_quantiles`])
     /// * `IndexError` - if the confidence interval falls outside the range of the data
     ///
+    /// # Notes
+    ///
+    /// The rank of the quantile is \\( \operatorname{round}(q n) \\) rather than the nearest-rank
+    /// convention \\( \lceil q n \rceil \\). Rounding treats both tails alike: the ranks of the
+    /// quantiles \\( q \\) and \\( 1 - q \\) mirror each other (barring ties at one half), so that
+    /// the intervals of, e.g., the 5th and the 95th percentiles are symmetric.
+    ///
     /// # Examples
     ///
     /// ```
@@ -88,28 +107,66 @@ impl Stats {
         }
 
         let successes = (quantile * self.population as f64).round() as usize;
+        if successes < MIN_RANK || self.population - successes < MIN_RANK {
+            let (min_supported, max_supported) = self.supported_quantiles();
+            return Err(error::CIError::QuantileTooExtreme {
+                quantile,
+                population: self.population,
+                min_supported,
+                max_supported,
+            });
+        }
         let proportion_ci = proportion::ci_wilson(confidence, self.population, successes)?;
 
-        let (low, high): (f64, f64) = proportion_ci.into();
+        let (low, high): (Option<f64>, Option<f64>) = proportion_ci.into();
 
-        if low < 0. {
+        if let Some(low) = low.filter(|&low| low < 0.) {
             // interval falls outside the range of the data
-            return Err(error::CIError::IndexError(low, self.population));
+            return Err(error::CIError::IndexError {
+                index: low,
+                len: self.population,
+            });
         }
 
-        if high > 1. {
+        if let Some(high) = high.filter(|&high| high > 1.) {
             // interval falls outside the range of the data
-            return Err(error::CIError::IndexError(high, self.population));
+            return Err(error::CIError::IndexError {
+                index: high,
+                len: self.population,
+            });
         }
 
-        let lo_index = self.index(low)?;
-        let hi_index = self.index(high)?;
+        let low = low.map(|low| self.index(low)).transpose()?;
+        let high = high.map(|high| self.index(high)).transpose()?;
+        Interval::<usize>::try_from((low, high)).map_err(|e| e.into())
+    }
 
-        match confidence {
-            Confidence::TwoSided(_) => Interval::new(lo_index, hi_index).map_err(|e| e.into()),
-            Confidence::UpperOneSided(_) => Ok(Interval::new_upper(lo_index)),
-            Confidence::LowerOneSided(_) => Ok(Interval::new_lower(hi_index)),
-        }
+    ///
+    /// Return the smallest and the largest quantiles for which [`Self::ci`] can compute an interval
+    /// with this population, i.e., the quantiles of rank 2 and \\( n - 2 \\) respectively.
+    ///
+    /// These are required by the Wilson score interval (see [`proportion::ci_wilson`]), which needs
+    /// at least two successes and two failures. If the population is less than 4, no quantile
+    /// is supported and the smallest quantile is larger than the largest one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = quantile::Stats::new(20);
+    /// assert_eq!(stats.supported_quantiles(), (0.1, 0.9));
+    /// let confidence = Confidence::new_two_sided(0.95);
+    /// assert!(stats.ci(confidence, 0.1).is_ok());
+    /// assert!(matches!(
+    ///     stats.ci(confidence, 0.02),
+    ///     Err(error::CIError::QuantileTooExtreme { min_supported, .. }) if min_supported == 0.1
+    /// ));
+    /// ```
+    ///
+    pub fn supported_quantiles(&self) -> (f64, f64) {
+        let n = self.population as f64;
+        let min_rank = MIN_RANK as f64;
+        (min_rank / n, (n - min_rank) / n)
     }
 
     ///
@@ -154,6 +211,14 @@ impl Stats {
     }
 }
 
+///
+/// Pool the population counts of two instances.
+///
+/// __Warning:__ this is only meaningful to obtain the total population before computing the
+/// index interval on the _whole_ data, sorted together. The index intervals computed on shards
+/// sorted separately do not combine into an interval on the whole data; use [`MergeableSketch`]
+/// to compute quantile intervals over sharded data.
+///
 impl core::ops::Add for Stats {
     type Output = Self;
 
@@ -172,6 +237,268 @@ impl core::ops::AddAssign for Stats {
     }
 }
 
+impl utils::Accumulator for Stats {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+///
+/// Verdict on a service-level objective (SLO), as returned by [`SloTracker::verdict`].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SloVerdict {
+    /// the objective is met with the requested confidence
+    Met,
+    /// the objective is not met with the requested confidence
+    NotMet,
+    /// there is not enough evidence to decide either way with the requested confidence
+    Inconclusive,
+}
+
+///
+/// Incremental accumulator to check a service-level objective (SLO) on a quantile, of the form
+/// "at least a proportion `target_quantile` of the observations are within `threshold`"
+/// (equivalently, "the `target_quantile`-quantile is at most `threshold`").
+///
+/// The tracker only counts the observations and those that are less or equal to the threshold,
+/// so it uses constant memory regardless of the number of observations. Trackers with the same
+/// threshold and target can be merged with `+` or `+=` (e.g., for sharded ingestion).
+///
+/// # Examples
+///
+/// "At least 95% of the messages are delivered within 1 second, with 90% confidence."
+///
+/// ```
+/// # use stats_ci::*;
+/// use stats_ci::quantile::{SloTracker, SloVerdict};
+/// let confidence = Confidence::new(0.9);
+///
+/// // delivery times (in seconds) of 2000 messages, of which 1930 (96.5%) arrive within 1 second
+/// let delivery_times = (0..2_000).map(|i| if i % 400 < 386 { 0.25 } else { 1.5 });
+/// let mut tracker = SloTracker::new(1.0, 0.95)?;
+/// tracker.extend_iter(delivery_times);
+/// assert_eq!(tracker.within_threshold(), 1_930);
+/// assert_eq!(tracker.verdict(confidence)?, SloVerdict::Met);
+///
+/// // with only 200 messages and the same proportion (193 within 1 second), there is not enough evidence
+/// let mut small = SloTracker::new(1.0, 0.95)?;
+/// small.extend_iter((0..200).map(|i| if i < 193 { 0.25 } else { 1.5 }));
+/// assert_eq!(small.verdict(confidence)?, SloVerdict::Inconclusive);
+///
+/// // a second shard with 1870 messages out of 2000 within 1 second clearly misses the objective...
+/// let mut shard = SloTracker::new(1.0, 0.95)?;
+/// shard.extend_iter((0..2_000).map(|i| if i < 1_870 { 0.25 } else { 1.5 }));
+/// assert_eq!(shard.verdict(confidence)?, SloVerdict::NotMet);
+///
+/// // ...and so does the merged result (3800 out of 4000, i.e., exactly 95%)
+/// let merged = tracker + shard;
+/// assert_eq!(merged.population(), 4_000);
+/// assert_eq!(merged.verdict(confidence)?, SloVerdict::Inconclusive);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SloTracker<T> {
+    threshold: T,
+    target_quantile: f64,
+    population: usize,
+    within_threshold: usize,
+}
+
+impl<T: PartialOrd> SloTracker<T> {
+    ///
+    /// Create a new tracker with no observations.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - the threshold that observations must not exceed
+    /// * `target_quantile` - the minimal proportion of observations within the threshold (must be in (0, 1))
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidQuantile` - if `target_quantile` is not in (0, 1)
+    /// * `InvalidInputData` - if `threshold` is not comparable with itself (e.g., NaN)
+    ///
+    pub fn new(threshold: T, target_quantile: f64) -> CIResult<Self> {
+        if !(0. < target_quantile && target_quantile < 1.) {
+            return Err(error::CIError::InvalidQuantile(target_quantile));
+        }
+        if threshold.partial_cmp(&threshold).is_none() {
+            return Err(error::CIError::InvalidInputData);
+        }
+        Ok(Self {
+            threshold,
+            target_quantile,
+            population: 0,
+            within_threshold: 0,
+        })
+    }
+
+    ///
+    /// Add an observation.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Notes
+    ///
+    /// An observation that cannot be compared with the threshold (e.g., NaN) is counted as
+    /// exceeding it.
+    ///
+    pub fn append(&mut self, value: T) {
+        self.population += 1;
+        if value <= self.threshold {
+            self.within_threshold += 1;
+        }
+    }
+
+    ///
+    /// Add all the observations of an iterator.
+    ///
+    /// Complexity: \\( O(n) \\) where \\( n \\) is the number of observations
+    ///
+    pub fn extend_iter<I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in values {
+            self.append(value);
+        }
+    }
+
+    ///
+    /// Add all the observations of an iterator.
+    ///
+    #[deprecated(
+        note = "use `extend_iter`; `extend` will take the observations by reference, as for the other accumulators"
+    )]
+    pub fn extend<I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.extend_iter(values)
+    }
+
+    ///
+    /// Return the threshold of the objective.
+    ///
+    pub fn threshold(&self) -> &T {
+        &self.threshold
+    }
+
+    ///
+    /// Return the target quantile of the objective.
+    ///
+    pub fn target_quantile(&self) -> f64 {
+        self.target_quantile
+    }
+
+    ///
+    /// Return the number of observations.
+    ///
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    ///
+    /// Return the number of observations less or equal to the threshold.
+    ///
+    pub fn within_threshold(&self) -> usize {
+        self.within_threshold
+    }
+
+    ///
+    /// Return the proportion of observations less or equal to the threshold, or NaN if there
+    /// are no observations.
+    ///
+    pub fn attainment(&self) -> f64 {
+        self.within_threshold as f64 / self.population as f64
+    }
+
+    ///
+    /// Decide whether the objective is met with the given confidence level.
+    ///
+    /// The decision is based on the one-sided Wilson score bounds of the proportion of
+    /// observations within the threshold, each at the confidence level of `confidence`
+    /// (the kind of `confidence` is irrelevant):
+    /// * [`SloVerdict::Met`] - if the lower bound is at least `target_quantile`,
+    /// * [`SloVerdict::NotMet`] - if the upper bound is less than `target_quantile`,
+    /// * [`SloVerdict::Inconclusive`] - otherwise.
+    ///
+    /// Unlike [`proportion::ci`], the bounds are computed even when all (or none) of the
+    /// observations are within the threshold.
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if there are no observations
+    ///
+    pub fn verdict(&self, confidence: Confidence) -> CIResult<SloVerdict> {
+        if self.population == 0 {
+            return Err(error::CIError::TooFewSamples(self.population));
+        }
+        // both one-sided bounds at the same level
+        let z = stats::z_value(Confidence::new_upper(confidence.level()));
+        let (low, high) = proportion::wilson_bounds(z, self.population, self.within_threshold);
+        Ok(if low >= self.target_quantile {
+            SloVerdict::Met
+        } else if high < self.target_quantile {
+            SloVerdict::NotMet
+        } else {
+            SloVerdict::Inconclusive
+        })
+    }
+}
+
+impl<T: PartialOrd> core::ops::Add for SloTracker<T> {
+    type Output = Self;
+
+    ///
+    /// Merge two trackers.
+    ///
+    /// # Panics
+    ///
+    /// * if the thresholds or the target quantiles of the two trackers differ
+    ///
+    #[inline]
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<T: PartialOrd> core::ops::AddAssign for SloTracker<T> {
+    ///
+    /// Merge another tracker into this one.
+    ///
+    /// # Panics
+    ///
+    /// * if the thresholds or the target quantiles of the two trackers differ
+    ///
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        assert!(
+            self.threshold == rhs.threshold && self.target_quantile == rhs.target_quantile,
+            "cannot merge SLO trackers with different objectives"
+        );
+        self.population += rhs.population;
+        self.within_threshold += rhs.within_threshold;
+    }
+}
+
+impl<T: PartialOrd + Clone> utils::Accumulator for SloTracker<T> {
+    fn reset(&mut self) -> Self {
+        let empty = Self {
+            threshold: self.threshold.clone(),
+            target_quantile: self.target_quantile,
+            population: 0,
+            within_threshold: 0,
+        };
+        core::mem::replace(self, empty)
+    }
+}
+
 ///
 /// Compute the confidence interval for a given quantile, assuming that the data is __already sorted__.
 /// This is the function to call if the data is known to be sorted,
@@ -226,16 +553,100 @@ where
 {
     assert!(quantile > 0. && quantile < 1.);
 
-    ci_indices(confidence, sorted.len(), quantile).and_then(|indices| match indices.into() {
-        (Some(lo), Some(hi)) => {
-            Interval::new(sorted[lo].clone(), sorted[hi].clone()).map_err(|e| e.into())
+    let (lo, hi): (Option<usize>, Option<usize>) =
+        ci_indices(confidence, sorted.len(), quantile)?.into();
+    Interval::<T>::try_from((
+        lo.map(|lo| sorted[lo].clone()),
+        hi.map(|hi| sorted[hi].clone()),
+    ))
+    .map_err(|e| e.into())
+}
+
+///
+/// Compute the confidence interval for a given quantile of data provided by an iterator
+/// __already in sorted order__, such as the keys or values of a [`BTreeMap`](std::collections::BTreeMap)
+/// or the lines of a sorted file.
+///
+/// The interval on the indices is computed as with [`ci_indices`] and the iterator is then walked
+/// once, keeping only the elements at the bounds of that interval. Unlike [`ci_sorted_unchecked`],
+/// this does not require the data to be indexable nor to be collected beforehand.
+///
+/// Complexity: \\( O(n) \\) where \\( n \\) is `len`.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `sorted_iter` - the sorted sample
+/// * `len` - the number of elements in the sample (only the first `len` elements of the iterator are considered)
+/// * `quantile` - the quantile to compute the confidence interval for (must be in (0, 1))
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
+/// * `InvalidQuantile` - if the quantile is not in (0, 1)
+/// * `QuantileTooExtreme` - if the quantile is too close to 0 or 1 for the number of samples
+/// * `IndexError` - if the iterator ends before reaching a bound of the interval
+/// * `IteratorTooShort` - if the iterator has fewer than `len` elements
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// use std::collections::BTreeMap;
+/// // response times (ms) keyed by request id
+/// let mut latencies = BTreeMap::new();
+/// for i in 1..=15 {
+///     latencies.insert(i * 10, format!("request {i}"));
+/// }
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = quantile::ci_of_sorted_iter(confidence, latencies.keys(), latencies.len(), 0.5)?;
+/// assert_eq!(interval, Interval::new(&50, &120)?);
+///
+/// let confidence = Confidence::new_upper(0.975);
+/// let interval = quantile::ci_of_sorted_iter(confidence, latencies.into_keys(), 15, 0.5)?;
+/// assert_eq!(interval, Interval::new_upper(50));
+/// # Ok::<(),error::CIError>(())
+/// ```
+pub fn ci_of_sorted_iter<T, I>(
+    confidence: Confidence,
+    sorted_iter: I,
+    len: usize,
+    quantile: f64,
+) -> CIResult<Interval<T>>
+where
+    T: PartialOrd + Clone,
+    I: IntoIterator<Item = T>,
+{
+    let (lo, hi): (Option<usize>, Option<usize>) = ci_indices(confidence, len, quantile)?.into();
+
+    let mut low = None;
+    let mut high = None;
+    let mut count = 0;
+    for (i, x) in sorted_iter.into_iter().take(len).enumerate() {
+        count += 1;
+        if Some(i) == lo {
+            low = Some(x.clone());
+        }
+        if Some(i) == hi {
+            high = Some(x);
+        }
+    }
+
+    for index in [lo, hi].into_iter().flatten() {
+        if index >= count {
+            return Err(error::CIError::IndexError {
+                index: index as f64,
+                len: count,
+            });
         }
-        (Some(lo), None) => Ok(Interval::new_upper(sorted[lo].clone())),
-        (None, Some(hi)) => Ok(Interval::new_lower(sorted[hi].clone())),
-        _ => Err(error::CIError::IntervalError(
-            interval::IntervalError::EmptyInterval,
-        )),
-    })
+    }
+    if count < len {
+        return Err(error::CIError::IteratorTooShort {
+            len: count,
+            expected: len,
+        });
+    }
+    Interval::<T>::try_from((low, high)).map_err(|e| e.into())
 }
 
 ///
@@ -301,6 +712,543 @@ where
     ci_sorted_unchecked(confidence, &sorted, quantile)
 }
 
+///
+/// Compute the confidence intervals for several quantiles of the same data, assuming that the
+/// data is __already sorted__ (see [`ci_sorted_unchecked`]).
+///
+/// Complexity: \\( O(k) \\) where \\( k \\) is the number of quantiles.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `sorted` - the sorted sample
+/// * `quantiles` - the quantiles to compute the confidence intervals for (each must be in (0, 1))
+///
+/// # Output
+///
+/// The intervals, in the order of `quantiles`.
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
+/// * `InvalidQuantile` - if some quantile is not in (0, 1)
+/// * `QuantileTooExtreme` - if some quantile is too close to 0 or 1 for the number of samples
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = (1..=1_000).collect::<Vec<_>>();
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let intervals = quantile::ci_many_sorted_unchecked(confidence, &data, &[0.5, 0.9, 0.99])?;
+/// assert_eq!(intervals[0], quantile::ci_sorted_unchecked(confidence, &data, 0.5)?);
+/// assert!(intervals[2].contains(&990));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci_many_sorted_unchecked<T>(
+    confidence: Confidence,
+    sorted: &[T],
+    quantiles: &[f64],
+) -> CIResult<Vec<Interval<T>>>
+where
+    T: PartialOrd + Clone,
+{
+    many_indices(confidence, sorted.len(), quantiles)?
+        .into_iter()
+        .map(|(lo, hi)| {
+            Interval::<T>::try_from((
+                lo.map(|lo| sorted[lo].clone()),
+                hi.map(|hi| sorted[hi].clone()),
+            ))
+            .map_err(|e| e.into())
+        })
+        .collect()
+}
+
+///
+/// Compute the confidence intervals for several quantiles of the same data at once.
+///
+/// Calling [`ci`] for each quantile sorts (a copy of) the data every time. Instead, the ranks of
+/// the bounds of all the intervals (two per quantile, see [`ci_indices`]) are computed first, and
+/// only the elements at these ranks are put in place, by successive partial selections on a
+/// single copy of the data. All quantiles are validated before the data is copied.
+///
+/// The intervals are the same as those returned by [`ci`] for each quantile.
+///
+/// Complexity: \\( O(n \log k) \\) where \\( n \\) is the number of samples and \\( k \\) the number of quantiles.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `data` - the sample data
+/// * `quantiles` - the quantiles to compute the confidence intervals for (each must be in (0, 1))
+///
+/// # Output
+///
+/// The intervals, in the order of `quantiles`.
+///
+/// # Errors
+///
+/// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
+/// * `InvalidQuantile` - if some quantile is not in (0, 1)
+/// * `QuantileTooExtreme` - if some quantile is too close to 0 or 1 for the number of samples
+///
+/// # Panics
+///
+/// * if the data contains elements that are not comparable (with their partial ordering).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // response times (ms)
+/// let data = (0..10_000_u64).map(|i| (i * 7_919) % 10_007).collect::<Vec<_>>();
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let percentiles = [0.5, 0.9, 0.99];
+/// let intervals = quantile::ci_many(confidence, &data, &percentiles)?;
+/// for (interval, &p) in intervals.iter().zip(&percentiles) {
+///     assert_eq!(*interval, quantile::ci(confidence, &data, p)?);
+/// }
+/// assert!(quantile::ci_many(confidence, &data, &[0.5, 1.5]).is_err());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// Notes:
+///
+/// This function is only available with the `std` feature enabled.
+#[cfg(any(test, feature = "std"))]
+pub fn ci_many<T, I>(
+    confidence: Confidence,
+    data: &I,
+    quantiles: &[f64],
+) -> CIResult<Vec<Interval<T>>>
+where
+    T: PartialOrd + Copy,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+{
+    let mut partitioned: Vec<T> = data.into_iter().copied().collect();
+    let indices = many_indices(confidence, partitioned.len(), quantiles)?;
+    let mut ranks = indices
+        .iter()
+        .flat_map(|&(lo, hi)| [lo, hi])
+        .flatten()
+        .collect::<Vec<_>>();
+    ranks.sort_unstable();
+    ranks.dedup();
+    select_ranks(&mut partitioned, 0, &ranks);
+    ci_many_sorted_unchecked(confidence, &partitioned, quantiles)
+}
+
+///
+/// Indices of the bounds of the intervals of each quantile (see [`ci_indices`]).
+///
+fn many_indices(
+    confidence: Confidence,
+    data_len: usize,
+    quantiles: &[f64],
+) -> CIResult<Vec<(Option<usize>, Option<usize>)>> {
+    quantiles
+        .iter()
+        .map(|&quantile| Ok(ci_indices(confidence, data_len, quantile)?.into()))
+        .collect()
+}
+
+///
+/// Rearrange `data` such that the element at each of the (sorted, distinct) `ranks` is the one
+/// that would be there if `data` were sorted, where `offset` is the rank of the first element of `data`.
+///
+/// Each selection splits the slice around the middle rank, so that the ranks on either side are
+/// selected within their own part.
+///
+#[cfg(any(test, feature = "std"))]
+fn select_ranks<T: PartialOrd>(data: &mut [T], offset: usize, ranks: &[usize]) {
+    if ranks.is_empty() {
+        return;
+    }
+    let middle = ranks.len() / 2;
+    let rank = ranks[middle] - offset;
+    let (left, _, right) = data.select_nth_unstable_by(rank, |a, b| a.partial_cmp(b).unwrap());
+    select_ranks(left, offset, &ranks[..middle]);
+    select_ranks(right, offset + rank + 1, &ranks[middle + 1..]);
+}
+
+///
+/// Compute the confidence interval for a given quantile of grouped (frequency) data, given as
+/// pairs of a value and its number of occurrences (e.g., the bins of a histogram).
+///
+/// The interval on the ranks is computed on the total count, as with [`ci`], and the ranks are
+/// then mapped back to values by walking the cumulative counts, without materializing the
+/// individual observations. The result is the same as calling [`ci`] on the expanded data.
+///
+/// Complexity: \\( O(k \log k) \\) where \\( k \\) is the number of pairs.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `pairs` - the values with their counts, in any order (values may appear more than once)
+/// * `quantile` - the quantile to compute the confidence interval for (must be in (0, 1))
+///
+/// # Errors
+///
+/// * `InvalidInputData` - if some count is zero
+/// * `TooFewSamples` - if the total count is too small to compute a confidence interval
+/// * `InvalidQuantile` - if the quantile is not in (0, 1)
+/// * `CountOverflow` - if the total count does not fit in a `usize`
+///
+/// # Panics
+///
+/// * if the values contain elements that are not comparable (with their partial ordering).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // response times (ms) with their number of occurrences
+/// let histogram = [(12, 2_000_000), (10, 1_500_000), (15, 700_000), (40, 3_000)];
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let interval = quantile::ci_weighted(confidence, &histogram, 0.99)?;
+/// assert_eq!(interval, Interval::new(15, 15)?);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// Notes:
+///
+/// This function is only available with the `std` feature enabled.
+#[cfg(any(test, feature = "std"))]
+pub fn ci_weighted<T>(
+    confidence: Confidence,
+    pairs: &[(T, u64)],
+    quantile: f64,
+) -> CIResult<Interval<T>>
+where
+    T: PartialOrd + Clone,
+{
+    if pairs.iter().any(|(_, count)| *count == 0) {
+        return Err(error::CIError::InvalidInputData);
+    }
+    let total = pairs
+        .iter()
+        .try_fold(0_u64, |total, (_, count)| total.checked_add(*count))
+        .and_then(|total| usize::try_from(total).ok())
+        .ok_or(error::CIError::CountOverflow)?;
+
+    let mut sorted: Vec<&(T, u64)> = pairs.iter().collect();
+    sorted.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+    // value of the observation at the given rank in the expanded data
+    let value_at = |rank: usize| {
+        let mut cumulative = 0_u64;
+        sorted
+            .iter()
+            .find(|(_, count)| {
+                cumulative += count;
+                cumulative > rank as u64
+            })
+            .map(|(value, _)| value.clone())
+            .expect("rank is within the total count")
+    };
+
+    let (lo, hi): (Option<usize>, Option<usize>) =
+        Stats::new(total).ci(confidence, quantile)?.into();
+    Interval::<T>::try_from((lo.map(value_at), hi.map(value_at))).map_err(|e| e.into())
+}
+
+///
+/// Mergeable summary of a sample, for confidence intervals on quantiles of data split across
+/// shards (e.g., threads, processes, or machines).
+///
+/// The index intervals obtained on separately sorted shards cannot be combined into an interval on
+/// the whole data. Instead, each shard builds a sketch, the sketches are merged with `+`, and the
+/// interval is computed once on the merged view with [`MergeableSketch::ci`].
+///
+/// A sketch is in one of two modes:
+/// * __exact__ ([`MergeableSketch::exact`]): every value is kept, and the interval is the same as
+///   with [`ci`] on the concatenated data;
+/// * __bounded__ ([`MergeableSketch::bounded`]): at most `capacity` order statistics are kept, each
+///   with the number of values that it stands for.
+///
+/// Merging an exact sketch with a bounded one yields a bounded sketch; merging two bounded sketches
+/// yields a sketch with the smaller of the two capacities.
+///
+/// # Approximation error (bounded mode)
+///
+/// When a bounded sketch holds more than `capacity` entries, adjacent entries are grouped into
+/// `capacity / 2` groups of (about) equal counts, each represented by its largest value.
+/// The bounds of the interval are then values of the sample whose rank (in the sorted concatenated
+/// data) exceeds the rank of the exact bounds by at most [`MergeableSketch::rank_error`].
+///
+/// This bound is tracked through compressions and merges: a compression adds the largest count of a group
+/// (minus the count of its largest entry), and a merge adds the bounds of both sketches.
+/// For a single sketch of \\( n \\) values, it is about \\( 2n / \text{capacity} \\); when merging
+/// \\( m \\) shards one after the other, it grows to about \\( m \\) times that.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = (0..1000).map(|i| (i * 7919) % 1000).collect::<Vec<_>>();
+/// let confidence = Confidence::new_two_sided(0.95);
+///
+/// // exact: same as the interval on the concatenated data
+/// let sketch = data
+///     .chunks(100)
+///     .map(|shard| quantile::MergeableSketch::exact_from(shard.iter().copied()))
+///     .fold(quantile::MergeableSketch::exact(), |a, b| a + b);
+/// assert_eq!(sketch.ci(confidence, 0.5)?, quantile::ci(confidence, &data, 0.5)?);
+///
+/// // bounded: the ranks of the bounds are off by at most `rank_error`
+/// let mut sketch = quantile::MergeableSketch::bounded(64)?;
+/// for shard in data.chunks(100) {
+///     sketch += quantile::MergeableSketch::exact_from(shard.iter().copied());
+/// }
+/// let exact = quantile::ci_indices(confidence, data.len(), 0.5)?;
+/// let approx = sketch.ci(confidence, 0.5)?;
+/// // the data is a permutation of 0..1000, so values are their own ranks
+/// let rank = approx.low_i() as usize;
+/// assert!(exact.low_u() <= rank && rank <= exact.low_u() + sketch.rank_error());
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// Notes:
+///
+/// This type is only available with the `std` feature enabled.
+///
+#[cfg(any(test, feature = "std"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeableSketch<T> {
+    /// values sorted in increasing order, with the number of values they stand for
+    entries: Vec<(T, usize)>,
+    count: usize,
+    capacity: Option<usize>,
+    rank_error: usize,
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<T: PartialOrd + Clone> MergeableSketch<T> {
+    ///
+    /// Create an empty sketch in exact mode (keeps every value).
+    ///
+    pub fn exact() -> Self {
+        Self {
+            entries: Vec::new(),
+            count: 0,
+            capacity: None,
+            rank_error: 0,
+        }
+    }
+
+    ///
+    /// Create a sketch in exact mode from the values of a shard.
+    ///
+    /// # Panics
+    ///
+    /// * if the values contain elements that are not comparable (with their partial ordering).
+    ///
+    pub fn exact_from<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let mut sketch = Self::exact();
+        sketch.extend_iter(values);
+        sketch
+    }
+
+    ///
+    /// Create an empty sketch in bounded mode, keeping at most `capacity` order statistics.
+    ///
+    /// # Errors
+    ///
+    /// * [`error::CIError::InvalidCapacity`] - if `capacity` is less than 2
+    ///
+    pub fn bounded(capacity: usize) -> CIResult<Self> {
+        if capacity < 2 {
+            return Err(error::CIError::InvalidCapacity(capacity));
+        }
+        Ok(Self {
+            capacity: Some(capacity),
+            ..Self::exact()
+        })
+    }
+
+    ///
+    /// Add a value to the sketch.
+    ///
+    /// # Panics
+    ///
+    /// * if the value is not comparable with the values of the sketch (e.g., NaN).
+    ///
+    pub fn append(&mut self, value: T) {
+        self.extend_iter(core::iter::once(value));
+    }
+
+    ///
+    /// Add values to the sketch.
+    ///
+    /// Complexity: \\( O((k+m) \log (k+m)) \\) where \\( k \\) is the number of entries of the sketch and \\( m \\) the number of values.
+    ///
+    /// # Panics
+    ///
+    /// * if the values contain elements that are not comparable (with their partial ordering).
+    ///
+    pub fn extend_iter<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        let before = self.entries.len();
+        self.entries
+            .extend(values.into_iter().map(|value| (value, 1)));
+        self.count += self.entries.len() - before;
+        self.normalize();
+    }
+
+    ///
+    /// Add values to the sketch.
+    ///
+    #[deprecated(
+        note = "use `extend_iter`; `extend` will take the values by reference, as for the other accumulators"
+    )]
+    pub fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        self.extend_iter(values)
+    }
+
+    ///
+    /// Number of values summarized by the sketch.
+    ///
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// Test whether the sketch summarizes no values.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    ///
+    /// Test whether the sketch is in exact mode.
+    ///
+    pub fn is_exact(&self) -> bool {
+        self.capacity.is_none()
+    }
+
+    ///
+    /// Maximal number of order statistics kept by the sketch (`None` in exact mode).
+    ///
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    ///
+    /// Upper bound on the difference between the rank of a bound computed from the sketch and the
+    /// rank of the corresponding exact bound (always zero in exact mode).
+    ///
+    pub fn rank_error(&self) -> usize {
+        self.rank_error
+    }
+
+    ///
+    /// Compute the confidence interval for a given quantile of the values summarized by the sketch.
+    ///
+    /// In exact mode, the result is the same as with [`ci`] on the concatenated data.
+    /// In bounded mode, the bounds are values of the sample whose rank exceeds that of the exact
+    /// bounds by at most [`MergeableSketch::rank_error`].
+    ///
+    /// # Errors
+    ///
+    /// * `TooFewSamples` - if the number of samples is too small to compute a confidence interval
+    /// * `InvalidQuantile` - if the quantile is not in (0, 1)
+    /// * `QuantileTooExtreme` - if the quantile is too close to 0 or 1 for the number of samples
+    /// * `IndexError` - if the confidence interval falls outside the range of the data
+    ///
+    pub fn ci(&self, confidence: Confidence, quantile: f64) -> CIResult<Interval<T>> {
+        // value of the entry that covers the given rank
+        let value_at = |rank: usize| {
+            let mut cumulative = 0;
+            self.entries
+                .iter()
+                .find(|(_, count)| {
+                    cumulative += count;
+                    cumulative > rank
+                })
+                .map(|(value, _)| value.clone())
+                .expect("rank is within the total count")
+        };
+        let (lo, hi): (Option<usize>, Option<usize>) =
+            Stats::new(self.count).ci(confidence, quantile)?.into();
+        Interval::<T>::try_from((lo.map(value_at), hi.map(value_at))).map_err(|e| e.into())
+    }
+
+    ///
+    /// Sort the entries and, in bounded mode, compress them down to the capacity.
+    ///
+    fn normalize(&mut self) {
+        // stable sort, so that merging is deterministic
+        self.entries
+            .sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        if self.entries.len() <= capacity {
+            return;
+        }
+        // groups of adjacent entries with (about) equal counts, represented by their largest value
+        let depth = self.count.div_ceil(capacity / 2);
+        let mut groups = Vec::with_capacity(capacity / 2 + 1);
+        let mut group_count = 0;
+        let mut added_error = 0;
+        let mut entries = core::mem::take(&mut self.entries).into_iter().peekable();
+        while let Some((value, count)) = entries.next() {
+            group_count += count;
+            if group_count >= depth || entries.peek().is_none() {
+                added_error = added_error.max(group_count - count);
+                groups.push((value, group_count));
+                group_count = 0;
+            }
+        }
+        self.entries = groups;
+        self.rank_error += added_error;
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<T: PartialOrd + Clone> Default for MergeableSketch<T> {
+    fn default() -> Self {
+        Self::exact()
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<T: PartialOrd + Clone> core::ops::Add for MergeableSketch<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<T: PartialOrd + Clone> core::ops::AddAssign for MergeableSketch<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.capacity = match (self.capacity, rhs.capacity) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.entries.extend(rhs.entries);
+        self.count += rhs.count;
+        self.rank_error += rhs.rank_error;
+        self.normalize();
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<T: PartialOrd + Clone> utils::Accumulator for MergeableSketch<T> {
+    fn reset(&mut self) -> Self {
+        let empty = Self {
+            capacity: self.capacity,
+            ..Self::exact()
+        };
+        core::mem::replace(self, empty)
+    }
+}
+
 ///
 /// Compute the confidence interval for a given quantile.
 /// Use [`ci_sorted_unchecked`] instead if the data is already sorted.
@@ -445,6 +1393,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ci_many() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1666);
+        let quantiles = [0.99, 0.5, 0.9, 0.5, 0.1, 0.25, 0.98];
+        for size in [1_000, 2_000, 10_007] {
+            // with many duplicates
+            let data = (0..size)
+                .map(|_| rng.gen_range(0..size / 4))
+                .collect::<Vec<_>>();
+            let mut sorted = data.clone();
+            sorted.sort();
+            for confidence in [
+                Confidence::new_two_sided(0.95),
+                Confidence::new_upper(0.9),
+                Confidence::new_lower(0.99),
+            ] {
+                let intervals = ci_many(confidence, &data, &quantiles)?;
+                let from_sorted = ci_many_sorted_unchecked(confidence, &sorted, &quantiles)?;
+                assert_eq!(intervals.len(), quantiles.len());
+                assert_eq!(intervals, from_sorted);
+                for (interval, &quantile) in intervals.iter().zip(&quantiles) {
+                    assert_eq!(*interval, ci(confidence, &data, quantile)?, "{quantile}");
+                }
+            }
+        }
+
+        // the selected ranks are in place
+        let data = (0..1_000).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+        let mut sorted = data.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let ranks = [0, 3, 4, 500, 998, 999];
+        let mut partitioned = data.clone();
+        select_ranks(&mut partitioned, 0, &ranks);
+        for rank in ranks {
+            assert_eq!(partitioned[rank], sorted[rank]);
+        }
+
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(ci_many(confidence, &data, &[])?, vec![]);
+        // all the quantiles are validated
+        assert_eq!(
+            ci_many(confidence, &data, &[0.5, 1.5]),
+            Err(error::CIError::InvalidQuantile(1.5))
+        );
+        assert_eq!(
+            ci_many_sorted_unchecked(confidence, &sorted, &[0., 0.5]),
+            Err(error::CIError::InvalidQuantile(0.))
+        );
+        assert!(matches!(
+            ci_many(confidence, &data[..10].to_vec(), &[0.5, 0.999]),
+            Err(error::CIError::QuantileTooExtreme { .. })
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_quantile_ci() -> CIResult<()> {
         let data = [
@@ -513,6 +1517,56 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_quantile_too_extreme() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        // smallest population from which each quantile can be answered
+        for (quantile, first_supported) in [
+            (0.02, 75),
+            (0.98, 76),
+            (0.05, 30),
+            (0.95, 31),
+            (0.1, 15),
+            (0.9, 16),
+            (0.25, 6),
+            (0.75, 7),
+        ] {
+            for population in 4..=200 {
+                let stats = Stats::new(population);
+                match stats.ci(confidence, quantile) {
+                    Ok(_) => assert!(
+                        population >= first_supported,
+                        "quantile {quantile} should not be supported with {population} samples"
+                    ),
+                    Err(error::CIError::QuantileTooExtreme {
+                        quantile: q,
+                        population: n,
+                        min_supported,
+                        max_supported,
+                    }) => {
+                        assert!(
+                            population < first_supported,
+                            "quantile {quantile} should be supported with {population} samples"
+                        );
+                        assert_eq!((q, n), (quantile, population));
+                        assert_eq!((min_supported, max_supported), stats.supported_quantiles());
+                        assert!(quantile < min_supported || max_supported < quantile);
+                    }
+                    Err(e) => panic!("unexpected error: {e}"),
+                }
+            }
+        }
+
+        // the supported quantiles are always answerable
+        for population in 4..=200 {
+            let stats = Stats::new(population);
+            let (min_supported, max_supported) = stats.supported_quantiles();
+            stats.ci(confidence, min_supported)?;
+            stats.ci(confidence, max_supported)?;
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_one_sided() {
         let data = [
@@ -537,6 +1591,20 @@ mod tests {
         let confidence = Confidence::new_lower(0.975);
         let interval = quantile::ci_indices(confidence, data.len(), quantile).unwrap();
         assert_eq!(interval, Interval::new_lower(11));
+
+        let stats = Stats::new(data.len());
+        assert!(stats
+            .ci(Confidence::new_two_sided(0.95), quantile)
+            .unwrap()
+            .is_two_sided());
+        assert!(stats
+            .ci(Confidence::new_upper(0.95), quantile)
+            .unwrap()
+            .is_upper());
+        assert!(stats
+            .ci(Confidence::new_lower(0.95), quantile)
+            .unwrap()
+            .is_lower());
     }
 
     #[test]
@@ -611,6 +1679,185 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ci_weighted() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1600);
+        let confidences = [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_two_sided(0.5),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ];
+        for _ in 0..200 {
+            let pairs: Vec<(i32, u64)> = (0..rng.gen_range(1..8))
+                .map(|_| (rng.gen_range(-5..20), rng.gen_range(1..30)))
+                .collect();
+            let expanded: Vec<i32> = pairs
+                .iter()
+                .flat_map(|&(value, count)| core::iter::repeat_n(value, count as usize))
+                .collect();
+            for confidence in confidences {
+                for quantile in [0.05, 0.25, 0.5, 0.9] {
+                    let weighted = ci_weighted(confidence, &pairs, quantile);
+                    let flat = ci(confidence, &expanded, quantile);
+                    assert_eq!(weighted.is_ok(), flat.is_ok(), "{pairs:?}");
+                    assert_eq!(weighted.ok(), flat.ok(), "{pairs:?}");
+                }
+            }
+        }
+
+        // a single dominant bin captures the whole interval
+        let histogram = [(3., 1), (1., 2), (2., 5_000_000), (5., 3)];
+        let confidence = Confidence::new_two_sided(0.99);
+        for quantile in [0.01, 0.5, 0.99] {
+            assert_eq!(
+                ci_weighted(confidence, &histogram, quantile)?,
+                Interval::new(2., 2.)?
+            );
+        }
+
+        // counts in the billions without expansion
+        let histogram = [(1, 4_000_000_000), (2, 6_000_000_000)];
+        assert_eq!(
+            ci_weighted(confidence, &histogram, 0.4)?,
+            Interval::new(1, 2)?
+        );
+
+        assert_eq!(
+            ci_weighted(confidence, &[(1, 10), (2, 0)], 0.5),
+            Err(error::CIError::InvalidInputData)
+        );
+        assert_eq!(
+            ci_weighted(confidence, &[(1, 2), (2, 1)], 0.5),
+            Err(error::CIError::TooFewSamples(3))
+        );
+        assert!(matches!(
+            ci_weighted(confidence, &[(1, u64::MAX), (2, 1)], 0.5),
+            Err(error::CIError::CountOverflow)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_mergeable_sketch_exact() -> CIResult<()> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1627);
+        let confidences = [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ];
+        for _ in 0..50 {
+            let shards: Vec<Vec<i32>> = (0..rng.gen_range(1..6))
+                .map(|_| {
+                    (0..rng.gen_range(0..50))
+                        .map(|_| rng.gen_range(-20..20))
+                        .collect()
+                })
+                .collect();
+            let concatenated: Vec<i32> = shards.concat();
+            let sketch = shards
+                .iter()
+                .map(|shard| MergeableSketch::exact_from(shard.iter().copied()))
+                .fold(MergeableSketch::default(), |a, b| a + b);
+            assert!(sketch.is_exact());
+            assert_eq!(sketch.len(), concatenated.len());
+            assert_eq!(sketch.rank_error(), 0);
+            for confidence in confidences {
+                for quantile in [0.05, 0.5, 0.9] {
+                    let merged = sketch.ci(confidence, quantile);
+                    let flat = ci(confidence, &concatenated, quantile);
+                    assert_eq!(merged.is_ok(), flat.is_ok(), "{shards:?}");
+                    assert_eq!(merged.ok(), flat.ok(), "{shards:?}");
+                }
+            }
+        }
+
+        let mut sketch = MergeableSketch::exact();
+        assert!(sketch.is_empty());
+        assert_eq!(
+            sketch.ci(Confidence::new(0.95), 0.5),
+            Err(error::CIError::TooFewSamples(0))
+        );
+        for x in [5., 1., 3., 2., 4.] {
+            sketch.append(x);
+        }
+        assert_eq!(
+            sketch.ci(Confidence::new(0.5), 0.5)?,
+            ci(Confidence::new(0.5), &[1., 2., 3., 4., 5.], 0.5)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_mergeable_sketch_bounded() -> CIResult<()> {
+        use rand::seq::SliceRandom;
+        use rand::SeedableRng;
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1627);
+        // a permutation of 0..n, so that values are their own ranks in the concatenated data
+        let n = 10_000;
+        let mut data: Vec<usize> = (0..n).collect();
+        data.shuffle(&mut rng);
+        let confidences = [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_upper(0.9),
+            Confidence::new_lower(0.99),
+        ];
+        for capacity in [16, 64, 256] {
+            for shard_size in [n / 20, n / 4, n] {
+                let mut sketch = MergeableSketch::bounded(capacity)?;
+                for shard in data.chunks(shard_size) {
+                    sketch += MergeableSketch::exact_from(shard.iter().copied());
+                    assert!(sketch.entries.len() <= capacity);
+                }
+                assert_eq!(sketch.len(), n);
+                assert_eq!(sketch.capacity(), Some(capacity));
+                // about 2n/capacity per shard merged
+                let shards = n.div_ceil(shard_size);
+                assert!(
+                    sketch.rank_error() <= shards * 2 * n / capacity,
+                    "{capacity}"
+                );
+                for confidence in confidences {
+                    for quantile in [0.05, 0.5, 0.9] {
+                        let exact = ci_indices(confidence, n, quantile)?;
+                        let approx = sketch.ci(confidence, quantile)?;
+                        for (exact, approx) in [
+                            (exact.left(), approx.left()),
+                            (exact.right(), approx.right()),
+                        ] {
+                            assert_eq!(exact.is_some(), approx.is_some());
+                            if let (Some(&rank), Some(&value)) = (exact, approx) {
+                                assert!(rank <= value, "{capacity} {shard_size}");
+                                assert!(
+                                    value <= rank + sketch.rank_error(),
+                                    "{capacity} {shard_size}"
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // a single compression of n values
+        let sketch =
+            MergeableSketch::exact_from(data.iter().copied()) + MergeableSketch::bounded(100)?;
+        assert!(!sketch.is_exact());
+        assert_eq!(sketch.rank_error(), n / 50 - 1);
+
+        // the smallest capacity is retained
+        let sketch = MergeableSketch::<f64>::bounded(100)? + MergeableSketch::bounded(10)?;
+        assert_eq!(sketch.capacity(), Some(10));
+
+        assert_eq!(
+            MergeableSketch::<f64>::bounded(1),
+            Err(error::CIError::InvalidCapacity(1))
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_proportion_add() {
         let stats1 = quantile::Stats::new(100);
@@ -622,4 +1869,133 @@ mod tests {
         stats += quantile::Stats::new(250);
         assert_eq!(stats, quantile::Stats::new(350));
     }
+
+    #[test]
+    fn test_slo_tracker() -> CIResult<()> {
+        let confidence = Confidence::new(0.9);
+
+        // 1930 of 2000: lower one-sided Wilson bound at 90% is about 0.9593
+        let mut tracker = SloTracker::new(1_000, 0.95)?;
+        tracker.extend_iter((0..2_000).map(|i| if i < 1_930 { 200 } else { 1_001 }));
+        assert_eq!(tracker.population(), 2_000);
+        assert_eq!(tracker.within_threshold(), 1_930);
+        assert_eq!(tracker.attainment(), 0.965);
+        assert_eq!(tracker.verdict(confidence)?, SloVerdict::Met);
+        // ... but not at 99.99% confidence
+        assert_eq!(
+            tracker.verdict(Confidence::new(0.9999))?,
+            SloVerdict::Inconclusive
+        );
+
+        // the threshold itself is within the objective
+        let mut tracker = SloTracker::new(1.0, 0.5)?;
+        tracker.extend_iter([1.0; 10]);
+        assert_eq!(tracker.verdict(confidence)?, SloVerdict::Met);
+
+        // no observation within the threshold (including NaN)
+        let mut tracker = SloTracker::new(1.0, 0.5)?;
+        tracker.extend_iter([2.0; 9]);
+        tracker.append(f64::NAN);
+        assert_eq!(tracker.within_threshold(), 0);
+        assert_eq!(tracker.verdict(confidence)?, SloVerdict::NotMet);
+
+        // merging
+        let mut sharded = SloTracker::new(10, 0.9)?;
+        sharded.extend_iter(0..10);
+        let mut other = SloTracker::new(10, 0.9)?;
+        other.extend_iter(5..15);
+        sharded += other;
+        assert_eq!(sharded.population(), 20);
+        assert_eq!(sharded.within_threshold(), 16);
+
+        // errors
+        assert!(matches!(
+            SloTracker::new(1.0, 1.0),
+            Err(error::CIError::InvalidQuantile(_))
+        ));
+        assert_eq!(
+            SloTracker::new(f64::NAN, 0.5),
+            Err(error::CIError::InvalidInputData)
+        );
+        assert_eq!(
+            SloTracker::new(1.0, 0.5)?.verdict(confidence),
+            Err(error::CIError::TooFewSamples(0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slo_tracker_merge_mismatch() {
+        let _ = SloTracker::new(1, 0.9).unwrap() + SloTracker::new(2, 0.9).unwrap();
+    }
+
+    #[test]
+    fn test_ci_of_sorted_iter() -> CIResult<()> {
+        use std::collections::BTreeMap;
+        use std::io::BufRead;
+
+        let data = [
+            8., 11., 12., 13., 15., 17., 19., 20., 21., 21., 22., 23., 25., 26., 28.,
+        ];
+        // same as with a slice
+        for confidence in [
+            Confidence::new_two_sided(0.95),
+            Confidence::new_two_sided(0.5),
+            Confidence::new_lower(0.975),
+            Confidence::new_upper(0.975),
+        ] {
+            for quantile in [0.25, 0.5, 0.9] {
+                let expected = ci_sorted_unchecked(confidence, &data, quantile);
+                let interval = ci_of_sorted_iter(confidence, data, data.len(), quantile);
+                assert_eq!(interval.ok(), expected.ok());
+            }
+        }
+
+        // values of a BTreeMap (sorted by key, and the values increase with the keys)
+        let map: BTreeMap<usize, f64> = data.iter().enumerate().map(|(i, &x)| (i, x)).collect();
+        let confidence = Confidence::new_two_sided(0.95);
+        let interval = ci_of_sorted_iter(confidence, map.values(), map.len(), 0.5)?;
+        assert_eq!(interval, Interval::new(&15., &23.)?);
+
+        // streaming source, e.g., a sorted file read line by line
+        let file = (1..=1_000)
+            .map(|i| format!("{}\n", i * 3))
+            .collect::<String>();
+        let lines = std::io::Cursor::new(file)
+            .lines()
+            .map(|line| line.unwrap().parse::<u64>().unwrap());
+        let interval = ci_of_sorted_iter(confidence, lines, 1_000, 0.5)?;
+        let expected = ci_indices(confidence, 1_000, 0.5)?;
+        assert_eq!(
+            interval,
+            Interval::new(
+                (expected.low().unwrap() as u64 + 1) * 3,
+                (expected.high().unwrap() as u64 + 1) * 3
+            )?
+        );
+
+        // iterator shorter than announced
+        assert_eq!(
+            ci_of_sorted_iter(confidence, data, 20, 0.5),
+            Err(error::CIError::IteratorTooShort {
+                len: 15,
+                expected: 20
+            })
+        );
+        assert!(matches!(
+            ci_of_sorted_iter(confidence, data, 100, 0.5),
+            Err(error::CIError::IndexError { index: _, len: 15 })
+        ));
+        // extra elements are ignored
+        assert_eq!(
+            ci_of_sorted_iter(confidence, data.iter().chain(&[100.]), 15, 0.5)?,
+            Interval::new(&15., &23.)?
+        );
+        assert_eq!(
+            ci_of_sorted_iter(confidence, data, 3, 0.5),
+            Err(error::CIError::TooFewSamples(3))
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,370 @@
+//!
+//! Confidence intervals over the rate of events occurring during a known exposure (e.g., duration).
+//!
+//! The number of events observed over an exposure \\( T \\) is assumed to follow a Poisson
+//! distribution with mean \\( \lambda T \\), where \\( \lambda \\) is the unknown rate.
+//! The intervals are exact (Garwood intervals), based on the relationship between the Poisson
+//! and the gamma (or equivalently chi-squared) distributions.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! // 532 errors observed over one day (in seconds)
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let interval = rate::ci(confidence, 532, 86_400.)?;
+//! # use approx::*;
+//! assert_abs_diff_eq!(interval, Interval::new(0.0056452, 0.0067036)?, epsilon = 1e-6);
+//!
+//! // upper bound on the error rate
+//! let confidence = Confidence::new_lower(0.95);
+//! let interval = rate::ci(confidence, 532, 86_400.)?;
+//! assert!(interval.is_lower());
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+//! # References
+//!
+//! * F. Garwood. Fiducial limits for the Poisson distribution. Biometrika, 28(3/4):437–442, 1936.
+//! * [Wikipedia - Poisson distribution (confidence interval)](https://en.wikipedia.org/wiki/Poisson_distribution#Confidence_interval)
+//!
+use super::*;
+use crate::stats::gamma_quantile;
+use error::*;
+
+///
+/// Represents the state of the computation of a confidence interval for a rate of events.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let mut stats = rate::Stats::default();
+/// stats.add_events(12, 3_600.)?;
+/// stats.add_events(7, 1_800.)?;
+/// assert_eq!(stats.events(), 19);
+/// assert_eq!(stats.exposure(), 5_400.);
+/// let interval = stats.ci(Confidence::new_two_sided(0.95))?;
+/// assert!(interval.contains(&stats.rate()));
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stats {
+    events: u64,
+    exposure: f64,
+}
+
+impl Stats {
+    ///
+    /// Creates a new statistics object with initial values for the number of events and the exposure.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub const fn new(events: u64, exposure: f64) -> Self {
+        Stats { events, exposure }
+    }
+
+    ///
+    /// Returns the number of events observed.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn events(&self) -> u64 {
+        self.events
+    }
+
+    ///
+    /// Returns the total exposure (e.g., duration of observation).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn exposure(&self) -> f64 {
+        self.exposure
+    }
+
+    ///
+    /// Returns the point estimate of the rate (number of events per unit of exposure).
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    pub fn rate(&self) -> f64 {
+        self.events as f64 / self.exposure
+    }
+
+    ///
+    /// Add a number of events observed over an additional exposure.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - the number of events observed
+    /// * `exposure` - the exposure (e.g., duration) over which they were observed
+    ///
+    /// # Errors
+    ///
+    /// * `CountOverflow` - if the total number of events does not fit in a `u64`, in which case
+    ///   the statistics are left unchanged
+    ///
+    pub fn add_events(&mut self, events: u64, exposure: f64) -> CIResult<()> {
+        *self = self.try_add(Stats::new(events, exposure))?;
+        Ok(())
+    }
+
+    ///
+    /// Combines two statistics objects by adding the number of events and the exposures.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Errors
+    ///
+    /// * `CountOverflow` - if the total number of events does not fit in a `u64`
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats = rate::Stats::new(10, 100.).try_add(rate::Stats::new(5, 50.))?;
+    /// assert_eq!(stats, rate::Stats::new(15, 150.));
+    /// assert!(stats.try_add(rate::Stats::new(u64::MAX, 1.)).is_err());
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    pub fn try_add(self, rhs: Self) -> CIResult<Self> {
+        let events = self
+            .events
+            .checked_add(rhs.events)
+            .ok_or(CIError::CountOverflow)?;
+        Ok(Stats {
+            events,
+            exposure: self.exposure + rhs.exposure,
+        })
+    }
+
+    ///
+    /// Computes the confidence interval over the rate of events.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Arguments
+    ///
+    /// * `confidence` - the confidence level (must be in (0, 1))
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidExposure` - if the exposure is not strictly positive and finite
+    ///
+    /// # Notes
+    ///
+    /// The confidence interval is computed using the function [`ci`].
+    ///
+    pub fn ci(&self, confidence: Confidence) -> CIResult<Interval<f64>> {
+        ci(confidence, self.events, self.exposure)
+    }
+}
+
+impl core::ops::Add for Stats {
+    type Output = Self;
+
+    ///
+    /// Combines two statistics objects by adding the number of events and the exposures.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Panics
+    ///
+    /// * if the total number of events does not fit in a `u64` (use [`Stats::try_add`] to handle
+    ///   it as an error)
+    ///
+    /// # Examples
+    /// ```
+    /// # use stats_ci::*;
+    /// let stats1 = rate::Stats::new(10, 100.);
+    /// let stats2 = rate::Stats::new(5, 50.);
+    /// let stats = stats1 + stats2;
+    /// assert_eq!(stats, rate::Stats::new(15, 150.));
+    /// ```
+    fn add(self, rhs: Self) -> Self::Output {
+        match self.try_add(rhs) {
+            Ok(stats) => stats,
+            Err(error) => panic!("{error}"),
+        }
+    }
+}
+
+impl core::ops::AddAssign for Stats {
+    ///
+    /// Combines two statistics objects by adding the number of events and the exposures.
+    ///
+    /// Complexity: \\( O(1) \\)
+    ///
+    /// # Panics
+    ///
+    /// * if the total number of events does not fit in a `u64` (use [`Stats::try_add`] to handle
+    ///   it as an error)
+    ///
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl utils::Accumulator for Stats {
+    fn reset(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+///
+/// Computes the exact confidence interval over the rate of events observed during a given exposure.
+///
+/// Complexity: \\( O(1) \\)
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level (must be in (0, 1))
+/// * `events` - the number of events observed
+/// * `exposure` - the exposure (e.g., duration) over which the events were observed
+///
+/// # Errors
+///
+/// * `InvalidExposure` - if the exposure is not strictly positive and finite
+///
+/// # Notes
+///
+/// With \\( k \\) events observed over an exposure \\( T \\), the bounds of the interval are:
+/// \\[
+/// \lambda_{low} = \frac{1}{T} G^{-1}_{k}(1-q) = \frac{\chi^2_{2k}(1-q)}{2T}
+/// \qquad
+/// \lambda_{high} = \frac{1}{T} G^{-1}_{k+1}(q) = \frac{\chi^2_{2k+2}(q)}{2T}
+/// \\]
+/// where \\( G^{-1}_{s} \\) is the quantile function of the gamma distribution with shape \\( s \\) and unit rate,
+/// and \\( q \\) is \\( 1 - \alpha/2 \\) for two-sided intervals or the confidence level for one-sided intervals.
+/// When no event is observed, the lower bound is zero.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// // no event observed over 1000 hours
+/// let interval = rate::ci(Confidence::new_two_sided(0.95), 0, 1_000.)?;
+/// # use approx::*;
+/// assert_eq!(interval.low_f(), 0.);
+/// assert_abs_diff_eq!(interval.high_f(), 3.689 / 1_000., epsilon = 1e-6);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn ci(confidence: Confidence, events: u64, exposure: f64) -> CIResult<Interval<f64>> {
+    if !(exposure > 0. && exposure.is_finite()) {
+        return Err(CIError::InvalidExposure(exposure));
+    }
+
+    let q = confidence.quantile();
+    let k = events as f64;
+    let low = if events == 0 {
+        0.
+    } else {
+        gamma_quantile(1. - q, k)? / exposure
+    };
+    let high = gamma_quantile(q, k + 1.)? / exposure;
+
+    Interval::from_finite_bounds_for(confidence, low, high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_exact_table() -> CIResult<()> {
+        // reference values: published tables of exact Poisson confidence limits
+        let confidence = Confidence::new_two_sided(0.95);
+        let table = [
+            (0, 0., 3.689),
+            (1, 0.0253, 5.572),
+            (2, 0.2422, 7.225),
+            (5, 1.624, 11.668),
+            (10, 4.795, 18.390),
+            (20, 12.217, 30.888),
+        ];
+        for (events, low, high) in table {
+            let interval = ci(confidence, events, 1.)?;
+            assert_abs_diff_eq!(interval, Interval::new(low, high)?, epsilon = 1e-3);
+
+            let interval = ci(confidence, events, 10.)?;
+            assert_abs_diff_eq!(
+                interval,
+                Interval::new(low / 10., high / 10.)?,
+                epsilon = 1e-4
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_one_sided() -> CIResult<()> {
+        // one-sided 95% upper limit for zero events is -ln(0.05) ≈ 2.996
+        let ci_lower = ci(Confidence::new_lower(0.95), 0, 1.)?;
+        assert!(ci_lower.is_lower());
+        assert_abs_diff_eq!(ci_lower.high_f(), -(0.05_f64).ln(), epsilon = 1e-6);
+
+        let ci_upper = ci(Confidence::new_upper(0.95), 0, 1.)?;
+        assert!(ci_upper.is_upper());
+        assert_eq!(ci_upper.low_f(), 0.);
+
+        // one-sided 97.5% bounds coincide with two-sided 95% bounds
+        let ci_two = ci(Confidence::new_two_sided(0.95), 10, 1.)?;
+        let ci_lower = ci(Confidence::new_lower(0.975), 10, 1.)?;
+        let ci_upper = ci(Confidence::new_upper(0.975), 10, 1.)?;
+        assert_abs_diff_eq!(ci_lower.high_f(), ci_two.high_f(), epsilon = 1e-10);
+        assert_abs_diff_eq!(ci_upper.low_f(), ci_two.low_f(), epsilon = 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_exposure() {
+        let confidence = Confidence::new_two_sided(0.95);
+        for exposure in [0., -1., f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                ci(confidence, 10, exposure),
+                Err(CIError::InvalidExposure(_))
+            ));
+        }
+        assert!(Stats::default().ci(confidence).is_err());
+    }
+
+    #[test]
+    fn test_stats() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut stats = Stats::default();
+        stats.add_events(300, 40_000.)?;
+        stats.add_events(232, 46_400.)?;
+        assert_eq!(stats, Stats::new(532, 86_400.));
+        assert_eq!(stats.ci(confidence)?, ci(confidence, 532, 86_400.)?);
+        assert_abs_diff_eq!(stats.rate(), 532. / 86_400., epsilon = 1e-12);
+
+        let mut summed = Stats::new(300, 40_000.);
+        summed += Stats::new(232, 46_400.);
+        assert_eq!(summed, stats);
+        assert_eq!(Stats::new(300, 40_000.) + Stats::new(232, 46_400.), stats);
+
+        // overflowing event counts are rejected and leave the statistics unchanged
+        assert_eq!(stats.add_events(u64::MAX, 1.), Err(CIError::CountOverflow));
+        assert_eq!(stats, Stats::new(532, 86_400.));
+        assert_eq!(
+            stats.try_add(Stats::new(u64::MAX - 531, 1.)),
+            Err(CIError::CountOverflow)
+        );
+        assert_eq!(
+            stats.try_add(Stats::new(u64::MAX - 532, 1.))?,
+            Stats::new(u64::MAX, 86_401.)
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_overflow() {
+        let _ = Stats::new(u64::MAX, 1.) + Stats::new(1, 1.);
+    }
+}
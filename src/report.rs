@@ -0,0 +1,473 @@
+//!
+//! Types bundling several confidence intervals computed on the same sample, meant for reporting.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! let baseline = mean::Arithmetic::from_iter(&[10.2, 9.8, 10.5, 10.1, 9.9, 10.3, 10.0, 9.7])?;
+//! let candidate = mean::Arithmetic::from_iter(&[8.1, 8.4, 7.9, 8.3, 8.0, 8.2, 8.5, 7.8])?;
+//!
+//! let confidence = Confidence::new_two_sided(0.95);
+//! let baseline = baseline.measurement_ci(confidence)?;
+//! let candidate = candidate.measurement_ci(confidence)?;
+//! println!("baseline:  {}", baseline);
+//! println!("candidate: {}", candidate);
+//!
+//! // relative change of the candidate w.r.t. the baseline
+//! let change = candidate.relative_to(&baseline)?;
+//! assert!(change.mean.high_f() < 0.); // significantly faster
+//! # Ok::<(),error::CIError>(())
+//! ```
+//!
+use super::*;
+use error::*;
+use num_traits::Float;
+
+///
+/// Confidence intervals on both the mean and the standard deviation of a sample, together with
+/// the number of samples and the confidence level used to compute them.
+///
+/// It is obtained through [`mean::Arithmetic::measurement_ci`].
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+/// let stats = mean::Arithmetic::from_iter(&data)?;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let measurement = stats.measurement_ci(confidence)?;
+/// assert_eq!(measurement.mean, stats.ci_mean(confidence)?);
+/// assert_eq!(measurement.std_dev, stats.ci_std_dev(confidence)?);
+/// assert_eq!(measurement.count, 10);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeasurementCi<F: Float> {
+    /// The confidence interval on the mean.
+    pub mean: Interval<F>,
+
+    /// The confidence interval on the standard deviation.
+    pub std_dev: Interval<F>,
+
+    /// The number of samples.
+    pub count: usize,
+
+    /// The confidence level used for both intervals.
+    pub confidence: Confidence,
+}
+
+impl<F: Float> MeasurementCi<F> {
+    ///
+    /// Relative change of this measurement with respect to a baseline measurement, expressed as
+    /// intervals on the ratios minus one (e.g., `-0.1` means 10% smaller than the baseline).
+    ///
+    /// The resulting intervals are conservative: they contain all the values \\( x / y - 1 \\)
+    /// for \\( x \\) in the interval of this measurement and \\( y \\) in that of the baseline.
+    /// The count and confidence of this measurement are retained in the result.
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::InvalidBaseline`] - if an interval of the baseline is not two-sided and strictly positive
+    ///
+    pub fn relative_to(&self, baseline: &MeasurementCi<F>) -> CIResult<MeasurementCi<F>> {
+        Ok(MeasurementCi {
+            mean: relative_change(&self.mean, &baseline.mean)?,
+            std_dev: relative_change(&self.std_dev, &baseline.std_dev)?,
+            count: self.count,
+            confidence: self.confidence,
+        })
+    }
+}
+
+impl<F: Float + core::fmt::Display> core::fmt::Display for MeasurementCi<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "mean: {}, std. dev.: {} (n: {}, {}% {})",
+            self.mean,
+            self.std_dev,
+            self.count,
+            self.confidence.percent(),
+            self.confidence.kind()
+        )
+    }
+}
+
+///
+/// Interval of the relative change \\( x / y - 1 \\) for \\( x \\) in `interval` and \\( y \\) in `baseline`.
+///
+fn relative_change<F: Float>(
+    interval: &Interval<F>,
+    baseline: &Interval<F>,
+) -> CIResult<Interval<F>> {
+    let (base_low, base_high) = match baseline {
+        Interval::TwoSided(low, high) if *low > F::zero() => (*low, *high),
+        _ => {
+            return Err(CIError::InvalidBaseline(
+                "the baseline must be a two-sided and strictly positive interval".to_string(),
+            ))
+        }
+    };
+    let min_ratio = |x: F| {
+        if x >= F::zero() {
+            x / base_high
+        } else {
+            x / base_low
+        }
+    };
+    let max_ratio = |x: F| {
+        if x >= F::zero() {
+            x / base_low
+        } else {
+            x / base_high
+        }
+    };
+    let low = min_ratio(interval.low_f()) - F::one();
+    let high = max_ratio(interval.high_f()) - F::one();
+    Interval::try_new_finite(low, high).map_err(|e| e.into())
+}
+
+///
+/// A confidence interval on the mean, with its metadata, as a flat record meant for export to
+/// tabular formats (e.g., for plotting). One-sided intervals have a missing (`None`) bound.
+///
+/// Records are created with [`Record::from_stats`] or [`Record::from_interval`], and exported
+/// with [`to_csv`] or [`to_json`].
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// let stats = mean::Arithmetic::from_iter(&[1., 2., 3., 4., 5.])?;
+/// let record = report::Record::from_stats("run 1", &stats, Confidence::new_upper(0.95))?;
+/// assert_eq!(record.estimate, 3.);
+/// assert!(record.low.is_some());
+/// assert_eq!(record.high, None);
+/// assert_eq!(record.kind, "upper one-sided");
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// This is only available with the `export` feature.
+///
+#[cfg(feature = "export")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Record {
+    /// The label identifying the record (e.g., the parameters of a run).
+    pub label: String,
+
+    /// The point estimate (e.g., the sample mean).
+    pub estimate: f64,
+
+    /// The lower bound of the interval, if any.
+    pub low: Option<f64>,
+
+    /// The upper bound of the interval, if any.
+    pub high: Option<f64>,
+
+    /// The confidence level, in the range (0, 1).
+    pub confidence: f64,
+
+    /// The kind of interval (see [`Confidence::kind`]).
+    pub kind: String,
+
+    /// The number of samples.
+    pub n: usize,
+}
+
+#[cfg(feature = "export")]
+impl Record {
+    ///
+    /// Names of the columns, in the order in which they are exported.
+    ///
+    pub const COLUMNS: [&'static str; 7] = [
+        "label",
+        "estimate",
+        "low",
+        "high",
+        "confidence",
+        "kind",
+        "n",
+    ];
+
+    ///
+    /// Create a record from the confidence interval on the mean of a statistics state.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - the label of the record
+    /// * `stats` - the statistics state
+    /// * `confidence` - the confidence level of the interval
+    ///
+    /// # Errors
+    ///
+    /// * errors from [`StatisticsOps::ci_mean`]
+    /// * [`CIError::FloatConversionError`] - if a value cannot be converted to `f64`
+    ///
+    pub fn from_stats<F: Float, S: StatisticsOps<F>>(
+        label: impl Into<String>,
+        stats: &S,
+        confidence: Confidence,
+    ) -> CIResult<Self> {
+        Self::from_interval(
+            label,
+            stats.sample_mean(),
+            &stats.ci_mean(confidence)?,
+            confidence,
+            stats.sample_count(),
+        )
+    }
+
+    ///
+    /// Create a record from an interval and its metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - the label of the record
+    /// * `estimate` - the point estimate
+    /// * `interval` - the confidence interval
+    /// * `confidence` - the confidence level of the interval
+    /// * `n` - the number of samples
+    ///
+    /// # Errors
+    ///
+    /// * [`CIError::FloatConversionError`] - if a value cannot be converted to `f64`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stats_ci::*;
+    /// let confidence = Confidence::new_two_sided(0.9);
+    /// let interval = Interval::new(1.5, 2.5)?;
+    /// let record = report::Record::from_interval("x", 2., &interval, confidence, 12)?;
+    /// assert_eq!((record.low, record.high), (Some(1.5), Some(2.5)));
+    /// assert_eq!(record.confidence, 0.9);
+    /// # Ok::<(),error::CIError>(())
+    /// ```
+    ///
+    pub fn from_interval<F: Float>(
+        label: impl Into<String>,
+        estimate: F,
+        interval: &Interval<F>,
+        confidence: Confidence,
+        n: usize,
+    ) -> CIResult<Self> {
+        let (low, high) = (*interval).into();
+        Ok(Self {
+            label: label.into(),
+            estimate: estimate.try_f64("estimate")?,
+            low: low.map(|low: F| low.try_f64("low")).transpose()?,
+            high: high.map(|high: F| high.try_f64("high")).transpose()?,
+            confidence: confidence.level(),
+            kind: confidence.kind().to_string(),
+            n,
+        })
+    }
+}
+
+///
+/// Export records as CSV, with a header row and the columns in the order of [`Record::COLUMNS`].
+/// Missing bounds are empty fields.
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// let interval = Interval::new(1.5, 2.5)?;
+/// let records = [
+///     report::Record::from_interval("a", 2., &interval, Confidence::new(0.95), 10)?,
+///     report::Record::from_interval("b", 2., &Interval::new_lower(2.5), Confidence::new_lower(0.95), 10)?,
+/// ];
+/// assert_eq!(
+///     report::to_csv(&records),
+///     "label,estimate,low,high,confidence,kind,n\n\
+///      a,2.0,1.5,2.5,0.95,two-sided,10\n\
+///      b,2.0,,2.5,0.95,lower one-sided,10\n"
+/// );
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// This is only available with the `export` feature.
+///
+#[cfg(feature = "export")]
+pub fn to_csv(records: &[Record]) -> String {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    // writing to memory cannot fail, and records only contain plain fields
+    writer
+        .write_record(Record::COLUMNS)
+        .expect("in-memory write");
+    for record in records {
+        writer.serialize(record).expect("in-memory write");
+    }
+    let bytes = writer.into_inner().expect("in-memory write");
+    String::from_utf8(bytes).expect("records are valid UTF-8")
+}
+
+///
+/// Export records as a JSON array of objects, with the fields in the order of
+/// [`Record::COLUMNS`]. Missing bounds are `null`.
+///
+/// # Examples
+///
+/// ```
+/// use stats_ci::*;
+/// let interval = Interval::new_upper(1.5);
+/// let record = report::Record::from_interval("a", 2., &interval, Confidence::new_upper(0.9), 10)?;
+/// assert_eq!(
+///     report::to_json(&[record]),
+///     r#"[{"label":"a","estimate":2.0,"low":1.5,"high":null,"confidence":0.9,"kind":"upper one-sided","n":10}]"#
+/// );
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+/// This is only available with the `export` feature.
+///
+#[cfg(feature = "export")]
+pub fn to_json(records: &[Record]) -> String {
+    serde_json::to_string(records).expect("records only contain plain fields")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_measurement_ci() -> CIResult<()> {
+        let data = [
+            10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4, 10.7,
+        ];
+        let stats = mean::Arithmetic::from_iter(&data)?;
+        for confidence in [
+            Confidence::new_two_sided(0.9),
+            Confidence::new_upper(0.95),
+            Confidence::new_lower(0.99),
+        ] {
+            let measurement = stats.measurement_ci(confidence)?;
+            assert_eq!(measurement.mean, stats.ci_mean(confidence)?);
+            assert_eq!(measurement.std_dev, stats.ci_std_dev(confidence)?);
+            assert_eq!(measurement.count, data.len());
+            assert_eq!(measurement.confidence, confidence);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_relative_to() -> CIResult<()> {
+        let confidence = Confidence::new_two_sided(0.95);
+        let baseline = MeasurementCi {
+            mean: Interval::new(10., 20.)?,
+            std_dev: Interval::new(1., 2.)?,
+            count: 10,
+            confidence,
+        };
+        let measurement = MeasurementCi {
+            mean: Interval::new(-5., 30.)?,
+            std_dev: Interval::new(2., 4.)?,
+            count: 12,
+            confidence,
+        };
+        let change = measurement.relative_to(&baseline)?;
+        assert_abs_diff_eq!(change.mean, Interval::new(-1.5, 2.)?);
+        assert_abs_diff_eq!(change.std_dev, Interval::new(0., 3.)?);
+        assert_eq!(change.count, 12);
+
+        let identity = baseline.relative_to(&baseline)?;
+        assert!(identity.mean.contains(&0.));
+        assert!(identity.std_dev.contains(&0.));
+
+        let one_sided = MeasurementCi {
+            mean: Interval::new_upper(15.),
+            ..measurement
+        };
+        let change = one_sided.relative_to(&baseline)?;
+        assert_abs_diff_eq!(change.mean, Interval::new_upper(-0.25));
+
+        assert!(matches!(
+            baseline.relative_to(&measurement),
+            Err(CIError::InvalidBaseline(_))
+        ));
+        assert!(matches!(
+            baseline.relative_to(&one_sided),
+            Err(CIError::InvalidBaseline(_))
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() -> CIResult<()> {
+        let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+        let stats = mean::Arithmetic::from_iter(&data)?;
+        for confidence in [Confidence::new_two_sided(0.95), Confidence::new_lower(0.9)] {
+            let measurement = stats.measurement_ci(confidence)?;
+            let serialized = toml::to_string(&measurement).unwrap();
+            assert!(serialized.contains("mean"));
+            assert!(serialized.contains("std_dev"));
+            assert!(serialized.contains("count"));
+            assert!(serialized.contains("confidence"));
+            let deserialized: MeasurementCi<f64> = toml::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, measurement);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "export")]
+    #[test]
+    fn test_export_round_trip() -> CIResult<()> {
+        let data = [
+            10.6, 6.6, 26.7, 0.4, 5.7, 0.3, 1.1, 5.0, 8.4, 1.4, 15.1, 0.3, 20.4, 1.2, 28.4, 10.7,
+        ];
+        let arithmetic = mean::Arithmetic::from_iter(&data)?;
+        let geometric = mean::Geometric::from_iter(&data)?;
+        let records = vec![
+            Record::from_stats("arithmetic", &arithmetic, Confidence::new_two_sided(0.95))?,
+            Record::from_stats("geometric, upper", &geometric, Confidence::new_upper(0.9))?,
+            Record::from_stats("\"quoted\" lower", &arithmetic, Confidence::new_lower(0.99))?,
+        ];
+        assert_eq!(records[0].estimate, arithmetic.sample_mean());
+        assert_eq!(
+            (records[0].low, records[0].high),
+            arithmetic.ci_mean(Confidence::new_two_sided(0.95))?.into()
+        );
+        assert_eq!(records[1].high, None);
+        assert_eq!(records[2].low, None);
+        assert_eq!(records[2].n, data.len());
+
+        let csv = to_csv(&records);
+        assert!(csv.starts_with("label,estimate,low,high,confidence,kind,n\n"));
+        let parsed = csv::Reader::from_reader(csv.as_bytes())
+            .deserialize()
+            .collect::<Result<Vec<Record>, _>>()
+            .unwrap();
+        assert_eq!(parsed, records);
+
+        let json = to_json(&records);
+        let parsed: Vec<Record> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, records);
+
+        assert_eq!(to_csv(&[]), "label,estimate,low,high,confidence,kind,n\n");
+        assert_eq!(to_json(&[]), "[]");
+        Ok(())
+    }
+
+    #[test]
+    fn test_display() -> CIResult<()> {
+        let measurement = MeasurementCi {
+            mean: Interval::new(10., 20.)?,
+            std_dev: Interval::new(1., 2.)?,
+            count: 10,
+            confidence: Confidence::new_two_sided(0.95),
+        };
+        assert_eq!(
+            measurement.to_string(),
+            "mean: [10, 20], std. dev.: [1, 2] (n: 10, 95% two-sided)"
+        );
+        Ok(())
+    }
+}
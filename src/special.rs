@@ -0,0 +1,648 @@
+//!
+//! Self-contained implementations of the quantile functions of the distributions used by the crate
+//! (normal, Student's t, gamma, and Fisher-Snedecor), used instead of [`statrs`](https://crates.io/crates/statrs)
+//! when the `statrs` feature is disabled or the `internal-quantiles` feature is enabled.
+//!
+//! The quantiles are obtained by refining an initial approximation with safeguarded Newton
+//! iterations on the cumulative distribution function, which is itself computed from the
+//! regularized incomplete gamma and beta functions. The results are accurate to about 1e-12.
+//!
+//! # References
+//!
+//! * P. J. Acklam. An algorithm for computing the inverse normal cumulative distribution function. 2003.
+//! * W. H. Press, S. A. Teukolsky, W. T. Vetterling, B. P. Flannery. Numerical Recipes: The Art of Scientific Computing (3rd edition), Chapter 6. Cambridge University Press, 2007.
+//! * C. Lanczos. A precision approximation of the gamma function. SIAM J. Numer. Anal. Ser. B, 1(1):86–96, 1964.
+//!
+/// Relative precision targeted by the iterative algorithms.
+const EPSILON: f64 = 1e-15;
+
+/// Smallest magnitude used to avoid divisions by zero in continued fractions.
+const TINY: f64 = 1e-300;
+
+/// Maximum number of iterations of the iterative algorithms.
+const MAX_ITERATIONS: usize = 10_000;
+
+///
+/// Natural logarithm of the gamma function for positive arguments (Lanczos approximation, \\( g = 7 \\)).
+///
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // reflection formula
+        let pi = core::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1. - x);
+    }
+    let x = x - 1.;
+    let t = x + G + 0.5;
+    let series = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .skip(1)
+        .fold(COEFFICIENTS[0], |sum, (i, c)| sum + c / (x + i as f64));
+    0.5 * (2. * core::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+///
+/// Regularized lower and upper incomplete gamma functions \\( (P(a, x), Q(a, x)) \\).
+///
+fn incomplete_gamma(a: f64, x: f64) -> (f64, f64) {
+    if x <= 0. {
+        return (0., 1.);
+    }
+    let prefactor = (a * x.ln() - x - ln_gamma(a)).exp();
+    if x < a + 1. {
+        // series expansion
+        let mut term = 1. / a;
+        let mut sum = term;
+        let mut n = a;
+        for _ in 0..MAX_ITERATIONS {
+            n += 1.;
+            term *= x / n;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        let p = sum * prefactor;
+        (p, 1. - p)
+    } else {
+        // continued fraction (modified Lentz's method)
+        let mut b = x + 1. - a;
+        let mut c = 1. / TINY;
+        let mut d = 1. / b;
+        let mut h = d;
+        for i in 1..MAX_ITERATIONS {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1. / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.).abs() < EPSILON {
+                break;
+            }
+        }
+        let q = prefactor * h;
+        (1. - q, q)
+    }
+}
+
+///
+/// Continued fraction of the regularized incomplete beta function (modified Lentz's method).
+///
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let qab = a + b;
+    let qap = a + 1.;
+    let qam = a - 1.;
+    let mut c = 1.;
+    let mut d = 1. - qab * x / qap;
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1. / d;
+    let mut h = d;
+    for m in 1..MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2. * m;
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1. + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1. + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1. / d;
+        h *= d * c;
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1. + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1. + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1. / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.).abs() < EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+///
+/// Regularized incomplete beta function \\( I_x(a, b) \\).
+///
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0. {
+        return 0.;
+    }
+    if x >= 1. {
+        return 1.;
+    }
+    let prefactor =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (-x).ln_1p()).exp();
+    if x < (a + 1.) / (a + b + 2.) {
+        prefactor * beta_continued_fraction(a, b, x) / a
+    } else {
+        1. - prefactor * beta_continued_fraction(b, a, 1. - x) / b
+    }
+}
+
+///
+/// Find the root of an increasing function within a bracket `[low, high]` with Newton iterations,
+/// falling back to bisection whenever a Newton step leaves the bracket.
+///
+/// The function `f` returns the value of the function and its derivative at a given point.
+///
+fn solve_increasing<Fun>(f: Fun, mut low: f64, mut high: f64, mut x: f64) -> f64
+where
+    Fun: Fn(f64) -> (f64, f64),
+{
+    for _ in 0..MAX_ITERATIONS {
+        let (value, derivative) = f(x);
+        if value == 0. {
+            return x;
+        }
+        if value < 0. {
+            low = x;
+        } else {
+            high = x;
+        }
+        let newton = x - value / derivative;
+        let next = if newton.is_finite() && low < newton && newton < high {
+            newton
+        } else {
+            0.5 * (low + high)
+        };
+        if (next - x).abs() <= EPSILON * x.abs() || high - low <= EPSILON * high.abs() {
+            return next;
+        }
+        x = next;
+    }
+    x
+}
+
+///
+/// Quantile function of the standard normal distribution.
+///
+/// Acklam's rational approximation (relative error below 1.15e-9) refined by one step of Halley's method.
+///
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p.is_nan() || !(0. ..=1.).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0. {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1. {
+        return f64::INFINITY;
+    }
+    if p > 0.5 {
+        return -normal_quantile(1. - p);
+    }
+
+    let x = if p < P_LOW {
+        let q = (-2. * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+    };
+
+    // refinement (Halley's method); x <= 0, so the cdf is computed from the lower tail
+    let e = normal_cdf(x) - p;
+    let u = e * (2. * core::f64::consts::PI).sqrt() * (x * x / 2.).exp();
+    x - u / (1. + x * u / 2.)
+}
+
+///
+/// Cumulative distribution function of the standard normal distribution.
+///
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+    // Φ(x) = erfc(-x/√2)/2, with erfc(y) = Q(1/2, y²) for y ≥ 0
+    let (p, q) = incomplete_gamma(0.5, x * x / 2.);
+    if x < 0. {
+        0.5 * q
+    } else {
+        0.5 + 0.5 * p
+    }
+}
+
+///
+/// Cumulative distribution function of Student's t distribution with the given (positive) degrees of freedom.
+///
+pub(crate) fn students_t_cdf(x: f64, degrees_of_freedom: f64) -> f64 {
+    if x.is_nan() || degrees_of_freedom.is_nan() || degrees_of_freedom <= 0. {
+        return f64::NAN;
+    }
+    if degrees_of_freedom.is_infinite() {
+        return normal_cdf(x);
+    }
+    // P(T > |x|) = I_{ν/(ν+x²)}(ν/2, 1/2) / 2
+    let tail = 0.5
+        * incomplete_beta(
+            degrees_of_freedom / 2.,
+            0.5,
+            degrees_of_freedom / (degrees_of_freedom + x * x),
+        );
+    if x < 0. {
+        tail
+    } else {
+        1. - tail
+    }
+}
+
+///
+/// Quantile function of Student's t distribution with the given (positive) degrees of freedom.
+///
+pub(crate) fn students_t_quantile(p: f64, degrees_of_freedom: f64) -> f64 {
+    if p.is_nan() || !(0. ..=1.).contains(&p) || degrees_of_freedom.is_nan() {
+        return f64::NAN;
+    }
+    if degrees_of_freedom.is_infinite() {
+        return normal_quantile(p);
+    }
+    if p == 0.5 {
+        return 0.;
+    }
+    if p > 0.5 {
+        return -students_t_quantile(1. - p, degrees_of_freedom);
+    }
+    if p == 0. {
+        return f64::NEG_INFINITY;
+    }
+
+    // solve for t > 0 such that P(T > t) = p, then return -t
+    let nu = degrees_of_freedom;
+    let ln_density_factor =
+        ln_gamma((nu + 1.) / 2.) - ln_gamma(nu / 2.) - 0.5 * (nu * core::f64::consts::PI).ln();
+    let upper_tail = |t: f64| 0.5 * incomplete_beta(nu / 2., 0.5, nu / (nu + t * t));
+    let density = |t: f64| (ln_density_factor - (nu + 1.) / 2. * (t * t / nu).ln_1p()).exp();
+    let f = |t: f64| (p - upper_tail(t), density(t));
+
+    // initial approximation (Cornish-Fisher expansion around the normal quantile)
+    let z = -normal_quantile(p);
+    let z3 = z.powi(3);
+    let z5 = z.powi(5);
+    let guess = z + (z3 + z) / (4. * nu) + (5. * z5 + 16. * z3 + 3. * z) / (96. * nu * nu);
+
+    let mut high = guess.max(1.);
+    while f(high).0 < 0. {
+        high *= 2.;
+    }
+    -solve_increasing(f, 0., high, guess.min(high))
+}
+
+///
+/// Quantile function of the gamma distribution with the given (positive) shape and unit rate.
+///
+pub(crate) fn gamma_quantile(p: f64, shape: f64) -> f64 {
+    if p.is_nan() || !(0. ..=1.).contains(&p) || shape.is_nan() {
+        return f64::NAN;
+    }
+    if p == 0. {
+        return 0.;
+    }
+    if p == 1. {
+        return f64::INFINITY;
+    }
+
+    let ln_gamma_shape = ln_gamma(shape);
+    let density = |x: f64| ((shape - 1.) * x.ln() - x - ln_gamma_shape).exp();
+    // the tail with the smallest probability is used to avoid cancellation
+    let f = |x: f64| {
+        let (lower, upper) = incomplete_gamma(shape, x);
+        let value = if p <= 0.5 {
+            lower - p
+        } else {
+            (1. - p) - upper
+        };
+        (value, density(x))
+    };
+
+    // initial approximation (Wilson-Hilferty transformation)
+    let z = normal_quantile(p);
+    let c = 1. / (9. * shape);
+    let wilson_hilferty = shape * (1. - c + z * c.sqrt()).powi(3);
+    let guess = if wilson_hilferty > 0. {
+        wilson_hilferty
+    } else {
+        // small quantiles: P(a, x) ≈ x^a / Γ(a + 1)
+        ((p.ln() + ln_gamma(shape + 1.)) / shape).exp()
+    };
+
+    let mut high = guess.max(shape + 1.);
+    while f(high).0 < 0. {
+        high *= 2.;
+    }
+    solve_increasing(f, 0., high, guess.min(high))
+}
+
+///
+/// Quantile function of the beta distribution with the given (positive) shape parameters.
+///
+fn beta_quantile(p: f64, a: f64, b: f64) -> f64 {
+    if p.is_nan() || !(0. ..=1.).contains(&p) || a.is_nan() || b.is_nan() {
+        return f64::NAN;
+    }
+    if p == 0. {
+        return 0.;
+    }
+    if p == 1. {
+        return 1.;
+    }
+    let ln_beta = ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b);
+    let density = |x: f64| ((a - 1.) * x.ln() + (b - 1.) * (-x).ln_1p() - ln_beta).exp();
+    let f = |x: f64| (incomplete_beta(a, b, x) - p, density(x));
+    solve_increasing(f, 0., 1., a / (a + b))
+}
+
+///
+/// Quantile function of the Fisher-Snedecor (F) distribution with the given (positive) degrees of freedom.
+///
+pub(crate) fn fisher_snedecor_quantile(p: f64, d1: f64, d2: f64) -> f64 {
+    // X = d1 F / (d1 F + d2) follows the beta distribution with shapes d1/2 and d2/2;
+    // above the median, 1 - X is computed instead to avoid cancellation
+    if p <= 0.5 {
+        let x = beta_quantile(p, d1 / 2., d2 / 2.);
+        d2 * x / (d1 * (1. - x))
+    } else {
+        let y = beta_quantile(1. - p, d2 / 2., d1 / 2.);
+        d2 * (1. - y) / (d1 * y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_ln_gamma() {
+        assert_abs_diff_eq!(ln_gamma(1.), 0., epsilon = 1e-14);
+        assert_abs_diff_eq!(ln_gamma(2.), 0., epsilon = 1e-14);
+        assert_abs_diff_eq!(
+            ln_gamma(0.5),
+            core::f64::consts::PI.sqrt().ln(),
+            epsilon = 1e-14
+        );
+        assert_abs_diff_eq!(ln_gamma(10.), 362_880_f64.ln(), epsilon = 1e-12);
+        // Stirling series for large arguments
+        let x = 1e5_f64;
+        let stirling =
+            (x - 0.5) * x.ln() - x + 0.5 * (2. * core::f64::consts::PI).ln() + 1. / (12. * x)
+                - 1. / (360. * x.powi(3));
+        assert_relative_eq!(ln_gamma(x), stirling, max_relative = 1e-14);
+    }
+
+    #[test]
+    fn test_normal_quantile() {
+        // reference values: standard tables of the normal distribution
+        let table = [
+            (0.5, 0.),
+            (0.8, 0.841_621_233_572_914_3),
+            (0.9, 1.281_551_565_544_600_5),
+            (0.95, 1.644_853_626_951_472_2),
+            (0.975, 1.959_963_984_540_054),
+            (0.995, 2.575_829_303_548_900_4),
+            (0.9995, 3.290_526_731_491_926),
+        ];
+        for (p, z) in table {
+            assert_abs_diff_eq!(normal_quantile(p), z, epsilon = 1e-12);
+            assert_abs_diff_eq!(normal_quantile(1. - p), -z, epsilon = 1e-12);
+        }
+        assert_eq!(normal_quantile(0.), f64::NEG_INFINITY);
+        assert_eq!(normal_quantile(1.), f64::INFINITY);
+        assert!(normal_quantile(1.5).is_nan());
+        for p in [1e-300, 1e-20, 1e-10, 0.01, 0.3] {
+            assert_relative_eq!(normal_cdf(normal_quantile(p)), p, max_relative = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_students_t_quantile() {
+        // closed forms for 1 (Cauchy) and 2 degrees of freedom
+        for p in [0.001, 0.025, 0.1, 0.3, 0.6, 0.95, 0.9995] {
+            let cauchy = (core::f64::consts::PI * (p - 0.5)).tan();
+            assert_relative_eq!(students_t_quantile(p, 1.), cauchy, max_relative = 1e-10);
+            let two = (2. * p - 1.) / (2. * p * (1. - p)).sqrt();
+            assert_relative_eq!(students_t_quantile(p, 2.), two, max_relative = 1e-10);
+        }
+        // reference values: standard tables of Student's t distribution
+        let table = [
+            (0.975, 5., 2.570_581_835_636_314),
+            (0.975, 10., 2.228_138_851_986_274),
+            (0.995, 30., 2.749_995_653_567_353),
+            (0.95, 100., 1.660_234_326_050_953),
+            // computed by numerical integration of the density at 30 digits
+            (0.2, 1_000., -0.841_980_822_162_428_6),
+        ];
+        for (p, nu, t) in table {
+            assert_abs_diff_eq!(students_t_quantile(p, nu), t, epsilon = 1e-9);
+        }
+        assert_abs_diff_eq!(
+            students_t_quantile(0.975, 99_999.),
+            normal_quantile(0.975),
+            epsilon = 1e-4
+        );
+        // heavy tail: P(T > t) ≈ K t^(-ν) / ν for large t
+        let nu = 0.5_f64;
+        let k = (ln_gamma((nu + 1.) / 2.)
+            - ln_gamma(nu / 2.)
+            - 0.5 * (nu * core::f64::consts::PI).ln()
+            + (nu + 1.) / 2. * nu.ln())
+        .exp();
+        let p = 1e-8;
+        assert_relative_eq!(
+            students_t_quantile(1. - p, nu),
+            (k / (nu * p)).powf(1. / nu),
+            max_relative = 1e-6
+        );
+        assert_eq!(students_t_quantile(0.5, 3.), 0.);
+        assert_eq!(
+            students_t_quantile(0.9, f64::INFINITY),
+            normal_quantile(0.9)
+        );
+    }
+
+    #[test]
+    fn test_students_t_cdf() {
+        // closed forms for 1 (Cauchy) and 2 degrees of freedom
+        for t in [-50., -3.2, -0.7, 0., 0.25, 1.9, 12.] {
+            let cauchy = 0.5 + f64::atan(t) / core::f64::consts::PI;
+            assert_relative_eq!(students_t_cdf(t, 1.), cauchy, max_relative = 1e-12);
+            let two = 0.5 + t / (2. * f64::sqrt(2. + t * t));
+            assert_relative_eq!(students_t_cdf(t, 2.), two, max_relative = 1e-12);
+        }
+        for nu in [0.5, 3., 17., 1_000.] {
+            for p in [1e-6, 0.025, 0.3, 0.5, 0.975] {
+                let t = students_t_quantile(p, nu);
+                assert_relative_eq!(students_t_cdf(t, nu), p, max_relative = 1e-9);
+            }
+        }
+        assert_eq!(students_t_cdf(1.5, f64::INFINITY), normal_cdf(1.5));
+        assert!(students_t_cdf(1., 0.).is_nan());
+    }
+
+    #[test]
+    fn test_gamma_quantile() {
+        // exponential distribution (shape 1)
+        for p in [1e-6, 0.025, 0.5, 0.975, 0.999_999] {
+            assert_relative_eq!(gamma_quantile(p, 1.), -(-p).ln_1p(), max_relative = 1e-12);
+        }
+        // chi-squared with 2k degrees of freedom is 2 Gamma(k); reference values from standard tables
+        let table = [
+            (0.05, 0.5, 0.003_932_140_000_019_5),
+            (0.975, 1., 7.377_758_908_227_871),
+            (0.025, 5., 3.246_972_780_236_841),
+            (0.95, 10., 31.410_432_844_230_918),
+        ];
+        for (p, k, chi2) in table {
+            assert_abs_diff_eq!(2. * gamma_quantile(p, k), chi2, epsilon = 1e-9);
+        }
+        for shape in [0.05, 0.5, 3., 533., 1e5] {
+            for p in [1e-10, 0.025, 0.5, 0.975] {
+                let x = gamma_quantile(p, shape);
+                assert_relative_eq!(incomplete_gamma(shape, x).0, p, max_relative = 1e-9);
+            }
+        }
+        assert_eq!(gamma_quantile(0., 2.), 0.);
+        assert_eq!(gamma_quantile(1., 2.), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_fisher_snedecor_quantile() {
+        // reference values: standard tables of the F distribution
+        let table = [
+            (0.95, 5., 10., 3.325_834_530_413_012),
+            (0.975, 9., 9., 4.025_994_158_282_978),
+        ];
+        for (p, d1, d2, f) in table {
+            assert_relative_eq!(fisher_snedecor_quantile(p, d1, d2), f, max_relative = 1e-9);
+        }
+        // F(1, ν) is the square of Student's t with ν degrees of freedom
+        for nu in [1., 20., 333.] {
+            assert_relative_eq!(
+                fisher_snedecor_quantile(0.99, 1., nu),
+                students_t_quantile(0.995, nu).powi(2),
+                max_relative = 1e-9
+            );
+        }
+        // F(d1, d2) and 1 / F(d2, d1) have the same distribution
+        for (d1, d2) in [(0.5, 3.), (2., 2.), (7., 42.), (300., 1_000.)] {
+            for p in [1e-6, 0.025, 0.5, 0.975, 0.999_999] {
+                let f = fisher_snedecor_quantile(p, d1, d2);
+                assert_relative_eq!(
+                    f,
+                    1. / fisher_snedecor_quantile(1. - p, d2, d1),
+                    max_relative = 1e-9
+                );
+                let x = d1 * f / (d1 * f + d2);
+                assert_relative_eq!(incomplete_beta(d1 / 2., d2 / 2., x), p, max_relative = 1e-9);
+            }
+        }
+        assert_eq!(fisher_snedecor_quantile(0., 3., 4.), 0.);
+        assert_eq!(fisher_snedecor_quantile(1., 3., 4.), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_against_statrs() {
+        use statrs::distribution::{ContinuousCDF, FisherSnedecor, Gamma, Normal, StudentsT};
+        let normal = Normal::new(0., 1.).unwrap();
+        for p in [1e-8, 0.001, 0.05, 0.2, 0.5, 0.8, 0.95, 0.999] {
+            assert_abs_diff_eq!(normal_quantile(p), normal.inverse_cdf(p), epsilon = 1e-9);
+            if p < 0.001 {
+                // statrs is inaccurate for extreme quantiles with very few degrees of freedom,
+                // and only accurate to about 1e-8 elsewhere
+                continue;
+            }
+            for nu in [0.5, 1., 2.5, 7., 42., 1_000., 50_000.] {
+                let student_t = StudentsT::new(0., 1., nu).unwrap();
+                assert_relative_eq!(
+                    students_t_quantile(p, nu),
+                    student_t.inverse_cdf(p),
+                    epsilon = 1e-9,
+                    max_relative = 1e-8
+                );
+                let t = student_t.inverse_cdf(p);
+                assert_abs_diff_eq!(students_t_cdf(t, nu), student_t.cdf(t), epsilon = 1e-9);
+            }
+            for shape in [0.5, 1., 4.5, 100., 10_000.] {
+                let gamma = Gamma::new(shape, 1.).unwrap();
+                let reference = gamma.inverse_cdf(p);
+                // statrs inverts the gamma CDF with a coarse tolerance and fails to converge
+                // for some extreme quantiles (returns NaN); accuracy is covered by the
+                // round-trip checks in `test_gamma_quantile` instead
+                if reference.is_nan() {
+                    continue;
+                }
+                assert_relative_eq!(gamma_quantile(p, shape), reference, max_relative = 1e-5);
+            }
+            for (d1, d2) in [(1., 1.), (4., 9.), (30., 12.), (500., 800.)] {
+                let fisher_snedecor = FisherSnedecor::new(d1, d2).unwrap();
+                assert_relative_eq!(
+                    fisher_snedecor_quantile(p, d1, d2),
+                    fisher_snedecor.inverse_cdf(p),
+                    max_relative = 1e-8
+                );
+            }
+        }
+    }
+}
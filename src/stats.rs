@@ -1,8 +1,65 @@
+//!
+//! Critical values of the distributions used to compute confidence intervals.
+//!
+//! These functions are used internally by the confidence intervals of the crate, but can also
+//! be called directly, e.g., to compute a critical value once and reuse it across many intervals
+//! (see [`mean::Arithmetic::ci_mean_with_critical`]).
+//!
+//! The module also defines the [`Alternative`] hypothesis of the one-sided p-values computed
+//! from the same distributions (see e.g. [`mean::Arithmetic::p_value_one_sided_vs`]).
+//!
+
 use crate::*;
+use error::CIError;
+use mean::CriticalDistribution;
+
+#[cfg(all(feature = "statrs", not(feature = "internal-quantiles")))]
+use statrs_backend as backend;
 
-use lazy_static::lazy_static;
-use statrs::distribution::ContinuousCDF;
-use statrs::distribution::{Normal, StudentsT};
+#[cfg(any(not(feature = "statrs"), feature = "internal-quantiles"))]
+use crate::special as backend;
+
+///
+/// Quantile functions computed with [`statrs`](https://crates.io/crates/statrs).
+///
+#[cfg(all(feature = "statrs", not(feature = "internal-quantiles")))]
+mod statrs_backend {
+    use lazy_static::lazy_static;
+    use statrs::distribution::ContinuousCDF;
+    use statrs::distribution::{FisherSnedecor, Gamma, Normal, StudentsT};
+
+    pub(crate) fn normal_quantile(p: f64) -> f64 {
+        lazy_static! {
+            static ref NORMAL: Normal = Normal::new(0., 1.).unwrap();
+        }
+        NORMAL.inverse_cdf(p)
+    }
+
+    pub(crate) fn students_t_quantile(p: f64, degrees_of_freedom: f64) -> f64 {
+        StudentsT::new(0., 1., degrees_of_freedom)
+            .map_or(f64::NAN, |student_t| student_t.inverse_cdf(p))
+    }
+
+    pub(crate) fn gamma_quantile(p: f64, shape: f64) -> f64 {
+        Gamma::new(shape, 1.).map_or(f64::NAN, |gamma| gamma.inverse_cdf(p))
+    }
+
+    pub(crate) fn fisher_snedecor_quantile(p: f64, d1: f64, d2: f64) -> f64 {
+        FisherSnedecor::new(d1, d2)
+            .map_or(f64::NAN, |fisher_snedecor| fisher_snedecor.inverse_cdf(p))
+    }
+
+    pub(crate) fn normal_cdf(x: f64) -> f64 {
+        lazy_static! {
+            static ref NORMAL: Normal = Normal::new(0., 1.).unwrap();
+        }
+        NORMAL.cdf(x)
+    }
+
+    pub(crate) fn students_t_cdf(x: f64, degrees_of_freedom: f64) -> f64 {
+        StudentsT::new(0., 1., degrees_of_freedom).map_or(f64::NAN, |student_t| student_t.cdf(x))
+    }
+}
 
 ///
 /// return the z-value of the normal distribution for a given confidence level.
@@ -11,11 +68,44 @@ use statrs::distribution::{Normal, StudentsT};
 ///
 /// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
 ///
+/// The z-values of the most common confidence levels are read from a precomputed table, which
+/// avoids evaluating the quantile function of the normal distribution (and initializing its
+/// backend) on the hot path. Other levels fall back to the configured backend.
+///
+#[inline]
 pub fn z_value(confidence: Confidence) -> f64 {
-    lazy_static! {
-        static ref NORMAL: Normal = Normal::new(0., 1.).unwrap();
+    let quantile = confidence.quantile();
+    match tabulated_normal_quantile(quantile) {
+        Some(z) => z,
+        None => backend::normal_quantile(quantile),
     }
-    NORMAL.inverse_cdf(confidence.quantile())
+}
+
+///
+/// Tolerance when matching a requested quantile against [`NORMAL_QUANTILES`], to absorb the
+/// rounding of e.g. `1 - 0.05 / 2`.
+///
+const NORMAL_QUANTILE_EPSILON: f64 = 1e-12;
+
+///
+/// Precomputed quantiles of the standard normal distribution, as pairs `(p, z)` such that
+/// \\( \Phi(z) = p \\).
+///
+const NORMAL_QUANTILES: [(f64, f64); 6] = [
+    (0.9, 1.2815515655446004),
+    (0.95, 1.6448536269514722),
+    (0.975, 1.959963984540054),
+    (0.99, 2.3263478740408408),
+    (0.995, 2.5758293035489004),
+    (0.999, 3.090232306167813),
+];
+
+#[inline]
+fn tabulated_normal_quantile(p: f64) -> Option<f64> {
+    NORMAL_QUANTILES
+        .iter()
+        .find(|(q, _)| (p - q).abs() < NORMAL_QUANTILE_EPSILON)
+        .map(|&(_, z)| z)
 }
 
 ///
@@ -26,31 +116,272 @@ pub fn z_value(confidence: Confidence) -> f64 {
 /// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
 /// * `degrees_of_freedom` - the degrees of freedom of the t-distribution
 ///
-/// # Panics
+/// # Errors
 ///
-/// * if `degrees_of_freedom` is negative or zero
+/// * [`CIError::InvalidDegreesOfFreedom`] - if `degrees_of_freedom` is negative, zero, or NaN
 ///
-pub fn t_value(confidence: Confidence, degrees_of_freedom: f64) -> f64 {
-    let student_t = StudentsT::new(0., 1., degrees_of_freedom).unwrap();
-    student_t.inverse_cdf(confidence.quantile())
+pub fn t_value(confidence: Confidence, degrees_of_freedom: f64) -> CIResult<f64> {
+    check_degrees_of_freedom(degrees_of_freedom)?;
+    Ok(backend::students_t_quantile(
+        confidence.quantile(),
+        degrees_of_freedom,
+    ))
+}
+
+///
+/// return the quantile of the chi-squared distribution for a given probability and degree of freedom.
+///
+/// # Arguments
+///
+/// * `p` - the probability, e.g. 0.975
+/// * `degrees_of_freedom` - the degrees of freedom of the chi-squared distribution
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if `p` is not in the range (0, 1)
+/// * [`CIError::InvalidDegreesOfFreedom`] - if `degrees_of_freedom` is negative, zero, or NaN
+///
+pub(crate) fn chi_squared_value(p: f64, degrees_of_freedom: f64) -> CIResult<f64> {
+    check_quantile_probability(p)?;
+    check_degrees_of_freedom(degrees_of_freedom)?;
+    // the chi-squared distribution with k degrees of freedom is a gamma distribution with shape k/2 and scale 2
+    Ok(2. * backend::gamma_quantile(p, degrees_of_freedom / 2.))
+}
+
+///
+/// return the quantile of the Fisher-Snedecor (F) distribution with the given degrees of freedom.
+///
+/// # Arguments
+///
+/// * `p` - the probability, e.g. 0.975
+/// * `d1` - the degrees of freedom of the numerator
+/// * `d2` - the degrees of freedom of the denominator
+///
+/// # Errors
+///
+/// * [`CIError::InvalidProbability`] - if `p` is not in the range (0, 1)
+/// * [`CIError::InvalidDegreesOfFreedom`] - if `d1` or `d2` is negative, zero, or NaN
+///
+pub(crate) fn fisher_snedecor_value(p: f64, d1: f64, d2: f64) -> CIResult<f64> {
+    check_quantile_probability(p)?;
+    check_degrees_of_freedom(d1)?;
+    check_degrees_of_freedom(d2)?;
+    Ok(backend::fisher_snedecor_quantile(p, d1, d2))
+}
+
+#[inline]
+fn check_quantile_probability(p: f64) -> CIResult<()> {
+    // NaN fails both comparisons
+    if p > 0. && p < 1. {
+        Ok(())
+    } else {
+        Err(CIError::InvalidProbability(p))
+    }
+}
+
+#[inline]
+fn check_degrees_of_freedom(degrees_of_freedom: f64) -> CIResult<()> {
+    if degrees_of_freedom.is_nan() || degrees_of_freedom <= 0. {
+        Err(CIError::InvalidDegreesOfFreedom(degrees_of_freedom))
+    } else {
+        Ok(())
+    }
+}
+
+///
+/// return the quantile of the gamma distribution with the given shape and unit rate.
+///
+/// # Arguments
+///
+/// * `p` - the probability, e.g. 0.975
+/// * `shape` - the shape of the gamma distribution
+///
+/// # Errors
+///
+/// * [`CIError::InvalidShape`] - if `shape` is not positive and finite
+///
+pub(crate) fn gamma_quantile(p: f64, shape: f64) -> CIResult<f64> {
+    if !shape.is_finite() || shape <= 0. {
+        return Err(CIError::InvalidShape(shape));
+    }
+    Ok(backend::gamma_quantile(p, shape))
+}
+
+///
+/// Alternative hypothesis of a one-sided test, i.e., the side on which the value of the statistic
+/// is expected to lie if the null hypothesis is false.
+///
+/// A one-sided p-value with [`Alternative::Greater`] is the dual of an upper one-sided interval
+/// \( [a, +\infty) \) (see [`Confidence::new_upper`]) and one with [`Alternative::Less`] is the dual
+/// of a lower one-sided interval \( (-\infty, b] \) (see [`Confidence::new_lower`]).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alternative {
+    /// The true value is greater than the hypothesized value.
+    Greater,
+
+    /// The true value is less than the hypothesized value.
+    Less,
+}
+
+///
+/// Probability that a random variable with the given distribution exceeds `x`, i.e., the upper tail
+/// \( 1 - F(x) \) of Student's t distribution with `degrees_of_freedom` or of the standard normal
+/// distribution.
+///
+pub(crate) fn upper_tail(
+    x: f64,
+    distribution: CriticalDistribution,
+    degrees_of_freedom: f64,
+) -> f64 {
+    // both distributions are symmetric: 1 - F(x) = F(-x), which is more accurate in the tail
+    match distribution {
+        CriticalDistribution::StudentT => backend::students_t_cdf(-x, degrees_of_freedom),
+        CriticalDistribution::Normal => backend::normal_cdf(-x),
+    }
+}
+
+///
+/// p-value of the observed `estimate` against the `hypothesized` value, for an estimate with the
+/// given standard error whose studentized value follows `distribution`.
+///
+/// The p-value is two-sided if `alternative` is `None`. With a zero standard error, the p-value is
+/// either zero or one, consistently with the degenerate interval at `estimate`.
+///
+pub(crate) fn p_value(
+    estimate: f64,
+    hypothesized: f64,
+    std_err: f64,
+    distribution: CriticalDistribution,
+    degrees_of_freedom: f64,
+    alternative: Option<Alternative>,
+) -> f64 {
+    if std_err == 0. {
+        let excluded = match alternative {
+            None => estimate != hypothesized,
+            Some(Alternative::Greater) => hypothesized < estimate,
+            Some(Alternative::Less) => hypothesized > estimate,
+        };
+        return if excluded { 0. } else { 1. };
+    }
+    let statistic = (estimate - hypothesized) / std_err;
+    match alternative {
+        None => (2. * upper_tail(statistic.abs(), distribution, degrees_of_freedom)).min(1.),
+        Some(Alternative::Greater) => upper_tail(statistic, distribution, degrees_of_freedom),
+        Some(Alternative::Less) => upper_tail(-statistic, distribution, degrees_of_freedom),
+    }
 }
 
 const POPULATION_LIMIT: f64 = 100_000.;
 
-pub(crate) fn interval_bounds(
+///
+/// Return the critical value used for an interval with the given confidence level and degrees of freedom,
+/// together with the distribution it was taken from.
+///
+/// Student's t distribution is used below 100'000 degrees of freedom and the normal distribution above.
+/// The degrees of freedom of the interval on an arithmetic mean are the number of samples minus one.
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
+/// * `degrees_of_freedom` - the degrees of freedom
+///
+/// # Errors
+///
+/// * [`CIError::InvalidDegreesOfFreedom`] - if `degrees_of_freedom` is negative, zero, or NaN
+///
+/// # Example
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let (critical, distribution) = stats::critical_value(confidence, 9.)?;
+/// assert_eq!(critical, stats::t_value(confidence, 9.)?);
+/// assert_eq!(distribution, mean::CriticalDistribution::StudentT);
+/// let (critical, distribution) = stats::critical_value(confidence, 1e6)?;
+/// assert_eq!(critical, stats::z_value(confidence));
+/// assert_eq!(distribution, mean::CriticalDistribution::Normal);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn critical_value(
     confidence: Confidence,
-    mean: f64,
-    std_err_mean: f64,
     degrees_of_freedom: f64,
-) -> (f64, f64) {
-    let span = if degrees_of_freedom < POPULATION_LIMIT {
-        let t = t_value(confidence, degrees_of_freedom);
-        t * std_err_mean
+) -> CIResult<(f64, CriticalDistribution)> {
+    if degrees_of_freedom.is_nan() || degrees_of_freedom <= 0. {
+        Err(CIError::InvalidDegreesOfFreedom(degrees_of_freedom))
+    } else if !uses_normal_approximation(degrees_of_freedom) {
+        Ok((
+            t_value(confidence, degrees_of_freedom)?,
+            CriticalDistribution::StudentT,
+        ))
     } else {
-        let z = z_value(confidence);
-        z * std_err_mean
-    };
-    (mean - span, mean + span)
+        Ok((z_value(confidence), CriticalDistribution::Normal))
+    }
+}
+
+///
+/// Test whether an interval with the given degrees of freedom is computed with the normal
+/// distribution rather than Student's t distribution (see [`critical_value`]), i.e., whether
+/// `degrees_of_freedom` is at least 100'000.
+///
+/// # Example
+/// ```
+/// # use stats_ci::*;
+/// assert!(!stats::uses_normal_approximation(99_999.));
+/// assert!(stats::uses_normal_approximation(100_000.));
+/// ```
+///
+pub fn uses_normal_approximation(degrees_of_freedom: f64) -> bool {
+    degrees_of_freedom >= POPULATION_LIMIT
+}
+
+///
+/// Return the interval on a mean centered on the estimate shifted by `shift`, i.e.,
+/// \\( \text{estimate} + \text{shift} \pm c \times \text{std\_err} \\)
+/// where \\( c \\) is the critical value for the confidence level and degrees of freedom
+/// (see [`critical_value`]). Only one of the two bounds is kept for one-sided intervals.
+///
+/// With a zero shift, this is the usual interval, symmetric around the estimate.
+/// A non-zero shift yields an interval that is asymmetric around the estimate, as used by the
+/// skewness correction of [`mean::Arithmetic::ci_mean_skew_corrected`].
+///
+/// # Arguments
+///
+/// * `confidence` - the confidence level, e.g. 0.95 for 95% confidence
+/// * `estimate` - the point estimate, e.g. the sample mean
+/// * `std_err` - the standard error of the estimate
+/// * `degrees_of_freedom` - the degrees of freedom
+/// * `shift` - the adjustment of the center of the interval
+///
+/// # Errors
+///
+/// * [`CIError::InvalidDegreesOfFreedom`] - if `degrees_of_freedom` is negative, zero, or NaN
+/// * [`CIError::IntervalError`] - if a bound of the interval is not finite
+///
+/// # Example
+/// ```
+/// # use stats_ci::*;
+/// let confidence = Confidence::new_two_sided(0.95);
+/// let t = stats::t_value(confidence, 9.)?;
+/// let ci = stats::interval_bounds_adjusted(confidence, 10., 1., 9., 0.5)?;
+/// assert_eq!(ci, Interval::new(10.5 - t, 10.5 + t)?);
+/// let ci = stats::interval_bounds_adjusted(Confidence::new_upper(0.95), 10., 1., 9., 0.5)?;
+/// assert_eq!(ci.high(), None);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub fn interval_bounds_adjusted(
+    confidence: Confidence,
+    estimate: f64,
+    std_err: f64,
+    degrees_of_freedom: f64,
+    shift: f64,
+) -> CIResult<Interval<f64>> {
+    let (critical_value, _) = critical_value(confidence, degrees_of_freedom)?;
+    let center = estimate + shift;
+    let span = critical_value * std_err;
+    Interval::from_finite_bounds_for(confidence, center - span, center + span)
 }
 
 #[cfg(test)]
@@ -67,7 +398,7 @@ mod tests {
                 Confidence::new_lower,
             ] {
                 let confidence = new_confidence(confidence_level);
-                let t_value = t_value(confidence, 1000.);
+                let t_value = t_value(confidence, 1000.).unwrap();
                 let z_value = z_value(confidence);
                 assert_abs_diff_eq!(t_value, z_value, epsilon = 1e-2);
             }
@@ -75,7 +406,84 @@ mod tests {
     }
 
     #[test]
-    fn test_interval_bounds() {
+    fn test_chi_squared_and_fisher_snedecor_values() -> CIResult<()> {
+        // reference values computed with mpmath
+        assert_abs_diff_eq!(
+            chi_squared_value(0.975, 10.)?,
+            20.483_177_350_807,
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(
+            fisher_snedecor_value(0.975, 5., 10.)?,
+            4.236_085_668_188_6,
+            epsilon = 1e-6
+        );
+
+        for degrees_of_freedom in [0., -1., f64::NAN] {
+            assert!(matches!(
+                chi_squared_value(0.975, degrees_of_freedom),
+                Err(CIError::InvalidDegreesOfFreedom(_))
+            ));
+            assert!(matches!(
+                fisher_snedecor_value(0.975, degrees_of_freedom, 10.),
+                Err(CIError::InvalidDegreesOfFreedom(_))
+            ));
+            assert!(matches!(
+                fisher_snedecor_value(0.975, 5., degrees_of_freedom),
+                Err(CIError::InvalidDegreesOfFreedom(_))
+            ));
+        }
+        for p in [0., 1., -0.5, 1.5, f64::NAN] {
+            assert!(matches!(
+                chi_squared_value(p, 10.),
+                Err(CIError::InvalidProbability(_))
+            ));
+            assert!(matches!(
+                fisher_snedecor_value(p, 5., 10.),
+                Err(CIError::InvalidProbability(_))
+            ));
+        }
+        for shape in [0., -1., f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                gamma_quantile(0.975, shape),
+                Err(CIError::InvalidShape(_))
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_tabulated_z_values() {
+        for (p, z) in NORMAL_QUANTILES {
+            assert_abs_diff_eq!(z, backend::normal_quantile(p), epsilon = 1e-9);
+            #[cfg(feature = "statrs")]
+            assert_abs_diff_eq!(z, statrs_quantile(p), epsilon = 1e-9);
+            assert_eq!(tabulated_normal_quantile(p), Some(z));
+        }
+        assert_eq!(tabulated_normal_quantile(0.96), None);
+
+        // common levels hit the table despite rounding in the computed quantile
+        assert_eq!(z_value(Confidence::new_two_sided(0.95)), 1.959963984540054);
+        assert_eq!(z_value(Confidence::new_upper(0.95)), 1.6448536269514722);
+        assert_eq!(z_value(Confidence::new_two_sided(0.99)), 2.5758293035489004);
+        assert_eq!(z_value(Confidence::new_lower(0.9)), 1.2815515655446004);
+
+        // other levels fall back to the backend
+        let confidence = Confidence::new_two_sided(0.96);
+        assert_eq!(
+            z_value(confidence),
+            backend::normal_quantile(confidence.quantile())
+        );
+    }
+
+    #[cfg(feature = "statrs")]
+    fn statrs_quantile(p: f64) -> f64 {
+        use statrs::distribution::{ContinuousCDF, Normal};
+        Normal::new(0., 1.).unwrap().inverse_cdf(p)
+    }
+
+    #[test]
+    fn test_critical_value() {
         let confidence = Confidence::new_two_sided(0.95);
         let mean = 0.;
         let std_err_mean = 1.;
@@ -87,11 +495,15 @@ mod tests {
             POPULATION_LIMIT + 2.,
         ] {
             let degrees_of_freedom = n - 1.;
-            let actual = interval_bounds(confidence, mean, std_err_mean, degrees_of_freedom);
+            let (critical, distribution) = critical_value(confidence, degrees_of_freedom).unwrap();
+            let actual = (
+                mean - critical * std_err_mean,
+                mean + critical * std_err_mean,
+            );
             let bounds_t = only_t(confidence, mean, std_err_mean, degrees_of_freedom);
             let bounds_z = only_z(confidence, mean, std_err_mean);
 
-            println!("n = {} (dof: {})", n, degrees_of_freedom);
+            println!("n = {} (dof: {}, {})", n, degrees_of_freedom, distribution);
             println!("actual: ({}, {})", actual.0, actual.1);
             println!("bounds_t: ({}, {})", bounds_t.0, bounds_t.1);
             println!("bounds_z: ({}, {})", bounds_z.0, bounds_z.1);
@@ -101,7 +513,29 @@ mod tests {
             assert_abs_diff_eq!(actual.1, bounds_t.1, epsilon = 1e-4);
             assert_abs_diff_eq!(actual.0, bounds_z.0, epsilon = 1e-4);
             assert_abs_diff_eq!(actual.1, bounds_z.1, epsilon = 1e-4);
+            if degrees_of_freedom < POPULATION_LIMIT {
+                assert_eq!(distribution, CriticalDistribution::StudentT);
+            } else {
+                assert_eq!(distribution, CriticalDistribution::Normal);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_degrees_of_freedom() {
+        let confidence = Confidence::new_two_sided(0.95);
+        for degrees_of_freedom in [0., -1., -0.5, f64::NAN, f64::NEG_INFINITY] {
+            assert!(matches!(
+                t_value(confidence, degrees_of_freedom),
+                Err(CIError::InvalidDegreesOfFreedom(_))
+            ));
+            assert!(matches!(
+                critical_value(confidence, degrees_of_freedom),
+                Err(CIError::InvalidDegreesOfFreedom(_))
+            ));
         }
+        assert!(t_value(confidence, 0.5).is_ok());
+        assert!(critical_value(confidence, f64::INFINITY).is_ok());
     }
 
     fn only_t(
@@ -110,7 +544,7 @@ mod tests {
         std_err_mean: f64,
         degrees_of_freedom: f64,
     ) -> (f64, f64) {
-        let t = t_value(confidence, degrees_of_freedom);
+        let t = t_value(confidence, degrees_of_freedom).unwrap();
         let span = t * std_err_mean;
         (mean - span, mean + span)
     }
@@ -0,0 +1,435 @@
+//!
+//! Assertion helpers to check statistical properties of measurements within test suites
+//! (e.g., to detect performance regressions).
+//!
+//! The functions never panic by themselves: they return an [`AssertionReport`] describing the
+//! failure, and the caller decides what to do with it (e.g., `unwrap` it or propagate it with `?`).
+//! The computations are deterministic for given data.
+//!
+//! This module requires the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use stats_ci::*;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let latencies = [10.2, 9.8, 10.5, 10.1, 9.9, 10.3, 10.0, 9.7];
+//! let confidence = Confidence::new_two_sided(0.95);
+//! testing::assert_mean_below(&latencies, confidence, 11.)?;
+//!
+//! let report = testing::assert_mean_below(&latencies, confidence, 10.).unwrap_err();
+//! assert!(report.message.starts_with("mean not below threshold"));
+//! # Ok(())
+//! # }
+//! ```
+//!
+use super::*;
+use error::*;
+
+///
+/// Describes a failed assertion: the interval that was computed, the statistics of the samples,
+/// and a rendered message.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssertionReport {
+    /// The confidence interval computed for the assertion, or `None` if it could not be computed
+    /// (e.g., too few samples), in which case the message gives the cause.
+    pub interval: Option<Interval<f64>>,
+
+    /// The threshold the interval was compared to.
+    pub threshold: f64,
+
+    /// The confidence level used to compute the interval.
+    pub confidence: Confidence,
+
+    /// The statistics of the sample (of the candidate for comparisons), or empty statistics if
+    /// the sample contains values that are not finite.
+    pub sample: mean::Arithmetic<f64>,
+
+    /// The statistics of the baseline for comparisons, or `None` if the baseline contains values
+    /// that are not finite.
+    pub baseline: Option<mean::Arithmetic<f64>>,
+
+    /// The rendered message describing the failure.
+    pub message: String,
+}
+
+impl core::fmt::Display for AssertionReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AssertionReport {}
+
+///
+/// Asserts that the mean of the data is significantly below a threshold, i.e., that the upper bound
+/// of the confidence interval on the mean is strictly smaller than the threshold.
+///
+/// # Arguments
+///
+/// * `data` - the sample data
+/// * `confidence` - the confidence level; a lower one-sided confidence (see [`Confidence::new_lower`])
+///   gives the tightest bound, while an upper one-sided confidence never succeeds
+/// * `threshold` - the value the mean should be below
+///
+/// # Errors
+///
+/// Returns an [`AssertionReport`] if the upper bound of the interval is not below the threshold,
+/// if the data contains values that are not finite, or if the interval cannot be computed.
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let data = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+/// assert!(testing::assert_mean_below(&data, Confidence::new_lower(0.95), 8.).is_ok());
+/// assert!(testing::assert_mean_below(&data, Confidence::new_lower(0.95), 6.).is_err());
+/// ```
+///
+#[allow(clippy::result_large_err)] // the report is only built when the assertion fails
+pub fn assert_mean_below(
+    data: &[f64],
+    confidence: Confidence,
+    threshold: f64,
+) -> Result<(), AssertionReport> {
+    let sample = sample_stats(data).map_err(|e| AssertionReport {
+        interval: None,
+        threshold,
+        confidence,
+        sample: mean::Arithmetic::new(),
+        baseline: None,
+        message: format!("mean not below threshold: invalid sample: {}", e),
+    })?;
+    let mut report = AssertionReport {
+        interval: None,
+        threshold,
+        confidence,
+        sample,
+        baseline: None,
+        message: String::new(),
+    };
+    match sample.ci_mean(confidence) {
+        Ok(interval) if interval.high_f() < threshold => Ok(()),
+        Ok(interval) => {
+            report.interval = Some(interval);
+            report.message = format!(
+                "mean not below threshold: {} CI for the mean is {}, threshold: {} (sample {})",
+                describe_confidence(confidence),
+                describe_interval(&interval),
+                describe_value(threshold),
+                describe_sample(&sample)
+            );
+            Err(report)
+        }
+        Err(e) => {
+            report.message = format!(
+                "mean not below threshold: could not compute the confidence interval: {} (sample {})",
+                e,
+                describe_sample(&sample)
+            );
+            Err(report)
+        }
+    }
+}
+
+///
+/// Asserts that the mean of a candidate sample shows no significant regression (increase) with respect
+/// to the mean of a baseline sample, beyond a maximum relative increase.
+///
+/// The relative increase is \\( (\mu_c - \mu_b) / \bar{x}_b \\), whose confidence interval is obtained
+/// by scaling the interval on the difference of the means (see [`comparison::Unpaired`]) by the
+/// baseline sample mean. The assertion fails only if the lower bound of that interval exceeds the
+/// maximum relative increase, i.e., if the data is significant evidence of a regression.
+///
+/// # Arguments
+///
+/// * `baseline` - the baseline sample (e.g., timings of the reference version)
+/// * `candidate` - the candidate sample (e.g., timings of the new version)
+/// * `confidence` - the confidence level; an upper one-sided confidence (see [`Confidence::new_upper`])
+///   gives the tightest bound, while a lower one-sided confidence is rejected since its interval
+///   has no lower bound and could never detect a regression
+/// * `max_relative_increase` - the tolerated relative increase (e.g., `0.05` for 5%)
+///
+/// # Errors
+///
+/// Returns an [`AssertionReport`] if a significant regression is detected, if the confidence is
+/// lower one-sided, if a sample contains values that are not finite, or if the interval cannot be
+/// computed (e.g., too few samples or a baseline mean that is not strictly positive).
+///
+/// # Examples
+///
+/// ```
+/// # use stats_ci::*;
+/// let baseline = [10.2, 9.8, 10.5, 10.1, 9.9, 10.3, 10.0, 9.7];
+/// let candidate = [10.4, 10.0, 10.6, 10.2, 10.1, 10.5, 10.3, 9.9];
+/// let confidence = Confidence::new_upper(0.95);
+/// assert!(testing::assert_no_regression(&baseline, &candidate, confidence, 0.05).is_ok());
+/// assert!(testing::assert_no_regression(&candidate, &baseline, confidence, 0.).is_ok());
+/// let slower = candidate.map(|x| x * 1.2);
+/// assert!(testing::assert_no_regression(&baseline, &slower, confidence, 0.05).is_err());
+/// ```
+///
+#[allow(clippy::result_large_err)] // the report is only built when the assertion fails
+pub fn assert_no_regression(
+    baseline: &[f64],
+    candidate: &[f64],
+    confidence: Confidence,
+    max_relative_increase: f64,
+) -> Result<(), AssertionReport> {
+    let invalid = |which: &str, e: CIError| AssertionReport {
+        interval: None,
+        threshold: max_relative_increase,
+        confidence,
+        sample: mean::Arithmetic::new(),
+        baseline: None,
+        message: format!("regression check failed: invalid {} sample: {}", which, e),
+    };
+    let baseline = sample_stats(baseline).map_err(|e| invalid("baseline", e))?;
+    let sample = sample_stats(candidate).map_err(|e| invalid("candidate", e))?;
+    let mut report = AssertionReport {
+        interval: None,
+        threshold: max_relative_increase,
+        confidence,
+        sample,
+        baseline: Some(baseline),
+        message: String::new(),
+    };
+    let samples = format!(
+        "baseline {}; candidate {}",
+        describe_sample(&baseline),
+        describe_sample(&sample)
+    );
+    if confidence.is_lower() {
+        report.message = format!(
+            "regression check failed: a {} confidence cannot detect a regression ({})",
+            confidence.kind(),
+            samples
+        );
+        return Err(report);
+    }
+    match relative_increase_ci(&baseline, &sample, confidence) {
+        Ok(interval) if interval.low_f() <= max_relative_increase => Ok(()),
+        Ok(interval) => {
+            report.interval = Some(interval);
+            report.message = format!(
+                "regression detected: {} CI for the relative increase of the mean is {}, maximum: {} ({})",
+                describe_confidence(confidence),
+                describe_interval(&interval),
+                describe_value(max_relative_increase),
+                samples
+            );
+            Err(report)
+        }
+        Err(e) => {
+            report.message = format!(
+                "regression check failed: could not compute the confidence interval: {} ({})",
+                e, samples
+            );
+            Err(report)
+        }
+    }
+}
+
+///
+/// Confidence interval on the relative increase of the mean of the candidate w.r.t. the baseline.
+///
+fn relative_increase_ci(
+    baseline: &mean::Arithmetic<f64>,
+    candidate: &mean::Arithmetic<f64>,
+    confidence: Confidence,
+) -> CIResult<Interval<f64>> {
+    let difference = comparison::Unpaired::new(*candidate, *baseline).ci_mean(confidence)?;
+    let baseline_mean = baseline.sample_mean();
+    if baseline_mean.is_nan() || baseline_mean <= 0. {
+        return Err(CIError::InvalidBaseline(format!(
+            "the baseline mean must be strictly positive: {}",
+            baseline_mean
+        )));
+    }
+    Ok(difference / baseline_mean)
+}
+
+fn sample_stats(data: &[f64]) -> CIResult<mean::Arithmetic<f64>> {
+    let mut stats = mean::Arithmetic::new();
+    for &x in data {
+        if !x.is_finite() {
+            return Err(CIError::InvalidInputData);
+        }
+        stats.append(x)?;
+    }
+    Ok(stats)
+}
+
+fn describe_confidence(confidence: Confidence) -> String {
+    format!("{}% {}", confidence.percent(), confidence.kind())
+}
+
+fn describe_value(x: f64) -> String {
+    format!("{:.4}", x)
+}
+
+fn describe_interval(interval: &Interval<f64>) -> String {
+    match interval {
+        Interval::TwoSided(low, high) => {
+            format!("[{}, {}]", describe_value(*low), describe_value(*high))
+        }
+        Interval::UpperOneSided(low) => format!("[{},->)", describe_value(*low)),
+        Interval::LowerOneSided(high) => format!("(<-,{}]", describe_value(*high)),
+    }
+}
+
+fn describe_sample(sample: &mean::Arithmetic<f64>) -> String {
+    let std_dev = if sample.sample_count() < 2 {
+        f64::NAN
+    } else {
+        sample.sample_std_dev()
+    };
+    format!(
+        "mean: {}, std. dev.: {}, n: {}",
+        describe_value(sample.sample_mean()),
+        describe_value(std_dev),
+        sample.sample_count()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+
+    const BASELINE: [f64; 8] = [10.2, 9.8, 10.5, 10.1, 9.9, 10.3, 10.0, 9.7];
+
+    #[test]
+    fn test_mean_below() {
+        let confidence = Confidence::new_two_sided(0.95);
+        assert_eq!(assert_mean_below(&BASELINE, confidence, 10.5), Ok(()));
+
+        let report = assert_mean_below(&BASELINE, confidence, 10.2).unwrap_err();
+        assert_eq!(
+            report.message,
+            "mean not below threshold: 95% two-sided CI for the mean is [9.8393, 10.2857], \
+             threshold: 10.2000 (sample mean: 10.0625, std. dev.: 0.2669, n: 8)"
+        );
+        assert_eq!(report.to_string(), report.message);
+        assert_abs_diff_eq!(
+            report.interval.unwrap(),
+            Interval::new(9.8393, 10.2857).unwrap(),
+            epsilon = 1e-4
+        );
+        assert_eq!(report.threshold, 10.2);
+        assert_eq!(report.sample.sample_count(), 8);
+        assert_eq!(report.baseline, None);
+
+        let report = assert_mean_below(&BASELINE, Confidence::new_upper(0.95), 100.).unwrap_err();
+        assert_eq!(
+            report.message,
+            "mean not below threshold: 95% upper one-sided CI for the mean is [9.8837,->), \
+             threshold: 100.0000 (sample mean: 10.0625, std. dev.: 0.2669, n: 8)"
+        );
+    }
+
+    #[test]
+    fn test_mean_below_single_sample() {
+        // a single sample gives a degenerate interval at its value
+        let confidence = Confidence::new_two_sided(0.95);
+        assert!(assert_mean_below(&[1.], confidence, 10.).is_ok());
+        let report = assert_mean_below(&[12.], confidence, 10.).unwrap_err();
+        assert_eq!(report.interval, Some(Interval::new(12., 12.).unwrap()));
+        assert_eq!(
+            report.message,
+            "mean not below threshold: 95% two-sided CI for the mean is [12.0000, 12.0000], \
+             threshold: 10.0000 (sample mean: 12.0000, std. dev.: NaN, n: 1)"
+        );
+    }
+
+    #[test]
+    fn test_no_regression() {
+        let confidence = Confidence::new_upper(0.95);
+        let candidate = BASELINE.map(|x| x * 1.05);
+        assert_eq!(
+            assert_no_regression(&BASELINE, &candidate, confidence, 0.1),
+            Ok(())
+        );
+        assert_eq!(
+            assert_no_regression(&candidate, &BASELINE, confidence, 0.),
+            Ok(())
+        );
+
+        let report = assert_no_regression(&BASELINE, &candidate, confidence, 0.).unwrap_err();
+        let interval = report.interval.unwrap();
+        assert!(interval.is_upper());
+        assert_abs_diff_eq!(interval, Interval::new_upper(0.0263), epsilon = 1e-4);
+        assert_eq!(report.baseline.unwrap().sample_count(), 8);
+        assert_eq!(
+            report.message,
+            "regression detected: 95% upper one-sided CI for the relative increase of the mean is [0.0263,->), \
+             maximum: 0.0000 (baseline mean: 10.0625, std. dev.: 0.2669, n: 8; \
+             candidate mean: 10.5656, std. dev.: 0.2803, n: 8)"
+        );
+    }
+
+    #[test]
+    fn test_no_regression_error() {
+        let confidence = Confidence::new_two_sided(0.95);
+        let report = assert_no_regression(&[-1., 0., -2.], &[1., 2.], confidence, 0.).unwrap_err();
+        assert_eq!(report.interval, None);
+        assert_eq!(
+            report.message,
+            "regression check failed: could not compute the confidence interval: \
+             Invalid baseline: the baseline mean must be strictly positive: -1 \
+             (baseline mean: -1.0000, std. dev.: 1.0000, n: 3; \
+             candidate mean: 1.5000, std. dev.: 0.7071, n: 2)"
+        );
+    }
+
+    #[test]
+    fn test_no_regression_lower_confidence() {
+        // a lower one-sided interval has no lower bound, so it could never detect the regression
+        let slower = BASELINE.map(|x| x * 2.);
+        let report =
+            assert_no_regression(&BASELINE, &slower, Confidence::new_lower(0.95), 0.).unwrap_err();
+        assert_eq!(report.interval, None);
+        assert!(
+            report.message.starts_with(
+                "regression check failed: a lower one-sided confidence cannot detect a regression"
+            ),
+            "{}",
+            report.message
+        );
+    }
+
+    #[test]
+    fn test_non_finite_samples() {
+        let confidence = Confidence::new_upper(0.95);
+        let mut invalid = BASELINE;
+        invalid[3] = f64::NAN;
+        let report = assert_no_regression(&BASELINE, &invalid, confidence, 0.).unwrap_err();
+        assert_eq!(
+            report.message,
+            "regression check failed: invalid candidate sample: Invalid input data found"
+        );
+        invalid[3] = f64::INFINITY;
+        let report = assert_no_regression(&invalid, &BASELINE, confidence, 0.).unwrap_err();
+        assert_eq!(
+            report.message,
+            "regression check failed: invalid baseline sample: Invalid input data found"
+        );
+        let report = assert_mean_below(&invalid, confidence, 100.).unwrap_err();
+        assert_eq!(
+            report.message,
+            "mean not below threshold: invalid sample: Invalid input data found"
+        );
+        assert_eq!(report.sample, mean::Arithmetic::new());
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let confidence = Confidence::new_two_sided(0.9);
+        let candidate = BASELINE.map(|x| x + 1.);
+        let first = assert_no_regression(&BASELINE, &candidate, confidence, 0.01);
+        let second = assert_no_regression(&BASELINE, &candidate, confidence, 0.01);
+        assert!(first.is_err());
+        assert_eq!(first, second);
+    }
+}
@@ -4,6 +4,10 @@
 //! Offers support for the computation of compensated floating point sums (aka. Kahan summation).
 //!
 //! The main type is [`KahanSum`] where you can find further explanations and an example.
+//!
+//! Also defines the [`Accumulator`] trait, implemented by the statistics objects of the crate to
+//! reset them in place at the end of a reporting period, and the [`Epoched`] wrapper that counts
+//! these periods.
 
 use num_traits::Float;
 
@@ -28,6 +32,7 @@ use num_traits::Float;
 /// assert_ne!(naive, repetitions as f32 * 0.1);
 /// ```
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KahanSum<T: Float> {
     sum: T,
     compensation: T,
@@ -52,7 +57,45 @@ impl<T: Float> KahanSum<T> {
     /// Return the current value of the sum
     ///
     pub fn value(&self) -> T {
-        self.sum + self.compensation
+        self.sum - self.compensation
+    }
+
+    ///
+    /// Return the running sum without the compensation term
+    ///
+    pub fn sum_raw(&self) -> T {
+        self.sum
+    }
+
+    ///
+    /// Create a register from its running sum and compensation term (see [`KahanSum::sum_raw`]
+    /// and [`KahanSum::compensation`]).
+    ///
+    #[cfg(feature = "persist")]
+    pub(crate) fn from_raw_parts(sum: T, compensation: T) -> Self {
+        Self { sum, compensation }
+    }
+
+    ///
+    /// Return the current compensation term, i.e. the (negated) low-order part lost by the
+    /// running sum, such that the value of the sum is `sum_raw() - compensation()`.
+    ///
+    /// This is mostly useful for debugging: a compensation that grows large relative to
+    /// the sum is a sign of precision trouble.
+    ///
+    pub fn compensation(&self) -> T {
+        self.compensation
+    }
+
+    ///
+    /// Apply `f` to both the running sum and the compensation term, e.g., to multiply the sum by
+    /// a power of two, which is exact for both terms (barring underflow).
+    ///
+    pub(crate) fn map_parts(self, f: impl Fn(T) -> T) -> Self {
+        Self {
+            sum: f(self.sum),
+            compensation: f(self.compensation),
+        }
     }
 }
 
@@ -76,8 +119,9 @@ impl<T: Float + core::fmt::Display> core::fmt::Display for KahanSum<T> {
 
 impl<T: Float> core::ops::AddAssign<Self> for KahanSum<T> {
     fn add_assign(&mut self, rhs: Self) {
+        // the true value of rhs is `rhs.sum - rhs.compensation`
+        self.compensation = self.compensation + rhs.compensation;
         kahan_add(&mut self.sum, rhs.sum, &mut self.compensation);
-        kahan_add(&mut self.sum, rhs.compensation, &mut self.compensation);
     }
 }
 
@@ -100,6 +144,66 @@ where
     }
 }
 
+impl<T: Float> core::ops::Neg for KahanSum<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            sum: -self.sum,
+            compensation: -self.compensation,
+        }
+    }
+}
+
+///
+/// Subtraction is implemented as the addition of the negation.
+///
+/// Note that, while each operation is compensated, subtracting values of similar magnitude
+/// reintroduces the risk of catastrophic cancellation inherent to floating point subtraction.
+///
+impl<T: Float> core::ops::SubAssign<Self> for KahanSum<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self += -rhs;
+    }
+}
+
+///
+/// Subtraction is implemented as the addition of the negation.
+///
+/// Note that, while each operation is compensated, subtracting values of similar magnitude
+/// reintroduces the risk of catastrophic cancellation inherent to floating point subtraction.
+///
+impl<T: Float> core::ops::SubAssign<T> for KahanSum<T> {
+    fn sub_assign(&mut self, rhs: T) {
+        *self += -rhs;
+    }
+}
+
+impl<T: Float, X> core::ops::Sub<X> for KahanSum<T>
+where
+    Self: core::ops::SubAssign<X>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: X) -> Self::Output {
+        let mut diff = self;
+        diff -= rhs;
+        diff
+    }
+}
+
+impl<T: Float> core::iter::Sum<T> for KahanSum<T> {
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Self::default(), |sum, x| sum + x)
+    }
+}
+
+impl<T: Float> core::iter::Sum<KahanSum<T>> for KahanSum<T> {
+    fn sum<I: Iterator<Item = KahanSum<T>>>(iter: I) -> Self {
+        iter.fold(Self::default(), |sum, x| sum + x)
+    }
+}
+
 impl<T: Float> From<T> for KahanSum<T> {
     fn from(value: T) -> Self {
         Self::new(value)
@@ -129,6 +233,119 @@ fn kahan_add<T: Float>(current_sum: &mut T, x: T, compensation: &mut T) {
     *current_sum = t;
 }
 
+///
+/// Lifecycle of the statistics objects that accumulate samples (e.g., [`mean::Arithmetic`](crate::mean::Arithmetic),
+/// [`proportion::Stats`](crate::proportion::Stats), or [`comparison::Unpaired`](crate::comparison::Unpaired)),
+/// for long-running processes that report their statistics periodically.
+///
+/// Resetting a state in place, rather than replacing it, closes the current period and returns its
+/// statistics in a single operation, so that no sample is lost or counted twice between the two.
+///
+/// # Examples
+/// ```
+/// use stats_ci::*;
+/// let mut stats = mean::Arithmetic::new();
+/// stats.extend(&[1., 2., 3.])?;
+/// let closed = stats.reset();
+/// assert_eq!(closed.sample_count(), 3);
+/// assert_eq!(stats.sample_count(), 0);
+/// # Ok::<(),error::CIError>(())
+/// ```
+///
+pub trait Accumulator: Clone {
+    ///
+    /// Reset the state to an empty state with the same parameters (e.g., the smoothing factor of
+    /// [`mean::Ewma`](crate::mean::Ewma) or the names of the columns of [`mean::Columns`](crate::mean::Columns)),
+    /// and return the state as it was before the reset.
+    ///
+    fn reset(&mut self) -> Self;
+
+    ///
+    /// Copy of the current state, e.g., to compute intervals while the state keeps accumulating samples.
+    ///
+    fn snapshot(&self) -> Self {
+        self.clone()
+    }
+}
+
+///
+/// Statistics object with a counter of the number of times it was reset (its epoch), so that the
+/// readers of the statistics can detect that a new period has started.
+///
+/// The epoch starts at zero and is incremented by [`Accumulator::reset`], which returns the closed
+/// epoch, i.e., the wrapped state before the reset together with its epoch number.
+///
+/// # Examples
+/// ```
+/// use stats_ci::*;
+/// use utils::Epoched;
+/// let mut errors = Epoched::new(proportion::Stats::default());
+/// errors.stats_mut().extend(&[false, false, true, false]);
+/// let seen = errors.epoch();
+///
+/// // report boundary
+/// let closed = errors.reset();
+/// assert_eq!(closed.epoch(), 0);
+/// assert_eq!(closed.stats(), &proportion::Stats::new(4, 1));
+/// assert_eq!(errors.stats(), &proportion::Stats::default());
+/// assert_ne!(errors.epoch(), seen); // the readers see the rollover
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Epoched<S> {
+    epoch: u64,
+    stats: S,
+}
+
+impl<S: Accumulator> Epoched<S> {
+    ///
+    /// Wrap a statistics object, starting at epoch zero.
+    ///
+    pub fn new(stats: S) -> Self {
+        Self { epoch: 0, stats }
+    }
+
+    ///
+    /// Number of times the state was reset.
+    ///
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    ///
+    /// Wrapped statistics of the current epoch.
+    ///
+    pub fn stats(&self) -> &S {
+        &self.stats
+    }
+
+    ///
+    /// Mutable reference to the wrapped statistics of the current epoch, e.g., to add samples.
+    ///
+    pub fn stats_mut(&mut self) -> &mut S {
+        &mut self.stats
+    }
+
+    ///
+    /// Unwrap the statistics of the current epoch.
+    ///
+    pub fn into_inner(self) -> S {
+        self.stats
+    }
+}
+
+impl<S: Accumulator> Accumulator for Epoched<S> {
+    fn reset(&mut self) -> Self {
+        let closed = Self {
+            epoch: self.epoch,
+            stats: self.stats.reset(),
+        };
+        self.epoch += 1;
+        closed
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,6 +423,98 @@ mod tests {
         assert!((expected - normal).abs() > 500_000.); // normal summation is not accurate for f32
     }
 
+    #[test]
+    #[allow(clippy::assign_op_pattern)] // testing `Sub` explicitly
+    fn test_kahan_sub() {
+        type Float = f32;
+
+        let iterations = 10_000_000_usize;
+        let mut normal: Float = 0.;
+        let mut kahan = KahanSum::<Float>::default();
+        let mut kahan2 = KahanSum::<Float>::default();
+
+        let x = 1.1;
+
+        for _ in 0..iterations {
+            normal -= x;
+            kahan -= x;
+            kahan2 = kahan2 - KahanSum::new(x);
+        }
+        let expected = -(iterations as Float) * x;
+        assert_abs_diff_eq!(expected, kahan.value(), epsilon = 1e-10);
+        assert_abs_diff_eq!(expected, kahan2.value(), epsilon = 1e-10);
+        assert!((expected - normal).abs() > 100_000.); // normal subtraction is not accurate for f32
+    }
+
+    #[test]
+    fn test_kahan_neg() {
+        let mut sum = KahanSum::new(1e8_f32);
+        for _ in 0..10 {
+            sum += 1.1;
+        }
+        let neg = -sum;
+        assert_eq!(neg.sum_raw(), -sum.sum_raw());
+        assert_eq!(neg.compensation(), -sum.compensation());
+        assert_eq!(neg.value(), -sum.value());
+        assert_eq!((sum + neg).value(), 0.);
+        assert_eq!(-neg, sum);
+    }
+
+    #[test]
+    fn test_kahan_iter_sum() {
+        type Float = f32;
+
+        let iterations = 10_000_000_usize;
+        let x: Float = 1.1;
+        let expected = iterations as Float * x;
+
+        let kahan: KahanSum<Float> = (0..iterations).map(|_| x).sum();
+        assert_abs_diff_eq!(expected, kahan.value(), epsilon = 1e-10);
+
+        let kahan: KahanSum<Float> = (0..iterations / 2).map(|_| KahanSum::new(x) + x).sum();
+        assert_abs_diff_eq!(expected, kahan.value(), epsilon = 1e-10);
+
+        let naive: Float = (0..iterations).map(|_| x).sum();
+        assert!((expected - naive).abs() > 100_000.);
+    }
+
+    #[test]
+    fn test_kahan_compensation() {
+        let mut sum = KahanSum::new(0.0_f32);
+        assert_eq!(sum.compensation(), 0.);
+        for _ in 0..1000 {
+            sum += 0.1;
+        }
+        assert_ne!(sum.compensation(), 0.);
+        assert_eq!(sum.value(), sum.sum_raw() - sum.compensation());
+    }
+
+    #[test]
+    fn test_kahan_value_sign_regression() {
+        // the low-order part lost by the running sum is the negated compensation
+        let mut sum = KahanSum::new(1.0_f64);
+        sum += 1e-16;
+        assert_eq!(sum.sum_raw(), 1.);
+        assert_eq!(sum.compensation(), -1e-16);
+        // adding the compensation instead of subtracting it gives 0.9999999999999999
+        assert_eq!(sum.value(), 1.);
+    }
+
+    #[test]
+    fn test_kahan_merge_regression() {
+        // merging must combine the compensations, not add the compensation of the right-hand
+        // side as a value (which gives 1.4000000000000001)
+        let mut a = KahanSum::new(0.0_f64);
+        a += 1.;
+        a += 0.1;
+        let mut b = KahanSum::new(0.0_f64);
+        for _ in 0..3 {
+            b += 0.1;
+        }
+        a += b;
+        assert_eq!(a.value(), 1.4);
+    }
+
     #[test]
     fn test_doctest() {
         let repetitions = 10_000;
@@ -218,4 +527,129 @@ mod tests {
         assert_eq!(sum.value(), repetitions as f32 * 0.1);
         assert_ne!(naive, repetitions as f32 * 0.1);
     }
+    #[test]
+    fn test_accumulator_reset() -> crate::CIResult<()> {
+        use crate::*;
+        let confidence = Confidence::new_two_sided(0.95);
+        let first = [1.5, 2.5, 0.5, 4., 3.];
+        let second = [10., 12., 11.];
+
+        // the closed state has the same intervals as a state that was never reset
+        let mut stats = mean::Arithmetic::from_iter(&first)?;
+        let closed = stats.reset();
+        let reference = mean::Arithmetic::from_iter(&first)?;
+        assert_eq!(closed, reference);
+        assert_eq!(closed.ci_mean(confidence)?, reference.ci_mean(confidence)?);
+        assert_eq!(stats, mean::Arithmetic::default());
+        stats.extend(&second)?;
+        assert_eq!(stats, mean::Arithmetic::from_iter(&second)?);
+
+        let mut stats = mean::Harmonic::from_iter(&first)?;
+        let closed = stats.reset();
+        assert_eq!(
+            closed.ci_mean(confidence)?,
+            mean::Harmonic::from_iter(&first)?.ci_mean(confidence)?
+        );
+        assert_eq!(stats.sample_count(), 0);
+
+        let mut stats = comparison::Unpaired::from_iter(&first, &second)?;
+        let closed = stats.reset();
+        assert_eq!(
+            closed.ci_mean(confidence)?,
+            comparison::Unpaired::ci(confidence, &first, &second)?
+        );
+        assert_eq!(stats, comparison::Unpaired::default());
+
+        let mut stats = comparison::Paired::default();
+        stats.extend(&first[..3], &second)?;
+        let snapshot = stats.snapshot();
+        let closed = stats.reset();
+        assert_eq!(closed, snapshot);
+        assert_eq!(
+            closed.ci_mean(confidence)?,
+            comparison::Paired::ci(confidence, &first[..3].to_vec(), &second)?
+        );
+        assert_eq!(stats, comparison::Paired::default());
+
+        let mut stats = proportion::Stats::new(100, 12);
+        assert_eq!(stats.reset(), proportion::Stats::new(100, 12));
+        assert_eq!(stats, proportion::Stats::default());
+
+        let mut stats = rate::Stats::new(7, 120.);
+        assert_eq!(stats.reset(), rate::Stats::new(7, 120.));
+        assert_eq!(stats, rate::Stats::default());
+
+        let mut stats = quantile::Stats::new(42);
+        assert_eq!(stats.reset(), quantile::Stats::new(42));
+        assert_eq!(stats, quantile::Stats::default());
+
+        // the parameters of the states are kept
+        let mut stats = mean::Ewma::new(0.25)?;
+        stats.extend(first)?;
+        let closed = stats.reset();
+        assert_eq!(closed.sample_count(), first.len());
+        assert_eq!(stats, mean::Ewma::new(0.25)?);
+
+        let mut stats = mean::Columns::<f64>::new(&["x", "y"]);
+        stats.append_row(&[1., 2.])?;
+        assert_eq!(stats.reset().sample_count(), 1);
+        assert_eq!(stats, mean::Columns::new(&["x", "y"]));
+
+        let mut stats = proportion::MultinomialStats::from_counts(&[3, 0, 5]);
+        assert_eq!(stats.reset().population(), 8);
+        assert_eq!(stats, proportion::MultinomialStats::new(3));
+
+        let mut stats = quantile::SloTracker::new(1., 0.9)?;
+        stats.extend_iter(first);
+        assert_eq!(stats.reset().population(), first.len());
+        assert_eq!(stats, quantile::SloTracker::new(1., 0.9)?);
+
+        let mut stats = quantile::MergeableSketch::bounded(16)?;
+        stats.extend_iter(first);
+        let snapshot = stats.snapshot();
+        assert_eq!(stats.reset(), snapshot);
+        assert_eq!(stats, quantile::MergeableSketch::bounded(16)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_epoched() -> crate::CIResult<()> {
+        use crate::*;
+        let confidence = Confidence::new_two_sided(0.95);
+        let mut stats = Epoched::new(mean::Arithmetic::new());
+        assert_eq!(stats.epoch(), 0);
+        for epoch in 0..3 {
+            let data = (0..10)
+                .map(|i| (i * (epoch + 1)) as f64)
+                .collect::<Vec<_>>();
+            stats.stats_mut().extend(&data)?;
+            let snapshot = stats.snapshot();
+            let closed = stats.reset();
+            assert_eq!(closed, snapshot);
+            assert_eq!(closed.epoch(), epoch);
+            assert_eq!(
+                closed.stats().ci_mean(confidence)?,
+                mean::Arithmetic::ci(confidence, &data)?
+            );
+            assert_eq!(stats.epoch(), epoch + 1);
+            assert_eq!(stats.stats().sample_count(), 0);
+        }
+        assert_eq!(stats.into_inner(), mean::Arithmetic::default());
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_epoched_serde() -> crate::CIResult<()> {
+        use crate::*;
+        let mut stats = Epoched::new(proportion::Stats::default());
+        stats.stats_mut().extend(&[true, false]);
+        let _ = stats.reset();
+        stats.stats_mut().extend(&[true, true, false]);
+        let serialized = toml::to_string(&stats).unwrap();
+        assert!(serialized.contains("epoch = 1"));
+        let deserialized: Epoched<proportion::Stats> = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, stats);
+        Ok(())
+    }
 }
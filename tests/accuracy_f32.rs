@@ -0,0 +1,148 @@
+// Accuracy of the computations with `f32` samples, compared against the same computations
+// carried out on the same data in `f64`, with tolerances specific to `f32`.
+use approx::*;
+use rand::distributions::Distribution;
+use statrs::distribution::*;
+use stats_ci::*;
+
+use rand_chacha::ChaCha8Rng;
+use rand_seeder::Seeder;
+
+mod common;
+
+const SEED_STRING: &str =
+    "Seed to the number generator so that the test is deterministically reproducible!";
+
+// relative tolerance on the mean, the standard deviation, and the bounds of the interval
+// with `f32` accumulators (moderate sample sizes only); for the standard deviation, it is
+// multiplied by the condition number of the variance, 1 + (mean / std_dev)^2, since the
+// variance is obtained from the sum of squares by cancellation
+const F32_TOLERANCE: f64 = 1e-4;
+
+// relative tolerance with `f64` accumulators: only the final rounding to `f32` remains
+const MIXED_TOLERANCE: f64 = 1e-6;
+
+type Sampler = Box<dyn Fn(&mut ChaCha8Rng) -> f64>;
+
+#[test]
+fn test_f32_vs_f64() -> Result<(), Box<dyn std::error::Error>> {
+    let mut rng: ChaCha8Rng = Seeder::from(SEED_STRING).make_rng();
+    let confidence = Confidence::new_two_sided(0.95);
+
+    let distributions: Vec<(&str, Sampler)> = vec![
+        ("normal(0,1)", {
+            let d = Normal::new(0., 1.)?;
+            Box::new(move |rng| d.sample(rng))
+        }),
+        ("uniform(0,1)", {
+            let d = Uniform::new(0., 1.)?;
+            Box::new(move |rng| d.sample(rng))
+        }),
+        ("exp(1)", {
+            let d = Exp::new(1.)?;
+            Box::new(move |rng| d.sample(rng))
+        }),
+        ("normal(100,1)", {
+            let d = Normal::new(100., 1.)?;
+            Box::new(move |rng| d.sample(rng))
+        }),
+    ];
+
+    for (name, sample) in &distributions {
+        for size in [10, 100, 1_000, 10_000] {
+            let data: Vec<f32> = (0..size).map(|_| sample(&mut rng) as f32).collect();
+            let data_f64: Vec<f64> = data.iter().map(|&x| x as f64).collect();
+            let reference = mean::Arithmetic::<f64>::from_iter(&data_f64)?;
+            let ci_reference = reference.ci_mean(confidence)?;
+
+            let condition = 1. + (reference.sample_mean() / reference.sample_std_dev()).powi(2);
+            let narrow = mean::Arithmetic::<f32>::from_iter(&data)?;
+            let mixed = mean::ArithmeticF32::from_iter(&data)?;
+            for (kind, mean, std_dev, ci, tolerance, std_dev_tolerance) in [
+                (
+                    "f32",
+                    narrow.sample_mean(),
+                    narrow.sample_std_dev(),
+                    narrow.ci_mean(confidence)?,
+                    F32_TOLERANCE,
+                    F32_TOLERANCE * condition,
+                ),
+                (
+                    "mixed",
+                    mixed.sample_mean(),
+                    mixed.sample_std_dev(),
+                    mixed.ci_mean(confidence)?,
+                    MIXED_TOLERANCE,
+                    MIXED_TOLERANCE,
+                ),
+            ] {
+                let scale = reference
+                    .sample_mean()
+                    .abs()
+                    .max(reference.sample_std_dev());
+                let diff_mean = (mean as f64 - reference.sample_mean()).abs() / scale;
+                let diff_std_dev = (std_dev as f64 - reference.sample_std_dev()).abs()
+                    / reference.sample_std_dev();
+                println!(
+                    ">> {name:>14} size {size:>6} {kind:>5}  {}  {}",
+                    common::color_closer_is_better(diff_mean, 0., tolerance)
+                        .paint(format!("Δmean: {diff_mean:.3e}")),
+                    common::color_closer_is_better(diff_std_dev, 0., std_dev_tolerance)
+                        .paint(format!("Δstd_dev: {diff_std_dev:.3e}")),
+                );
+                assert!(diff_mean < tolerance, "{name} {size} {kind}: mean");
+                assert!(
+                    diff_std_dev < std_dev_tolerance,
+                    "{name} {size} {kind}: std_dev"
+                );
+                assert_relative_eq!(
+                    ci.low_f() as f64,
+                    ci_reference.low_f(),
+                    epsilon = tolerance * scale,
+                    max_relative = tolerance
+                );
+                assert_relative_eq!(
+                    ci.high_f() as f64,
+                    ci_reference.high_f(),
+                    epsilon = tolerance * scale,
+                    max_relative = tolerance
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_f32_large_sample() -> Result<(), Box<dyn std::error::Error>> {
+    // millions of samples with a mean large relative to the standard deviation:
+    // only the mixed-precision accumulation keeps the variance accurate
+    let mut rng: ChaCha8Rng = Seeder::from(SEED_STRING).make_rng();
+    let distrib = Normal::new(1_000., 1.)?;
+    let data: Vec<f32> = (0..4_000_000)
+        .map(|_| distrib.sample(&mut rng) as f32)
+        .collect();
+    let data_f64: Vec<f64> = data.iter().map(|&x| x as f64).collect();
+    let reference = mean::Arithmetic::<f64>::from_iter(&data_f64)?;
+
+    let mixed = mean::ArithmeticF32::from_iter(&data)?;
+    assert_relative_eq!(
+        mixed.sample_std_dev() as f64,
+        reference.sample_std_dev(),
+        max_relative = MIXED_TOLERANCE
+    );
+    assert_relative_eq!(
+        mixed.sample_mean() as f64,
+        reference.sample_mean(),
+        max_relative = MIXED_TOLERANCE
+    );
+
+    let narrow = mean::Arithmetic::<f32>::from_iter(&data)?;
+    let error = (narrow.sample_std_dev() as f64 - reference.sample_std_dev()).abs();
+    println!(
+        "std. dev. error with f32 accumulators: {error:.3e}, mixed precision: {:.3e}",
+        (mixed.sample_std_dev() as f64 - reference.sample_std_dev()).abs()
+    );
+    assert!(error > 100. * MIXED_TOLERANCE);
+    Ok(())
+}
@@ -0,0 +1,243 @@
+use statrs::distribution::*;
+use stats_ci::*;
+
+use rand_chacha::ChaCha8Rng;
+use rand_seeder::Seeder;
+
+mod common;
+
+const SEED_STRING: &str =
+    "Seed to the number generator so that the test is deterministically reproducible!";
+
+///
+/// Cases where the interval is known to undercover, as (distribution, sample size, quantile,
+/// confidence, observed hit rate). The hit rate of these cases is below the nominal level by more
+/// than twice the tolerance; the test fails if a case is missing from the list (regression) or if
+/// a listed case now achieves the nominal coverage (the list should then be updated).
+///
+/// Undercoverage concentrates on small samples and on the side of the interval towards the tail
+/// of the quantile, where the approximate (Wilson) interval on the ranks is floored to indices.
+///
+const KNOWN_GAPS: &[(&str, usize, f64, Confidence, f64)] = &[
+    ("normal", 15, 0.1, Confidence::TwoSided(0.9), 0.808),
+    ("exponential", 15, 0.1, Confidence::TwoSided(0.9), 0.784),
+    ("uniform", 15, 0.1, Confidence::TwoSided(0.9), 0.818),
+    ("normal", 15, 0.1, Confidence::TwoSided(0.95), 0.808),
+    ("exponential", 15, 0.1, Confidence::TwoSided(0.95), 0.784),
+    ("uniform", 15, 0.1, Confidence::TwoSided(0.95), 0.818),
+    ("normal", 15, 0.1, Confidence::UpperOneSided(0.95), 0.812),
+    (
+        "exponential",
+        15,
+        0.1,
+        Confidence::UpperOneSided(0.95),
+        0.784,
+    ),
+    ("uniform", 15, 0.1, Confidence::UpperOneSided(0.95), 0.82),
+    ("exponential", 50, 0.1, Confidence::TwoSided(0.9), 0.844),
+    ("normal", 50, 0.1, Confidence::TwoSided(0.95), 0.9),
+    ("exponential", 50, 0.1, Confidence::TwoSided(0.95), 0.868),
+    ("uniform", 50, 0.1, Confidence::TwoSided(0.95), 0.894),
+    (
+        "exponential",
+        50,
+        0.1,
+        Confidence::UpperOneSided(0.95),
+        0.88,
+    ),
+    ("uniform", 50, 0.1, Confidence::UpperOneSided(0.95), 0.9),
+    ("normal", 50, 0.9, Confidence::TwoSided(0.9), 0.846),
+    ("uniform", 50, 0.9, Confidence::TwoSided(0.9), 0.85),
+    ("normal", 50, 0.9, Confidence::TwoSided(0.95), 0.86),
+    ("exponential", 50, 0.9, Confidence::TwoSided(0.95), 0.884),
+    ("uniform", 50, 0.9, Confidence::TwoSided(0.95), 0.868),
+    ("normal", 50, 0.9, Confidence::LowerOneSided(0.95), 0.868),
+    (
+        "exponential",
+        50,
+        0.9,
+        Confidence::LowerOneSided(0.95),
+        0.892,
+    ),
+    ("uniform", 50, 0.9, Confidence::LowerOneSided(0.95), 0.88),
+    ("normal", 50, 0.95, Confidence::TwoSided(0.95), 0.892),
+    ("exponential", 50, 0.95, Confidence::TwoSided(0.95), 0.902),
+    ("normal", 50, 0.95, Confidence::LowerOneSided(0.95), 0.9),
+    (
+        "exponential",
+        200,
+        0.1,
+        Confidence::UpperOneSided(0.95),
+        0.886,
+    ),
+    ("uniform", 200, 0.9, Confidence::LowerOneSided(0.95), 0.908),
+    ("normal", 200, 0.95, Confidence::TwoSided(0.9), 0.842),
+    ("exponential", 200, 0.95, Confidence::TwoSided(0.9), 0.838),
+    ("uniform", 200, 0.95, Confidence::TwoSided(0.9), 0.838),
+    ("exponential", 200, 0.95, Confidence::TwoSided(0.95), 0.906),
+    ("normal", 200, 0.95, Confidence::LowerOneSided(0.95), 0.86),
+    (
+        "exponential",
+        200,
+        0.95,
+        Confidence::LowerOneSided(0.95),
+        0.854,
+    ),
+    ("uniform", 200, 0.95, Confidence::LowerOneSided(0.95), 0.86),
+];
+
+///
+/// Cases where no interval can be computed: for small samples and extreme quantiles, the interval
+/// on the ranks falls outside of the data.
+///
+const UNSUPPORTED: &[(usize, f64)] = &[(15, 0.9), (15, 0.95)];
+
+#[test]
+fn test_accuracy_quantile() {
+    let tolerance = 0.02;
+    let repetitions = 500;
+    let sample_sizes = [15, 50, 200, 1000];
+    let quantiles = [0.1, 0.5, 0.9, 0.95];
+    let confidences = [
+        // two-sided
+        Confidence::new_two_sided(0.9),
+        Confidence::new_two_sided(0.95),
+        // upper one-sided
+        Confidence::new_upper(0.95),
+        // lower one-sided
+        Confidence::new_lower(0.95),
+    ];
+
+    let normal = Normal::new(0., 1.).unwrap();
+    let exponential = Exp::new(1.).unwrap();
+    let uniform = Uniform::new(0., 1.).unwrap();
+
+    let mut gaps = vec![];
+    for sample_size in sample_sizes {
+        for quantile in quantiles {
+            for confidence in confidences {
+                let cases = [
+                    (
+                        "normal",
+                        hit_rate(
+                            &normal,
+                            normal.inverse_cdf(quantile),
+                            sample_size,
+                            repetitions,
+                            confidence,
+                            quantile,
+                        ),
+                    ),
+                    (
+                        "exponential",
+                        hit_rate(
+                            &exponential,
+                            exponential.inverse_cdf(quantile),
+                            sample_size,
+                            repetitions,
+                            confidence,
+                            quantile,
+                        ),
+                    ),
+                    (
+                        "uniform",
+                        hit_rate(
+                            &uniform,
+                            uniform.inverse_cdf(quantile),
+                            sample_size,
+                            repetitions,
+                            confidence,
+                            quantile,
+                        ),
+                    ),
+                ];
+                for (name, hit_rate) in cases {
+                    let Some(hit_rate) = hit_rate else {
+                        println!(
+                            "unsupported  [{}, size: {}, quantile: {}, {:?}]",
+                            name, sample_size, quantile, confidence
+                        );
+                        assert!(
+                            UNSUPPORTED.contains(&(sample_size, quantile)),
+                            "unexpected error for size {} and quantile {}",
+                            sample_size,
+                            quantile
+                        );
+                        continue;
+                    };
+                    let color = common::highlight_color(hit_rate, confidence.level(), tolerance);
+                    println!(
+                        "{}  [{}, size: {}, quantile: {}, {:?}]",
+                        color.paint(format!(
+                            "hit rate: {:.1}% (Δ: {:.1}%)",
+                            hit_rate * 100.,
+                            (confidence.level() - hit_rate).abs() * 100.,
+                        )),
+                        name,
+                        sample_size,
+                        quantile,
+                        confidence
+                    );
+                    if hit_rate < confidence.level() - 2. * tolerance {
+                        gaps.push((name, sample_size, quantile, confidence, hit_rate));
+                    }
+                }
+            }
+        }
+        println!();
+    }
+
+    for &(name, sample_size, quantile, confidence, hit_rate) in &gaps {
+        assert!(
+            KNOWN_GAPS
+                .iter()
+                .any(|&(n, s, q, c, _)| (n, s, q, c) == (name, sample_size, quantile, confidence)),
+            "undercoverage: {:.1}% [{}, size: {}, quantile: {}, {:?}]",
+            hit_rate * 100.,
+            name,
+            sample_size,
+            quantile,
+            confidence
+        );
+    }
+    for &(name, sample_size, quantile, confidence, _) in KNOWN_GAPS {
+        assert!(
+            gaps.iter()
+                .any(|&(n, s, q, c, _)| (n, s, q, c) == (name, sample_size, quantile, confidence)),
+            "known gap now covered: [{}, size: {}, quantile: {}, {:?}]",
+            name,
+            sample_size,
+            quantile,
+            confidence
+        );
+    }
+}
+
+///
+/// Proportion of the intervals that contain the true quantile, or `None` if the intervals
+/// cannot be computed.
+///
+fn hit_rate<D>(
+    distrib: &D,
+    target: f64,
+    sample_size: usize,
+    repetitions: usize,
+    confidence: Confidence,
+    quantile: f64,
+) -> Option<f64>
+where
+    D: rand::distributions::Distribution<f64>,
+{
+    let mut rng: ChaCha8Rng = Seeder::from(SEED_STRING).make_rng();
+    let mut hits = 0;
+    for _ in 0..repetitions {
+        let sample = (0..sample_size)
+            .map(|_| distrib.sample(&mut rng))
+            .collect::<Vec<_>>();
+        let ci = quantile::ci(confidence, &sample, quantile).ok()?;
+        if ci.contains(&target) {
+            hits += 1;
+        }
+    }
+    Some(hits as f64 / repetitions as f64)
+}